@@ -0,0 +1,86 @@
+package org.openmsupply.client;
+
+import android.content.Context;
+import android.content.SharedPreferences;
+import android.security.keystore.KeyGenParameterSpec;
+import android.security.keystore.KeyProperties;
+import android.util.Base64;
+
+import com.getcapacitor.Logger;
+
+import java.security.KeyStore;
+import java.security.SecureRandom;
+
+import javax.crypto.Cipher;
+import javax.crypto.KeyGenerator;
+import javax.crypto.SecretKey;
+import javax.crypto.spec.GCMParameterSpec;
+
+// Key of the SQLCipher database. A random key is generated on first use and stored in shared
+// preferences, wrapped by a key that never leaves the Android Keystore
+public class DatabaseKeyStore {
+    private static final String KEYSTORE = "AndroidKeyStore";
+    private static final String WRAPPING_KEY_ALIAS = "omSupplyDatabaseKey";
+    private static final String PREFERENCES = "omSupplyDatabase";
+    private static final String WRAPPED_KEY = "wrappedKey";
+    private static final String WRAPPED_KEY_IV = "wrappedKeyIv";
+    private static final String TRANSFORMATION = "AES/GCM/NoPadding";
+
+    private final SharedPreferences preferences;
+
+    public DatabaseKeyStore(Context context) {
+        preferences = context.getSharedPreferences(PREFERENCES, Context.MODE_PRIVATE);
+    }
+
+    // Hex encoded 256 bit key, empty if the keystore is not available (database stays unencrypted)
+    public String getKey() {
+        try {
+            String wrappedKey = preferences.getString(WRAPPED_KEY, null);
+            String iv = preferences.getString(WRAPPED_KEY_IV, null);
+            if (wrappedKey != null && iv != null) {
+                Cipher cipher = Cipher.getInstance(TRANSFORMATION);
+                cipher.init(Cipher.DECRYPT_MODE, getWrappingKey(),
+                        new GCMParameterSpec(128, Base64.decode(iv, Base64.NO_WRAP)));
+                return toHex(cipher.doFinal(Base64.decode(wrappedKey, Base64.NO_WRAP)));
+            }
+
+            byte[] key = new byte[32];
+            new SecureRandom().nextBytes(key);
+            Cipher cipher = Cipher.getInstance(TRANSFORMATION);
+            cipher.init(Cipher.ENCRYPT_MODE, getWrappingKey());
+            preferences.edit()
+                    .putString(WRAPPED_KEY, Base64.encodeToString(cipher.doFinal(key), Base64.NO_WRAP))
+                    .putString(WRAPPED_KEY_IV, Base64.encodeToString(cipher.getIV(), Base64.NO_WRAP))
+                    .commit();
+            return toHex(key);
+        } catch (Exception e) {
+            Logger.error("Problem getting database key from the Android Keystore", e);
+            return "";
+        }
+    }
+
+    private SecretKey getWrappingKey() throws Exception {
+        KeyStore keyStore = KeyStore.getInstance(KEYSTORE);
+        keyStore.load(null);
+        if (keyStore.containsAlias(WRAPPING_KEY_ALIAS)) {
+            return ((KeyStore.SecretKeyEntry) keyStore.getEntry(WRAPPING_KEY_ALIAS, null)).getSecretKey();
+        }
+
+        KeyGenerator generator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES, KEYSTORE);
+        generator.init(new KeyGenParameterSpec.Builder(WRAPPING_KEY_ALIAS,
+                KeyProperties.PURPOSE_ENCRYPT | KeyProperties.PURPOSE_DECRYPT)
+                .setBlockModes(KeyProperties.BLOCK_MODE_GCM)
+                .setEncryptionPaddings(KeyProperties.ENCRYPTION_PADDING_NONE)
+                .setKeySize(256)
+                .build());
+        return generator.generateKey();
+    }
+
+    private static String toHex(byte[] bytes) {
+        StringBuilder hex = new StringBuilder();
+        for (byte b : bytes) {
+            hex.append(String.format("%02x", b));
+        }
+        return hex.toString();
+    }
+}
@@ -49,7 +49,8 @@ public class MainActivity extends BridgeActivity {
 
         String path = getFilesDir().getAbsolutePath();
         String cache = getCacheDir().getAbsolutePath();
-        server.start(discoveryConstants.PORT, path, cache, discoveryConstants.hardwareId);
+        String databaseKey = new DatabaseKeyStore(this).getKey();
+        server.start(discoveryConstants.PORT, path, cache, discoveryConstants.hardwareId, databaseKey);
     }
 
     @Override
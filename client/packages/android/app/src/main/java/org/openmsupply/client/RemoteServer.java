@@ -13,9 +13,9 @@ public class RemoteServer {
 
     }
 
-    public void start(int port, String filesDir, String cacheDir, String androidId) {
+    public void start(int port, String filesDir, String cacheDir, String androidId, String databaseKey) {
         Logger.info("Starting OMS Rust Server");
-        startServer(port, filesDir, cacheDir, androidId);
+        startServer(port, filesDir, cacheDir, androidId, databaseKey);
     }
 
     public void stop() {
@@ -23,7 +23,8 @@ public class RemoteServer {
     }
 
     // Mapping to methods in server/android/src/android.lib
-    private static native void startServer(int port, String filesDir, String cacheDir, String androidId);
+    private static native void startServer(int port, String filesDir, String cacheDir, String androidId,
+            String databaseKey);
 
     private static native void stopServer();
 }
@@ -8,9 +8,11 @@ export AR=${NDK_BIN}/llvm-ar
 export CC_armv7_linux_androideabi=${NDK_BIN}/armv7a-linux-androideabi22-clang
 
 # Build arm64-v8a:aarch64-linux-android (Defined in cargo.toml)
+# Set SERVER_FEATURES=sqlcipher to encrypt the database
 PATH=PATH=$PATH:$NDK_BIN \
     cargo build \
         --release \
+        ${SERVER_FEATURES:+--features="$SERVER_FEATURES"} \
         --manifest-path="../../../server/android/Cargo.toml" \
         --config="../../../server/android/.cargo/config.toml" \
         --target-dir="$(pwd)/server-lib"
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from './serde_json/JsonValue';
+
+export type GraphqlMutationInput = {
+  store_id: string;
+  user_id: string;
+  /**
+   *Name of the mutation as declared in plugin manifest
+   */
+  name: string;
+  /**
+   *Validated against input_schema of the mutation declared in plugin manifest
+   */
+  input: JsonValue;
+};
@@ -0,0 +1,46 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InvoiceLineStatus } from './InvoiceLineStatus';
+import type { InvoiceLineType } from './InvoiceLineType';
+
+export type InvoiceLineRow = {
+  id: string;
+  invoice_id: string;
+  item_link_id: string;
+  item_name: string;
+  item_code: string;
+  stock_line_id: string | null;
+  location_id: string | null;
+  batch: string | null;
+  expiry_date: string | null;
+  pack_size: number;
+  cost_price_per_pack: number;
+  /**
+   * Sell price before tax
+   */
+  sell_price_per_pack: number;
+  total_before_tax: number;
+  total_after_tax: number;
+  /**
+   * Optional column to store line a line specific tax value
+   */
+  tax_percentage: number | null;
+  type: InvoiceLineType;
+  number_of_packs: number;
+  prescribed_quantity: number | null;
+  note: string | null;
+  foreign_currency_price_before_tax: number | null;
+  item_variant_id: string | null;
+  linked_invoice_id: string | null;
+  vvm_status_id: string | null;
+  reason_option_id: string | null;
+  campaign_id: string | null;
+  program_id: string | null;
+  shipped_number_of_packs: number | null;
+  volume_per_pack: number;
+  shipped_pack_size: number | null;
+  status: InvoiceLineStatus | null;
+  manufacture_date: string | null;
+  purchase_order_line_id: string | null;
+  donor_id: string | null;
+  manufacturer_id: string | null;
+};
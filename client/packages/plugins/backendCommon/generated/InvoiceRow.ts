@@ -0,0 +1,47 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InvoiceStatus } from './InvoiceStatus';
+import type { InvoiceType } from './InvoiceType';
+
+export type InvoiceRow = {
+  id: string;
+  name_store_id: string | null;
+  store_id: string;
+  user_id: string | null;
+  invoice_number: bigint;
+  type: InvoiceType;
+  status: InvoiceStatus;
+  on_hold: boolean;
+  comment: string | null;
+  their_reference: string | null;
+  transport_reference: string | null;
+  created_datetime: string;
+  allocated_datetime: string | null;
+  picked_datetime: string | null;
+  shipped_datetime: string | null;
+  delivered_datetime: string | null;
+  received_datetime: string | null;
+  verified_datetime: string | null;
+  cancelled_datetime: string | null;
+  colour: string | null;
+  requisition_id: string | null;
+  linked_invoice_id: string | null;
+  tax_percentage: number | null;
+  currency_id: string | null;
+  currency_rate: number;
+  clinician_link_id: string | null;
+  original_shipment_id: string | null;
+  backdated_datetime: string | null;
+  diagnosis_id: string | null;
+  program_id: string | null;
+  name_insurance_join_id: string | null;
+  insurance_discount_amount: number | null;
+  insurance_discount_percentage: number | null;
+  is_cancellation: boolean;
+  expected_delivery_date: string | null;
+  purchase_order_id: string | null;
+  shipping_method_id: string | null;
+  charges_local_currency: number;
+  charges_foreign_currency: number;
+  name_id: string;
+  default_donor_id: string | null;
+};
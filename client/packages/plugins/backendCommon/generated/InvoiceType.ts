@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type InvoiceType =
+  | 'OUTBOUND_SHIPMENT'
+  | 'INBOUND_SHIPMENT'
+  | 'PRESCRIPTION'
+  | 'INVENTORY_ADDITION'
+  | 'INVENTORY_REDUCTION'
+  | 'REPACK'
+  | 'SUPPLIER_RETURN'
+  | 'CUSTOMER_RETURN';
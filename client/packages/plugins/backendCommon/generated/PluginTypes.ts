@@ -4,6 +4,7 @@ import type { AverageMonthlyConsumptionInput } from './AverageMonthlyConsumption
 import type { AverageMonthlyConsumptionItem } from './AverageMonthlyConsumptionItem';
 import type { Function } from './Function';
 import type { GetConsumptionInput } from './GetConsumptionInput';
+import type { GraphqlMutationInput } from './GraphqlMutationInput';
 import type { GraphqlQueryInput } from './GraphqlQueryInput';
 import type { JsonValue } from './serde_json/JsonValue';
 import type { PluginDataFilter } from './PluginDataFilter';
@@ -18,6 +19,8 @@ import type { TransformRequestRequisitionLineOutput } from './TransformRequestRe
 import type { UseGraphqlInput } from './UseGraphqlInput';
 import type { UseRepositoryInput } from './UseRepositoryInput';
 import type { UseRepositoryOutput } from './UseRepositoryOutput';
+import type { ValidateMutationInput } from './ValidateMutationInput';
+import type { ValidateMutationOutput } from './ValidateMutationOutput';
 
 export type PluginTypes = {
   average_monthly_consumption: Function<
@@ -30,8 +33,10 @@ export type PluginTypes = {
   >;
   get_consumption: Function<GetConsumptionInput, { [key in string]?: number }>;
   graphql_query: Function<GraphqlQueryInput, JsonValue>;
+  graphql_mutation: Function<GraphqlMutationInput, JsonValue>;
   processor: Function<ProcessorInput, ProcessorOutput>;
   schedule: Function<ScheduleInput, ScheduleOutput>;
+  validate_mutation: Function<ValidateMutationInput, ValidateMutationOutput>;
   get_store_preferences: StorePreferenceRow;
   get_plugin_data: Function<PluginDataFilter, Array<PluginDataRow>>;
   use_repository: Function<UseRepositoryInput, UseRepositoryOutput>;
@@ -27,5 +27,6 @@ export type RequisitionRow = {
   order_type: string | null;
   is_emergency: boolean;
   created_from_requisition_id: string | null;
+  emergency_reason_option_id: string | null;
   destination_customer_id: string | null;
 };
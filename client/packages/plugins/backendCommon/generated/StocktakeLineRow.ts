@@ -0,0 +1,34 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type StocktakeLineRow = {
+  id: string;
+  stocktake_id: string;
+  /**
+   * If missing, a new stock line needs to be created when finalizing the stocktake
+   */
+  stock_line_id: string | null;
+  location_id: string | null;
+  /**
+   * Comment for this stocktake line
+   */
+  comment: string | null;
+  snapshot_number_of_packs: number;
+  counted_number_of_packs: number | null;
+  item_link_id: string;
+  item_name: string;
+  batch: string | null;
+  expiry_date: string | null;
+  manufacture_date: string | null;
+  pack_size: number | null;
+  cost_price_per_pack: number | null;
+  sell_price_per_pack: number | null;
+  note: string | null;
+  item_variant_id: string | null;
+  reason_option_id: string | null;
+  vvm_status_id: string | null;
+  volume_per_pack: number;
+  campaign_id: string | null;
+  program_id: string | null;
+  donor_id: string | null;
+  manufacturer_id: string | null;
+};
@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StocktakeStatus } from './StocktakeStatus';
+
+export type StocktakeRow = {
+  id: string;
+  store_id: string;
+  user_id: string;
+  stocktake_number: bigint;
+  comment: string | null;
+  description: string | null;
+  status: StocktakeStatus;
+  created_datetime: string;
+  stocktake_date: string | null;
+  finalised_datetime: string | null;
+  /**
+   * reference to the inventory adjustment shipment
+   */
+  inventory_addition_id: string | null;
+  inventory_reduction_id: string | null;
+  is_locked: boolean;
+  program_id: string | null;
+  counted_by: string | null;
+  verified_by: string | null;
+  is_initial_stocktake: boolean;
+};
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidateMutationProposedRows } from './ValidateMutationProposedRows';
+
+export type ValidateMutationInput = {
+  store_id: string;
+  user_id: string;
+  /**
+   *Records as they will be committed, the mutation is rolled back if any errors are returned
+   */
+  proposed: ValidateMutationProposedRows;
+};
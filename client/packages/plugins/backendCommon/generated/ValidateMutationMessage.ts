@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ValidateMutationMessage = {
+  code: string;
+  message: string;
+  /**
+   * Id of the line (or record) the message relates to
+   */
+  record_id?: string;
+};
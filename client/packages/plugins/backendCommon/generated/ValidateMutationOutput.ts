@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidateMutationMessage } from './ValidateMutationMessage';
+
+export type ValidateMutationOutput = {
+  /**
+   * Non blocking, mutation will proceed
+   */
+  warnings?: Array<ValidateMutationMessage>;
+  /**
+   * Blocking, mutation will be rejected
+   */
+  errors?: Array<ValidateMutationMessage>;
+};
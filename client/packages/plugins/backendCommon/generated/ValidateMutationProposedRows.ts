@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { InvoiceLineRow } from './InvoiceLineRow';
+import type { InvoiceRow } from './InvoiceRow';
+import type { RequisitionLineRow } from './RequisitionLineRow';
+import type { RequisitionRow } from './RequisitionRow';
+import type { StocktakeLineRow } from './StocktakeLineRow';
+import type { StocktakeRow } from './StocktakeRow';
+
+export type ValidateMutationProposedRows =
+  | { t: 'Invoice'; v: { invoice: InvoiceRow; lines: Array<InvoiceLineRow> } }
+  | {
+      t: 'Requisition';
+      v: { requisition: RequisitionRow; lines: Array<RequisitionLineRow> };
+    }
+  | {
+      t: 'Stocktake';
+      v: { stocktake: StocktakeRow; lines: Array<StocktakeLineRow> };
+    };
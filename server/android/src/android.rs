@@ -7,6 +7,9 @@ pub mod android {
     use std::thread::{self, JoinHandle};
 
     use jni::sys::jchar;
+    use repository::database_encryption::{
+        is_plaintext_database, reencrypt_database, DatabaseEncryptionSettings,
+    };
     use repository::database_settings::DatabaseSettings;
     use server::{logging_init, start_server};
     use service::settings::{DiscoveryMode, LogMode, LoggingSettings, ServerSettings, Settings};
@@ -31,16 +34,24 @@ pub mod android {
         files_dir: JString,
         cache_dir: JString,
         android_id: JString,
+        database_key: JString,
     ) {
         let (off_switch, off_switch_receiver) = mpsc::channel(1);
-        let (files_dir, android_id, cache_dir) = unowned_env
+        let (files_dir, android_id, cache_dir, database_key) = unowned_env
             .with_env(|env| -> Result<_, jni::errors::Error> {
                 let files_dir = files_dir.try_to_string(env)?;
                 let android_id = android_id.try_to_string(env)?;
                 let cache_dir = cache_dir.try_to_string(env)?;
-                Ok((files_dir, android_id, cache_dir))
+                let database_key = database_key.try_to_string(env)?;
+                Ok((files_dir, android_id, cache_dir, database_key))
             })
             .resolve::<LogErrorAndDefault>();
+        // Database stays plaintext in builds without SQLCipher
+        let database_key = if cfg!(feature = "sqlcipher") {
+            database_key
+        } else {
+            String::new()
+        };
         let files_dir = PathBuf::from(&files_dir);
         let db_path = files_dir.join("omsupply-database");
 
@@ -67,6 +78,10 @@ pub mod android {
                 connection_pool_timeout_seconds: None,
                 // See https://github.com/openmsupply/remote-server/issues/1076
                 init_sql: Some(format!("PRAGMA temp_store_directory = '{cache_dir}';")),
+                // Key is held by the Android Keystore (see DatabaseKeyStore.java), empty when
+                // the keystore is not available
+                encryption: (!database_key.is_empty())
+                    .then(|| DatabaseEncryptionSettings::with_key(database_key.clone())),
             },
             // sync settings need to be configured at runtime
             sync: None,
@@ -79,12 +94,21 @@ pub mod android {
             mail: None,
             // Feature flags won't work using tablet as a server. Run in client mode and connect to a desktop server instead
             features: None,
+            graphql: None,
         };
 
         logging_init(settings.logging.clone(), None);
         log_panics::init();
         log::info!("omSupply server starting...");
 
+        // Databases created before encryption was introduced are encrypted in place
+        let database_path = PathBuf::from(settings.database.database_path());
+        if !database_key.is_empty() && is_plaintext_database(&database_path).unwrap_or(false) {
+            log::info!("Encrypting database");
+            reencrypt_database(&database_path, None, Some(&database_key))
+                .expect("Failed to encrypt database");
+        }
+
         // run server in background thread
         let thread = thread::spawn(move || {
             // This code is from expanding macro in main.rs
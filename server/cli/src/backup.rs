@@ -0,0 +1,91 @@
+use chrono::NaiveDateTime;
+use repository::get_storage_connection_manager;
+use service::{
+    backup::{
+        backup as backup_service, record_backup_result, restore as restore_service, BackupError,
+        RestoreOptions, RestoreResult,
+    },
+    settings::{is_develop, Settings},
+};
+use std::io;
+
+#[derive(clap::Parser, Debug)]
+pub(super) struct RestoreArguments {
+    /// Name of backup in directory specified by backup configurations
+    #[clap(short, long)]
+    backup_name: String,
+    /// In dev can specify this to skip confirmation
+    #[clap(short, long)]
+    skip_confirmation: bool,
+    /// Postgres only, restore the base backup and replay archived WAL up to this time (UTC),
+    /// e.g. "2024-08-22 05:30:00". Requires wal_archive_dir and pg_data_dir in backup configurations
+    #[clap(short, long, value_parser = parse_point_in_time)]
+    point_in_time: Option<NaiveDateTime>,
+}
+
+fn parse_point_in_time(value: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+}
+
+pub(super) fn backup(settings: &Settings) -> Result<(), BackupError> {
+    let result = backup_service(settings);
+
+    // Status is reported to central server with the next sync
+    let connection_manager =
+        get_storage_connection_manager(&settings.database).map_err(anyhow::Error::from)?;
+    record_backup_result(&connection_manager.connection()?, &result)?;
+
+    println!("Backup completed in folder {}", result?);
+    Ok(())
+}
+
+pub(super) fn restore(
+    settings: &Settings,
+    RestoreArguments {
+        backup_name,
+        skip_confirmation,
+        point_in_time,
+    }: RestoreArguments,
+) -> Result<(), BackupError> {
+    confirmation(skip_confirmation)?;
+
+    let result = restore_service(
+        settings,
+        RestoreOptions {
+            backup_name,
+            point_in_time,
+        },
+    )?;
+
+    match result {
+        RestoreResult::Restored => println!("Restore completed"),
+        RestoreResult::PointInTimePrepared { previous_data_dir } => println!(
+            "Postgres data directory was replaced (previous data directory moved to {}), start postgres to replay archived WAL up to {}, then start omSupply",
+            previous_data_dir.display(),
+            // Unwrap is safe, point in time restore was requested
+            point_in_time.unwrap()
+        ),
+    }
+
+    Ok(())
+}
+
+fn confirmation(skip_confirmation: bool) -> Result<(), BackupError> {
+    if is_develop() && skip_confirmation {
+        return Ok(());
+    }
+
+    // In production confirm restore
+    let confirmation = "I understand";
+    println!(
+        r#"This operation will completely wipe your omSupply database and other omSupply, are you sure (please type "{confirmation}" to continue): "#
+    );
+    let mut buffer = String::new();
+    io::stdin().read_line(&mut buffer)?;
+
+    if buffer.to_lowercase().trim() != confirmation.to_lowercase() {
+        return Err(BackupError::RestoreNotConfirmed);
+    }
+
+    Ok(())
+}
@@ -17,6 +17,7 @@ use repository::{
 use serde::{Deserialize, Serialize};
 use server::{configuration, logging_init};
 use service::{
+    activity_log_chain::{verify_activity_log_chain, verify_activity_log_chains},
     apis::login_v4::LoginUserInfoV4,
     auth_data::AuthData,
     login::{LoginInput, LoginService},
@@ -43,14 +44,17 @@ use tokio::task::spawn_blocking;
 
 mod backup;
 use backup::*;
+mod encrypt_database;
+use encrypt_database::*;
 
 #[cfg(feature = "integration_test")]
 use cli::LoadTest;
 use cli::{
     all_tests, generate_and_install_plugin_bundle, generate_plugin_bundle,
     generate_plugin_typescript_types, generate_report_data, generate_reports_recursive,
-    install_plugin_bundle, GenerateAndInstallPluginBundle, GeneratePluginBundle,
+    install_plugin_bundle, test_plugin, GenerateAndInstallPluginBundle, GeneratePluginBundle,
     InstallPluginBundle, RefreshDatesRepository, ReportError, TestCredentials, TestData,
+    TestPlugin,
 };
 
 const DATA_EXPORT_FOLDER: &str = "data";
@@ -168,6 +172,10 @@ enum Action {
     /// User can specify max number of backup to keep, see example configuration file
     Backup,
     Restore(RestoreArguments),
+    /// Encrypt the SQLite database with the key from `database.encryption` configurations, or
+    /// change the key when it's already encrypted (requires the sqlcipher feature, stop the server
+    /// first)
+    EncryptDatabase(EncryptDatabaseArguments),
     BuildReports {
         /// Optional reports path. If supplied, this dir should be the same structure as per standard reports.
         /// Will generate a json of all reports within this directory
@@ -180,6 +188,9 @@ enum Action {
     InstallPluginBundle(InstallPluginBundle),
     /// Will generate and then install  plugin bundle
     GenerateAndInstallPluginBundle(GenerateAndInstallPluginBundle),
+    /// Will run backend plugins in bundle against test case input files, using database with mock data,
+    /// and compare plugin output to expected output files
+    TestPlugin(TestPlugin),
     UpsertReports {
         /// Optional reports json path. This needs to be of type ReportsData. If none supplied, will upload the standard generated reports
         #[clap(short, long, num_args=0..)]
@@ -221,6 +232,14 @@ enum Action {
         #[clap(short, long, action = ArgAction::SetTrue, conflicts_with="enable")]
         disable: bool,
     },
+    /// Verify the activity log hash chains against the anchors stored on this site, reporting where
+    /// a chain breaks. Exits with an error if any chain is broken. The activityLogChainVerification
+    /// query compares with the anchors held by the central server
+    VerifyActivityLog {
+        /// Only verify this store's chain, by default all chains are verified
+        #[clap(short, long)]
+        store_id: Option<String>,
+    },
     /// Test connectivity to configured services (config, database, ping, sync, mail)
     TestConnection {
         /// Username for the login test
@@ -264,7 +283,7 @@ async fn initialise_from_central(
     test_db::setup(&settings.database).await;
     info!("Finished database reset");
 
-    let connection_manager = get_storage_connection_manager(&settings.database);
+    let connection_manager = get_storage_connection_manager(&settings.database)?;
     let service_provider = Arc::new(ServiceProvider::new(connection_manager.clone()));
 
     let sync_settings = settings
@@ -348,7 +367,7 @@ async fn main() -> anyhow::Result<()> {
         }
         Action::Migrate => {
             info!("Applying database migrations");
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             if let Some(init_sql) = &settings.database.startup_sql() {
                 connection_manager.execute(init_sql).unwrap();
             }
@@ -417,7 +436,7 @@ async fn main() -> anyhow::Result<()> {
         Action::InitialiseFromExport { name, refresh } => {
             test_db::setup(&settings.database).await;
 
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let service_provider = Arc::new(ServiceProvider::new(connection_manager.clone()));
             let ctx = service_provider.basic_context()?;
 
@@ -486,7 +505,7 @@ async fn main() -> anyhow::Result<()> {
             );
         }
         Action::RefreshDates { enable_sync } => {
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let connection = connection_manager.connection()?;
 
             info!("Refreshing dates");
@@ -571,7 +590,7 @@ async fn main() -> anyhow::Result<()> {
                 None => vec![standard_reports_dir, standard_forms_dir],
             };
 
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let con = connection_manager.connection()?;
 
             for file_path in file_list {
@@ -596,7 +615,7 @@ async fn main() -> anyhow::Result<()> {
             sub_context,
             excel_template_path,
         } => {
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let con = connection_manager.connection()?;
 
             let filter = ReportFilter::new().id(EqualFilter::equal_to(id.to_string()));
@@ -648,7 +667,7 @@ async fn main() -> anyhow::Result<()> {
             info!("Report upserted");
         }
         Action::ReloadEmbeddedReports => {
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let con = connection_manager.connection()?;
 
             StandardReports::load_reports(&con, true)?;
@@ -659,6 +678,9 @@ async fn main() -> anyhow::Result<()> {
         Action::Restore(arguments) => {
             restore(&settings, arguments)?;
         }
+        Action::EncryptDatabase(arguments) => {
+            encrypt_database(&settings, arguments)?;
+        }
         Action::GeneratePluginBundle(arguments) => {
             generate_plugin_bundle(arguments)?;
         }
@@ -668,6 +690,9 @@ async fn main() -> anyhow::Result<()> {
         Action::GenerateAndInstallPluginBundle(arguments) => {
             generate_and_install_plugin_bundle(arguments).await?;
         }
+        Action::TestPlugin(arguments) => {
+            test_plugin(arguments).await?;
+        }
         Action::ShowReport {
             path,
             config,
@@ -747,7 +772,7 @@ async fn main() -> anyhow::Result<()> {
             enable,
             disable,
         } => {
-            let connection_manager = get_storage_connection_manager(&settings.database);
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
             let con = connection_manager.connection()?;
 
             let mut filter = ReportFilter::new().code(EqualFilter::equal_to(code.to_owned()));
@@ -782,6 +807,58 @@ async fn main() -> anyhow::Result<()> {
                 );
             }
         }
+        Action::VerifyActivityLog { store_id } => {
+            let connection_manager = get_storage_connection_manager(&settings.database)?;
+            let con = connection_manager.connection()?;
+
+            let results = match store_id {
+                Some(store_id) => vec![verify_activity_log_chain(&con, &store_id)?],
+                None => verify_activity_log_chains(&con)?,
+            };
+
+            let mut is_intact = true;
+            for result in results {
+                let head = result
+                    .head
+                    .as_ref()
+                    .map(|head| format!("head {} {}", head.sequence, head.hash))
+                    .unwrap_or_else(|| "no chain".to_string());
+                if result.is_intact() {
+                    println!(
+                        "{} {}: {} links, {} anchors, {head}",
+                        "[PASS]".green(),
+                        result.store_id,
+                        result.links_checked,
+                        result.anchors_checked
+                    );
+                    continue;
+                }
+
+                is_intact = false;
+                println!(
+                    "{} {}: {} links, {} anchors, {head}",
+                    "[FAIL]".red(),
+                    result.store_id,
+                    result.links_checked,
+                    result.anchors_checked
+                );
+                for chain_break in result.breaks {
+                    println!(
+                        "  {:?} at sequence {}, activity log {}, anchor {}",
+                        chain_break.r#type,
+                        chain_break
+                            .sequence
+                            .map_or("-".to_string(), |sequence| sequence.to_string()),
+                        chain_break.activity_log_id.as_deref().unwrap_or("-"),
+                        chain_break.anchor_id.as_deref().unwrap_or("-"),
+                    );
+                }
+            }
+
+            if !is_intact {
+                return Err(anyhow!("Activity log chain verification failed"));
+            }
+        }
         Action::GeneratePluginTypescriptTypes {
             path,
             skip_prettify,
@@ -109,7 +109,7 @@ impl Test for DatabaseTest {
             config.database.database_name, config.database.host
         );
 
-        let connection_manager = get_storage_connection_manager(&config.database);
+        let connection_manager = get_storage_connection_manager(&config.database)?;
         let result = connection_manager.execute("select 1");
 
         if result.is_ok() {
@@ -332,7 +332,7 @@ pub fn get_url(config: &service::settings::Settings) -> Result<Url> {
 
 pub fn get_sync_settings(config: &service::settings::Settings) -> Result<SyncSettings> {
     let machine_uid = machine_uid::get().expect("Failed to query OS for hardware id");
-    let connection_manager = get_storage_connection_manager(&config.database);
+    let connection_manager = get_storage_connection_manager(&config.database)?;
     let service_provider = ServiceProvider::new(connection_manager.clone());
 
     service_provider
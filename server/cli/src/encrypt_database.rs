@@ -0,0 +1,96 @@
+use clap::ArgAction;
+use service::settings::Settings;
+
+#[derive(clap::Args)]
+#[cfg_attr(not(feature = "sqlcipher"), allow(dead_code))]
+pub(super) struct EncryptDatabaseArguments {
+    /// Decrypt the database back to plain SQLite, remove `encryption` from database
+    /// configurations afterwards
+    #[clap(long, action = ArgAction::SetTrue)]
+    decrypt: bool,
+}
+
+/// Encrypts a plaintext database with the configured key, or changes the key of an encrypted
+/// database (rekey). The server must be stopped
+#[cfg(feature = "sqlcipher")]
+pub(super) fn encrypt_database(
+    settings: &Settings,
+    EncryptDatabaseArguments { decrypt }: EncryptDatabaseArguments,
+) -> anyhow::Result<()> {
+    use anyhow::anyhow;
+    use colored::Colorize;
+    use repository::database_encryption::{
+        generate_database_key, is_plaintext_database, prompt_key, reencrypt_database,
+        write_keystore_key, DatabaseKeySource, DATABASE_KEYSTORE_SERVICE,
+    };
+    use std::path::PathBuf;
+
+    let database = &settings.database;
+    let encryption = database.encryption.as_ref().ok_or(anyhow!(
+        "Database encryption is not specified in configuration files"
+    ))?;
+    let path = PathBuf::from(database.database_path());
+    if !path.try_exists()? {
+        return Err(anyhow!("Database '{}' doesn't exist", path.display()));
+    }
+
+    if is_plaintext_database(&path)? {
+        if decrypt {
+            return Err(anyhow!("Database '{}' is not encrypted", path.display()));
+        }
+        let key = database.encryption_key()?;
+        reencrypt_database(&path, None, key.as_deref())?;
+        println!(
+            "{} Database '{}' encrypted",
+            "[PASS]".green(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let old_key = database
+        .encryption_key()?
+        .ok_or(anyhow!("Database key is not configured"))?;
+
+    if decrypt {
+        reencrypt_database(&path, Some(&old_key), None)?;
+        println!(
+            "{} Database '{}' decrypted",
+            "[PASS]".green(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    match encryption.key_source {
+        DatabaseKeySource::Keystore => {
+            let new_key = generate_database_key();
+            reencrypt_database(&path, Some(&old_key), Some(&new_key))?;
+            // Database is only readable with the new key now, revert if it can't be stored
+            if let Err(error) =
+                write_keystore_key(DATABASE_KEYSTORE_SERVICE, &database.database_name, &new_key)
+            {
+                reencrypt_database(&path, Some(&new_key), Some(&old_key))?;
+                return Err(error.into());
+            }
+        }
+        DatabaseKeySource::Prompt => {
+            let new_key = prompt_key("Enter the new database key: ")?;
+            if new_key.is_empty() {
+                return Err(anyhow!("Key is empty"));
+            }
+            if prompt_key("Repeat the new database key: ")? != new_key {
+                return Err(anyhow!("Keys don't match"));
+            }
+            reencrypt_database(&path, Some(&old_key), Some(&new_key))?;
+        }
+    }
+
+    println!("{} Database '{}' rekeyed", "[PASS]".green(), path.display());
+    Ok(())
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+pub(super) fn encrypt_database(_: &Settings, _: EncryptDatabaseArguments) -> anyhow::Result<()> {
+    Err(repository::database_encryption::DatabaseKeyError::NotSupported.into())
+}
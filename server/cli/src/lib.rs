@@ -16,6 +16,9 @@ pub use helpers::*;
 mod plugins;
 pub use plugins::*;
 
+mod test_plugin;
+pub use test_plugin::*;
+
 mod generate_plugin_typescript_types;
 pub use generate_plugin_typescript_types::*;
 
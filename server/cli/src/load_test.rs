@@ -480,12 +480,14 @@ impl LoadTest {
                 connection_pool_min_idle: None,
                 connection_pool_timeout_seconds: None,
                 init_sql: None,
+                encryption: None,
             },
             logging: None,
             backup: None,
             mail: None,
             sync: None,
             features: None,
+            graphql: None,
         };
         let base_config_path = self.output_dir.join("base.yaml");
         std::fs::write(base_config_path, serde_yml::to_string(&base_config)?)?;
@@ -569,6 +571,7 @@ impl LoadTest {
                     connection_pool_min_idle: None,
                     connection_pool_timeout_seconds: None,
                     init_sql: None,
+                    encryption: None,
                 },
                 sync: Some(SyncSettings {
                     url: self.msupply_central_url.clone(),
@@ -586,6 +589,7 @@ impl LoadTest {
                 backup: None,
                 mail: None,
                 features: None,
+                graphql: None,
             };
 
             let full_site = TestSite {
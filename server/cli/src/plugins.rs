@@ -2,7 +2,8 @@ use base64::{prelude::BASE64_STANDARD, Engine};
 use log::{info, warn};
 use repository::{
     BackendPluginRow, FrontendPluginFile, FrontendPluginFiles, FrontendPluginRow,
-    FrontendPluginTypes, PluginTypes, PluginVariantType,
+    FrontendPluginTypes, PluginManifest, PluginMigration, PluginMutation, PluginTypes,
+    PluginVariantType,
 };
 use reqwest::Url;
 use serde::Deserialize;
@@ -102,6 +103,12 @@ enum PluginDescription {
     Backend {
         types: PluginTypes,
         variant_type: PluginVariantType,
+        /// Named mutations with json schema for input, exposed via pluginGraphqlMutation
+        #[serde(default)]
+        mutations: Vec<PluginMutation>,
+        /// Migrations for plugin tables, `{prefix}` is replaced with plugin table namespace
+        #[serde(default)]
+        migrations: Vec<PluginMigration>,
     },
     #[serde(rename = "frontend")]
     FrontEnd { types: FrontendPluginTypes },
@@ -220,7 +227,20 @@ fn process_manifest(bundle: &mut PluginBundle, path: &PathBuf) -> Result<(), Err
         PluginDescription::Backend {
             types,
             variant_type,
-        } => bundle_backend_plugin(bundle, code, types, variant_type, plugin_root, version)?,
+            mutations,
+            migrations,
+        } => bundle_backend_plugin(
+            bundle,
+            code,
+            types,
+            variant_type,
+            PluginManifest {
+                mutations,
+                migrations,
+            },
+            plugin_root,
+            version,
+        )?,
         PluginDescription::FrontEnd { types } => {
             bundle_frontend_plugin(bundle, code, types, plugin_root, version)?
         }
@@ -234,6 +254,7 @@ fn bundle_backend_plugin(
     code: String,
     types: PluginTypes,
     variant_type: PluginVariantType,
+    manifest: PluginManifest,
     plugin_root: &Path,
     version: String,
 ) -> Result<(), Error> {
@@ -252,6 +273,7 @@ fn bundle_backend_plugin(
         types,
         code,
         version,
+        manifest,
     });
 
     Ok(())
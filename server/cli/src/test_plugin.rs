@@ -0,0 +1,328 @@
+use actix_web::web::Data;
+use colored::Colorize;
+use log::info;
+use repository::{
+    mock::MockDataInserts, test_db::setup_all, BackendPluginRowRepository, PluginType,
+    RepositoryError,
+};
+use service::{
+    backend_plugin::plugin_provider::{PluginBundle, PluginInstance},
+    boajs::{
+        context::BoaJsContext,
+        utils::{ExecuteGraphQlError, ExecuteGraphql},
+    },
+    service_provider::ServiceProvider,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use thiserror::Error as ThisError;
+use util::format_error;
+
+const INPUT_SUFFIX: &str = ".input.json";
+const OUTPUT_SUFFIX: &str = ".output.json";
+
+#[derive(ThisError, Debug)]
+pub enum Error {
+    #[error("Failed to read file {0}")]
+    FailedToReadFile(PathBuf, #[source] std::io::Error),
+    #[error("Failed to write file {0}")]
+    FailedToWriteFile(PathBuf, #[source] std::io::Error),
+    #[error("Failed to parse json file {0}")]
+    FailedToParseJson(PathBuf, #[source] serde_json::Error),
+    #[error("Failed to read dir {0}")]
+    FailedToReadDir(PathBuf, #[source] std::io::Error),
+    #[error(transparent)]
+    DatabaseError(#[from] RepositoryError),
+    #[error("Plugin {0} (version {1}) is not compatible with app version")]
+    PluginNotCompatible(String, String),
+    #[error("{0} of {1} plugin test cases failed")]
+    TestCasesFailed(usize, usize),
+}
+
+#[derive(clap::Parser, Debug)]
+pub struct TestPlugin {
+    /// Path to plugin bundle json (see generate-plugin-bundle)
+    #[clap(short, long)]
+    bundle: PathBuf,
+    /// Directory with test cases, laid out as {plugin_code}/{plugin_type}/{case}.input.json,
+    /// expected output is compared against {case}.output.json
+    #[clap(short, long)]
+    tests_dir: PathBuf,
+    /// Write plugin output to {case}.output.json instead of comparing, cases without
+    /// {case}.output.json fail unless this is set
+    #[clap(short, long)]
+    update_snapshots: bool,
+}
+
+/// use_graphql is not available without running server
+struct NoGraphql;
+
+#[async_trait::async_trait]
+impl ExecuteGraphql for NoGraphql {
+    async fn execute_graphql(
+        &self,
+        _: &str,
+        _: &str,
+        _: serde_json::Value,
+    ) -> Result<serde_json::Value, ExecuteGraphQlError> {
+        Err(ExecuteGraphQlError::Graphql(
+            "use_graphql is not available in test-plugin".to_string(),
+        ))
+    }
+}
+
+enum CaseResult {
+    Passed,
+    Updated,
+    Failed(String),
+}
+
+struct TestCase {
+    plugin_code: String,
+    r#type: PluginType,
+    name: String,
+    input_path: PathBuf,
+    output_path: PathBuf,
+}
+
+fn read_json(path: &Path) -> Result<serde_json::Value, Error> {
+    let content =
+        fs::read_to_string(path).map_err(|e| Error::FailedToReadFile(path.to_path_buf(), e))?;
+    serde_json::from_str(&content).map_err(|e| Error::FailedToParseJson(path.to_path_buf(), e))
+}
+
+fn plugin_type_dir_name(r#type: &PluginType) -> String {
+    serde_json::to_string(r#type).unwrap().replace('"', "")
+}
+
+fn find_test_cases(
+    tests_dir: &Path,
+    plugin_code: &str,
+    r#type: &PluginType,
+) -> Result<Vec<TestCase>, Error> {
+    let dir = tests_dir
+        .join(plugin_code)
+        .join(plugin_type_dir_name(r#type));
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut cases = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| Error::FailedToReadDir(dir.clone(), e))? {
+        let input_path = entry
+            .map_err(|e| Error::FailedToReadDir(dir.clone(), e))?
+            .path();
+        let file_name = input_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let Some(name) = file_name.strip_suffix(INPUT_SUFFIX) else {
+            continue;
+        };
+
+        cases.push(TestCase {
+            plugin_code: plugin_code.to_string(),
+            r#type: r#type.clone(),
+            name: name.to_string(),
+            output_path: dir.join(format!("{name}{OUTPUT_SUFFIX}")),
+            input_path,
+        });
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(cases)
+}
+
+fn run_test_case(
+    plugin: &PluginInstance,
+    case: &TestCase,
+    update_snapshots: bool,
+) -> Result<(CaseResult, Duration), Error> {
+    let input = read_json(&case.input_path)?;
+
+    let started = Instant::now();
+    let result = plugin.call_json(case.r#type.clone(), input);
+    let elapsed = started.elapsed();
+
+    let output = match result {
+        Ok(output) => output,
+        Err(e) => return Ok((CaseResult::Failed(format_error(&e)), elapsed)),
+    };
+
+    if update_snapshots {
+        fs::write(
+            &case.output_path,
+            serde_json::to_string_pretty(&output).unwrap(),
+        )
+        .map_err(|e| Error::FailedToWriteFile(case.output_path.clone(), e))?;
+        return Ok((CaseResult::Updated, elapsed));
+    }
+
+    if !case.output_path.exists() {
+        return Ok((
+            CaseResult::Failed(format!(
+                "{} is missing, run with --update-snapshots to create it",
+                case.output_path.display()
+            )),
+            elapsed,
+        ));
+    }
+
+    let expected = read_json(&case.output_path)?;
+    if expected == output {
+        return Ok((CaseResult::Passed, elapsed));
+    }
+
+    Ok((
+        CaseResult::Failed(format!(
+            "expected:\n{}\nreceived:\n{}",
+            serde_json::to_string_pretty(&expected).unwrap(),
+            serde_json::to_string_pretty(&output).unwrap()
+        )),
+        elapsed,
+    ))
+}
+
+/// Runs plugin test cases against fresh database populated with repository mock data,
+/// no running server is required
+pub async fn test_plugin(
+    TestPlugin {
+        bundle,
+        tests_dir,
+        update_snapshots,
+    }: TestPlugin,
+) -> Result<(), Error> {
+    let bundle_content =
+        fs::read_to_string(&bundle).map_err(|e| Error::FailedToReadFile(bundle.clone(), e))?;
+    let PluginBundle {
+        backend_plugins, ..
+    } = serde_json::from_str(&bundle_content)
+        .map_err(|e| Error::FailedToParseJson(bundle.clone(), e))?;
+
+    info!("Creating fixture database");
+    let (_, connection, connection_manager, _) =
+        setup_all("test_plugin", MockDataInserts::all()).await;
+
+    let service_provider = Data::new(ServiceProvider::new(connection_manager));
+    BoaJsContext::new(&service_provider, NoGraphql).bind();
+
+    let mut total = 0;
+    let mut failed = 0;
+    for row in backend_plugins {
+        let code = row.code.clone();
+        let version = row.version.clone();
+        let types = row.types.0.clone();
+
+        // Upserting plugin row also applies plugin table migrations
+        BackendPluginRowRepository::new(&connection).upsert_one(row.clone())?;
+        PluginInstance::bind(row);
+
+        for r#type in types {
+            let plugin = PluginInstance::get_one_with_code(&code, r#type.clone())
+                .ok_or(Error::PluginNotCompatible(code.clone(), version.clone()))?;
+
+            for case in find_test_cases(&tests_dir, &code, &r#type)? {
+                total += 1;
+                let (result, elapsed) = run_test_case(&plugin, &case, update_snapshots)?;
+                let case_name = format!(
+                    "{}/{}/{}",
+                    case.plugin_code,
+                    plugin_type_dir_name(&case.r#type),
+                    case.name
+                );
+
+                match result {
+                    CaseResult::Passed => {
+                        println!("{} {case_name} ({elapsed:?})", "PASS".green())
+                    }
+                    CaseResult::Updated => {
+                        println!("{} {case_name} ({elapsed:?})", "UPDATED".yellow())
+                    }
+                    CaseResult::Failed(reason) => {
+                        failed += 1;
+                        println!("{} {case_name} ({elapsed:?})\n{reason}", "FAIL".red())
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{} test cases, {} failed", total, failed);
+    if failed > 0 {
+        return Err(Error::TestCasesFailed(failed, total));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use base64::{prelude::BASE64_STANDARD, Engine};
+    use repository::{migrations::Version, BackendPluginRow, PluginTypes};
+    use serde_json::json;
+
+    #[test]
+    fn test_plugin_cases() {
+        let code = "test-plugin-cases";
+        let r#type = PluginType::GraphqlMutation;
+        let tests_dir = std::env::temp_dir().join("test_plugin_cases");
+        let _ = fs::remove_dir_all(&tests_dir);
+        let cases_dir = tests_dir.join(code).join(plugin_type_dir_name(&r#type));
+        fs::create_dir_all(&cases_dir).unwrap();
+        fs::write(cases_dir.join("b.input.json"), r#"{ "count": 2 }"#).unwrap();
+        fs::write(cases_dir.join("b.output.json"), r#"{ "count": 2 }"#).unwrap();
+        fs::write(cases_dir.join("a.input.json"), r#"{ "count": 1 }"#).unwrap();
+        fs::write(cases_dir.join("notes.txt"), "Not a test case").unwrap();
+
+        let bundle = r#"
+            export const plugins = {
+                graphql_mutation: ({ count }) => ({ count: count + 1 }),
+            };
+        "#;
+        PluginInstance::bind(BackendPluginRow {
+            id: code.to_string(),
+            code: code.to_string(),
+            version: Version::from_package_json().to_string(),
+            bundle_base64: BASE64_STANDARD.encode(bundle),
+            types: PluginTypes(vec![r#type.clone()]),
+            ..Default::default()
+        });
+        let plugin = PluginInstance::get_one_with_code(code, r#type.clone()).unwrap();
+
+        let cases = find_test_cases(&tests_dir, code, &r#type).unwrap();
+        assert_eq!(
+            cases
+                .iter()
+                .map(|case| case.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        let (missing_snapshot, mismatched_snapshot) = (&cases[0], &cases[1]);
+
+        // Missing snapshot fails and is not created
+        let (result, _) = run_test_case(&plugin, missing_snapshot, false).unwrap();
+        assert!(matches!(result, CaseResult::Failed(_)));
+        assert!(!missing_snapshot.output_path.exists());
+
+        let (result, _) = run_test_case(&plugin, mismatched_snapshot, false).unwrap();
+        assert!(matches!(result, CaseResult::Failed(_)));
+
+        // Updating writes plugin output, which then passes
+        for case in &cases {
+            let (result, _) = run_test_case(&plugin, case, true).unwrap();
+            assert!(matches!(result, CaseResult::Updated));
+            let (result, _) = run_test_case(&plugin, case, false).unwrap();
+            assert!(matches!(result, CaseResult::Passed));
+        }
+        assert_eq!(
+            read_json(&mismatched_snapshot.output_path).unwrap(),
+            json!({ "count": 3 })
+        );
+
+        fs::remove_dir_all(&tests_dir).unwrap();
+    }
+}
@@ -3,6 +3,7 @@ pub mod generic_inputs;
 pub mod loader;
 pub mod operational_status;
 pub mod pagination;
+pub mod query_limits;
 pub mod simple_generic_errors;
 pub mod standard_graphql_error;
 pub mod test_helpers;
@@ -20,6 +21,7 @@ use actix_web::http::header::COOKIE;
 use repository::StorageConnectionManager;
 use service::auth_data::AuthData;
 use service::plugin::validation::ValidatedPluginBucket;
+use service::rate_limit::RateLimiter;
 use service::service_provider::ServiceProvider;
 
 use loader::LoaderRegistry;
@@ -47,6 +49,8 @@ pub trait ContextExt {
     fn get_validated_plugins(&self) -> &Mutex<ValidatedPluginBucket>;
     fn restart_switch(&self) -> Sender<bool>;
     fn get_operational_status(&self) -> &RwLock<OperationalStatus>;
+    fn get_rate_limiter(&self) -> Option<&RateLimiter>;
+    fn get_client_ip(&self) -> Option<String>;
 }
 
 impl<'a> ContextExt for Context<'a> {
@@ -96,6 +100,17 @@ impl<'a> ContextExt for Context<'a> {
     fn get_operational_status(&self) -> &RwLock<OperationalStatus> {
         self.data_unchecked::<Data<RwLock<OperationalStatus>>>()
     }
+
+    // Not available for self requests, e.g. report data queries, which are not rate limited
+    fn get_rate_limiter(&self) -> Option<&RateLimiter> {
+        self.data_opt::<Data<RateLimiter>>()
+            .map(|data| data.get_ref())
+    }
+
+    fn get_client_ip(&self) -> Option<String> {
+        self.data_opt::<RequestClientData>()
+            .and_then(|d| d.ip_address.to_owned())
+    }
 }
 
 #[derive(Clone)]
@@ -106,6 +121,18 @@ pub struct RequestUserData {
     pub refresh_token: Option<String>,
 }
 
+#[derive(Clone)]
+pub struct RequestClientData {
+    pub ip_address: Option<String>,
+}
+
+pub fn client_data_from_request(http_req: &HttpRequest) -> RequestClientData {
+    // Peer address rather than forwarded headers, which the client can set
+    RequestClientData {
+        ip_address: http_req.peer_addr().map(|address| address.ip().to_string()),
+    }
+}
+
 pub fn auth_data_from_request(http_req: &HttpRequest) -> RequestUserData {
     let headers = http_req.headers();
     // retrieve auth token
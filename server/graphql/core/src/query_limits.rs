@@ -0,0 +1,85 @@
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextValidation},
+    ErrorExtensions, Pos, ServerError, ValidationResult,
+};
+use service::settings::GraphqlSettings;
+use std::sync::Arc;
+
+/// Rejects queries nested deeper or more complex than configured in `GraphqlSettings`, errors
+/// include the limit and the actual value so clients can split the query
+#[derive(Clone)]
+pub struct QueryLimits {
+    max_depth: usize,
+    max_complexity: usize,
+}
+
+impl QueryLimits {
+    pub fn new(settings: &GraphqlSettings) -> Self {
+        QueryLimits {
+            max_depth: settings.max_depth,
+            max_complexity: settings.max_complexity,
+        }
+    }
+}
+
+impl ExtensionFactory for QueryLimits {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryLimitsExtension {
+            max_depth: self.max_depth,
+            max_complexity: self.max_complexity,
+        })
+    }
+}
+
+struct QueryLimitsExtension {
+    max_depth: usize,
+    max_complexity: usize,
+}
+
+fn limit_error(message: &str, kind: &str, limit: usize, actual: usize) -> ServerError {
+    async_graphql::Error::new(message)
+        .extend_with(|_, e| {
+            e.set(
+                "details",
+                format!("{kind} of {actual} exceeds the limit of {limit}"),
+            );
+            e.set("limit", limit as u64);
+            e.set("actual", actual as u64);
+        })
+        .into_server_error(Pos::default())
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryLimitsExtension {
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        let result = next.run(ctx).await?;
+
+        let mut errors = Vec::new();
+        if result.depth > self.max_depth {
+            errors.push(limit_error(
+                "Query is nested too deep",
+                "Depth",
+                self.max_depth,
+                result.depth,
+            ));
+        }
+        if result.complexity > self.max_complexity {
+            errors.push(limit_error(
+                "Query is too complex",
+                "Complexity",
+                self.max_complexity,
+                result.complexity,
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -49,7 +49,12 @@ impl From<RepositoryError> for NodeError {
 // Generic Errors
 
 use repository::RepositoryError;
-use service::SingleRecordError;
+use service::{
+    backend_plugin::types::validate_mutation::{
+        PluginValidationFailed as ServicePluginValidationFailed, PluginValidationMessage,
+    },
+    SingleRecordError,
+};
 
 pub struct DatabaseError(pub RepositoryError);
 
@@ -385,3 +390,55 @@ impl FailedTranslation {
         self.0.to_owned()
     }
 }
+
+#[derive(SimpleObject)]
+pub struct PluginValidationMessageNode {
+    pub plugin_code: String,
+    pub code: String,
+    pub message: String,
+    pub record_id: Option<String>,
+}
+
+impl PluginValidationMessageNode {
+    pub fn from_domain(
+        PluginValidationMessage {
+            plugin_code,
+            code,
+            message,
+            record_id,
+        }: PluginValidationMessage,
+    ) -> Self {
+        PluginValidationMessageNode {
+            plugin_code,
+            code,
+            message,
+            record_id,
+        }
+    }
+}
+
+pub struct PluginValidationFailed(pub ServicePluginValidationFailed);
+#[Object]
+impl PluginValidationFailed {
+    pub async fn description(&self) -> &str {
+        "Rejected by plugin validation"
+    }
+
+    pub async fn errors(&self) -> Vec<PluginValidationMessageNode> {
+        self.0
+            .errors
+            .clone()
+            .into_iter()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
+
+    pub async fn warnings(&self) -> Vec<PluginValidationMessageNode> {
+        self.0
+            .warnings
+            .clone()
+            .into_iter()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
+}
@@ -4,6 +4,7 @@ use async_graphql::{Context, ErrorExtensions, Result};
 use repository::RepositoryError;
 use service::{
     auth::{AuthDeniedKind, AuthError, ResourceAccessRequest, ValidatedUser},
+    rate_limit::RateLimitedOperation,
     ListError,
 };
 use thiserror::Error;
@@ -22,6 +23,12 @@ pub enum StandardGraphqlError {
 
     #[error("Forbidden")]
     Forbidden(String),
+
+    #[error("Too many requests")]
+    TooManyRequests {
+        details: String,
+        retry_after_seconds: u64,
+    },
 }
 
 impl ErrorExtensions for StandardGraphqlError {
@@ -32,6 +39,13 @@ impl ErrorExtensions for StandardGraphqlError {
             StandardGraphqlError::BadUserInput(details) => e.set("details", details.clone()),
             StandardGraphqlError::Unauthenticated(details) => e.set("details", details.clone()),
             StandardGraphqlError::Forbidden(details) => e.set("details", details.clone()),
+            StandardGraphqlError::TooManyRequests {
+                details,
+                retry_after_seconds,
+            } => {
+                e.set("details", details.clone());
+                e.set("retryAfterSeconds", *retry_after_seconds);
+            }
         })
     }
 }
@@ -106,6 +120,27 @@ pub fn validate_auth(
     })
 }
 
+/// Validates the user and their client IP are within the rate limit of an expensive operation
+pub fn validate_rate_limit(
+    ctx: &Context<'_>,
+    user: &ValidatedUser,
+    operation: RateLimitedOperation,
+) -> Result<()> {
+    let Some(rate_limiter) = ctx.get_rate_limiter() else {
+        return Ok(());
+    };
+
+    rate_limiter
+        .check(operation, &user.user_id, ctx.get_client_ip().as_deref())
+        .map_err(|error| {
+            StandardGraphqlError::TooManyRequests {
+                details: format!("{error:?}"),
+                retry_after_seconds: error.retry_after_seconds,
+            }
+            .extend()
+        })
+}
+
 pub fn list_error_to_gql_err(err: ListError) -> async_graphql::Error {
     let gql_err = match err {
         ListError::DatabaseError(err) => err.into(),
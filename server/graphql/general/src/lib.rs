@@ -9,8 +9,10 @@ use std::collections::HashMap;
 
 pub use self::queries::item::{ItemSortFieldInput, ItemSortInput, ItemsResponse};
 pub use self::queries::sync_status::*;
-pub use self::subscriptions::{InitialisationSubscriptions, SyncStatusSubscriptions};
 use self::queries::*;
+pub use self::subscriptions::{
+    InitialisationSubscriptions, StoreEventSubscriptions, SyncStatusSubscriptions,
+};
 
 use abbreviation::abbreviations;
 use diagnosis::diagnoses_active;
@@ -20,14 +22,23 @@ use service::sync::CentralServerConfig;
 use crate::store_preference::store_preferences;
 use graphql_types::types::{
     AbbreviationNode, CurrenciesResponse, CurrencyFilterInput, CurrencySortInput, DiagnosisNode,
-    MasterListFilterInput, StorePreferenceNode,
+    InvoiceConnector, MasterListFilterInput, StorePreferenceNode,
 };
 use mutations::{
+    api_key::{
+        create_api_key, revoke_api_key, rotate_api_key, CreateApiKeyInput, NewApiKeyNode,
+        RotateApiKeyInput,
+    },
+    audit_log::{update_audit_log_settings, AuditLogSettingsInput},
     barcode::{insert_barcode, BarcodeInput},
     common::SyncSettingsInput,
     display_settings::{
         update_display_settings, DisplaySettingsInput, UpdateDisplaySettingsResponse,
     },
+    epcis::{
+        export_epcis_events, update_epcis_settings, EpcisExportNode, EpcisSettingsInput,
+        ExportEpcisEventsInput,
+    },
     initialise_site::{initialise_site, InitialiseSiteResponse},
     insert_insurance::{insert_insurance, InsertInsuranceInput, InsertInsuranceResponse},
     label_printer_settings::{
@@ -36,17 +47,31 @@ use mutations::{
     },
     log::{update_log_level, LogLevelInput, UpsertLogLevelResponse},
     manual_sync::manual_sync,
+    network_stock::{create_redistribution_shipments, RedistributionTransferInput},
     sync_settings::{update_sync_settings, UpdateSyncSettingsResponse},
     update_insurance::{update_insurance, UpdateInsuranceInput, UpdateInsuranceResponse},
     update_name_properties::{
         update_name_properties, UpdateNamePropertiesInput, UpdateNamePropertiesResponse,
     },
     update_user,
+    webhook::{
+        delete_webhook, replay_webhook, retry_webhook_delivery, upsert_webhook, UpsertWebhookInput,
+    },
 };
 use queries::{
     abbreviation::AbbreviationFilterInput,
+    activity_log_chain::{activity_log_chain_verification, ActivityLogChainVerificationNode},
+    api_key::{api_keys, ApiKeyConnector, ApiKeyNode},
+    audit_log::{
+        audit_log_settings, audit_logs, AuditLogConnector, AuditLogSettingsNode, AuditedTableNode,
+    },
     currency::currencies,
+    demand_forecast::{demand_forecast, DemandForecastConnector, DemandForecastInput},
     display_settings::{display_settings, DisplaySettingsHash, DisplaySettingsNode},
+    epcis::{epcis_settings, EpcisSettingsNode},
+    expiry_risk::{
+        expiry_risk, network_expiry_risk, ExpiryRiskInput, StockLineExpiryRiskConnector,
+    },
     initialisation_status::{initialisation_status, InitialisationStatusNode},
     insurance_policies::{
         insurance_policies, insurance_policy, InsuranceResponse, InsuranceSortInput,
@@ -54,9 +79,18 @@ use queries::{
     },
     insurance_providers::{insurance_providers, InsuranceProvidersResponse},
     migration_status::{migration_status, MigrationStatusNode},
+    network_stock::{
+        network_stock_levels, redistribution_suggestions, NetworkStockConnector, NetworkStockInput,
+        RedistributionSuggestionConnector,
+    },
     requisition_line_chart::{ConsumptionOptionsInput, StockEvolutionOptionsInput},
     shipping_method::{get_shipping_methods, ShippingMethodFilterInput, ShippingMethodsResponse},
+    site_health::{site_health, SiteHealthConnector},
     sync_settings::{sync_settings, SyncSettingsNode},
+    webhook::{
+        webhook_deliveries, webhooks, WebhookConnector, WebhookDeliveryConnector,
+        WebhookDeliveryNode, WebhookDeliveryStatusNode, WebhookNode,
+    },
 };
 
 #[derive(Default, Clone)]
@@ -226,7 +260,9 @@ impl GeneralQueries {
         inbound_shipment_external_counts(ctx, store_id, timezone_offset)
     }
 
-    #[graphql(deprecation = "Use outboundShipmentCounts, inboundShipmentCounts, or inboundShipmentExternalCounts instead")]
+    #[graphql(
+        deprecation = "Use outboundShipmentCounts, inboundShipmentCounts, or inboundShipmentExternalCounts instead"
+    )]
     #[allow(deprecated)]
     pub async fn invoice_counts(
         &self,
@@ -264,6 +300,16 @@ impl GeneralQueries {
         )
     }
 
+    /// Monthly demand forecast per item, from consumption history corrected for stock outs
+    pub async fn demand_forecast(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: DemandForecastInput,
+    ) -> Result<DemandForecastConnector> {
+        demand_forecast(ctx, &store_id, input)
+    }
+
     pub async fn activity_logs(
         &self,
         ctx: &Context<'_>,
@@ -276,6 +322,29 @@ impl GeneralQueries {
         activity_logs(ctx, store_id, page, filter, sort)
     }
 
+    /// Recomputes the store's activity log hash chain and reports where it breaks, compared with
+    /// the anchors held by the central server
+    pub async fn activity_log_chain_verification(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Compare with the anchors stored on this site instead, e.g. offline")]
+        use_local_anchors: Option<bool>,
+    ) -> Result<ActivityLogChainVerificationNode> {
+        activity_log_chain_verification(ctx, store_id, use_local_anchors).await
+    }
+
+    /// Field level changes of a master data record, newest first
+    pub async fn audit_logs(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        table: AuditedTableNode,
+        record_id: String,
+    ) -> Result<AuditLogConnector> {
+        audit_logs(ctx, store_id, table, record_id)
+    }
+
     /// Available without authorisation in operational and initialisation states
     pub async fn initialisation_status(
         &self,
@@ -455,6 +524,71 @@ impl GeneralQueries {
         label_printer_settings(ctx)
     }
 
+    pub async fn epcis_settings(&self, ctx: &Context<'_>) -> Result<Option<EpcisSettingsNode>> {
+        epcis_settings(ctx)
+    }
+
+    pub async fn audit_log_settings(&self, ctx: &Context<'_>) -> Result<AuditLogSettingsNode> {
+        audit_log_settings(ctx)
+    }
+
+    /// API keys of service accounts, newest first
+    pub async fn api_keys(&self, ctx: &Context<'_>) -> Result<ApiKeyConnector> {
+        api_keys(ctx)
+    }
+
+    pub async fn webhooks(&self, ctx: &Context<'_>) -> Result<WebhookConnector> {
+        webhooks(ctx)
+    }
+
+    /// Deliveries of outbound webhooks by status, use `DEAD_LETTER` for the ones that gave up
+    pub async fn webhook_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        status: WebhookDeliveryStatusNode,
+        webhook_id: Option<String>,
+    ) -> Result<WebhookDeliveryConnector> {
+        webhook_deliveries(ctx, status, webhook_id)
+    }
+
+    /// Projected expiry losses per stock line in the store, with suggested actions
+    pub async fn expiry_risk(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: ExpiryRiskInput,
+    ) -> Result<StockLineExpiryRiskConnector> {
+        expiry_risk(ctx, store_id, input)
+    }
+
+    /// Projected expiry losses per stock line across stores, central server only
+    pub async fn network_expiry_risk(
+        &self,
+        ctx: &Context<'_>,
+        store_ids: Option<Vec<String>>,
+        input: ExpiryRiskInput,
+    ) -> Result<StockLineExpiryRiskConnector> {
+        network_expiry_risk(ctx, store_ids, input)
+    }
+
+    /// Months of stock of items in every store, central server only
+    pub async fn network_stock_levels(
+        &self,
+        ctx: &Context<'_>,
+        input: NetworkStockInput,
+    ) -> Result<NetworkStockConnector> {
+        network_stock_levels(ctx, input)
+    }
+
+    /// Transfers from overstocked to understocked stores, central server only
+    pub async fn redistribution_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        input: NetworkStockInput,
+    ) -> Result<RedistributionSuggestionConnector> {
+        redistribution_suggestions(ctx, input)
+    }
+
     pub async fn name_properties(&self, ctx: &Context<'_>) -> Result<NamePropertyResponse> {
         name_properties(ctx)
     }
@@ -600,6 +734,97 @@ impl GeneralMutations {
         update_label_printer_settings(ctx, input)
     }
 
+    pub async fn update_epcis_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: EpcisSettingsInput,
+    ) -> Result<EpcisSettingsNode> {
+        update_epcis_settings(ctx, input)
+    }
+
+    pub async fn update_audit_log_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: AuditLogSettingsInput,
+    ) -> Result<AuditLogSettingsNode> {
+        update_audit_log_settings(ctx, input)
+    }
+
+    /// Exports receipt, dispensing and shipment events of the store in the period as GS1 EPCIS 2.0
+    pub async fn export_epcis_events(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: ExportEpcisEventsInput,
+    ) -> Result<EpcisExportNode> {
+        export_epcis_events(ctx, store_id, input).await
+    }
+
+    /// Creates a service account with an API key scoped to the given resources and stores
+    pub async fn create_api_key(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateApiKeyInput,
+    ) -> Result<NewApiKeyNode> {
+        create_api_key(ctx, input)
+    }
+
+    /// Replaces the key with a new one for the same service account, the old key is revoked
+    pub async fn rotate_api_key(
+        &self,
+        ctx: &Context<'_>,
+        input: RotateApiKeyInput,
+    ) -> Result<NewApiKeyNode> {
+        rotate_api_key(ctx, input)
+    }
+
+    pub async fn revoke_api_key(&self, ctx: &Context<'_>, id: String) -> Result<ApiKeyNode> {
+        revoke_api_key(ctx, id)
+    }
+
+    /// Webhooks are posted changelog events signed with the secret, new webhooks start from the
+    /// latest change
+    pub async fn upsert_webhook(
+        &self,
+        ctx: &Context<'_>,
+        input: UpsertWebhookInput,
+    ) -> Result<WebhookNode> {
+        upsert_webhook(ctx, input)
+    }
+
+    pub async fn delete_webhook(&self, ctx: &Context<'_>, id: String) -> Result<String> {
+        delete_webhook(ctx, id)
+    }
+
+    /// Delivers changes again from the changelog cursor onwards
+    pub async fn replay_webhook(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        from_cursor: u64,
+    ) -> Result<WebhookNode> {
+        replay_webhook(ctx, id, from_cursor)
+    }
+
+    /// Retries a dead letter delivery with the next dispatch
+    pub async fn retry_webhook_delivery(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<WebhookDeliveryNode> {
+        retry_webhook_delivery(ctx, id)
+    }
+
+    /// Creates draft outbound shipments for accepted redistribution suggestions sent from the store
+    pub async fn create_redistribution_shipments(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: Vec<RedistributionTransferInput>,
+    ) -> Result<InvoiceConnector> {
+        create_redistribution_shipments(ctx, store_id, input)
+    }
+
     pub async fn update_name_properties(
         &self,
         ctx: &Context<'_>,
@@ -705,6 +930,18 @@ impl DiscoveryQueries {
     }
 }
 
+// Central server only queries
+#[derive(Default, Clone)]
+pub struct CentralGeneralQueries;
+
+#[Object]
+impl CentralGeneralQueries {
+    /// Latest health report and backup status of every site reporting to this central server
+    pub async fn site_health(&self, ctx: &Context<'_>) -> Result<SiteHealthConnector> {
+        site_health(ctx)
+    }
+}
+
 // Central server only mutations
 #[derive(Default, Clone)]
 pub struct CentralGeneralMutations;
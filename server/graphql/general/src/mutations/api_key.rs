@@ -0,0 +1,176 @@
+use std::str::FromStr;
+
+use async_graphql::*;
+use chrono::NaiveDateTime;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    api_key::{
+        CreateApiKey, CreateApiKeyError, NewApiKey, RevokeApiKeyError, RotateApiKey,
+        RotateApiKeyError,
+    },
+    auth::{Resource, ResourceAccessRequest},
+};
+
+use crate::queries::api_key::ApiKeyNode;
+
+#[derive(InputObject)]
+pub struct CreateApiKeyInput {
+    pub id: String,
+    pub name: String,
+    /// Permission resources the key can access, e.g. `QueryStockLine`
+    pub resources: Vec<String>,
+    pub store_ids: Vec<String>,
+    pub expiry_datetime: Option<NaiveDateTime>,
+}
+
+#[derive(InputObject)]
+pub struct RotateApiKeyInput {
+    pub id: String,
+    pub new_id: String,
+    /// Keeps the expiry of the rotated key when not provided
+    pub expiry_datetime: Option<NaiveDateTime>,
+}
+
+#[derive(SimpleObject)]
+pub struct NewApiKeyNode {
+    pub api_key: ApiKeyNode,
+    /// Only returned once, send as `Authorization: Bearer <key>`
+    pub key: String,
+}
+
+pub fn create_api_key(ctx: &Context<'_>, input: CreateApiKeyInput) -> Result<NewApiKeyNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let resources = input
+        .resources
+        .iter()
+        .map(|resource| {
+            Resource::from_str(resource).map_err(|_| {
+                StandardGraphqlError::BadUserInput(format!("Unknown resource {resource}")).extend()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context("".to_string(), user.user_id)?;
+
+    let new_api_key = match service_provider.api_key_service.create_api_key(
+        &service_context,
+        CreateApiKey {
+            id: input.id,
+            name: input.name,
+            resources,
+            store_ids: input.store_ids,
+            expiry_datetime: input.expiry_datetime,
+        },
+    ) {
+        Ok(new_api_key) => new_api_key,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                CreateApiKeyError::ApiKeyAlreadyExists
+                | CreateApiKeyError::NameCannotBeEmpty
+                | CreateApiKeyError::NoResources
+                | CreateApiKeyError::StoreDoesNotExist(_)
+                | CreateApiKeyError::ExpiryMustBeInTheFuture => BadUserInput(formatted_error),
+                CreateApiKeyError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(NewApiKeyNode::from_domain(new_api_key))
+}
+
+pub fn rotate_api_key(ctx: &Context<'_>, input: RotateApiKeyInput) -> Result<NewApiKeyNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context("".to_string(), user.user_id)?;
+
+    let new_api_key = match service_provider.api_key_service.rotate_api_key(
+        &service_context,
+        RotateApiKey {
+            id: input.id,
+            new_id: input.new_id,
+            expiry_datetime: input.expiry_datetime,
+        },
+    ) {
+        Ok(new_api_key) => new_api_key,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                RotateApiKeyError::ApiKeyDoesNotExist
+                | RotateApiKeyError::ApiKeyIsRevoked
+                | RotateApiKeyError::ApiKeyAlreadyExists
+                | RotateApiKeyError::ExpiryMustBeInTheFuture => BadUserInput(formatted_error),
+                RotateApiKeyError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(NewApiKeyNode::from_domain(new_api_key))
+}
+
+pub fn revoke_api_key(ctx: &Context<'_>, id: String) -> Result<ApiKeyNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context("".to_string(), user.user_id)?;
+
+    let api_key = match service_provider
+        .api_key_service
+        .revoke_api_key(&service_context, &id)
+    {
+        Ok(api_key) => api_key,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                RevokeApiKeyError::ApiKeyDoesNotExist | RevokeApiKeyError::ApiKeyIsRevoked => {
+                    BadUserInput(formatted_error)
+                }
+                RevokeApiKeyError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(ApiKeyNode { api_key })
+}
+
+impl NewApiKeyNode {
+    fn from_domain(NewApiKey { api_key, key }: NewApiKey) -> NewApiKeyNode {
+        NewApiKeyNode {
+            api_key: ApiKeyNode { api_key },
+            key,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    audit_log::{self, AuditLogSettings, UpdateAuditLogSettingsError},
+    auth::{Resource, ResourceAccessRequest},
+};
+
+use crate::queries::audit_log::AuditLogSettingsNode;
+
+#[derive(InputObject)]
+pub struct AuditLogSettingsInput {
+    /// At least one day, null keeps entries forever
+    pub retention_days: Option<i32>,
+}
+
+pub fn update_audit_log_settings(
+    ctx: &Context<'_>,
+    input: AuditLogSettingsInput,
+) -> Result<AuditLogSettingsNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let settings = AuditLogSettings {
+        retention_days: input.retention_days,
+    };
+    match audit_log::update_audit_log_settings(&service_context, &settings) {
+        Ok(settings) => Ok(AuditLogSettingsNode { settings }),
+        Err(error) => {
+            let formatted_error = format!("{error:#?}");
+            let graphql_error = match error {
+                UpdateAuditLogSettingsError::InvalidRetentionDays => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                UpdateAuditLogSettingsError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
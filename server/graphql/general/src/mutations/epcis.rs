@@ -0,0 +1,151 @@
+use async_graphql::*;
+use chrono::NaiveDateTime;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, validate_rate_limit, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    epcis::{capture_epcis_document, EpcisExportError, EpcisPeriod, EpcisSettings},
+    rate_limit::RateLimitedOperation,
+};
+
+use crate::queries::epcis::EpcisSettingsNode;
+
+#[derive(InputObject)]
+pub struct EpcisSettingsInput {
+    pub capture_url: String,
+    /// Keeps the existing api key when not provided
+    pub api_key: Option<String>,
+    pub clear_api_key: Option<bool>,
+}
+
+pub fn update_epcis_settings(
+    ctx: &Context<'_>,
+    input: EpcisSettingsInput,
+) -> Result<EpcisSettingsNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+    let service = &service_provider.epcis_service;
+
+    let existing_api_key = service
+        .epcis_settings(&service_context)?
+        .and_then(|settings| settings.api_key);
+    let api_key = match (input.api_key, input.clear_api_key.unwrap_or(false)) {
+        (_, true) => None,
+        (Some(api_key), false) => Some(api_key),
+        (None, false) => existing_api_key,
+    };
+
+    let settings = EpcisSettings {
+        capture_url: input.capture_url,
+        api_key,
+    };
+    service.update_epcis_settings(&service_context, &settings)?;
+
+    Ok(EpcisSettingsNode { settings })
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+pub enum EpcisExportDestination {
+    /// JSON-LD file to download
+    File,
+    /// Post to the configured capture endpoint
+    CaptureEndpoint,
+}
+
+#[derive(InputObject)]
+pub struct ExportEpcisEventsInput {
+    pub from_datetime: NaiveDateTime,
+    pub to_datetime: NaiveDateTime,
+    pub destination: EpcisExportDestination,
+}
+
+#[derive(SimpleObject)]
+pub struct EpcisExportNode {
+    pub file_id: Option<String>,
+    /// Capture job returned by the capture endpoint
+    pub capture_id: Option<String>,
+}
+
+pub async fn export_epcis_events(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: ExportEpcisEventsInput,
+) -> Result<EpcisExportNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::Report,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::Export)?;
+
+    let period = EpcisPeriod {
+        from: input.from_datetime,
+        to: input.to_datetime,
+    };
+    let service_provider = ctx.service_provider();
+    let service = &service_provider.epcis_service;
+
+    let (settings, document) = {
+        let service_context = service_provider.context(store_id, user.user_id)?;
+
+        if input.destination == EpcisExportDestination::File {
+            let file_id = service
+                .export_epcis_to_file(
+                    &service_context,
+                    &ctx.get_settings().server.base_dir,
+                    &period,
+                )
+                .map_err(map_error)?;
+            return Ok(EpcisExportNode {
+                file_id: Some(file_id),
+                capture_id: None,
+            });
+        }
+
+        let settings = service
+            .epcis_settings(&service_context)?
+            .ok_or(EpcisExportError::CaptureEndpointNotConfigured)
+            .map_err(map_error)?;
+        let document = service
+            .generate_epcis_document(&service_context, &period)
+            .map_err(map_error)?;
+        (settings, document)
+    };
+
+    let result = capture_epcis_document(&settings, &document)
+        .await
+        .map_err(map_error)?;
+
+    Ok(EpcisExportNode {
+        file_id: None,
+        capture_id: result.capture_id,
+    })
+}
+
+fn map_error(error: EpcisExportError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        EpcisExportError::InvalidPeriod | EpcisExportError::CaptureEndpointNotConfigured => {
+            BadUserInput(formatted_error)
+        }
+        EpcisExportError::CaptureFailed(_)
+        | EpcisExportError::FileError(_)
+        | EpcisExportError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
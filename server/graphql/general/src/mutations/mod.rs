@@ -1,12 +1,17 @@
+pub mod api_key;
+pub mod audit_log;
 pub mod barcode;
 pub mod common;
 pub mod display_settings;
+pub mod epcis;
 pub mod initialise_site;
 pub mod insert_insurance;
 pub mod label_printer_settings;
 pub mod log;
 pub mod manual_sync;
+pub mod network_stock;
 pub mod sync_settings;
 pub mod update_insurance;
 pub mod update_name_properties;
 pub mod update_user;
+pub mod webhook;
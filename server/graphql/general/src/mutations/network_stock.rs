@@ -0,0 +1,104 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::InvoiceConnector;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    network_stock::{
+        CreateRedistributionShipmentsError, RedistributionSuggestion, SuggestedTransferLine,
+    },
+};
+
+#[derive(InputObject)]
+pub struct RedistributionTransferLineInput {
+    pub stock_line_id: String,
+    pub number_of_packs: f64,
+}
+
+#[derive(InputObject)]
+pub struct RedistributionTransferInput {
+    pub item_id: String,
+    pub from_store_id: String,
+    pub to_store_id: String,
+    pub lines: Vec<RedistributionTransferLineInput>,
+}
+
+/// Creates draft outbound shipments from the sending store for the accepted redistribution
+/// suggestions, one per receiving store. All shipments are created in one transaction, transfers
+/// sent from other stores are rejected
+pub fn create_redistribution_shipments(
+    ctx: &Context<'_>,
+    store_id: String,
+    transfers: Vec<RedistributionTransferInput>,
+) -> Result<InvoiceConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateOutboundShipment,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let shipments = service_provider
+        .network_stock_service
+        .create_redistribution_shipments(
+            &service_context,
+            transfers
+                .into_iter()
+                .map(RedistributionTransferInput::to_domain)
+                .collect(),
+        )
+        .map_err(map_error)?;
+
+    Ok(InvoiceConnector::from_vec(shipments))
+}
+
+impl RedistributionTransferInput {
+    fn to_domain(self) -> RedistributionSuggestion {
+        let RedistributionTransferInput {
+            item_id,
+            from_store_id,
+            to_store_id,
+            lines,
+        } = self;
+
+        RedistributionSuggestion {
+            item_id,
+            from_store_id,
+            to_store_id,
+            lines: lines
+                .into_iter()
+                .map(|line| SuggestedTransferLine {
+                    stock_line_id: line.stock_line_id,
+                    number_of_packs: line.number_of_packs,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+}
+
+fn map_error(error: CreateRedistributionShipmentsError) -> Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        CreateRedistributionShipmentsError::NotCentralServer
+        | CreateRedistributionShipmentsError::NothingToTransfer
+        | CreateRedistributionShipmentsError::NotFromThisStore
+        | CreateRedistributionShipmentsError::SendingStoreNotActiveOnSite
+        | CreateRedistributionShipmentsError::ReceivingStoreDoesNotExist(_)
+        | CreateRedistributionShipmentsError::OutboundShipmentError { .. }
+        | CreateRedistributionShipmentsError::StockOutLineError { .. } => {
+            BadUserInput(formatted_error)
+        }
+        CreateRedistributionShipmentsError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
@@ -25,7 +25,9 @@ pub fn update_name_properties(
     )?;
 
     let service_provider = ctx.service_provider();
-    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+    let service_context = service_provider
+        .context(store_id.to_string(), user.user_id)?
+        .with_audit_reason(input.reason.clone());
 
     match service_provider.name_service.update_name_properties(
         &service_context,
@@ -47,10 +49,18 @@ pub fn update_name_properties(
 pub struct UpdateNamePropertiesInput {
     pub id: String,
     pub properties: Option<String>,
+    /// Recorded in the audit log with the changed fields
+    pub reason: Option<String>,
 }
 
 impl From<UpdateNamePropertiesInput> for UpdateNameProperties {
-    fn from(UpdateNamePropertiesInput { id, properties }: UpdateNamePropertiesInput) -> Self {
+    fn from(
+        UpdateNamePropertiesInput {
+            id,
+            properties,
+            reason: _,
+        }: UpdateNamePropertiesInput,
+    ) -> Self {
         UpdateNameProperties { id, properties }
     }
 }
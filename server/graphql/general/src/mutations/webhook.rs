@@ -0,0 +1,171 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ActivityLogNodeType;
+use repository::{ActivityLogType, ChangelogTableName};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    webhook::{
+        DeleteWebhookError, ReplayWebhookError, RetryWebhookDeliveryError, UpsertWebhook,
+        UpsertWebhookError,
+    },
+};
+
+use crate::queries::webhook::{WebhookDeliveryNode, WebhookNode};
+
+#[derive(InputObject)]
+pub struct UpsertWebhookInput {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// Signs each delivery, required for new webhooks and kept when not provided
+    pub secret: Option<String>,
+    /// Activity log events to deliver, e.g. `INVOICE_STATUS_SHIPPED`
+    pub event_types: Vec<ActivityLogNodeType>,
+    /// Tables whose row changes are delivered, e.g. `Invoice`
+    pub table_names: Vec<String>,
+    pub is_active: bool,
+}
+
+fn validate_admin(ctx: &Context<'_>) -> Result<()> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+    Ok(())
+}
+
+pub fn upsert_webhook(ctx: &Context<'_>, input: UpsertWebhookInput) -> Result<WebhookNode> {
+    validate_admin(ctx)?;
+
+    let table_names = input
+        .table_names
+        .iter()
+        .map(|table_name| {
+            serde_json::from_value::<ChangelogTableName>(serde_json::json!(table_name)).map_err(
+                |_| {
+                    StandardGraphqlError::BadUserInput(format!("Unknown table {table_name}"))
+                        .extend()
+                },
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let webhook = match service_provider.webhook_service.upsert_webhook(
+        &service_context,
+        UpsertWebhook {
+            id: input.id,
+            name: input.name,
+            url: input.url,
+            secret: input.secret,
+            event_types: input
+                .event_types
+                .into_iter()
+                .map(ActivityLogType::from)
+                .collect(),
+            table_names,
+            is_active: input.is_active,
+        },
+    ) {
+        Ok(webhook) => webhook,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertWebhookError::NameCannotBeEmpty
+                | UpsertWebhookError::InvalidUrl
+                | UpsertWebhookError::SecretCannotBeEmpty
+                | UpsertWebhookError::NoEventsOrTables => BadUserInput(formatted_error),
+                UpsertWebhookError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(WebhookNode { webhook })
+}
+
+pub fn delete_webhook(ctx: &Context<'_>, id: String) -> Result<String> {
+    validate_admin(ctx)?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .webhook_service
+        .delete_webhook(&service_context, &id)
+    {
+        Ok(id) => Ok(id),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                DeleteWebhookError::WebhookDoesNotExist => BadUserInput(formatted_error),
+                DeleteWebhookError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn replay_webhook(ctx: &Context<'_>, id: String, from_cursor: u64) -> Result<WebhookNode> {
+    validate_admin(ctx)?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .webhook_service
+        .replay_webhook(&service_context, &id, from_cursor)
+    {
+        Ok(webhook) => Ok(WebhookNode { webhook }),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                ReplayWebhookError::WebhookDoesNotExist
+                | ReplayWebhookError::CursorAfterLatestChange => BadUserInput(formatted_error),
+                ReplayWebhookError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn retry_webhook_delivery(ctx: &Context<'_>, id: String) -> Result<WebhookDeliveryNode> {
+    validate_admin(ctx)?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .webhook_service
+        .retry_delivery(&service_context, &id)
+    {
+        Ok(delivery) => Ok(WebhookDeliveryNode { delivery }),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                RetryWebhookDeliveryError::DeliveryDoesNotExist
+                | RetryWebhookDeliveryError::DeliveryIsNotADeadLetter => {
+                    BadUserInput(formatted_error)
+                }
+                RetryWebhookDeliveryError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
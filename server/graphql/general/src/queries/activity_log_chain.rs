@@ -0,0 +1,133 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    activity_log_chain::{
+        verify_activity_log_chain, verify_activity_log_chain_with_central, ActivityLogChainBreak,
+        ActivityLogChainVerification, VerifyWithCentralError,
+    },
+    auth::{Resource, ResourceAccessRequest},
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::activity_log_chain::ActivityLogChainBreakType")]
+pub enum ActivityLogChainBreakTypeNode {
+    SequenceGap,
+    PreviousHashMismatch,
+    LogMissing,
+    LogAltered,
+    UnchainedLog,
+    AnchorMismatch,
+    ChainTruncated,
+}
+
+pub struct ActivityLogChainVerificationNode {
+    pub verification: ActivityLogChainVerification,
+}
+
+pub struct ActivityLogChainBreakNode {
+    pub chain_break: ActivityLogChainBreak,
+}
+
+#[Object]
+impl ActivityLogChainVerificationNode {
+    pub async fn store_id(&self) -> &str {
+        &self.verification.store_id
+    }
+
+    pub async fn is_intact(&self) -> bool {
+        self.verification.is_intact()
+    }
+
+    pub async fn links_checked(&self) -> i64 {
+        self.verification.links_checked
+    }
+
+    pub async fn anchors_checked(&self) -> i64 {
+        self.verification.anchors_checked
+    }
+
+    /// Sequence of the last link, null when the store has no chained activity logs
+    pub async fn head_sequence(&self) -> Option<i64> {
+        self.verification.head.as_ref().map(|head| head.sequence)
+    }
+
+    /// Hash of the last link
+    pub async fn head_hash(&self) -> Option<&str> {
+        self.verification
+            .head
+            .as_ref()
+            .map(|head| head.hash.as_str())
+    }
+
+    /// Where the chain breaks, in chain order
+    pub async fn breaks(&self) -> Vec<ActivityLogChainBreakNode> {
+        self.verification
+            .breaks
+            .iter()
+            .cloned()
+            .map(|chain_break| ActivityLogChainBreakNode { chain_break })
+            .collect()
+    }
+}
+
+#[Object]
+impl ActivityLogChainBreakNode {
+    pub async fn r#type(&self) -> ActivityLogChainBreakTypeNode {
+        ActivityLogChainBreakTypeNode::from(self.chain_break.r#type)
+    }
+
+    pub async fn sequence(&self) -> Option<i64> {
+        self.chain_break.sequence
+    }
+
+    pub async fn activity_log_id(&self) -> &Option<String> {
+        &self.chain_break.activity_log_id
+    }
+
+    pub async fn anchor_id(&self) -> &Option<String> {
+        &self.chain_break.anchor_id
+    }
+}
+
+pub(crate) async fn activity_log_chain_verification(
+    ctx: &Context<'_>,
+    store_id: String,
+    use_local_anchors: Option<bool>,
+) -> Result<ActivityLogChainVerificationNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLog,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    if use_local_anchors.unwrap_or(false) {
+        let connection = ctx.get_connection_manager().connection()?;
+        let verification = verify_activity_log_chain(&connection, &store_id)
+            .map_err(StandardGraphqlError::from_repository_error)?;
+        return Ok(ActivityLogChainVerificationNode { verification });
+    }
+
+    match verify_activity_log_chain_with_central(ctx.service_provider(), &store_id).await {
+        Ok(verification) => Ok(ActivityLogChainVerificationNode { verification }),
+        Err(error) => {
+            let formatted_error = format!("{error:#?}");
+            let graphql_error = match error {
+                VerifyWithCentralError::SyncNotConfigured => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                VerifyWithCentralError::CentralServerError(_)
+                | VerifyWithCentralError::SyncApiV5CreatingError(_)
+                | VerifyWithCentralError::SyncApiV6CreatingError(_)
+                | VerifyWithCentralError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            Err(graphql_error.extend())
+        }
+    }
+}
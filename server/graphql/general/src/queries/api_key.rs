@@ -0,0 +1,104 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    api_key::ApiKey,
+    auth::{Resource, ResourceAccessRequest},
+};
+
+pub struct ApiKeyNode {
+    pub api_key: ApiKey,
+}
+
+#[Object]
+impl ApiKeyNode {
+    pub async fn id(&self) -> &str {
+        &self.api_key.api_key_row.id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.api_key.api_key_row.name
+    }
+
+    /// Service account user the key authenticates as
+    pub async fn user_id(&self) -> &str {
+        &self.api_key.api_key_row.user_id
+    }
+
+    /// Start of the key, the key itself is never returned
+    pub async fn key_prefix(&self) -> &str {
+        &self.api_key.api_key_row.key_prefix
+    }
+
+    pub async fn resources(&self) -> Vec<String> {
+        self.api_key
+            .resources
+            .iter()
+            .map(Resource::to_string)
+            .collect()
+    }
+
+    pub async fn store_ids(&self) -> &Vec<String> {
+        &self.api_key.store_ids
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.api_key.api_key_row.created_datetime, Utc)
+    }
+
+    pub async fn expiry_datetime(&self) -> Option<DateTime<Utc>> {
+        self.api_key
+            .api_key_row
+            .expiry_datetime
+            .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn last_used_datetime(&self) -> Option<DateTime<Utc>> {
+        self.api_key
+            .api_key_row
+            .last_used_datetime
+            .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn revoked_datetime(&self) -> Option<DateTime<Utc>> {
+        self.api_key
+            .api_key_row
+            .revoked_datetime
+            .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ApiKeyConnector {
+    pub total_count: u32,
+    pub nodes: Vec<ApiKeyNode>,
+}
+
+pub(crate) fn api_keys(ctx: &Context<'_>) -> Result<ApiKeyConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let api_keys = service_provider
+        .api_key_service
+        .get_api_keys(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ApiKeyConnector {
+        total_count: api_keys.len() as u32,
+        nodes: api_keys
+            .into_iter()
+            .map(|api_key| ApiKeyNode { api_key })
+            .collect(),
+    })
+}
@@ -0,0 +1,148 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::AuditLogRow;
+use service::{
+    audit_log::{get_audit_log_settings, get_record_audit_log, AuditLogSettings},
+    auth::{Resource, ResourceAccessRequest},
+};
+
+/// Master data tables whose edits are audited
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AuditedTableNode {
+    Item,
+    ItemVariant,
+    Location,
+    Name,
+    Preference,
+    UserPermission,
+}
+
+impl AuditedTableNode {
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            AuditedTableNode::Item => "item",
+            AuditedTableNode::ItemVariant => "item_variant",
+            AuditedTableNode::Location => "location",
+            AuditedTableNode::Name => "name",
+            AuditedTableNode::Preference => "preference",
+            AuditedTableNode::UserPermission => "user_permission",
+        }
+    }
+}
+
+pub struct AuditLogNode {
+    pub row: AuditLogRow,
+}
+
+#[Object]
+impl AuditLogNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    /// Shared by the fields changed in the same edit
+    pub async fn change_id(&self) -> &str {
+        &self.row.change_id
+    }
+
+    pub async fn record_id(&self) -> &str {
+        &self.row.record_id
+    }
+
+    pub async fn field(&self) -> &str {
+        &self.row.field
+    }
+
+    /// JSON, except text which is returned as is. Null when the record was created or the field
+    /// was empty
+    pub async fn old_value(&self) -> &Option<String> {
+        &self.row.old_value
+    }
+
+    pub async fn new_value(&self) -> &Option<String> {
+        &self.row.new_value
+    }
+
+    pub async fn user_id(&self) -> &str {
+        &self.row.user_id
+    }
+
+    pub async fn store_id(&self) -> &Option<String> {
+        &self.row.store_id
+    }
+
+    pub async fn reason(&self) -> &Option<String> {
+        &self.row.reason
+    }
+
+    pub async fn datetime(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.row.datetime, Utc)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AuditLogConnector {
+    pub total_count: u32,
+    pub nodes: Vec<AuditLogNode>,
+}
+
+pub struct AuditLogSettingsNode {
+    pub settings: AuditLogSettings,
+}
+
+#[Object]
+impl AuditLogSettingsNode {
+    /// Entries older than this are deleted, null keeps entries forever
+    pub async fn retention_days(&self) -> Option<i32> {
+        self.settings.retention_days
+    }
+}
+
+/// Field changes of the record, newest first
+pub(crate) fn audit_logs(
+    ctx: &Context<'_>,
+    store_id: String,
+    table: AuditedTableNode,
+    record_id: String,
+) -> Result<AuditLogConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLog,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let rows = get_record_audit_log(&service_context, table.table_name(), &record_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(AuditLogConnector {
+        total_count: rows.len() as u32,
+        nodes: rows.into_iter().map(|row| AuditLogNode { row }).collect(),
+    })
+}
+
+pub(crate) fn audit_log_settings(ctx: &Context<'_>) -> Result<AuditLogSettingsNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let settings = get_audit_log_settings(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(AuditLogSettingsNode { settings })
+}
@@ -0,0 +1,184 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    demand_forecast::{
+        DemandForecastError, DemandForecastInput as ServiceInput, ForecastPoint,
+        ItemDemandForecast, MonthlyDemand,
+    },
+};
+
+type ServiceError = DemandForecastError;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::demand_forecast::ForecastModel")]
+pub enum ForecastModelType {
+    MovingAverage,
+    ExponentialSmoothing,
+    HoltWinters,
+}
+
+#[derive(InputObject)]
+pub struct DemandForecastInput {
+    pub item_ids: Vec<String>,
+    /// Defaults to the model with the lowest error on the item's history
+    pub model: Option<ForecastModelType>,
+    /// Defaults to 24
+    pub history_months: Option<u32>,
+    /// Defaults to 6
+    pub horizon_months: Option<u32>,
+}
+
+pub struct ItemDemandForecastNode {
+    pub forecast: ItemDemandForecast,
+}
+
+pub struct MonthlyDemandNode {
+    pub demand: MonthlyDemand,
+}
+
+pub struct ForecastPointNode {
+    pub point: ForecastPoint,
+}
+
+#[derive(SimpleObject)]
+pub struct DemandForecastConnector {
+    pub nodes: Vec<ItemDemandForecastNode>,
+}
+
+#[Object]
+impl ItemDemandForecastNode {
+    pub async fn item_id(&self) -> &str {
+        &self.forecast.item_id
+    }
+
+    /// Model used for the forecast
+    pub async fn model(&self) -> ForecastModelType {
+        ForecastModelType::from(self.forecast.model)
+    }
+
+    pub async fn history(&self) -> Vec<MonthlyDemandNode> {
+        self.forecast
+            .history
+            .iter()
+            .cloned()
+            .map(|demand| MonthlyDemandNode { demand })
+            .collect()
+    }
+
+    pub async fn forecast(&self) -> Vec<ForecastPointNode> {
+        self.forecast
+            .forecast
+            .iter()
+            .cloned()
+            .map(|point| ForecastPointNode { point })
+            .collect()
+    }
+}
+
+#[Object]
+impl MonthlyDemandNode {
+    pub async fn month(&self) -> NaiveDate {
+        self.demand.month
+    }
+
+    pub async fn consumption(&self) -> f64 {
+        self.demand.consumption
+    }
+
+    pub async fn days_out_of_stock(&self) -> f64 {
+        self.demand.days_out_of_stock
+    }
+
+    /// Consumption corrected for days out of stock, null when out of stock for the whole month
+    pub async fn adjusted_consumption(&self) -> Option<f64> {
+        self.demand.adjusted_consumption
+    }
+}
+
+#[Object]
+impl ForecastPointNode {
+    pub async fn month(&self) -> NaiveDate {
+        self.point.month
+    }
+
+    pub async fn quantity(&self) -> f64 {
+        self.point.quantity
+    }
+
+    /// Lower bound of the 95% confidence interval
+    pub async fn lower_bound(&self) -> f64 {
+        self.point.lower_bound
+    }
+
+    /// Upper bound of the 95% confidence interval
+    pub async fn upper_bound(&self) -> f64 {
+        self.point.upper_bound
+    }
+}
+
+pub fn demand_forecast(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: DemandForecastInput,
+) -> Result<DemandForecastConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::RequisitionChart,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let forecasts = service_provider
+        .demand_forecast_service
+        .get_demand_forecasts(&service_context, input.to_domain())
+        .map_err(map_error)?;
+
+    Ok(DemandForecastConnector {
+        nodes: forecasts
+            .into_iter()
+            .map(|forecast| ItemDemandForecastNode { forecast })
+            .collect(),
+    })
+}
+
+impl DemandForecastInput {
+    fn to_domain(self) -> ServiceInput {
+        let DemandForecastInput {
+            item_ids,
+            model,
+            history_months,
+            horizon_months,
+        } = self;
+        let default = ServiceInput::default();
+
+        ServiceInput {
+            item_ids,
+            model: model.map(Into::into),
+            history_months: history_months.unwrap_or(default.history_months),
+            horizon_months: horizon_months.unwrap_or(default.horizon_months),
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        ServiceError::InvalidHistoryMonths | ServiceError::InvalidHorizonMonths => {
+            BadUserInput(formatted_error)
+        }
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
@@ -0,0 +1,42 @@
+use async_graphql::*;
+use graphql_core::{standard_graphql_error::validate_auth, ContextExt};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    epcis::EpcisSettings,
+};
+
+pub struct EpcisSettingsNode {
+    pub settings: EpcisSettings,
+}
+
+#[Object]
+impl EpcisSettingsNode {
+    /// Capture endpoint of the EPCIS 2.0 repository events are posted to
+    pub async fn capture_url(&self) -> &str {
+        &self.settings.capture_url
+    }
+
+    /// Api key itself is never returned
+    pub async fn has_api_key(&self) -> bool {
+        self.settings.api_key.is_some()
+    }
+}
+
+pub(crate) fn epcis_settings(ctx: &Context<'_>) -> Result<Option<EpcisSettingsNode>> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let settings = service_provider
+        .epcis_service
+        .epcis_settings(&service_context)?;
+
+    Ok(settings.map(|settings| EpcisSettingsNode { settings }))
+}
@@ -0,0 +1,225 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::NaiveDate;
+use graphql_core::{
+    loader::{StockLineByIdLoader, StoreByIdLoader},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{StockLineNode, StoreNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    expiry_risk::{
+        ExpiryRiskAction, ExpiryRiskError, ExpiryRiskInput as ServiceInput, StockLineExpiryRisk,
+    },
+};
+
+type ServiceError = ExpiryRiskError;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExpiryRiskActionType {
+    PrioritiseInAllocation,
+    Redistribute,
+    ReturnToSupplier,
+}
+
+#[derive(InputObject)]
+pub struct ExpiryRiskInput {
+    pub item_ids: Option<Vec<String>>,
+    /// Only include stock expiring within this many months
+    pub expiring_within_months: Option<f64>,
+    /// Only include stock lines with quantity at risk, defaults to false
+    pub at_risk_only: Option<bool>,
+}
+
+pub struct StockLineExpiryRiskNode {
+    pub risk: StockLineExpiryRisk,
+}
+
+#[derive(SimpleObject)]
+pub struct StockLineExpiryRiskConnector {
+    pub nodes: Vec<StockLineExpiryRiskNode>,
+}
+
+#[Object]
+impl StockLineExpiryRiskNode {
+    pub async fn stock_line_id(&self) -> &str {
+        &self.risk.stock_line.id
+    }
+
+    pub async fn stock_line(&self, ctx: &Context<'_>) -> Result<Option<StockLineNode>> {
+        let loader = ctx.get_loader::<DataLoader<StockLineByIdLoader>>();
+        Ok(loader
+            .load_one(self.risk.stock_line.id.clone())
+            .await?
+            .map(StockLineNode::from_domain))
+    }
+
+    pub async fn store_id(&self) -> &str {
+        &self.risk.stock_line.store_id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.risk.item_id
+    }
+
+    pub async fn batch(&self) -> &Option<String> {
+        &self.risk.stock_line.batch
+    }
+
+    pub async fn expiry_date(&self) -> &Option<NaiveDate> {
+        &self.risk.stock_line.expiry_date
+    }
+
+    pub async fn average_monthly_consumption(&self) -> f64 {
+        self.risk.average_monthly_consumption
+    }
+
+    pub async fn quantity(&self) -> f64 {
+        self.risk.quantity
+    }
+
+    /// Units projected to be used before the expiry date at the current AMC
+    pub async fn projected_consumption(&self) -> f64 {
+        self.risk.projected_consumption
+    }
+
+    pub async fn quantity_at_risk(&self) -> f64 {
+        self.risk.quantity_at_risk
+    }
+
+    /// Quantity at risk at cost price
+    pub async fn value_at_risk(&self) -> f64 {
+        self.risk.value_at_risk
+    }
+
+    pub async fn suggested_action(&self) -> Option<ExpiryRiskActionType> {
+        self.risk
+            .suggested_action
+            .as_ref()
+            .map(|action| match action {
+                ExpiryRiskAction::PrioritiseInAllocation => {
+                    ExpiryRiskActionType::PrioritiseInAllocation
+                }
+                ExpiryRiskAction::Redistribute { .. } => ExpiryRiskActionType::Redistribute,
+                ExpiryRiskAction::ReturnToSupplier { .. } => ExpiryRiskActionType::ReturnToSupplier,
+            })
+    }
+
+    /// Store to send the stock to when the suggested action is to redistribute
+    pub async fn redistribute_to_store(&self, ctx: &Context<'_>) -> Result<Option<StoreNode>> {
+        let Some(ExpiryRiskAction::Redistribute { store_id, .. }) = &self.risk.suggested_action
+        else {
+            return Ok(None);
+        };
+
+        let loader = ctx.get_loader::<DataLoader<StoreByIdLoader>>();
+        Ok(loader
+            .load_one(store_id.clone())
+            .await?
+            .map(StoreNode::from_domain))
+    }
+
+    pub async fn redistribute_quantity(&self) -> Option<f64> {
+        match &self.risk.suggested_action {
+            Some(ExpiryRiskAction::Redistribute { quantity, .. }) => Some(*quantity),
+            _ => None,
+        }
+    }
+
+    /// Supplier to return the stock to when the suggested action is to return it
+    pub async fn return_to_supplier_id(&self) -> Option<&str> {
+        match &self.risk.suggested_action {
+            Some(ExpiryRiskAction::ReturnToSupplier { supplier_id }) => Some(supplier_id),
+            _ => None,
+        }
+    }
+}
+
+pub fn expiry_risk(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: ExpiryRiskInput,
+) -> Result<StockLineExpiryRiskConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let risks = service_provider
+        .expiry_risk_service
+        .get_expiry_risk(&service_context, input.to_domain())
+        .map_err(map_error)?;
+
+    Ok(StockLineExpiryRiskConnector::from_vec(risks))
+}
+
+pub fn network_expiry_risk(
+    ctx: &Context<'_>,
+    store_ids: Option<Vec<String>>,
+    input: ExpiryRiskInput,
+) -> Result<StockLineExpiryRiskConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let risks = service_provider
+        .expiry_risk_service
+        .get_network_expiry_risk(&service_context, store_ids, input.to_domain())
+        .map_err(map_error)?;
+
+    Ok(StockLineExpiryRiskConnector::from_vec(risks))
+}
+
+impl StockLineExpiryRiskConnector {
+    fn from_vec(risks: Vec<StockLineExpiryRisk>) -> Self {
+        StockLineExpiryRiskConnector {
+            nodes: risks
+                .into_iter()
+                .map(|risk| StockLineExpiryRiskNode { risk })
+                .collect(),
+        }
+    }
+}
+
+impl ExpiryRiskInput {
+    fn to_domain(self) -> ServiceInput {
+        let ExpiryRiskInput {
+            item_ids,
+            expiring_within_months,
+            at_risk_only,
+        } = self;
+
+        ServiceInput {
+            item_ids,
+            expiring_within_months,
+            at_risk_only: at_risk_only.unwrap_or(false),
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        ServiceError::NotCentralServer => BadUserInput(formatted_error),
+        ServiceError::PluginError(_) | ServiceError::DatabaseError(_) => {
+            InternalError(formatted_error)
+        }
+    };
+
+    graphql_error.extend()
+}
@@ -5,7 +5,7 @@ use graphql_core::{
     loader::{InvoiceByIdLoader, UserLoader},
     map_filter,
     pagination::PaginationInput,
-    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    standard_graphql_error::{validate_auth, validate_rate_limit, StandardGraphqlError},
     ContextExt,
 };
 
@@ -21,6 +21,7 @@ use repository::{
 use service::{
     auth::{Resource, ResourceAccessRequest},
     ledger::get_item_ledger,
+    rate_limit::RateLimitedOperation,
     ListResult,
 };
 
@@ -157,13 +158,14 @@ pub fn item_ledger(
     page: Option<PaginationInput>,
     filter: Option<ItemLedgerFilterInput>,
 ) -> Result<ItemLedgerResponse> {
-    validate_auth(
+    let user = validate_auth(
         ctx,
         &ResourceAccessRequest {
             resource: Resource::QueryItems,
             store_id: Some(store_id.clone()),
         },
     )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::ItemLedger)?;
 
     let ledger = get_item_ledger(
         &ctx.get_connection_manager().connection()?,
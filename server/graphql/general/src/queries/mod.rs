@@ -30,12 +30,14 @@ pub mod store;
 pub use self::store::*;
 pub mod activity_log;
 pub use self::activity_log::*;
+pub mod activity_log_chain;
 pub mod database_settings;
 pub use self::database_settings::*;
 pub mod display_settings;
 pub mod initialisation_status;
 pub mod migration_status;
 pub mod name_property;
+pub mod network_stock;
 pub use self::name_property::*;
 pub mod requisition_line_chart;
 pub mod response_requisition_line_stats;
@@ -55,10 +57,16 @@ pub use self::log::*;
 pub mod last_successful_user_sync;
 pub use self::last_successful_user_sync::*;
 pub use self::plugin::*;
+pub mod api_key;
+pub mod audit_log;
 pub mod currency;
+pub mod demand_forecast;
+pub mod epcis;
+pub mod expiry_risk;
 pub mod label_printer_settings;
 pub use self::label_printer_settings::*;
 pub mod pricing;
+pub mod webhook;
 pub use self::pricing::*;
 pub mod reason_option;
 pub use self::reason_option::*;
@@ -77,6 +85,7 @@ pub mod diagnosis;
 pub mod insurance_policies;
 pub mod insurance_providers;
 pub mod shipping_method;
+pub mod site_health;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,232 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    network_stock::{
+        NetworkStockError, NetworkStockInput as ServiceInput, RedistributionSuggestion,
+        StoreItemStock, SuggestedTransferLine,
+    },
+};
+
+type ServiceError = NetworkStockError;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::network_stock::StockStatus")]
+pub enum StockStatusType {
+    Understocked,
+    Ok,
+    Overstocked,
+}
+
+#[derive(InputObject)]
+pub struct NetworkStockInput {
+    pub item_ids: Vec<String>,
+    /// Defaults to all active stores
+    pub store_ids: Option<Vec<String>>,
+}
+
+pub struct StoreItemStockNode {
+    pub stock: StoreItemStock,
+}
+
+pub struct RedistributionSuggestionNode {
+    pub suggestion: RedistributionSuggestion,
+}
+
+pub struct SuggestedTransferLineNode {
+    pub line: SuggestedTransferLine,
+}
+
+#[derive(SimpleObject)]
+pub struct NetworkStockConnector {
+    pub nodes: Vec<StoreItemStockNode>,
+}
+
+#[derive(SimpleObject)]
+pub struct RedistributionSuggestionConnector {
+    pub nodes: Vec<RedistributionSuggestionNode>,
+}
+
+#[Object]
+impl StoreItemStockNode {
+    pub async fn store_id(&self) -> &str {
+        &self.stock.store_id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.stock.item_id
+    }
+
+    pub async fn available_stock_on_hand(&self) -> f64 {
+        self.stock.available_stock_on_hand
+    }
+
+    pub async fn average_monthly_consumption(&self) -> f64 {
+        self.stock.average_monthly_consumption
+    }
+
+    /// Null when the item has no consumption in the store
+    pub async fn months_of_stock(&self) -> Option<f64> {
+        self.stock.months_of_stock
+    }
+
+    pub async fn low_stock_threshold(&self) -> f64 {
+        self.stock.low_stock_threshold
+    }
+
+    pub async fn over_stock_threshold(&self) -> f64 {
+        self.stock.over_stock_threshold
+    }
+
+    pub async fn status(&self) -> StockStatusType {
+        StockStatusType::from(self.stock.status)
+    }
+}
+
+#[Object]
+impl RedistributionSuggestionNode {
+    pub async fn item_id(&self) -> &str {
+        &self.suggestion.item_id
+    }
+
+    pub async fn from_store_id(&self) -> &str {
+        &self.suggestion.from_store_id
+    }
+
+    pub async fn to_store_id(&self) -> &str {
+        &self.suggestion.to_store_id
+    }
+
+    pub async fn quantity(&self) -> f64 {
+        self.suggestion.quantity()
+    }
+
+    /// Units that would otherwise expire in the sending store
+    pub async fn quantity_at_risk(&self) -> f64 {
+        self.suggestion.quantity_at_risk()
+    }
+
+    pub async fn lines(&self) -> Vec<SuggestedTransferLineNode> {
+        self.suggestion
+            .lines
+            .iter()
+            .cloned()
+            .map(|line| SuggestedTransferLineNode { line })
+            .collect()
+    }
+}
+
+#[Object]
+impl SuggestedTransferLineNode {
+    pub async fn stock_line_id(&self) -> &str {
+        &self.line.stock_line_id
+    }
+
+    pub async fn batch(&self) -> &Option<String> {
+        &self.line.batch
+    }
+
+    pub async fn expiry_date(&self) -> &Option<NaiveDate> {
+        &self.line.expiry_date
+    }
+
+    pub async fn pack_size(&self) -> f64 {
+        self.line.pack_size
+    }
+
+    pub async fn number_of_packs(&self) -> f64 {
+        self.line.number_of_packs
+    }
+
+    pub async fn quantity_at_risk(&self) -> f64 {
+        self.line.quantity_at_risk
+    }
+}
+
+pub fn network_stock_levels(
+    ctx: &Context<'_>,
+    input: NetworkStockInput,
+) -> Result<NetworkStockConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let stock_levels = service_provider
+        .network_stock_service
+        .get_network_stock_levels(&service_context, input.to_domain())
+        .map_err(map_error)?;
+
+    Ok(NetworkStockConnector {
+        nodes: stock_levels
+            .into_iter()
+            .map(|stock| StoreItemStockNode { stock })
+            .collect(),
+    })
+}
+
+pub fn redistribution_suggestions(
+    ctx: &Context<'_>,
+    input: NetworkStockInput,
+) -> Result<RedistributionSuggestionConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let suggestions = service_provider
+        .network_stock_service
+        .get_redistribution_suggestions(&service_context, input.to_domain())
+        .map_err(map_error)?;
+
+    Ok(RedistributionSuggestionConnector {
+        nodes: suggestions
+            .into_iter()
+            .map(|suggestion| RedistributionSuggestionNode { suggestion })
+            .collect(),
+    })
+}
+
+impl NetworkStockInput {
+    fn to_domain(self) -> ServiceInput {
+        let NetworkStockInput {
+            item_ids,
+            store_ids,
+        } = self;
+
+        ServiceInput {
+            item_ids,
+            store_ids,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        ServiceError::NotCentralServer => BadUserInput(formatted_error),
+        ServiceError::PluginError(_) | ServiceError::DatabaseError(_) => {
+            InternalError(formatted_error)
+        }
+    };
+
+    graphql_error.extend()
+}
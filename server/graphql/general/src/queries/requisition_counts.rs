@@ -1,9 +1,13 @@
 use async_graphql::*;
+use chrono::{DateTime, Utc};
 use graphql_core::{
     standard_graphql_error::{validate_auth, StandardGraphqlError},
     ContextExt,
 };
-use service::auth::{Resource, ResourceAccessRequest};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    dashboard::requisition_count::ProgramEmergencyRequisitionCount,
+};
 
 pub struct RequisitionCounts {
     store_id: String,
@@ -21,6 +25,25 @@ pub struct EmergencyResponseRequisitionCounts {
     store_id: String,
 }
 
+pub struct ProgramEmergencyRequisitionCountNode {
+    count: ProgramEmergencyRequisitionCount,
+}
+
+#[Object]
+impl ProgramEmergencyRequisitionCountNode {
+    async fn program_id(&self) -> &str {
+        &self.count.program.id
+    }
+
+    async fn program_name(&self) -> &str {
+        &self.count.program.name
+    }
+
+    async fn count(&self) -> i64 {
+        self.count.count
+    }
+}
+
 #[Object]
 impl ResponseRequisitionCounts {
     async fn new(&self, ctx: &Context<'_>) -> Result<i64> {
@@ -61,6 +84,29 @@ impl EmergencyResponseRequisitionCounts {
 
         Ok(count)
     }
+
+    /// Emergency orders received per program since `createdAfter`, regardless of status
+    async fn by_program(
+        &self,
+        ctx: &Context<'_>,
+        created_after: DateTime<Utc>,
+    ) -> Result<Vec<ProgramEmergencyRequisitionCountNode>> {
+        let service_provider = ctx.service_provider();
+        let service_ctx = service_provider.context(self.store_id.clone(), "".to_string())?;
+        let service = &service_provider.requisition_count_service;
+        let counts = service
+            .emergency_response_requisition_count_by_program(
+                &service_ctx,
+                &self.store_id,
+                created_after.naive_utc(),
+            )
+            .map_err(StandardGraphqlError::from)?;
+
+        Ok(counts
+            .into_iter()
+            .map(|count| ProgramEmergencyRequisitionCountNode { count })
+            .collect())
+    }
 }
 
 #[Object]
@@ -0,0 +1,168 @@
+use async_graphql::*;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    site_health::{get_site_health, SiteHealth},
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::site_health::SiteHealthIssue")]
+pub enum SiteHealthIssueNode {
+    NotReporting,
+    LowFreeDisk,
+    BackupFailed,
+    BackupOverdue,
+    ProcessorErrors,
+    PendingChangelogs,
+    ClockOffset,
+}
+
+pub struct SiteHealthNode {
+    pub site_health: SiteHealth,
+}
+
+fn to_utc(datetime: NaiveDateTime) -> DateTime<Utc> {
+    DateTime::from_naive_utc_and_offset(datetime, Utc)
+}
+
+#[Object]
+impl SiteHealthNode {
+    pub async fn site_id(&self) -> i32 {
+        self.site_health.site_id
+    }
+
+    pub async fn store_codes(&self) -> &Vec<String> {
+        &self.site_health.store_codes
+    }
+
+    /// Empty when the site is healthy
+    pub async fn issues(&self) -> Vec<SiteHealthIssueNode> {
+        self.site_health
+            .issues
+            .iter()
+            .map(|issue| SiteHealthIssueNode::from(*issue))
+            .collect()
+    }
+
+    pub async fn app_version(&self) -> Option<&str> {
+        self.site_health
+            .report
+            .as_ref()
+            .map(|report| report.app_version.as_str())
+    }
+
+    /// When the last health report was received
+    pub async fn reported_datetime(&self) -> Option<DateTime<Utc>> {
+        self.site_health
+            .report
+            .as_ref()
+            .map(|report| to_utc(report.received_datetime))
+    }
+
+    /// Site clock minus central server clock, at the last report
+    pub async fn clock_offset_seconds(&self) -> Option<i64> {
+        self.site_health
+            .report
+            .as_ref()
+            .map(|report| report.clock_offset_seconds)
+    }
+
+    pub async fn database_size_bytes(&self) -> Option<i64> {
+        self.site_health
+            .report
+            .as_ref()
+            .and_then(|report| report.database_size_bytes)
+    }
+
+    pub async fn free_disk_bytes(&self) -> Option<i64> {
+        self.site_health
+            .report
+            .as_ref()
+            .and_then(|report| report.free_disk_bytes)
+    }
+
+    /// Processor errors over the last 24 hours
+    pub async fn processor_error_count(&self) -> Option<i64> {
+        self.site_health
+            .report
+            .as_ref()
+            .map(|report| report.processor_error_count)
+    }
+
+    pub async fn last_processor_error(&self) -> Option<&str> {
+        self.site_health
+            .report
+            .as_ref()
+            .and_then(|report| report.last_processor_error.as_deref())
+    }
+
+    pub async fn last_processor_error_datetime(&self) -> Option<DateTime<Utc>> {
+        self.site_health
+            .report
+            .as_ref()
+            .and_then(|report| report.last_processor_error_datetime)
+            .map(to_utc)
+    }
+
+    /// Records waiting to be pushed to central server
+    pub async fn pending_changelog_count(&self) -> Option<i64> {
+        self.site_health
+            .report
+            .as_ref()
+            .map(|report| report.pending_changelog_count)
+    }
+
+    pub async fn last_backup_attempt_datetime(&self) -> Option<DateTime<Utc>> {
+        self.site_health
+            .backup_status
+            .as_ref()
+            .map(|status| to_utc(status.last_attempt_datetime))
+    }
+
+    pub async fn last_backup_success_datetime(&self) -> Option<DateTime<Utc>> {
+        self.site_health
+            .backup_status
+            .as_ref()
+            .and_then(|status| status.last_success_datetime)
+            .map(to_utc)
+    }
+
+    pub async fn last_backup_error(&self) -> Option<&str> {
+        self.site_health
+            .backup_status
+            .as_ref()
+            .and_then(|status| status.error.as_deref())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SiteHealthConnector {
+    pub total_count: u32,
+    pub nodes: Vec<SiteHealthNode>,
+}
+
+pub(crate) fn site_health(ctx: &Context<'_>) -> Result<SiteHealthConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let connection = ctx.get_connection_manager().connection()?;
+    let site_health =
+        get_site_health(&connection).map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(SiteHealthConnector {
+        total_count: site_health.len() as u32,
+        nodes: site_health
+            .into_iter()
+            .map(|site_health| SiteHealthNode { site_health })
+            .collect(),
+    })
+}
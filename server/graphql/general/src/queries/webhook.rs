@@ -0,0 +1,196 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ActivityLogNodeType;
+use repository::{WebhookDeliveryRow, WebhookDeliveryStatus};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    webhook::Webhook,
+};
+
+pub struct WebhookNode {
+    pub webhook: Webhook,
+}
+
+#[Object]
+impl WebhookNode {
+    pub async fn id(&self) -> &str {
+        &self.webhook.webhook_row.id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.webhook.webhook_row.name
+    }
+
+    pub async fn url(&self) -> &str {
+        &self.webhook.webhook_row.url
+    }
+
+    /// Activity log events delivered, the secret itself is never returned
+    pub async fn event_types(&self) -> Vec<ActivityLogNodeType> {
+        self.webhook
+            .event_types
+            .iter()
+            .cloned()
+            .map(ActivityLogNodeType::from)
+            .collect()
+    }
+
+    /// Tables whose row changes are delivered
+    pub async fn table_names(&self) -> Vec<String> {
+        self.webhook
+            .table_names
+            .iter()
+            .filter_map(|table_name| serde_json::to_value(table_name).ok())
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect()
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.webhook.webhook_row.is_active
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.webhook.webhook_row.created_datetime, Utc)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WebhookConnector {
+    pub total_count: u32,
+    pub nodes: Vec<WebhookNode>,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::WebhookDeliveryStatus")]
+pub enum WebhookDeliveryStatusNode {
+    Pending,
+    Delivered,
+    DeadLetter,
+}
+
+pub struct WebhookDeliveryNode {
+    pub delivery: WebhookDeliveryRow,
+}
+
+#[Object]
+impl WebhookDeliveryNode {
+    pub async fn id(&self) -> &str {
+        &self.delivery.id
+    }
+
+    pub async fn webhook_id(&self) -> &str {
+        &self.delivery.webhook_id
+    }
+
+    pub async fn changelog_cursor(&self) -> i64 {
+        self.delivery.changelog_cursor
+    }
+
+    pub async fn event(&self) -> &str {
+        &self.delivery.event
+    }
+
+    /// JSON body posted to the webhook
+    pub async fn payload(&self) -> &str {
+        &self.delivery.payload
+    }
+
+    pub async fn status(&self) -> WebhookDeliveryStatusNode {
+        WebhookDeliveryStatusNode::from(self.delivery.status)
+    }
+
+    pub async fn attempts(&self) -> i32 {
+        self.delivery.attempts
+    }
+
+    pub async fn next_attempt_datetime(&self) -> Option<DateTime<Utc>> {
+        self.delivery
+            .next_attempt_datetime
+            .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc))
+    }
+
+    pub async fn last_error(&self) -> &Option<String> {
+        &self.delivery.last_error
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.delivery.created_datetime, Utc)
+    }
+
+    pub async fn delivered_datetime(&self) -> Option<DateTime<Utc>> {
+        self.delivery
+            .delivered_datetime
+            .map(|datetime| DateTime::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WebhookDeliveryConnector {
+    pub total_count: u32,
+    pub nodes: Vec<WebhookDeliveryNode>,
+}
+
+pub(crate) fn webhooks(ctx: &Context<'_>) -> Result<WebhookConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let webhooks = service_provider
+        .webhook_service
+        .get_webhooks(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(WebhookConnector {
+        total_count: webhooks.len() as u32,
+        nodes: webhooks
+            .into_iter()
+            .map(|webhook| WebhookNode { webhook })
+            .collect(),
+    })
+}
+
+/// Newest first, dead letters are the deliveries that gave up
+pub(crate) fn webhook_deliveries(
+    ctx: &Context<'_>,
+    status: WebhookDeliveryStatusNode,
+    webhook_id: Option<String>,
+) -> Result<WebhookDeliveryConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    let deliveries = service_provider
+        .webhook_service
+        .get_deliveries(
+            &service_context,
+            WebhookDeliveryStatus::from(status),
+            webhook_id.as_deref(),
+        )
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(WebhookDeliveryConnector {
+        total_count: deliveries.len() as u32,
+        nodes: deliveries
+            .into_iter()
+            .map(|delivery| WebhookDeliveryNode { delivery })
+            .collect(),
+    })
+}
@@ -1,19 +1,24 @@
 mod initialisation_status;
+mod store_event;
 mod sync_info;
 
 use actix_web::web::Data;
 use async_graphql::*;
 use futures::stream::Stream;
 use graphql_core::standard_graphql_error::validate_auth;
+use graphql_types::types::{InvoiceNode, RequisitionNode, StockLineNode};
+use repository::{InvoiceRow, InvoiceType};
 use service::{
     auth::{Resource, ResourceAccessRequest},
-    subscription::ResolvedSubscription,
+    subscription::{ResolvedSubscription, StoreEvent},
 };
 use tokio::sync::broadcast;
 
 use crate::queries::initialisation_status::InitialisationStatusNode;
 
 use initialisation_status::initialisation_status_stream;
+use store_event::store_event_stream;
+pub use store_event::{InvoiceChangeNodeType, InvoiceChangedNode, TemperatureBreachCreatedNode};
 use sync_info::{sync_info_stream, SyncInfoUpdatedNode};
 
 fn get_subscription_broadcast(
@@ -59,6 +64,176 @@ impl SyncStatusSubscriptions {
     }
 }
 
+// ── Store event subscriptions (authenticated, same permissions as the matching queries) ──
+
+fn validate_store_auth(ctx: &Context<'_>, resource: Resource, store_id: &str) -> Result<()> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    Ok(())
+}
+
+/// Resource needed to query the invoice, matches the resource the invoice queries check for its type
+fn invoice_query_resource(invoice: &InvoiceRow) -> Resource {
+    match invoice.r#type {
+        InvoiceType::OutboundShipment => Resource::QueryOutboundShipment,
+        InvoiceType::InboundShipment if invoice.purchase_order_id.is_some() => {
+            Resource::QueryInboundShipmentExternal
+        }
+        InvoiceType::InboundShipment => Resource::QueryInboundShipment,
+        InvoiceType::Prescription => Resource::QueryPrescription,
+        InvoiceType::SupplierReturn => Resource::QuerySupplierReturn,
+        InvoiceType::CustomerReturn => Resource::QueryCustomerReturn,
+        InvoiceType::InventoryAddition | InvoiceType::InventoryReduction | InvoiceType::Repack => {
+            Resource::QueryInvoice
+        }
+    }
+}
+
+/// Invoice query resources the user has in the store, errors if the user can't query any invoices
+fn permitted_invoice_resources(ctx: &Context<'_>, store_id: &str) -> Result<Vec<Resource>> {
+    let resources = [
+        Resource::QueryOutboundShipment,
+        Resource::QueryInboundShipment,
+        Resource::QueryInboundShipmentExternal,
+        Resource::QueryPrescription,
+        Resource::QuerySupplierReturn,
+        Resource::QueryCustomerReturn,
+        Resource::QueryInvoice,
+    ];
+
+    let mut permitted = Vec::new();
+    let mut denied = None;
+    for resource in resources {
+        match validate_store_auth(ctx, resource.clone(), store_id) {
+            Ok(()) => permitted.push(resource),
+            Err(error) => denied = denied.or(Some(error)),
+        }
+    }
+
+    match denied {
+        Some(error) if permitted.is_empty() => Err(error),
+        _ => Ok(permitted),
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct StoreEventSubscriptions;
+
+#[Subscription]
+impl StoreEventSubscriptions {
+    /// Invoice of the store created or its status changed, only invoices of the types the user
+    /// can query are streamed
+    async fn invoice_changed(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<impl Stream<Item = InvoiceChangedNode>> {
+        let permitted_resources = permitted_invoice_resources(ctx, &store_id)?;
+        Ok(store_event_stream(
+            get_subscription_broadcast(ctx)?,
+            store_id,
+            move |event| {
+                let (r#type, invoice) = match event {
+                    StoreEvent::InvoiceCreated(invoice)
+                    | StoreEvent::InboundShipmentFromTransfer(invoice) => {
+                        (InvoiceChangeNodeType::Created, invoice)
+                    }
+                    StoreEvent::InvoiceStatusChanged(invoice) => {
+                        (InvoiceChangeNodeType::StatusChanged, invoice)
+                    }
+                    _ => return None,
+                };
+                if !permitted_resources.contains(&invoice_query_resource(&invoice.invoice_row)) {
+                    return None;
+                }
+                Some(InvoiceChangedNode {
+                    r#type,
+                    invoice: InvoiceNode::from_domain(invoice),
+                })
+            },
+        ))
+    }
+
+    /// Inbound shipment created in the store from another store's outbound shipment
+    async fn inbound_shipment_from_transfer(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<impl Stream<Item = InvoiceNode>> {
+        validate_store_auth(ctx, Resource::QueryInboundShipment, &store_id)?;
+        Ok(store_event_stream(
+            get_subscription_broadcast(ctx)?,
+            store_id,
+            |event| match event {
+                StoreEvent::InboundShipmentFromTransfer(invoice) => {
+                    Some(InvoiceNode::from_domain(invoice))
+                }
+                _ => None,
+            },
+        ))
+    }
+
+    /// Response requisition created in the store from another store's request requisition
+    async fn requisition_received(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<impl Stream<Item = RequisitionNode>> {
+        validate_store_auth(ctx, Resource::QueryRequisition, &store_id)?;
+        Ok(store_event_stream(
+            get_subscription_broadcast(ctx)?,
+            store_id,
+            |event| match event {
+                StoreEvent::RequisitionReceived(requisition) => {
+                    Some(RequisitionNode::from_domain(requisition))
+                }
+                _ => None,
+            },
+        ))
+    }
+
+    async fn stock_line_changed(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<impl Stream<Item = StockLineNode>> {
+        validate_store_auth(ctx, Resource::QueryStockLine, &store_id)?;
+        Ok(store_event_stream(
+            get_subscription_broadcast(ctx)?,
+            store_id,
+            |event| match event {
+                StoreEvent::StockLineChanged(stock_line) => {
+                    Some(StockLineNode::from_domain(stock_line))
+                }
+                _ => None,
+            },
+        ))
+    }
+
+    async fn temperature_breach_created(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<impl Stream<Item = TemperatureBreachCreatedNode>> {
+        validate_store_auth(ctx, Resource::QueryTemperatureBreach, &store_id)?;
+        Ok(store_event_stream(
+            get_subscription_broadcast(ctx)?,
+            store_id,
+            |event| match event {
+                StoreEvent::TemperatureBreachCreated(temperature_breach) => {
+                    Some(TemperatureBreachCreatedNode { temperature_breach })
+                }
+                _ => None,
+            },
+        ))
+    }
+}
+
 // ── Initialisation subscriptions (no auth) ──
 
 #[derive(Default, Clone)]
@@ -0,0 +1,79 @@
+use actix_web::web::Data;
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream};
+use graphql_types::types::InvoiceNode;
+use repository::TemperatureBreach;
+use service::subscription::{ResolvedSubscription, StoreEvent};
+use tokio::sync::broadcast;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InvoiceChangeNodeType {
+    Created,
+    StatusChanged,
+}
+
+#[derive(SimpleObject)]
+pub struct InvoiceChangedNode {
+    pub r#type: InvoiceChangeNodeType,
+    pub invoice: InvoiceNode,
+}
+
+/// Newly detected breach, query `temperatureBreaches` for the full breach
+pub struct TemperatureBreachCreatedNode {
+    pub temperature_breach: TemperatureBreach,
+}
+
+#[Object]
+impl TemperatureBreachCreatedNode {
+    pub async fn id(&self) -> &str {
+        &self.temperature_breach.temperature_breach_row.id
+    }
+
+    pub async fn sensor_id(&self) -> &str {
+        &self.temperature_breach.temperature_breach_row.sensor_id
+    }
+
+    pub async fn location_id(&self) -> &Option<String> {
+        &self.temperature_breach.temperature_breach_row.location_id
+    }
+
+    pub async fn start_datetime(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(
+            self.temperature_breach
+                .temperature_breach_row
+                .start_datetime,
+            Utc,
+        )
+    }
+}
+
+/// Streams the store events of `store_id` that `map` resolves into a node
+pub fn store_event_stream<T, F>(
+    broadcast: Data<broadcast::Sender<ResolvedSubscription>>,
+    store_id: String,
+    map: F,
+) -> impl Stream<Item = T>
+where
+    F: Fn(StoreEvent) -> Option<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let rx = broadcast.subscribe();
+
+    stream::unfold((rx, map), move |(mut rx, map)| {
+        let store_id = store_id.clone();
+        async move {
+            loop {
+                match rx.recv().await {
+                    Ok(ResolvedSubscription::StoreEvent(event)) if event.store_id() == store_id => {
+                        if let Some(node) = map(event) {
+                            return Some((node, (rx, map)));
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                    _ => continue,
+                }
+            }
+        }
+    })
+}
@@ -9,7 +9,9 @@ use graphql_core::generic_inputs::{InboundShipmentType, TaxInput};
 use graphql_core::simple_generic_errors::{
     CannotEditInvoice, OtherPartyNotASupplier, OtherPartyNotVisible,
 };
-use graphql_core::simple_generic_errors::{CannotReverseInvoiceStatus, RecordNotFound};
+use graphql_core::simple_generic_errors::{
+    CannotReverseInvoiceStatus, PluginValidationFailed, RecordNotFound,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 use graphql_types::types::InvoiceNode;
@@ -94,11 +96,19 @@ pub fn update(
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(service_provider.invoice_service.update_inbound_shipment(
+    let result = service_provider.invoice_service.update_inbound_shipment(
         &service_context,
         input.to_domain(),
         r#type.to_domain(),
-    ))
+    );
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(invoice) => {
+            UpdateResponse::Response(invoice.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 #[derive(Interface)]
@@ -113,6 +123,7 @@ pub enum UpdateErrorInterface {
     CannotChangeStatusOfInvoiceOnHold(CannotChangeStatusOfInvoiceOnHold),
     CannotReceiveWithPendingLines(CannotReceiveWithPendingLines),
     CannotIssueForeignCurrencyForInternalSuppliers(CannotIssueInForeignCurrency),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl UpdateInput {
@@ -215,6 +226,11 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
                 ),
             )
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         ServiceError::CannotUpdateStatusAndDonorAtTheSameTime
         | ServiceError::NotThisStoreInvoice
@@ -230,6 +246,7 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
             BadUserInput(formatted_error)
         }
         ServiceError::PreferenceError(_) => InternalError(formatted_error),
+        ServiceError::PluginError(_) => InternalError(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::UpdatedInvoiceDoesNotExist => InternalError(formatted_error),
     };
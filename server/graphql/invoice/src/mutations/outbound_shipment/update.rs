@@ -1,7 +1,9 @@
 use async_graphql::*;
 use chrono::{DateTime, NaiveDate, Utc};
 use graphql_core::generic_inputs::{NullableUpdateInput, TaxInput};
-use graphql_core::simple_generic_errors::{CannotReverseInvoiceStatus, NodeError, RecordNotFound};
+use graphql_core::simple_generic_errors::{
+    CannotReverseInvoiceStatus, NodeError, PluginValidationFailed, RecordNotFound,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 use graphql_types::types::{InvoiceLineConnector, InvoiceNode};
@@ -75,11 +77,17 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_service
-            .update_outbound_shipment(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .invoice_service
+        .update_outbound_shipment(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(invoice) => {
+            UpdateResponse::Response(invoice.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<UpdateResponse> {
@@ -106,6 +114,7 @@ pub enum UpdateErrorInterface {
     CannotHaveEstimatedDeliveryDateBeforeShippedDate(
         CannotHaveEstimatedDeliveryDateBeforeShippedDate,
     ),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl UpdateInput {
@@ -193,6 +202,11 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
                 ),
             )
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         ServiceError::NotAnOutboundShipment => BadUserInput(formatted_error),
         ServiceError::NotThisStoreInvoice => BadUserInput(formatted_error),
@@ -202,6 +216,7 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::ExceedsMaximumBackdatingDays => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::PreferenceError(_) => InternalError(formatted_error),
+        ServiceError::PluginError(_) => InternalError(formatted_error),
         ServiceError::InvoiceLineHasNoStockLine(_) => InternalError(formatted_error),
         ServiceError::UpdatedInvoiceDoesNotExist => InternalError(formatted_error),
     };
@@ -2,7 +2,8 @@ use async_graphql::*;
 use chrono::{DateTime, Utc};
 use graphql_core::generic_inputs::NullableUpdateInput;
 use graphql_core::simple_generic_errors::{
-    CannotReverseInvoiceStatus, InvalidStockSelection, NodeError, RecordNotFound,
+    CannotReverseInvoiceStatus, InvalidStockSelection, NodeError, PluginValidationFailed,
+    RecordNotFound,
 };
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
@@ -69,11 +70,17 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_service
-            .update_prescription(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .invoice_service
+        .update_prescription(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(invoice) => {
+            UpdateResponse::Response(invoice.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<UpdateResponse> {
@@ -95,6 +102,7 @@ pub enum UpdatePrescriptionErrorInterface {
     InvoiceIsNotEditable(InvoiceIsNotEditable),
     CanOnlyChangeToPickedWhenNoUnallocatedLines(CanOnlyChangeToPickedWhenNoUnallocatedLines),
     CantBackDate(InvalidStockSelection),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl UpdateInput {
@@ -169,12 +177,19 @@ fn map_error(error: ServiceError) -> Result<UpdatePrescriptionErrorInterface> {
             ))
         }
 
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdatePrescriptionErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
+
         // Standard Graphql Errors
         ServiceError::NotAPrescriptionInvoice
         | ServiceError::ClinicianDoesNotExist
         | ServiceError::NotThisStoreInvoice
         | ServiceError::PatientDoesNotExist => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_)
+        | ServiceError::PluginError(_)
         | ServiceError::InvoiceLineHasNoStockLine(_)
         | ServiceError::UpdatedInvoiceDoesNotExist => InternalError(formatted_error),
     };
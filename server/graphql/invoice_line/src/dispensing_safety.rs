@@ -0,0 +1,128 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{
+    DispensingAlertNode, DrugInteractionConnector, ItemClinicalInfoConnector,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    dispensing_safety::{CheckPrescriptionItem, CheckPrescriptionItemError},
+};
+
+#[derive(InputObject)]
+pub struct CheckPrescriptionItemInput {
+    pub invoice_id: String,
+    pub item_id: String,
+    /// Dosing directions, checked against the item's maximum daily dose
+    pub directions: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct DispensingAlertConnector {
+    pub total_count: u32,
+    pub nodes: Vec<DispensingAlertNode>,
+}
+
+pub fn check_prescription_item(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: CheckPrescriptionItemInput,
+) -> Result<DispensingAlertConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutatePrescription,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let alerts = match service_provider
+        .dispensing_safety_service
+        .check_prescription_item(&service_context, input.to_domain())
+    {
+        Ok(alerts) => alerts,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                CheckPrescriptionItemError::InvoiceDoesNotExist
+                | CheckPrescriptionItemError::NotThisStoreInvoice
+                | CheckPrescriptionItemError::NotAPrescription
+                | CheckPrescriptionItemError::ItemDoesNotExist => BadUserInput(formatted_error),
+                CheckPrescriptionItemError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(DispensingAlertConnector {
+        total_count: alerts.len() as u32,
+        nodes: DispensingAlertNode::from_vec(alerts),
+    })
+}
+
+pub fn item_clinical_info(
+    ctx: &Context<'_>,
+    store_id: String,
+    item_ids: Vec<String>,
+) -> Result<ItemClinicalInfoConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryItems,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let rows = service_provider
+        .dispensing_safety_service
+        .get_item_clinical_info(&service_context, &item_ids)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ItemClinicalInfoConnector::from_vec(rows))
+}
+
+pub fn drug_interactions(ctx: &Context<'_>, store_id: String) -> Result<DrugInteractionConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryItems,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let rows = service_provider
+        .dispensing_safety_service
+        .get_drug_interactions(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(DrugInteractionConnector::from_vec(rows))
+}
+
+impl CheckPrescriptionItemInput {
+    pub fn to_domain(self) -> CheckPrescriptionItem {
+        let CheckPrescriptionItemInput {
+            invoice_id,
+            item_id,
+            directions,
+        } = self;
+
+        CheckPrescriptionItem {
+            invoice_id,
+            item_id,
+            directions,
+        }
+    }
+}
@@ -1,10 +1,13 @@
+mod dispensing_safety;
 pub mod invoice_line_queries;
 pub mod mutations;
 use self::mutations::{inbound_shipment_line, outbound_shipment_line, prescription_line};
 use async_graphql::*;
 use graphql_core::generic_inputs::InboundShipmentType;
 use graphql_core::{generic_inputs::PrintReportSortInput, pagination::PaginationInput};
-use graphql_types::types::{DraftStockOutItemData, InvoiceNode};
+use graphql_types::types::{
+    DraftStockOutItemData, DrugInteractionConnector, InvoiceNode, ItemClinicalInfoConnector,
+};
 use invoice_line_queries::{
     draft_outbound_lines, invoice_lines, InvoiceLineFilterInput, InvoiceLineSortInput,
     InvoiceLinesResponse,
@@ -36,6 +39,34 @@ impl InvoiceLineQueries {
     ) -> Result<DraftStockOutItemData> {
         draft_outbound_lines(ctx, &store_id, &item_id, &invoice_id)
     }
+
+    /// Allergy, interaction, duplicate therapy and maximum dose alerts for adding an item to a
+    /// prescription
+    pub async fn check_prescription_item(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: dispensing_safety::CheckPrescriptionItemInput,
+    ) -> Result<dispensing_safety::DispensingAlertConnector> {
+        dispensing_safety::check_prescription_item(ctx, store_id, input)
+    }
+
+    pub async fn item_clinical_info(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        item_ids: Vec<String>,
+    ) -> Result<ItemClinicalInfoConnector> {
+        dispensing_safety::item_clinical_info(ctx, store_id, item_ids)
+    }
+
+    pub async fn drug_interactions(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<DrugInteractionConnector> {
+        dispensing_safety::drug_interactions(ctx, store_id)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -349,4 +380,41 @@ impl InvoiceLineMutations {
     ) -> Result<prescription_line::delete::DeleteResponse> {
         prescription_line::delete::delete(ctx, &store_id, input)
     }
+
+    // Dispensing safety reference data (central server only)
+    async fn upsert_item_clinical_info(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::dispensing_safety::UpsertItemClinicalInfoInput,
+    ) -> Result<mutations::dispensing_safety::UpsertItemClinicalInfoResponse> {
+        mutations::dispensing_safety::upsert_item_clinical_info(ctx, store_id, input)
+    }
+
+    async fn delete_item_clinical_info(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<mutations::dispensing_safety::DeleteClinicalReferenceResponse> {
+        mutations::dispensing_safety::delete_item_clinical_info(ctx, store_id, id)
+    }
+
+    async fn upsert_drug_interaction(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::dispensing_safety::UpsertDrugInteractionInput,
+    ) -> Result<mutations::dispensing_safety::UpsertDrugInteractionResponse> {
+        mutations::dispensing_safety::upsert_drug_interaction(ctx, store_id, input)
+    }
+
+    async fn delete_drug_interaction(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<mutations::dispensing_safety::DeleteClinicalReferenceResponse> {
+        mutations::dispensing_safety::delete_drug_interaction(ctx, store_id, id)
+    }
 }
@@ -0,0 +1,235 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{
+    DeleteResponse, DrugInteractionNode, DrugInteractionSeverityNode, ItemClinicalInfoNode,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    dispensing_safety::{
+        DeleteClinicalReferenceError, UpsertDrugInteraction, UpsertDrugInteractionError,
+        UpsertItemClinicalInfo, UpsertItemClinicalInfoError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct UpsertItemClinicalInfoInput {
+    pub id: String,
+    pub item_id: String,
+    /// WHO Anatomical Therapeutic Chemical code, e.g. `N02BE01`
+    pub atc_code: Option<String>,
+    /// Units of the item
+    pub max_daily_dose: Option<f64>,
+}
+
+#[derive(InputObject)]
+pub struct UpsertDrugInteractionInput {
+    pub id: String,
+    /// ATC code or group of at least 3 characters
+    pub atc_code_a: String,
+    pub atc_code_b: String,
+    pub severity: DrugInteractionSeverityNode,
+    pub description: String,
+}
+
+#[derive(Union)]
+pub enum UpsertItemClinicalInfoResponse {
+    Response(ItemClinicalInfoNode),
+}
+
+#[derive(Union)]
+pub enum UpsertDrugInteractionResponse {
+    Response(DrugInteractionNode),
+}
+
+#[derive(Union)]
+pub enum DeleteClinicalReferenceResponse {
+    Response(DeleteResponse),
+}
+
+pub fn upsert_item_clinical_info(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: UpsertItemClinicalInfoInput,
+) -> Result<UpsertItemClinicalInfoResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .dispensing_safety_service
+        .upsert_item_clinical_info(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertItemClinicalInfoResponse::Response(
+            ItemClinicalInfoNode::from_domain(row),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertItemClinicalInfoError::NotCentralServer => Forbidden(formatted_error),
+                UpsertItemClinicalInfoError::ItemDoesNotExist
+                | UpsertItemClinicalInfoError::ItemAlreadyHasClinicalInfo
+                | UpsertItemClinicalInfoError::InvalidAtcCode
+                | UpsertItemClinicalInfoError::MaxDailyDoseMustBePositive => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertItemClinicalInfoError::CreatedRecordNotFound
+                | UpsertItemClinicalInfoError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_item_clinical_info(
+    ctx: &Context<'_>,
+    store_id: String,
+    id: String,
+) -> Result<DeleteClinicalReferenceResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    map_delete_response(
+        service_provider
+            .dispensing_safety_service
+            .delete_item_clinical_info(&service_context, &id),
+    )
+}
+
+pub fn upsert_drug_interaction(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: UpsertDrugInteractionInput,
+) -> Result<UpsertDrugInteractionResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .dispensing_safety_service
+        .upsert_drug_interaction(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertDrugInteractionResponse::Response(
+            DrugInteractionNode::from_domain(row),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertDrugInteractionError::NotCentralServer => Forbidden(formatted_error),
+                UpsertDrugInteractionError::InvalidAtcCode => BadUserInput(formatted_error),
+                UpsertDrugInteractionError::CreatedRecordNotFound
+                | UpsertDrugInteractionError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_drug_interaction(
+    ctx: &Context<'_>,
+    store_id: String,
+    id: String,
+) -> Result<DeleteClinicalReferenceResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    map_delete_response(
+        service_provider
+            .dispensing_safety_service
+            .delete_drug_interaction(&service_context, &id),
+    )
+}
+
+fn map_delete_response(
+    result: Result<String, DeleteClinicalReferenceError>,
+) -> Result<DeleteClinicalReferenceResponse> {
+    match result {
+        Ok(id) => Ok(DeleteClinicalReferenceResponse::Response(DeleteResponse(
+            id,
+        ))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                DeleteClinicalReferenceError::NotCentralServer => Forbidden(formatted_error),
+                DeleteClinicalReferenceError::RecordDoesNotExist => BadUserInput(formatted_error),
+                DeleteClinicalReferenceError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertItemClinicalInfoInput {
+    pub fn to_domain(self) -> UpsertItemClinicalInfo {
+        let UpsertItemClinicalInfoInput {
+            id,
+            item_id,
+            atc_code,
+            max_daily_dose,
+        } = self;
+
+        UpsertItemClinicalInfo {
+            id,
+            item_id,
+            atc_code,
+            max_daily_dose,
+        }
+    }
+}
+
+impl UpsertDrugInteractionInput {
+    pub fn to_domain(self) -> UpsertDrugInteraction {
+        let UpsertDrugInteractionInput {
+            id,
+            atc_code_a,
+            atc_code_b,
+            severity,
+            description,
+        } = self;
+
+        UpsertDrugInteraction {
+            id,
+            atc_code_a,
+            atc_code_b,
+            severity: severity.into(),
+            description,
+        }
+    }
+}
@@ -2,7 +2,9 @@ use async_graphql::*;
 use chrono::NaiveDate;
 
 use graphql_core::generic_inputs::NullableUpdateInput;
-use graphql_core::simple_generic_errors::{CannotEditInvoice, ForeignKey, ForeignKeyError};
+use graphql_core::simple_generic_errors::{
+    CannotEditInvoice, ForeignKey, ForeignKeyError, PluginValidationFailed,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 use graphql_types::types::InvoiceLineNode;
@@ -74,11 +76,19 @@ pub fn insert(
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_line_service
-            .insert_stock_in_line(&service_context, input.to_domain(), Some(r#type.to_domain())),
-    )
+    let result = service_provider.invoice_line_service.insert_stock_in_line(
+        &service_context,
+        input.to_domain(),
+        Some(r#type.to_domain()),
+    );
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        InsertResponse::Response(invoice_line) => {
+            InsertResponse::Response(invoice_line.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 #[derive(Interface)]
@@ -87,6 +97,7 @@ pub fn insert(
 pub enum InsertErrorInterface {
     ForeignKeyError(ForeignKeyError),
     CannotEditInvoice(CannotEditInvoice),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl InsertInput {
@@ -183,6 +194,12 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
             ))
         }
 
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(InsertErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
+
         // Standard Graphql Errors
         ServiceError::NotThisStoreInvoice
         | ServiceError::LineAlreadyExists
@@ -202,9 +219,9 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
         | ServiceError::PurchaseOrderLineIdRequired
         | ServiceError::PurchaseOrderLineDoesNotExist
         | ServiceError::ItemNotFound => BadUserInput(formatted_error),
-        ServiceError::DatabaseError(_) | ServiceError::NewlyCreatedLineDoesNotExist => {
-            InternalError(formatted_error)
-        }
+        ServiceError::DatabaseError(_)
+        | ServiceError::NewlyCreatedLineDoesNotExist
+        | ServiceError::PluginError(_) => InternalError(formatted_error),
         ServiceError::IncorrectLocationType => BadUserInput(formatted_error),
         ServiceError::WrongInboundShipmentType => BadUserInput(formatted_error),
     };
@@ -2,7 +2,8 @@ use async_graphql::*;
 use chrono::NaiveDate;
 use graphql_core::generic_inputs::{NullableUpdateInput, TaxInput};
 use graphql_core::simple_generic_errors::{
-    CannotEditInvoice, ForeignKey, ForeignKeyError, NotAnInboundShipment, RecordNotFound,
+    CannotEditInvoice, ForeignKey, ForeignKeyError, NotAnInboundShipment, PluginValidationFailed,
+    RecordNotFound,
 };
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
@@ -85,12 +86,17 @@ pub fn update(
         &[(input.id.clone(), input.status.clone())],
     )?;
 
-    let response = match service_provider.invoice_line_service.update_stock_in_line(
+    let result = service_provider.invoice_line_service.update_stock_in_line(
         &service_context,
         input.to_domain(),
         Some(r#type.to_domain()),
-    ) {
-        Ok(invoice_line) => UpdateResponse::Response(InvoiceLineNode::from_domain(invoice_line)),
+    );
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    let response = match result {
+        Ok(invoice_line) => UpdateResponse::Response(
+            InvoiceLineNode::from_domain(invoice_line).with_plugin_warnings(plugin_warnings),
+        ),
         Err(error) => UpdateResponse::Error(UpdateError {
             error: map_error(error)?,
         }),
@@ -108,6 +114,7 @@ pub enum UpdateErrorInterface {
     CannotEditInvoice(CannotEditInvoice),
     NotAnInboundShipment(NotAnInboundShipment),
     BatchIsReserved(BatchIsReserved),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl UpdateInput {
@@ -227,6 +234,11 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::BatchIsReserved => {
             return Ok(UpdateErrorInterface::BatchIsReserved(BatchIsReserved {}))
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         ServiceError::NotThisStoreInvoice
         | ServiceError::NotAStockIn
@@ -245,6 +257,7 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         | ServiceError::ItemNotFound => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::UpdatedLineDoesNotExist => InternalError(formatted_error),
+        ServiceError::PluginError(_) => InternalError(formatted_error),
         ServiceError::IncorrectLocationType => BadUserInput(formatted_error),
         ServiceError::WrongInboundShipmentType => BadUserInput(formatted_error),
     };
@@ -1,3 +1,4 @@
+pub mod dispensing_safety;
 pub mod inbound_shipment_line;
 pub mod outbound_shipment_line;
 pub mod prescription_line;
@@ -1,6 +1,8 @@
 use async_graphql::*;
 
-use graphql_core::simple_generic_errors::{self, CannotEditInvoice, ForeignKey, ForeignKeyError};
+use graphql_core::simple_generic_errors::{
+    self, CannotEditInvoice, ForeignKey, ForeignKeyError, PluginValidationFailed,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 use graphql_types::types::InvoiceLineNode;
@@ -52,11 +54,17 @@ pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<I
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_line_service
-            .insert_stock_out_line(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .invoice_line_service
+        .insert_stock_out_line(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        InsertResponse::Response(invoice_line) => {
+            InsertResponse::Response(invoice_line.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<InvoiceLine, ServiceError>) -> Result<InsertResponse> {
@@ -81,6 +89,7 @@ pub enum InsertErrorInterface {
     LocationIsOnHold(LocationIsOnHold),
     LocationNotFound(LocationNotFound),
     StockLineIsOnHold(StockLineIsOnHold),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl InsertInput {
@@ -118,6 +127,7 @@ impl InsertInput {
             item_variant_id: None,
             donor_id: None,
             manufacturer_id: None,
+            dispensing_override_reason: None,
         }
     }
 }
@@ -172,13 +182,19 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
                 },
             ))
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(InsertErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         NotThisStoreInvoice
         | InvoiceTypeDoesNotMatch
         | LineAlreadyExists
         | VVMStatusDoesNotExist
+        | DispensingAlertsRequireOverride(_)
         | NumberOfPacksBelowZero => StandardGraphqlError::BadUserInput(formatted_error),
-        AutoPickFailed(_) | DatabaseError(_) | NewlyCreatedLineDoesNotExist => {
+        AutoPickFailed(_) | DatabaseError(_) | NewlyCreatedLineDoesNotExist | PluginError(_) => {
             StandardGraphqlError::InternalError(formatted_error)
         }
     };
@@ -3,7 +3,9 @@ use async_graphql::*;
 use graphql_core::generic_inputs::TaxInput;
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::{
-    simple_generic_errors::{CannotEditInvoice, ForeignKey, ForeignKeyError, RecordNotFound},
+    simple_generic_errors::{
+        CannotEditInvoice, ForeignKey, ForeignKeyError, PluginValidationFailed, RecordNotFound,
+    },
     ContextExt,
 };
 use graphql_types::types::InvoiceLineNode;
@@ -43,11 +45,17 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_line_service
-            .update_stock_out_line(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .invoice_line_service
+        .update_stock_out_line(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(invoice_line) => {
+            UpdateResponse::Response(invoice_line.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<InvoiceLine, ServiceError>) -> Result<UpdateResponse> {
@@ -86,6 +94,7 @@ pub enum UpdateErrorInterface {
     LocationNotFound(LocationNotFound),
     StockLineIsOnHold(StockLineIsOnHold),
     NotEnoughStockForReduction(NotEnoughStockForReduction),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl UpdateInput {
@@ -170,6 +179,11 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
                 },
             ))
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         NotThisStoreInvoice
         | InvoiceTypeDoesNotMatch
@@ -180,7 +194,7 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         | VVMStatusDoesNotExist
         | NotThisInvoiceLine(_)
         | LineDoesNotReferenceStockLine => StandardGraphqlError::BadUserInput(formatted_error),
-        AutoPickFailed(_) | DatabaseError(_) | UpdatedLineDoesNotExist => {
+        AutoPickFailed(_) | DatabaseError(_) | UpdatedLineDoesNotExist | PluginError(_) => {
             StandardGraphqlError::InternalError(formatted_error)
         }
     };
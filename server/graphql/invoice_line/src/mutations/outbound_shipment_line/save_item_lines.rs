@@ -86,6 +86,7 @@ impl SaveOutboundShipmentLinesInput {
                 .collect(),
             prescribed_quantity: None, // Only used for prescription lines
             note: None,                // Not used yet
+            dispensing_override_reason: None,
         }
     }
 }
@@ -1,12 +1,15 @@
 use async_graphql::*;
 
-use graphql_core::simple_generic_errors::{self, CannotEditInvoice, ForeignKey, ForeignKeyError};
+use graphql_core::simple_generic_errors::{
+    self, CannotEditInvoice, ForeignKey, ForeignKeyError, PluginValidationFailed,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 
-use graphql_types::types::InvoiceLineNode;
+use graphql_types::types::{DispensingAlertNode, InvoiceLineNode};
 use repository::InvoiceLine;
 use service::auth::{Resource, ResourceAccessRequest};
+use service::dispensing_safety::DispensingAlert;
 
 use crate::mutations::outbound_shipment_line::line::{
     self, LocationIsOnHold, LocationNotFound, NotEnoughStockForReduction,
@@ -24,6 +27,8 @@ pub struct InsertInput {
     pub stock_line_id: String,
     pub number_of_packs: f64,
     pub note: Option<String>,
+    /// Reason for dispensing despite dispensing safety errors for the item
+    pub dispensing_override_reason: Option<String>,
 }
 
 #[derive(SimpleObject)]
@@ -51,11 +56,17 @@ pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<I
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .invoice_line_service
-            .insert_stock_out_line(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .invoice_line_service
+        .insert_stock_out_line(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        InsertResponse::Response(invoice_line) => {
+            InsertResponse::Response(invoice_line.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<InvoiceLine, ServiceError>) -> Result<InsertResponse> {
@@ -80,6 +91,21 @@ pub enum InsertErrorInterface {
     LocationIsOnHold(LocationIsOnHold),
     LocationNotFound(LocationNotFound),
     StockLineIsOnHold(StockLineIsOnHold),
+    DispensingAlertsRequireOverride(DispensingAlertsRequireOverride),
+    PluginValidationFailed(PluginValidationFailed),
+}
+
+pub struct DispensingAlertsRequireOverride(pub Vec<DispensingAlert>);
+
+#[Object]
+impl DispensingAlertsRequireOverride {
+    pub async fn description(&self) -> &str {
+        "Dispensing safety errors for the item, an override reason is required"
+    }
+
+    pub async fn alerts(&self) -> Vec<DispensingAlertNode> {
+        DispensingAlertNode::from_vec(self.0.clone())
+    }
 }
 
 fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
@@ -132,13 +158,23 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
                 },
             ))
         }
+        DispensingAlertsRequireOverride(alerts) => {
+            return Ok(InsertErrorInterface::DispensingAlertsRequireOverride(
+                self::DispensingAlertsRequireOverride(alerts),
+            ))
+        }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(InsertErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         // Standard Graphql Errors
         NotThisStoreInvoice
         | InvoiceTypeDoesNotMatch
         | LineAlreadyExists
         | VVMStatusDoesNotExist
         | NumberOfPacksBelowZero => StandardGraphqlError::BadUserInput(formatted_error),
-        AutoPickFailed(_) | DatabaseError(_) | NewlyCreatedLineDoesNotExist => {
+        AutoPickFailed(_) | DatabaseError(_) | NewlyCreatedLineDoesNotExist | PluginError(_) => {
             StandardGraphqlError::InternalError(formatted_error)
         }
     };
@@ -154,6 +190,7 @@ impl InsertInput {
             stock_line_id,
             number_of_packs,
             note,
+            dispensing_override_reason,
         } = self;
 
         ServiceInput {
@@ -163,6 +200,7 @@ impl InsertInput {
             stock_line_id,
             number_of_packs,
             note,
+            dispensing_override_reason,
             // Default
             vvm_status_id: None,
             prescribed_quantity: None,
@@ -447,6 +485,27 @@ mod test {
             Some(service_provider(test_service, &connection_manager))
         );
 
+        //DispensingAlertsRequireOverride
+        let test_service = TestService(Box::new(|_| {
+            Err(ServiceError::DispensingAlertsRequireOverride(vec![]))
+        }));
+
+        let expected = json!({
+            "insertPrescriptionLine" : {
+                "error": {
+                    "__typename": "DispensingAlertsRequireOverride"
+                }
+            }
+        });
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
         //NotThisStoreInvoice
         let test_service = TestService(Box::new(|_| Err(ServiceError::NotThisStoreInvoice)));
         let expected_message = "Bad user input";
@@ -17,6 +17,8 @@ pub struct SavePrescriptionLinesInput {
     pub lines: Vec<PrescriptionLineInput>,
     pub prescribed_quantity: Option<f64>,
     pub note: Option<String>,
+    /// Reason for dispensing despite dispensing safety errors for the item
+    pub dispensing_override_reason: Option<String>,
 }
 
 #[derive(InputObject)]
@@ -66,6 +68,7 @@ impl SavePrescriptionLinesInput {
             lines,
             prescribed_quantity,
             note,
+            dispensing_override_reason,
         } = self;
 
         SaveStockOutItemLines {
@@ -74,6 +77,7 @@ impl SavePrescriptionLinesInput {
             placeholder_quantity: None, // Not used in Prescriptions
             prescribed_quantity,
             note,
+            dispensing_override_reason,
             lines: lines
                 .into_iter()
                 .map(|line| SaveStockOutInvoiceLine {
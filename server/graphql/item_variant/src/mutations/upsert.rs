@@ -25,6 +25,8 @@ pub struct UpsertItemVariantInput {
     pub manufacturer_id: Option<NullableUpdateInput<String>>,
     pub packaging_variants: Vec<PackagingVariantInput>,
     pub vvm_type: Option<NullableUpdateInput<String>>,
+    /// Recorded in the audit log with the changed fields
+    pub reason: Option<String>,
 }
 
 #[derive(InputObject)]
@@ -68,7 +70,9 @@ pub fn upsert_item_variant(
         },
     )?;
     let service_provider = ctx.service_provider();
-    let service_context = service_provider.context(store_id, user.user_id)?;
+    let service_context = service_provider
+        .context(store_id, user.user_id)?
+        .with_audit_reason(input.reason.clone());
 
     let result = service_provider
         .item_service
@@ -87,6 +91,7 @@ impl UpsertItemVariantInput {
             manufacturer_id,
             packaging_variants,
             vvm_type,
+            reason: _,
         } = self;
 
         UpsertItemVariantWithPackaging {
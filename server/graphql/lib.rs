@@ -14,7 +14,7 @@ use actix_web::HttpResponse;
 use actix_web::{guard, HttpRequest};
 
 use async_graphql::{EmptyMutation, EmptySubscription, MergedSubscription, Object, Schema, Subscription};
-use async_graphql::{MergedObject, Response};
+use async_graphql::{MergedObject, Response, ServerError};
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
 
 use graphql_asset::property::AssetPropertiesQueries;
@@ -30,15 +30,19 @@ use graphql_cold_chain::{ColdChainMutations, ColdChainQueries};
 use graphql_contact::ContactQueries;
 use graphql_contact_form::ContactFormMutations;
 use graphql_core::loader::LoaderRegistry;
+use graphql_core::query_limits::QueryLimits;
 use graphql_core::standard_graphql_error::StandardGraphqlError;
-use graphql_core::{auth_data_from_request, BoxedSelfRequest, RequestUserData, SelfRequest};
+use graphql_core::{
+    auth_data_from_request, client_data_from_request, BoxedSelfRequest, RequestUserData,
+    SelfRequest,
+};
 use graphql_demographic::{DemographicIndicatorQueries, DemographicMutations};
 use graphql_form_schema::{FormSchemaMutations, FormSchemaQueries};
 use graphql_general::campaign::{CampaignMutations, CampaignQueries};
 use graphql_general::{
-    CentralGeneralMutations, DiscoveryQueries, GeneralMutations, GeneralQueries,
-    InitialisationMutations, InitialisationQueries, InitialisationSubscriptions, MigrationQueries,
-    SyncStatusSubscriptions,
+    CentralGeneralMutations, CentralGeneralQueries, DiscoveryQueries, GeneralMutations,
+    GeneralQueries, InitialisationMutations, InitialisationQueries, InitialisationSubscriptions,
+    MigrationQueries, StoreEventSubscriptions, SyncStatusSubscriptions,
 };
 use graphql_inventory_adjustment::InventoryAdjustmentMutations;
 use graphql_invoice::{InvoiceMutations, InvoiceQueries};
@@ -48,7 +52,8 @@ use graphql_item_bundle::BundledItemMutations;
 use graphql_item_variant::{ItemVariantMutations, ItemVariantQueries};
 use graphql_location::{LocationMutations, LocationQueries};
 use graphql_plugin::{
-    CentralPluginMutations, CentralPluginQueries, PluginMutations, PluginQueries,
+    CentralPluginMutations, CentralPluginQueries, PluginMutationSchema, PluginMutations,
+    PluginQueries,
 };
 use graphql_preference::{PreferenceMutations, PreferenceQueries};
 use graphql_printer::{PrinterMutations, PrinterQueries};
@@ -74,6 +79,7 @@ use service::auth_data::AuthData;
 use service::subscription::ResolvedSubscription;
 use service::boajs::utils::{ExecuteGraphQlError, ExecuteGraphql};
 use service::plugin::validation::ValidatedPluginBucket;
+use service::rate_limit::RateLimiter;
 use service::service_provider::ServiceProvider;
 use service::settings::Settings;
 use service::sync::CentralServerConfig;
@@ -141,6 +147,10 @@ impl CentralServerQueryNode {
     async fn plugin(&self) -> CentralPluginQueries {
         CentralPluginQueries
     }
+
+    async fn general(&self) -> CentralGeneralQueries {
+        CentralGeneralQueries
+    }
 }
 
 #[derive(Default, Clone)]
@@ -314,7 +324,11 @@ impl BaseSubscriptions {
 }
 
 #[derive(MergedSubscription, Default, Clone)]
-pub struct Subscriptions(pub BaseSubscriptions, pub SyncStatusSubscriptions);
+pub struct Subscriptions(
+    pub BaseSubscriptions,
+    pub SyncStatusSubscriptions,
+    pub StoreEventSubscriptions,
+);
 
 /// We need to swap schema between initialisation and operational modes
 /// this is done to avoid validations check in operational mode where
@@ -323,6 +337,8 @@ pub struct GraphqlSchema {
     pub(crate) operational: OperationalSchema,
     initialisation: InitialisationSchema,
     migration: MigrationSchema,
+    /// Named mutations declared by plugins, served on /graphql/plugin
+    plugin_mutation: PluginMutationSchema,
     /// Set on startup based on InitialisationStatus and then updated via SiteIsInitialisedCallback after initialisation
     operational_status: Data<RwLock<OperationalStatus>>,
 }
@@ -367,6 +383,10 @@ impl GraphqlSchema {
         // Shared operational status across all schemas
         let operational_status_ref = Data::new(RwLock::new(operational_status.clone()));
 
+        // Depth, complexity and rate limits only apply to requests from clients, not self requests
+        let graphql_settings = settings.graphql();
+        let rate_limiter = Data::new(RateLimiter::new(&graphql_settings.rate_limit));
+
         // Operational schema
         let operational_builder =
             OperationalSchema::build(Queries::new(), Mutations::new(), Subscriptions::default())
@@ -380,7 +400,9 @@ impl GraphqlSchema {
                 // Add self requester to operational
                 .data(Data::new(SelfRequestImpl::new_boxed(self_requester_schema)))
                 .data(operational_status_ref.clone())
-                .extension(GraphQLRequestLogger);
+                .data(rate_limiter.clone())
+                .extension(GraphQLRequestLogger)
+                .extension(QueryLimits::new(&graphql_settings));
 
         // Initialisation schema should ony need service_provider
         let initialisation_builder = InitialisationSchema::build(
@@ -400,10 +422,18 @@ impl GraphqlSchema {
                 .data(operational_status_ref.clone())
                 .extension(GraphQLRequestLogger);
 
+        let plugin_mutation = PluginMutationSchema::new(
+            service_provider.clone(),
+            auth.clone(),
+            &graphql_settings,
+            rate_limiter,
+        );
+
         GraphqlSchema {
             operational: operational_builder.finish(),
             initialisation: initialisation_builder.finish(),
             migration: migration_builder.finish(),
+            plugin_mutation,
             operational_status: operational_status_ref.clone(),
         }
     }
@@ -424,12 +454,39 @@ impl GraphqlSchema {
             OperationalStatus::Operational => {
                 // auth_data is only available in schema in operational mode
                 let user_data = auth_data_from_request(&http_req);
-                self.operational.execute(req.data(user_data)).await
+                let client_data = client_data_from_request(&http_req);
+                self.operational
+                    .execute(req.data(user_data).data(client_data))
+                    .await
             }
             OperationalStatus::MigratingDatabase => self.migration.execute(req).await,
             OperationalStatus::Initialising => self.initialisation.execute(req).await,
         }
     }
+
+    async fn execute_plugin_mutation(
+        &self,
+        http_req: HttpRequest,
+        req: GraphQLRequest,
+    ) -> Response {
+        let req = req.into_inner();
+
+        match &*self.operational_status.read().await {
+            OperationalStatus::Operational => {
+                let user_data = auth_data_from_request(&http_req);
+                let client_data = client_data_from_request(&http_req);
+                self.plugin_mutation
+                    .execute(req.data(user_data).data(client_data))
+                    .await
+            }
+            OperationalStatus::MigratingDatabase | OperationalStatus::Initialising => {
+                Response::from_errors(vec![ServerError::new(
+                    "Plugin mutations are only available once the server is operational",
+                    None,
+                )])
+            }
+        }
+    }
 }
 
 pub fn attach_graphql_schema(
@@ -451,6 +508,11 @@ pub fn attach_graphql_schema(
                 web::resource("/graphql/ws")
                     .guard(guard::Get())
                     .to(graphql_ws),
+            )
+            .service(
+                web::resource("/graphql/plugin")
+                    .guard(guard::Post())
+                    .to(graphql_plugin_index),
             );
     }
 }
@@ -506,6 +568,15 @@ async fn graphql_index(
     schema.execute(http_req, req).await.into()
 }
 
+/// Entrypoint for named mutations declared by plugins
+async fn graphql_plugin_index(
+    schema: Data<GraphqlSchema>,
+    http_req: HttpRequest,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute_plugin_mutation(http_req, req).await.into()
+}
+
 async fn graphql_playground() -> HttpResponse {
     HttpResponse::Ok()
         .content_type("text/html; charset=utf-8")
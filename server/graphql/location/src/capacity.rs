@@ -0,0 +1,193 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::LocationNode;
+use repository::{location::Location, LocationRow};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    location::{
+        capacity::{LocationCapacityError, LocationCapacityWarning, LocationUtilisation},
+        putaway::PutawaySuggestion,
+    },
+};
+
+pub struct LocationUtilisationNode {
+    utilisation: LocationUtilisation,
+}
+
+#[Object]
+impl LocationUtilisationNode {
+    pub async fn location(&self) -> LocationNode {
+        location_node(&self.utilisation.location)
+    }
+
+    pub async fn volume(&self) -> f64 {
+        self.utilisation.location.volume
+    }
+
+    pub async fn volume_used(&self) -> f64 {
+        self.utilisation.volume_used
+    }
+
+    pub async fn free_volume(&self) -> f64 {
+        self.utilisation.free_volume()
+    }
+
+    /// Fraction of location volume in use, null when location volume is not set
+    pub async fn utilisation(&self) -> Option<f64> {
+        self.utilisation.utilisation()
+    }
+}
+
+pub struct LocationCapacityWarningNode {
+    warning: LocationCapacityWarning,
+}
+
+#[Object]
+impl LocationCapacityWarningNode {
+    pub async fn location(&self) -> LocationNode {
+        location_node(&self.warning.utilisation.location)
+    }
+
+    pub async fn volume(&self) -> f64 {
+        self.warning.utilisation.location.volume
+    }
+
+    pub async fn volume_used(&self) -> f64 {
+        self.warning.utilisation.volume_used
+    }
+
+    /// Volume of stock on the shipment to be received into the location
+    pub async fn incoming_volume(&self) -> f64 {
+        self.warning.incoming_volume
+    }
+
+    pub async fn invoice_line_ids(&self) -> &Vec<String> {
+        &self.warning.invoice_line_ids
+    }
+}
+
+pub struct PutawaySuggestionNode {
+    suggestion: PutawaySuggestion,
+}
+
+#[Object]
+impl PutawaySuggestionNode {
+    pub async fn invoice_line_id(&self) -> &str {
+        &self.suggestion.invoice_line_id
+    }
+
+    pub async fn required_location_type_id(&self) -> &Option<String> {
+        &self.suggestion.required_location_type_id
+    }
+
+    pub async fn volume(&self) -> f64 {
+        self.suggestion.volume
+    }
+
+    /// Null when no location of the required type has enough free volume
+    pub async fn location(&self) -> Option<LocationNode> {
+        self.suggestion.location.as_ref().map(location_node)
+    }
+}
+
+fn location_node(location_row: &LocationRow) -> LocationNode {
+    LocationNode::from_domain(Location {
+        location_row: location_row.clone(),
+    })
+}
+
+pub fn location_utilisation(
+    ctx: &Context<'_>,
+    store_id: String,
+) -> Result<Vec<LocationUtilisationNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLocation,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let utilisation = service_provider
+        .location_service
+        .get_location_utilisation(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(utilisation
+        .into_iter()
+        .map(|utilisation| LocationUtilisationNode { utilisation })
+        .collect())
+}
+
+pub fn inbound_shipment_capacity_warnings(
+    ctx: &Context<'_>,
+    store_id: String,
+    invoice_id: String,
+) -> Result<Vec<LocationCapacityWarningNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLocation,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let warnings = service_provider
+        .location_service
+        .get_inbound_shipment_capacity_warnings(&service_context, &invoice_id)
+        .map_err(map_error)?;
+
+    Ok(warnings
+        .into_iter()
+        .map(|warning| LocationCapacityWarningNode { warning })
+        .collect())
+}
+
+pub fn putaway_suggestions(
+    ctx: &Context<'_>,
+    store_id: String,
+    invoice_id: String,
+) -> Result<Vec<PutawaySuggestionNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryLocation,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let suggestions = service_provider
+        .location_service
+        .get_putaway_suggestions(&service_context, &invoice_id)
+        .map_err(map_error)?;
+
+    Ok(suggestions
+        .into_iter()
+        .map(|suggestion| PutawaySuggestionNode { suggestion })
+        .collect())
+}
+
+fn map_error(error: LocationCapacityError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        LocationCapacityError::InvoiceDoesNotExist
+        | LocationCapacityError::NotAnInboundShipment => BadUserInput(formatted_error),
+        LocationCapacityError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
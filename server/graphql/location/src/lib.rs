@@ -1,4 +1,6 @@
+mod capacity;
 mod mutations;
+use self::capacity::*;
 use self::mutations::*;
 
 use async_graphql::*;
@@ -60,6 +62,35 @@ impl LocationQueries {
             locations,
         )))
     }
+
+    /// Volume used and capacity of every location in store
+    pub async fn location_utilisation(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<Vec<LocationUtilisationNode>> {
+        location_utilisation(ctx, store_id)
+    }
+
+    /// Locations that would be overfilled once the inbound shipment is received
+    pub async fn inbound_shipment_capacity_warnings(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        invoice_id: String,
+    ) -> Result<Vec<LocationCapacityWarningNode>> {
+        inbound_shipment_capacity_warnings(ctx, store_id, invoice_id)
+    }
+
+    /// Suggested locations for inbound shipment lines that don't have a location yet
+    pub async fn putaway_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        invoice_id: String,
+    ) -> Result<Vec<PutawaySuggestionNode>> {
+        putaway_suggestions(ctx, store_id, invoice_id)
+    }
 }
 
 #[derive(Default, Clone)]
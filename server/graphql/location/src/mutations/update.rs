@@ -28,7 +28,9 @@ pub fn update_location(
     )?;
 
     let service_provider = ctx.service_provider();
-    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+    let service_context = service_provider
+        .context(store_id.to_string(), user.user_id)?
+        .with_audit_reason(input.reason.clone());
 
     match service_provider
         .location_service
@@ -51,6 +53,8 @@ pub struct UpdateLocationInput {
     pub on_hold: Option<bool>,
     pub location_type_id: Option<String>,
     pub volume: Option<f64>,
+    /// Recorded in the audit log with the changed fields
+    pub reason: Option<String>,
 }
 
 impl From<UpdateLocationInput> for UpdateLocation {
@@ -62,6 +66,7 @@ impl From<UpdateLocationInput> for UpdateLocation {
             on_hold,
             location_type_id,
             volume,
+            reason: _,
         }: UpdateLocationInput,
     ) -> Self {
         UpdateLocation {
@@ -4,6 +4,8 @@ mod plugin_graphql;
 mod queries;
 pub mod types;
 
+pub use plugin_graphql::named_mutation::PluginMutationSchema;
+
 use async_graphql::*;
 use plugin_data::query::{PluginDataFilterInput, PluginDataResponse, PluginDataSortInput};
 use queries::uploaded_info::PluginInfoNode;
@@ -83,4 +85,23 @@ impl PluginMutations {
     ) -> Result<plugin_data::mutations::update::UpdateResponse> {
         plugin_data::mutations::update::update_plugin_data(ctx, &store_id, input)
     }
+
+    /// Calls mutation declared in plugin manifest, input is validated against declared input schema.
+    /// Declared mutations are also exposed by name on /graphql/plugin (see `PluginMutationSchema`)
+    async fn plugin_graphql_mutation(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        plugin_code: String,
+        name: String,
+        input: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        plugin_graphql::mutation::plugin_graphql_mutation(
+            ctx,
+            &store_id,
+            &plugin_code,
+            &name,
+            input,
+        )
+    }
 }
@@ -1 +1,3 @@
+pub mod mutation;
+pub mod named_mutation;
 pub mod query;
@@ -0,0 +1,52 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, validate_rate_limit, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    plugin::PluginGraphqlMutationError as ServiceError,
+    rate_limit::RateLimitedOperation,
+};
+
+pub fn plugin_graphql_mutation(
+    ctx: &Context<'_>,
+    store_id: &str,
+    plugin_code: &str,
+    name: &str,
+    input: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::PluginGraphql,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::PluginMutation)?;
+
+    let service_provider = ctx.service_provider();
+
+    let result = service_provider
+        .plugin_service
+        .plugin_graphql_mutation(store_id.to_string(), user.user_id, plugin_code, name, input)
+        .map_err(map_error)?;
+
+    Ok(result)
+}
+
+fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error}");
+
+    let graphql_error = match error {
+        ServiceError::NotFound
+        | ServiceError::MutationNotDeclared(_)
+        | ServiceError::InvalidInput(_) => BadUserInput(formatted_error),
+        ServiceError::PluginError(_) | ServiceError::InvalidInputSchema(_) => {
+            InternalError(formatted_error)
+        }
+    };
+
+    graphql_error.extend()
+}
@@ -0,0 +1,268 @@
+use std::sync::RwLock;
+
+use actix_web::web::Data;
+use async_graphql::{
+    dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, Scalar, Schema, TypeRef},
+    Request, Response, ServerError, Value,
+};
+use graphql_core::query_limits::QueryLimits;
+use repository::PluginMutation;
+use service::{
+    auth_data::AuthData, rate_limit::RateLimiter, service_provider::ServiceProvider,
+    settings::GraphqlSettings,
+};
+
+use super::mutation::plugin_graphql_mutation;
+
+const JSON_SCALAR: &str = "JSON";
+
+/// Mutations declared in plugin manifests are only known at runtime (plugins are installed and
+/// synced while the server is running), so they can't be part of the operational schema. They are
+/// exposed as named mutations (`{pluginCode}_{name}`) in this dynamic schema instead, which is
+/// rebuilt when declared mutations change
+pub struct PluginMutationSchema {
+    service_provider: Data<ServiceProvider>,
+    auth: Data<AuthData>,
+    // Same depth, complexity and rate limits as the operational schema
+    query_limits: QueryLimits,
+    rate_limiter: Data<RateLimiter>,
+    schema: RwLock<Option<(Vec<(String, PluginMutation)>, Schema)>>,
+}
+
+impl PluginMutationSchema {
+    pub fn new(
+        service_provider: Data<ServiceProvider>,
+        auth: Data<AuthData>,
+        graphql_settings: &GraphqlSettings,
+        rate_limiter: Data<RateLimiter>,
+    ) -> Self {
+        PluginMutationSchema {
+            service_provider,
+            auth,
+            query_limits: QueryLimits::new(graphql_settings),
+            rate_limiter,
+            schema: RwLock::new(None),
+        }
+    }
+
+    pub async fn execute(&self, request: Request) -> Response {
+        match self.schema() {
+            Ok(schema) => schema.execute(request).await,
+            Err(error) => Response::from_errors(vec![ServerError::new(error, None)]),
+        }
+    }
+
+    fn schema(&self) -> Result<Schema, String> {
+        let declared = self
+            .service_provider
+            .plugin_service
+            .declared_plugin_graphql_mutations();
+
+        if let Some((built_with, schema)) = &*self.schema.read().unwrap() {
+            if *built_with == declared {
+                return Ok(schema.clone());
+            }
+        }
+
+        let schema = self
+            .build(&declared)
+            .map_err(|error| format!("Failed to build plugin mutation schema: {error}"))?;
+        *self.schema.write().unwrap() = Some((declared, schema.clone()));
+
+        Ok(schema)
+    }
+
+    fn build(
+        &self,
+        declared: &[(String, PluginMutation)],
+    ) -> Result<Schema, async_graphql::dynamic::SchemaError> {
+        let names: Vec<String> = declared
+            .iter()
+            .map(|(plugin_code, mutation)| field_name(plugin_code, &mutation.name))
+            .collect();
+
+        let query = Object::new("Query").field(
+            Field::new(
+                "declaredMutations",
+                TypeRef::named_nn_list_nn(TypeRef::STRING),
+                move |_| {
+                    let names = names.clone();
+                    FieldFuture::new(async move {
+                        Ok(Some(FieldValue::list(
+                            names.into_iter().map(FieldValue::value),
+                        )))
+                    })
+                },
+            )
+            .description("Names of mutations declared by plugins"),
+        );
+
+        let mut builder =
+            Schema::build("Query", (!declared.is_empty()).then_some("Mutation"), None)
+                .register(Scalar::new(JSON_SCALAR))
+                .register(query);
+
+        if !declared.is_empty() {
+            let mutation = declared.iter().fold(
+                Object::new("Mutation"),
+                |mutation, (plugin_code, declared)| {
+                    mutation.field(mutation_field(plugin_code, declared))
+                },
+            );
+            builder = builder.register(mutation);
+        }
+
+        builder
+            .data(self.service_provider.clone())
+            .data(self.auth.clone())
+            .data(self.rate_limiter.clone())
+            .extension(self.query_limits.clone())
+            .finish()
+    }
+}
+
+/// Graphql names are restricted to `[_A-Za-z][_0-9A-Za-z]*`
+fn field_name(plugin_code: &str, name: &str) -> String {
+    format!("{plugin_code}_{name}")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn mutation_field(plugin_code: &str, mutation: &PluginMutation) -> Field {
+    let description = format!(
+        "Declared by plugin {plugin_code}, input is validated against schema: {}",
+        mutation.input_schema
+    );
+    let field_name = field_name(plugin_code, &mutation.name);
+    let plugin_code = plugin_code.to_string();
+    let name = mutation.name.clone();
+
+    Field::new(field_name, TypeRef::named_nn(JSON_SCALAR), move |ctx| {
+        let plugin_code = plugin_code.clone();
+        let name = name.clone();
+        FieldFuture::new(async move {
+            let store_id = ctx.args.try_get("storeId")?.string()?.to_string();
+            let input = ctx.args.try_get("input")?.as_value().clone().into_json()?;
+
+            let result = plugin_graphql_mutation(ctx.ctx, &store_id, &plugin_code, &name, input)?;
+
+            Ok(Some(FieldValue::value(Value::from_json(result)?)))
+        })
+    })
+    .description(description)
+    .argument(InputValue::new(
+        "storeId",
+        TypeRef::named_nn(TypeRef::STRING),
+    ))
+    .argument(InputValue::new("input", TypeRef::named_nn(JSON_SCALAR)))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, RwLock};
+
+    use actix_web::web::Data;
+    use async_graphql::Request;
+    use base64::{prelude::BASE64_STANDARD, Engine};
+    use repository::{
+        migrations::Version, mock::MockDataInserts, test_db::setup_all, BackendPluginRow,
+        PluginManifest, PluginMutation, PluginType, PluginTypes,
+    };
+    use serde_json::json;
+    use service::{
+        auth_data::AuthData, backend_plugin::plugin_provider::PluginInstance,
+        rate_limit::RateLimiter, service_provider::ServiceProvider, settings::GraphqlSettings,
+        token_bucket::TokenBucket,
+    };
+
+    use super::PluginMutationSchema;
+
+    #[actix_rt::test]
+    async fn plugin_named_mutation() {
+        let (_, _, connection_manager, _) =
+            setup_all("plugin_named_mutation", MockDataInserts::none()).await;
+
+        let service_provider = Data::new(ServiceProvider::new(connection_manager));
+        let auth = Data::new(AuthData {
+            auth_token_secret: "n/a".to_string(),
+            token_bucket: Arc::new(RwLock::new(TokenBucket::new())),
+            no_ssl: true,
+            debug_no_access_control: true,
+        });
+        let graphql_settings = GraphqlSettings::default();
+        let schema = PluginMutationSchema::new(
+            service_provider.clone(),
+            auth.clone(),
+            &graphql_settings,
+            Data::new(RateLimiter::new(&graphql_settings.rate_limit)),
+        );
+
+        let bundle = r#"
+            export const plugins = {
+                graphql_mutation: ({ name, input }) => ({ name, count: input.count + 1 }),
+            };
+        "#;
+        PluginInstance::bind(BackendPluginRow {
+            id: "named_mutation_test".to_string(),
+            code: "named-mutation-test".to_string(),
+            version: Version::from_package_json().to_string(),
+            bundle_base64: BASE64_STANDARD.encode(bundle),
+            types: PluginTypes(vec![PluginType::GraphqlMutation]),
+            manifest: PluginManifest {
+                mutations: vec![PluginMutation {
+                    name: "increment".to_string(),
+                    input_schema: json!({
+                        "type": "object",
+                        "properties": { "count": { "type": "integer" } },
+                        "required": ["count"]
+                    }),
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let response = schema
+            .execute(Request::new(
+                r#"mutation {
+                    named_mutation_test_increment(storeId: "store_a", input: { count: 1 })
+                }"#,
+            ))
+            .await;
+        assert_eq!(response.errors, vec![]);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({
+                "named_mutation_test_increment": { "name": "increment", "count": 2 }
+            })
+        );
+
+        // Input not matching declared schema
+        let response = schema
+            .execute(Request::new(
+                r#"mutation {
+                    named_mutation_test_increment(storeId: "store_a", input: { count: "one" })
+                }"#,
+            ))
+            .await;
+        assert_eq!(response.errors.len(), 1);
+
+        // Query limits of the operational schema apply
+        let graphql_settings = GraphqlSettings {
+            max_complexity: 0,
+            ..Default::default()
+        };
+        let limited_schema = PluginMutationSchema::new(
+            service_provider,
+            auth,
+            &graphql_settings,
+            Data::new(RateLimiter::new(&graphql_settings.rate_limit)),
+        );
+        let response = limited_schema
+            .execute(Request::new("query { declaredMutations }"))
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].message, "Query is too complex");
+    }
+}
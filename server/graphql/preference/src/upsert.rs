@@ -108,6 +108,10 @@ pub struct UpsertPreferencesInput {
     pub store_custom_colour: Option<Vec<StringStorePrefInput>>,
     pub invoice_status_options: Option<Vec<InvoiceStatusOptionsInput>>,
     pub show_indicative_price_in_requisitions: Option<Vec<BoolStorePrefInput>>,
+    pub use_demand_forecast_for_suggested_quantity: Option<Vec<BoolStorePrefInput>>,
+
+    /// Recorded in the audit log with the changed fields
+    pub reason: Option<String>,
 }
 
 pub fn upsert_preferences(
@@ -115,7 +119,7 @@ pub fn upsert_preferences(
     store_id: String,
     input: UpsertPreferencesInput,
 ) -> Result<()> {
-    validate_auth(
+    let user = validate_auth(
         ctx,
         &ResourceAccessRequest {
             resource: Resource::MutatePreferences,
@@ -123,7 +127,9 @@ pub fn upsert_preferences(
         },
     )?;
     let service_provider = ctx.service_provider();
-    let service_context = service_provider.basic_context()?;
+    let service_context = service_provider
+        .context(store_id, user.user_id)?
+        .with_audit_reason(input.reason.clone());
 
     service_provider
         .preference_service
@@ -175,6 +181,8 @@ impl UpsertPreferencesInput {
             invoice_status_options,
             external_inbound_shipment_lines_must_be_authorised,
             show_indicative_price_in_requisitions,
+            use_demand_forecast_for_suggested_quantity,
+            reason: _,
         } = self;
 
         UpsertPreferences {
@@ -274,6 +282,10 @@ impl UpsertPreferencesInput {
             show_indicative_price_in_requisitions: show_indicative_price_in_requisitions
                 .as_ref()
                 .map(|i| i.iter().map(|i| i.to_domain()).collect()),
+            use_demand_forecast_for_suggested_quantity:
+                use_demand_forecast_for_suggested_quantity
+                    .as_ref()
+                    .map(|i| i.iter().map(|i| i.to_domain()).collect()),
         }
     }
 }
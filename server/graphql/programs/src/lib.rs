@@ -324,6 +324,27 @@ impl ProgramsQueries {
     ) -> Result<VaccinationCardResponse> {
         vaccination_card(ctx, store_id, program_enrolment_id)
     }
+
+    /// Refill schedules of the patient's chronic medication dispensed in the store
+    pub async fn patient_refill_schedules(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        patient_id: String,
+        options: Option<RefillScheduleOptionsInput>,
+    ) -> Result<PatientRefillScheduleConnector> {
+        patient_refill_schedules(ctx, store_id, patient_id, options)
+    }
+
+    /// Patients due for a refill, late or lost to follow up
+    pub async fn refills_due(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        options: Option<RefillScheduleOptionsInput>,
+    ) -> Result<PatientRefillScheduleConnector> {
+        refills_due(ctx, store_id, options)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -25,5 +25,7 @@ pub mod program;
 pub mod r_and_r_form;
 pub use self::program::*;
 pub use self::r_and_r_form::*;
+pub mod refill;
+pub use self::refill::*;
 pub mod vaccination;
 pub use self::vaccination::*;
@@ -0,0 +1,285 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::NaiveDate;
+use graphql_core::{
+    loader::{ItemLoader, PatientLoader},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{patient::PatientNode, ItemNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    refill::{PatientRefillSchedule, Refill, RefillScheduleError, RefillScheduleInput},
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(remote = "service::refill::RefillStatus")]
+pub enum RefillStatusNode {
+    OnSchedule,
+    Due,
+    Late,
+    Missed,
+    LostToFollowUp,
+}
+
+#[derive(InputObject, Default)]
+pub struct RefillScheduleOptionsInput {
+    /// Only include prescriptions for this program
+    pub program_id: Option<String>,
+    /// Days supplied when the directions don't give a daily dose, defaults to 30
+    pub default_days_supplied: Option<u32>,
+    /// Days after the due date before a refill counts as late, defaults to 7
+    pub grace_period_days: Option<u32>,
+    /// Days after the due date before a patient is lost to follow up, defaults to 28
+    pub lost_to_follow_up_days: Option<u32>,
+    /// Days used for the medication possession ratio, defaults to 180
+    pub adherence_period_days: Option<u32>,
+    /// Refills due within this many days are due, defaults to 7
+    pub due_within_days: Option<u32>,
+}
+
+pub struct RefillNode {
+    pub refill: Refill,
+}
+
+#[Object]
+impl RefillNode {
+    pub async fn invoice_id(&self) -> &str {
+        &self.refill.dispensing.invoice_id
+    }
+
+    pub async fn dispensed_date(&self) -> NaiveDate {
+        self.refill.dispensing.date
+    }
+
+    /// Units dispensed
+    pub async fn quantity(&self) -> f64 {
+        self.refill.dispensing.quantity
+    }
+
+    /// Units per day from the directions, null when they couldn't be interpreted
+    pub async fn daily_dose(&self) -> Option<f64> {
+        self.refill.dispensing.daily_dose
+    }
+
+    pub async fn days_supplied(&self) -> i64 {
+        self.refill.dispensing.days_supplied
+    }
+
+    pub async fn supply_start_date(&self) -> NaiveDate {
+        self.refill.supply_start_date
+    }
+
+    pub async fn due_date(&self) -> NaiveDate {
+        self.refill.due_date
+    }
+
+    /// Days after the previous refill was due
+    pub async fn days_late(&self) -> i64 {
+        self.refill.days_late
+    }
+}
+
+pub struct PatientRefillScheduleNode {
+    pub store_id: String,
+    pub allowed_ctx: Vec<String>,
+    pub schedule: PatientRefillSchedule,
+}
+
+#[Object]
+impl PatientRefillScheduleNode {
+    pub async fn patient_id(&self) -> &str {
+        &self.schedule.patient_id
+    }
+
+    pub async fn patient(&self, ctx: &Context<'_>) -> Result<Option<PatientNode>> {
+        let loader = ctx.get_loader::<DataLoader<PatientLoader>>();
+        Ok(loader
+            .load_one(self.schedule.patient_id.clone())
+            .await?
+            .map(|patient| PatientNode {
+                store_id: self.store_id.clone(),
+                allowed_ctx: self.allowed_ctx.clone(),
+                patient,
+            }))
+    }
+
+    pub async fn program_id(&self) -> &Option<String> {
+        &self.schedule.program_id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.schedule.item_id
+    }
+
+    pub async fn item_name(&self) -> &str {
+        &self.schedule.item_name
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        Ok(loader
+            .load_one(self.schedule.item_id.clone())
+            .await?
+            .map(ItemNode::from_domain))
+    }
+
+    /// Directions of the latest dispensing
+    pub async fn directions(&self) -> &Option<String> {
+        &self.schedule.directions
+    }
+
+    pub async fn refills(&self) -> Vec<RefillNode> {
+        self.schedule
+            .history
+            .refills
+            .iter()
+            .cloned()
+            .map(|refill| RefillNode { refill })
+            .collect()
+    }
+
+    pub async fn next_due_date(&self) -> NaiveDate {
+        self.schedule.history.next_due_date
+    }
+
+    pub async fn days_overdue(&self) -> i64 {
+        self.schedule.history.days_overdue
+    }
+
+    /// Refills collected after the grace period
+    pub async fn late_refills(&self) -> u32 {
+        self.schedule.history.late_refills
+    }
+
+    /// Whole supply periods without medication between refills
+    pub async fn missed_refills(&self) -> u32 {
+        self.schedule.history.missed_refills
+    }
+
+    /// Days supplied over days between dispensings in the adherence period, null with fewer than
+    /// 2 dispensings
+    pub async fn medication_possession_ratio(&self) -> Option<f64> {
+        self.schedule.history.medication_possession_ratio
+    }
+
+    pub async fn status(&self) -> RefillStatusNode {
+        RefillStatusNode::from(self.schedule.history.status)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PatientRefillScheduleConnector {
+    pub total_count: u32,
+    pub nodes: Vec<PatientRefillScheduleNode>,
+}
+
+pub fn patient_refill_schedules(
+    ctx: &Context<'_>,
+    store_id: String,
+    patient_id: String,
+    options: Option<RefillScheduleOptionsInput>,
+) -> Result<PatientRefillScheduleConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryPatient,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+    let allowed_ctx = user.capabilities();
+
+    let service_provider = ctx.service_provider();
+    let context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let result = service_provider
+        .refill_service
+        .get_patient_refill_schedules(
+            &context,
+            &patient_id,
+            options.unwrap_or_default().to_domain(),
+        );
+
+    to_connector(result, store_id, allowed_ctx)
+}
+
+/// Patients with refills due, overdue or lost to follow up, earliest due first
+pub fn refills_due(
+    ctx: &Context<'_>,
+    store_id: String,
+    options: Option<RefillScheduleOptionsInput>,
+) -> Result<PatientRefillScheduleConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryPatient,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+    let allowed_ctx = user.capabilities();
+
+    let service_provider = ctx.service_provider();
+    let context = service_provider.context(store_id.clone(), user.user_id)?;
+
+    let result = service_provider
+        .refill_service
+        .get_refills_due(&context, options.unwrap_or_default().to_domain());
+
+    to_connector(result, store_id, allowed_ctx)
+}
+
+fn to_connector(
+    result: Result<Vec<PatientRefillSchedule>, RefillScheduleError>,
+    store_id: String,
+    allowed_ctx: Vec<String>,
+) -> Result<PatientRefillScheduleConnector> {
+    let schedules = match result {
+        Ok(schedules) => schedules,
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                RefillScheduleError::DefaultDaysSuppliedMustBePositive
+                | RefillScheduleError::ThresholdsCannotBeNegative => BadUserInput(formatted_error),
+                RefillScheduleError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(PatientRefillScheduleConnector {
+        total_count: schedules.len() as u32,
+        nodes: schedules
+            .into_iter()
+            .map(|schedule| PatientRefillScheduleNode {
+                store_id: store_id.clone(),
+                allowed_ctx: allowed_ctx.clone(),
+                schedule,
+            })
+            .collect(),
+    })
+}
+
+impl RefillScheduleOptionsInput {
+    pub fn to_domain(self) -> RefillScheduleInput {
+        let RefillScheduleOptionsInput {
+            program_id,
+            default_days_supplied,
+            grace_period_days,
+            lost_to_follow_up_days,
+            adherence_period_days,
+            due_within_days,
+        } = self;
+        let defaults = RefillScheduleInput::default();
+        let days = |days: Option<u32>, default: i64| days.map(i64::from).unwrap_or(default);
+
+        RefillScheduleInput {
+            program_id,
+            default_days_supplied: days(default_days_supplied, defaults.default_days_supplied),
+            grace_period_days: days(grace_period_days, defaults.grace_period_days),
+            lost_to_follow_up_days: days(lost_to_follow_up_days, defaults.lost_to_follow_up_days),
+            adherence_period_days: days(adherence_period_days, defaults.adherence_period_days),
+            due_within_days: days(due_within_days, defaults.due_within_days),
+        }
+    }
+}
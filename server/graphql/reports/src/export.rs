@@ -1,9 +1,12 @@
 use async_graphql::{Context, Result};
 use graphql_core::{
-    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    standard_graphql_error::{validate_auth, validate_rate_limit, StandardGraphqlError},
     ContextExt,
 };
-use service::auth::{Resource, ResourceAccessRequest};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    rate_limit::RateLimitedOperation,
+};
 
 use crate::print::{PrintReportNode, PrintReportResponse};
 
@@ -13,13 +16,14 @@ pub async fn csv_to_excel(
     csv_data: String,
     filename: String,
 ) -> Result<PrintReportResponse> {
-    validate_auth(
+    let user = validate_auth(
         ctx,
         &ResourceAccessRequest {
             resource: Resource::Report,
             store_id: Some(store_id),
         },
     )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::Export)?;
 
     let service_provider = ctx.service_provider();
     let service = &service_provider.report_service;
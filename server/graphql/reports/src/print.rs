@@ -1,10 +1,13 @@
 use async_graphql::*;
 use chrono::Utc;
 use graphql_core::generic_inputs::PrintReportSortInput;
-use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::standard_graphql_error::{
+    validate_auth, validate_rate_limit, StandardGraphqlError,
+};
 use graphql_core::{ContextExt, RequestUserData};
 use repository::query_json;
 use service::auth::{Resource, ResourceAccessRequest};
+use service::rate_limit::RateLimitedOperation;
 use service::report::definition::{GraphQlQuery, PrintReportSort, ReportDefinition, SQLQuery};
 use service::report::report_service::{ReportError, ResolvedReportQuery};
 
@@ -72,6 +75,7 @@ pub async fn generate_report(
             store_id: Some(store_id.to_string()),
         },
     )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::Report)?;
 
     let sort = sort.map(|s| s.to_domain());
     let service_provider = ctx.service_provider();
@@ -151,6 +155,7 @@ pub async fn generate_report_definition(
             store_id: Some(store_id.to_string()),
         },
     )?;
+    validate_rate_limit(ctx, &user, RateLimitedOperation::Report)?;
 
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.clone(), user.user_id)?;
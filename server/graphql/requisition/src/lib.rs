@@ -4,14 +4,14 @@ use graphql_core::pagination::PaginationInput;
 use graphql_types::types::program_indicator::{
     ProgramIndicatorFilterInput, ProgramIndicatorResponse, ProgramIndicatorSortInput,
 };
-use graphql_types::types::RequisitionNodeType;
+use graphql_types::types::{RequisitionApprovalStepNode, RequisitionNodeType};
 
 pub mod mutations;
 mod program_indicator;
 mod program_settings;
 mod requisition_queries;
 
-use self::mutations::{request_requisition, response_requisition};
+use self::mutations::{approval_step, request_requisition, response_requisition};
 use self::requisition_queries::*;
 use mutations::update_indicator_value::{
     self, UpdateIndicatorValueInput, UpdateIndicatorValueResponse,
@@ -57,6 +57,24 @@ impl RequisitionQueries {
         get_requisition_by_number(ctx, &store_id, requisition_number, r#type)
     }
 
+    /// Approval chain steps configured for the supplying store
+    pub async fn requisition_approval_steps(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<Vec<RequisitionApprovalStepNode>> {
+        get_requisition_approval_steps(ctx, &store_id)
+    }
+
+    /// Response requisitions where the current user is the approver of the next step
+    pub async fn requisitions_pending_my_approval(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<RequisitionsResponse> {
+        get_requisitions_pending_my_approval(ctx, &store_id)
+    }
+
     pub async fn supplier_program_requisition_settings(
         &self,
         ctx: &Context<'_>,
@@ -250,6 +268,36 @@ impl RequisitionMutations {
     ) -> Result<UpdateIndicatorValueResponse> {
         update_indicator_value::update(ctx, store_id, input)
     }
+
+    /// Approve or deny the next step of response requisition approval chain,
+    /// line approved quantities can be approved, denied or adjusted at each step
+    async fn approve_response_requisition(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: response_requisition::approve::ApproveResponseRequisitionInput,
+    ) -> Result<response_requisition::approve::ApproveResponse> {
+        response_requisition::approve::approve(ctx, &store_id, input)
+    }
+
+    /// Configure response requisition approval chain step, only available on central server
+    async fn upsert_requisition_approval_step(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: approval_step::UpsertRequisitionApprovalStepInput,
+    ) -> Result<approval_step::UpsertRequisitionApprovalStepResponse> {
+        approval_step::upsert_requisition_approval_step(ctx, store_id, input)
+    }
+
+    async fn delete_requisition_approval_step(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: approval_step::DeleteRequisitionApprovalStepInput,
+    ) -> Result<approval_step::DeleteRequisitionApprovalStepResponse> {
+        approval_step::delete_requisition_approval_step(ctx, store_id, input)
+    }
 }
 
 #[cfg(test)]
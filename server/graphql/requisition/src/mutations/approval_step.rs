@@ -0,0 +1,153 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{DeleteResponse, RequisitionApprovalStepNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    requisition::approval::{
+        DeleteRequisitionApprovalStep, DeleteRequisitionApprovalStepError,
+        UpsertRequisitionApprovalStep, UpsertRequisitionApprovalStepError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct UpsertRequisitionApprovalStepInput {
+    pub id: String,
+    /// Supplying store the step applies to, applies to all stores if not set
+    pub store_id: Option<String>,
+    /// Program the step applies to, applies to all programs if not set
+    pub program_id: Option<String>,
+    pub step_number: i32,
+    pub approver_user_id: String,
+    pub threshold_quantity: Option<f64>,
+    pub threshold_value: Option<f64>,
+}
+
+#[derive(Union)]
+pub enum UpsertRequisitionApprovalStepResponse {
+    Response(RequisitionApprovalStepNode),
+}
+
+#[derive(InputObject)]
+pub struct DeleteRequisitionApprovalStepInput {
+    pub id: String,
+}
+
+#[derive(Union)]
+pub enum DeleteRequisitionApprovalStepResponse {
+    Response(DeleteResponse),
+}
+
+pub fn upsert_requisition_approval_step(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: UpsertRequisitionApprovalStepInput,
+) -> Result<UpsertRequisitionApprovalStepResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .requisition_service
+        .upsert_requisition_approval_step(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertRequisitionApprovalStepResponse::Response(
+            RequisitionApprovalStepNode::from_domain(row),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertRequisitionApprovalStepError::NotCentralServer => Forbidden(formatted_error),
+                UpsertRequisitionApprovalStepError::StoreDoesNotExist
+                | UpsertRequisitionApprovalStepError::ProgramDoesNotExist
+                | UpsertRequisitionApprovalStepError::ApproverDoesNotExist
+                | UpsertRequisitionApprovalStepError::StepNumberMustBePositive
+                | UpsertRequisitionApprovalStepError::StepNumberAlreadyExists
+                | UpsertRequisitionApprovalStepError::ThresholdCannotBeNegative => {
+                    BadUserInput(formatted_error)
+                }
+                UpsertRequisitionApprovalStepError::CreatedRecordNotFound
+                | UpsertRequisitionApprovalStepError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertRequisitionApprovalStepInput {
+    pub fn to_domain(self) -> UpsertRequisitionApprovalStep {
+        let UpsertRequisitionApprovalStepInput {
+            id,
+            store_id,
+            program_id,
+            step_number,
+            approver_user_id,
+            threshold_quantity,
+            threshold_value,
+        } = self;
+
+        UpsertRequisitionApprovalStep {
+            id,
+            store_id,
+            program_id,
+            step_number,
+            approver_user_id,
+            threshold_quantity,
+            threshold_value,
+        }
+    }
+}
+
+pub fn delete_requisition_approval_step(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: DeleteRequisitionApprovalStepInput,
+) -> Result<DeleteRequisitionApprovalStepResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .requisition_service
+        .delete_requisition_approval_step(
+            &service_context,
+            DeleteRequisitionApprovalStep { id: input.id },
+        ) {
+        Ok(id) => Ok(DeleteRequisitionApprovalStepResponse::Response(
+            DeleteResponse(id),
+        )),
+        Err(error) => {
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                DeleteRequisitionApprovalStepError::NotCentralServer => {
+                    StandardGraphqlError::Forbidden(formatted_error)
+                }
+                DeleteRequisitionApprovalStepError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
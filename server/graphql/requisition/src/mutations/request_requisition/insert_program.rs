@@ -25,9 +25,20 @@ pub struct InsertProgramRequestRequisitionInput {
     pub their_reference: Option<String>,
     pub comment: Option<String>,
     pub program_order_type_id: String,
-    pub period_id: String,
+    /// Can be omitted for emergency order types, which are not tied to the period schedule
+    pub period_id: Option<String>,
     /// Defaults to 2 weeks from now
     pub expected_delivery_date: Option<NaiveDate>,
+    /// Required for emergency order types when emergency requisition reasons exist
+    pub emergency_reason_option_id: Option<String>,
+}
+
+pub struct EmergencyReasonNotProvided;
+#[Object]
+impl EmergencyReasonNotProvided {
+    pub async fn description(&self) -> &str {
+        "Reason is required for emergency orders"
+    }
 }
 
 #[derive(Interface)]
@@ -36,6 +47,7 @@ pub struct InsertProgramRequestRequisitionInput {
 pub enum InsertErrorInterface {
     MaxOrdersReachedForPeriod(MaxOrdersReachedForPeriod),
     SupplierNotValid(SupplierNotValid),
+    EmergencyReasonNotProvided(EmergencyReasonNotProvided),
 }
 
 #[derive(SimpleObject)]
@@ -99,9 +111,16 @@ pub fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
         ServiceError::SupplierNotValid => {
             return Ok(InsertErrorInterface::SupplierNotValid(SupplierNotValid))
         }
+        ServiceError::EmergencyReasonNotProvided => {
+            return Ok(InsertErrorInterface::EmergencyReasonNotProvided(
+                EmergencyReasonNotProvided,
+            ))
+        }
         // Standard Graphql Errors
         ServiceError::RequisitionAlreadyExists => BadUserInput(formatted_error),
         ServiceError::ProgramOrderTypeDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::PeriodNotProvided => BadUserInput(formatted_error),
+        ServiceError::EmergencyReasonDoesNotExist => BadUserInput(formatted_error),
 
         ServiceError::NewlyCreatedRequisitionDoesNotExist => InternalError(formatted_error),
         ServiceError::DatabaseError(_) | ServiceError::PluginError(_) => {
@@ -123,6 +142,7 @@ impl InsertProgramRequestRequisitionInput {
             expected_delivery_date,
             program_order_type_id,
             period_id,
+            emergency_reason_option_id,
         } = self;
 
         InsertProgramRequestRequisition {
@@ -135,6 +155,7 @@ impl InsertProgramRequestRequisitionInput {
                 .or(Some(date_now_with_offset(expected_delivery_date_offset()))),
             program_order_type_id,
             period_id,
+            emergency_reason_option_id,
         }
     }
 }
@@ -286,7 +307,8 @@ mod test {
                     comment: Some("comment input".to_string()),
                     expected_delivery_date: Some(NaiveDate::from_ymd_opt(2022, 1, 3).unwrap()),
                     program_order_type_id: "program_order_type_id".to_string(),
-                    period_id: "period_id".to_string(),
+                    period_id: Some("period_id".to_string()),
+                    emergency_reason_option_id: None,
                 }
             );
             Ok(Requisition {
@@ -4,7 +4,7 @@ use graphql_core::{
     generic_inputs::NullableUpdateInput,
     simple_generic_errors::{
         CannotEditRequisition, OrderingTooManyItems, OtherPartyNotACustomer,
-        OtherPartyNotASupplier, OtherPartyNotVisible, RecordNotFound,
+        OtherPartyNotASupplier, OtherPartyNotVisible, PluginValidationFailed, RecordNotFound,
     },
     standard_graphql_error::{validate_auth, StandardGraphqlError},
     ContextExt,
@@ -55,6 +55,7 @@ pub enum UpdateErrorInterface {
     CannotEditRequisition(CannotEditRequisition),
     OrderingTooManyItems(OrderingTooManyItems),
     RequisitionReasonsNotProvided(RequisitionReasonsNotProvided),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 #[derive(SimpleObject)]
@@ -92,11 +93,17 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
 
-    map_response(
-        service_provider
-            .requisition_service
-            .update_request_requisition(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .requisition_service
+        .update_request_requisition(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(requisition) => {
+            UpdateResponse::Response(requisition.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<Requisition, ServiceError>) -> Result<UpdateResponse> {
@@ -186,6 +193,11 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
                 OtherPartyNotVisible,
             ))
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         ServiceError::NotThisStoreRequisition
         | ServiceError::OrderTypeNotFound
         | ServiceError::NotARequestRequisition
@@ -0,0 +1,217 @@
+use async_graphql::*;
+
+use graphql_core::{
+    simple_generic_errors::{CannotEditRequisition, RecordNotFound},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::RequisitionNode;
+use repository::RequisitionApprovalDecision;
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    requisition::response_requisition::{
+        ApproveResponseRequisition as ServiceInput,
+        ApproveResponseRequisitionError as ServiceError, ApproveResponseRequisitionLine,
+        ApproveResponseRequisitionLineAction,
+    },
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RequisitionApprovalDecisionInput {
+    Approved,
+    Denied,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ApproveResponseRequisitionLineActionInput {
+    /// Approve requested quantity
+    Approve,
+    /// Set approved quantity to zero
+    Deny,
+    /// Approve `approvedQuantity`
+    Adjust,
+}
+
+#[derive(InputObject)]
+pub struct ApproveResponseRequisitionLineInput {
+    pub id: String,
+    pub action: ApproveResponseRequisitionLineActionInput,
+    /// Required for Adjust action
+    pub approved_quantity: Option<f64>,
+    pub comment: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct ApproveResponseRequisitionInput {
+    pub id: String,
+    pub decision: RequisitionApprovalDecisionInput,
+    pub comment: Option<String>,
+    /// Lines not included keep their approved quantity (requested quantity on the first step)
+    pub lines: Option<Vec<ApproveResponseRequisitionLineInput>>,
+}
+
+pub struct NotCurrentApprover;
+#[Object]
+impl NotCurrentApprover {
+    pub async fn description(&self) -> &str {
+        "User is not the approver of the next approval step"
+    }
+}
+
+pub struct RequisitionNotPendingApproval;
+#[Object]
+impl RequisitionNotPendingApproval {
+    pub async fn description(&self) -> &str {
+        "Requisition is not pending approval"
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "ApproveResponseRequisitionErrorInterface")]
+#[graphql(field(name = "description", ty = "String"))]
+pub enum ApproveErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotEditRequisition(CannotEditRequisition),
+    NotCurrentApprover(NotCurrentApprover),
+    RequisitionNotPendingApproval(RequisitionNotPendingApproval),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "ApproveResponseRequisitionError")]
+pub struct ApproveError {
+    pub error: ApproveErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "ApproveResponseRequisitionResponse")]
+pub enum ApproveResponse {
+    Error(ApproveError),
+    Response(RequisitionNode),
+}
+
+pub fn approve(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: ApproveResponseRequisitionInput,
+) -> Result<ApproveResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let response = match service_provider
+        .requisition_service
+        .approve_response_requisition(&service_context, input.to_domain()?)
+    {
+        Ok(requisition) => ApproveResponse::Response(RequisitionNode::from_domain(requisition)),
+        Err(error) => ApproveResponse::Error(ApproveError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(response)
+}
+
+impl ApproveResponseRequisitionInput {
+    pub fn to_domain(self) -> Result<ServiceInput> {
+        let ApproveResponseRequisitionInput {
+            id,
+            decision,
+            comment,
+            lines,
+        } = self;
+
+        let lines = lines
+            .unwrap_or_default()
+            .into_iter()
+            .map(ApproveResponseRequisitionLineInput::to_domain)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ServiceInput {
+            id,
+            decision: match decision {
+                RequisitionApprovalDecisionInput::Approved => RequisitionApprovalDecision::Approved,
+                RequisitionApprovalDecisionInput::Denied => RequisitionApprovalDecision::Denied,
+            },
+            comment,
+            lines,
+        })
+    }
+}
+
+impl ApproveResponseRequisitionLineInput {
+    pub fn to_domain(self) -> Result<ApproveResponseRequisitionLine> {
+        let ApproveResponseRequisitionLineInput {
+            id,
+            action,
+            approved_quantity,
+            comment,
+        } = self;
+
+        let action = match (action, approved_quantity) {
+            (ApproveResponseRequisitionLineActionInput::Approve, _) => {
+                ApproveResponseRequisitionLineAction::Approve
+            }
+            (ApproveResponseRequisitionLineActionInput::Deny, _) => {
+                ApproveResponseRequisitionLineAction::Deny
+            }
+            (ApproveResponseRequisitionLineActionInput::Adjust, Some(quantity)) => {
+                ApproveResponseRequisitionLineAction::Adjust(quantity)
+            }
+            (ApproveResponseRequisitionLineActionInput::Adjust, None) => {
+                return Err(StandardGraphqlError::BadUserInput(format!(
+                    "Approved quantity is required to adjust line {id}"
+                ))
+                .extend())
+            }
+        };
+
+        Ok(ApproveResponseRequisitionLine {
+            id,
+            action,
+            comment,
+        })
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<ApproveErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{error:#?}");
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::RequisitionDoesNotExist => {
+            return Ok(ApproveErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::CannotEditRequisition => {
+            return Ok(ApproveErrorInterface::CannotEditRequisition(
+                CannotEditRequisition {},
+            ))
+        }
+        ServiceError::NotCurrentApprover => {
+            return Ok(ApproveErrorInterface::NotCurrentApprover(
+                NotCurrentApprover,
+            ))
+        }
+        ServiceError::NotPendingApproval | ServiceError::NoApprovalStepPending => {
+            return Ok(ApproveErrorInterface::RequisitionNotPendingApproval(
+                RequisitionNotPendingApproval,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotThisStoreRequisition => BadUserInput(formatted_error),
+        ServiceError::NotAResponseRequisition => BadUserInput(formatted_error),
+        ServiceError::LineDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::ApprovedQuantityCannotBeNegative(_) => BadUserInput(formatted_error),
+        ServiceError::UpdatedRequisitionDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
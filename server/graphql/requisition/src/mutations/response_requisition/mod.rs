@@ -1,4 +1,5 @@
 pub(crate) mod add_from_master_list;
+pub(crate) mod approve;
 pub(crate) mod create_requisition_shipment;
 pub mod delete;
 pub(crate) mod insert;
@@ -1,7 +1,9 @@
 use async_graphql::*;
 
 use graphql_core::{
-    simple_generic_errors::{CannotEditRequisition, OrderingTooManyItems, RecordNotFound},
+    simple_generic_errors::{
+        CannotEditRequisition, OrderingTooManyItems, PluginValidationFailed, RecordNotFound,
+    },
     standard_graphql_error::{validate_auth, StandardGraphqlError},
     ContextExt,
 };
@@ -55,6 +57,7 @@ pub enum UpdateErrorInterface {
     CannotEditRequisition(CannotEditRequisition),
     RequisitionReasonsNotProvided(RequisitionReasonsNotProvided),
     OrderingTooManyItems(OrderingTooManyItems),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 #[derive(SimpleObject)]
@@ -86,7 +89,10 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
         .requisition_service
         .update_response_requisition(&service_context, input.to_domain())
     {
-        Ok(requisition) => UpdateResponse::Response(RequisitionNode::from_domain(requisition)),
+        Ok(requisition) => UpdateResponse::Response(
+            RequisitionNode::from_domain(requisition)
+                .with_plugin_warnings(service_context.take_plugin_warnings()),
+        ),
         Err(error) => UpdateResponse::Error(UpdateError {
             error: map_error(error)?,
         }),
@@ -143,8 +149,15 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::NotThisStoreRequisition
         | ServiceError::OrderTypeNotFound
         | ServiceError::NotAResponseRequisition => BadUserInput(formatted_error),
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
         ServiceError::UpdatedRequisitionDoesNotExist => InternalError(formatted_error),
-        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) | ServiceError::PluginError(_) => {
+            InternalError(formatted_error)
+        }
     };
 
     Err(graphql_error.extend())
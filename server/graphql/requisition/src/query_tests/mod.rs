@@ -1,4 +1,5 @@
 mod requisition;
+mod requisition_approval;
 mod requisition_by_number;
 mod requisition_loaders;
 mod requisitions;
@@ -0,0 +1,107 @@
+mod test {
+    use async_graphql::EmptyMutation;
+    use graphql_core::{assert_graphql_query, test_helpers::setup_graphql_test};
+    use repository::{
+        mock::{mock_name_a, mock_new_response_requisition_for_update_test, MockDataInserts},
+        RepositoryError, Requisition, RequisitionApprovalStepRow, StorageConnectionManager,
+    };
+    use serde_json::json;
+
+    use service::{
+        requisition::RequisitionServiceTrait,
+        service_provider::{ServiceContext, ServiceProvider},
+    };
+
+    use crate::RequisitionQueries;
+
+    pub struct TestService;
+
+    impl RequisitionServiceTrait for TestService {
+        fn get_requisition_approval_steps(
+            &self,
+            _: &ServiceContext,
+            store_id: &str,
+        ) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+            Ok(vec![RequisitionApprovalStepRow {
+                id: "step_1".to_string(),
+                store_id: Some(store_id.to_string()),
+                step_number: 1,
+                approver_user_id: "user_a".to_string(),
+                threshold_quantity: Some(5.0),
+                ..Default::default()
+            }])
+        }
+
+        fn get_requisitions_pending_my_approval(
+            &self,
+            _: &ServiceContext,
+            _: &str,
+        ) -> Result<Vec<Requisition>, RepositoryError> {
+            Ok(vec![Requisition {
+                requisition_row: mock_new_response_requisition_for_update_test(),
+                name_row: mock_name_a(),
+                ..Default::default()
+            }])
+        }
+    }
+
+    fn service_provider(connection_manager: &StorageConnectionManager) -> ServiceProvider {
+        let mut service_provider = ServiceProvider::new(connection_manager.clone());
+        service_provider.requisition_service = Box::new(TestService);
+        service_provider
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_requisition_approval_queries() {
+        let (_, _, connection_manager, settings) = setup_graphql_test(
+            RequisitionQueries,
+            EmptyMutation,
+            "test_graphql_requisition_approval_queries",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let query = r#"
+        query {
+          requisitionApprovalSteps(storeId: \"store_a\") {
+            id
+            storeId
+            stepNumber
+            approverUserId
+            thresholdQuantity
+          }
+          requisitionsPendingMyApproval(storeId: \"store_a\") {
+            ... on RequisitionConnector {
+              nodes {
+                id
+              }
+              totalCount
+            }
+          }
+       }
+        "#;
+
+        let expected = json!({
+            "requisitionApprovalSteps": [{
+                "id": "step_1",
+                "storeId": "store_a",
+                "stepNumber": 1,
+                "approverUserId": "user_a",
+                "thresholdQuantity": 5.0
+            }],
+            "requisitionsPendingMyApproval": {
+                "nodes": [{ "id": mock_new_response_requisition_for_update_test().id }],
+                "totalCount": 1
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            query,
+            &None,
+            &expected,
+            Some(service_provider(&connection_manager))
+        );
+    }
+}
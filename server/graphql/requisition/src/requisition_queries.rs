@@ -11,14 +11,18 @@ use graphql_core::{
     ContextExt,
 };
 use graphql_types::types::{
-    RequisitionConnector, RequisitionNode, RequisitionNodeStatus, RequisitionNodeType,
+    RequisitionApprovalStepNode, RequisitionConnector, RequisitionNode, RequisitionNodeStatus,
+    RequisitionNodeType,
 };
 use repository::{
     DateFilter, DatetimeFilter, EqualFilter, PaginationOption, RequisitionStatus, RequisitionType,
     StringFilter,
 };
 use repository::{RequisitionFilter, RequisitionSort, RequisitionSortField};
-use service::auth::{Resource, ResourceAccessRequest};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    usize_to_u32, ListResult,
+};
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq)]
 #[graphql(rename_items = "camelCase")]
@@ -37,6 +41,7 @@ pub enum RequisitionSortFieldInput {
     OrderType,
     ProgramName,
     PeriodStartDate,
+    IsEmergency,
 }
 
 #[derive(InputObject)]
@@ -166,6 +171,58 @@ pub fn get_requisitions(
     ))
 }
 
+pub fn get_requisition_approval_steps(
+    ctx: &Context<'_>,
+    store_id: &str,
+) -> Result<Vec<RequisitionApprovalStepNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let steps = service_provider
+        .requisition_service
+        .get_requisition_approval_steps(&service_context, store_id)?;
+
+    Ok(steps
+        .into_iter()
+        .map(RequisitionApprovalStepNode::from_domain)
+        .collect())
+}
+
+pub fn get_requisitions_pending_my_approval(
+    ctx: &Context<'_>,
+    store_id: &str,
+) -> Result<RequisitionsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
+
+    let requisitions = service_provider
+        .requisition_service
+        .get_requisitions_pending_my_approval(&service_context, store_id)?;
+
+    Ok(RequisitionsResponse::Response(
+        RequisitionConnector::from_domain(ListResult {
+            count: usize_to_u32(requisitions.len()),
+            rows: requisitions,
+        }),
+    ))
+}
+
 pub fn get_requisition_by_number(
     ctx: &Context<'_>,
     store_id: &str,
@@ -0,0 +1,51 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{KitAssemblyConnector, KitComponentConnector};
+use service::auth::{Resource, ResourceAccessRequest};
+
+pub fn kit_components(
+    ctx: &Context<'_>,
+    store_id: String,
+    kit_item_id: String,
+) -> Result<KitComponentConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let kit_components = service_provider
+        .kit_service
+        .get_kit_components(&service_context, &kit_item_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(KitComponentConnector::from_vec(kit_components))
+}
+
+pub fn kit_assemblies(ctx: &Context<'_>, store_id: String) -> Result<KitAssemblyConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let kit_assemblies = service_provider
+        .kit_service
+        .get_kit_assemblies(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(KitAssemblyConnector::from_vec(kit_assemblies))
+}
@@ -1,4 +1,7 @@
+mod kit;
 pub mod mutations;
+mod recall;
+mod serial_number;
 use async_graphql::*;
 use chrono::{DateTime, Utc};
 use graphql_core::{
@@ -244,6 +247,70 @@ impl StockLineQueries {
             StockLineConnector::from_domain(stock_lines),
         ))
     }
+
+    /// Manufacturer recalls raised on central server
+    pub async fn recalls(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(
+            desc = "Only return recalls that have not been closed",
+            default = false
+        )]
+        active_only: bool,
+    ) -> Result<RecallConnector> {
+        recall::recalls(ctx, store_id, active_only)
+    }
+
+    /// Recall status report, tracing where recalled stock is held and where it was issued
+    pub async fn recall_trace(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        recall_id: String,
+    ) -> Result<RecallTraceNode> {
+        recall::recall_trace(ctx, store_id, recall_id)
+    }
+
+    /// Serialised units held in stock line
+    pub async fn stock_line_serial_numbers(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        stock_line_id: String,
+    ) -> Result<Vec<SerialNumberNode>> {
+        serial_number::stock_line_serial_numbers(ctx, store_id, stock_line_id)
+    }
+
+    /// Custody history of a serialised unit: receipts, issues and stocktake counts, oldest first
+    pub async fn serial_number_history(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        item_id: String,
+        serial_number: String,
+    ) -> Result<Vec<SerialNumberHistoryNode>> {
+        serial_number::serial_number_history(ctx, store_id, item_id, serial_number)
+    }
+
+    /// Bill of materials of a kit item
+    pub async fn kit_components(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        kit_item_id: String,
+    ) -> Result<KitComponentConnector> {
+        kit::kit_components(ctx, store_id, kit_item_id)
+    }
+
+    /// Kit assemblies and disassemblies done in store, latest first
+    pub async fn kit_assemblies(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<KitAssemblyConnector> {
+        kit::kit_assemblies(ctx, store_id)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -268,4 +335,94 @@ impl StockLineMutations {
     ) -> Result<mutations::UpdateResponse> {
         mutations::update(ctx, &store_id, input)
     }
+
+    /// Raise or update a recall, matching stock is put on hold in all stores (central server only)
+    async fn upsert_recall(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::recall::UpsertRecallInput,
+    ) -> Result<mutations::recall::UpsertRecallResponse> {
+        mutations::recall::upsert_recall(ctx, store_id, input)
+    }
+
+    async fn close_recall(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        recall_id: String,
+    ) -> Result<mutations::recall::CloseRecallResponse> {
+        mutations::recall::close_recall(ctx, store_id, recall_id)
+    }
+
+    /// Create supplier returns for recalled stock available in store, one per supplier
+    async fn create_recall_supplier_returns(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        recall_id: String,
+    ) -> Result<mutations::recall::CreateRecallSupplierReturnsResponse> {
+        mutations::recall::create_recall_supplier_returns(ctx, store_id, recall_id)
+    }
+
+    /// Receive (inbound lines) or pick (outbound lines) specific units of a serialised item
+    async fn set_invoice_line_serial_numbers(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::serial_number::SetInvoiceLineSerialNumbersInput,
+    ) -> Result<Vec<SerialNumberNode>> {
+        mutations::serial_number::set_invoice_line_serial_numbers(ctx, store_id, input)
+    }
+
+    /// Compare counted units of a stocktake line against units held, uncounted units are marked
+    /// as missing
+    async fn verify_stocktake_serial_numbers(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::serial_number::VerifyStocktakeSerialNumbersInput,
+    ) -> Result<StocktakeSerialNumbersNode> {
+        mutations::serial_number::verify_stocktake_serial_numbers(ctx, store_id, input)
+    }
+
+    /// Add or update a bill of materials line of a kit item (central server only)
+    async fn upsert_kit_component(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::kit::UpsertKitComponentInput,
+    ) -> Result<mutations::kit::UpsertKitComponentResponse> {
+        mutations::kit::upsert_kit_component(ctx, store_id, input)
+    }
+
+    async fn delete_kit_component(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<mutations::kit::DeleteKitComponentResponse> {
+        mutations::kit::delete_kit_component(ctx, store_id, id)
+    }
+
+    /// Make kits from component stock according to the kit's bill of materials, the kit stock
+    /// line expires with its first expiring component
+    async fn assemble_kit(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::kit::AssembleKitInput,
+    ) -> Result<mutations::kit::AssembleKitResponse> {
+        mutations::kit::assemble_kit(ctx, store_id, input)
+    }
+
+    /// Break kits back down into component stock
+    async fn disassemble_kit(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: mutations::kit::DisassembleKitInput,
+    ) -> Result<mutations::kit::DisassembleKitResponse> {
+        mutations::kit::disassemble_kit(ctx, store_id, input)
+    }
 }
@@ -0,0 +1,290 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{DeleteResponse, KitAssemblyNode, KitComponentNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    kit::{
+        AssembleKit, AssembleKitError, DeleteKitComponentError, DisassembleKit,
+        DisassembleKitError, UpsertKitComponent, UpsertKitComponentError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct UpsertKitComponentInput {
+    pub id: String,
+    pub kit_item_id: String,
+    pub component_item_id: String,
+    /// Units of the component in one unit of the kit
+    pub quantity: f64,
+}
+
+#[derive(InputObject)]
+pub struct AssembleKitInput {
+    pub id: String,
+    pub kit_item_id: String,
+    /// Units of the kit item to make
+    pub number_of_kits: f64,
+    /// Component stock lines to use, available component stock is used first expiry first if
+    /// not set
+    pub component_stock_line_ids: Option<Vec<String>>,
+    pub batch: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct DisassembleKitInput {
+    pub id: String,
+    pub kit_stock_line_id: String,
+    /// Units of the kit item to break down
+    pub number_of_kits: f64,
+    /// Location of the component stock, defaults to the kit's location
+    pub location_id: Option<String>,
+}
+
+#[derive(Union)]
+pub enum UpsertKitComponentResponse {
+    Response(KitComponentNode),
+}
+
+#[derive(Union)]
+pub enum DeleteKitComponentResponse {
+    Response(DeleteResponse),
+}
+
+#[derive(Union)]
+pub enum AssembleKitResponse {
+    Response(KitAssemblyNode),
+}
+
+#[derive(Union)]
+pub enum DisassembleKitResponse {
+    Response(KitAssemblyNode),
+}
+
+pub fn upsert_kit_component(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: UpsertKitComponentInput,
+) -> Result<UpsertKitComponentResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .kit_service
+        .upsert_kit_component(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertKitComponentResponse::Response(
+            KitComponentNode::from_domain(row),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertKitComponentError::NotCentralServer => Forbidden(formatted_error),
+                UpsertKitComponentError::KitItemDoesNotExist
+                | UpsertKitComponentError::ComponentItemDoesNotExist
+                | UpsertKitComponentError::KitCannotBeItsOwnComponent
+                | UpsertKitComponentError::ComponentAlreadyInKit
+                | UpsertKitComponentError::QuantityMustBePositive => BadUserInput(formatted_error),
+                UpsertKitComponentError::CreatedRecordNotFound
+                | UpsertKitComponentError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn delete_kit_component(
+    ctx: &Context<'_>,
+    store_id: String,
+    id: String,
+) -> Result<DeleteKitComponentResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .kit_service
+        .delete_kit_component(&service_context, &id)
+    {
+        Ok(id) => Ok(DeleteKitComponentResponse::Response(DeleteResponse(id))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                DeleteKitComponentError::NotCentralServer => Forbidden(formatted_error),
+                DeleteKitComponentError::KitComponentDoesNotExist => BadUserInput(formatted_error),
+                DeleteKitComponentError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn assemble_kit(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: AssembleKitInput,
+) -> Result<AssembleKitResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::CreateRepack,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .kit_service
+        .assemble_kit(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(AssembleKitResponse::Response(KitAssemblyNode::from_domain(
+            row,
+        ))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                AssembleKitError::KitAssemblyAlreadyExists
+                | AssembleKitError::KitItemDoesNotExist
+                | AssembleKitError::KitHasNoComponents
+                | AssembleKitError::NumberOfKitsMustBePositive
+                | AssembleKitError::CannotHaveFractionalKits
+                | AssembleKitError::LocationDoesNotExist
+                | AssembleKitError::StockLineDoesNotExist(_)
+                | AssembleKitError::StockLineIsNotAComponent(_)
+                | AssembleKitError::NotEnoughComponentStock { .. } => BadUserInput(formatted_error),
+                AssembleKitError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn disassemble_kit(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: DisassembleKitInput,
+) -> Result<DisassembleKitResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::CreateRepack,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .kit_service
+        .disassemble_kit(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(DisassembleKitResponse::Response(
+            KitAssemblyNode::from_domain(row),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                DisassembleKitError::KitAssemblyAlreadyExists
+                | DisassembleKitError::StockLineDoesNotExist
+                | DisassembleKitError::NotThisStoreStockLine
+                | DisassembleKitError::StockLineIsNotAKit
+                | DisassembleKitError::NumberOfKitsMustBePositive
+                | DisassembleKitError::CannotHaveFractionalKits
+                | DisassembleKitError::NotEnoughKitStock { .. }
+                | DisassembleKitError::LocationDoesNotExist
+                | DisassembleKitError::ComponentItemDoesNotExist(_) => {
+                    BadUserInput(formatted_error)
+                }
+                DisassembleKitError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertKitComponentInput {
+    pub fn to_domain(self) -> UpsertKitComponent {
+        let UpsertKitComponentInput {
+            id,
+            kit_item_id,
+            component_item_id,
+            quantity,
+        } = self;
+
+        UpsertKitComponent {
+            id,
+            kit_item_id,
+            component_item_id,
+            quantity,
+        }
+    }
+}
+
+impl AssembleKitInput {
+    pub fn to_domain(self) -> AssembleKit {
+        let AssembleKitInput {
+            id,
+            kit_item_id,
+            number_of_kits,
+            component_stock_line_ids,
+            batch,
+            location_id,
+        } = self;
+
+        AssembleKit {
+            id,
+            kit_item_id,
+            number_of_kits,
+            component_stock_line_ids: component_stock_line_ids.unwrap_or_default(),
+            batch,
+            location_id,
+        }
+    }
+}
+
+impl DisassembleKitInput {
+    pub fn to_domain(self) -> DisassembleKit {
+        let DisassembleKitInput {
+            id,
+            kit_stock_line_id,
+            number_of_kits,
+            location_id,
+        } = self;
+
+        DisassembleKit {
+            id,
+            kit_stock_line_id,
+            number_of_kits,
+            location_id,
+        }
+    }
+}
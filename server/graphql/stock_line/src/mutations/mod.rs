@@ -2,3 +2,6 @@ pub mod insert;
 pub use insert::*;
 pub mod update;
 pub use update::*;
+pub mod kit;
+pub mod recall;
+pub mod serial_number;
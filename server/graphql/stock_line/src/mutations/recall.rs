@@ -0,0 +1,180 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{InvoiceConnector, RecallNode, RecallSeverityNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    recall::{CloseRecallError, CreateRecallSupplierReturnsError, UpsertRecall, UpsertRecallError},
+};
+
+#[derive(InputObject)]
+pub struct UpsertRecallInput {
+    pub id: String,
+    pub item_id: String,
+    /// Recall all batches of the item if not set
+    pub batch: Option<String>,
+    /// Recall all variants of the item if not set
+    pub item_variant_id: Option<String>,
+    pub reason: String,
+    pub severity: RecallSeverityNode,
+}
+
+#[derive(Union)]
+pub enum UpsertRecallResponse {
+    Response(RecallNode),
+}
+
+#[derive(Union)]
+pub enum CloseRecallResponse {
+    Response(RecallNode),
+}
+
+#[derive(Union)]
+pub enum CreateRecallSupplierReturnsResponse {
+    Response(InvoiceConnector),
+}
+
+pub fn upsert_recall(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: UpsertRecallInput,
+) -> Result<UpsertRecallResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .recall_service
+        .upsert_recall(&service_context, input.to_domain())
+    {
+        Ok(row) => Ok(UpsertRecallResponse::Response(RecallNode::from_domain(row))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                UpsertRecallError::NotCentralServer => Forbidden(formatted_error),
+                UpsertRecallError::ItemDoesNotExist
+                | UpsertRecallError::ItemVariantDoesNotExist
+                | UpsertRecallError::ItemVariantDoesNotBelongToItem
+                | UpsertRecallError::ReasonNotProvided
+                | UpsertRecallError::RecallIsClosed => BadUserInput(formatted_error),
+                UpsertRecallError::CreatedRecordNotFound | UpsertRecallError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl UpsertRecallInput {
+    pub fn to_domain(self) -> UpsertRecall {
+        let UpsertRecallInput {
+            id,
+            item_id,
+            batch,
+            item_variant_id,
+            reason,
+            severity,
+        } = self;
+
+        UpsertRecall {
+            id,
+            item_id,
+            batch,
+            item_variant_id,
+            reason,
+            severity: severity.into(),
+        }
+    }
+}
+
+pub fn close_recall(
+    ctx: &Context<'_>,
+    store_id: String,
+    recall_id: String,
+) -> Result<CloseRecallResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.basic_context()?;
+
+    match service_provider
+        .recall_service
+        .close_recall(&service_context, &recall_id)
+    {
+        Ok(row) => Ok(CloseRecallResponse::Response(RecallNode::from_domain(row))),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                CloseRecallError::NotCentralServer => Forbidden(formatted_error),
+                CloseRecallError::RecallDoesNotExist | CloseRecallError::RecallAlreadyClosed => {
+                    BadUserInput(formatted_error)
+                }
+                CloseRecallError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn create_recall_supplier_returns(
+    ctx: &Context<'_>,
+    store_id: String,
+    recall_id: String,
+) -> Result<CreateRecallSupplierReturnsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateSupplierReturn,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .recall_service
+        .create_recall_supplier_returns(&service_context, &recall_id)
+    {
+        Ok(invoices) => Ok(CreateRecallSupplierReturnsResponse::Response(
+            InvoiceConnector::from_vec(invoices),
+        )),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                CreateRecallSupplierReturnsError::RecallDoesNotExist
+                | CreateRecallSupplierReturnsError::RecallIsClosed
+                | CreateRecallSupplierReturnsError::NoStockToReturn
+                | CreateRecallSupplierReturnsError::SupplierReturnError { .. } => {
+                    BadUserInput(formatted_error)
+                }
+                CreateRecallSupplierReturnsError::DatabaseError(_) => {
+                    InternalError(formatted_error)
+                }
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
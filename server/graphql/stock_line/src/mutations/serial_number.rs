@@ -0,0 +1,141 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{SerialNumberNode, StocktakeSerialNumbersNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    serial_number::{
+        SetInvoiceLineSerialNumbers, SetInvoiceLineSerialNumbersError,
+        VerifyStocktakeSerialNumbers, VerifyStocktakeSerialNumbersError,
+    },
+};
+
+#[derive(InputObject)]
+pub struct SetInvoiceLineSerialNumbersInput {
+    pub invoice_line_id: String,
+    /// Serial numbers or scanned GS1 codes, replaces serial numbers already on the line
+    pub serial_numbers: Vec<String>,
+}
+
+#[derive(InputObject)]
+pub struct VerifyStocktakeSerialNumbersInput {
+    pub stocktake_line_id: String,
+    /// Serial numbers or scanned GS1 codes of units counted
+    pub serial_numbers: Vec<String>,
+}
+
+pub fn set_invoice_line_serial_numbers(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: SetInvoiceLineSerialNumbersInput,
+) -> Result<Vec<SerialNumberNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .serial_number_service
+        .set_invoice_line_serial_numbers(&service_context, input.to_domain())
+    {
+        Ok(rows) => Ok(SerialNumberNode::from_vec(rows)),
+        Err(error) => {
+            use SetInvoiceLineSerialNumbersError as ServiceError;
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                ServiceError::InvoiceLineDoesNotExist
+                | ServiceError::NotThisStoreInvoice
+                | ServiceError::CannotEditInvoice
+                | ServiceError::NotAStockLine
+                | ServiceError::InvalidSerialNumber(_)
+                | ServiceError::GtinDoesNotMatchItem(_)
+                | ServiceError::DuplicateSerialNumber(_)
+                | ServiceError::TooManySerialNumbers
+                | ServiceError::SerialNumberAlreadyInStock(_)
+                | ServiceError::SerialNumberNotAvailable(_) => BadUserInput(formatted_error),
+                ServiceError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+pub fn verify_stocktake_serial_numbers(
+    ctx: &Context<'_>,
+    store_id: String,
+    input: VerifyStocktakeSerialNumbersInput,
+) -> Result<StocktakeSerialNumbersNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .serial_number_service
+        .verify_stocktake_serial_numbers(&service_context, input.to_domain())
+    {
+        Ok(serial_numbers) => Ok(StocktakeSerialNumbersNode { serial_numbers }),
+        Err(error) => {
+            use StandardGraphqlError::*;
+            use VerifyStocktakeSerialNumbersError as ServiceError;
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                ServiceError::StocktakeLineDoesNotExist
+                | ServiceError::NotThisStoreStocktake
+                | ServiceError::StocktakeIsFinalised
+                | ServiceError::StocktakeLineHasNoStockLine
+                | ServiceError::InvalidSerialNumber(_)
+                | ServiceError::GtinDoesNotMatchItem(_) => BadUserInput(formatted_error),
+                ServiceError::DatabaseError(_) => InternalError(formatted_error),
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
+
+impl SetInvoiceLineSerialNumbersInput {
+    pub fn to_domain(self) -> SetInvoiceLineSerialNumbers {
+        let SetInvoiceLineSerialNumbersInput {
+            invoice_line_id,
+            serial_numbers,
+        } = self;
+
+        SetInvoiceLineSerialNumbers {
+            invoice_line_id,
+            serial_numbers,
+        }
+    }
+}
+
+impl VerifyStocktakeSerialNumbersInput {
+    pub fn to_domain(self) -> VerifyStocktakeSerialNumbers {
+        let VerifyStocktakeSerialNumbersInput {
+            stocktake_line_id,
+            serial_numbers,
+        } = self;
+
+        VerifyStocktakeSerialNumbers {
+            stocktake_line_id,
+            serial_numbers,
+        }
+    }
+}
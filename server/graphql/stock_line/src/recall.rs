@@ -0,0 +1,68 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{RecallConnector, RecallTraceNode};
+use service::{
+    auth::{Resource, ResourceAccessRequest},
+    recall::RecallTraceError,
+};
+
+pub fn recalls(ctx: &Context<'_>, store_id: String, active_only: bool) -> Result<RecallConnector> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let recalls = service_provider
+        .recall_service
+        .get_recalls(&service_context, active_only)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(RecallConnector::from_vec(recalls))
+}
+
+pub fn recall_trace(
+    ctx: &Context<'_>,
+    store_id: String,
+    recall_id: String,
+) -> Result<RecallTraceNode> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    match service_provider
+        .recall_service
+        .get_recall_trace(&service_context, &recall_id)
+    {
+        Ok(trace) => Ok(RecallTraceNode::from_domain(trace)),
+        Err(error) => {
+            let formatted_error = format!("{error:#?}");
+
+            let graphql_error = match error {
+                RecallTraceError::RecallDoesNotExist => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                RecallTraceError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+
+            Err(graphql_error.extend())
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{SerialNumberHistoryNode, SerialNumberNode};
+use service::auth::{Resource, ResourceAccessRequest};
+
+pub fn stock_line_serial_numbers(
+    ctx: &Context<'_>,
+    store_id: String,
+    stock_line_id: String,
+) -> Result<Vec<SerialNumberNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let serial_numbers = service_provider
+        .serial_number_service
+        .get_stock_line_serial_numbers(&service_context, &stock_line_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(SerialNumberNode::from_vec(serial_numbers))
+}
+
+pub fn serial_number_history(
+    ctx: &Context<'_>,
+    store_id: String,
+    item_id: String,
+    serial_number: String,
+) -> Result<Vec<SerialNumberHistoryNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLine,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context(store_id, user.user_id)?;
+
+    let history = service_provider
+        .serial_number_service
+        .get_serial_number_history(&service_context, &item_id, &serial_number)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(SerialNumberHistoryNode::from_vec(history))
+}
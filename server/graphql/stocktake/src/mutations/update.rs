@@ -1,7 +1,9 @@
 use async_graphql::*;
 
 use chrono::NaiveDate;
-use graphql_core::simple_generic_errors::{CannotEditStocktake, StocktakeIsLocked};
+use graphql_core::simple_generic_errors::{
+    CannotEditStocktake, PluginValidationFailed, StocktakeIsLocked,
+};
 use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
 use graphql_core::ContextExt;
 use graphql_types::generic_errors::{
@@ -74,6 +76,7 @@ pub enum UpdateErrorInterface {
     StocktakeIsLocked(StocktakeIsLocked),
     CannotEditStocktake(CannotEditStocktake),
     StockLinesReducedBelowZero(StockLinesReducedBelowZero),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 #[derive(SimpleObject)]
@@ -100,11 +103,17 @@ pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<U
 
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context(store_id.to_string(), user.user_id)?;
-    map_response(
-        service_provider
-            .stocktake_service
-            .update_stocktake(&service_context, input.to_domain()),
-    )
+    let result = service_provider
+        .stocktake_service
+        .update_stocktake(&service_context, input.to_domain());
+    let plugin_warnings = service_context.take_plugin_warnings();
+
+    map_response(result).map(|response| match response {
+        UpdateResponse::Response(stocktake) => {
+            UpdateResponse::Response(stocktake.with_plugin_warnings(plugin_warnings))
+        }
+        error => error,
+    })
 }
 
 pub fn map_response(from: Result<Stocktake, ServiceError>) -> Result<UpdateResponse> {
@@ -143,6 +152,11 @@ fn map_error(err: ServiceError) -> Result<UpdateErrorInterface> {
                 StockLinesReducedBelowZero(lines),
             ))
         }
+        ServiceError::PluginValidationFailed(failed) => {
+            return Ok(UpdateErrorInterface::PluginValidationFailed(
+                PluginValidationFailed(failed),
+            ))
+        }
 
         // Standard Graphql Errors
         // TODO some are structured errors (where can be changed concurrently)
@@ -152,6 +166,7 @@ fn map_error(err: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::InternalError(err) => InternalError(err),
         ServiceError::InsertStockInLineError { .. }
         | ServiceError::InsertStockOutLineError { .. }
+        | ServiceError::PluginError(_)
         | ServiceError::DatabaseError(_) => InternalError(formatted_error),
     };
 
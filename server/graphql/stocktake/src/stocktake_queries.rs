@@ -105,7 +105,7 @@ pub fn stocktakes(
             nodes: stocktakes
                 .rows
                 .into_iter()
-                .map(|stocktake| StocktakeNode { stocktake })
+                .map(|stocktake| StocktakeNode::from_domain(stocktake))
                 .collect(),
         })),
         Err(err) => Err(list_error_to_gql_err(err)),
@@ -140,7 +140,9 @@ pub fn stocktake(ctx: &Context<'_>, store_id: &str, id: &str) -> Result<Stocktak
     ) {
         Ok(mut stocktakes) => {
             let result = match stocktakes.rows.pop() {
-                Some(stocktake) => StocktakeResponse::Response(StocktakeNode { stocktake }),
+                Some(stocktake) => {
+                    StocktakeResponse::Response(StocktakeNode::from_domain(stocktake))
+                }
                 None => StocktakeResponse::Error(ErrorWrapper {
                     error: NodeErrorInterface::RecordNotFound(RecordNotFound {}),
                 }),
@@ -178,7 +180,9 @@ pub fn stocktake_by_number(
     ) {
         Ok(mut stocktakes) => {
             let result = match stocktakes.rows.pop() {
-                Some(stocktake) => StocktakeResponse::Response(StocktakeNode { stocktake }),
+                Some(stocktake) => {
+                    StocktakeResponse::Response(StocktakeNode::from_domain(stocktake))
+                }
                 None => StocktakeResponse::Error(ErrorWrapper {
                     error: NodeErrorInterface::RecordNotFound(RecordNotFound {}),
                 }),
@@ -112,6 +112,16 @@ pub enum ActivityLogNodeType {
     PatientCreated,
     PatientUpdated,
     InvoiceDateBackdated,
+    RequisitionApprovalStepApproved,
+    RequisitionDenied,
+    RequisitionLineApprovedQuantityAdjusted,
+    StockLineRecalled,
+    KitAssembled,
+    KitDisassembled,
+    DispensingAlertOverridden,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    ApiKeyUsed,
 }
 
 #[Object]
@@ -0,0 +1,185 @@
+use async_graphql::{dataloader::DataLoader, *};
+use graphql_core::{loader::ItemLoader, ContextExt};
+use repository::{DrugInteractionRow, ItemClinicalInfoRow};
+use service::dispensing_safety::DispensingAlert;
+
+use super::ItemNode;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::dispensing_safety::DispensingAlertType")]
+pub enum DispensingAlertTypeNode {
+    Allergy,
+    Interaction,
+    DuplicateTherapy,
+    MaximumDailyDose,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "service::dispensing_safety::DispensingAlertSeverity")]
+pub enum DispensingAlertSeverityNode {
+    Warning,
+    Error,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::DrugInteractionSeverity")]
+pub enum DrugInteractionSeverityNode {
+    Minor,
+    Moderate,
+    Major,
+    Contraindicated,
+}
+
+pub struct DispensingAlertNode {
+    pub alert: DispensingAlert,
+}
+
+#[Object]
+impl DispensingAlertNode {
+    pub async fn alert_type(&self) -> DispensingAlertTypeNode {
+        DispensingAlertTypeNode::from(self.alert.alert_type)
+    }
+
+    /// Errors need an override reason to dispense the item
+    pub async fn severity(&self) -> DispensingAlertSeverityNode {
+        DispensingAlertSeverityNode::from(self.alert.severity)
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.alert.item_id
+    }
+
+    /// Item on the prescription or current medication the alert is against
+    pub async fn related_item_id(&self) -> &Option<String> {
+        &self.alert.related_item_id
+    }
+
+    pub async fn related_item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        let Some(related_item_id) = &self.alert.related_item_id else {
+            return Ok(None);
+        };
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        Ok(loader
+            .load_one(related_item_id.clone())
+            .await?
+            .map(ItemNode::from_domain))
+    }
+
+    pub async fn message(&self) -> &str {
+        &self.alert.message
+    }
+}
+
+impl DispensingAlertNode {
+    pub fn from_domain(alert: DispensingAlert) -> DispensingAlertNode {
+        DispensingAlertNode { alert }
+    }
+
+    pub fn from_vec(alerts: Vec<DispensingAlert>) -> Vec<DispensingAlertNode> {
+        alerts
+            .into_iter()
+            .map(DispensingAlertNode::from_domain)
+            .collect()
+    }
+}
+
+pub struct ItemClinicalInfoNode {
+    pub item_clinical_info: ItemClinicalInfoRow,
+}
+
+#[Object]
+impl ItemClinicalInfoNode {
+    pub async fn id(&self) -> &str {
+        &self.item_clinical_info.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.item_clinical_info.item_id
+    }
+
+    /// WHO Anatomical Therapeutic Chemical code, e.g. `N02BE01`
+    pub async fn atc_code(&self) -> &Option<String> {
+        &self.item_clinical_info.atc_code
+    }
+
+    /// Units of the item
+    pub async fn max_daily_dose(&self) -> Option<f64> {
+        self.item_clinical_info.max_daily_dose
+    }
+}
+
+impl ItemClinicalInfoNode {
+    pub fn from_domain(item_clinical_info: ItemClinicalInfoRow) -> ItemClinicalInfoNode {
+        ItemClinicalInfoNode { item_clinical_info }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ItemClinicalInfoConnector {
+    total_count: u32,
+    nodes: Vec<ItemClinicalInfoNode>,
+}
+
+impl ItemClinicalInfoConnector {
+    pub fn from_vec(rows: Vec<ItemClinicalInfoRow>) -> ItemClinicalInfoConnector {
+        ItemClinicalInfoConnector {
+            total_count: rows.len() as u32,
+            nodes: rows
+                .into_iter()
+                .map(ItemClinicalInfoNode::from_domain)
+                .collect(),
+        }
+    }
+}
+
+pub struct DrugInteractionNode {
+    pub drug_interaction: DrugInteractionRow,
+}
+
+#[Object]
+impl DrugInteractionNode {
+    pub async fn id(&self) -> &str {
+        &self.drug_interaction.id
+    }
+
+    /// ATC code or group, e.g. `B01AA` matches all vitamin K antagonists
+    pub async fn atc_code_a(&self) -> &str {
+        &self.drug_interaction.atc_code_a
+    }
+
+    pub async fn atc_code_b(&self) -> &str {
+        &self.drug_interaction.atc_code_b
+    }
+
+    pub async fn severity(&self) -> DrugInteractionSeverityNode {
+        DrugInteractionSeverityNode::from(self.drug_interaction.severity)
+    }
+
+    pub async fn description(&self) -> &str {
+        &self.drug_interaction.description
+    }
+}
+
+impl DrugInteractionNode {
+    pub fn from_domain(drug_interaction: DrugInteractionRow) -> DrugInteractionNode {
+        DrugInteractionNode { drug_interaction }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct DrugInteractionConnector {
+    total_count: u32,
+    nodes: Vec<DrugInteractionNode>,
+}
+
+impl DrugInteractionConnector {
+    pub fn from_vec(rows: Vec<DrugInteractionRow>) -> DrugInteractionConnector {
+        DrugInteractionConnector {
+            total_count: rows.len() as u32,
+            nodes: rows
+                .into_iter()
+                .map(DrugInteractionNode::from_domain)
+                .collect(),
+        }
+    }
+}
@@ -13,13 +13,15 @@ use graphql_core::{
         NameByIdLoaderInput, ProgramByIdLoader, PurchaseOrderLineByIdLoader, ReasonOptionLoader,
         StockLineByIdLoader, VVMStatusByIdLoader,
     },
-    simple_generic_errors::NodeError,
+    simple_generic_errors::{NodeError, PluginValidationMessageNode},
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
 };
 use repository::{location::Location, InvoiceLine, InvoiceLineRow, InvoiceLineStatsRow, ItemRow};
 use serde::Serialize;
-use service::{usize_to_u32, ListResult};
+use service::{
+    backend_plugin::types::validate_mutation::PluginValidationMessage, usize_to_u32, ListResult,
+};
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
@@ -44,6 +46,7 @@ pub enum InvoiceLineStatusType {
 
 pub struct InvoiceLineNode {
     invoice_line: InvoiceLine,
+    plugin_warnings: Vec<PluginValidationMessage>,
 }
 
 #[derive(SimpleObject)]
@@ -342,6 +345,15 @@ impl InvoiceLineNode {
             .map(PurchaseOrderLineNode::from_domain);
         Ok(result)
     }
+
+    /// Non blocking messages from validate_mutation plugins, only returned in mutation responses
+    pub async fn plugin_warnings(&self) -> Vec<PluginValidationMessageNode> {
+        self.plugin_warnings
+            .iter()
+            .cloned()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
 }
 
 #[derive(Union)]
@@ -387,7 +399,17 @@ impl InvoiceLineConnector {
 
 impl InvoiceLineNode {
     pub fn from_domain(invoice_line: InvoiceLine) -> InvoiceLineNode {
-        InvoiceLineNode { invoice_line }
+        InvoiceLineNode {
+            invoice_line,
+            plugin_warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_plugin_warnings(self, plugin_warnings: Vec<PluginValidationMessage>) -> Self {
+        Self {
+            plugin_warnings,
+            ..self
+        }
     }
 
     pub fn row(&self) -> &InvoiceLineRow {
@@ -433,36 +455,34 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query(&self) -> InvoiceLineNode {
-                InvoiceLineNode {
-                    invoice_line: repository::InvoiceLine {
-                        invoice_line_row: InvoiceLineRow {
-                            id: "line_id".to_string(),
-                            invoice_id: "line_invoice_id".to_string(),
-                            r#type: InvoiceLineType::Service,
-                            item_link_id: "line_item_id".to_string(),
-                            item_name: "line_item_name".to_string(),
-                            item_code: "line_item_code".to_string(),
-                            pack_size: 1.0,
-                            number_of_packs: 2.0,
-                            batch: Some("line_batch".to_string()),
-                            expiry_date: Some(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()),
-                            location_id: Some("line_location_id".to_string()),
-                            note: None,
-                            ..Default::default()
-                        },
-                        invoice_row: InvoiceRow::default(),
-                        item_row: ItemRow {
-                            id: "line_item_id".to_string(),
-                            ..Default::default()
-                        },
-                        invoice_line_stats_row: InvoiceLineStatsRow::default(),
-                        location_row_option: Some(LocationRow {
-                            name: "line_location_name".to_string(),
-                            ..Default::default()
-                        }),
-                        stock_line_option: None,
+                InvoiceLineNode::from_domain(repository::InvoiceLine {
+                    invoice_line_row: InvoiceLineRow {
+                        id: "line_id".to_string(),
+                        invoice_id: "line_invoice_id".to_string(),
+                        r#type: InvoiceLineType::Service,
+                        item_link_id: "line_item_id".to_string(),
+                        item_name: "line_item_name".to_string(),
+                        item_code: "line_item_code".to_string(),
+                        pack_size: 1.0,
+                        number_of_packs: 2.0,
+                        batch: Some("line_batch".to_string()),
+                        expiry_date: Some(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()),
+                        location_id: Some("line_location_id".to_string()),
+                        note: None,
+                        ..Default::default()
                     },
-                }
+                    invoice_row: InvoiceRow::default(),
+                    item_row: ItemRow {
+                        id: "line_item_id".to_string(),
+                        ..Default::default()
+                    },
+                    invoice_line_stats_row: InvoiceLineStatsRow::default(),
+                    location_row_option: Some(LocationRow {
+                        name: "line_location_name".to_string(),
+                        ..Default::default()
+                    }),
+                    stock_line_option: None,
+                })
             }
         }
 
@@ -526,46 +546,40 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query_stock_in(&self) -> InvoiceLineNode {
-                InvoiceLineNode {
-                    invoice_line: InvoiceLine {
-                        invoice_line_row: InvoiceLineRow {
-                            total_before_tax: 1.0,
-                            total_after_tax: 2.0,
-                            tax_percentage: Some(10.0),
-                            r#type: InvoiceLineType::StockIn,
-                            ..Default::default()
-                        },
+                InvoiceLineNode::from_domain(InvoiceLine {
+                    invoice_line_row: InvoiceLineRow {
+                        total_before_tax: 1.0,
+                        total_after_tax: 2.0,
+                        tax_percentage: Some(10.0),
+                        r#type: InvoiceLineType::StockIn,
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
             pub async fn test_query_stock_out(&self) -> InvoiceLineNode {
-                InvoiceLineNode {
-                    invoice_line: InvoiceLine {
-                        invoice_line_row: InvoiceLineRow {
-                            total_before_tax: 1.0,
-                            total_after_tax: 2.0,
-                            tax_percentage: Some(5.0),
-                            r#type: InvoiceLineType::StockOut,
-                            ..Default::default()
-                        },
+                InvoiceLineNode::from_domain(InvoiceLine {
+                    invoice_line_row: InvoiceLineRow {
+                        total_before_tax: 1.0,
+                        total_after_tax: 2.0,
+                        tax_percentage: Some(5.0),
+                        r#type: InvoiceLineType::StockOut,
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
             pub async fn test_query_service(&self) -> InvoiceLineNode {
-                InvoiceLineNode {
-                    invoice_line: InvoiceLine {
-                        invoice_line_row: InvoiceLineRow {
-                            total_before_tax: 1.0,
-                            total_after_tax: 2.0,
-                            tax_percentage: None,
-                            r#type: InvoiceLineType::Service,
-                            ..Default::default()
-                        },
+                InvoiceLineNode::from_domain(InvoiceLine {
+                    invoice_line_row: InvoiceLineRow {
+                        total_before_tax: 1.0,
+                        total_after_tax: 2.0,
+                        tax_percentage: None,
+                        r#type: InvoiceLineType::Service,
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
         }
 
@@ -17,6 +17,7 @@ use graphql_core::loader::{
 };
 use graphql_core::{
     loader::{InvoiceStatsLoader, NameByIdLoader, RequisitionsByIdLoader},
+    simple_generic_errors::PluginValidationMessageNode,
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
 };
@@ -26,7 +27,9 @@ use repository::{
 
 use repository::Invoice;
 use serde::Serialize;
-use service::{usize_to_u32, ListResult};
+use service::{
+    backend_plugin::types::validate_mutation::PluginValidationMessage, usize_to_u32, ListResult,
+};
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -103,6 +106,7 @@ pub struct EqualFilterInvoiceStatusInput {
 
 pub struct InvoiceNode {
     pub invoice: Invoice,
+    pub plugin_warnings: Vec<PluginValidationMessage>,
 }
 
 #[derive(SimpleObject)]
@@ -553,11 +557,30 @@ impl InvoiceNode {
             _ => InboundNodeType::ManualExternal, // Default to external for non-inbound shipments
         }
     }
+
+    /// Non blocking messages from validate_mutation plugins, only returned in mutation responses
+    pub async fn plugin_warnings(&self) -> Vec<PluginValidationMessageNode> {
+        self.plugin_warnings
+            .iter()
+            .cloned()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
 }
 
 impl InvoiceNode {
     pub fn from_domain(invoice: Invoice) -> InvoiceNode {
-        InvoiceNode { invoice }
+        InvoiceNode {
+            invoice,
+            plugin_warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_plugin_warnings(self, plugin_warnings: Vec<PluginValidationMessage>) -> Self {
+        Self {
+            plugin_warnings,
+            ..self
+        }
     }
     pub fn row(&self) -> &InvoiceRow {
         &self.invoice.invoice_row
@@ -733,12 +756,10 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query(&self) -> InvoiceNode {
-                InvoiceNode {
-                    invoice: Invoice {
-                        invoice_row: invoice(),
-                        ..Default::default()
-                    },
-                }
+                InvoiceNode::from_domain(Invoice {
+                    invoice_row: invoice(),
+                    ..Default::default()
+                })
             }
         }
         let total_before_tax = 50.0 + 100.0 + 100.0;
@@ -0,0 +1,162 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    loader::{InvoiceByIdLoader, ItemLoader, StockLineByIdLoader},
+    standard_graphql_error::StandardGraphqlError,
+    ContextExt,
+};
+use repository::{KitAssemblyRow, KitComponentRow};
+
+use super::{InvoiceNode, ItemNode, StockLineNode};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::KitAssemblyType")]
+pub enum KitAssemblyTypeNode {
+    Assembly,
+    Disassembly,
+}
+
+/// Bill of materials line of a kit item
+pub struct KitComponentNode {
+    pub kit_component: KitComponentRow,
+}
+
+#[Object]
+impl KitComponentNode {
+    pub async fn id(&self) -> &str {
+        &self.kit_component.id
+    }
+
+    pub async fn kit_item_id(&self) -> &str {
+        &self.kit_component.kit_item_id
+    }
+
+    pub async fn component_item_id(&self) -> &str {
+        &self.kit_component.component_item_id
+    }
+
+    pub async fn component_item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        let item_option = loader
+            .load_one(self.kit_component.component_item_id.clone())
+            .await?;
+
+        item_option.map(ItemNode::from_domain).ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find item_id {} for kit_component_id {}",
+                &self.kit_component.component_item_id, &self.kit_component.id
+            ))
+            .extend(),
+        )
+    }
+
+    /// Units of the component in one unit of the kit
+    pub async fn quantity(&self) -> f64 {
+        self.kit_component.quantity
+    }
+}
+
+impl KitComponentNode {
+    pub fn from_domain(kit_component: KitComponentRow) -> KitComponentNode {
+        KitComponentNode { kit_component }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct KitComponentConnector {
+    total_count: u32,
+    nodes: Vec<KitComponentNode>,
+}
+
+impl KitComponentConnector {
+    pub fn from_vec(kit_components: Vec<KitComponentRow>) -> KitComponentConnector {
+        KitComponentConnector {
+            total_count: kit_components.len() as u32,
+            nodes: kit_components
+                .into_iter()
+                .map(KitComponentNode::from_domain)
+                .collect(),
+        }
+    }
+}
+
+pub struct KitAssemblyNode {
+    pub kit_assembly: KitAssemblyRow,
+}
+
+#[Object]
+impl KitAssemblyNode {
+    pub async fn id(&self) -> &str {
+        &self.kit_assembly.id
+    }
+
+    pub async fn r#type(&self) -> KitAssemblyTypeNode {
+        KitAssemblyTypeNode::from(self.kit_assembly.assembly_type.clone())
+    }
+
+    pub async fn kit_item_id(&self) -> &str {
+        &self.kit_assembly.kit_item_id
+    }
+
+    pub async fn kit_item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        Ok(loader
+            .load_one(self.kit_assembly.kit_item_id.clone())
+            .await?
+            .map(ItemNode::from_domain))
+    }
+
+    /// Kit stock line created by assembly or broken down by disassembly
+    pub async fn kit_stock_line(&self, ctx: &Context<'_>) -> Result<Option<StockLineNode>> {
+        let loader = ctx.get_loader::<DataLoader<StockLineByIdLoader>>();
+        Ok(loader
+            .load_one(self.kit_assembly.kit_stock_line_id.clone())
+            .await?
+            .map(StockLineNode::from_domain))
+    }
+
+    /// Repack invoice the component and kit stock movements are recorded on
+    pub async fn invoice(&self, ctx: &Context<'_>) -> Result<Option<InvoiceNode>> {
+        let loader = ctx.get_loader::<DataLoader<InvoiceByIdLoader>>();
+        Ok(loader
+            .load_one(self.kit_assembly.invoice_id.clone())
+            .await?
+            .map(InvoiceNode::from_domain))
+    }
+
+    pub async fn number_of_kits(&self) -> f64 {
+        self.kit_assembly.number_of_kits
+    }
+
+    pub async fn user_id(&self) -> &Option<String> {
+        &self.kit_assembly.user_id
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.kit_assembly.created_datetime, Utc)
+    }
+}
+
+impl KitAssemblyNode {
+    pub fn from_domain(kit_assembly: KitAssemblyRow) -> KitAssemblyNode {
+        KitAssemblyNode { kit_assembly }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct KitAssemblyConnector {
+    total_count: u32,
+    nodes: Vec<KitAssemblyNode>,
+}
+
+impl KitAssemblyConnector {
+    pub fn from_vec(kit_assemblies: Vec<KitAssemblyRow>) -> KitAssemblyConnector {
+        KitAssemblyConnector {
+            total_count: kit_assemblies.len() as u32,
+            nodes: kit_assemblies
+                .into_iter()
+                .map(KitAssemblyNode::from_domain)
+                .collect(),
+        }
+    }
+}
@@ -95,6 +95,16 @@ impl LocationNode {
         Ok(result)
     }
 
+    /// Fraction of location volume in use, null when location volume is not set
+    pub async fn utilisation(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        if self.row().volume <= 0.0 {
+            return Ok(None);
+        }
+
+        let volume_used = self.volume_used(ctx).await?;
+        Ok(Some(volume_used / self.row().volume))
+    }
+
     pub async fn stock(&self, ctx: &Context<'_>) -> Result<StockLineConnector> {
         let loader = ctx.get_loader::<DataLoader<StockLineByLocationIdLoader>>();
         let result_option = loader.load_one(self.row().id.clone()).await?;
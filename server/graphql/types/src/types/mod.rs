@@ -16,9 +16,24 @@ pub use self::ancillary_item::*;
 pub mod item_stats;
 pub use self::item_stats::*;
 
+pub mod kit;
+pub use self::kit::*;
+
+pub mod dispensing_safety;
+pub use self::dispensing_safety::*;
+
+pub mod recall;
+pub use self::recall::*;
+
+pub mod serial_number;
+pub use self::serial_number::*;
+
 pub mod requisition;
 pub use self::requisition::*;
 
+pub mod requisition_approval;
+pub use self::requisition_approval::*;
+
 pub mod requisition_line;
 pub use self::requisition_line::*;
 
@@ -213,6 +213,10 @@ impl PreferencesNode {
         ))
     }
 
+    pub async fn use_demand_forecast_for_suggested_quantity(&self) -> Result<bool> {
+        self.load_preference(&self.preferences.use_demand_forecast_for_suggested_quantity)
+    }
+
     pub async fn invoice_status_options(&self) -> Result<Vec<InvoiceNodeStatus>> {
         let domain_statuses = self.load_preference(&self.preferences.invoice_status_options)?;
         let statuses = domain_statuses
@@ -309,6 +313,7 @@ pub enum PreferenceKey {
     WarnWhenMissingRecentStocktake,
     InvoiceStatusOptions,
     ShowIndicativePriceInRequisitions,
+    UseDemandForecastForSuggestedQuantity,
 }
 
 #[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
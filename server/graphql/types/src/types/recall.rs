@@ -0,0 +1,145 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, Utc};
+use graphql_core::{loader::ItemLoader, standard_graphql_error::StandardGraphqlError, ContextExt};
+use repository::{RecallRow, RecallSeverity};
+use service::recall::RecallTrace;
+
+use super::{InvoiceLineConnector, ItemNode, StockLineConnector};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::RecallSeverity")]
+pub enum RecallSeverityNode {
+    High,
+    Medium,
+    Low,
+}
+
+pub struct RecallNode {
+    pub recall: RecallRow,
+}
+
+#[Object]
+impl RecallNode {
+    pub async fn id(&self) -> &str {
+        &self.recall.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.recall.item_id
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        let item_option = loader.load_one(self.recall.item_id.clone()).await?;
+
+        item_option.map(ItemNode::from_domain).ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find item_id {} for recall_id {}",
+                &self.recall.item_id, &self.recall.id
+            ))
+            .extend(),
+        )
+    }
+
+    /// Recalled batch, all batches of the item are recalled if not set
+    pub async fn batch(&self) -> &Option<String> {
+        &self.recall.batch
+    }
+
+    /// Recalled item variant, all variants of the item are recalled if not set
+    pub async fn item_variant_id(&self) -> &Option<String> {
+        &self.recall.item_variant_id
+    }
+
+    pub async fn reason(&self) -> &str {
+        &self.recall.reason
+    }
+
+    pub async fn severity(&self) -> RecallSeverityNode {
+        RecallSeverityNode::from(self.recall.severity.clone())
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.recall.created_datetime, Utc)
+    }
+
+    pub async fn closed_datetime(&self) -> Option<DateTime<Utc>> {
+        self.recall
+            .closed_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+impl RecallNode {
+    pub fn from_domain(recall: RecallRow) -> RecallNode {
+        RecallNode { recall }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RecallConnector {
+    total_count: u32,
+    nodes: Vec<RecallNode>,
+}
+
+impl RecallConnector {
+    pub fn from_vec(recalls: Vec<RecallRow>) -> RecallConnector {
+        RecallConnector {
+            total_count: recalls.len() as u32,
+            nodes: recalls.into_iter().map(RecallNode::from_domain).collect(),
+        }
+    }
+}
+
+/// Recall status report, quantities are in units
+pub struct RecallTraceNode {
+    pub trace: RecallTrace,
+}
+
+#[Object]
+impl RecallTraceNode {
+    pub async fn recall(&self) -> RecallNode {
+        RecallNode::from_domain(self.trace.recall.clone())
+    }
+
+    /// Recalled stock still in stores
+    pub async fn stock_lines(&self) -> StockLineConnector {
+        StockLineConnector::from_vec(self.trace.stock_lines.clone())
+    }
+
+    /// Recalled stock shipped to other stores or customers and dispensed to patients
+    pub async fn issued_lines(&self) -> InvoiceLineConnector {
+        InvoiceLineConnector::from_vec(self.trace.issued_lines.clone())
+    }
+
+    /// Recalled stock returned to suppliers
+    pub async fn returned_lines(&self) -> InvoiceLineConnector {
+        InvoiceLineConnector::from_vec(self.trace.returned_lines.clone())
+    }
+
+    pub async fn units_in_stock(&self) -> f64 {
+        self.trace.units_in_stock()
+    }
+
+    pub async fn units_on_hold(&self) -> f64 {
+        self.trace.units_on_hold()
+    }
+
+    pub async fn units_shipped(&self) -> f64 {
+        self.trace.units_shipped()
+    }
+
+    pub async fn units_dispensed(&self) -> f64 {
+        self.trace.units_dispensed()
+    }
+
+    pub async fn units_returned(&self) -> f64 {
+        self.trace.units_returned()
+    }
+}
+
+impl RecallTraceNode {
+    pub fn from_domain(trace: RecallTrace) -> RecallTraceNode {
+        RecallTraceNode { trace }
+    }
+}
@@ -45,7 +45,7 @@ impl RepackNode {
             StandardGraphqlError::InternalError(format!("Cannot find invoice {}", self.id))
                 .extend(),
         )?;
-        Ok(InvoiceNode { invoice })
+        Ok(InvoiceNode::from_domain(invoice))
     }
 
     async fn repack_id(&self) -> &str {
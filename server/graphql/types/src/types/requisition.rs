@@ -4,21 +4,23 @@ use chrono::{DateTime, NaiveDate, Utc};
 use graphql_core::loader::ItemLoader;
 use graphql_core::{
     loader::{
-        InvoiceByRequisitionIdLoader, NameByIdLoader, NameByIdLoaderInput,
+        InvoiceByRequisitionIdLoader, NameByIdLoader, NameByIdLoaderInput, ReasonOptionLoader,
         RequisitionLinesByRequisitionIdLoader, RequisitionLinesRemainingToSupplyLoader,
         RequisitionsByIdLoader, SyncFileReferenceLoader, UserLoader,
     },
+    simple_generic_errors::PluginValidationMessageNode,
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
 };
 use repository::{requisition_row::RequisitionRow, ApprovalStatusType, NameRow, Requisition};
 use service::{
+    backend_plugin::types::validate_mutation::PluginValidationMessage,
     requisition_line::ancillary_items::{AncillaryDelta, AncillaryState},
     ListResult,
 };
 
 use super::{
-    program_node::ProgramNode, InvoiceConnector, ItemNode, NameNode, PeriodNode,
+    program_node::ProgramNode, InvoiceConnector, ItemNode, NameNode, PeriodNode, ReasonOptionNode,
     RequisitionLineConnector, UserNode,
 };
 use crate::types::SyncFileReferenceConnector;
@@ -141,6 +143,7 @@ pub enum RequisitionNodeStatus {
 #[derive(PartialEq, Debug)]
 pub struct RequisitionNode {
     requisition: Requisition,
+    plugin_warnings: Vec<PluginValidationMessage>,
 }
 
 #[derive(SimpleObject)]
@@ -426,6 +429,23 @@ impl RequisitionNode {
         self.row().is_emergency
     }
 
+    pub async fn emergency_reason_option_id(&self) -> &Option<String> {
+        &self.row().emergency_reason_option_id
+    }
+
+    pub async fn emergency_reason(&self, ctx: &Context<'_>) -> Result<Option<ReasonOptionNode>> {
+        let loader = ctx.get_loader::<DataLoader<ReasonOptionLoader>>();
+
+        let reason_option_id = match &self.row().emergency_reason_option_id {
+            Some(reason_option_id) => reason_option_id,
+            None => return Ok(None),
+        };
+
+        let result = loader.load_one(reason_option_id.clone()).await?;
+
+        Ok(result.map(ReasonOptionNode::from_domain))
+    }
+
     pub async fn created_from_requisition(
         &self,
         ctx: &Context<'_>,
@@ -447,6 +467,15 @@ impl RequisitionNode {
         &self.row().created_from_requisition_id
     }
 
+    /// Non blocking messages from validate_mutation plugins, only returned in mutation responses
+    pub async fn plugin_warnings(&self) -> Vec<PluginValidationMessageNode> {
+        self.plugin_warnings
+            .iter()
+            .cloned()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
+
     // % allocated ?
     // % shipped ?
     // lead time ?
@@ -462,7 +491,17 @@ impl RequisitionNode {
     }
 
     pub fn from_domain(requisition: Requisition) -> RequisitionNode {
-        RequisitionNode { requisition }
+        RequisitionNode {
+            requisition,
+            plugin_warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_plugin_warnings(self, plugin_warnings: Vec<PluginValidationMessage>) -> Self {
+        Self {
+            plugin_warnings,
+            ..self
+        }
     }
 }
 
@@ -511,37 +550,31 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query_user_exists(&self) -> RequisitionNode {
-                RequisitionNode {
-                    requisition: Requisition {
-                        requisition_row: RequisitionRow {
-                            user_id: Some(mock_user_account_a().id),
-                            ..Default::default()
-                        },
+                RequisitionNode::from_domain(Requisition {
+                    requisition_row: RequisitionRow {
+                        user_id: Some(mock_user_account_a().id),
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
             pub async fn test_query_user_does_not_exist(&self) -> RequisitionNode {
-                RequisitionNode {
-                    requisition: Requisition {
-                        requisition_row: RequisitionRow {
-                            user_id: Some("does not exist".to_string()),
-                            ..Default::default()
-                        },
+                RequisitionNode::from_domain(Requisition {
+                    requisition_row: RequisitionRow {
+                        user_id: Some("does not exist".to_string()),
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
             pub async fn test_query_user_not_associated(&self) -> RequisitionNode {
-                RequisitionNode {
-                    requisition: Requisition {
-                        requisition_row: RequisitionRow {
-                            user_id: None,
-                            ..Default::default()
-                        },
+                RequisitionNode::from_domain(Requisition {
+                    requisition_row: RequisitionRow {
+                        user_id: None,
                         ..Default::default()
                     },
-                }
+                    ..Default::default()
+                })
             }
         }
 
@@ -596,12 +629,10 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query(&self) -> RequisitionNode {
-                RequisitionNode {
-                    requisition: Requisition {
-                        requisition_row: TestData::requisition(),
-                        ..Default::default()
-                    },
-                }
+                RequisitionNode::from_domain(Requisition {
+                    requisition_row: TestData::requisition(),
+                    ..Default::default()
+                })
             }
         }
 
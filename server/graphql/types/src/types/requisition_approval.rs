@@ -0,0 +1,47 @@
+use async_graphql::*;
+use repository::RequisitionApprovalStepRow;
+
+pub struct RequisitionApprovalStepNode {
+    pub step: RequisitionApprovalStepRow,
+}
+
+#[Object]
+impl RequisitionApprovalStepNode {
+    pub async fn id(&self) -> &str {
+        &self.step.id
+    }
+
+    /// Supplying store the step applies to, applies to all stores if not set
+    pub async fn store_id(&self) -> &Option<String> {
+        &self.step.store_id
+    }
+
+    /// Program the step applies to, applies to all programs if not set
+    pub async fn program_id(&self) -> &Option<String> {
+        &self.step.program_id
+    }
+
+    pub async fn step_number(&self) -> i32 {
+        self.step.step_number
+    }
+
+    pub async fn approver_user_id(&self) -> &str {
+        &self.step.approver_user_id
+    }
+
+    /// Step applies when total requested quantity reaches this threshold
+    pub async fn threshold_quantity(&self) -> Option<f64> {
+        self.step.threshold_quantity
+    }
+
+    /// Step applies when total requested value reaches this threshold
+    pub async fn threshold_value(&self) -> Option<f64> {
+        self.step.threshold_value
+    }
+}
+
+impl RequisitionApprovalStepNode {
+    pub fn from_domain(step: RequisitionApprovalStepRow) -> RequisitionApprovalStepNode {
+        RequisitionApprovalStepNode { step }
+    }
+}
@@ -0,0 +1,161 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use repository::{SerialNumberRow, SerialNumberStatus};
+use service::serial_number::{SerialNumberHistoryEntry, StocktakeSerialNumbers};
+
+use super::InvoiceNodeType;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+#[graphql(remote = "repository::SerialNumberStatus")]
+pub enum SerialNumberStatusNode {
+    Available,
+    Issued,
+    Missing,
+}
+
+pub struct SerialNumberNode {
+    pub serial_number: SerialNumberRow,
+}
+
+#[Object]
+impl SerialNumberNode {
+    pub async fn id(&self) -> &str {
+        &self.serial_number.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.serial_number.item_id
+    }
+
+    pub async fn store_id(&self) -> &str {
+        &self.serial_number.store_id
+    }
+
+    pub async fn serial_number(&self) -> &str {
+        &self.serial_number.serial_number
+    }
+
+    /// Not set until stock the unit was received with is created
+    pub async fn stock_line_id(&self) -> &Option<String> {
+        &self.serial_number.stock_line_id
+    }
+
+    pub async fn status(&self) -> SerialNumberStatusNode {
+        SerialNumberStatusNode::from(self.serial_number.status.clone())
+    }
+
+    pub async fn last_verified_datetime(&self) -> Option<DateTime<Utc>> {
+        self.serial_number
+            .last_verified_datetime
+            .map(|datetime| DateTime::<Utc>::from_naive_utc_and_offset(datetime, Utc))
+    }
+}
+
+impl SerialNumberNode {
+    pub fn from_domain(serial_number: SerialNumberRow) -> SerialNumberNode {
+        SerialNumberNode { serial_number }
+    }
+
+    pub fn from_vec(serial_numbers: Vec<SerialNumberRow>) -> Vec<SerialNumberNode> {
+        serial_numbers
+            .into_iter()
+            .map(SerialNumberNode::from_domain)
+            .collect()
+    }
+}
+
+pub struct SerialNumberHistoryNode {
+    pub entry: SerialNumberHistoryEntry,
+}
+
+#[Object]
+impl SerialNumberHistoryNode {
+    pub async fn id(&self) -> &str {
+        &self.entry.line.id
+    }
+
+    pub async fn store_id(&self) -> &str {
+        &self.entry.line.store_id
+    }
+
+    pub async fn status(&self) -> SerialNumberStatusNode {
+        SerialNumberStatusNode::from(self.entry.serial_number.status.clone())
+    }
+
+    pub async fn datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_naive_utc_and_offset(self.entry.line.created_datetime, Utc)
+    }
+
+    pub async fn invoice_id(&self) -> Option<&str> {
+        self.entry
+            .invoice
+            .as_ref()
+            .map(|invoice| invoice.id.as_str())
+    }
+
+    pub async fn invoice_number(&self) -> Option<i64> {
+        self.entry
+            .invoice
+            .as_ref()
+            .map(|invoice| invoice.invoice_number)
+    }
+
+    pub async fn invoice_type(&self) -> Option<InvoiceNodeType> {
+        self.entry
+            .invoice
+            .as_ref()
+            .map(|invoice| InvoiceNodeType::from(invoice.r#type.clone()))
+    }
+
+    /// Other party of the invoice, supplier or customer
+    pub async fn other_party_name_id(&self) -> Option<&str> {
+        self.entry
+            .invoice
+            .as_ref()
+            .map(|invoice| invoice.name_id.as_str())
+    }
+
+    pub async fn stocktake_id(&self) -> Option<&str> {
+        self.entry
+            .stocktake
+            .as_ref()
+            .map(|stocktake| stocktake.id.as_str())
+    }
+
+    pub async fn stocktake_number(&self) -> Option<i64> {
+        self.entry
+            .stocktake
+            .as_ref()
+            .map(|stocktake| stocktake.stocktake_number)
+    }
+}
+
+impl SerialNumberHistoryNode {
+    pub fn from_vec(history: Vec<SerialNumberHistoryEntry>) -> Vec<SerialNumberHistoryNode> {
+        history
+            .into_iter()
+            .map(|entry| SerialNumberHistoryNode { entry })
+            .collect()
+    }
+}
+
+pub struct StocktakeSerialNumbersNode {
+    pub serial_numbers: StocktakeSerialNumbers,
+}
+
+#[Object]
+impl StocktakeSerialNumbersNode {
+    pub async fn found(&self) -> Vec<SerialNumberNode> {
+        SerialNumberNode::from_vec(self.serial_numbers.found.clone())
+    }
+
+    /// Units that were not counted, these are now marked as missing
+    pub async fn missing(&self) -> Vec<SerialNumberNode> {
+        SerialNumberNode::from_vec(self.serial_numbers.missing.clone())
+    }
+
+    /// Counted serial numbers that are not part of the stock line
+    pub async fn unknown(&self) -> &Vec<String> {
+        &self.serial_numbers.unknown
+    }
+}
@@ -3,11 +3,13 @@ use async_graphql::{dataloader::DataLoader, Context, Enum, ErrorExtensions, Obje
 use chrono::{DateTime, NaiveDate, Utc};
 use graphql_core::{
     loader::{InvoiceByIdLoader, ProgramByIdLoader, StocktakeLineByStocktakeIdLoader, UserLoader},
+    simple_generic_errors::PluginValidationMessageNode,
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
 };
 use repository::StocktakeRow;
 use serde::Serialize;
+use service::backend_plugin::types::validate_mutation::PluginValidationMessage;
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
@@ -19,6 +21,7 @@ pub enum StocktakeNodeStatus {
 
 pub struct StocktakeNode {
     pub stocktake: StocktakeRow,
+    pub plugin_warnings: Vec<PluginValidationMessage>,
 }
 
 #[Object]
@@ -105,7 +108,7 @@ impl StocktakeNode {
                 ))
                 .extend(),
             )?;
-            Ok(Some(InvoiceNode { invoice }))
+            Ok(Some(InvoiceNode::from_domain(invoice)))
         } else {
             Ok(None)
         }
@@ -120,7 +123,7 @@ impl StocktakeNode {
                 ))
                 .extend(),
             )?;
-            Ok(Some(InvoiceNode { invoice }))
+            Ok(Some(InvoiceNode::from_domain(invoice)))
         } else {
             Ok(None)
         }
@@ -155,11 +158,30 @@ impl StocktakeNode {
 
         Ok(result)
     }
+
+    /// Non blocking messages from validate_mutation plugins, only returned in mutation responses
+    pub async fn plugin_warnings(&self) -> Vec<PluginValidationMessageNode> {
+        self.plugin_warnings
+            .iter()
+            .cloned()
+            .map(PluginValidationMessageNode::from_domain)
+            .collect()
+    }
 }
 
 impl StocktakeNode {
     pub fn from_domain(stocktake: StocktakeRow) -> StocktakeNode {
-        StocktakeNode { stocktake }
+        StocktakeNode {
+            stocktake,
+            plugin_warnings: Vec::new(),
+        }
+    }
+
+    pub fn with_plugin_warnings(self, plugin_warnings: Vec<PluginValidationMessage>) -> Self {
+        Self {
+            plugin_warnings,
+            ..self
+        }
     }
 }
 
@@ -192,20 +214,16 @@ mod test {
         #[Object]
         impl TestQuery {
             pub async fn test_query_user_exists(&self) -> StocktakeNode {
-                StocktakeNode {
-                    stocktake: StocktakeRow {
-                        user_id: mock_user_account_a().id,
-                        ..Default::default()
-                    },
-                }
+                StocktakeNode::from_domain(StocktakeRow {
+                    user_id: mock_user_account_a().id,
+                    ..Default::default()
+                })
             }
             pub async fn test_query_user_does_not_exist(&self) -> StocktakeNode {
-                StocktakeNode {
-                    stocktake: StocktakeRow {
-                        user_id: "does not exist".to_string(),
-                        ..Default::default()
-                    },
-                }
+                StocktakeNode::from_domain(StocktakeRow {
+                    user_id: "does not exist".to_string(),
+                    ..Default::default()
+                })
             }
         }
 
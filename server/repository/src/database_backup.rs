@@ -0,0 +1,118 @@
+//! Database operations used by backup and restore (see service::backup)
+
+use crate::{database_settings::DatabaseSettings, DBBackendConnection, RepositoryError};
+use diesel::{Connection, RunQueryDsl};
+#[cfg(not(feature = "postgres"))]
+use std::path::Path;
+
+/// Consistent copy of the sqlite database at `from`, safe to take while the server is running.
+/// With sqlcipher the copy is encrypted with `to_key` (None for plaintext)
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+pub fn snapshot_sqlite_database(
+    from: &Path,
+    from_key: Option<&str>,
+    to: &Path,
+    to_key: Option<&str>,
+) -> Result<(), RepositoryError> {
+    #[cfg(feature = "sqlcipher")]
+    {
+        crate::database_encryption::export_database(from, from_key, to, to_key)
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    {
+        // Keys can't be resolved without sqlcipher (see DatabaseEncryptionSettings::resolve_key)
+        let _ = (from_key, to_key);
+        let mut connection = establish(from)?;
+        let to = to.to_string_lossy().replace('\'', "''");
+        diesel::sql_query(format!("VACUUM INTO '{to}'")).execute(&mut connection)?;
+        Ok(())
+    }
+}
+
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+#[derive(QueryableByName)]
+struct QuickCheck {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    quick_check: String,
+}
+
+/// Opens the sqlite database and checks its integrity (`PRAGMA quick_check`)
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+pub fn check_sqlite_database(path: &Path, key: Option<&str>) -> Result<(), RepositoryError> {
+    use diesel::connection::SimpleConnection;
+
+    let mut connection = establish(path)?;
+    if let Some(key) = key {
+        connection.batch_execute(&format!(
+            "PRAGMA key = {};",
+            crate::database_encryption::key_literal(key)
+        ))?;
+    }
+
+    let result: Vec<String> = diesel::sql_query("PRAGMA quick_check")
+        .load::<QuickCheck>(&mut connection)?
+        .into_iter()
+        .map(|row| row.quick_check)
+        .collect();
+
+    if result != ["ok"] {
+        return Err(RepositoryError::as_db_error(
+            "Database integrity check failed",
+            result,
+        ));
+    }
+    Ok(())
+}
+
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+fn establish(path: &Path) -> Result<DBBackendConnection, RepositoryError> {
+    DBBackendConnection::establish(&path.to_string_lossy())
+        .map_err(|error| RepositoryError::as_db_error("Failed to open database", error))
+}
+
+/// Drops and re-creates the (empty) postgres database, before restoring a dump into it
+#[cfg(feature = "postgres")]
+pub fn drop_and_create_database(settings: &DatabaseSettings) -> Result<(), RepositoryError> {
+    let mut connection = DBBackendConnection::establish(&settings.connection_string_without_db())
+        .map_err(|error| {
+        RepositoryError::as_db_error("Failed to connect to postgres", error)
+    })?;
+
+    let database_name = &settings.database_name;
+    diesel::sql_query(format!(r#"DROP DATABASE IF EXISTS "{database_name}""#))
+        .execute(&mut connection)?;
+    diesel::sql_query(format!(r#"CREATE DATABASE "{database_name}""#)).execute(&mut connection)?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "postgres"))]
+pub fn drop_and_create_database(_: &DatabaseSettings) -> Result<(), RepositoryError> {
+    unreachable!("Only used for postgres")
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "postgres"))]
+mod test {
+    use super::*;
+    use crate::test_db::{get_test_db_settings, setup};
+
+    #[actix_rt::test]
+    async fn test_snapshot_sqlite_database() {
+        let settings = get_test_db_settings("test_snapshot_sqlite_database");
+        setup(&settings).await;
+
+        let snapshot = std::env::temp_dir().join("test_snapshot_sqlite_database.sqlite");
+        let _ = std::fs::remove_file(&snapshot);
+        snapshot_sqlite_database(Path::new(&settings.database_path()), None, &snapshot, None)
+            .unwrap();
+
+        check_sqlite_database(&snapshot, None).unwrap();
+        std::fs::remove_file(&snapshot).unwrap();
+    }
+}
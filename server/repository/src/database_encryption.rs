@@ -0,0 +1,254 @@
+//! At-rest encryption of SQLite databases with SQLCipher, requires the `sqlcipher` feature.
+//! Keys are either 64 hex characters (raw 256 bit key, as generated for the OS keystore) or a
+//! passphrase, which SQLCipher derives a key from
+
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read, path::Path};
+use thiserror::Error;
+
+/// Keystore service of the database key, the keystore user is the database name
+pub const DATABASE_KEYSTORE_SERVICE: &str = "omSupply database";
+/// Keystore service of the backup key, kept separate so a leaked backup key doesn't expose the
+/// live database (and the other way around)
+pub const BACKUP_KEYSTORE_SERVICE: &str = "omSupply backup";
+
+/// First bytes of every unencrypted SQLite database
+const PLAINTEXT_HEADER: &[u8] = b"SQLite format 3\0";
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum DatabaseKeySource {
+    /// Generated on first use and held in the OS keystore (macOS Keychain, Windows Credential
+    /// Manager or Secret Service on linux). The key never leaves the machine, so a backup
+    /// encrypted with a keystore key can only be restored on the same machine
+    Keystore,
+    /// Entered on the console at startup, not suitable for services started without a console
+    Prompt,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct DatabaseEncryptionSettings {
+    pub key_source: DatabaseKeySource,
+    /// Resolved from `key_source` when needed. Set directly on android, where the key comes from
+    /// the Android Keystore. Never read from or written to configuration files
+    #[serde(skip)]
+    pub key: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum DatabaseKeyError {
+    #[error(
+        "Encryption is configured but this build doesn't include SQLCipher (sqlcipher feature)"
+    )]
+    NotSupported,
+    #[error("Problem accessing the OS keystore: {0}")]
+    Keystore(String),
+    #[error("Problem reading the key from the console")]
+    Prompt(#[from] std::io::Error),
+    #[error("Key is empty")]
+    EmptyKey,
+}
+
+impl DatabaseEncryptionSettings {
+    pub fn with_key(key: String) -> Self {
+        DatabaseEncryptionSettings {
+            key_source: DatabaseKeySource::Keystore,
+            key: Some(key),
+        }
+    }
+
+    /// Key held by `keystore_service` for `keystore_user`, a key is generated and stored in the
+    /// keystore if there is none yet
+    pub fn resolve_key(
+        &self,
+        keystore_service: &str,
+        keystore_user: &str,
+    ) -> Result<String, DatabaseKeyError> {
+        if !cfg!(feature = "sqlcipher") {
+            return Err(DatabaseKeyError::NotSupported);
+        }
+        if let Some(key) = &self.key {
+            return Ok(key.clone());
+        }
+
+        let key = match self.key_source {
+            DatabaseKeySource::Keystore => {
+                match read_keystore_key(keystore_service, keystore_user)? {
+                    Some(key) => key,
+                    None => {
+                        let key = generate_database_key();
+                        write_keystore_key(keystore_service, keystore_user, &key)?;
+                        key
+                    }
+                }
+            }
+            DatabaseKeySource::Prompt => {
+                prompt_key(&format!("Enter the {keystore_service} key: "))?
+            }
+        };
+
+        if key.is_empty() {
+            return Err(DatabaseKeyError::EmptyKey);
+        }
+        Ok(key)
+    }
+}
+
+/// Random 256 bit key, hex encoded
+pub fn generate_database_key() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// False for encrypted databases, and for files that don't exist or are empty (new databases)
+pub fn is_plaintext_database(path: &Path) -> std::io::Result<bool> {
+    if !path.try_exists()? {
+        return Ok(false);
+    }
+    let mut header = [0u8; PLAINTEXT_HEADER.len()];
+    match File::open(path)?.read_exact(&mut header) {
+        Ok(()) => Ok(header == PLAINTEXT_HEADER),
+        Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Key as an SQLCipher key literal, for `PRAGMA key = ` and `ATTACH DATABASE .. KEY `
+pub fn key_literal(key: &str) -> String {
+    let is_raw_key = key.len() == 64 && key.chars().all(|c| c.is_ascii_hexdigit());
+    if is_raw_key {
+        format!("\"x'{key}'\"")
+    } else {
+        format!("'{}'", key.replace('\'', "''"))
+    }
+}
+
+#[cfg(all(feature = "sqlcipher", not(target_os = "android")))]
+pub fn read_keystore_key(
+    keystore_service: &str,
+    keystore_user: &str,
+) -> Result<Option<String>, DatabaseKeyError> {
+    let entry = keyring::Entry::new(keystore_service, keystore_user)
+        .map_err(|error| DatabaseKeyError::Keystore(error.to_string()))?;
+    match entry.get_password() {
+        Ok(key) => Ok(Some(key)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(DatabaseKeyError::Keystore(error.to_string())),
+    }
+}
+
+#[cfg(all(feature = "sqlcipher", not(target_os = "android")))]
+pub fn write_keystore_key(
+    keystore_service: &str,
+    keystore_user: &str,
+    key: &str,
+) -> Result<(), DatabaseKeyError> {
+    keyring::Entry::new(keystore_service, keystore_user)
+        .and_then(|entry| entry.set_password(key))
+        .map_err(|error| DatabaseKeyError::Keystore(error.to_string()))
+}
+
+#[cfg(all(feature = "sqlcipher", not(target_os = "android")))]
+pub fn prompt_key(prompt: &str) -> Result<String, DatabaseKeyError> {
+    Ok(rpassword::prompt_password(prompt)?)
+}
+
+// Android passes the key in (see `DatabaseEncryptionSettings::with_key`)
+#[cfg(not(all(feature = "sqlcipher", not(target_os = "android"))))]
+pub fn read_keystore_key(_: &str, _: &str) -> Result<Option<String>, DatabaseKeyError> {
+    Err(DatabaseKeyError::NotSupported)
+}
+
+#[cfg(not(all(feature = "sqlcipher", not(target_os = "android"))))]
+pub fn write_keystore_key(_: &str, _: &str, _: &str) -> Result<(), DatabaseKeyError> {
+    Err(DatabaseKeyError::NotSupported)
+}
+
+#[cfg(not(all(feature = "sqlcipher", not(target_os = "android"))))]
+pub fn prompt_key(_: &str) -> Result<String, DatabaseKeyError> {
+    Err(DatabaseKeyError::NotSupported)
+}
+
+/// Copies the database at `from` to a new database at `to`, decrypting with `from_key` and
+/// encrypting with `to_key` (None for plaintext). The database at `from` must not be in use
+#[cfg(feature = "sqlcipher")]
+pub fn export_database(
+    from: &Path,
+    from_key: Option<&str>,
+    to: &Path,
+    to_key: Option<&str>,
+) -> Result<(), crate::RepositoryError> {
+    use crate::{DBBackendConnection, RepositoryError};
+    use diesel::{connection::SimpleConnection, Connection};
+
+    let mut connection = DBBackendConnection::establish(&from.to_string_lossy())
+        .map_err(|error| RepositoryError::as_db_error("Failed to open database", error))?;
+    if let Some(from_key) = from_key {
+        connection.batch_execute(&format!("PRAGMA key = {};", key_literal(from_key)))?;
+    }
+
+    let to_path = to.to_string_lossy().replace('\'', "''");
+    let to_key = to_key.map(key_literal).unwrap_or_else(|| "''".to_string());
+    connection.batch_execute(&format!(
+        "ATTACH DATABASE '{to_path}' AS export KEY {to_key};
+        SELECT sqlcipher_export('export');
+        DETACH DATABASE export;"
+    ))?;
+
+    Ok(())
+}
+
+/// Replaces the database at `path` with a copy encrypted with `new_key`. `old_key` is None for a
+/// plaintext database, and `new_key` None to decrypt. The database must not be in use
+#[cfg(feature = "sqlcipher")]
+pub fn reencrypt_database(
+    path: &Path,
+    old_key: Option<&str>,
+    new_key: Option<&str>,
+) -> Result<(), crate::RepositoryError> {
+    use crate::RepositoryError;
+    use std::fs;
+
+    let with_suffix = |suffix: &str| {
+        let mut file_name = path.as_os_str().to_owned();
+        file_name.push(suffix);
+        std::path::PathBuf::from(file_name)
+    };
+    let io_error =
+        |error: std::io::Error| RepositoryError::as_db_error("Database file error", error);
+
+    let exported = with_suffix(".reencrypting");
+    if exported.exists() {
+        fs::remove_file(&exported).map_err(io_error)?;
+    }
+    export_database(path, old_key, &exported, new_key)?;
+
+    // The write ahead log belongs to the old file, its content was exported
+    for suffix in ["-wal", "-shm"] {
+        let file = with_suffix(suffix);
+        if file.exists() {
+            fs::remove_file(file).map_err(io_error)?;
+        }
+    }
+    fs::rename(&exported, path).map_err(io_error)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_key_literal() {
+        let raw_key = "ab".repeat(32);
+        assert_eq!(key_literal(&raw_key), format!("\"x'{raw_key}'\""));
+        assert_eq!(key_literal("pass'phrase"), "'pass''phrase'");
+        // Not 64 characters, used as a passphrase
+        assert_eq!(key_literal("abcd"), "'abcd'");
+
+        let generated = generate_database_key();
+        assert_eq!(generated.len(), 64);
+        assert!(key_literal(&generated).starts_with("\"x'"));
+    }
+}
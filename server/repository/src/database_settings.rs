@@ -1,4 +1,7 @@
-use crate::db_diesel::{DBBackendConnection, StorageConnectionManager};
+use crate::{
+    database_encryption::{DatabaseEncryptionSettings, DatabaseKeyError},
+    db_diesel::{DBBackendConnection, StorageConnectionManager},
+};
 use diesel::{
     connection::SimpleConnection,
     r2d2::{ConnectionManager, Pool},
@@ -6,12 +9,18 @@ use diesel::{
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use thiserror::Error;
 
 // Timeout for waiting for the SQLite lock (https://www.sqlite.org/c3ref/busy_timeout.html).
 // A locked DB results in the "SQLite database is locked" error.
 #[cfg(not(feature = "postgres"))]
 const SQLITE_LOCKWAIT_MS: u32 = 30 * 1000;
 
+#[cfg(not(feature = "postgres"))]
+use crate::database_encryption::{is_plaintext_database, key_literal, DATABASE_KEYSTORE_SERVICE};
+#[cfg(not(feature = "postgres"))]
+use std::path::Path;
+
 #[cfg(not(feature = "postgres"))]
 const SQLITE_WAL_PRAGMA: &str = "PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;";
 
@@ -31,6 +40,25 @@ pub struct DatabaseSettings {
     pub connection_pool_timeout_seconds: Option<u64>,
     /// SQL run once at startup. For example, to run pragma statements
     pub init_sql: Option<String>,
+    /// Encrypts the SQLite database, requires the sqlcipher feature
+    #[serde(default)]
+    pub encryption: Option<DatabaseEncryptionSettings>,
+}
+
+#[derive(Error, Debug)]
+pub enum StorageConnectionError {
+    #[error(transparent)]
+    DatabaseKey(#[from] DatabaseKeyError),
+    #[error("Database '{0}' is not encrypted, encrypt it with the encrypt-database cli command")]
+    PlaintextDatabase(String),
+    #[error("Failed to open database '{0}', check the database key")]
+    WrongKey(String, #[source] diesel::r2d2::Error),
+    #[error("Failed to connect to database")]
+    Connection(#[from] diesel::r2d2::Error),
+    #[error("Failed to create database '{0}'")]
+    CreateDatabase(String, #[source] diesel::result::Error),
+    #[error("Failed to create database connection pool")]
+    Pool(#[from] diesel::r2d2::PoolError),
 }
 
 // feature postgres
@@ -109,13 +137,35 @@ impl DatabaseSettings {
             None => Some(SQLITE_WAL_PRAGMA.to_string()),
         }
     }
+
+    /// Key of the encrypted database, None when encryption is not configured
+    pub fn encryption_key(&self) -> Result<Option<String>, DatabaseKeyError> {
+        self.encryption
+            .as_ref()
+            .map(|encryption| {
+                encryption.resolve_key(DATABASE_KEYSTORE_SERVICE, &self.database_name)
+            })
+            .transpose()
+    }
 }
 
 // feature sqlite
 #[cfg(not(feature = "postgres"))]
-#[derive(Debug)]
 pub struct SqliteConnectionOptions {
     pub busy_timeout_ms: Option<u32>,
+    /// SQLCipher key, must be set before anything else is run on the connection
+    pub key: Option<String>,
+}
+
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+impl std::fmt::Debug for SqliteConnectionOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteConnectionOptions")
+            .field("busy_timeout_ms", &self.busy_timeout_ms)
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
 }
 // feature sqlite
 #[cfg(not(feature = "postgres"))]
@@ -124,6 +174,15 @@ impl diesel::r2d2::CustomizeConnection<diesel::SqliteConnection, diesel::r2d2::E
 {
     // TODO: make relevant sqlite customisation settings configurable at runtime.
     fn on_acquire(&self, conn: &mut diesel::SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        if let Some(key) = &self.key {
+            // Reading the schema fails with "file is not a database" when the key is wrong
+            conn.batch_execute(&format!(
+                "PRAGMA key = {}; SELECT count(*) FROM sqlite_master;",
+                key_literal(key)
+            ))
+            .map_err(diesel::r2d2::Error::QueryError)?;
+        }
+
         //Set busy_timeout first as setting WAL can generate busy during a write
         if let Some(d) = self.busy_timeout_ms {
             conn.batch_execute(&format!("PRAGMA busy_timeout = {d};"))
@@ -139,7 +198,9 @@ impl diesel::r2d2::CustomizeConnection<diesel::SqliteConnection, diesel::r2d2::E
 
 // feature postgres
 #[cfg(feature = "postgres")]
-pub fn get_storage_connection_manager(settings: &DatabaseSettings) -> StorageConnectionManager {
+pub fn get_storage_connection_manager(
+    settings: &DatabaseSettings,
+) -> Result<StorageConnectionManager, StorageConnectionError> {
     use crate::diesel::r2d2::ManageConnection;
     let connection_manager =
         ConnectionManager::<DBBackendConnection>::new(&settings.connection_string());
@@ -159,18 +220,14 @@ pub fn get_storage_connection_manager(settings: &DatabaseSettings) -> StorageCon
                 &settings.connection_string_without_db(),
             );
 
-            match root_connection_manager.connect() {
-                Ok(mut root_connection) => {
-                    root_connection
-                        .batch_execute(&format!("CREATE DATABASE \"{}\";", &settings.database_name))
-                        .expect("Failed to create database");
-                }
-                Err(e) => {
-                    panic!("Failed to connect to postgres root: {}", e);
-                }
-            }
+            root_connection_manager
+                .connect()?
+                .batch_execute(&format!("CREATE DATABASE \"{}\";", &settings.database_name))
+                .map_err(|error| {
+                    StorageConnectionError::CreateDatabase(settings.database_name.clone(), error)
+                })?;
         } else {
-            panic!("Failed to connect to database: {}", e);
+            return Err(e.into());
         }
     }
     info!("Connecting to database '{}'", settings.database_name);
@@ -186,21 +243,38 @@ pub fn get_storage_connection_manager(settings: &DatabaseSettings) -> StorageCon
                 .connection_pool_timeout_seconds
                 .unwrap_or(DEFAULT_CONNECTION_POOL_TIMEOUT_SECONDS),
         ))
-        .build(connection_manager)
-        .expect("Failed to connect to database");
-    StorageConnectionManager::new(pool)
+        .build(connection_manager)?;
+    Ok(StorageConnectionManager::new(pool))
 }
 
 // feature sqlite
 #[cfg(not(feature = "postgres"))]
-pub fn get_storage_connection_manager(settings: &DatabaseSettings) -> StorageConnectionManager {
+pub fn get_storage_connection_manager(
+    settings: &DatabaseSettings,
+) -> Result<StorageConnectionManager, StorageConnectionError> {
+    use diesel::r2d2::{CustomizeConnection, ManageConnection};
     info!("Connecting to database '{}'", settings.database_path());
     let db_path = settings.database_path();
-    let connection_manager = ConnectionManager::<DBBackendConnection>::new(db_path);
+
+    let key = settings.encryption_key()?;
+    if key.is_some() && is_plaintext_database(Path::new(&db_path)).unwrap_or(false) {
+        return Err(StorageConnectionError::PlaintextDatabase(db_path));
+    }
+
+    let connection_manager = ConnectionManager::<DBBackendConnection>::new(&db_path);
+    let options = SqliteConnectionOptions {
+        busy_timeout_ms: Some(SQLITE_LOCKWAIT_MS),
+        key,
+    };
+    // Checked before building the pool, which keeps retrying a wrong key until the timeout
+    if options.key.is_some() {
+        options
+            .on_acquire(&mut connection_manager.connect()?)
+            .map_err(|error| StorageConnectionError::WrongKey(db_path.clone(), error))?;
+    }
+
     let pool = Pool::builder()
-        .connection_customizer(Box::new(SqliteConnectionOptions {
-            busy_timeout_ms: Some(SQLITE_LOCKWAIT_MS),
-        }))
+        .connection_customizer(Box::new(options))
         .max_size(
             settings
                 .connection_pool_max_connections
@@ -212,10 +286,9 @@ pub fn get_storage_connection_manager(settings: &DatabaseSettings) -> StorageCon
                 .connection_pool_timeout_seconds
                 .unwrap_or(DEFAULT_CONNECTION_POOL_TIMEOUT_SECONDS),
         ))
-        .build(connection_manager)
-        .expect("Failed to connect to database");
+        .build(connection_manager)?;
 
-    StorageConnectionManager::new(pool)
+    Ok(StorageConnectionManager::new(pool))
 }
 
 #[cfg(test)]
@@ -231,6 +304,7 @@ mod database_setting_test {
             host: "".to_string(),
             database_name: "".to_string(),
             init_sql: startup_sql,
+            encryption: None,
             database_path: None,
             connection_pool_max_connections: None,
             connection_pool_min_idle: None,
@@ -249,6 +323,7 @@ mod database_setting_test {
             host: "localhost".to_string(),
             database_name: "testdb".to_string(),
             init_sql: None,
+            encryption: None,
             database_path: None,
             connection_pool_max_connections: None,
             connection_pool_timeout_seconds: None,
@@ -288,4 +363,41 @@ mod database_setting_test {
             Some(expected_init_sql)
         )
     }
+
+    #[cfg(all(feature = "sqlcipher", not(feature = "postgres")))]
+    #[test]
+    fn test_storage_connection_errors() {
+        use super::{get_storage_connection_manager, StorageConnectionError};
+        use crate::database_encryption::{reencrypt_database, DatabaseEncryptionSettings};
+
+        let dir = std::env::temp_dir().join("test_storage_connection_errors");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("database.sqlite");
+        let mut settings = empty_db_settings_with_startup_sql(None);
+        settings.database_name = db_path.to_string_lossy().to_string();
+
+        get_storage_connection_manager(&settings)
+            .unwrap()
+            .execute("CREATE TABLE test (id TEXT);")
+            .unwrap();
+
+        // Key configured for a plaintext database
+        settings.encryption = Some(DatabaseEncryptionSettings::with_key("key".to_string()));
+        assert!(matches!(
+            get_storage_connection_manager(&settings),
+            Err(StorageConnectionError::PlaintextDatabase(_))
+        ));
+
+        reencrypt_database(&db_path, None, Some("key")).unwrap();
+        assert!(get_storage_connection_manager(&settings).is_ok());
+
+        settings.encryption = Some(DatabaseEncryptionSettings::with_key("wrong".to_string()));
+        assert!(matches!(
+            get_storage_connection_manager(&settings),
+            Err(StorageConnectionError::WrongKey(_, _))
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
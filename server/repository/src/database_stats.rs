@@ -0,0 +1,68 @@
+//! Size and location of the database, reported to the central server with site health
+
+use crate::{RepositoryError, StorageConnection};
+use diesel::{sql_types::BigInt, RunQueryDsl};
+use std::path::PathBuf;
+
+#[derive(QueryableByName)]
+struct DatabaseSize {
+    #[diesel(sql_type = BigInt)]
+    size: i64,
+}
+
+/// Size of the database in bytes (including free pages for sqlite)
+pub fn database_size_bytes(connection: &StorageConnection) -> Result<i64, RepositoryError> {
+    #[cfg(feature = "postgres")]
+    let query = "SELECT pg_database_size(current_database()) AS size";
+    // feature sqlite
+    #[cfg(not(feature = "postgres"))]
+    let query =
+        "SELECT page_count * page_size AS size FROM pragma_page_count(), pragma_page_size()";
+
+    let result =
+        diesel::sql_query(query).get_result::<DatabaseSize>(connection.lock().connection())?;
+    Ok(result.size)
+}
+
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+#[derive(QueryableByName)]
+struct DatabaseFile {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    file: String,
+}
+
+/// File of the sqlite database, None for postgres (data directory may be on another machine) and
+/// for in memory databases
+pub fn database_file_path(
+    connection: &StorageConnection,
+) -> Result<Option<PathBuf>, RepositoryError> {
+    #[cfg(feature = "postgres")]
+    {
+        let _ = connection;
+        Ok(None)
+    }
+
+    // feature sqlite
+    #[cfg(not(feature = "postgres"))]
+    {
+        let result = diesel::sql_query("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .get_result::<DatabaseFile>(connection.lock().connection())?;
+        Ok(Some(PathBuf::from(result.file)).filter(|file| !file.as_os_str().is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{mock::MockDataInserts, test_db::setup_all};
+
+    #[actix_rt::test]
+    async fn test_database_stats() {
+        let (_, connection, _, _) = setup_all("test_database_stats", MockDataInserts::none()).await;
+
+        assert!(database_size_bytes(&connection).unwrap() > 0);
+        // Test databases can be in memory
+        let _ = database_file_path(&connection).unwrap();
+    }
+}
@@ -0,0 +1,121 @@
+use super::{
+    activity_log_chain_anchor_row::activity_log_chain_anchor::dsl::*, ChangeLogInsertRow,
+    ChangelogRepository, ChangelogTableName, RowActionType,
+};
+use crate::{RepositoryError, StorageConnection, Upsert};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    activity_log_chain_anchor (id) {
+        id -> Text,
+        store_id -> Text,
+        sequence -> BigInt,
+        hash -> Text,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Head of a store's activity log hash chain at a point in time. Anchors are pushed to the
+/// open mSupply central server, so a chain rewritten locally no longer matches the anchors held
+/// centrally
+#[derive(
+    Clone,
+    Queryable,
+    Insertable,
+    Identifiable,
+    Debug,
+    PartialEq,
+    Eq,
+    AsChangeset,
+    Serialize,
+    Deserialize,
+    Default,
+)]
+#[diesel(table_name = activity_log_chain_anchor)]
+pub struct ActivityLogChainAnchorRow {
+    pub id: String,
+    pub store_id: String,
+    pub sequence: i64,
+    pub hash: String,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct ActivityLogChainAnchorRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ActivityLogChainAnchorRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ActivityLogChainAnchorRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ActivityLogChainAnchorRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(activity_log_chain_anchor)
+            .values(row)
+            .on_conflict(id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        let changelog = ChangeLogInsertRow {
+            table_name: ChangelogTableName::ActivityLogChainAnchor,
+            record_id: row.id.clone(),
+            row_action: RowActionType::Upsert,
+            store_id: Some(row.store_id.clone()),
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&changelog)
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        anchor_id: &str,
+    ) -> Result<Option<ActivityLogChainAnchorRow>, RepositoryError> {
+        let result = activity_log_chain_anchor
+            .filter(id.eq(anchor_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Latest anchor of the store
+    pub fn find_latest(
+        &self,
+        for_store_id: &str,
+    ) -> Result<Option<ActivityLogChainAnchorRow>, RepositoryError> {
+        let result = activity_log_chain_anchor
+            .filter(store_id.eq(for_store_id))
+            .order(sequence.desc())
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        for_store_id: &str,
+    ) -> Result<Vec<ActivityLogChainAnchorRow>, RepositoryError> {
+        let result = activity_log_chain_anchor
+            .filter(store_id.eq(for_store_id))
+            .order(sequence.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
+
+impl Upsert for ActivityLogChainAnchorRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = ActivityLogChainAnchorRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            ActivityLogChainAnchorRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
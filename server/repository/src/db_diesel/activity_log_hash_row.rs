@@ -0,0 +1,259 @@
+use super::{
+    activity_log_hash_row::activity_log_hash::dsl as hash_dsl,
+    activity_log_row::{activity_log, ActivityLogRow},
+};
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::{
+    prelude::*,
+    result::Error::NotFound,
+    sql_query,
+    sql_types::{BigInt, Nullable, Text},
+};
+use sha2::{Digest, Sha256};
+
+table! {
+    activity_log_hash (activity_log_id) {
+        activity_log_id -> Text,
+        store_id -> Text,
+        sequence -> BigInt,
+        previous_hash -> Nullable<Text>,
+        hash -> Text,
+    }
+}
+
+joinable!(activity_log_hash -> activity_log (activity_log_id));
+allow_tables_to_appear_in_same_query!(activity_log_hash, activity_log);
+
+/// Position of an activity log in its store's hash chain. `hash` covers the stored activity log
+/// row and `previous_hash`, so editing or removing a chained log breaks every later link.
+/// Activity logs without a store are not chained
+#[derive(
+    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+)]
+#[diesel(table_name = activity_log_hash)]
+#[diesel(primary_key(activity_log_id))]
+pub struct ActivityLogHashRow {
+    pub activity_log_id: String,
+    pub store_id: String,
+    /// Starts at 1 for the first chained log of the store
+    pub sequence: i64,
+    /// None for the first link
+    pub previous_hash: Option<String>,
+    /// SHA-256, hex encoded
+    pub hash: String,
+}
+
+/// Hash of an activity log as the link after `previous_hash` at `sequence`
+pub fn activity_log_chain_hash(
+    log: &ActivityLogRow,
+    sequence: i64,
+    previous_hash: Option<&str>,
+) -> String {
+    let ActivityLogRow {
+        id,
+        r#type,
+        user_id,
+        store_id,
+        record_id,
+        datetime,
+        changed_to,
+        changed_from,
+    } = log;
+
+    // Serialised as an array so field boundaries can't be shifted between fields
+    let content = serde_json::json!([
+        sequence,
+        previous_hash,
+        id,
+        r#type,
+        user_id,
+        store_id,
+        record_id,
+        datetime,
+        changed_to,
+        changed_from,
+    ]);
+
+    format!("{:x}", Sha256::digest(content.to_string().as_bytes()))
+}
+
+/// Sequence claimed for the next link of a store's chain and the hash of the link before it
+#[derive(QueryableByName, Debug)]
+struct ClaimedLink {
+    #[diesel(sql_type = BigInt)]
+    sequence: i64,
+    #[diesel(sql_type = Nullable<Text>)]
+    hash: Option<String>,
+}
+
+// Updating the store's head row locks it until the transaction ends, so concurrent appends to the
+// same store wait for each other rather than both linking to the same head
+const CLAIM_NEXT_LINK_QUERY: &str = "UPDATE activity_log_chain_head SET sequence = sequence + 1 WHERE store_id = $1 RETURNING sequence, hash;";
+
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+const HEAD_INSERT_QUERY: &str = "INSERT INTO activity_log_chain_head (store_id, sequence) VALUES ($1, 1) RETURNING sequence, hash;";
+
+// feature postgres
+// Another transaction may insert the store's head first, ON CONFLICT DO NOTHING returns no row
+// instead of a unique constraint violation that would abort the transaction (see number_row.rs)
+#[cfg(feature = "postgres")]
+const HEAD_INSERT_QUERY: &str = "INSERT INTO activity_log_chain_head (store_id, sequence) VALUES ($1, 1) ON CONFLICT DO NOTHING RETURNING sequence, hash;";
+
+const SET_HEAD_HASH_QUERY: &str =
+    "UPDATE activity_log_chain_head SET hash = $2 WHERE store_id = $1;";
+
+pub struct ActivityLogHashRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ActivityLogHashRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ActivityLogHashRowRepository { connection }
+    }
+
+    /// Appends the log to its store's chain, the log must already be inserted so the hash covers
+    /// the row as stored (datetime precision differs between databases)
+    pub fn append(&self, log_id: &str) -> Result<Option<ActivityLogHashRow>, RepositoryError> {
+        self.connection
+            .transaction_sync(|connection| {
+                ActivityLogHashRowRepository::new(connection).append_in_transaction(log_id)
+            })
+            .map_err(|error| error.to_inner_error())
+    }
+
+    fn append_in_transaction(
+        &self,
+        log_id: &str,
+    ) -> Result<Option<ActivityLogHashRow>, RepositoryError> {
+        let log: ActivityLogRow = activity_log::table
+            .filter(activity_log::id.eq(log_id))
+            .first(self.connection.lock().connection())?;
+        let Some(store_id) = log.store_id.clone() else {
+            return Ok(None);
+        };
+
+        let ClaimedLink {
+            sequence,
+            hash: previous_hash,
+        } = self.claim_next_link(&store_id)?;
+        let row = ActivityLogHashRow {
+            activity_log_id: log.id.clone(),
+            hash: activity_log_chain_hash(&log, sequence, previous_hash.as_deref()),
+            store_id,
+            sequence,
+            previous_hash,
+        };
+
+        let mut guard = self.connection.lock();
+        sql_query(SET_HEAD_HASH_QUERY)
+            .bind::<Text, _>(&row.store_id)
+            .bind::<Text, _>(&row.hash)
+            .execute(guard.connection())?;
+        diesel::insert_into(hash_dsl::activity_log_hash)
+            .values(&row)
+            .execute(guard.connection())?;
+        Ok(Some(row))
+    }
+
+    fn claim_next_link(&self, store_id: &str) -> Result<ClaimedLink, RepositoryError> {
+        let claim_query = sql_query(CLAIM_NEXT_LINK_QUERY).bind::<Text, _>(store_id);
+
+        let mut guard = self.connection.lock();
+        match claim_query.clone().get_result(guard.connection()) {
+            Ok(claimed) => Ok(claimed),
+            // First link of the store
+            Err(NotFound) => match sql_query(HEAD_INSERT_QUERY)
+                .bind::<Text, _>(store_id)
+                .get_result(guard.connection())
+            {
+                Ok(claimed) => Ok(claimed),
+                // Another transaction inserted the head first, it's committed by now
+                Err(NotFound) => Ok(claim_query.get_result(guard.connection())?),
+                Err(error) => Err(error.into()),
+            },
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Last link of the store's chain
+    pub fn find_head(&self, store_id: &str) -> Result<Option<ActivityLogHashRow>, RepositoryError> {
+        let result = hash_dsl::activity_log_hash
+            .filter(hash_dsl::store_id.eq(store_id))
+            .order(hash_dsl::sequence.desc())
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_activity_log_id(
+        &self,
+        log_id: &str,
+    ) -> Result<Option<ActivityLogHashRow>, RepositoryError> {
+        let result = hash_dsl::activity_log_hash
+            .filter(hash_dsl::activity_log_id.eq(log_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_sequence(
+        &self,
+        store_id: &str,
+        sequence: i64,
+    ) -> Result<Option<ActivityLogHashRow>, RepositoryError> {
+        let result = hash_dsl::activity_log_hash
+            .filter(hash_dsl::store_id.eq(store_id))
+            .filter(hash_dsl::sequence.eq(sequence))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Stores that have a chain
+    pub fn find_store_ids(&self) -> Result<Vec<String>, RepositoryError> {
+        let result = hash_dsl::activity_log_hash
+            .select(hash_dsl::store_id)
+            .distinct()
+            .order(hash_dsl::store_id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Links after `after_sequence` in chain order, with the activity log they cover (None when
+    /// the log was deleted)
+    pub fn find_links(
+        &self,
+        store_id: &str,
+        after_sequence: i64,
+        limit: i64,
+    ) -> Result<Vec<(ActivityLogHashRow, Option<ActivityLogRow>)>, RepositoryError> {
+        let result = hash_dsl::activity_log_hash
+            .left_join(activity_log::table)
+            .filter(hash_dsl::store_id.eq(store_id))
+            .filter(hash_dsl::sequence.gt(after_sequence))
+            .order(hash_dsl::sequence.asc())
+            .limit(limit)
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Ids of the store's activity logs logged since `from_datetime` that are not in the chain,
+    /// i.e. inserted without going through the repository
+    pub fn find_unchained_log_ids(
+        &self,
+        store_id: &str,
+        from_datetime: NaiveDateTime,
+    ) -> Result<Vec<String>, RepositoryError> {
+        let result = activity_log::table
+            .left_join(hash_dsl::activity_log_hash)
+            .filter(activity_log::store_id.eq(store_id))
+            .filter(activity_log::datetime.ge(from_datetime))
+            .filter(hash_dsl::activity_log_id.is_null())
+            .select(activity_log::id)
+            .order(activity_log::datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
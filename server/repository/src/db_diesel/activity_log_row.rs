@@ -3,7 +3,10 @@ use super::StorageConnection;
 use crate::{
     db_diesel::store_row::store, repository_error::RepositoryError, user_account, Delete, Upsert,
 };
-use crate::{ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType};
+use crate::{
+    ActivityLogHashRowRepository, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName,
+    RowActionType,
+};
 
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
@@ -120,6 +123,16 @@ pub enum ActivityLogType {
     PatientUpdated,
     PatientCreated,
     InvoiceDateBackdated,
+    RequisitionApprovalStepApproved,
+    RequisitionDenied,
+    RequisitionLineApprovedQuantityAdjusted,
+    StockLineRecalled,
+    KitAssembled,
+    KitDisassembled,
+    DispensingAlertOverridden,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    ApiKeyUsed,
 }
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
@@ -146,7 +159,18 @@ impl<'a> ActivityLogRowRepository<'a> {
         ActivityLogRowRepository { connection }
     }
 
+    /// Inserts an activity log logged on this site and appends it to its store's hash chain
     pub fn insert_one(&self, row: &ActivityLogRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(activity_log::table)
+            .values(row)
+            .execute(self.connection.lock().connection())?;
+        ActivityLogHashRowRepository::new(self.connection).append(&row.id)?;
+        self.insert_changelog(row, RowActionType::Upsert)
+    }
+
+    /// Inserts an activity log received by sync. It's not chained, it was chained on the site
+    /// that logged it
+    pub fn sync_insert_one(&self, row: &ActivityLogRow) -> Result<i64, RepositoryError> {
         diesel::insert_into(activity_log::table)
             .values(row)
             .execute(self.connection.lock().connection())?;
@@ -187,7 +211,7 @@ impl<'a> ActivityLogRowRepository<'a> {
 
 impl Upsert for ActivityLogRow {
     fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
-        let change_log_id = ActivityLogRowRepository::new(con).insert_one(self)?;
+        let change_log_id = ActivityLogRowRepository::new(con).sync_insert_one(self)?;
         Ok(Some(change_log_id))
     }
 
@@ -0,0 +1,69 @@
+use super::api_idempotency_key_row::api_idempotency_key::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    api_idempotency_key (id) {
+        id -> Text,
+        user_id -> Text,
+        idempotency_key -> Text,
+        request_hash -> Text,
+        response_status -> Integer,
+        response_body -> Text,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Response of a REST API create, replayed when the create is retried with the same
+/// `Idempotency-Key`. Keys are local to the site and not synced
+#[derive(
+    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+)]
+#[diesel(table_name = api_idempotency_key)]
+pub struct ApiIdempotencyKeyRow {
+    pub id: String,
+    pub user_id: String,
+    pub idempotency_key: String,
+    /// SHA-256 hash of the request method, path and body, hex encoded
+    pub request_hash: String,
+    pub response_status: i32,
+    pub response_body: String,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct ApiIdempotencyKeyRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ApiIdempotencyKeyRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ApiIdempotencyKeyRowRepository { connection }
+    }
+
+    pub fn insert_one(&self, row: &ApiIdempotencyKeyRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(api_idempotency_key)
+            .values(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_user_and_key(
+        &self,
+        for_user_id: &str,
+        for_key: &str,
+    ) -> Result<Option<ApiIdempotencyKeyRow>, RepositoryError> {
+        let result = api_idempotency_key
+            .filter(user_id.eq(for_user_id))
+            .filter(idempotency_key.eq(for_key))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn delete_created_before(&self, datetime: NaiveDateTime) -> Result<(), RepositoryError> {
+        diesel::delete(api_idempotency_key.filter(created_datetime.lt(datetime)))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use super::api_key_row::api_key::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    api_key (id) {
+        id -> Text,
+        user_id -> Text,
+        name -> Text,
+        key_prefix -> Text,
+        key_hash -> Text,
+        resources -> Text,
+        store_ids -> Text,
+        created_datetime -> Timestamp,
+        expiry_datetime -> Nullable<Timestamp>,
+        last_used_datetime -> Nullable<Timestamp>,
+        revoked_datetime -> Nullable<Timestamp>,
+    }
+}
+
+/// API key of a service account, the service account is the `user_id` user. Keys are local to
+/// the site and not synced
+#[derive(
+    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+)]
+#[diesel(table_name = api_key)]
+#[diesel(treat_none_as_null = true)]
+pub struct ApiKeyRow {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    /// Start of the key, to tell keys apart without storing them
+    pub key_prefix: String,
+    /// SHA-256 hash of the key, hex encoded
+    pub key_hash: String,
+    /// JSON array of the resources the key can access
+    pub resources: String,
+    /// JSON array of the stores the key can access
+    pub store_ids: String,
+    pub created_datetime: NaiveDateTime,
+    pub expiry_datetime: Option<NaiveDateTime>,
+    pub last_used_datetime: Option<NaiveDateTime>,
+    pub revoked_datetime: Option<NaiveDateTime>,
+}
+
+pub struct ApiKeyRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ApiKeyRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ApiKeyRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ApiKeyRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(api_key)
+            .values(row)
+            .on_conflict(id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, api_key_id: &str) -> Result<Option<ApiKeyRow>, RepositoryError> {
+        let result = api_key
+            .filter(id.eq(api_key_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_key_hash(&self, hash: &str) -> Result<Option<ApiKeyRow>, RepositoryError> {
+        let result = api_key
+            .filter(key_hash.eq(hash))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Newest first
+    pub fn find_all(&self) -> Result<Vec<ApiKeyRow>, RepositoryError> {
+        let result = api_key
+            .order(created_datetime.desc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn update_last_used_datetime(
+        &self,
+        api_key_id: &str,
+        datetime: NaiveDateTime,
+    ) -> Result<(), RepositoryError> {
+        diesel::update(api_key.filter(id.eq(api_key_id)))
+            .set(last_used_datetime.eq(datetime))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use super::audit_log_row::audit_log::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use util::uuid::uuid;
+
+table! {
+    audit_log (id) {
+        id -> Text,
+        table_name -> Text,
+        record_id -> Text,
+        change_id -> Text,
+        field -> Text,
+        old_value -> Nullable<Text>,
+        new_value -> Nullable<Text>,
+        user_id -> Text,
+        store_id -> Nullable<Text>,
+        reason -> Nullable<Text>,
+        datetime -> Timestamp,
+    }
+}
+
+/// Field changed by an audited upsert. Fields changed by the same upsert share `change_id`.
+/// Values are JSON, except strings which are stored as is
+#[derive(Clone, Queryable, Insertable, Debug, PartialEq, Eq, AsChangeset, Default)]
+#[diesel(table_name = audit_log)]
+pub struct AuditLogRow {
+    pub id: String,
+    pub table_name: String,
+    pub record_id: String,
+    pub change_id: String,
+    pub field: String,
+    /// None when the record was created or the field was null
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub user_id: String,
+    pub store_id: Option<String>,
+    pub reason: Option<String>,
+    pub datetime: NaiveDateTime,
+}
+
+pub struct AuditLogRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AuditLogRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AuditLogRowRepository { connection }
+    }
+
+    /// Inserts the rows in one statement
+    pub fn insert_many(&self, rows: &[AuditLogRow]) -> Result<(), RepositoryError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        diesel::insert_into(audit_log)
+            .values(rows)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    /// Changes of a record, newest first
+    pub fn find_many_by_record(
+        &self,
+        for_table_name: &str,
+        for_record_id: &str,
+    ) -> Result<Vec<AuditLogRow>, RepositoryError> {
+        let result = audit_log
+            .filter(table_name.eq(for_table_name))
+            .filter(record_id.eq(for_record_id))
+            .order((datetime.desc(), field.asc()))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Returns the number of changes deleted
+    pub fn delete_before(&self, before: NaiveDateTime) -> Result<usize, RepositoryError> {
+        let result = diesel::delete(audit_log.filter(datetime.lt(before)))
+            .execute(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
+
+/// Records the fields an upsert changes. Started before the upsert, to read the record as it was,
+/// and finished with the upserted row. Does nothing when the connection has no audit context
+///
+/// ```ignore
+/// let audit = AuditCapture::start(self.connection, "location", &row.id, || {
+///     self.find_one_by_id(&row.id)
+/// })?;
+/// // upsert
+/// audit.finish(row)?;
+/// ```
+pub struct AuditCapture<'a, T> {
+    connection: &'a StorageConnection,
+    table_name: &'static str,
+    record_id: String,
+    previous: Option<T>,
+    is_active: bool,
+}
+
+impl<'a, T: Serialize> AuditCapture<'a, T> {
+    pub fn start(
+        connection: &'a StorageConnection,
+        table_name: &'static str,
+        record_id: &str,
+        find_previous: impl FnOnce() -> Result<Option<T>, RepositoryError>,
+    ) -> Result<Self, RepositoryError> {
+        let is_active = connection.audit_context().is_some();
+        let previous = if is_active { find_previous()? } else { None };
+
+        Ok(AuditCapture {
+            connection,
+            table_name,
+            record_id: record_id.to_string(),
+            previous,
+            is_active,
+        })
+    }
+
+    pub fn finish(self, new: &T) -> Result<(), RepositoryError> {
+        let Some(audit_context) = self.connection.audit_context().filter(|_| self.is_active) else {
+            return Ok(());
+        };
+
+        let change_id = uuid();
+        let now = Utc::now().naive_utc();
+        let rows: Vec<AuditLogRow> = changed_fields(self.previous.as_ref(), new)?
+            .into_iter()
+            .map(|(changed_field, old, new)| AuditLogRow {
+                id: uuid(),
+                table_name: self.table_name.to_string(),
+                record_id: self.record_id.clone(),
+                change_id: change_id.clone(),
+                field: changed_field,
+                old_value: old,
+                new_value: new,
+                user_id: audit_context.user_id.clone(),
+                store_id: audit_context.store_id.clone(),
+                reason: audit_context.reason.clone(),
+                datetime: now,
+            })
+            .collect();
+
+        AuditLogRowRepository::new(self.connection).insert_many(&rows)
+    }
+}
+
+/// Top level fields that differ, with their old and new values. All non null fields when there is
+/// no previous value
+fn changed_fields<T: Serialize>(
+    previous: Option<&T>,
+    new: &T,
+) -> Result<Vec<(String, Option<String>, Option<String>)>, RepositoryError> {
+    let to_object = |value: &T| match serde_json::to_value(value) {
+        Ok(Value::Object(object)) => Ok(object),
+        Ok(_) => Err(RepositoryError::as_db_error(
+            "Audited row must serialise to an object",
+            "",
+        )),
+        Err(error) => Err(RepositoryError::as_db_error(
+            "Failed to serialise audited row",
+            error,
+        )),
+    };
+    let old = previous.map(to_object).transpose()?.unwrap_or_default();
+    let new = to_object(new)?;
+
+    let result = new
+        .into_iter()
+        .filter_map(|(key, new_value)| {
+            let old_value = old.get(&key).cloned().unwrap_or(Value::Null);
+            (old_value != new_value)
+                .then(|| (key, to_audit_value(old_value), to_audit_value(new_value)))
+        })
+        .collect();
+    Ok(result)
+}
+
+fn to_audit_value(value: Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value),
+        value => Some(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        id: String,
+        name: String,
+        on_hold: bool,
+        volume: Option<f64>,
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let old = Row {
+            id: "id".to_string(),
+            name: "old".to_string(),
+            on_hold: false,
+            volume: None,
+        };
+        let new = Row {
+            id: "id".to_string(),
+            name: "new".to_string(),
+            on_hold: false,
+            volume: Some(1.5),
+        };
+
+        assert_eq!(
+            changed_fields(Some(&old), &new).unwrap(),
+            vec![
+                (
+                    "name".to_string(),
+                    Some("old".to_string()),
+                    Some("new".to_string())
+                ),
+                ("volume".to_string(), None, Some("1.5".to_string())),
+            ]
+        );
+        // Created
+        assert_eq!(
+            changed_fields(None, &old).unwrap(),
+            vec![
+                ("id".to_string(), None, Some("id".to_string())),
+                ("name".to_string(), None, Some("old".to_string())),
+                ("on_hold".to_string(), None, Some("false".to_string())),
+            ]
+        );
+    }
+}
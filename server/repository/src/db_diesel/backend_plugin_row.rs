@@ -1,5 +1,6 @@
 use super::{
-    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, PluginMigrationRowRepository,
+    RowActionType, StorageConnection,
 };
 
 use crate::{repository_error::RepositoryError, Delete, Upsert};
@@ -14,9 +15,11 @@ pub enum PluginType {
     TransformRequestRequisitionLines,
     GetConsumption,
     GraphqlQuery,
+    GraphqlMutation,
     // TODO backwards compatibility ? When integrating this one via sync
     Processor,
     Schedule,
+    ValidateMutation,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
@@ -34,6 +37,45 @@ impl From<PluginTypes> for String {
     }
 }
 
+/// Named mutation exposed through `pluginGraphqlMutation`, input is validated against `input_schema`
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PluginMutation {
+    pub name: String,
+    pub input_schema: serde_json::Value,
+}
+
+/// SQL migration for plugin owned tables, `{prefix}` in `sql` is replaced with the table
+/// namespace of the plugin (see plugin_table_name)
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PluginMigration {
+    pub version: i32,
+    pub sql: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub mutations: Vec<PluginMutation>,
+    #[serde(default)]
+    pub migrations: Vec<PluginMigration>,
+}
+
+/// Malformed manifest fails deserialization of the row, rather than silently dropping declared
+/// mutations and migrations
+impl TryFrom<String> for PluginManifest {
+    type Error = serde_json::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value)
+    }
+}
+
+impl From<PluginManifest> for String {
+    fn from(value: PluginManifest) -> Self {
+        serde_json::to_string(&value).unwrap_or_default()
+    }
+}
+
 #[derive(DbEnum, Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -51,6 +93,7 @@ table! {
       bundle_base64 -> Text,
       types -> Text,
       variant_type  -> crate::db_diesel::backend_plugin_row::PluginVariantTypeMapping,
+      manifest -> Text,
   }
 }
 
@@ -67,6 +110,10 @@ pub struct BackendPluginRow {
     #[diesel(deserialize_as = String)]
     pub types: PluginTypes,
     pub variant_type: PluginVariantType,
+    #[diesel(serialize_as = String)]
+    #[diesel(deserialize_as = String)]
+    #[serde(default)]
+    pub manifest: PluginManifest,
 }
 
 pub struct BackendPluginRowRepository<'a> {
@@ -100,8 +147,12 @@ impl<'a> BackendPluginRowRepository<'a> {
             .values(row.clone())
             .on_conflict(backend_plugin::id)
             .do_update()
-            .set(row)
+            .set(row.clone())
             .execute(self.connection.lock().connection())?;
+        // Plugin tables need to exist before any of the plugin's records are integrated
+        // or the plugin is called, so they are created alongside the plugin row
+        PluginMigrationRowRepository::new(self.connection)
+            .migrate(&row.code, &row.manifest.migrations)?;
         self.insert_changelog(&id, RowActionType::Upsert)
     }
 
@@ -231,4 +282,30 @@ mod test {
             r#"["average_monthly_consumption","transform_request_requisition_lines"]"#
         );
     }
+
+    #[actix_rt::test]
+    async fn backend_plugin_row_malformed_manifest() {
+        let (_, connection, _, _) = setup_all(
+            "backend_plugin_row_malformed_manifest",
+            MockDataInserts::none(),
+        )
+        .await;
+
+        let repo = BackendPluginRowRepository::new(&connection);
+        let id = "backend_plugin_row_malformed_manifest";
+        repo.upsert_one(BackendPluginRow {
+            id: id.to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            repo.find_one_by_id(id).unwrap().unwrap().manifest,
+            PluginManifest::default()
+        );
+
+        sql_query("UPDATE backend_plugin SET manifest = '{\"mutations\": 1}'")
+            .execute(connection.lock().connection())
+            .unwrap();
+        assert!(repo.find_one_by_id(id).is_err());
+    }
 }
@@ -119,6 +119,7 @@ pub enum ChangelogTableName {
     Report,
     FormSchema,
     PluginData,
+    PluginTable,
     Preference,
     VVMStatusLog,
     Campaign,
@@ -126,6 +127,17 @@ pub enum ChangelogTableName {
     PurchaseOrder,
     PurchaseOrderLine,
     MasterList,
+    RequisitionApprovalStep,
+    RequisitionApproval,
+    Recall,
+    SerialNumber,
+    SerialNumberLine,
+    KitComponent,
+    KitAssembly,
+    ItemClinicalInfo,
+    DrugInteraction,
+    ActivityLogChainAnchor,
+    EmailQueue,
 }
 
 pub(crate) enum ChangeLogSyncStyle {
@@ -140,6 +152,16 @@ pub(crate) enum ChangeLogSyncStyle {
 // When adding a new change log record type, specify how it should be synced
 // If new requirements are needed a different ChangeLogSyncStyle can be added
 impl ChangelogTableName {
+    /// Tables whose store records are resolved into live subscription events
+    pub fn is_store_event(&self) -> bool {
+        matches!(
+            self,
+            ChangelogTableName::StockLine
+                | ChangelogTableName::TemperatureBreach
+                | ChangelogTableName::ActivityLog
+        )
+    }
+
     pub(crate) fn sync_style(&self) -> ChangeLogSyncStyle {
         match self {
             ChangelogTableName::BackendPlugin => ChangeLogSyncStyle::Central,
@@ -204,6 +226,7 @@ impl ChangelogTableName {
             ChangelogTableName::Report => ChangeLogSyncStyle::Central,
             ChangelogTableName::FormSchema => ChangeLogSyncStyle::Central,
             ChangelogTableName::PluginData => ChangeLogSyncStyle::RemoteAndCentral,
+            ChangelogTableName::PluginTable => ChangeLogSyncStyle::RemoteAndCentral,
             ChangelogTableName::Preference => ChangeLogSyncStyle::RemoteAndCentral,
             ChangelogTableName::VVMStatusLog => ChangeLogSyncStyle::Legacy,
             ChangelogTableName::Campaign => ChangeLogSyncStyle::Central,
@@ -211,6 +234,17 @@ impl ChangelogTableName {
             ChangelogTableName::PurchaseOrder => ChangeLogSyncStyle::Legacy,
             ChangelogTableName::PurchaseOrderLine => ChangeLogSyncStyle::Legacy,
             ChangelogTableName::MasterList => ChangeLogSyncStyle::ProcessorOnly,
+            ChangelogTableName::RequisitionApprovalStep => ChangeLogSyncStyle::Central,
+            ChangelogTableName::RequisitionApproval => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::Recall => ChangeLogSyncStyle::Central,
+            ChangelogTableName::SerialNumber => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::SerialNumberLine => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::KitComponent => ChangeLogSyncStyle::Central,
+            ChangelogTableName::KitAssembly => ChangeLogSyncStyle::Remote,
+            ChangelogTableName::ItemClinicalInfo => ChangeLogSyncStyle::Central,
+            ChangelogTableName::DrugInteraction => ChangeLogSyncStyle::Central,
+            ChangelogTableName::ActivityLogChainAnchor => ChangeLogSyncStyle::RemoteToCentral,
+            ChangelogTableName::EmailQueue => ChangeLogSyncStyle::RemoteToCentral,
         }
     }
 }
@@ -388,6 +422,15 @@ impl<'a> ChangelogRepository<'a> {
         Ok(result as u64)
     }
 
+    /// Returns the cursor of the first change log of a record, used to tell if a record is new
+    pub fn first_cursor(&self, record_id: &str) -> Result<Option<i64>, RepositoryError> {
+        let result = changelog::table
+            .filter(changelog::record_id.eq(record_id))
+            .select(diesel::dsl::min(changelog::cursor))
+            .first::<Option<i64>>(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
     /// Returns latest change log
     /// After initial sync we use this method to get the latest cursor to make sure we don't try to push any records that were synced to this site on initialisation
     pub fn latest_cursor(&self) -> Result<u64, RepositoryError> {
@@ -444,6 +487,10 @@ impl<'a> ChangelogRepository<'a> {
             .unwrap_or_default(); // This shouldn't happen, maybe should unwrap or panic?
 
         self.connection.notify(TransactionNotification::ChangelogInsert);
+        if row.store_id.is_some() && row.table_name.is_store_event() {
+            self.connection
+                .notify(TransactionNotification::StoreRecordChanged);
+        }
         Ok(cursor_id)
     }
 
@@ -457,6 +504,10 @@ impl<'a> ChangelogRepository<'a> {
         let cursor_id = diesel::select(last_insert_rowid())
             .get_result::<i64>(self.connection.lock().connection())?;
         self.connection.notify(TransactionNotification::ChangelogInsert);
+        if row.store_id.is_some() && row.table_name.is_store_event() {
+            self.connection
+                .notify(TransactionNotification::StoreRecordChanged);
+        }
         Ok(cursor_id)
     }
 }
@@ -0,0 +1,137 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+table! {
+    drug_interaction (id) {
+        id -> Text,
+        atc_code_a -> Text,
+        atc_code_b -> Text,
+        severity -> crate::db_diesel::drug_interaction_row::DrugInteractionSeverityMapping,
+        description -> Text,
+    }
+}
+
+#[derive(
+    DbEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum DrugInteractionSeverity {
+    #[default]
+    Minor,
+    Moderate,
+    Major,
+    Contraindicated,
+}
+
+/// Interaction between medicines in two ATC groups. Codes match any item whose ATC code starts
+/// with them, so an interaction can be between whole therapeutic classes
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = drug_interaction)]
+pub struct DrugInteractionRow {
+    pub id: String,
+    pub atc_code_a: String,
+    pub atc_code_b: String,
+    pub severity: DrugInteractionSeverity,
+    pub description: String,
+}
+
+pub struct DrugInteractionRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> DrugInteractionRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        DrugInteractionRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &DrugInteractionRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(drug_interaction::table)
+            .values(row)
+            .on_conflict(drug_interaction::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row.id.clone(), RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row_id: String,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::DrugInteraction,
+            record_id: row_id,
+            row_action: action,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<DrugInteractionRow>, RepositoryError> {
+        let result = drug_interaction::table
+            .filter(drug_interaction::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<DrugInteractionRow>, RepositoryError> {
+        let result = drug_interaction::table
+            .order(drug_interaction::atc_code_a.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<Option<i64>, RepositoryError> {
+        diesel::delete(drug_interaction::table.filter(drug_interaction::id.eq(id)))
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(
+            self.insert_changelog(id.to_string(), RowActionType::Delete)?,
+        ))
+    }
+}
+
+impl Upsert for DrugInteractionRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = DrugInteractionRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            DrugInteractionRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DrugInteractionRowDelete(pub String);
+impl Delete for DrugInteractionRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        DrugInteractionRowRepository::new(con).delete(&self.0)
+    }
+
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            DrugInteractionRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
@@ -1,8 +1,12 @@
-use super::email_queue_row::email_queue::dsl::*;
+use super::{
+    email_queue_row::email_queue::dsl::*, ChangeLogInsertRow, ChangelogRepository,
+    ChangelogTableName, RowActionType,
+};
 use crate::{RepositoryError, StorageConnection, Upsert};
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
 
 table! {
     email_queue (id) {
@@ -21,7 +25,7 @@ table! {
     }
 }
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 #[PgType = "email_queue_status_enum"]
 pub enum EmailQueueStatus {
@@ -32,8 +36,20 @@ pub enum EmailQueueStatus {
     Failed,  // Failed will NOT be re-tried
 }
 
+/// Emails queued on remote sites are pushed to the open mSupply central server, which is the only
+/// site that sends them
 #[derive(
-    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+    Clone,
+    Queryable,
+    Insertable,
+    Identifiable,
+    Debug,
+    PartialEq,
+    Eq,
+    AsChangeset,
+    Default,
+    Serialize,
+    Deserialize,
 )]
 #[diesel(table_name = email_queue)]
 pub struct EmailQueueRow {
@@ -60,6 +76,22 @@ impl<'a> EmailQueueRowRepository<'a> {
         EmailQueueRowRepository { connection }
     }
 
+    /// Queues a new email, status updates while sending use upsert_one and are not synced
+    pub fn insert_one(&self, email_queue_row: &EmailQueueRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(email_queue)
+            .values(email_queue_row)
+            .execute(self.connection.lock().connection())?;
+
+        let changelog = ChangeLogInsertRow {
+            table_name: ChangelogTableName::EmailQueue,
+            record_id: email_queue_row.id.clone(),
+            row_action: RowActionType::Upsert,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&changelog)
+    }
+
     pub fn upsert_one(&self, email_queue_row: &EmailQueueRow) -> Result<(), RepositoryError> {
         diesel::insert_into(email_queue)
             .values(email_queue_row)
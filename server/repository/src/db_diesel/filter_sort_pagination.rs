@@ -340,3 +340,11 @@ impl Pagination {
         }
     }
 }
+
+/// Keyset pagination position: rows after the row with `sort_key` and `id`, in ascending sort key
+/// then id order. Unlike an offset, pages don't shift when rows are inserted or deleted
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyset<T> {
+    pub sort_key: T,
+    pub id: String,
+}
@@ -8,14 +8,15 @@ use super::{
 use crate::{
     diesel_extensions::datetime_coalesce,
     diesel_macros::{
-        apply_date_time_filter, apply_equal_filter, apply_sort, apply_sort_no_case,
+        apply_date_time_filter, apply_equal_filter, apply_keyset, apply_sort, apply_sort_no_case,
         apply_string_filter,
     },
     ClinicianLinkRow,
 };
 
-use crate::{DatetimeFilter, EqualFilter, Pagination, Sort, StringFilter};
+use crate::{DatetimeFilter, EqualFilter, Keyset, Pagination, Sort, StringFilter};
 
+use chrono::NaiveDateTime;
 use diesel::{
     dsl::IntoBoxed,
     prelude::*,
@@ -190,6 +191,23 @@ impl<'a> InvoiceRepository<'a> {
         Ok(result.into_iter().map(to_domain).collect())
     }
 
+    /// Up to `limit` invoices after `after`, by created datetime then id
+    pub fn query_after(
+        &self,
+        filter: Option<InvoiceFilter>,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+    ) -> Result<Vec<Invoice>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        apply_keyset!(query, after, invoice::created_datetime, invoice::id);
+
+        let result = query
+            .limit(limit as i64)
+            .load::<InvoiceJoin>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
+
     pub fn find_one_by_id(&self, record_id: &str) -> Result<InvoiceJoin, RepositoryError> {
         Ok(invoice::table
             .filter(invoice::id.eq(record_id))
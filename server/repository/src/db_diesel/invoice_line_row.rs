@@ -17,6 +17,8 @@ use diesel::prelude::*;
 
 use chrono::NaiveDate;
 use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
 define_linked_tables! {
     view: invoice_line = "invoice_line_view",
@@ -91,7 +93,8 @@ allow_tables_to_appear_in_same_query!(invoice_line_stats, reason_option);
 allow_tables_to_appear_in_same_query!(invoice_line_stats, item_link);
 allow_tables_to_appear_in_same_query!(invoice_line_stats, item);
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default, TS, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum InvoiceLineType {
     #[default]
@@ -101,7 +104,8 @@ pub enum InvoiceLineType {
     Service,
 }
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, TS, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum InvoiceLineStatus {
     Pending,
@@ -109,7 +113,7 @@ pub enum InvoiceLineStatus {
     Rejected,
 }
 
-#[derive(Clone, Queryable, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Debug, PartialEq, Default, TS, Serialize, Deserialize)]
 #[diesel(table_name = invoice_line)]
 pub struct InvoiceLineRow {
     pub id: String,
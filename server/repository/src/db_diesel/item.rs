@@ -15,12 +15,12 @@ use ts_rs::TS;
 use crate::{
     category_row::category,
     diesel_macros::{
-        apply_equal_filter, apply_sort, apply_sort_no_case, apply_string_filter,
+        apply_equal_filter, apply_keyset, apply_sort, apply_sort_no_case, apply_string_filter,
         apply_string_or_filter,
     },
     item_store_join::item_store_join,
     repository_error::RepositoryError,
-    EqualFilter, Pagination, Sort, StringFilter,
+    EqualFilter, Keyset, Pagination, Sort, StringFilter,
 };
 
 #[derive(PartialEq, Debug, Clone, Default)]
@@ -243,6 +243,24 @@ impl<'a> ItemRepository<'a> {
         Ok(result.into_iter().map(to_domain).collect())
     }
 
+    /// Up to `limit` items after `after`, by name then id
+    pub fn query_after(
+        &self,
+        store_id: String,
+        filter: Option<ItemFilter>,
+        after: Option<Keyset<String>>,
+        limit: u32,
+    ) -> Result<Vec<Item>, RepositoryError> {
+        let mut query = Self::create_filtered_query(store_id, filter);
+        apply_keyset!(query, after, item::name, item::id);
+
+        let result = query
+            .limit(limit as i64)
+            .load::<ItemAndUnit>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
+
     pub fn create_filtered_query(store_id: String, filter: Option<ItemFilter>) -> BoxedItemQuery {
         let mut query = item::table.left_join(unit::table).into_boxed();
 
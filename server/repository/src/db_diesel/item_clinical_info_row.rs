@@ -0,0 +1,126 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    item_clinical_info (id) {
+        id -> Text,
+        item_id -> Text,
+        atc_code -> Nullable<Text>,
+        max_daily_dose -> Nullable<Double>,
+    }
+}
+
+/// Clinical reference data of an item used by dispensing safety checks. `max_daily_dose` is in
+/// units of the item
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = item_clinical_info)]
+#[diesel(treat_none_as_null = true)]
+pub struct ItemClinicalInfoRow {
+    pub id: String,
+    pub item_id: String,
+    /// WHO Anatomical Therapeutic Chemical code, e.g. `N02BE01`
+    pub atc_code: Option<String>,
+    pub max_daily_dose: Option<f64>,
+}
+
+pub struct ItemClinicalInfoRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ItemClinicalInfoRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ItemClinicalInfoRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &ItemClinicalInfoRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(item_clinical_info::table)
+            .values(row)
+            .on_conflict(item_clinical_info::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row.id.clone(), RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row_id: String,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::ItemClinicalInfo,
+            record_id: row_id,
+            row_action: action,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<ItemClinicalInfoRow>, RepositoryError> {
+        let result = item_clinical_info::table
+            .filter(item_clinical_info::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_item_ids(
+        &self,
+        item_ids: &[String],
+    ) -> Result<Vec<ItemClinicalInfoRow>, RepositoryError> {
+        let result = item_clinical_info::table
+            .filter(item_clinical_info::item_id.eq_any(item_ids))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<Option<i64>, RepositoryError> {
+        diesel::delete(item_clinical_info::table.filter(item_clinical_info::id.eq(id)))
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(
+            self.insert_changelog(id.to_string(), RowActionType::Delete)?,
+        ))
+    }
+}
+
+impl Upsert for ItemClinicalInfoRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = ItemClinicalInfoRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            ItemClinicalInfoRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ItemClinicalInfoRowDelete(pub String);
+impl Delete for ItemClinicalInfoRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        ItemClinicalInfoRowRepository::new(con).delete(&self.0)
+    }
+
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            ItemClinicalInfoRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
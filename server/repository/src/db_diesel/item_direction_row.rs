@@ -68,6 +68,18 @@ impl<'a> ItemDirectionRowRepository<'a> {
         Ok(result)
     }
 
+    /// Directions of the items, highest priority first
+    pub fn find_many_by_item_link_ids(
+        &self,
+        item_link_ids: &[String],
+    ) -> Result<Vec<ItemDirectionRow>, RepositoryError> {
+        let result = item_direction
+            .filter(item_link_id.eq_any(item_link_ids))
+            .order(priority.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
     pub fn delete(&self, item_direction_id: &str) -> Result<(), RepositoryError> {
         diesel::delete(item_direction.filter(id.eq(item_direction_id)))
             .execute(self.connection.lock().connection())?;
@@ -1,4 +1,4 @@
-use crate::{Delete, Upsert};
+use crate::{AuditCapture, Delete, Upsert};
 
 use super::{
     clinician_link_row::clinician_link, item_link_row::item_link, item_row::item::dsl::*,
@@ -53,7 +53,7 @@ pub enum ItemType {
     NonStock,
 }
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Default, Serialize)]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum VENCategory {
     V,
@@ -63,7 +63,7 @@ pub enum VENCategory {
     NotAssigned,
 }
 
-#[derive(Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset)]
+#[derive(Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = item)]
 pub struct ItemRow {
@@ -130,12 +130,16 @@ impl<'a> ItemRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, item_row: &ItemRow) -> Result<(), RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "item", &item_row.id, || {
+            self.find_one_by_id(&item_row.id)
+        })?;
         diesel::insert_into(item)
             .values(item_row)
             .on_conflict(id)
             .do_update()
             .set(item_row)
             .execute(self.connection.lock().connection())?;
+        audit.finish(item_row)?;
 
         insert_or_ignore_item_link(self.connection, item_row)?;
         Ok(())
@@ -4,8 +4,8 @@ use crate::{
         location_type_row::location_type, name_link_row::name_link, name_row::name,
     },
     diesel_macros::define_linked_tables,
-    item_link, user_account, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName,
-    RepositoryError, RowActionType, StorageConnection, Upsert,
+    item_link, user_account, AuditCapture, ChangeLogInsertRow, ChangelogRepository,
+    ChangelogTableName, RepositoryError, RowActionType, StorageConnection, Upsert,
 };
 
 use chrono::NaiveDateTime;
@@ -71,7 +71,11 @@ impl<'a> ItemVariantRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &ItemVariantRow) -> Result<i64, RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "item_variant", &row.id, || {
+            self.find_one_by_id(&row.id)
+        })?;
         self._upsert(row)?;
+        audit.finish(row)?;
         self.insert_changelog(row.id.to_string(), RowActionType::Upsert)
     }
 
@@ -36,6 +36,7 @@ pub enum KeyType {
     AssignRequisitionNumberProcessorCursor,
     AddCentralPatientVisibilityProcessorCursor,
     RequisitionAutoFinaliseProcessorCursor,
+    RecallProcessorCursor,
     // Nested key value store to store dynamic cursor values as JSON text
     DynamicCursor,
 
@@ -56,12 +57,19 @@ pub enum KeyType {
     SettingsDisplayCustomTheme,
     SettingsDisplayCustomThemeHash,
     SettingsLabelPrinter,
+    SettingsEpcis,
+    SettingsAuditLogRetentionDays,
 
     LogLevel,
     LogDirectory,
     LogFileName,
 
     LastLedgerFixRun,
+
+    /// JSON of the last backup attempt, see service::backup::BackupStatus
+    BackupStatus,
+    /// Set when the database was restored from a backup, cleared by the next successful sync
+    SyncResyncAfterRestore,
 }
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
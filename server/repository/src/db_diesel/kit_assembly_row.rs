@@ -0,0 +1,126 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+table! {
+    kit_assembly (id) {
+        id -> Text,
+        store_id -> Text,
+        invoice_id -> Text,
+        kit_item_id -> Text,
+        kit_stock_line_id -> Text,
+        number_of_kits -> Double,
+        assembly_type -> crate::db_diesel::kit_assembly_row::KitAssemblyTypeMapping,
+        user_id -> Nullable<Text>,
+        created_datetime -> Timestamp,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum KitAssemblyType {
+    /// Components consumed to make kits
+    #[default]
+    Assembly,
+    /// Kits broken back down into components
+    Disassembly,
+}
+
+/// Kit assembly or disassembly done in a store. Stock movements are recorded on the repack
+/// invoice `invoice_id`, `kit_stock_line_id` is the kit stock line created or broken down
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = kit_assembly)]
+#[diesel(treat_none_as_null = true)]
+pub struct KitAssemblyRow {
+    pub id: String,
+    pub store_id: String,
+    pub invoice_id: String,
+    pub kit_item_id: String,
+    pub kit_stock_line_id: String,
+    pub number_of_kits: f64,
+    pub assembly_type: KitAssemblyType,
+    pub user_id: Option<String>,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct KitAssemblyRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> KitAssemblyRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        KitAssemblyRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &KitAssemblyRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(kit_assembly::table)
+            .values(row)
+            .on_conflict(kit_assembly::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        let changelog = ChangeLogInsertRow {
+            table_name: ChangelogTableName::KitAssembly,
+            record_id: row.id.clone(),
+            row_action: RowActionType::Upsert,
+            store_id: Some(row.store_id.clone()),
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&changelog)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<KitAssemblyRow>, RepositoryError> {
+        let result = kit_assembly::table
+            .filter(kit_assembly::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<KitAssemblyRow>, RepositoryError> {
+        let result = kit_assembly::table
+            .filter(kit_assembly::store_id.eq(store_id))
+            .order(kit_assembly::created_datetime.desc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_kit_stock_line_id(
+        &self,
+        kit_stock_line_id: &str,
+    ) -> Result<Vec<KitAssemblyRow>, RepositoryError> {
+        let result = kit_assembly::table
+            .filter(kit_assembly::kit_stock_line_id.eq(kit_stock_line_id))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
+
+impl Upsert for KitAssemblyRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = KitAssemblyRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            KitAssemblyRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
@@ -0,0 +1,125 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    kit_component (id) {
+        id -> Text,
+        kit_item_id -> Text,
+        component_item_id -> Text,
+        quantity -> Double,
+    }
+}
+
+/// Bill of materials line, `quantity` units of the component item go into one unit of the kit item
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = kit_component)]
+pub struct KitComponentRow {
+    pub id: String,
+    pub kit_item_id: String,
+    pub component_item_id: String,
+    pub quantity: f64,
+}
+
+pub struct KitComponentRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> KitComponentRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        KitComponentRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &KitComponentRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(kit_component::table)
+            .values(row)
+            .on_conflict(kit_component::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row.id.clone(), RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row_id: String,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::KitComponent,
+            record_id: row_id,
+            row_action: action,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<KitComponentRow>, RepositoryError> {
+        let result = kit_component::table
+            .filter(kit_component::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Bill of materials of a kit item
+    pub fn find_many_by_kit_item_id(
+        &self,
+        kit_item_id: &str,
+    ) -> Result<Vec<KitComponentRow>, RepositoryError> {
+        let result = kit_component::table
+            .filter(kit_component::kit_item_id.eq(kit_item_id))
+            .order(kit_component::component_item_id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<Option<i64>, RepositoryError> {
+        diesel::delete(kit_component::table.filter(kit_component::id.eq(id)))
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(
+            self.insert_changelog(id.to_string(), RowActionType::Delete)?,
+        ))
+    }
+}
+
+impl Upsert for KitComponentRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = KitComponentRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            KitComponentRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KitComponentRowDelete(pub String);
+impl Delete for KitComponentRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        KitComponentRowRepository::new(con).delete(&self.0)
+    }
+
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            KitComponentRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
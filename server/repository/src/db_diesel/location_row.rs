@@ -2,9 +2,12 @@ use super::{
     assets::asset_internal_location_row::asset_internal_location, item_link_row::item_link,
     store_row::store, RepositoryError, StorageConnection,
 };
-use crate::{ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType};
+use crate::{
+    AuditCapture, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType,
+};
 use crate::{Delete, Upsert};
 use diesel::prelude::*;
+use serde::Serialize;
 
 table! {
     location (id) {
@@ -22,7 +25,7 @@ joinable!(location -> store (store_id));
 allow_tables_to_appear_in_same_query!(location, item_link);
 allow_tables_to_appear_in_same_query!(location, asset_internal_location);
 
-#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default, Serialize)]
 #[diesel(table_name = location)]
 #[diesel(treat_none_as_null = true)]
 pub struct LocationRow {
@@ -45,12 +48,16 @@ impl<'a> LocationRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &LocationRow) -> Result<i64, RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "location", &row.id, || {
+            self.find_one_by_id(&row.id)
+        })?;
         diesel::insert_into(location::table)
             .values(row)
             .on_conflict(location::id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
+        audit.finish(row)?;
         self.insert_changelog(row, RowActionType::Upsert)
     }
 
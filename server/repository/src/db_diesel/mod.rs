@@ -3,11 +3,16 @@ use crate::repository_error::RepositoryError;
 pub mod abbreviation;
 pub mod abbreviation_row;
 pub mod activity_log;
+mod activity_log_chain_anchor_row;
+mod activity_log_hash_row;
 pub mod activity_log_row;
 pub mod adjustment;
 pub mod ancillary_item;
 pub mod ancillary_item_row;
+mod api_idempotency_key_row;
+mod api_key_row;
 pub mod assets;
+mod audit_log_row;
 pub mod backend_plugin_row;
 pub mod barcode;
 mod barcode_row;
@@ -42,6 +47,7 @@ pub mod document;
 pub mod document_registry;
 mod document_registry_config;
 pub mod document_registry_row;
+mod drug_interaction_row;
 pub mod email_queue_row;
 pub mod encounter;
 pub mod encounter_row;
@@ -64,6 +70,7 @@ pub mod invoice_row;
 pub mod item;
 pub mod item_category;
 pub mod item_category_row;
+mod item_clinical_info_row;
 pub mod item_direction;
 pub mod item_direction_row;
 pub mod item_ledger;
@@ -74,6 +81,8 @@ pub mod item_variant;
 pub mod item_warning_join;
 pub mod item_warning_join_row;
 pub mod key_value_store;
+mod kit_assembly_row;
+mod kit_component_row;
 pub mod location;
 pub mod location_movement;
 mod location_movement_row;
@@ -101,6 +110,8 @@ pub mod patient;
 pub mod period;
 pub mod plugin_data;
 pub mod plugin_data_row;
+mod plugin_migration_row;
+pub mod plugin_table_row;
 pub mod preference;
 mod preference_row;
 pub mod printer;
@@ -120,11 +131,14 @@ pub mod purchase_order_line_row;
 pub mod purchase_order_row;
 pub mod reason_option;
 pub mod reason_option_row;
+mod recall_row;
 pub mod replenishment;
 pub mod report;
 mod report_query;
 pub mod report_row;
 pub mod requisition;
+mod requisition_approval_row;
+mod requisition_approval_step_row;
 pub mod requisition_line;
 pub mod rnr_form;
 pub mod rnr_form_line;
@@ -132,6 +146,10 @@ pub mod rnr_form_line_row;
 pub mod rnr_form_row;
 pub mod sensor;
 pub mod sensor_row;
+mod serial_number_line_row;
+mod serial_number_row;
+mod site_backup_status_row;
+mod site_health_report_row;
 pub mod shipping_method;
 pub mod shipping_method_row;
 pub mod stock_line;
@@ -176,13 +194,20 @@ pub mod vaccine_course;
 pub mod vvm_status;
 pub mod warning;
 pub mod warning_row;
+mod webhook_delivery_row;
+mod webhook_row;
 
 pub use abbreviation_row::*;
+pub use activity_log_chain_anchor_row::*;
+pub use activity_log_hash_row::*;
 pub use activity_log_row::*;
 pub use adjustment::*;
 pub use ancillary_item::*;
 pub use ancillary_item_row::*;
+pub use api_idempotency_key_row::*;
+pub use api_key_row::*;
 pub use assets::*;
+pub use audit_log_row::*;
 pub use backend_plugin_row::*;
 pub use barcode_row::*;
 pub use campaign::*;
@@ -207,6 +232,7 @@ pub use document::*;
 pub use document_registry::*;
 pub use document_registry_config::*;
 pub use document_registry_row::*;
+pub use drug_interaction_row::*;
 pub use encounter::*;
 pub use encounter_row::*;
 pub use filter_sort_pagination::*;
@@ -222,6 +248,7 @@ pub use invoice_line::*;
 pub use invoice_line_row::*;
 pub use invoice_row::*;
 pub use item::*;
+pub use item_clinical_info_row::*;
 pub use item_direction_row::*;
 pub use item_ledger::*;
 pub use item_link_row::*;
@@ -230,6 +257,8 @@ pub use item_store_join::*;
 pub use item_warning_join::*;
 pub use item_warning_join_row::*;
 pub use key_value_store::*;
+pub use kit_assembly_row::*;
+pub use kit_component_row::*;
 pub use location_movement_row::*;
 pub use location_row::*;
 pub use location_type::*;
@@ -254,6 +283,8 @@ pub use patient::*;
 pub use period::*;
 pub use plugin_data::*;
 pub use plugin_data_row::*;
+pub use plugin_migration_row::*;
+pub use plugin_table_row::*;
 pub use preference::*;
 pub use preference_row::*;
 pub use printer_row::*;
@@ -271,11 +302,14 @@ pub use purchase_order_line_row::*;
 pub use purchase_order_row::*;
 pub use reason_option::*;
 pub use reason_option_row::*;
+pub use recall_row::*;
 pub use replenishment::*;
 pub use report::*;
 pub use report_query::*;
 pub use report_row::*;
 pub use requisition::*;
+pub use requisition_approval_row::*;
+pub use requisition_approval_step_row::*;
 pub use requisition_line::*;
 pub use rnr_form::*;
 pub use rnr_form_line::*;
@@ -283,6 +317,10 @@ pub use rnr_form_line_row::*;
 pub use rnr_form_row::*;
 pub use sensor::*;
 pub use sensor_row::*;
+pub use serial_number_line_row::*;
+pub use serial_number_row::*;
+pub use site_backup_status_row::*;
+pub use site_health_report_row::*;
 pub use shipping_method::*;
 pub use shipping_method_row::*;
 pub use stock_line::*;
@@ -322,6 +360,8 @@ pub use vaccination_course::*;
 pub use vaccination_row::*;
 pub use warning::*;
 pub use warning_row::*;
+pub use webhook_delivery_row::*;
+pub use webhook_row::*;
 
 use diesel::{
     prelude::*,
@@ -5,12 +5,13 @@ use super::{
 
 use crate::{
     diesel_macros::{
-        apply_equal_filter, apply_sort_no_case, apply_string_filter, apply_string_or_filter,
+        apply_equal_filter, apply_keyset, apply_sort_no_case, apply_string_filter,
+        apply_string_or_filter,
     },
     name_oms_fields_alias,
     repository_error::RepositoryError,
-    EqualFilter, NameOmsFieldsRow, NameRowType, Pagination, Sort, StoreFilter, StoreRepository,
-    StringFilter,
+    EqualFilter, Keyset, NameOmsFieldsRow, NameRowType, Pagination, Sort, StoreFilter,
+    StoreRepository, StringFilter,
 };
 
 use diesel::{dsl::IntoBoxed, prelude::*};
@@ -158,6 +159,24 @@ impl<'a> NameRepository<'a> {
         Ok(result.into_iter().map(Name::from_join).collect())
     }
 
+    /// Up to `limit` names after `after`, by name then id
+    pub fn query_after(
+        &self,
+        store_id: &str,
+        filter: Option<NameFilter>,
+        after: Option<Keyset<String>>,
+        limit: u32,
+    ) -> Result<Vec<Name>, RepositoryError> {
+        let mut query = Self::create_filtered_query(store_id.to_string(), filter);
+        apply_keyset!(query, after, name::name_, name::id);
+
+        let result = query
+            .limit(limit as i64)
+            .load::<NameAndNameStoreJoin>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(Name::from_join).collect())
+    }
+
     /// Returns a list of names left joined to name_store_join (for name_store_joins matching store_id parameter)
     /// Names will still be present in result even if name_store_join doesn't match store_id in parameters
     /// but it's considered invisible in subsequent filters.
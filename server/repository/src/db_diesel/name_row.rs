@@ -5,7 +5,7 @@ use super::{
     StorageConnection,
 };
 use crate::{
-    item_link, name_link, repository_error::RepositoryError, ChangeLogInsertRow,
+    item_link, name_link, repository_error::RepositoryError, AuditCapture, ChangeLogInsertRow,
     ChangelogRepository, ChangelogTableName, EqualFilter, NameLinkRow, NameLinkRowRepository,
     RowActionType,
 };
@@ -246,7 +246,11 @@ impl<'a> NameRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &NameRow) -> Result<i64, RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "name", &row.id, || {
+            self.find_one_by_id(&row.id)
+        })?;
         self._upsert_one(row)?;
+        audit.finish(row)?;
         insert_or_ignore_name_link(self.connection, row)?;
 
         self.insert_changelog(row.id.clone(), RowActionType::Upsert)
@@ -306,9 +310,18 @@ impl<'a> NameRowRepository<'a> {
         name_id: &str,
         properties: &Option<String>,
     ) -> Result<i64, RepositoryError> {
-        diesel::update(name_oms_fields::table.find(name_id))
+        let audit = AuditCapture::start(self.connection, "name", name_id, || {
+            self.find_one_oms_fields_by_id(name_id)
+        })?;
+        let updated = diesel::update(name_oms_fields::table.find(name_id))
             .set(name_oms_fields::properties.eq(properties))
             .execute(self.connection.lock().connection())?;
+        if updated > 0 {
+            audit.finish(&NameOmsFieldsRow {
+                id: name_id.to_string(),
+                properties: properties.clone(),
+            })?;
+        }
 
         self.insert_changelog_oms_fields(name_id.to_string(), RowActionType::Upsert)
     }
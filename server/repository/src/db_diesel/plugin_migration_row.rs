@@ -0,0 +1,102 @@
+use super::{
+    backend_plugin_row::backend_plugin, plugin_table_name_prefix, PluginMigration,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{connection::SimpleConnection, prelude::*};
+
+table! {
+    plugin_migration (id) {
+        id -> Text,
+        plugin_code -> Text,
+        version -> Integer,
+        applied_datetime -> Timestamp,
+    }
+}
+
+/// Log of plugin manifest migrations applied on this site, not synced since every site
+/// applies migrations when backend plugin is upserted
+#[derive(Clone, Queryable, Insertable, Debug, PartialEq, Default)]
+#[diesel(table_name = plugin_migration)]
+pub struct PluginMigrationRow {
+    pub id: String,
+    pub plugin_code: String,
+    pub version: i32,
+    pub applied_datetime: NaiveDateTime,
+}
+
+pub struct PluginMigrationRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> PluginMigrationRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        PluginMigrationRowRepository { connection }
+    }
+
+    pub fn find_latest_version(&self, plugin_code: &str) -> Result<Option<i32>, RepositoryError> {
+        let result = plugin_migration::table
+            .filter(plugin_migration::plugin_code.eq(plugin_code))
+            .select(diesel::dsl::max(plugin_migration::version))
+            .first(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Codes of installed backend plugins with applied migrations, i.e. plugins that own tables
+    pub fn find_installed_plugin_codes(&self) -> Result<Vec<String>, RepositoryError> {
+        let installed_codes: Vec<String> = backend_plugin::table
+            .select(backend_plugin::code)
+            .load(self.connection.lock().connection())?;
+
+        let result = plugin_migration::table
+            .filter(plugin_migration::plugin_code.eq_any(installed_codes))
+            .select(plugin_migration::plugin_code)
+            .distinct()
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn insert_one(&self, row: &PluginMigrationRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(plugin_migration::table)
+            .values(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    /// Applies migrations with version above the latest applied version for the plugin,
+    /// in version order
+    pub fn migrate(
+        &self,
+        plugin_code: &str,
+        migrations: &[PluginMigration],
+    ) -> Result<(), RepositoryError> {
+        let latest_version = self.find_latest_version(plugin_code)?.unwrap_or(0);
+
+        let mut migrations: Vec<&PluginMigration> = migrations
+            .iter()
+            .filter(|migration| migration.version > latest_version)
+            .collect();
+        migrations.sort_by_key(|migration| migration.version);
+
+        let prefix = plugin_table_name_prefix(plugin_code);
+        for PluginMigration { version, sql } in migrations {
+            log::info!("Running plugin {plugin_code} migration {version}");
+            self.connection
+                .lock()
+                .connection()
+                .batch_execute(&sql.replace("{prefix}", &prefix))?;
+
+            self.insert_one(&PluginMigrationRow {
+                id: format!("{plugin_code}_{version}"),
+                plugin_code: plugin_code.to_string(),
+                version: *version,
+                applied_datetime: Utc::now().naive_utc(),
+            })?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, JsonRawRow,
+    PluginMigrationRowRepository, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use diesel::{prelude::*, sql_query, sql_types::Text};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const PLUGIN_TABLE_PREFIX: &str = "plugin_";
+/// Core tables that share the plugin table prefix
+const CORE_TABLES: [&str; 2] = ["plugin_data", "plugin_migration"];
+
+/// Tables declared in plugin manifest migrations are namespaced by plugin code,
+/// i.e. `case` table of `tb-register` plugin becomes `plugin_tb_register_case`
+pub fn plugin_table_name_prefix(plugin_code: &str) -> String {
+    let code: String = plugin_code
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '_',
+        })
+        .collect();
+    format!("{PLUGIN_TABLE_PREFIX}{code}_")
+}
+
+/// All plugin table rows share one changelog table name, so table name is part of the
+/// record_id for changelog and sync
+pub fn plugin_table_record_id(table_name: &str, id: &str) -> String {
+    format!("{table_name}:{id}")
+}
+
+pub fn parse_plugin_table_record_id(record_id: &str) -> Option<(&str, &str)> {
+    record_id.split_once(':')
+}
+
+fn is_identifier(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Row of a table declared by plugin manifest migrations, plugin tables must have
+/// `id` text primary key and can have `store_id` column (used to determine which sites
+/// the record syncs to, records without store_id are synced to all sites)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+pub struct PluginTableRow {
+    pub table_name: String,
+    /// Column values keyed by column name
+    pub data: serde_json::Value,
+}
+
+impl PluginTableRow {
+    pub fn id(&self) -> Option<&str> {
+        self.data.get("id").and_then(serde_json::Value::as_str)
+    }
+
+    pub fn store_id(&self) -> Option<String> {
+        self.data
+            .get("store_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+    }
+}
+
+pub struct PluginTableRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> PluginTableRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        PluginTableRowRepository { connection }
+    }
+
+    /// Code of the installed plugin whose migrations declare the table. Plugin codes can be
+    /// prefixes of each other (`tb` and `tb-register`), the longest matching prefix owns the table
+    pub fn find_table_plugin_code(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<String>, RepositoryError> {
+        if CORE_TABLES.contains(&table_name) || !is_identifier(table_name) {
+            return Ok(None);
+        }
+
+        let plugin_code = PluginMigrationRowRepository::new(self.connection)
+            .find_installed_plugin_codes()?
+            .into_iter()
+            .map(|code| (plugin_table_name_prefix(&code), code))
+            .filter(|(prefix, _)| table_name.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, code)| code);
+
+        Ok(plugin_code)
+    }
+
+    /// Plugins can only use tables declared by their own migrations
+    pub fn validate_plugin_table(
+        &self,
+        plugin_code: &str,
+        table_name: &str,
+    ) -> Result<(), RepositoryError> {
+        if self.find_table_plugin_code(table_name)?.as_deref() != Some(plugin_code) {
+            return Err(RepositoryError::as_db_error(
+                "Table is not declared by the plugin",
+                format!("{plugin_code}: {table_name}"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_table_name(&self, table_name: &str) -> Result<(), RepositoryError> {
+        if self.find_table_plugin_code(table_name)?.is_none() {
+            return Err(RepositoryError::as_db_error(
+                "Invalid plugin table name",
+                table_name,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn upsert_one(&self, row: &PluginTableRow) -> Result<i64, RepositoryError> {
+        let table_name = &row.table_name;
+        self.validate_table_name(table_name)?;
+
+        let id = row.id().ok_or(RepositoryError::as_db_error(
+            "Plugin table row without id",
+            row,
+        ))?;
+        let columns: Vec<&String> = row
+            .data
+            .as_object()
+            .map(|object| object.keys().collect())
+            .unwrap_or_default();
+        if let Some(column) = columns.iter().find(|column| !is_identifier(column)) {
+            return Err(RepositoryError::as_db_error(
+                "Invalid plugin table column",
+                column,
+            ));
+        }
+
+        let column_list = columns
+            .iter()
+            .map(|column| format!(r#""{column}""#))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let update_set = columns
+            .iter()
+            .filter(|column| column.as_str() != "id")
+            .map(|column| format!(r#""{column}" = excluded."{column}""#))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let on_conflict = match update_set.is_empty() {
+            true => "DO NOTHING".to_string(),
+            false => format!("DO UPDATE SET {update_set}"),
+        };
+
+        // Values are bound as one json parameter and extracted per column by the database,
+        // this way column types declared in plugin migration are respected
+        #[cfg(not(feature = "postgres"))]
+        let query = {
+            let values = columns
+                .iter()
+                .map(|column| format!("json_extract(?1, '$.{column}')"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "INSERT INTO {table_name} ({column_list}) SELECT {values} WHERE true ON CONFLICT(id) {on_conflict}"
+            )
+        };
+        #[cfg(feature = "postgres")]
+        let query = format!(
+            "INSERT INTO {table_name} ({column_list}) SELECT {column_list} FROM json_populate_record(NULL::{table_name}, $1::json) ON CONFLICT(id) {on_conflict}"
+        );
+
+        sql_query(query)
+            .bind::<Text, _>(row.data.to_string())
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(table_name, id, row.store_id(), RowActionType::Upsert)
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        table_name: &str,
+        id: &str,
+    ) -> Result<Option<PluginTableRow>, RepositoryError> {
+        self.validate_table_name(table_name)?;
+
+        #[cfg(not(feature = "postgres"))]
+        let query = {
+            #[derive(QueryableByName)]
+            struct Column {
+                #[diesel(sql_type = Text)]
+                name: String,
+            }
+
+            let columns: Vec<Column> = sql_query(format!(
+                "SELECT name FROM pragma_table_info('{table_name}')"
+            ))
+            .load(self.connection.lock().connection())?;
+            let json_object = columns
+                .iter()
+                .map(|Column { name }| format!(r#"'{name}', "{name}""#))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("SELECT json_object({json_object}) AS json_row FROM {table_name} WHERE id = ?")
+        };
+        #[cfg(feature = "postgres")]
+        let query =
+            format!("SELECT row_to_json(t)::text AS json_row FROM {table_name} t WHERE id = $1");
+
+        let result = sql_query(query)
+            .bind::<Text, _>(id)
+            .get_results::<JsonRawRow>(self.connection.lock().connection())?
+            .into_iter()
+            .next();
+
+        let Some(JsonRawRow { json_row }) = result else {
+            return Ok(None);
+        };
+
+        let data = serde_json::from_str(&json_row)
+            .map_err(|e| RepositoryError::as_db_error("Cannot parse plugin table row", e))?;
+
+        Ok(Some(PluginTableRow {
+            table_name: table_name.to_string(),
+            data,
+        }))
+    }
+
+    pub fn delete(&self, table_name: &str, id: &str) -> Result<Option<i64>, RepositoryError> {
+        let Some(old_row) = self.find_one_by_id(table_name, id)? else {
+            return Ok(None);
+        };
+
+        let change_log_id =
+            self.insert_changelog(table_name, id, old_row.store_id(), RowActionType::Delete)?;
+
+        #[cfg(not(feature = "postgres"))]
+        let query = format!("DELETE FROM {table_name} WHERE id = ?");
+        #[cfg(feature = "postgres")]
+        let query = format!("DELETE FROM {table_name} WHERE id = $1");
+
+        sql_query(query)
+            .bind::<Text, _>(id)
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(change_log_id))
+    }
+
+    fn insert_changelog(
+        &self,
+        table_name: &str,
+        id: &str,
+        store_id: Option<String>,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::PluginTable,
+            record_id: plugin_table_record_id(table_name, id),
+            row_action: action,
+            store_id,
+            name_id: None,
+        };
+
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+}
+
+impl Upsert for PluginTableRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let change_log_id = PluginTableRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(change_log_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        let id = self.id().unwrap_or_default();
+        assert_eq!(
+            PluginTableRowRepository::new(con)
+                .find_one_by_id(&self.table_name, id)
+                .map(|row| row.is_some()),
+            Ok(true)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PluginTableRowDelete {
+    pub table_name: String,
+    pub id: String,
+}
+impl Delete for PluginTableRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        PluginTableRowRepository::new(con).delete(&self.table_name, &self.id)
+    }
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            PluginTableRowRepository::new(con).find_one_by_id(&self.table_name, &self.id),
+            Ok(None)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        mock::MockDataInserts, test_db::setup_all, BackendPluginRow, BackendPluginRowRepository,
+        PluginManifest, PluginMigration, PluginMigrationRowRepository,
+    };
+
+    use super::*;
+    use serde_json::json;
+
+    #[actix_rt::test]
+    async fn plugin_table_row() {
+        let (_, connection, _, _) = setup_all("plugin_table_row", MockDataInserts::none()).await;
+
+        let migrations = vec![
+            PluginMigration {
+                version: 1,
+                sql: "CREATE TABLE {prefix}case (id TEXT NOT NULL PRIMARY KEY, store_id TEXT, note TEXT);"
+                    .to_string(),
+            },
+            PluginMigration {
+                version: 2,
+                sql: "ALTER TABLE {prefix}case ADD COLUMN visits INTEGER;".to_string(),
+            },
+        ];
+
+        // Migrations are applied when backend plugin is upserted
+        BackendPluginRowRepository::new(&connection)
+            .upsert_one(BackendPluginRow {
+                id: "tb_register".to_string(),
+                code: "tb-register".to_string(),
+                manifest: PluginManifest {
+                    migrations: migrations.clone(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap();
+
+        let migration_repo = PluginMigrationRowRepository::new(&connection);
+        assert_eq!(
+            migration_repo.find_latest_version("tb-register"),
+            Ok(Some(2))
+        );
+        // Already applied migrations are not run again
+        migration_repo.migrate("tb-register", &migrations).unwrap();
+
+        let table_name = "plugin_tb_register_case".to_string();
+        let repo = PluginTableRowRepository::new(&connection);
+        let row = PluginTableRow {
+            table_name: table_name.clone(),
+            data: json!({ "id": "case1", "store_id": "store_a", "note": "note", "visits": 1 }),
+        };
+        repo.upsert_one(&row).unwrap();
+        assert_eq!(repo.find_one_by_id(&table_name, "case1"), Ok(Some(row)));
+
+        // Update only some columns
+        let row = PluginTableRow {
+            table_name: table_name.clone(),
+            data: json!({ "id": "case1", "visits": 2 }),
+        };
+        repo.upsert_one(&row).unwrap();
+        assert_eq!(
+            repo.find_one_by_id(&table_name, "case1"),
+            Ok(Some(PluginTableRow {
+                table_name: table_name.clone(),
+                data: json!({ "id": "case1", "store_id": "store_a", "note": "note", "visits": 2 }),
+            }))
+        );
+
+        // Only tables of installed plugins can be used, core tables with the plugin prefix
+        // are rejected
+        for table_name in [
+            "store",
+            "plugin_data",
+            "plugin_migration",
+            "plugin_other_case",
+        ] {
+            assert!(repo
+                .upsert_one(&PluginTableRow {
+                    table_name: table_name.to_string(),
+                    data: json!({ "id": "store_a" }),
+                })
+                .is_err());
+        }
+
+        // Plugin can only use its own tables, `tb` prefix also matches `tb-register` tables
+        BackendPluginRowRepository::new(&connection)
+            .upsert_one(BackendPluginRow {
+                id: "tb".to_string(),
+                code: "tb".to_string(),
+                manifest: PluginManifest {
+                    migrations: vec![PluginMigration {
+                        version: 1,
+                        sql: "CREATE TABLE {prefix}contact (id TEXT NOT NULL PRIMARY KEY);"
+                            .to_string(),
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            repo.find_table_plugin_code(&table_name),
+            Ok(Some("tb-register".to_string()))
+        );
+        assert!(repo
+            .validate_plugin_table("tb-register", &table_name)
+            .is_ok());
+        assert!(repo.validate_plugin_table("tb", &table_name).is_err());
+        assert!(repo
+            .validate_plugin_table("tb", "plugin_tb_contact")
+            .is_ok());
+
+        assert!(repo.delete(&table_name, "case1").unwrap().is_some());
+        assert_eq!(repo.find_one_by_id(&table_name, "case1"), Ok(None));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, Delete, RepositoryError,
-    RowActionType, StorageConnection, Upsert,
+    AuditCapture, ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, Delete,
+    RepositoryError, RowActionType, StorageConnection, Upsert,
 };
 
 use super::preference_row::preference::dsl::*;
@@ -38,12 +38,16 @@ impl<'a> PreferenceRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, preference_row: &PreferenceRow) -> Result<i64, RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "preference", &preference_row.id, || {
+            self.find_one_by_id(&preference_row.id)
+        })?;
         diesel::insert_into(preference::table)
             .values(preference_row)
             .on_conflict(id)
             .do_update()
             .set(preference_row)
             .execute(self.connection.lock().connection())?;
+        audit.finish(preference_row)?;
 
         self.insert_changelog(preference_row.to_owned(), RowActionType::Upsert)
     }
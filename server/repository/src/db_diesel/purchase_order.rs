@@ -1,7 +1,8 @@
 use super::{DBType, RepositoryError, StorageConnection};
 use crate::db_diesel::name_row::name;
 use crate::diesel_macros::{
-    apply_date_filter, apply_date_time_filter, apply_equal_filter, apply_sort, apply_string_filter,
+    apply_date_filter, apply_date_time_filter, apply_equal_filter, apply_keyset, apply_sort,
+    apply_string_filter,
 };
 use crate::purchase_order_row::{
     purchase_order::{self},
@@ -9,7 +10,8 @@ use crate::purchase_order_row::{
     PurchaseOrderRow, PurchaseOrderStatsRow, PurchaseOrderStatus,
 };
 
-use crate::{DateFilter, DatetimeFilter, EqualFilter, Pagination, Sort, StringFilter};
+use crate::{DateFilter, DatetimeFilter, EqualFilter, Keyset, Pagination, Sort, StringFilter};
+use chrono::NaiveDateTime;
 use diesel::query_dsl::QueryDsl;
 use diesel::{dsl::IntoBoxed, prelude::*, RunQueryDsl};
 
@@ -103,6 +105,28 @@ impl<'a> PurchaseOrderRepository<'a> {
         Ok(result.into_iter().map(to_domain).collect())
     }
 
+    /// Up to `limit` purchase orders after `after`, by created datetime then id
+    pub fn query_after(
+        &self,
+        filter: Option<PurchaseOrderFilter>,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+    ) -> Result<Vec<PurchaseOrder>, RepositoryError> {
+        let mut query = Self::create_filtered_query(filter);
+        apply_keyset!(
+            query,
+            after,
+            purchase_order::created_datetime,
+            purchase_order::id
+        );
+
+        let result = query
+            .limit(limit as i64)
+            .load::<PurchaseOrderJoin>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
+
     pub fn create_filtered_query(filter: Option<PurchaseOrderFilter>) -> BoxedPurchaseOrderQuery {
         let mut query = query().into_boxed();
 
@@ -0,0 +1,151 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+table! {
+    recall (id) {
+        id -> Text,
+        item_id -> Text,
+        batch -> Nullable<Text>,
+        item_variant_id -> Nullable<Text>,
+        reason -> Text,
+        severity -> crate::db_diesel::recall_row::RecallSeverityMapping,
+        created_datetime -> Timestamp,
+        closed_datetime -> Nullable<Timestamp>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum RecallSeverity {
+    #[default]
+    High,
+    Medium,
+    Low,
+}
+
+/// Manufacturer recall raised on central server. Matches stock lines of the item,
+/// narrowed down by `batch` and/or `item_variant_id` when set (all stock of the item otherwise)
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = recall)]
+#[diesel(treat_none_as_null = true)]
+pub struct RecallRow {
+    pub id: String,
+    pub item_id: String,
+    pub batch: Option<String>,
+    pub item_variant_id: Option<String>,
+    pub reason: String,
+    pub severity: RecallSeverity,
+    pub created_datetime: NaiveDateTime,
+    pub closed_datetime: Option<NaiveDateTime>,
+}
+
+pub struct RecallRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> RecallRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        RecallRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &RecallRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(recall::table)
+            .values(row)
+            .on_conflict(recall::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row.id.to_string(), RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row_id: String,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::Recall,
+            record_id: row_id,
+            row_action: action,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, recall_id: &str) -> Result<Option<RecallRow>, RepositoryError> {
+        let result = recall::table
+            .filter(recall::id.eq(recall_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// All recalls, most recent first
+    pub fn find_all(&self) -> Result<Vec<RecallRow>, RepositoryError> {
+        let result = recall::table
+            .order(recall::created_datetime.desc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Recalls that have not been closed, most recent first
+    pub fn find_active(&self) -> Result<Vec<RecallRow>, RepositoryError> {
+        let result = recall::table
+            .filter(recall::closed_datetime.is_null())
+            .order(recall::created_datetime.desc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_active_by_item_id(&self, item_id: &str) -> Result<Vec<RecallRow>, RepositoryError> {
+        let result = recall::table
+            .filter(recall::closed_datetime.is_null())
+            .filter(recall::item_id.eq(item_id))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
+
+impl RecallRow {
+    /// Does stock line with given batch and item variant fall under this recall (item is assumed to match)
+    pub fn matches(&self, batch: Option<&str>, item_variant_id: Option<&str>) -> bool {
+        let batch_matches = match &self.batch {
+            Some(recalled_batch) => batch == Some(recalled_batch.as_str()),
+            None => true,
+        };
+        let variant_matches = match &self.item_variant_id {
+            Some(recalled_variant) => item_variant_id == Some(recalled_variant.as_str()),
+            None => true,
+        };
+
+        batch_matches && variant_matches
+    }
+}
+
+impl Upsert for RecallRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = RecallRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RecallRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
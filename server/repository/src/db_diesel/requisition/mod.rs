@@ -50,6 +50,7 @@ pub enum RequisitionSortField {
     OrderType,
     ProgramName,
     PeriodStartDate,
+    IsEmergency,
 }
 
 pub type RequisitionSort = Sort<RequisitionSortField>;
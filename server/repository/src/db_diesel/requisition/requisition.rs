@@ -9,14 +9,15 @@ use crate::{
         program_requisition::program_row::program, store_row::store,
     },
     diesel_macros::{
-        apply_date_filter, apply_date_time_filter, apply_equal_filter, apply_sort,
+        apply_date_filter, apply_date_time_filter, apply_equal_filter, apply_keyset, apply_sort,
         apply_sort_no_case, apply_string_filter,
     },
     repository_error::RepositoryError,
-    DBType, EqualFilter, NameRow, PeriodRow, ProgramRow, StorageConnection, StoreRow,
+    DBType, EqualFilter, Keyset, NameRow, PeriodRow, ProgramRow, StorageConnection, StoreRow,
 };
 
 use crate::Pagination;
+use chrono::NaiveDateTime;
 use diesel::{
     dsl::IntoBoxed,
     prelude::*,
@@ -118,6 +119,9 @@ impl<'a> RequisitionRepository<'a> {
                 RequisitionSortField::ProgramName => {
                     apply_sort_no_case!(query, sort, program::name);
                 }
+                RequisitionSortField::IsEmergency => {
+                    apply_sort!(query, sort, requisition::is_emergency);
+                }
             }
         } else {
             query = query.order(requisition::id.asc())
@@ -137,6 +141,23 @@ impl<'a> RequisitionRepository<'a> {
 
         Ok(result.into_iter().map(to_domain).collect())
     }
+
+    /// Up to `limit` requisitions after `after`, by created datetime then id
+    pub fn query_after(
+        &self,
+        filter: Option<RequisitionFilter>,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+    ) -> Result<Vec<Requisition>, RepositoryError> {
+        let mut query = create_filtered_query(filter)?;
+        apply_keyset!(query, after, requisition::created_datetime, requisition::id);
+
+        let result = query
+            .limit(limit as i64)
+            .load::<RequisitionJoin>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
 }
 
 #[diesel::dsl::auto_type]
@@ -0,0 +1,124 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+table! {
+    requisition_approval (id) {
+        id -> Text,
+        requisition_id -> Text,
+        store_id -> Text,
+        step_id -> Text,
+        step_number -> Integer,
+        user_id -> Text,
+        decision -> crate::db_diesel::requisition_approval_row::RequisitionApprovalDecisionMapping,
+        comment -> Nullable<Text>,
+        datetime -> Timestamp,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum RequisitionApprovalDecision {
+    #[default]
+    Approved,
+    Denied,
+}
+
+/// Decision made by an approver for one step of response requisition approval chain
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = requisition_approval)]
+#[diesel(treat_none_as_null = true)]
+pub struct RequisitionApprovalRow {
+    pub id: String,
+    pub requisition_id: String,
+    pub store_id: String,
+    pub step_id: String,
+    pub step_number: i32,
+    pub user_id: String,
+    pub decision: RequisitionApprovalDecision,
+    pub comment: Option<String>,
+    pub datetime: NaiveDateTime,
+}
+
+pub struct RequisitionApprovalRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> RequisitionApprovalRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        RequisitionApprovalRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &RequisitionApprovalRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(requisition_approval::table)
+            .values(row)
+            .on_conflict(requisition_approval::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row, RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row: &RequisitionApprovalRow,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::RequisitionApproval,
+            record_id: row.id.clone(),
+            row_action: action,
+            store_id: Some(row.store_id.clone()),
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        approval_id: &str,
+    ) -> Result<Option<RequisitionApprovalRow>, RepositoryError> {
+        let result = requisition_approval::table
+            .filter(requisition_approval::id.eq(approval_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_requisition_id(
+        &self,
+        requisition_id: &str,
+    ) -> Result<Vec<RequisitionApprovalRow>, RepositoryError> {
+        let result = requisition_approval::table
+            .filter(requisition_approval::requisition_id.eq(requisition_id))
+            .order(requisition_approval::datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
+
+impl Upsert for RequisitionApprovalRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = RequisitionApprovalRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RequisitionApprovalRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
@@ -0,0 +1,192 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    requisition_approval_step (id) {
+        id -> Text,
+        store_id -> Nullable<Text>,
+        program_id -> Nullable<Text>,
+        step_number -> Integer,
+        approver_user_id -> Text,
+        threshold_quantity -> Nullable<Double>,
+        threshold_value -> Nullable<Double>,
+        deleted_datetime -> Nullable<Timestamp>,
+    }
+}
+
+/// One step of a response requisition approval chain, configured on central server.
+/// Steps without `store_id` or `program_id` apply to all supplying stores or programs,
+/// steps with thresholds only apply when total requested quantity or value
+/// (requested quantity * price per unit) reaches either threshold
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = requisition_approval_step)]
+#[diesel(treat_none_as_null = true)]
+pub struct RequisitionApprovalStepRow {
+    pub id: String,
+    pub store_id: Option<String>,
+    pub program_id: Option<String>,
+    pub step_number: i32,
+    pub approver_user_id: String,
+    pub threshold_quantity: Option<f64>,
+    pub threshold_value: Option<f64>,
+    pub deleted_datetime: Option<NaiveDateTime>,
+}
+
+pub struct RequisitionApprovalStepRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> RequisitionApprovalStepRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        RequisitionApprovalStepRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &RequisitionApprovalStepRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(requisition_approval_step::table)
+            .values(row)
+            .on_conflict(requisition_approval_step::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row.id.to_string(), RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row_id: String,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::RequisitionApprovalStep,
+            record_id: row_id,
+            row_action: action,
+            store_id: None,
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        step_id: &str,
+    ) -> Result<Option<RequisitionApprovalStepRow>, RepositoryError> {
+        let result = requisition_approval_step::table
+            .filter(requisition_approval_step::id.eq(step_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Active steps configured for the store (or for all stores), for any program,
+    /// ordered by step number
+    pub fn find_active_by_store(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+        let result = requisition_approval_step::table
+            .filter(requisition_approval_step::deleted_datetime.is_null())
+            .filter(
+                requisition_approval_step::store_id
+                    .eq(store_id)
+                    .or(requisition_approval_step::store_id.is_null()),
+            )
+            .order(requisition_approval_step::step_number.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Active steps configured for the store and program (or for all stores or programs),
+    /// ordered by step number
+    pub fn find_active_by_store_and_program(
+        &self,
+        store_id: &str,
+        program_id: Option<&str>,
+    ) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+        let mut query = requisition_approval_step::table
+            .filter(requisition_approval_step::deleted_datetime.is_null())
+            .filter(
+                requisition_approval_step::store_id
+                    .eq(store_id)
+                    .or(requisition_approval_step::store_id.is_null()),
+            )
+            .into_boxed();
+
+        query = match program_id {
+            Some(program_id) => query.filter(
+                requisition_approval_step::program_id
+                    .eq(program_id)
+                    .or(requisition_approval_step::program_id.is_null()),
+            ),
+            None => query.filter(requisition_approval_step::program_id.is_null()),
+        };
+
+        // Steps of different scopes can share a step number, id keeps their order stable
+        let result = query
+            .order(requisition_approval_step::step_number.asc())
+            .then_order_by(requisition_approval_step::id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Active steps configured for exactly this store and program scope (none meaning all stores
+    /// or all programs)
+    pub fn find_active_by_scope(
+        &self,
+        store_id: Option<&str>,
+        program_id: Option<&str>,
+    ) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+        let mut query = requisition_approval_step::table
+            .filter(requisition_approval_step::deleted_datetime.is_null())
+            .into_boxed();
+
+        query = match store_id {
+            Some(store_id) => query.filter(requisition_approval_step::store_id.eq(store_id)),
+            None => query.filter(requisition_approval_step::store_id.is_null()),
+        };
+        query = match program_id {
+            Some(program_id) => query.filter(requisition_approval_step::program_id.eq(program_id)),
+            None => query.filter(requisition_approval_step::program_id.is_null()),
+        };
+
+        let result = query
+            .order(requisition_approval_step::step_number.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn mark_deleted(&self, step_id: &str) -> Result<i64, RepositoryError> {
+        diesel::update(
+            requisition_approval_step::table.filter(requisition_approval_step::id.eq(step_id)),
+        )
+        .set(requisition_approval_step::deleted_datetime.eq(Some(chrono::Utc::now().naive_utc())))
+        .execute(self.connection.lock().connection())?;
+
+        // Upsert row action as this is a soft delete, not actual delete
+        self.insert_changelog(step_id.to_string(), RowActionType::Upsert)
+    }
+}
+
+impl Upsert for RequisitionApprovalStepRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = RequisitionApprovalStepRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            RequisitionApprovalStepRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
@@ -0,0 +1,144 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+table! {
+    serial_number_line (id) {
+        id -> Text,
+        serial_number_id -> Text,
+        store_id -> Text,
+        invoice_line_id -> Nullable<Text>,
+        stocktake_line_id -> Nullable<Text>,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Serial number received, picked (invoice line) or verified (stocktake line), these make up
+/// custody history of a serialised unit
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = serial_number_line)]
+#[diesel(treat_none_as_null = true)]
+pub struct SerialNumberLineRow {
+    pub id: String,
+    pub serial_number_id: String,
+    pub store_id: String,
+    pub invoice_line_id: Option<String>,
+    pub stocktake_line_id: Option<String>,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct SerialNumberLineRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SerialNumberLineRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SerialNumberLineRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &SerialNumberLineRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(serial_number_line::table)
+            .values(row)
+            .on_conflict(serial_number_line::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row, RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row: &SerialNumberLineRow,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::SerialNumberLine,
+            record_id: row.id.clone(),
+            row_action: action,
+            store_id: Some(row.store_id.clone()),
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<SerialNumberLineRow>, RepositoryError> {
+        let result = serial_number_line::table
+            .filter(serial_number_line::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_invoice_line_id(
+        &self,
+        invoice_line_id: &str,
+    ) -> Result<Vec<SerialNumberLineRow>, RepositoryError> {
+        let result = serial_number_line::table
+            .filter(serial_number_line::invoice_line_id.eq(invoice_line_id))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Lines of serial numbers, oldest first
+    pub fn find_many_by_serial_number_ids(
+        &self,
+        serial_number_ids: &[String],
+    ) -> Result<Vec<SerialNumberLineRow>, RepositoryError> {
+        let result = serial_number_line::table
+            .filter(serial_number_line::serial_number_id.eq_any(serial_number_ids))
+            .order(serial_number_line::created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<Option<i64>, RepositoryError> {
+        let Some(row) = self.find_one_by_id(id)? else {
+            return Ok(None);
+        };
+
+        diesel::delete(serial_number_line::table.filter(serial_number_line::id.eq(id)))
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(self.insert_changelog(&row, RowActionType::Delete)?))
+    }
+}
+
+impl Upsert for SerialNumberLineRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = SerialNumberLineRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            SerialNumberLineRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SerialNumberLineRowDelete(pub String);
+impl Delete for SerialNumberLineRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        SerialNumberLineRowRepository::new(con).delete(&self.0)
+    }
+
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            SerialNumberLineRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
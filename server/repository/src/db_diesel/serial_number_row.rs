@@ -0,0 +1,187 @@
+use super::{
+    ChangeLogInsertRow, ChangelogRepository, ChangelogTableName, RowActionType, StorageConnection,
+};
+
+use crate::{repository_error::RepositoryError, Delete, Upsert};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+
+table! {
+    serial_number (id) {
+        id -> Text,
+        item_id -> Text,
+        store_id -> Text,
+        serial_number -> Text,
+        stock_line_id -> Nullable<Text>,
+        status -> crate::db_diesel::serial_number_row::SerialNumberStatusMapping,
+        created_datetime -> Timestamp,
+        last_verified_datetime -> Nullable<Timestamp>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum SerialNumberStatus {
+    /// In store, part of `stock_line_id`
+    #[default]
+    Available,
+    /// Picked on an outbound shipment, prescription or supplier return
+    Issued,
+    /// Not found during stocktake
+    Missing,
+}
+
+/// Individual unit of a serialised item held by a store. Stock line is only known once the stock
+/// the unit was received with is created (e.g. when inbound shipment is received)
+#[derive(
+    Clone, Insertable, Queryable, Debug, PartialEq, AsChangeset, Serialize, Deserialize, Default,
+)]
+#[diesel(table_name = serial_number)]
+#[diesel(treat_none_as_null = true)]
+pub struct SerialNumberRow {
+    pub id: String,
+    pub item_id: String,
+    pub store_id: String,
+    pub serial_number: String,
+    pub stock_line_id: Option<String>,
+    pub status: SerialNumberStatus,
+    pub created_datetime: NaiveDateTime,
+    pub last_verified_datetime: Option<NaiveDateTime>,
+}
+
+pub struct SerialNumberRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SerialNumberRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SerialNumberRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &SerialNumberRow) -> Result<i64, RepositoryError> {
+        diesel::insert_into(serial_number::table)
+            .values(row)
+            .on_conflict(serial_number::id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+
+        self.insert_changelog(row, RowActionType::Upsert)
+    }
+
+    fn insert_changelog(
+        &self,
+        row: &SerialNumberRow,
+        action: RowActionType,
+    ) -> Result<i64, RepositoryError> {
+        let row = ChangeLogInsertRow {
+            table_name: ChangelogTableName::SerialNumber,
+            record_id: row.id.clone(),
+            row_action: action,
+            store_id: Some(row.store_id.clone()),
+            name_id: None,
+        };
+        ChangelogRepository::new(self.connection).insert(&row)
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<SerialNumberRow>, RepositoryError> {
+        let result = serial_number::table
+            .filter(serial_number::id.eq(id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_id(&self, ids: &[String]) -> Result<Vec<SerialNumberRow>, RepositoryError> {
+        let result = serial_number::table
+            .filter(serial_number::id.eq_any(ids))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Serial number of an item in a store
+    pub fn find_one_in_store(
+        &self,
+        store_id: &str,
+        item_id: &str,
+        serial: &str,
+    ) -> Result<Option<SerialNumberRow>, RepositoryError> {
+        let result = serial_number::table
+            .filter(serial_number::store_id.eq(store_id))
+            .filter(serial_number::item_id.eq(item_id))
+            .filter(serial_number::serial_number.eq(serial))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Serial number of an item in every store it has been held in
+    pub fn find_many_by_serial_number(
+        &self,
+        item_id: &str,
+        serial: &str,
+    ) -> Result<Vec<SerialNumberRow>, RepositoryError> {
+        let result = serial_number::table
+            .filter(serial_number::item_id.eq(item_id))
+            .filter(serial_number::serial_number.eq(serial))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_stock_line_id(
+        &self,
+        stock_line_id: &str,
+    ) -> Result<Vec<SerialNumberRow>, RepositoryError> {
+        let result = serial_number::table
+            .filter(serial_number::stock_line_id.eq(stock_line_id))
+            .order(serial_number::serial_number.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<Option<i64>, RepositoryError> {
+        let Some(row) = self.find_one_by_id(id)? else {
+            return Ok(None);
+        };
+
+        diesel::delete(serial_number::table.filter(serial_number::id.eq(id)))
+            .execute(self.connection.lock().connection())?;
+
+        Ok(Some(self.insert_changelog(&row, RowActionType::Delete)?))
+    }
+}
+
+impl Upsert for SerialNumberRow {
+    fn upsert(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        let cursor_id = SerialNumberRowRepository::new(con).upsert_one(self)?;
+        Ok(Some(cursor_id))
+    }
+
+    // Test only
+    fn assert_upserted(&self, con: &StorageConnection) {
+        assert_eq!(
+            SerialNumberRowRepository::new(con).find_one_by_id(&self.id),
+            Ok(Some(self.clone()))
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SerialNumberRowDelete(pub String);
+impl Delete for SerialNumberRowDelete {
+    fn delete(&self, con: &StorageConnection) -> Result<Option<i64>, RepositoryError> {
+        SerialNumberRowRepository::new(con).delete(&self.0)
+    }
+
+    // Test only
+    fn assert_deleted(&self, con: &StorageConnection) {
+        assert_eq!(
+            SerialNumberRowRepository::new(con).find_one_by_id(&self.0),
+            Ok(None)
+        )
+    }
+}
@@ -0,0 +1,70 @@
+use super::site_backup_status_row::site_backup_status::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    site_backup_status (site_id) {
+        site_id -> Integer,
+        last_attempt_datetime -> Timestamp,
+        last_success_datetime -> Nullable<Timestamp>,
+        last_backup_name -> Nullable<Text>,
+        is_verified -> Bool,
+        error -> Nullable<Text>,
+        received_datetime -> Timestamp,
+    }
+}
+
+/// Latest backup status reported by a site to the central server (with site status)
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = site_backup_status)]
+#[diesel(treat_none_as_null = true)]
+pub struct SiteBackupStatusRow {
+    pub site_id: i32,
+    pub last_attempt_datetime: NaiveDateTime,
+    pub last_success_datetime: Option<NaiveDateTime>,
+    pub last_backup_name: Option<String>,
+    /// Last attempt produced a backup that was opened and checksummed
+    pub is_verified: bool,
+    /// Error of the last attempt
+    pub error: Option<String>,
+    pub received_datetime: NaiveDateTime,
+}
+
+pub struct SiteBackupStatusRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SiteBackupStatusRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SiteBackupStatusRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &SiteBackupStatusRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(site_backup_status)
+            .values(row)
+            .on_conflict(site_id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_site_id(
+        &self,
+        site_id_to_find: i32,
+    ) -> Result<Option<SiteBackupStatusRow>, RepositoryError> {
+        let result = site_backup_status
+            .filter(site_id.eq(site_id_to_find))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<SiteBackupStatusRow>, RepositoryError> {
+        let result = site_backup_status
+            .order(site_id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,81 @@
+use super::site_health_report_row::site_health_report::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    site_health_report (site_id) {
+        site_id -> Integer,
+        app_version -> Text,
+        site_datetime -> Timestamp,
+        received_datetime -> Timestamp,
+        clock_offset_seconds -> BigInt,
+        database_size_bytes -> Nullable<BigInt>,
+        free_disk_bytes -> Nullable<BigInt>,
+        processor_error_count -> BigInt,
+        last_processor_error -> Nullable<Text>,
+        last_processor_error_datetime -> Nullable<Timestamp>,
+        pending_changelog_count -> BigInt,
+    }
+}
+
+/// Latest health report of a site, on central server (reported with site status)
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[diesel(table_name = site_health_report)]
+#[diesel(treat_none_as_null = true)]
+pub struct SiteHealthReportRow {
+    pub site_id: i32,
+    pub app_version: String,
+    /// Time on the site when the report was generated
+    pub site_datetime: NaiveDateTime,
+    pub received_datetime: NaiveDateTime,
+    /// Site clock minus central clock, includes the time the report was in transit
+    pub clock_offset_seconds: i64,
+    pub database_size_bytes: Option<i64>,
+    /// Free space on the disk holding the database (or the server's working folder)
+    pub free_disk_bytes: Option<i64>,
+    /// Processor errors in system log over the reporting window
+    pub processor_error_count: i64,
+    pub last_processor_error: Option<String>,
+    pub last_processor_error_datetime: Option<NaiveDateTime>,
+    /// Changelogs not yet pushed to central server, after the sync that sent the report
+    pub pending_changelog_count: i64,
+}
+
+pub struct SiteHealthReportRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SiteHealthReportRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SiteHealthReportRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &SiteHealthReportRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(site_health_report)
+            .values(row)
+            .on_conflict(site_id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_site_id(
+        &self,
+        site_id_to_find: i32,
+    ) -> Result<Option<SiteHealthReportRow>, RepositoryError> {
+        let result = site_health_report
+            .filter(site_id.eq(site_id_to_find))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<SiteHealthReportRow>, RepositoryError> {
+        let result = site_health_report
+            .order(site_id.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+}
@@ -14,13 +14,14 @@ use super::{
 use crate::{
     diesel_extensions::OrderByExtensions,
     diesel_macros::{
-        apply_date_filter, apply_equal_filter, apply_sort, apply_sort_asc_nulls_last,
+        apply_date_filter, apply_equal_filter, apply_keyset, apply_sort, apply_sort_asc_nulls_last,
         apply_sort_no_case, apply_string_filter,
     },
     location::{LocationFilter, LocationRepository},
     repository_error::RepositoryError,
     BarcodeRow, DateFilter, EqualFilter, Item, ItemFilter, ItemLinkRow, ItemRepository, ItemRow,
-    ItemSort, ItemSortField, MasterListLineRepository, NameRow, Pagination, Sort, StringFilter,
+    ItemSort, ItemSortField, Keyset, MasterListLineRepository, NameRow, Pagination, Sort,
+    StringFilter,
 };
 
 use diesel::{dsl::IntoBoxed, prelude::*};
@@ -184,6 +185,24 @@ impl<'a> StockLineRepository<'a> {
         Ok(result.into_iter().map(to_domain).collect())
     }
 
+    /// Up to `limit` stock lines after `after`, by item name then id
+    pub fn query_after(
+        &self,
+        filter: Option<StockLineFilter>,
+        store_id: Option<String>,
+        after: Option<Keyset<String>>,
+        limit: u32,
+    ) -> Result<Vec<StockLine>, RepositoryError> {
+        let mut query = Self::create_filtered_query(filter, store_id);
+        apply_keyset!(query, after, item::name, stock_line::id);
+
+        let result = query
+            .limit(limit as i64)
+            .load::<StockLineJoin>(self.connection.lock().connection())?;
+
+        Ok(result.into_iter().map(to_domain).collect())
+    }
+
     /// Returns one row per item that has at least one stock_line matching the
     /// supplied filter (within `store_id`). The predicate is identical to what
     /// `query()` would return, so an item appears here iff at least one of its
@@ -14,6 +14,8 @@ use crate::{
 use diesel::prelude::*;
 
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 
 define_linked_tables! {
     view: stocktake_line = "stocktake_line_view",
@@ -60,7 +62,7 @@ joinable!(stocktake_line -> name (donor_id));
 allow_tables_to_appear_in_same_query!(stocktake_line, item_link);
 allow_tables_to_appear_in_same_query!(stocktake_line, reason_option);
 
-#[derive(Clone, Queryable, Debug, PartialEq, Default)]
+#[derive(Clone, Queryable, Debug, PartialEq, Default, TS, Serialize, Deserialize)]
 #[diesel(table_name = stocktake_line)]
 pub struct StocktakeLineRow {
     pub id: String,
@@ -61,12 +61,25 @@ impl<'a> LockedConnection<'a> {
 #[derive(Clone, Hash, Eq, PartialEq)]
 pub enum TransactionNotification {
     ChangelogInsert,
+    /// Changelog inserted for a store record that live subscriptions follow, see
+    /// `ChangelogTableName::is_store_event`
+    StoreRecordChanged,
+}
+
+/// User editing through a connection, recorded with the fields changed by audited upserts (see
+/// `AuditCapture`)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditContext {
+    pub user_id: String,
+    pub store_id: Option<String>,
+    pub reason: Option<String>,
 }
 
 pub struct StorageConnection {
     raw_connection: Mutex<DBConnection>,
     on_commit: Option<Arc<dyn Fn(&TransactionNotification) + Send + Sync>>,
     pending_notifications: RwLock<HashSet<TransactionNotification>>,
+    audit_context: RwLock<Option<AuditContext>>,
 }
 
 impl StorageConnection {
@@ -86,6 +99,22 @@ impl StorageConnection {
         }
     }
 
+    /// Without an audit context (e.g. sync integration or processors) changes are not audited
+    pub fn set_audit_context(&self, audit_context: Option<AuditContext>) {
+        *self.audit_context.write().unwrap() = audit_context;
+    }
+
+    pub fn audit_context(&self) -> Option<AuditContext> {
+        self.audit_context.read().unwrap().clone()
+    }
+
+    /// Reason recorded with the changes that follow, ignored without an audit context
+    pub fn set_audit_reason(&self, reason: Option<String>) {
+        if let Some(audit_context) = self.audit_context.write().unwrap().as_mut() {
+            audit_context.reason = reason;
+        }
+    }
+
     /// Fire all pending notifications. Called after outermost transaction commits.
     fn flush_notifications(&self) {
         let notifications: HashSet<_> = {
@@ -142,6 +171,7 @@ impl StorageConnection {
             raw_connection: Mutex::new(connection),
             on_commit: None,
             pending_notifications: RwLock::new(HashSet::new()),
+            audit_context: RwLock::new(None),
         }
     }
 
@@ -107,6 +107,31 @@ impl<'a> SystemLogRowRepository<'a> {
         Ok(result)
     }
 
+    pub fn count_by_type_since(
+        &self,
+        r#type: SystemLogType,
+        since: NaiveDateTime,
+    ) -> Result<i64, RepositoryError> {
+        let result = system_log::table
+            .filter(system_log::type_.eq(r#type))
+            .filter(system_log::datetime.ge(since))
+            .count()
+            .get_result(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_latest_by_type(
+        &self,
+        r#type: SystemLogType,
+    ) -> Result<Option<SystemLogRow>, RepositoryError> {
+        let result = system_log::table
+            .filter(system_log::type_.eq(r#type))
+            .order(system_log::datetime.desc())
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
     pub fn find_all(&self) -> Result<Vec<SystemLogRow>, RepositoryError> {
         let result = system_log::table.load(self.connection.lock().connection())?;
         Ok(result)
@@ -1,8 +1,9 @@
 use super::StorageConnection;
 
 use crate::repository_error::RepositoryError;
-use crate::{Delete, Upsert};
+use crate::{AuditCapture, Delete, Upsert};
 use diesel::prelude::*;
+use serde::Serialize;
 
 use diesel_derive_enum::DbEnum;
 
@@ -16,7 +17,7 @@ table! {
     }
 }
 
-#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Hash, Default, Serialize)]
 #[cfg_attr(test, derive(strum::EnumIter))]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum PermissionType {
@@ -105,7 +106,7 @@ pub enum PermissionType {
     MutateClinician,
 }
 
-#[derive(Clone, Queryable, Insertable, Debug, PartialEq, Eq, AsChangeset, Default)]
+#[derive(Clone, Queryable, Insertable, Debug, PartialEq, Eq, AsChangeset, Default, Serialize)]
 #[diesel(treat_none_as_null = true)]
 #[diesel(table_name = user_permission)]
 pub struct UserPermissionRow {
@@ -128,12 +129,16 @@ impl<'a> UserPermissionRowRepository<'a> {
     }
 
     pub fn upsert_one(&self, row: &UserPermissionRow) -> Result<(), RepositoryError> {
+        let audit = AuditCapture::start(self.connection, "user_permission", &row.id, || {
+            self.find_one_by_id(&row.id)
+        })?;
         diesel::insert_into(user_permission::table)
             .values(row)
             .on_conflict(user_permission::id)
             .do_update()
             .set(row)
             .execute(self.connection.lock().connection())?;
+        audit.finish(row)?;
         Ok(())
     }
 
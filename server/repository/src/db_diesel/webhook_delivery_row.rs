@@ -0,0 +1,127 @@
+use super::webhook_delivery_row::webhook_delivery::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    webhook_delivery (id) {
+        id -> Text,
+        webhook_id -> Text,
+        changelog_cursor -> BigInt,
+        event -> Text,
+        payload -> Text,
+        status -> crate::db_diesel::webhook_delivery_row::WebhookDeliveryStatusMapping,
+        attempts -> Integer,
+        next_attempt_datetime -> Nullable<Timestamp>,
+        last_error -> Nullable<Text>,
+        created_datetime -> Timestamp,
+        delivered_datetime -> Nullable<Timestamp>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum WebhookDeliveryStatus {
+    /// Not delivered yet, retried at `next_attempt_datetime` after a failed attempt
+    #[default]
+    Pending,
+    Delivered,
+    /// Gave up after the maximum number of attempts, only retried manually
+    DeadLetter,
+}
+
+#[derive(
+    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+)]
+#[diesel(table_name = webhook_delivery)]
+#[diesel(treat_none_as_null = true)]
+pub struct WebhookDeliveryRow {
+    pub id: String,
+    pub webhook_id: String,
+    /// Changelog the delivery was created from
+    pub changelog_cursor: i64,
+    pub event: String,
+    /// JSON body posted to the webhook url
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_datetime: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub created_datetime: NaiveDateTime,
+    pub delivered_datetime: Option<NaiveDateTime>,
+}
+
+pub struct WebhookDeliveryRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> WebhookDeliveryRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        WebhookDeliveryRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &WebhookDeliveryRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(webhook_delivery)
+            .values(row)
+            .on_conflict(id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        delivery_id: &str,
+    ) -> Result<Option<WebhookDeliveryRow>, RepositoryError> {
+        let result = webhook_delivery
+            .filter(id.eq(delivery_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Up to `limit` pending deliveries that are due, oldest changelog first
+    pub fn find_due(
+        &self,
+        datetime: NaiveDateTime,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryRow>, RepositoryError> {
+        let result = webhook_delivery
+            .filter(status.eq(WebhookDeliveryStatus::Pending))
+            .filter(
+                next_attempt_datetime
+                    .is_null()
+                    .or(next_attempt_datetime.le(datetime)),
+            )
+            .order((changelog_cursor.asc(), id.asc()))
+            .limit(limit)
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    /// Newest first
+    pub fn find_by_status(
+        &self,
+        delivery_status: WebhookDeliveryStatus,
+        for_webhook_id: Option<&str>,
+    ) -> Result<Vec<WebhookDeliveryRow>, RepositoryError> {
+        let mut query = webhook_delivery
+            .filter(status.eq(delivery_status))
+            .into_boxed();
+        if let Some(for_webhook_id) = for_webhook_id {
+            query = query.filter(webhook_id.eq(for_webhook_id.to_string()));
+        }
+        let result = query
+            .order((changelog_cursor.desc(), id.asc()))
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete_by_webhook_id(&self, for_webhook_id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(webhook_delivery.filter(webhook_id.eq(for_webhook_id)))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use super::webhook_row::webhook::dsl::*;
+use crate::{RepositoryError, StorageConnection};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    webhook (id) {
+        id -> Text,
+        name -> Text,
+        url -> Text,
+        secret -> Text,
+        event_types -> Text,
+        table_names -> Text,
+        is_active -> Bool,
+        created_datetime -> Timestamp,
+    }
+}
+
+/// Subscription of an external system to changes on this site. Webhooks are local to the site
+/// and not synced
+#[derive(
+    Clone, Queryable, Insertable, Identifiable, Debug, PartialEq, Eq, AsChangeset, Default,
+)]
+#[diesel(table_name = webhook)]
+pub struct WebhookRow {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// Key of the HMAC signature sent with each delivery
+    pub secret: String,
+    /// JSON array of the activity log types delivered, e.g. invoice status changed to shipped
+    pub event_types: String,
+    /// JSON array of the changelog tables whose row changes are delivered
+    pub table_names: String,
+    pub is_active: bool,
+    pub created_datetime: NaiveDateTime,
+}
+
+pub struct WebhookRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> WebhookRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        WebhookRowRepository { connection }
+    }
+
+    pub fn upsert_one(&self, row: &WebhookRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(webhook)
+            .values(row)
+            .on_conflict(id)
+            .do_update()
+            .set(row)
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, webhook_id: &str) -> Result<Option<WebhookRow>, RepositoryError> {
+        let result = webhook
+            .filter(id.eq(webhook_id))
+            .first(self.connection.lock().connection())
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<WebhookRow>, RepositoryError> {
+        let result = webhook
+            .order(name.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn find_active(&self) -> Result<Vec<WebhookRow>, RepositoryError> {
+        let result = webhook
+            .filter(is_active.eq(true))
+            .order(created_datetime.asc())
+            .load(self.connection.lock().connection())?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, webhook_id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(webhook.filter(id.eq(webhook_id)))
+            .execute(self.connection.lock().connection())?;
+        Ok(())
+    }
+}
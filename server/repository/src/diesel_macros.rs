@@ -378,6 +378,35 @@ macro_rules! apply_sort_asc_nulls_first {
     }};
 }
 
+/// Example expand, when called with:
+///
+/// ```
+/// apply_keyset!(query, after, invoice::created_datetime, invoice::id)
+/// ```
+///
+/// ```
+/// if let Some(Keyset { sort_key, id }) = after {
+///     query = query.filter(
+///         invoice::created_datetime
+///             .gt(sort_key.clone())
+///             .or(invoice::created_datetime.eq(sort_key).and(invoice::id.gt(id))),
+///     );
+/// }
+/// query = query.order((invoice::created_datetime.asc(), invoice::id.asc()));
+/// ```
+macro_rules! apply_keyset {
+    ($query:ident, $keyset:expr, $sort_field:expr, $id_field:expr) => {{
+        if let Some(crate::Keyset { sort_key, id }) = $keyset {
+            $query = $query.filter(
+                $sort_field
+                    .gt(sort_key.clone())
+                    .or($sort_field.eq(sort_key).and($id_field.gt(id))),
+            );
+        }
+        $query = $query.order(($sort_field.asc(), $id_field.asc()));
+    }};
+}
+
 /// Generates table definitions and repository methods for the entity linking abstraction pattern.
 ///
 /// This macro automates the creation of:
@@ -603,6 +632,7 @@ macro_rules! define_linked_tables {
 pub(crate) use apply_date_filter;
 pub(crate) use apply_date_time_filter;
 pub(crate) use apply_equal_filter;
+pub(crate) use apply_keyset;
 pub(crate) use apply_number_filter;
 pub(crate) use apply_sort;
 pub(crate) use apply_sort_asc_nulls_first;
@@ -1,7 +1,10 @@
 #[macro_use]
 extern crate diesel;
 
+pub mod database_backup;
+pub mod database_encryption;
 pub mod database_settings;
+pub mod database_stats;
 pub mod db_diesel;
 pub mod diesel_extensions;
 pub(crate) mod diesel_helper_types;
@@ -44,6 +44,7 @@ mod v2_17_03;
 mod v2_17_05;
 mod v2_18_00;
 mod v2_19_00;
+mod v2_20_00;
 mod version;
 mod views;
 
@@ -156,6 +157,7 @@ pub fn migrate(
         Box::new(v2_17_05::V2_17_05),
         Box::new(v2_18_00::V2_18_00),
         Box::new(v2_19_00::V2_19_00),
+        Box::new(v2_20_00::V2_20_00),
     ];
 
     // Check if the database has been initialised, if not run the base sql to kick start the process
@@ -0,0 +1,53 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_activity_log_hash_chain"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        // Existing activity logs are not chained, each store's chain starts from the next log
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE activity_log_hash (
+                    activity_log_id TEXT NOT NULL PRIMARY KEY,
+                    store_id TEXT NOT NULL,
+                    sequence BIGINT NOT NULL,
+                    previous_hash TEXT,
+                    hash TEXT NOT NULL,
+                    UNIQUE (store_id, sequence)
+                );
+
+                CREATE TABLE activity_log_chain_head (
+                    store_id TEXT NOT NULL PRIMARY KEY,
+                    sequence BIGINT NOT NULL,
+                    hash TEXT
+                );
+
+                CREATE TABLE activity_log_chain_anchor (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    store_id TEXT NOT NULL,
+                    sequence BIGINT NOT NULL,
+                    hash TEXT NOT NULL,
+                    created_datetime {DATETIME} NOT NULL
+                );
+
+                CREATE INDEX index_activity_log_chain_anchor_store_id ON activity_log_chain_anchor (store_id);
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'activity_log_chain_anchor';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_api_idempotency_key_table"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE api_idempotency_key (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    user_id TEXT NOT NULL,
+                    idempotency_key TEXT NOT NULL,
+                    request_hash TEXT NOT NULL,
+                    response_status INTEGER NOT NULL,
+                    response_body TEXT NOT NULL,
+                    created_datetime {DATETIME} NOT NULL,
+                    UNIQUE (user_id, idempotency_key)
+                );
+            "#
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_api_key_table"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE api_key (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    user_id TEXT NOT NULL REFERENCES user_account(id),
+                    name TEXT NOT NULL,
+                    key_prefix TEXT NOT NULL,
+                    key_hash TEXT NOT NULL UNIQUE,
+                    resources TEXT NOT NULL,
+                    store_ids TEXT NOT NULL,
+                    created_datetime {DATETIME} NOT NULL,
+                    expiry_datetime {DATETIME},
+                    last_used_datetime {DATETIME},
+                    revoked_datetime {DATETIME}
+                );
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'API_KEY_CREATED';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'API_KEY_REVOKED';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'API_KEY_USED';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_audit_log_table"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE audit_log (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    table_name TEXT NOT NULL,
+                    record_id TEXT NOT NULL,
+                    change_id TEXT NOT NULL,
+                    field TEXT NOT NULL,
+                    old_value TEXT,
+                    new_value TEXT,
+                    user_id TEXT NOT NULL,
+                    store_id TEXT,
+                    reason TEXT,
+                    datetime {DATETIME} NOT NULL
+                );
+
+                CREATE INDEX index_audit_log_table_name_record_id ON audit_log (table_name, record_id);
+                CREATE INDEX index_audit_log_datetime ON audit_log (datetime);
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE key_type ADD VALUE IF NOT EXISTS 'SETTINGS_AUDIT_LOG_RETENTION_DAYS';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
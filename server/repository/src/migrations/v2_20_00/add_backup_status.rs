@@ -0,0 +1,39 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_backup_status"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        // Backup status reported by sites, on central server
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE site_backup_status (
+                    site_id INTEGER NOT NULL PRIMARY KEY,
+                    last_attempt_datetime {DATETIME} NOT NULL,
+                    last_success_datetime {DATETIME},
+                    last_backup_name TEXT,
+                    is_verified BOOLEAN NOT NULL,
+                    error TEXT,
+                    received_datetime {DATETIME} NOT NULL
+                );
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE key_type ADD VALUE IF NOT EXISTS 'BACKUP_STATUS';
+                    ALTER TYPE key_type ADD VALUE IF NOT EXISTS 'SYNC_RESYNC_AFTER_RESTORE';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_dispensing_safety_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let severity_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE drug_interaction_severity AS ENUM ('MINOR', 'MODERATE', 'MAJOR', 'CONTRAINDICATED');
+                "#
+            )?;
+
+            "drug_interaction_severity"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE item_clinical_info (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    item_id TEXT NOT NULL,
+                    atc_code TEXT,
+                    max_daily_dose {DOUBLE}
+                );
+
+                CREATE INDEX index_item_clinical_info_item_id ON item_clinical_info (item_id);
+
+                CREATE TABLE drug_interaction (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    atc_code_a TEXT NOT NULL,
+                    atc_code_b TEXT NOT NULL,
+                    severity {severity_type} NOT NULL,
+                    description TEXT NOT NULL
+                );
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'item_clinical_info';
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'drug_interaction';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'DISPENSING_ALERT_OVERRIDDEN';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
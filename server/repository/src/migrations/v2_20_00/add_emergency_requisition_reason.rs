@@ -0,0 +1,40 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_emergency_requisition_reason"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE reason_option_type
+                      ADD VALUE IF NOT EXISTS 'EMERGENCY_REQUISITION' AFTER 'REQUISITION_LINE_VARIANCE';
+                "#
+            )?;
+        }
+
+        // Re-integrate options that failed to translate before the type was known
+        sql!(
+            connection,
+            r#"
+                UPDATE sync_buffer
+                    SET integration_datetime = NULL
+                    WHERE table_name = 'options';
+            "#
+        )?;
+
+        sql!(
+            connection,
+            r#"
+                ALTER TABLE requisition ADD COLUMN emergency_reason_option_id TEXT;
+            "#
+        )?;
+
+        Ok(())
+    }
+}
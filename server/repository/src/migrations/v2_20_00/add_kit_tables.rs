@@ -0,0 +1,66 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_kit_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let assembly_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE kit_assembly_type AS ENUM ('ASSEMBLY', 'DISASSEMBLY');
+                "#
+            )?;
+
+            "kit_assembly_type"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE kit_component (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    kit_item_id TEXT NOT NULL,
+                    component_item_id TEXT NOT NULL,
+                    quantity {DOUBLE} NOT NULL
+                );
+
+                CREATE INDEX index_kit_component_kit_item_id ON kit_component (kit_item_id);
+
+                CREATE TABLE kit_assembly (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    store_id TEXT NOT NULL REFERENCES store(id),
+                    invoice_id TEXT NOT NULL,
+                    kit_item_id TEXT NOT NULL,
+                    kit_stock_line_id TEXT NOT NULL,
+                    number_of_kits {DOUBLE} NOT NULL,
+                    assembly_type {assembly_type} NOT NULL,
+                    user_id TEXT,
+                    created_datetime {DATETIME} NOT NULL
+                );
+
+                CREATE INDEX index_kit_assembly_kit_stock_line_id ON kit_assembly (kit_stock_line_id);
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'kit_component';
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'kit_assembly';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'KIT_ASSEMBLED';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'KIT_DISASSEMBLED';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
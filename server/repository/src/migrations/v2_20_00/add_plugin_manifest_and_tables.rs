@@ -0,0 +1,38 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_plugin_manifest_and_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        sql!(
+            connection,
+            r#"
+                ALTER TABLE backend_plugin ADD COLUMN manifest TEXT NOT NULL DEFAULT '{{}}';
+
+                -- Tables declared by plugins are created by plugin manifest migrations
+                -- when backend plugin is upserted, this tracks which have been applied
+                CREATE TABLE plugin_migration (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    plugin_code TEXT NOT NULL,
+                    version INTEGER NOT NULL,
+                    applied_datetime {DATETIME} NOT NULL
+                );
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'plugin_table';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_recall_table"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let severity_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE recall_severity AS ENUM ('HIGH', 'MEDIUM', 'LOW');
+                "#
+            )?;
+
+            "recall_severity"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE recall (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    item_id TEXT NOT NULL,
+                    batch TEXT,
+                    item_variant_id TEXT,
+                    reason TEXT NOT NULL,
+                    severity {severity_type} NOT NULL,
+                    created_datetime {DATETIME} NOT NULL,
+                    closed_datetime {DATETIME}
+                );
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'recall';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'STOCK_LINE_RECALLED';
+                    ALTER TYPE key_type ADD VALUE IF NOT EXISTS 'RECALL_PROCESSOR_CURSOR';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
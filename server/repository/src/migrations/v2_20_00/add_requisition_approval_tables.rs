@@ -0,0 +1,69 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_requisition_approval_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let decision_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE requisition_approval_decision AS ENUM ('APPROVED', 'DENIED');
+                "#
+            )?;
+
+            "requisition_approval_decision"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE requisition_approval_step (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    store_id TEXT,
+                    program_id TEXT,
+                    step_number INTEGER NOT NULL,
+                    approver_user_id TEXT NOT NULL,
+                    threshold_quantity {DOUBLE},
+                    threshold_value {DOUBLE},
+                    deleted_datetime {DATETIME}
+                );
+
+                CREATE TABLE requisition_approval (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    requisition_id TEXT NOT NULL REFERENCES requisition(id),
+                    store_id TEXT NOT NULL REFERENCES store(id),
+                    step_id TEXT NOT NULL,
+                    step_number INTEGER NOT NULL,
+                    user_id TEXT NOT NULL,
+                    decision {decision_type} NOT NULL,
+                    comment TEXT,
+                    datetime {DATETIME} NOT NULL
+                );
+
+                CREATE INDEX index_requisition_approval_requisition_id ON requisition_approval (requisition_id);
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'requisition_approval_step';
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'requisition_approval';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'REQUISITION_APPROVAL_STEP_APPROVED';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'REQUISITION_DENIED';
+                    ALTER TYPE activity_log_type ADD VALUE IF NOT EXISTS 'REQUISITION_LINE_APPROVED_QUANTITY_ADJUSTED';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_serial_number_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let status_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE serial_number_status AS ENUM ('AVAILABLE', 'ISSUED', 'MISSING');
+                "#
+            )?;
+
+            "serial_number_status"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE serial_number (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    item_id TEXT NOT NULL,
+                    store_id TEXT NOT NULL REFERENCES store(id),
+                    serial_number TEXT NOT NULL,
+                    stock_line_id TEXT,
+                    status {status_type} NOT NULL,
+                    created_datetime {DATETIME} NOT NULL,
+                    last_verified_datetime {DATETIME}
+                );
+
+                CREATE INDEX index_serial_number_item_id_serial_number ON serial_number (item_id, serial_number);
+                CREATE INDEX index_serial_number_stock_line_id ON serial_number (stock_line_id);
+
+                CREATE TABLE serial_number_line (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    serial_number_id TEXT NOT NULL REFERENCES serial_number(id),
+                    store_id TEXT NOT NULL REFERENCES store(id),
+                    invoice_line_id TEXT,
+                    stocktake_line_id TEXT,
+                    created_datetime {DATETIME} NOT NULL
+                );
+
+                CREATE INDEX index_serial_number_line_serial_number_id ON serial_number_line (serial_number_id);
+                CREATE INDEX index_serial_number_line_invoice_line_id ON serial_number_line (invoice_line_id);
+            "#
+        )?;
+
+        if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'serial_number';
+                    ALTER TYPE changelog_table_name ADD VALUE IF NOT EXISTS 'serial_number_line';
+                "#
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_site_health_report"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        // Health reports sent by sites, on central server
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE site_health_report (
+                    site_id INTEGER NOT NULL PRIMARY KEY,
+                    app_version TEXT NOT NULL,
+                    site_datetime {DATETIME} NOT NULL,
+                    received_datetime {DATETIME} NOT NULL,
+                    clock_offset_seconds BIGINT NOT NULL,
+                    database_size_bytes BIGINT,
+                    free_disk_bytes BIGINT,
+                    processor_error_count BIGINT NOT NULL,
+                    last_processor_error TEXT,
+                    last_processor_error_datetime {DATETIME},
+                    pending_changelog_count BIGINT NOT NULL
+                );
+            "#
+        )?;
+
+        Ok(())
+    }
+}
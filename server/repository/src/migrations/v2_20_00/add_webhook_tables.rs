@@ -0,0 +1,58 @@
+use crate::migrations::*;
+
+pub(crate) struct Migrate;
+
+impl MigrationFragment for Migrate {
+    fn identifier(&self) -> &'static str {
+        "add_webhook_tables"
+    }
+
+    fn migrate(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+        let status_type = if cfg!(feature = "postgres") {
+            sql!(
+                connection,
+                r#"
+                    CREATE TYPE webhook_delivery_status AS ENUM ('PENDING', 'DELIVERED', 'DEAD_LETTER');
+                "#
+            )?;
+
+            "webhook_delivery_status"
+        } else {
+            "TEXT"
+        };
+
+        sql!(
+            connection,
+            r#"
+                CREATE TABLE webhook (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    url TEXT NOT NULL,
+                    secret TEXT NOT NULL,
+                    event_types TEXT NOT NULL,
+                    table_names TEXT NOT NULL,
+                    is_active BOOLEAN NOT NULL,
+                    created_datetime {DATETIME} NOT NULL
+                );
+
+                CREATE TABLE webhook_delivery (
+                    id TEXT NOT NULL PRIMARY KEY,
+                    webhook_id TEXT NOT NULL REFERENCES webhook(id),
+                    changelog_cursor BIGINT NOT NULL,
+                    event TEXT NOT NULL,
+                    payload TEXT NOT NULL,
+                    status {status_type} NOT NULL,
+                    attempts INTEGER NOT NULL,
+                    next_attempt_datetime {DATETIME},
+                    last_error TEXT,
+                    created_datetime {DATETIME} NOT NULL,
+                    delivered_datetime {DATETIME}
+                );
+
+                CREATE INDEX index_webhook_delivery_status ON webhook_delivery (status, next_attempt_datetime);
+            "#
+        )?;
+
+        Ok(())
+    }
+}
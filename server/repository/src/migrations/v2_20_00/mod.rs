@@ -0,0 +1,76 @@
+use super::{version::Version, Migration, MigrationFragment};
+use crate::StorageConnection;
+
+mod add_activity_log_hash_chain;
+mod add_api_idempotency_key_table;
+mod add_api_key_table;
+mod add_audit_log_table;
+mod add_backup_status;
+mod add_dispensing_safety_tables;
+mod add_email_queue_changelog;
+mod add_emergency_requisition_reason;
+mod add_epcis_settings_key_type;
+mod add_kit_tables;
+mod add_plugin_manifest_and_tables;
+mod add_recall_table;
+mod add_requisition_approval_tables;
+mod add_serial_number_tables;
+mod add_site_health_report;
+mod add_webhook_tables;
+
+pub(crate) struct V2_20_00;
+impl Migration for V2_20_00 {
+    fn version(&self) -> Version {
+        Version::from_str("2.20.0")
+    }
+
+    fn migrate(&self, _connection: &StorageConnection) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn migrate_fragments(&self) -> Vec<Box<dyn MigrationFragment>> {
+        vec![
+            Box::new(add_plugin_manifest_and_tables::Migrate),
+            Box::new(add_requisition_approval_tables::Migrate),
+            Box::new(add_emergency_requisition_reason::Migrate),
+            Box::new(add_recall_table::Migrate),
+            Box::new(add_serial_number_tables::Migrate),
+            Box::new(add_epcis_settings_key_type::Migrate),
+            Box::new(add_kit_tables::Migrate),
+            Box::new(add_dispensing_safety_tables::Migrate),
+            Box::new(add_api_key_table::Migrate),
+            Box::new(add_webhook_tables::Migrate),
+            Box::new(add_api_idempotency_key_table::Migrate),
+            Box::new(add_activity_log_hash_chain::Migrate),
+            Box::new(add_audit_log_table::Migrate),
+            Box::new(add_backup_status::Migrate),
+            Box::new(add_site_health_report::Migrate),
+            Box::new(add_email_queue_changelog::Migrate),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[actix_rt::test]
+    async fn migration_2_20_00() {
+        use crate::migrations::*;
+        use crate::test_db::*;
+        use v2_19_00::V2_19_00;
+        use v2_20_00::V2_20_00;
+
+        let previous_version = V2_19_00.version();
+        let version = V2_20_00.version();
+
+        let SetupResult { connection, .. } = setup_test(SetupOption {
+            db_name: &format!("migration_{version}"),
+            version: Some(previous_version.clone()),
+            ..Default::default()
+        })
+        .await;
+
+        // Run this migration
+        migrate(&connection, Some(version.clone())).unwrap();
+        assert_eq!(get_database_version(&connection), version);
+    }
+}
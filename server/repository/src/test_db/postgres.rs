@@ -25,6 +25,7 @@ pub fn get_test_db_settings(db_name: &str) -> DatabaseSettings {
         host: "localhost".to_string(),
         database_name: db_name.to_string(),
         init_sql: None,
+        encryption: None,
         database_path: None,
         connection_pool_max_connections: None,
         connection_pool_min_idle: None,
@@ -52,7 +53,7 @@ fn create_template_db(
     .unwrap();
 
     // migrate the DB:
-    let connection_manager = get_storage_connection_manager(&db_settings);
+    let connection_manager = get_storage_connection_manager(&db_settings).unwrap();
     let connection = connection_manager.connection().unwrap();
     migrate(&connection, version).unwrap();
 
@@ -106,7 +107,7 @@ async fn setup_with_version_no_template(
     .execute(&mut connection)
     .unwrap();
 
-    let connection_manager = get_storage_connection_manager(&db_settings);
+    let connection_manager = get_storage_connection_manager(&db_settings).unwrap();
     let connection = connection_manager.connection().unwrap();
     migrate(&connection, version).unwrap();
 
@@ -211,7 +212,7 @@ pub(crate) async fn setup_with_version(
     .execute(&mut root_connection)
     .unwrap();
 
-    let connection_manager = get_storage_connection_manager(db_settings);
+    let connection_manager = get_storage_connection_manager(db_settings).unwrap();
     let collection = if !cache_all_mock_data {
         let connection = connection_manager.connection().unwrap();
         insert_all_mock_data(&connection, inserts).await
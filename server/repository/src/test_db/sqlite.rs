@@ -34,6 +34,7 @@ fn get_test_db_settings_etc(db_name: &str, is_template: bool) -> DatabaseSetting
             format!("{TEST_OUTPUT_DIR}/{db_name}.sqlite")
         },
         init_sql: None,
+        encryption: None,
         database_path: None,
         connection_pool_max_connections: None,
         connection_pool_min_idle: None,
@@ -159,6 +160,7 @@ fn connection_manager(db_settings: &DatabaseSettings) -> StorageConnectionManage
         .min_idle(Some(1))
         .connection_customizer(Box::new(SqliteConnectionOptions {
             busy_timeout_ms: Some(SQLITE_LOCKWAIT_MS),
+            key: None,
         }))
         .build(connection_manager)
         .expect("Failed to connect to database");
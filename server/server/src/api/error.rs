@@ -0,0 +1,125 @@
+use actix_web::{http::StatusCode, HttpResponse};
+use log::error;
+use repository::RepositoryError;
+use serde::Serialize;
+use service::{
+    auth::{AuthDeniedKind, AuthError},
+    ListError,
+};
+use utoipa::ToSchema;
+
+use super::json_response;
+
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Conflict(String),
+    /// The request was valid but the service rejected it, e.g. an unknown supplier
+    Unprocessable(String),
+    Internal(String),
+}
+
+/// Body of error responses
+#[derive(Serialize, ToSchema)]
+#[schema(as = Error)]
+pub struct ErrorBody {
+    /// Stable error code, e.g. NOT_FOUND
+    #[schema(example = "NOT_FOUND")]
+    pub error: &'static str,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Stable error code for clients to match on
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::BadRequest(_) => "BAD_REQUEST",
+            ApiError::Unauthorized(_) => "UNAUTHORIZED",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::Unprocessable(_) => "UNPROCESSABLE",
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::Unprocessable(message)
+            | ApiError::Internal(message) => message,
+        }
+    }
+
+    /// Service errors are enums describing why the service rejected the input
+    pub fn unprocessable<E: std::fmt::Debug>(error: E) -> Self {
+        ApiError::Unprocessable(format!("{error:?}"))
+    }
+
+    pub fn to_response(&self) -> HttpResponse {
+        if let ApiError::Internal(message) = self {
+            error!("REST API error: {message}");
+        }
+        json_response(
+            self.status(),
+            &ErrorBody {
+                error: self.code(),
+                message: self.message().to_string(),
+            },
+        )
+    }
+}
+
+impl From<RepositoryError> for ApiError {
+    fn from(error: RepositoryError) -> Self {
+        ApiError::Internal(format!("{error:?}"))
+    }
+}
+
+impl From<ListError> for ApiError {
+    fn from(error: ListError) -> Self {
+        match error {
+            ListError::LimitBelowMin(min) => {
+                ApiError::BadRequest(format!("limit must be at least {min}"))
+            }
+            ListError::LimitAboveMax(max) => {
+                ApiError::BadRequest(format!("limit must be at most {max}"))
+            }
+            ListError::DatabaseError(error) => error.into(),
+            ListError::PluginError(error) => ApiError::Internal(error),
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(error: AuthError) -> Self {
+        match error {
+            AuthError::Denied(AuthDeniedKind::NotAuthenticated(message)) => {
+                ApiError::Unauthorized(message)
+            }
+            AuthError::Denied(AuthDeniedKind::InsufficientPermission { msg, .. }) => {
+                ApiError::Forbidden(msg)
+            }
+            AuthError::InternalError(message) => ApiError::Internal(message),
+        }
+    }
+}
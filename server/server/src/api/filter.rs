@@ -0,0 +1,96 @@
+//! Query parameters map onto the repository filters: comma separated values match any of the
+//! values, text parameters match anywhere in the text and `*From`/`*To` parameters are inclusive
+//! ranges
+use chrono::{NaiveDate, NaiveDateTime};
+use repository::{DateFilter, DatetimeFilter, EqualFilter, StringFilter};
+use serde::de::DeserializeOwned;
+
+use super::ApiError;
+
+fn split(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn equal_any(value: Option<String>) -> Option<EqualFilter<String>> {
+    value.map(|value| EqualFilter::equal_any(split(&value)))
+}
+
+pub fn equal_any_number(
+    name: &str,
+    value: Option<String>,
+) -> Result<Option<EqualFilter<i64>>, ApiError> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let numbers = split(&value)
+        .iter()
+        .map(|number| number.parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ApiError::BadRequest(format!("{name} must be comma separated numbers")))?;
+    Ok(Some(EqualFilter::equal_any(numbers)))
+}
+
+/// Enum values use the same SCREAMING_SNAKE_CASE names as the JSON responses
+pub fn equal_any_enum<T: DeserializeOwned>(
+    name: &str,
+    value: Option<String>,
+) -> Result<Option<EqualFilter<T>>, ApiError> {
+    equal_any_with(name, value, |value| {
+        serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+    })
+}
+
+/// For enums without serde names matching the JSON responses
+pub fn equal_any_with<T, F>(
+    name: &str,
+    value: Option<String>,
+    parse: F,
+) -> Result<Option<EqualFilter<T>>, ApiError>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let values = split(&value)
+        .iter()
+        .map(|value| {
+            parse(value).ok_or_else(|| ApiError::BadRequest(format!("Invalid {name}: {value}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(EqualFilter::equal_any(values)))
+}
+
+pub fn like(value: Option<String>) -> Option<StringFilter> {
+    value.map(|value| StringFilter::like(&value))
+}
+
+pub fn datetime_range(
+    from: Option<NaiveDateTime>,
+    to: Option<NaiveDateTime>,
+) -> Option<DatetimeFilter> {
+    if from.is_none() && to.is_none() {
+        return None;
+    }
+    Some(DatetimeFilter {
+        after_or_equal_to: from,
+        before_or_equal_to: to,
+        ..Default::default()
+    })
+}
+
+pub fn date_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Option<DateFilter> {
+    if from.is_none() && to.is_none() {
+        return None;
+    }
+    Some(DateFilter {
+        after_or_equal_to: from,
+        before_or_equal_to: to,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,444 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use repository::{
+    EqualFilter, Invoice, InvoiceFilter, InvoiceLine, InvoiceLineFilter, InvoiceStatus,
+    InvoiceType, Keyset,
+};
+use serde::{Deserialize, Serialize};
+use service::{
+    auth::Resource,
+    auth_data::AuthData,
+    invoice::inbound_shipment::{
+        InboundShipmentType, InsertInboundShipment, InsertInboundShipmentError,
+    },
+    invoice_line::{
+        query::GetInvoiceLinesError,
+        stock_in_line::{InsertStockInLine, InsertStockInLineError, StockInType},
+    },
+    service_provider::{ServiceContext, ServiceProvider},
+    NullableUpdate,
+};
+use util::uuid::uuid;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    create_response,
+    filter::{datetime_range, equal_any, equal_any_enum, equal_any_number, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ApiError, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct InvoiceQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Comma separated, matches any
+    pub invoice_number: Option<String>,
+    /// Comma separated, matches any
+    pub other_party_id: Option<String>,
+    /// Matches anywhere in the text
+    pub other_party_name: Option<String>,
+    /// Comma separated invoice types, e.g. INBOUND_SHIPMENT
+    pub r#type: Option<String>,
+    /// Comma separated statuses, e.g. NEW,VERIFIED
+    pub status: Option<String>,
+    /// Matches anywhere in the text
+    pub their_reference: Option<String>,
+    /// Comma separated, matches any
+    pub requisition_id: Option<String>,
+    /// Comma separated, matches any
+    pub purchase_order_id: Option<String>,
+    pub created_datetime_from: Option<NaiveDateTime>,
+    pub created_datetime_to: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = Invoice)]
+pub struct InvoiceDto {
+    pub id: String,
+    pub invoice_number: i64,
+    #[schema(value_type = String, example = "INBOUND_SHIPMENT")]
+    pub r#type: InvoiceType,
+    #[schema(value_type = String, example = "NEW")]
+    pub status: InvoiceStatus,
+    pub other_party_id: String,
+    pub other_party_name: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub on_hold: bool,
+    pub requisition_id: Option<String>,
+    pub purchase_order_id: Option<String>,
+    pub created_datetime: NaiveDateTime,
+    pub shipped_datetime: Option<NaiveDateTime>,
+    pub received_datetime: Option<NaiveDateTime>,
+    pub verified_datetime: Option<NaiveDateTime>,
+    /// Only included for a single invoice
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<InvoiceLineDto>>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = InvoiceLine)]
+pub struct InvoiceLineDto {
+    pub id: String,
+    pub item_id: String,
+    pub item_code: String,
+    pub item_name: String,
+    pub batch: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub pack_size: f64,
+    pub number_of_packs: f64,
+    pub cost_price_per_pack: f64,
+    pub sell_price_per_pack: f64,
+    pub total_before_tax: f64,
+    pub total_after_tax: f64,
+    pub stock_line_id: Option<String>,
+    pub location_id: Option<String>,
+}
+
+/// Creates an inbound shipment with its lines
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInvoice {
+    /// Generated when not provided
+    pub id: Option<String>,
+    pub other_party_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub on_hold: Option<bool>,
+    #[serde(default)]
+    pub lines: Vec<CreateInvoiceLine>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInvoiceLine {
+    pub id: Option<String>,
+    pub item_id: String,
+    pub pack_size: f64,
+    pub number_of_packs: f64,
+    pub batch: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub cost_price_per_pack: f64,
+    pub sell_price_per_pack: f64,
+    pub location_id: Option<String>,
+}
+
+impl InvoiceQuery {
+    fn to_domain(self) -> Result<InvoiceFilter, ApiError> {
+        let InvoiceQuery {
+            id,
+            invoice_number,
+            other_party_id,
+            other_party_name,
+            r#type,
+            status,
+            their_reference,
+            requisition_id,
+            purchase_order_id,
+            created_datetime_from,
+            created_datetime_to,
+        } = self;
+
+        Ok(InvoiceFilter {
+            id: equal_any(id),
+            invoice_number: equal_any_number("invoiceNumber", invoice_number)?,
+            name_id: equal_any(other_party_id),
+            name: like(other_party_name),
+            r#type: equal_any_enum("type", r#type)?,
+            status: equal_any_enum("status", status)?,
+            their_reference: like(their_reference),
+            requisition_id: equal_any(requisition_id),
+            purchase_order_id: equal_any(purchase_order_id),
+            created_datetime: datetime_range(created_datetime_from, created_datetime_to),
+            ..Default::default()
+        })
+    }
+}
+
+impl InvoiceDto {
+    fn from_domain(invoice: Invoice, lines: Option<Vec<InvoiceLine>>) -> InvoiceDto {
+        let Invoice {
+            invoice_row,
+            name_row,
+            ..
+        } = invoice;
+
+        InvoiceDto {
+            id: invoice_row.id,
+            invoice_number: invoice_row.invoice_number,
+            r#type: invoice_row.r#type,
+            status: invoice_row.status,
+            other_party_id: name_row.id,
+            other_party_name: name_row.name,
+            their_reference: invoice_row.their_reference,
+            comment: invoice_row.comment,
+            on_hold: invoice_row.on_hold,
+            requisition_id: invoice_row.requisition_id,
+            purchase_order_id: invoice_row.purchase_order_id,
+            created_datetime: invoice_row.created_datetime,
+            shipped_datetime: invoice_row.shipped_datetime,
+            received_datetime: invoice_row.received_datetime,
+            verified_datetime: invoice_row.verified_datetime,
+            lines: lines.map(|lines| lines.into_iter().map(InvoiceLineDto::from_domain).collect()),
+        }
+    }
+}
+
+impl InvoiceLineDto {
+    fn from_domain(line: InvoiceLine) -> InvoiceLineDto {
+        let InvoiceLine {
+            invoice_line_row,
+            item_row,
+            ..
+        } = line;
+
+        InvoiceLineDto {
+            id: invoice_line_row.id,
+            item_id: item_row.id,
+            item_code: invoice_line_row.item_code,
+            item_name: invoice_line_row.item_name,
+            batch: invoice_line_row.batch,
+            expiry_date: invoice_line_row.expiry_date,
+            pack_size: invoice_line_row.pack_size,
+            number_of_packs: invoice_line_row.number_of_packs,
+            cost_price_per_pack: invoice_line_row.cost_price_per_pack,
+            sell_price_per_pack: invoice_line_row.sell_price_per_pack,
+            total_before_tax: invoice_line_row.total_before_tax,
+            total_after_tax: invoice_line_row.total_after_tax,
+            stock_line_id: invoice_line_row.stock_line_id,
+            location_id: invoice_line_row.location_id,
+        }
+    }
+}
+
+fn invoice_with_lines(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<InvoiceDto, ApiError> {
+    let invoice = service_provider
+        .invoice_service
+        .get_invoice(ctx, Some(ctx.store_id.as_str()), id, None)?
+        .ok_or_else(|| ApiError::NotFound(format!("Invoice {id} not found")))?;
+    let lines = service_provider
+        .invoice_line_service
+        .get_invoice_lines(
+            ctx,
+            &ctx.store_id,
+            None,
+            Some(InvoiceLineFilter {
+                invoice_id: Some(EqualFilter::equal_to(id.to_string())),
+                ..Default::default()
+            }),
+            None,
+        )
+        .map_err(|error| match error {
+            GetInvoiceLinesError::DatabaseError(error) => error.into(),
+            GetInvoiceLinesError::ListError(error) => error.into(),
+            error => ApiError::Internal(format!("{error:?}")),
+        })?;
+
+    Ok(InvoiceDto::from_domain(invoice, Some(lines.rows)))
+}
+
+/// Invoices of the store by created datetime, without lines. Cancellation reversals are left out
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/invoices",
+    params(("store_id" = String, Path), PageQuery, InvoiceQuery),
+    responses(
+        (status = 200, description = "Page of invoices", body = Page<InvoiceDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_invoices(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<InvoiceQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryInvoice,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain()?;
+            let result = service_provider.invoice_service.get_invoices_after(
+                ctx,
+                &store_id,
+                page_request.after.clone(),
+                page_request.fetch_limit(),
+                Some(filter),
+            )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |invoice| Keyset {
+                    sort_key: invoice.invoice_row.created_datetime,
+                    id: invoice.invoice_row.id.clone(),
+                },
+                |invoice| InvoiceDto::from_domain(invoice, None),
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
+
+/// Invoice with its lines
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/invoices/{id}",
+    params(("store_id" = String, Path), ("id" = String, Path)),
+    responses(
+        (status = 200, description = "Invoice with its lines", body = InvoiceDto),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 404, description = "No invoice with the id in the store", body = ErrorBody),
+    )
+)]
+pub async fn get_invoice(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (store_id, id) = path.into_inner();
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryInvoice,
+        &store_id,
+        |ctx| {
+            let invoice = invoice_with_lines(&service_provider, ctx, &id)?;
+            Ok(json_response(StatusCode::OK, &invoice))
+        },
+    )
+}
+
+/// Creates an inbound shipment with its lines
+#[utoipa::path(
+    post,
+    path = "/api/v1/stores/{store_id}/invoices",
+    params(
+        ("store_id" = String, Path),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and body replay the first response for 24 hours"),
+    ),
+    request_body = CreateInvoice,
+    responses(
+        (status = 201, description = "Created, or the replayed response of a retry", body = InvoiceDto),
+        (status = 400, description = "Invalid body", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 409, description = "Idempotency key reused with another body", body = ErrorBody),
+        (status = 422, description = "Rejected by the service", body = ErrorBody),
+    )
+)]
+pub async fn post_invoice(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    body: web::Bytes,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::MutateInboundShipment,
+        &store_id,
+        |ctx| {
+            create_response(&request, ctx, &body, |ctx, input: CreateInvoice| {
+                create_invoice(&service_provider, ctx, input)
+            })
+        },
+    )
+}
+
+fn create_invoice(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    input: CreateInvoice,
+) -> Result<InvoiceDto, ApiError> {
+    let CreateInvoice {
+        id,
+        other_party_id,
+        their_reference,
+        comment,
+        on_hold,
+        lines,
+    } = input;
+    let id = id.unwrap_or_else(uuid);
+
+    service_provider
+        .invoice_service
+        .insert_inbound_shipment(
+            ctx,
+            InsertInboundShipment {
+                id: id.clone(),
+                other_party_id,
+                on_hold,
+                comment,
+                their_reference,
+                colour: None,
+                requisition_id: None,
+                purchase_order_id: None,
+                insert_lines_from_purchase_order: false,
+            },
+            InboundShipmentType::InboundShipment,
+        )
+        .map_err(|error| match error {
+            InsertInboundShipmentError::DatabaseError(error) => error.into(),
+            error => ApiError::unprocessable(error),
+        })?;
+
+    for line in lines {
+        service_provider
+            .invoice_line_service
+            .insert_stock_in_line(
+                ctx,
+                InsertStockInLine {
+                    id: line.id.unwrap_or_else(uuid),
+                    invoice_id: id.clone(),
+                    item_id: line.item_id,
+                    location: line.location_id.map(|location_id| NullableUpdate {
+                        value: Some(location_id),
+                    }),
+                    pack_size: line.pack_size,
+                    batch: line.batch,
+                    cost_price_per_pack: line.cost_price_per_pack,
+                    sell_price_per_pack: line.sell_price_per_pack,
+                    expiry_date: line.expiry_date,
+                    number_of_packs: line.number_of_packs,
+                    r#type: StockInType::InboundShipment,
+                    ..Default::default()
+                },
+                Some(InboundShipmentType::InboundShipment),
+            )
+            .map_err(|error| match error {
+                InsertStockInLineError::DatabaseError(error) => error.into(),
+                error => ApiError::unprocessable(error),
+            })?;
+    }
+
+    invoice_with_lines(service_provider, ctx, &id)
+}
@@ -0,0 +1,165 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use repository::{Item, ItemFilter, ItemType, Keyset};
+use serde::{Deserialize, Serialize};
+use service::{
+    auth::Resource, auth_data::AuthData, item::get_items_after, service_provider::ServiceProvider,
+};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    filter::{equal_any, equal_any_with, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ApiError, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ItemQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Matches anywhere in the text
+    pub name: Option<String>,
+    /// Matches anywhere in the text
+    pub code: Option<String>,
+    /// Matches anywhere in the text
+    pub code_or_name: Option<String>,
+    /// Comma separated STOCK, SERVICE or NON_STOCK
+    pub r#type: Option<String>,
+    pub is_active: Option<bool>,
+    pub is_visible: Option<bool>,
+    pub has_stock_on_hand: Option<bool>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = Item)]
+pub struct ItemDto {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    #[schema(value_type = String, example = "STOCK")]
+    pub r#type: &'static str,
+    pub unit_name: Option<String>,
+    pub strength: Option<String>,
+    pub default_pack_size: f64,
+    pub is_vaccine: bool,
+    pub is_active: bool,
+}
+
+pub fn item_type(r#type: &ItemType) -> &'static str {
+    match r#type {
+        ItemType::Stock => "STOCK",
+        ItemType::Service => "SERVICE",
+        ItemType::NonStock => "NON_STOCK",
+    }
+}
+
+fn parse_item_type(value: &str) -> Option<ItemType> {
+    match value {
+        "STOCK" => Some(ItemType::Stock),
+        "SERVICE" => Some(ItemType::Service),
+        "NON_STOCK" => Some(ItemType::NonStock),
+        _ => None,
+    }
+}
+
+impl ItemQuery {
+    fn to_domain(self) -> Result<ItemFilter, ApiError> {
+        let ItemQuery {
+            id,
+            name,
+            code,
+            code_or_name,
+            r#type,
+            is_active,
+            is_visible,
+            has_stock_on_hand,
+        } = self;
+
+        Ok(ItemFilter {
+            id: equal_any(id),
+            name: like(name),
+            code: like(code),
+            code_or_name: like(code_or_name),
+            r#type: equal_any_with("type", r#type, parse_item_type)?,
+            is_active,
+            is_visible,
+            has_stock_on_hand,
+            ..Default::default()
+        })
+    }
+}
+
+impl ItemDto {
+    fn from_domain(Item { item_row, unit_row }: Item) -> ItemDto {
+        ItemDto {
+            r#type: item_type(&item_row.r#type),
+            unit_name: unit_row.map(|unit| unit.name),
+            id: item_row.id,
+            code: item_row.code,
+            name: item_row.name,
+            strength: item_row.strength,
+            default_pack_size: item_row.default_pack_size,
+            is_vaccine: item_row.is_vaccine,
+            is_active: item_row.is_active,
+        }
+    }
+}
+
+/// Items of the store by name
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/items",
+    params(("store_id" = String, Path), PageQuery, ItemQuery),
+    responses(
+        (status = 200, description = "Page of items", body = Page<ItemDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_items(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<ItemQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryItems,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain()?;
+            let result = get_items_after(
+                ctx,
+                &store_id,
+                page_request.after.clone(),
+                page_request.fetch_limit(),
+                Some(filter),
+            )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |item| Keyset {
+                    sort_key: item.item_row.name.clone(),
+                    id: item.item_row.id.clone(),
+                },
+                ItemDto::from_domain,
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
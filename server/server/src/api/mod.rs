@@ -0,0 +1,190 @@
+use actix_web::{
+    http::{header, StatusCode},
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use graphql_core::auth_data_from_request;
+use serde::Serialize;
+use service::{
+    api_idempotency::{
+        create_with_idempotency_key, hash_request, IdempotentCreateError, IdempotentResponse,
+    },
+    auth::{Resource, ResourceAccessRequest},
+    auth_data::AuthData,
+    service_provider::{ServiceContext, ServiceProvider},
+};
+
+mod error;
+mod filter;
+mod invoices;
+mod items;
+mod names;
+mod openapi;
+mod pagination;
+mod purchase_orders;
+mod requisitions;
+mod stock;
+#[cfg(test)]
+mod test;
+pub use error::{ApiError, ErrorBody};
+
+const URL_PATH: &str = "/api/v1";
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Versioned REST API over the service layer, documented by `{URL_PATH}/openapi.json`
+pub fn config_api(cfg: &mut web::ServiceConfig) {
+    let store_path = format!("{URL_PATH}/stores/{{store_id}}");
+
+    cfg.route(
+        &format!("{URL_PATH}/openapi.json"),
+        web::get().to(openapi::get_openapi),
+    );
+    cfg.route(
+        &format!("{store_path}/items"),
+        web::get().to(items::get_items),
+    );
+    cfg.route(
+        &format!("{store_path}/names"),
+        web::get().to(names::get_names),
+    );
+    cfg.route(
+        &format!("{store_path}/stock"),
+        web::get().to(stock::get_stock),
+    );
+    cfg.route(
+        &format!("{store_path}/invoices"),
+        web::get().to(invoices::get_invoices),
+    );
+    cfg.route(
+        &format!("{store_path}/invoices"),
+        web::post().to(invoices::post_invoice),
+    );
+    cfg.route(
+        &format!("{store_path}/invoices/{{id}}"),
+        web::get().to(invoices::get_invoice),
+    );
+    cfg.route(
+        &format!("{store_path}/requisitions"),
+        web::get().to(requisitions::get_requisitions),
+    );
+    cfg.route(
+        &format!("{store_path}/requisitions"),
+        web::post().to(requisitions::post_requisition),
+    );
+    cfg.route(
+        &format!("{store_path}/requisitions/{{id}}"),
+        web::get().to(requisitions::get_requisition),
+    );
+    cfg.route(
+        &format!("{store_path}/purchase-orders"),
+        web::get().to(purchase_orders::get_purchase_orders),
+    );
+    cfg.route(
+        &format!("{store_path}/purchase-orders"),
+        web::post().to(purchase_orders::post_purchase_order),
+    );
+    cfg.route(
+        &format!("{store_path}/purchase-orders/{{id}}"),
+        web::get().to(purchase_orders::get_purchase_order),
+    );
+}
+
+/// Validates the bearer token (user token or API key) has access to `resource` in the store and
+/// returns a context for the user and store
+fn validate_request(
+    request: &HttpRequest,
+    service_provider: &ServiceProvider,
+    auth_data: &AuthData,
+    resource: Resource,
+    store_id: &str,
+) -> Result<ServiceContext, ApiError> {
+    let token = auth_data_from_request(request).auth_token;
+    let basic_context = service_provider.basic_context()?;
+
+    let validated_user = service_provider.validation_service.validate(
+        &basic_context,
+        auth_data,
+        &token,
+        &None,
+        &ResourceAccessRequest {
+            resource,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    Ok(service_provider.context(store_id.to_string(), validated_user.user_id)?)
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> HttpResponse {
+    HttpResponse::build(status)
+        .append_header(header::ContentType::json())
+        .json(body)
+}
+
+/// Parses and runs a create in a transaction. With an `Idempotency-Key` header a retry of the same
+/// request replays the first response rather than creating the records again
+fn create_response<I, T, F>(
+    request: &HttpRequest,
+    ctx: &ServiceContext,
+    body: &[u8],
+    create: F,
+) -> Result<HttpResponse, ApiError>
+where
+    I: serde::de::DeserializeOwned,
+    T: Serialize,
+    F: FnOnce(&ServiceContext, I) -> Result<T, ApiError>,
+{
+    let input: I = serde_json::from_slice(body)
+        .map_err(|error| ApiError::BadRequest(format!("Invalid request body: {error}")))?;
+    let key = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .map(|value| {
+            value.to_str().map_err(|_| {
+                ApiError::BadRequest(format!("{IDEMPOTENCY_KEY_HEADER} must be visible ASCII"))
+            })
+        })
+        .transpose()?;
+    let request_hash = hash_request(request.method().as_str(), request.path(), body);
+
+    let (response, replayed) = create_with_idempotency_key(ctx, key, &request_hash, || {
+        let created = ctx
+            .connection
+            .transaction_sync(|_| create(ctx, input))
+            .map_err(|error| error.to_inner_error())?;
+        let body = serde_json::to_string(&created)
+            .map_err(|error| ApiError::Internal(error.to_string()))?;
+        Ok(IdempotentResponse {
+            status: StatusCode::CREATED.as_u16(),
+            body,
+        })
+    })
+    .map_err(|error| match error {
+        IdempotentCreateError::KeyReusedForDifferentRequest => ApiError::Conflict(format!(
+            "{IDEMPOTENCY_KEY_HEADER} was already used for a different request"
+        )),
+        IdempotentCreateError::CreateError(error) => error,
+        IdempotentCreateError::DatabaseError(error) => error.into(),
+    })?;
+
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::CREATED);
+    Ok(HttpResponse::build(status)
+        .append_header(header::ContentType::json())
+        .append_header((IDEMPOTENT_REPLAYED_HEADER, replayed.to_string()))
+        .body(response.body))
+}
+
+/// Handlers share the auth and error handling, `handle` runs with a validated context
+fn respond(
+    request: &HttpRequest,
+    service_provider: &Data<ServiceProvider>,
+    auth_data: &Data<AuthData>,
+    resource: Resource,
+    store_id: &str,
+    handle: impl FnOnce(&ServiceContext) -> Result<HttpResponse, ApiError>,
+) -> HttpResponse {
+    validate_request(request, service_provider, auth_data, resource, store_id)
+        .and_then(|ctx| handle(&ctx))
+        .unwrap_or_else(|error| error.to_response())
+}
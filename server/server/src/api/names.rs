@@ -0,0 +1,151 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use repository::{Keyset, Name, NameFilter};
+use serde::{Deserialize, Serialize};
+use service::{auth::Resource, auth_data::AuthData, service_provider::ServiceProvider};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    filter::{equal_any, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct NameQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Matches anywhere in the text
+    pub name: Option<String>,
+    /// Matches anywhere in the text
+    pub code: Option<String>,
+    /// Matches anywhere in the text
+    pub code_or_name: Option<String>,
+    pub is_customer: Option<bool>,
+    pub is_supplier: Option<bool>,
+    pub is_store: Option<bool>,
+    pub is_visible: Option<bool>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = Name)]
+pub struct NameDto {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    pub is_customer: bool,
+    pub is_supplier: bool,
+    /// Store of the name when the name is a store
+    pub store_id: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub country: Option<String>,
+}
+
+impl NameQuery {
+    fn to_domain(self) -> NameFilter {
+        let NameQuery {
+            id,
+            name,
+            code,
+            code_or_name,
+            is_customer,
+            is_supplier,
+            is_store,
+            is_visible,
+        } = self;
+
+        NameFilter {
+            id: equal_any(id),
+            name: like(name),
+            code: like(code),
+            code_or_name: like(code_or_name),
+            is_customer,
+            is_supplier,
+            is_store,
+            is_visible,
+            ..Default::default()
+        }
+    }
+}
+
+impl NameDto {
+    fn from_domain(name: Name) -> NameDto {
+        let Name {
+            name_row,
+            store_row,
+            ..
+        } = name;
+
+        NameDto {
+            id: name_row.id,
+            code: name_row.code,
+            name: name_row.name,
+            is_customer: name_row.is_customer,
+            is_supplier: name_row.is_supplier,
+            store_id: store_row.map(|store| store.id),
+            phone: name_row.phone,
+            email: name_row.email,
+            country: name_row.country,
+        }
+    }
+}
+
+/// Names visible to the store by name
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/names",
+    params(("store_id" = String, Path), PageQuery, NameQuery),
+    responses(
+        (status = 200, description = "Page of names", body = Page<NameDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_names(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<NameQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryName,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain();
+            let result = service_provider.name_service.get_names_after(
+                ctx,
+                &store_id,
+                page_request.after.clone(),
+                page_request.fetch_limit(),
+                Some(filter),
+            )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |name| Keyset {
+                    sort_key: name.name_row.name.clone(),
+                    id: name.name_row.id.clone(),
+                },
+                NameDto::from_domain,
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
@@ -0,0 +1,94 @@
+use actix_web::{http::StatusCode, HttpResponse};
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use super::{
+    invoices, items, json_response, names, purchase_orders, requisitions, stock, ErrorBody,
+};
+
+/// OpenAPI document of the REST API, generated from the handlers and their DTOs
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Open mSupply REST API", version = "1"),
+    paths(
+        items::get_items,
+        names::get_names,
+        stock::get_stock,
+        invoices::get_invoices,
+        invoices::get_invoice,
+        invoices::post_invoice,
+        requisitions::get_requisitions,
+        requisitions::get_requisition,
+        requisitions::post_requisition,
+        purchase_orders::get_purchase_orders,
+        purchase_orders::get_purchase_order,
+        purchase_orders::post_purchase_order,
+    ),
+    components(schemas(ErrorBody)),
+    modifiers(&BearerAuth),
+    security(("bearer" = []))
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("User token or API key"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+pub async fn get_openapi() -> HttpResponse {
+    json_response(StatusCode::OK, &ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_references() {
+        let document = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        // Every referenced schema is defined
+        let text = document.to_string();
+        for reference in text.split("#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "{name} is not defined");
+        }
+        for path in [
+            "items",
+            "names",
+            "stock",
+            "invoices",
+            "invoices/{id}",
+            "requisitions",
+            "requisitions/{id}",
+            "purchase-orders",
+            "purchase-orders/{id}",
+        ] {
+            assert!(
+                document["paths"][format!("/api/v1/stores/{{store_id}}/{path}")].is_object(),
+                "{path} is not documented"
+            );
+        }
+        assert_eq!(
+            document["paths"]["/api/v1/stores/{store_id}/invoices"]["post"]["responses"]["201"]
+                ["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Invoice"
+        );
+        assert!(document["components"]["securitySchemes"]["bearer"].is_object());
+    }
+}
@@ -0,0 +1,174 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use repository::Keyset;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::ApiError;
+
+pub const DEFAULT_LIMIT: u32 = 100;
+pub const MAX_LIMIT: u32 = 1000;
+
+/// `limit` and `cursor` query parameters, the cursor is the `nextCursor` of the previous page
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    /// Page size, 100 by default and at most 1000
+    pub limit: Option<u32>,
+    /// `nextCursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+/// Sort key and id of the last row of a page, the next page starts after it. Unlike an offset
+/// rows inserted or deleted in earlier pages don't shift the next page
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Cursor<T> {
+    sort_key: T,
+    id: String,
+}
+
+/// Page size and position, `T` is the type of the list's sort key
+#[derive(Debug, PartialEq)]
+pub struct PageRequest<T> {
+    pub limit: u32,
+    pub after: Option<Keyset<T>>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    /// Cursor of the next page, none on the last page
+    pub next_cursor: Option<String>,
+    /// Rows matching the filter across all pages
+    pub total_count: u32,
+}
+
+fn encode_cursor<T: Serialize>(keyset: Keyset<T>) -> String {
+    // Cursors are opaque to clients, only the server reads them
+    let Keyset { sort_key, id } = keyset;
+    let json = serde_json::to_vec(&Cursor { sort_key, id }).unwrap_or_default();
+    BASE64_URL_SAFE_NO_PAD.encode(json)
+}
+
+fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> Option<Keyset<T>> {
+    let json = BASE64_URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    let Cursor { sort_key, id } = serde_json::from_slice(&json).ok()?;
+    Some(Keyset { sort_key, id })
+}
+
+impl PageQuery {
+    pub fn to_domain<T: DeserializeOwned>(&self) -> Result<PageRequest<T>, ApiError> {
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT);
+        if limit == 0 || limit > MAX_LIMIT {
+            return Err(ApiError::BadRequest(format!(
+                "limit must be between 1 and {MAX_LIMIT}"
+            )));
+        }
+        let after = self
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                decode_cursor(cursor)
+                    .ok_or_else(|| ApiError::BadRequest("Invalid cursor".to_string()))
+            })
+            .transpose()?;
+
+        Ok(PageRequest { limit, after })
+    }
+}
+
+impl<T> PageRequest<T> {
+    /// Rows to read, one more than the page to know whether there is a next page
+    pub fn fetch_limit(&self) -> u32 {
+        self.limit + 1
+    }
+}
+
+impl<T> Page<T> {
+    /// Page of the `rows` read with `request.fetch_limit()`, `keyset` is the sort key and id of a
+    /// row
+    pub fn new<R, K: Serialize>(
+        mut rows: Vec<R>,
+        request: &PageRequest<K>,
+        total_count: u32,
+        keyset: impl Fn(&R) -> Keyset<K>,
+        to_dto: impl Fn(R) -> T,
+    ) -> Page<T> {
+        let has_next_page = rows.len() > request.limit as usize;
+        rows.truncate(request.limit as usize);
+        let next_cursor = has_next_page
+            .then(|| rows.last().map(|row| encode_cursor(keyset(row))))
+            .flatten();
+
+        Page {
+            data: rows.into_iter().map(to_dto).collect(),
+            next_cursor,
+            total_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyset(row: &(&str, &str)) -> Keyset<String> {
+        Keyset {
+            sort_key: row.0.to_string(),
+            id: row.1.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cursor_pagination() {
+        let first: PageRequest<String> = PageQuery {
+            limit: Some(2),
+            cursor: None,
+        }
+        .to_domain()
+        .unwrap();
+        assert_eq!(first.after, None);
+        assert_eq!(first.fetch_limit(), 3);
+
+        // Rows past the limit only tell there is a next page
+        let page = Page::new(
+            vec![("apple", "b"), ("apple", "c"), ("banana", "a")],
+            &first,
+            3,
+            keyset,
+            |row| row.1,
+        );
+        assert_eq!(page.data, vec!["b", "c"]);
+        let next: PageRequest<String> = PageQuery {
+            limit: Some(2),
+            cursor: page.next_cursor,
+        }
+        .to_domain()
+        .unwrap();
+        assert_eq!(
+            next.after,
+            Some(Keyset {
+                sort_key: "apple".to_string(),
+                id: "c".to_string(),
+            })
+        );
+
+        // Last page has no next cursor
+        let page = Page::new(vec![("banana", "a")], &next, 3, keyset, |row| row.1);
+        assert_eq!(page.next_cursor, None);
+
+        assert!(PageQuery {
+            limit: Some(MAX_LIMIT + 1),
+            cursor: None,
+        }
+        .to_domain::<String>()
+        .is_err());
+        assert!(PageQuery {
+            limit: None,
+            cursor: Some("not a cursor".to_string()),
+        }
+        .to_domain::<String>()
+        .is_err());
+    }
+}
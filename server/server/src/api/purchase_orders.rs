@@ -0,0 +1,399 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use repository::{
+    EqualFilter, Keyset, PurchaseOrder, PurchaseOrderFilter, PurchaseOrderLine,
+    PurchaseOrderLineFilter, PurchaseOrderStatus,
+};
+use serde::{Deserialize, Serialize};
+use service::{
+    auth::Resource,
+    auth_data::AuthData,
+    purchase_order::insert::{InsertPurchaseOrderError, InsertPurchaseOrderInput},
+    purchase_order_line::insert::{InsertPurchaseOrderLineError, InsertPurchaseOrderLineInput},
+    service_provider::{ServiceContext, ServiceProvider},
+};
+use util::uuid::uuid;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    create_response,
+    filter::{date_range, datetime_range, equal_any, equal_any_enum, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ApiError, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct PurchaseOrderQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Comma separated statuses, e.g. NEW,CONFIRMED
+    pub status: Option<String>,
+    /// Matches anywhere in the text
+    pub supplier_name: Option<String>,
+    pub created_datetime_from: Option<NaiveDateTime>,
+    pub created_datetime_to: Option<NaiveDateTime>,
+    pub requested_delivery_date_from: Option<NaiveDate>,
+    pub requested_delivery_date_to: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = PurchaseOrder)]
+pub struct PurchaseOrderDto {
+    pub id: String,
+    pub purchase_order_number: i64,
+    #[schema(value_type = String, example = "NEW")]
+    pub status: PurchaseOrderStatus,
+    pub supplier_id: String,
+    pub reference: Option<String>,
+    pub comment: Option<String>,
+    pub currency_id: Option<String>,
+    pub created_datetime: NaiveDateTime,
+    pub confirmed_datetime: Option<NaiveDateTime>,
+    pub sent_datetime: Option<NaiveDateTime>,
+    pub requested_delivery_date: Option<NaiveDate>,
+    /// Only included for a single purchase order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<PurchaseOrderLineDto>>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = PurchaseOrderLine)]
+pub struct PurchaseOrderLineDto {
+    pub id: String,
+    pub line_number: i64,
+    pub item_id: String,
+    pub item_code: String,
+    pub item_name: String,
+    pub requested_pack_size: f64,
+    pub requested_number_of_units: f64,
+    pub adjusted_number_of_units: Option<f64>,
+    pub requested_delivery_date: Option<NaiveDate>,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub price_per_pack_before_discount: f64,
+    pub price_per_pack_after_discount: f64,
+    pub comment: Option<String>,
+}
+
+/// Creates a purchase order with its lines
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePurchaseOrder {
+    /// Generated when not provided
+    pub id: Option<String>,
+    pub supplier_id: String,
+    #[serde(default)]
+    pub lines: Vec<CreatePurchaseOrderLine>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePurchaseOrderLine {
+    pub id: Option<String>,
+    pub item_id: String,
+    pub requested_pack_size: Option<f64>,
+    pub requested_number_of_units: Option<f64>,
+    pub requested_delivery_date: Option<NaiveDate>,
+    pub expected_delivery_date: Option<NaiveDate>,
+    pub price_per_pack_before_discount: Option<f64>,
+    pub price_per_pack_after_discount: Option<f64>,
+    pub comment: Option<String>,
+}
+
+impl PurchaseOrderQuery {
+    fn to_domain(self) -> Result<PurchaseOrderFilter, ApiError> {
+        let PurchaseOrderQuery {
+            id,
+            status,
+            supplier_name,
+            created_datetime_from,
+            created_datetime_to,
+            requested_delivery_date_from,
+            requested_delivery_date_to,
+        } = self;
+
+        Ok(PurchaseOrderFilter {
+            id: equal_any(id),
+            status: equal_any_enum("status", status)?,
+            supplier: like(supplier_name),
+            created_datetime: datetime_range(created_datetime_from, created_datetime_to),
+            requested_delivery_date: date_range(
+                requested_delivery_date_from,
+                requested_delivery_date_to,
+            ),
+            ..Default::default()
+        })
+    }
+}
+
+impl PurchaseOrderDto {
+    fn from_domain(
+        purchase_order: PurchaseOrder,
+        lines: Option<Vec<PurchaseOrderLine>>,
+    ) -> PurchaseOrderDto {
+        let row = purchase_order.purchase_order_row;
+
+        PurchaseOrderDto {
+            id: row.id,
+            purchase_order_number: row.purchase_order_number,
+            status: row.status,
+            supplier_id: row.supplier_name_id,
+            reference: row.reference,
+            comment: row.comment,
+            currency_id: row.currency_id,
+            created_datetime: row.created_datetime,
+            confirmed_datetime: row.confirmed_datetime,
+            sent_datetime: row.sent_datetime,
+            requested_delivery_date: row.requested_delivery_date,
+            lines: lines.map(|lines| {
+                lines
+                    .into_iter()
+                    .map(PurchaseOrderLineDto::from_domain)
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl PurchaseOrderLineDto {
+    fn from_domain(line: PurchaseOrderLine) -> PurchaseOrderLineDto {
+        let PurchaseOrderLine {
+            purchase_order_line_row: row,
+            item_row,
+            ..
+        } = line;
+
+        PurchaseOrderLineDto {
+            id: row.id,
+            line_number: row.line_number,
+            item_id: item_row.id,
+            item_code: item_row.code,
+            item_name: row.item_name,
+            requested_pack_size: row.requested_pack_size,
+            requested_number_of_units: row.requested_number_of_units,
+            adjusted_number_of_units: row.adjusted_number_of_units,
+            requested_delivery_date: row.requested_delivery_date,
+            expected_delivery_date: row.expected_delivery_date,
+            price_per_pack_before_discount: row.price_per_pack_before_discount,
+            price_per_pack_after_discount: row.price_per_pack_after_discount,
+            comment: row.comment,
+        }
+    }
+}
+
+fn purchase_order_with_lines(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<PurchaseOrderDto, ApiError> {
+    let store_id = Some(ctx.store_id.as_str());
+    let purchase_order = service_provider
+        .purchase_order_service
+        .get_purchase_order(ctx, store_id, id)?
+        .ok_or_else(|| ApiError::NotFound(format!("Purchase order {id} not found")))?;
+    let lines = service_provider
+        .purchase_order_line_service
+        .get_purchase_order_lines(
+            ctx,
+            store_id,
+            None,
+            Some(PurchaseOrderLineFilter {
+                purchase_order_id: Some(EqualFilter::equal_to(id.to_string())),
+                ..Default::default()
+            }),
+            None,
+        )?;
+
+    Ok(PurchaseOrderDto::from_domain(
+        purchase_order,
+        Some(lines.rows),
+    ))
+}
+
+/// Purchase orders of the store by created datetime, without lines
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/purchase-orders",
+    params(("store_id" = String, Path), PageQuery, PurchaseOrderQuery),
+    responses(
+        (status = 200, description = "Page of purchase orders", body = Page<PurchaseOrderDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_purchase_orders(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<PurchaseOrderQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryPurchaseOrder,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain()?;
+            let result = service_provider
+                .purchase_order_service
+                .get_purchase_orders_after(
+                    ctx,
+                    &store_id,
+                    page_request.after.clone(),
+                    page_request.fetch_limit(),
+                    Some(filter),
+                )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |purchase_order| Keyset {
+                    sort_key: purchase_order.purchase_order_row.created_datetime,
+                    id: purchase_order.purchase_order_row.id.clone(),
+                },
+                |purchase_order| PurchaseOrderDto::from_domain(purchase_order, None),
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
+
+/// Purchase order with its lines
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/purchase-orders/{id}",
+    params(("store_id" = String, Path), ("id" = String, Path)),
+    responses(
+        (status = 200, description = "Purchase order with its lines", body = PurchaseOrderDto),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 404, description = "No purchase order with the id in the store", body = ErrorBody),
+    )
+)]
+pub async fn get_purchase_order(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (store_id, id) = path.into_inner();
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryPurchaseOrder,
+        &store_id,
+        |ctx| {
+            let purchase_order = purchase_order_with_lines(&service_provider, ctx, &id)?;
+            Ok(json_response(StatusCode::OK, &purchase_order))
+        },
+    )
+}
+
+/// Creates a purchase order with its lines
+#[utoipa::path(
+    post,
+    path = "/api/v1/stores/{store_id}/purchase-orders",
+    params(
+        ("store_id" = String, Path),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and body replay the first response for 24 hours"),
+    ),
+    request_body = CreatePurchaseOrder,
+    responses(
+        (status = 201, description = "Created, or the replayed response of a retry", body = PurchaseOrderDto),
+        (status = 400, description = "Invalid body", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 409, description = "Idempotency key reused with another body", body = ErrorBody),
+        (status = 422, description = "Rejected by the service", body = ErrorBody),
+    )
+)]
+pub async fn post_purchase_order(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    body: web::Bytes,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::MutatePurchaseOrder,
+        &store_id,
+        |ctx| {
+            create_response(&request, ctx, &body, |ctx, input: CreatePurchaseOrder| {
+                create_purchase_order(&service_provider, ctx, input)
+            })
+        },
+    )
+}
+
+fn create_purchase_order(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    input: CreatePurchaseOrder,
+) -> Result<PurchaseOrderDto, ApiError> {
+    let CreatePurchaseOrder {
+        id,
+        supplier_id,
+        lines,
+    } = input;
+    let id = id.unwrap_or_else(uuid);
+
+    service_provider
+        .purchase_order_service
+        .insert_purchase_order(
+            ctx,
+            &ctx.store_id,
+            InsertPurchaseOrderInput {
+                id: id.clone(),
+                supplier_id,
+            },
+        )
+        .map_err(|error| match error {
+            InsertPurchaseOrderError::DatabaseError(error) => error.into(),
+            error => ApiError::unprocessable(error),
+        })?;
+
+    for line in lines {
+        service_provider
+            .purchase_order_line_service
+            .insert_purchase_order_line(
+                ctx,
+                InsertPurchaseOrderLineInput {
+                    id: line.id.unwrap_or_else(uuid),
+                    purchase_order_id: id.clone(),
+                    item_id_or_code: line.item_id,
+                    requested_pack_size: line.requested_pack_size,
+                    requested_number_of_units: line.requested_number_of_units,
+                    requested_delivery_date: line.requested_delivery_date,
+                    expected_delivery_date: line.expected_delivery_date,
+                    price_per_pack_before_discount: line.price_per_pack_before_discount,
+                    price_per_pack_after_discount: line.price_per_pack_after_discount,
+                    comment: line.comment,
+                    ..Default::default()
+                },
+            )
+            .map_err(|error| match error {
+                InsertPurchaseOrderLineError::DatabaseError(error) => error.into(),
+                error => ApiError::unprocessable(error),
+            })?;
+    }
+
+    purchase_order_with_lines(service_provider, ctx, &id)
+}
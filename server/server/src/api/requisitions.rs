@@ -0,0 +1,450 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use repository::{
+    EqualFilter, Keyset, Requisition, RequisitionFilter, RequisitionLine, RequisitionLineFilter,
+    RequisitionStatus, RequisitionType,
+};
+use serde::{Deserialize, Serialize};
+use service::{
+    auth::Resource,
+    auth_data::AuthData,
+    requisition::request_requisition::{InsertRequestRequisition, InsertRequestRequisitionError},
+    requisition_line::request_requisition_line::{
+        InsertRequestRequisitionLine, InsertRequestRequisitionLineError,
+        UpdateRequestRequisitionLine, UpdateRequestRequisitionLineError,
+    },
+    service_provider::{ServiceContext, ServiceProvider},
+};
+use util::uuid::uuid;
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    create_response,
+    filter::{datetime_range, equal_any, equal_any_enum, equal_any_number, equal_any_with, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ApiError, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct RequisitionQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Comma separated, matches any
+    pub requisition_number: Option<String>,
+    /// Comma separated, matches any
+    pub other_party_id: Option<String>,
+    /// Matches anywhere in the text
+    pub other_party_name: Option<String>,
+    /// Comma separated REQUEST or RESPONSE
+    pub r#type: Option<String>,
+    /// Comma separated statuses, e.g. DRAFT,SENT
+    pub status: Option<String>,
+    /// Matches anywhere in the text
+    pub their_reference: Option<String>,
+    pub created_datetime_from: Option<NaiveDateTime>,
+    pub created_datetime_to: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = Requisition)]
+pub struct RequisitionDto {
+    pub id: String,
+    pub requisition_number: i64,
+    #[schema(value_type = String, example = "REQUEST")]
+    pub r#type: &'static str,
+    #[schema(value_type = String, example = "DRAFT")]
+    pub status: RequisitionStatus,
+    pub other_party_id: String,
+    pub other_party_name: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub max_months_of_stock: f64,
+    pub min_months_of_stock: f64,
+    pub created_datetime: NaiveDateTime,
+    pub sent_datetime: Option<NaiveDateTime>,
+    pub finalised_datetime: Option<NaiveDateTime>,
+    pub expected_delivery_date: Option<NaiveDate>,
+    /// Only included for a single requisition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<RequisitionLineDto>>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = RequisitionLine)]
+pub struct RequisitionLineDto {
+    pub id: String,
+    pub item_id: String,
+    pub item_name: String,
+    pub requested_quantity: f64,
+    pub suggested_quantity: f64,
+    pub supply_quantity: f64,
+    pub approved_quantity: f64,
+    pub available_stock_on_hand: f64,
+    pub average_monthly_consumption: f64,
+    pub comment: Option<String>,
+}
+
+/// Creates a request requisition with its lines
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequisition {
+    /// Generated when not provided
+    pub id: Option<String>,
+    pub other_party_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub max_months_of_stock: f64,
+    pub min_months_of_stock: f64,
+    pub expected_delivery_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub lines: Vec<CreateRequisitionLine>,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequisitionLine {
+    pub id: Option<String>,
+    pub item_id: String,
+    /// Defaults to the suggested quantity
+    pub requested_quantity: Option<f64>,
+    pub comment: Option<String>,
+}
+
+pub fn requisition_type(r#type: &RequisitionType) -> &'static str {
+    match r#type {
+        RequisitionType::Request => "REQUEST",
+        RequisitionType::Response => "RESPONSE",
+        RequisitionType::Imprest => "IMPREST",
+        RequisitionType::StockHistory => "STOCK_HISTORY",
+    }
+}
+
+fn parse_requisition_type(value: &str) -> Option<RequisitionType> {
+    match value {
+        "REQUEST" => Some(RequisitionType::Request),
+        "RESPONSE" => Some(RequisitionType::Response),
+        "IMPREST" => Some(RequisitionType::Imprest),
+        "STOCK_HISTORY" => Some(RequisitionType::StockHistory),
+        _ => None,
+    }
+}
+
+impl RequisitionQuery {
+    fn to_domain(self) -> Result<RequisitionFilter, ApiError> {
+        let RequisitionQuery {
+            id,
+            requisition_number,
+            other_party_id,
+            other_party_name,
+            r#type,
+            status,
+            their_reference,
+            created_datetime_from,
+            created_datetime_to,
+        } = self;
+
+        Ok(RequisitionFilter {
+            id: equal_any(id),
+            requisition_number: equal_any_number("requisitionNumber", requisition_number)?,
+            name_id: equal_any(other_party_id),
+            name: like(other_party_name),
+            r#type: equal_any_with("type", r#type, parse_requisition_type)?,
+            status: equal_any_enum("status", status)?,
+            their_reference: like(their_reference),
+            created_datetime: datetime_range(created_datetime_from, created_datetime_to),
+            ..Default::default()
+        })
+    }
+}
+
+impl RequisitionDto {
+    fn from_domain(
+        requisition: Requisition,
+        lines: Option<Vec<RequisitionLine>>,
+    ) -> RequisitionDto {
+        let Requisition {
+            requisition_row,
+            name_row,
+            ..
+        } = requisition;
+
+        RequisitionDto {
+            r#type: requisition_type(&requisition_row.r#type),
+            id: requisition_row.id,
+            requisition_number: requisition_row.requisition_number,
+            status: requisition_row.status,
+            other_party_id: name_row.id,
+            other_party_name: name_row.name,
+            their_reference: requisition_row.their_reference,
+            comment: requisition_row.comment,
+            max_months_of_stock: requisition_row.max_months_of_stock,
+            min_months_of_stock: requisition_row.min_months_of_stock,
+            created_datetime: requisition_row.created_datetime,
+            sent_datetime: requisition_row.sent_datetime,
+            finalised_datetime: requisition_row.finalised_datetime,
+            expected_delivery_date: requisition_row.expected_delivery_date,
+            lines: lines.map(|lines| {
+                lines
+                    .into_iter()
+                    .map(RequisitionLineDto::from_domain)
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl RequisitionLineDto {
+    fn from_domain(line: RequisitionLine) -> RequisitionLineDto {
+        let RequisitionLine {
+            requisition_line_row,
+            item_row,
+            ..
+        } = line;
+
+        RequisitionLineDto {
+            id: requisition_line_row.id,
+            item_id: item_row.id,
+            item_name: requisition_line_row.item_name,
+            requested_quantity: requisition_line_row.requested_quantity,
+            suggested_quantity: requisition_line_row.suggested_quantity,
+            supply_quantity: requisition_line_row.supply_quantity,
+            approved_quantity: requisition_line_row.approved_quantity,
+            available_stock_on_hand: requisition_line_row.available_stock_on_hand,
+            average_monthly_consumption: requisition_line_row.average_monthly_consumption,
+            comment: requisition_line_row.comment,
+        }
+    }
+}
+
+fn requisition_with_lines(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<RequisitionDto, ApiError> {
+    let requisition = service_provider
+        .requisition_service
+        .get_requisition(ctx, Some(ctx.store_id.as_str()), id)?
+        .ok_or_else(|| ApiError::NotFound(format!("Requisition {id} not found")))?;
+    let lines = service_provider
+        .requisition_line_service
+        .get_requisition_lines(
+            ctx,
+            Some(RequisitionLineFilter {
+                requisition_id: Some(EqualFilter::equal_to(id.to_string())),
+                ..Default::default()
+            }),
+        )?;
+
+    Ok(RequisitionDto::from_domain(requisition, Some(lines.rows)))
+}
+
+/// Requisitions of the store by created datetime, without lines
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/requisitions",
+    params(("store_id" = String, Path), PageQuery, RequisitionQuery),
+    responses(
+        (status = 200, description = "Page of requisitions", body = Page<RequisitionDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_requisitions(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<RequisitionQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryRequisition,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain()?;
+            let result = service_provider
+                .requisition_service
+                .get_requisitions_after(
+                    ctx,
+                    &store_id,
+                    page_request.after.clone(),
+                    page_request.fetch_limit(),
+                    Some(filter),
+                )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |requisition| Keyset {
+                    sort_key: requisition.requisition_row.created_datetime,
+                    id: requisition.requisition_row.id.clone(),
+                },
+                |requisition| RequisitionDto::from_domain(requisition, None),
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
+
+/// Requisition with its lines
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/requisitions/{id}",
+    params(("store_id" = String, Path), ("id" = String, Path)),
+    responses(
+        (status = 200, description = "Requisition with its lines", body = RequisitionDto),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 404, description = "No requisition with the id in the store", body = ErrorBody),
+    )
+)]
+pub async fn get_requisition(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (store_id, id) = path.into_inner();
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryRequisition,
+        &store_id,
+        |ctx| {
+            let requisition = requisition_with_lines(&service_provider, ctx, &id)?;
+            Ok(json_response(StatusCode::OK, &requisition))
+        },
+    )
+}
+
+/// Creates a request requisition with its lines
+#[utoipa::path(
+    post,
+    path = "/api/v1/stores/{store_id}/requisitions",
+    params(
+        ("store_id" = String, Path),
+        ("Idempotency-Key" = Option<String>, Header, description = "Retries with the same key and body replay the first response for 24 hours"),
+    ),
+    request_body = CreateRequisition,
+    responses(
+        (status = 201, description = "Created, or the replayed response of a retry", body = RequisitionDto),
+        (status = 400, description = "Invalid body", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+        (status = 409, description = "Idempotency key reused with another body", body = ErrorBody),
+        (status = 422, description = "Rejected by the service", body = ErrorBody),
+    )
+)]
+pub async fn post_requisition(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    body: web::Bytes,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::MutateRequisition,
+        &store_id,
+        |ctx| {
+            create_response(&request, ctx, &body, |ctx, input: CreateRequisition| {
+                create_requisition(&service_provider, ctx, input)
+            })
+        },
+    )
+}
+
+fn create_requisition(
+    service_provider: &ServiceProvider,
+    ctx: &ServiceContext,
+    input: CreateRequisition,
+) -> Result<RequisitionDto, ApiError> {
+    let CreateRequisition {
+        id,
+        other_party_id,
+        their_reference,
+        comment,
+        max_months_of_stock,
+        min_months_of_stock,
+        expected_delivery_date,
+        lines,
+    } = input;
+    let id = id.unwrap_or_else(uuid);
+
+    service_provider
+        .requisition_service
+        .insert_request_requisition(
+            ctx,
+            InsertRequestRequisition {
+                id: id.clone(),
+                other_party_id,
+                colour: None,
+                their_reference,
+                comment,
+                max_months_of_stock,
+                min_months_of_stock,
+                expected_delivery_date,
+            },
+        )
+        .map_err(|error| match error {
+            InsertRequestRequisitionError::DatabaseError(error) => error.into(),
+            error => ApiError::unprocessable(error),
+        })?;
+
+    for line in lines {
+        let line_id = line.id.unwrap_or_else(uuid);
+        service_provider
+            .requisition_line_service
+            .insert_request_requisition_line(
+                ctx,
+                InsertRequestRequisitionLine {
+                    id: line_id.clone(),
+                    item_id: line.item_id,
+                    requisition_id: id.clone(),
+                },
+            )
+            .map_err(|error| match error {
+                InsertRequestRequisitionLineError::DatabaseError(error) => error.into(),
+                error => ApiError::unprocessable(error),
+            })?;
+
+        if line.requested_quantity.is_none() && line.comment.is_none() {
+            continue;
+        }
+        service_provider
+            .requisition_line_service
+            .update_request_requisition_line(
+                ctx,
+                UpdateRequestRequisitionLine {
+                    id: line_id,
+                    requested_quantity: line.requested_quantity,
+                    comment: line.comment,
+                    option_id: None,
+                },
+            )
+            .map_err(|error| match error {
+                UpdateRequestRequisitionLineError::DatabaseError(error) => error.into(),
+                error => ApiError::unprocessable(error),
+            })?;
+    }
+
+    requisition_with_lines(service_provider, ctx, &id)
+}
@@ -0,0 +1,160 @@
+use actix_web::{
+    http::StatusCode,
+    web::{self, Data},
+    HttpRequest, HttpResponse,
+};
+use chrono::NaiveDate;
+use repository::{EqualFilter, Keyset, StockLine, StockLineFilter};
+use serde::{Deserialize, Serialize};
+use service::{auth::Resource, auth_data::AuthData, service_provider::ServiceProvider};
+use utoipa::{IntoParams, ToSchema};
+
+use super::{
+    filter::{date_range, equal_any, like},
+    json_response,
+    pagination::{Page, PageQuery},
+    respond, ErrorBody,
+};
+
+#[derive(Deserialize, Debug, Default, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct StockQuery {
+    /// Comma separated, matches any
+    pub id: Option<String>,
+    /// Comma separated, matches any
+    pub item_id: Option<String>,
+    /// Matches anywhere in the text
+    pub item_code_or_name: Option<String>,
+    /// Comma separated, matches any
+    pub location_id: Option<String>,
+    pub is_available: Option<bool>,
+    /// Defaults to true, stock lines without packs in the store are not stock on hand
+    pub has_packs_in_store: Option<bool>,
+    pub expiry_date_from: Option<NaiveDate>,
+    pub expiry_date_to: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = StockLine)]
+pub struct StockLineDto {
+    pub id: String,
+    pub item_id: String,
+    pub item_code: String,
+    pub item_name: String,
+    pub batch: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub pack_size: f64,
+    pub available_number_of_packs: f64,
+    pub total_number_of_packs: f64,
+    pub cost_price_per_pack: f64,
+    pub sell_price_per_pack: f64,
+    pub on_hold: bool,
+    pub location_id: Option<String>,
+}
+
+impl StockQuery {
+    fn to_domain(self, store_id: &str) -> StockLineFilter {
+        let StockQuery {
+            id,
+            item_id,
+            item_code_or_name,
+            location_id,
+            is_available,
+            has_packs_in_store,
+            expiry_date_from,
+            expiry_date_to,
+        } = self;
+
+        StockLineFilter {
+            id: equal_any(id),
+            item_id: equal_any(item_id),
+            item_code_or_name: like(item_code_or_name),
+            location_id: equal_any(location_id),
+            is_available,
+            has_packs_in_store: Some(has_packs_in_store.unwrap_or(true)),
+            expiry_date: date_range(expiry_date_from, expiry_date_to),
+            store_id: Some(EqualFilter::equal_to(store_id.to_string())),
+            ..Default::default()
+        }
+    }
+}
+
+impl StockLineDto {
+    fn from_domain(stock_line: StockLine) -> StockLineDto {
+        let StockLine {
+            stock_line_row,
+            item_row,
+            ..
+        } = stock_line;
+
+        StockLineDto {
+            id: stock_line_row.id,
+            item_id: item_row.id,
+            item_code: item_row.code,
+            item_name: item_row.name,
+            batch: stock_line_row.batch,
+            expiry_date: stock_line_row.expiry_date,
+            pack_size: stock_line_row.pack_size,
+            available_number_of_packs: stock_line_row.available_number_of_packs,
+            total_number_of_packs: stock_line_row.total_number_of_packs,
+            cost_price_per_pack: stock_line_row.cost_price_per_pack,
+            sell_price_per_pack: stock_line_row.sell_price_per_pack,
+            on_hold: stock_line_row.on_hold,
+            location_id: stock_line_row.location_id,
+        }
+    }
+}
+
+/// Stock lines of the store by item name
+#[utoipa::path(
+    get,
+    path = "/api/v1/stores/{store_id}/stock",
+    params(("store_id" = String, Path), PageQuery, StockQuery),
+    responses(
+        (status = 200, description = "Page of stock lines", body = Page<StockLineDto>),
+        (status = 400, description = "Invalid parameters", body = ErrorBody),
+        (status = 401, description = "Missing or invalid token", body = ErrorBody),
+        (status = 403, description = "No access to the store", body = ErrorBody),
+    )
+)]
+pub async fn get_stock(
+    request: HttpRequest,
+    service_provider: Data<ServiceProvider>,
+    auth_data: Data<AuthData>,
+    store_id: web::Path<String>,
+    page: web::Query<PageQuery>,
+    query: web::Query<StockQuery>,
+) -> HttpResponse {
+    respond(
+        &request,
+        &service_provider,
+        &auth_data,
+        Resource::QueryStockLine,
+        &store_id,
+        |ctx| {
+            let page_request = page.to_domain()?;
+            let filter = query.into_inner().to_domain(&store_id);
+            let result = service_provider.stock_line_service.get_stock_lines_after(
+                ctx,
+                &store_id,
+                page_request.after.clone(),
+                page_request.fetch_limit(),
+                Some(filter),
+            )?;
+
+            let page = Page::new(
+                result.rows,
+                &page_request,
+                result.count,
+                |stock_line| Keyset {
+                    sort_key: stock_line.item_row.name.clone(),
+                    id: stock_line.stock_line_row.id.clone(),
+                },
+                StockLineDto::from_domain,
+            );
+            Ok(json_response(StatusCode::OK, &page))
+        },
+    )
+}
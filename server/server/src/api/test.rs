@@ -0,0 +1,100 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, read_body_json, TestRequest},
+    web::Data,
+    App,
+};
+use repository::{mock::MockDataInserts, test_db::setup_all, StorageConnectionManager};
+use serde_json::Value;
+use service::{auth_data::AuthData, service_provider::ServiceProvider, token_bucket::TokenBucket};
+
+use super::config_api;
+
+fn auth_data(debug_no_access_control: bool) -> Data<AuthData> {
+    Data::new(AuthData {
+        auth_token_secret: "n/a".to_string(),
+        token_bucket: Arc::new(RwLock::new(TokenBucket::new())),
+        no_ssl: true,
+        debug_no_access_control,
+    })
+}
+
+async fn get(
+    connection_manager: &StorageConnectionManager,
+    debug_no_access_control: bool,
+    uri: &str,
+) -> (StatusCode, Value) {
+    let app = init_service(
+        App::new()
+            .app_data(Data::new(ServiceProvider::new(connection_manager.clone())))
+            .app_data(auth_data(debug_no_access_control))
+            .configure(config_api),
+    )
+    .await;
+    let response = call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+    let status = response.status();
+
+    (status, read_body_json(response).await)
+}
+
+#[actix_rt::test]
+async fn test_api_item_pages() {
+    let (_, _, connection_manager, _) =
+        setup_all("test_api_item_pages", MockDataInserts::all()).await;
+
+    // Following next cursors visits every item once
+    let mut ids: Vec<String> = Vec::new();
+    let mut cursor: Option<String> = None;
+    let total_count = loop {
+        let uri = match &cursor {
+            Some(cursor) => format!("/api/v1/stores/store_a/items?limit=2&cursor={cursor}"),
+            None => "/api/v1/stores/store_a/items?limit=2".to_string(),
+        };
+        let (status, page) = get(&connection_manager, true, &uri).await;
+        assert_eq!(status, StatusCode::OK, "{page}");
+
+        let data = page["data"].as_array().unwrap();
+        assert!(data.len() <= 2);
+        for item in data {
+            ids.push(item["id"].as_str().unwrap().to_string());
+        }
+
+        match page["nextCursor"].as_str() {
+            Some(next_cursor) => cursor = Some(next_cursor.to_string()),
+            None => break page["totalCount"].as_u64().unwrap() as usize,
+        }
+    };
+
+    assert!(total_count > 2);
+    assert_eq!(ids.len(), total_count);
+    assert_eq!(ids.iter().collect::<HashSet<_>>().len(), total_count);
+}
+
+#[actix_rt::test]
+async fn test_api_errors() {
+    let (_, _, connection_manager, _) = setup_all("test_api_errors", MockDataInserts::all()).await;
+
+    let (status, body) = get(
+        &connection_manager,
+        true,
+        "/api/v1/stores/store_a/items?cursor=invalid",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "BAD_REQUEST");
+
+    // Without a token
+    let (status, body) = get(&connection_manager, false, "/api/v1/stores/store_a/items").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(body["error"], "UNAUTHORIZED");
+
+    // Document is public
+    let (status, body) = get(&connection_manager, false, "/api/v1/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["info"]["title"], "Open mSupply REST API");
+}
@@ -14,6 +14,7 @@ use service::{
     settings::Settings,
     sync::{
         api_v6::{
+            ActivityLogChainAnchorsRequestV6, ActivityLogChainAnchorsResponseV6,
             SiteStatusRequestV6, SiteStatusResponseV6, SyncDownloadFileRequestV6,
             SyncParsedErrorV6, SyncPatientPullRequestV6, SyncPullRequestV6, SyncPullResponseV6,
             SyncPushRequestV6, SyncPushResponseV6, SyncUploadFileRequestV6,
@@ -29,6 +30,7 @@ pub fn sync_on_central() -> impl HttpServiceFactory {
         .service(patient_pull)
         .service(push)
         .service(site_status)
+        .service(activity_log_chain_anchors)
         .service(download_file)
         .service(upload_file)
 }
@@ -88,6 +90,24 @@ async fn site_status(
     Ok(web::Json(response))
 }
 
+#[post("/activity_log_chain_anchors")]
+async fn activity_log_chain_anchors(
+    request: Json<ActivityLogChainAnchorsRequestV6>,
+    service_provider: Data<ServiceProvider>,
+) -> actix_web::Result<impl Responder> {
+    let response = match sync_on_central::get_activity_log_chain_anchors(
+        &service_provider,
+        request.into_inner(),
+    )
+    .await
+    {
+        Ok(anchors) => ActivityLogChainAnchorsResponseV6::Data(anchors),
+        Err(error) => ActivityLogChainAnchorsResponseV6::Error(error),
+    };
+
+    Ok(web::Json(response))
+}
+
 #[derive(Debug)]
 struct ToResponseError(SyncParsedErrorV6);
 impl Display for ToResponseError {
@@ -4,6 +4,7 @@
 extern crate machine_uid;
 
 use crate::{
+    api::config_api,
     central::config_central,
     certs::Certificates,
     cold_chain::config_cold_chain,
@@ -39,6 +40,7 @@ use service::{
         add_migration_results_to_system_log, system_log, system_log_entry, SystemLogMessage,
     },
     auth_data::AuthData,
+    backup::apply_restore_marker,
     boajs::context::BoaJsContext,
     ledger_fix::ledger_fix_driver::LedgerFixDriver,
     plugin::validation::ValidatedPluginBucket,
@@ -59,6 +61,7 @@ use actix_web::{web, web::Data, App, HttpServer};
 use std::sync::{Arc, Mutex, RwLock};
 use util::format_error;
 
+mod api;
 mod authentication;
 pub mod certs;
 pub mod cold_chain;
@@ -123,7 +126,8 @@ pub async fn start_server(
     std::fs::create_dir_all(base_dir)?;
 
     // INITIALISE DATABASE CONNECTION
-    let mut connection_manager = get_storage_connection_manager(&settings.database);
+    let mut connection_manager =
+        get_storage_connection_manager(&settings.database).map_err(std::io::Error::other)?;
     let connection = connection_manager.connection().unwrap();
 
     // INITIALISE CONTEXT
@@ -139,6 +143,9 @@ pub async fn start_server(
             repository::TransactionNotification::ChangelogInsert => {
                 commit_trigger.send(SubscriptionTrigger::PushQueueChanged);
             }
+            repository::TransactionNotification::StoreRecordChanged => {
+                commit_trigger.send(SubscriptionTrigger::StoreRecordsChanged);
+            }
         }
     }));
     let (file_sync_trigger, file_sync_driver) = FileSyncDriver::init(&settings);
@@ -310,6 +317,7 @@ pub async fn start_server(
             .configure(config_print)
             .configure(config_custom_translations)
             .configure(config_upload)
+            .configure(config_api)
             // Needs to be last to capture all unmatches routes
             .configure(config_serve_frontend)
     })
@@ -346,6 +354,14 @@ pub async fn start_server(
     add_migration_results_to_system_log(&connection, messages).unwrap();
     info!("Run DB migrations...done");
 
+    match apply_restore_marker(&connection, &settings) {
+        Ok(Some(backup_name)) => {
+            info!("Database was restored from backup {backup_name}, will re-sync on next sync")
+        }
+        Ok(None) => {}
+        Err(e) => log::error!("Failed to apply restore marker: {}", format_error(&e)),
+    }
+
     // Persist token secret now that the key_value_store table is guaranteed to exist
     save_token_secret(&connection, &token_secret_copy);
 
@@ -452,6 +468,7 @@ pub async fn start_server(
     let schedule_plugin_task = schedule_plugin::spawn();
     let scheduled_task_handle = spawn_scheduled_task_runner(
         service_provider.clone().into_inner(),
+        settings.clone(),
         settings.mail.clone().map(|m| m.interval).unwrap_or(60),
     );
 
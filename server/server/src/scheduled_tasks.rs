@@ -1,27 +1,39 @@
+use service::audit_log::prune_audit_log;
+use service::backup::run_scheduled_backup;
 use service::service_provider::ServiceProvider;
+use service::settings::Settings;
 use service::sync::CentralServerConfig;
+use service::webhook::dispatch_webhooks;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
 
 pub fn spawn_scheduled_task_runner(
     service_provider: Arc<ServiceProvider>,
+    settings: Settings,
     interval_secs: u64,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
-        scheduled_task_runner(service_provider, interval_secs).await;
+        scheduled_task_runner(service_provider, settings, interval_secs).await;
     })
 }
 
-async fn scheduled_task_runner(service_provider: Arc<ServiceProvider>, interval_secs: u64) {
+async fn scheduled_task_runner(
+    service_provider: Arc<ServiceProvider>,
+    settings: Settings,
+    interval_secs: u64,
+) {
     let mut interval = actix_web::rt::time::interval(Duration::from_secs(interval_secs));
     let service_context = service_provider.basic_context().unwrap();
+    let mut webhook_dispatch: Option<JoinHandle<()>> = None;
+    let mut scheduled_backup: Option<JoinHandle<()>> = None;
 
     loop {
         interval.tick().await;
         log::debug!("Processing Scheduled Tasks");
         if CentralServerConfig::is_central_server() {
-            // Email sending is only supported on the central server
+            // Email sending is only supported on the central server, emails queued on remote sites
+            // are pushed to central by sync
             let send_emails = service_provider
                 .email_service
                 .send_queued_emails(&service_context);
@@ -34,6 +46,83 @@ async fn scheduled_task_runner(service_provider: Arc<ServiceProvider>, interval_
                 Err(error) => log::error!("Error sending queued emails: {error:?}"),
             };
         }
+
+        // Slow webhook endpoints shouldn't hold up other tasks, a dispatch that is still running
+        // from an earlier tick is left to finish
+        if webhook_dispatch
+            .as_ref()
+            .is_none_or(|dispatch| dispatch.is_finished())
+        {
+            webhook_dispatch = Some(spawn_webhook_dispatch(service_provider.clone()));
+        }
+
+        match prune_audit_log(&service_context) {
+            Ok(num) => {
+                if num > 0 {
+                    log::info!("Pruned {num} audit log entries past retention");
+                }
+            }
+            Err(error) => log::error!("Error pruning audit log: {error:?}"),
+        };
+
+        // A backup can take longer than the interval, a run still in progress is left to finish
+        // rather than starting another one
+        if scheduled_backup
+            .as_ref()
+            .is_none_or(|backup| backup.is_finished())
+        {
+            scheduled_backup = Some(spawn_scheduled_backup(
+                service_provider.clone(),
+                settings.clone(),
+            ));
+        }
     }
 }
 
+fn spawn_webhook_dispatch(service_provider: Arc<ServiceProvider>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let service_context = match service_provider.basic_context() {
+            Ok(service_context) => service_context,
+            Err(error) => {
+                log::error!("Error dispatching webhooks: {error:?}");
+                return;
+            }
+        };
+        match dispatch_webhooks(&service_context).await {
+            Ok(result) => {
+                if result.delivered > 0 || result.failed > 0 {
+                    log::info!(
+                        "Delivered {} webhooks, {} failed",
+                        result.delivered,
+                        result.failed
+                    );
+                }
+            }
+            Err(error) => log::error!("Error dispatching webhooks: {error:?}"),
+        };
+    })
+}
+
+/// Backup copies files and the database, it runs on a blocking thread off the async runtime
+fn spawn_scheduled_backup(
+    service_provider: Arc<ServiceProvider>,
+    settings: Settings,
+) -> JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        let backup = service_provider
+            .connection_manager
+            .connection()
+            .and_then(|connection| run_scheduled_backup(&connection, &settings));
+        match backup {
+            Ok(Some(status)) => match status.error {
+                None => log::info!(
+                    "Scheduled backup completed: {}",
+                    status.last_backup_name.unwrap_or_default()
+                ),
+                Some(error) => log::error!("Scheduled backup failed: {error}"),
+            },
+            Ok(None) => {}
+            Err(error) => log::error!("Error running scheduled backup: {error:?}"),
+        };
+    })
+}
@@ -0,0 +1,429 @@
+use chrono::Utc;
+use repository::{
+    activity_log_chain_hash, ActivityLogChainAnchorRow, ActivityLogChainAnchorRowRepository,
+    ActivityLogHashRow, ActivityLogHashRowRepository, RepositoryError, StorageConnection,
+};
+use thiserror::Error;
+use util::uuid::uuid;
+
+use crate::{
+    service_provider::ServiceProvider,
+    settings_service::SettingsServiceTrait,
+    sync::{
+        api::{SyncApiV5, SyncApiV5CreatingError},
+        api_v6::{SyncApiErrorV6, SyncApiV6, SyncApiV6CreatingError},
+        settings::{SYNC_V5_VERSION, SYNC_V6_VERSION},
+        CentralServerConfig,
+    },
+};
+
+const VERIFY_BATCH_SIZE: i64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityLogChainBreakType {
+    /// Link sequence doesn't follow the previous link, links were removed
+    SequenceGap,
+    /// Link doesn't point to the previous link's hash, links were removed or rewritten
+    PreviousHashMismatch,
+    /// Activity log of the link was deleted
+    LogMissing,
+    /// Activity log doesn't match the link's hash, the log or the link was edited
+    LogAltered,
+    /// Activity log in the store that is not in the chain, inserted directly in the database
+    UnchainedLog,
+    /// Link at an anchored sequence has a different hash, the chain was rewritten after anchoring
+    AnchorMismatch,
+    /// Chain ends before an anchored sequence, links were removed from the end
+    ChainTruncated,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityLogChainBreak {
+    pub r#type: ActivityLogChainBreakType,
+    pub sequence: Option<i64>,
+    pub activity_log_id: Option<String>,
+    pub anchor_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ActivityLogChainVerification {
+    pub store_id: String,
+    pub links_checked: i64,
+    pub anchors_checked: i64,
+    /// Last link of the chain
+    pub head: Option<ActivityLogHashRow>,
+    /// In chain order, empty when the chain is intact
+    pub breaks: Vec<ActivityLogChainBreak>,
+}
+
+#[derive(Error, Debug)]
+pub enum VerifyWithCentralError {
+    #[error("Sync is not configured, central anchors can't be fetched")]
+    SyncNotConfigured,
+    #[error(transparent)]
+    SyncApiV5CreatingError(#[from] SyncApiV5CreatingError),
+    #[error(transparent)]
+    SyncApiV6CreatingError(#[from] SyncApiV6CreatingError),
+    #[error("Could not fetch anchors from the central server")]
+    CentralServerError(#[from] SyncApiErrorV6),
+    #[error("Database error")]
+    DatabaseError(#[from] RepositoryError),
+}
+
+impl ActivityLogChainVerification {
+    pub fn is_intact(&self) -> bool {
+        self.breaks.is_empty()
+    }
+}
+
+impl ActivityLogChainBreak {
+    fn link(r#type: ActivityLogChainBreakType, link: &ActivityLogHashRow) -> Self {
+        ActivityLogChainBreak {
+            r#type,
+            sequence: Some(link.sequence),
+            activity_log_id: Some(link.activity_log_id.clone()),
+            anchor_id: None,
+        }
+    }
+
+    fn anchor(r#type: ActivityLogChainBreakType, anchor: &ActivityLogChainAnchorRow) -> Self {
+        ActivityLogChainBreak {
+            r#type,
+            sequence: Some(anchor.sequence),
+            activity_log_id: None,
+            anchor_id: Some(anchor.id.clone()),
+        }
+    }
+}
+
+/// Recomputes the store's activity log hash chain and checks it against the anchors stored on
+/// this site. These can be rewritten along with the chain, see
+/// [verify_activity_log_chain_with_central]
+pub fn verify_activity_log_chain(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<ActivityLogChainVerification, RepositoryError> {
+    let anchors =
+        ActivityLogChainAnchorRowRepository::new(connection).find_many_by_store_id(store_id)?;
+    verify_activity_log_chain_against(connection, store_id, &anchors)
+}
+
+/// Verifies the store's chain against the anchors held by the central server, which is where
+/// anchors are pushed. On the central server its own anchors are used
+pub async fn verify_activity_log_chain_with_central(
+    service_provider: &ServiceProvider,
+    store_id: &str,
+) -> Result<ActivityLogChainVerification, VerifyWithCentralError> {
+    use VerifyWithCentralError as Error;
+    let ctx = service_provider.basic_context()?;
+
+    let central_url = match CentralServerConfig::get() {
+        CentralServerConfig::CentralServerUrl(url) => url,
+        CentralServerConfig::IsCentralServer | CentralServerConfig::ForcedCentralServer => {
+            return Ok(verify_activity_log_chain(&ctx.connection, store_id)?)
+        }
+        CentralServerConfig::NotConfigured => return Err(Error::SyncNotConfigured),
+    };
+    let sync_settings = service_provider
+        .settings
+        .sync_settings(&ctx)?
+        .ok_or(Error::SyncNotConfigured)?;
+    let sync_v5_settings =
+        SyncApiV5::new_settings(&sync_settings, service_provider, SYNC_V5_VERSION)?;
+    let anchors = SyncApiV6::new(&central_url, &sync_v5_settings, SYNC_V6_VERSION)?
+        .get_activity_log_chain_anchors(store_id)
+        .await?;
+
+    Ok(verify_activity_log_chain_against(
+        &ctx.connection,
+        store_id,
+        &anchors,
+    )?)
+}
+
+fn verify_activity_log_chain_against(
+    connection: &StorageConnection,
+    store_id: &str,
+    anchors: &[ActivityLogChainAnchorRow],
+) -> Result<ActivityLogChainVerification, RepositoryError> {
+    use ActivityLogChainBreakType as Type;
+    let repo = ActivityLogHashRowRepository::new(connection);
+
+    let mut result = ActivityLogChainVerification {
+        store_id: store_id.to_string(),
+        ..Default::default()
+    };
+    let mut first_datetime = None;
+
+    loop {
+        let after_sequence = result.head.as_ref().map(|head| head.sequence).unwrap_or(0);
+        let links = repo.find_links(store_id, after_sequence, VERIFY_BATCH_SIZE)?;
+        if links.is_empty() {
+            break;
+        }
+
+        for (link, log) in links {
+            let previous = result.head.as_ref();
+            if link.sequence != previous.map(|head| head.sequence).unwrap_or(0) + 1 {
+                result
+                    .breaks
+                    .push(ActivityLogChainBreak::link(Type::SequenceGap, &link));
+            }
+            if link.previous_hash.as_ref() != previous.map(|head| &head.hash) {
+                result.breaks.push(ActivityLogChainBreak::link(
+                    Type::PreviousHashMismatch,
+                    &link,
+                ));
+            }
+
+            match log {
+                None => result
+                    .breaks
+                    .push(ActivityLogChainBreak::link(Type::LogMissing, &link)),
+                Some(log) => {
+                    first_datetime.get_or_insert(log.datetime);
+                    let hash =
+                        activity_log_chain_hash(&log, link.sequence, link.previous_hash.as_deref());
+                    if hash != link.hash {
+                        result
+                            .breaks
+                            .push(ActivityLogChainBreak::link(Type::LogAltered, &link));
+                    }
+                }
+            }
+
+            result.links_checked += 1;
+            result.head = Some(link);
+        }
+    }
+
+    // Logs from before the chain was introduced are not chained
+    if let Some(first_datetime) = first_datetime {
+        for log_id in repo.find_unchained_log_ids(store_id, first_datetime)? {
+            result.breaks.push(ActivityLogChainBreak {
+                r#type: Type::UnchainedLog,
+                sequence: None,
+                activity_log_id: Some(log_id),
+                anchor_id: None,
+            });
+        }
+    }
+
+    // Anchors past the end of the chain, or any anchor when the whole chain was removed
+    let head_sequence = result.head.as_ref().map(|head| head.sequence).unwrap_or(0);
+    for anchor in anchors {
+        result.anchors_checked += 1;
+        if anchor.sequence > head_sequence {
+            result
+                .breaks
+                .push(ActivityLogChainBreak::anchor(Type::ChainTruncated, anchor));
+            continue;
+        }
+        let link = repo.find_one_by_sequence(store_id, anchor.sequence)?;
+        if link.map(|link| link.hash) != Some(anchor.hash.clone()) {
+            result
+                .breaks
+                .push(ActivityLogChainBreak::anchor(Type::AnchorMismatch, anchor));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Verifies the chains of all stores with chained activity logs
+pub fn verify_activity_log_chains(
+    connection: &StorageConnection,
+) -> Result<Vec<ActivityLogChainVerification>, RepositoryError> {
+    ActivityLogHashRowRepository::new(connection)
+        .find_store_ids()?
+        .iter()
+        .map(|store_id| verify_activity_log_chain(connection, store_id))
+        .collect()
+}
+
+/// Anchors the head of every chain that moved since its last anchor. Called before pushing to the
+/// open mSupply central server, so anchors reach central with the logs they cover
+pub fn anchor_activity_log_chains(
+    connection: &StorageConnection,
+) -> Result<Vec<ActivityLogChainAnchorRow>, RepositoryError> {
+    let hash_repo = ActivityLogHashRowRepository::new(connection);
+    let anchor_repo = ActivityLogChainAnchorRowRepository::new(connection);
+    let mut anchors = Vec::new();
+
+    for store_id in hash_repo.find_store_ids()? {
+        let Some(head) = hash_repo.find_head(&store_id)? else {
+            continue;
+        };
+        let anchored_sequence = anchor_repo
+            .find_latest(&store_id)?
+            .map(|anchor| anchor.sequence)
+            .unwrap_or(0);
+        if head.sequence <= anchored_sequence {
+            continue;
+        }
+
+        let anchor = ActivityLogChainAnchorRow {
+            id: uuid(),
+            store_id,
+            sequence: head.sequence,
+            hash: head.hash,
+            created_datetime: Utc::now().naive_utc(),
+        };
+        anchor_repo.upsert_one(&anchor)?;
+        anchors.push(anchor);
+    }
+
+    Ok(anchors)
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_store_a, mock_store_b, MockDataInserts},
+        test_db::setup_all,
+        ActivityLogRow, ActivityLogRowRepository, ActivityLogType, Upsert,
+    };
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_activity_log_chain() {
+        let (_, connection, connection_manager, _) =
+            setup_all("test_activity_log_chain", MockDataInserts::none().stores()).await;
+        let log_repo = ActivityLogRowRepository::new(&connection);
+        let store_id = mock_store_a().id;
+
+        for index in 1..=4 {
+            log_repo
+                .insert_one(&ActivityLogRow {
+                    id: format!("log_{index}"),
+                    r#type: ActivityLogType::InventoryAdjustment,
+                    store_id: Some(store_id.clone()),
+                    record_id: Some(format!("record_{index}")),
+                    datetime: Utc::now().naive_utc(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        // Not chained without a store
+        log_repo
+            .insert_one(&ActivityLogRow {
+                id: "no_store".to_string(),
+                datetime: Utc::now().naive_utc(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let result = verify_activity_log_chain(&connection, &store_id).unwrap();
+        assert_eq!(result.links_checked, 4);
+        assert_eq!(result.head.as_ref().map(|head| head.sequence), Some(4));
+        assert!(result.is_intact());
+
+        let anchors = anchor_activity_log_chains(&connection).unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].sequence, 4);
+        // Nothing new to anchor
+        assert!(anchor_activity_log_chains(&connection).unwrap().is_empty());
+
+        // Anchors held by central are compared the same way as local ones
+        let central_anchors = vec![ActivityLogChainAnchorRow {
+            id: "central".to_string(),
+            store_id: store_id.clone(),
+            sequence: 2,
+            hash: "before rewrite".to_string(),
+            created_datetime: Utc::now().naive_utc(),
+        }];
+        let result =
+            verify_activity_log_chain_against(&connection, &store_id, &central_anchors).unwrap();
+        assert_eq!(
+            result.breaks,
+            vec![ActivityLogChainBreak {
+                r#type: ActivityLogChainBreakType::AnchorMismatch,
+                sequence: Some(2),
+                activity_log_id: None,
+                anchor_id: Some("central".to_string()),
+            }]
+        );
+
+        // Synced logs are chained on the site that logged them
+        ActivityLogRow {
+            id: "synced".to_string(),
+            r#type: ActivityLogType::InventoryAdjustment,
+            store_id: Some(mock_store_b().id),
+            datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        }
+        .upsert(&connection)
+        .unwrap();
+        let hash_repo = ActivityLogHashRowRepository::new(&connection);
+        assert_eq!(hash_repo.find_one_by_activity_log_id("synced"), Ok(None));
+        assert_eq!(hash_repo.find_store_ids(), Ok(vec![store_id.clone()]));
+
+        // Edited log
+        connection_manager
+            .execute("UPDATE activity_log SET record_id = 'edited' WHERE id = 'log_2'")
+            .unwrap();
+        let result = verify_activity_log_chain(&connection, &store_id).unwrap();
+        assert_eq!(
+            result.breaks,
+            vec![ActivityLogChainBreak {
+                r#type: ActivityLogChainBreakType::LogAltered,
+                sequence: Some(2),
+                activity_log_id: Some("log_2".to_string()),
+                anchor_id: None,
+            }]
+        );
+
+        // Deleted log and its link at the end of the chain
+        connection_manager
+            .execute("DELETE FROM activity_log WHERE id = 'log_4'")
+            .unwrap();
+        connection_manager
+            .execute("DELETE FROM activity_log_hash WHERE activity_log_id = 'log_4'")
+            .unwrap();
+        let result = verify_activity_log_chain(&connection, &store_id).unwrap();
+        let break_types: Vec<_> = result.breaks.iter().map(|b| b.r#type).collect();
+        assert_eq!(
+            break_types,
+            vec![
+                ActivityLogChainBreakType::LogAltered,
+                ActivityLogChainBreakType::ChainTruncated
+            ]
+        );
+
+        // Log inserted directly in the database
+        connection_manager
+            .execute(&format!(
+                "INSERT INTO activity_log (id, type, store_id, datetime) VALUES ('direct', 'INVENTORY_ADJUSTMENT', '{store_id}', '2100-01-01 00:00:00')"
+            ))
+            .unwrap();
+        let result = verify_activity_log_chain(&connection, &store_id).unwrap();
+        assert!(result.breaks.contains(&ActivityLogChainBreak {
+            r#type: ActivityLogChainBreakType::UnchainedLog,
+            sequence: None,
+            activity_log_id: Some("direct".to_string()),
+            anchor_id: None,
+        }));
+
+        // Whole chain removed, along with the local anchors
+        connection_manager
+            .execute("DELETE FROM activity_log_hash")
+            .unwrap();
+        connection_manager
+            .execute("DELETE FROM activity_log_chain_anchor")
+            .unwrap();
+        assert!(verify_activity_log_chain(&connection, &store_id)
+            .unwrap()
+            .is_intact());
+        let result = verify_activity_log_chain_against(&connection, &store_id, &anchors).unwrap();
+        assert_eq!(
+            result.breaks,
+            vec![ActivityLogChainBreak {
+                r#type: ActivityLogChainBreakType::ChainTruncated,
+                sequence: Some(4),
+                activity_log_id: None,
+                anchor_id: Some(anchors[0].id.clone()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{Duration, Utc};
+use repository::{ApiIdempotencyKeyRow, ApiIdempotencyKeyRowRepository, RepositoryError};
+use sha2::{Digest, Sha256};
+use util::uuid::uuid;
+
+use crate::service_provider::ServiceContext;
+
+/// Responses are replayed for retries within this many hours, older keys are removed
+const IDEMPOTENCY_KEY_EXPIRY_HOURS: i64 = 24;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IdempotentCreateError<E> {
+    /// The key was already used by the user for a different request
+    KeyReusedForDifferentRequest,
+    CreateError(E),
+    DatabaseError(RepositoryError),
+}
+
+impl<E> From<RepositoryError> for IdempotentCreateError<E> {
+    fn from(error: RepositoryError) -> Self {
+        IdempotentCreateError::DatabaseError(error)
+    }
+}
+
+/// Hash identifying a request, a key can only be replayed for the same request
+pub fn hash_request(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// Runs `create` unless the user already used `key` for the same request, in which case the stored
+/// response is returned instead. Returns the response and whether it was replayed.
+///
+/// The create and the stored response are committed in the same transaction, so a concurrent retry
+/// either replays the response or fails on the unique key without creating records twice.
+/// Failed creates are not stored and can be retried with the same key.
+pub fn create_with_idempotency_key<E, F>(
+    ctx: &ServiceContext,
+    key: Option<&str>,
+    request_hash: &str,
+    create: F,
+) -> Result<(IdempotentResponse, bool), IdempotentCreateError<E>>
+where
+    F: FnOnce() -> Result<IdempotentResponse, E>,
+{
+    let Some(key) = key else {
+        return create()
+            .map(|response| (response, false))
+            .map_err(IdempotentCreateError::CreateError);
+    };
+
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let repo = ApiIdempotencyKeyRowRepository::new(connection);
+            let now = Utc::now().naive_utc();
+            repo.delete_created_before(now - Duration::hours(IDEMPOTENCY_KEY_EXPIRY_HOURS))?;
+
+            if let Some(existing) = repo.find_one_by_user_and_key(&ctx.user_id, key)? {
+                if existing.request_hash != request_hash {
+                    return Err(IdempotentCreateError::KeyReusedForDifferentRequest);
+                }
+                let response = IdempotentResponse {
+                    status: existing.response_status as u16,
+                    body: existing.response_body,
+                };
+                return Ok((response, true));
+            }
+
+            let response = create().map_err(IdempotentCreateError::CreateError)?;
+            repo.insert_one(&ApiIdempotencyKeyRow {
+                id: uuid(),
+                user_id: ctx.user_id.clone(),
+                idempotency_key: key.to_string(),
+                request_hash: request_hash.to_string(),
+                response_status: response.status as i32,
+                response_body: response.body.clone(),
+                created_datetime: now,
+            })?;
+            Ok((response, false))
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{mock::MockDataInserts, test_db::setup_all};
+
+    use crate::service_provider::ServiceProvider;
+
+    use super::{
+        create_with_idempotency_key, hash_request, IdempotentCreateError, IdempotentResponse,
+    };
+
+    #[actix_rt::test]
+    async fn test_create_with_idempotency_key() {
+        let (_, _, connection_manager, _) =
+            setup_all("test_create_with_idempotency_key", MockDataInserts::none()).await;
+        let service_provider = ServiceProvider::new(connection_manager);
+        let ctx = service_provider
+            .context("store".to_string(), "user".to_string())
+            .unwrap();
+
+        let hash = hash_request("POST", "/invoices", b"{}");
+        let response = |body: &str| IdempotentResponse {
+            status: 201,
+            body: body.to_string(),
+        };
+        let mut created = 0;
+
+        // Without a key every request creates
+        let result = create_with_idempotency_key(&ctx, None, &hash, || {
+            created += 1;
+            Ok::<_, ()>(response("first"))
+        });
+        assert_eq!(result, Ok((response("first"), false)));
+
+        // Failed creates are not stored
+        let result = create_with_idempotency_key(&ctx, Some("key"), &hash, || Err("invalid"));
+        assert_eq!(result, Err(IdempotentCreateError::CreateError("invalid")));
+
+        let result = create_with_idempotency_key(&ctx, Some("key"), &hash, || {
+            created += 1;
+            Ok::<_, ()>(response("second"))
+        });
+        assert_eq!(result, Ok((response("second"), false)));
+
+        // Retry replays the response
+        let result = create_with_idempotency_key(&ctx, Some("key"), &hash, || {
+            created += 1;
+            Ok::<_, ()>(response("third"))
+        });
+        assert_eq!(result, Ok((response("second"), true)));
+        assert_eq!(created, 2);
+
+        // Same key for a different request
+        let other_hash = hash_request("POST", "/invoices", b"{\"id\":\"other\"}");
+        let result = create_with_idempotency_key(&ctx, Some("key"), &other_hash, || {
+            Ok::<_, ()>(response("other"))
+        });
+        assert_eq!(
+            result,
+            Err(IdempotentCreateError::KeyReusedForDifferentRequest)
+        );
+
+        // Keys are per user
+        let other_ctx = service_provider
+            .context("store".to_string(), "other_user".to_string())
+            .unwrap();
+        let result = create_with_idempotency_key(&other_ctx, Some("key"), &other_hash, || {
+            Ok::<_, ()>(response("other"))
+        });
+        assert_eq!(result, Ok((response("other"), false)));
+    }
+}
@@ -0,0 +1,241 @@
+use chrono::{NaiveDateTime, Utc};
+use repository::{
+    ActivityLogType, ApiKeyRow, ApiKeyRowRepository, RepositoryError, StoreRowRepository,
+    UserAccountRow, UserAccountRowRepository,
+};
+use util::uuid::uuid;
+
+use crate::{activity_log::activity_log_entry, auth::Resource, service_provider::ServiceContext};
+
+use super::{generate_api_key, hash_api_key, ApiKey, NewApiKey};
+
+#[derive(Debug, Clone, Default)]
+pub struct CreateApiKey {
+    pub id: String,
+    /// Name of the service account, e.g. the integration using the key
+    pub name: String,
+    pub resources: Vec<Resource>,
+    pub store_ids: Vec<String>,
+    pub expiry_datetime: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RotateApiKey {
+    pub id: String,
+    /// Id of the replacement key
+    pub new_id: String,
+    /// Defaults to the expiry of the rotated key
+    pub expiry_datetime: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CreateApiKeyError {
+    ApiKeyAlreadyExists,
+    NameCannotBeEmpty,
+    NoResources,
+    StoreDoesNotExist(String),
+    ExpiryMustBeInTheFuture,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RotateApiKeyError {
+    ApiKeyDoesNotExist,
+    ApiKeyIsRevoked,
+    ApiKeyAlreadyExists,
+    ExpiryMustBeInTheFuture,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RevokeApiKeyError {
+    ApiKeyDoesNotExist,
+    ApiKeyIsRevoked,
+    DatabaseError(RepositoryError),
+}
+
+pub fn create_api_key(
+    ctx: &ServiceContext,
+    input: CreateApiKey,
+) -> Result<NewApiKey, CreateApiKeyError> {
+    use CreateApiKeyError as Error;
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let name = input.name.trim().to_string();
+            if name.is_empty() {
+                return Err(Error::NameCannotBeEmpty);
+            }
+            let repo = ApiKeyRowRepository::new(connection);
+            if repo.find_one_by_id(&input.id)?.is_some() {
+                return Err(Error::ApiKeyAlreadyExists);
+            }
+            if input.resources.is_empty() {
+                return Err(Error::NoResources);
+            }
+            let store_repo = StoreRowRepository::new(connection);
+            for store_id in &input.store_ids {
+                if store_repo.find_one_by_id(store_id)?.is_none() {
+                    return Err(Error::StoreDoesNotExist(store_id.clone()));
+                }
+            }
+            let now = Utc::now().naive_utc();
+            if input.expiry_datetime.is_some_and(|expiry| expiry <= now) {
+                return Err(Error::ExpiryMustBeInTheFuture);
+            }
+
+            // Service accounts can't log in, there is no password that matches an empty hash
+            let user = UserAccountRow {
+                id: uuid(),
+                username: format!("service-account:{}", input.id),
+                hashed_password: String::new(),
+                job_title: Some("Service account".to_string()),
+                is_active: true,
+                ..Default::default()
+            };
+            UserAccountRowRepository::new(connection).insert_one(&user)?;
+
+            let (key, key_prefix) = generate_api_key();
+            let row = ApiKeyRow {
+                id: input.id,
+                user_id: user.id,
+                name,
+                key_prefix,
+                key_hash: hash_api_key(&key),
+                resources: to_json(input.resources.iter().map(Resource::to_string)),
+                store_ids: to_json(input.store_ids.into_iter()),
+                created_datetime: now,
+                expiry_datetime: input.expiry_datetime,
+                last_used_datetime: None,
+                revoked_datetime: None,
+            };
+            repo.upsert_one(&row)?;
+            activity_log_entry(
+                ctx,
+                ActivityLogType::ApiKeyCreated,
+                Some(row.id.clone()),
+                None,
+                Some(row.name.clone()),
+            )?;
+
+            Ok(NewApiKey {
+                api_key: ApiKey::from_row(row),
+                key,
+            })
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn rotate_api_key(
+    ctx: &ServiceContext,
+    input: RotateApiKey,
+) -> Result<NewApiKey, RotateApiKeyError> {
+    use RotateApiKeyError as Error;
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = ApiKeyRowRepository::new(connection);
+            let old_key = repo
+                .find_one_by_id(&input.id)?
+                .ok_or(Error::ApiKeyDoesNotExist)?;
+            if old_key.revoked_datetime.is_some() {
+                return Err(Error::ApiKeyIsRevoked);
+            }
+            if repo.find_one_by_id(&input.new_id)?.is_some() {
+                return Err(Error::ApiKeyAlreadyExists);
+            }
+            let now = Utc::now().naive_utc();
+            let expiry_datetime = input.expiry_datetime.or(old_key.expiry_datetime);
+            if expiry_datetime.is_some_and(|expiry| expiry <= now) {
+                return Err(Error::ExpiryMustBeInTheFuture);
+            }
+
+            let (key, key_prefix) = generate_api_key();
+            let new_key = ApiKeyRow {
+                id: input.new_id,
+                key_prefix,
+                key_hash: hash_api_key(&key),
+                created_datetime: now,
+                expiry_datetime,
+                last_used_datetime: None,
+                revoked_datetime: None,
+                ..old_key.clone()
+            };
+            repo.upsert_one(&new_key)?;
+            repo.upsert_one(&ApiKeyRow {
+                revoked_datetime: Some(now),
+                ..old_key
+            })?;
+            activity_log_entry(
+                ctx,
+                ActivityLogType::ApiKeyRevoked,
+                Some(input.id.clone()),
+                None,
+                Some(format!("Rotated to {}", new_key.id)),
+            )?;
+            activity_log_entry(
+                ctx,
+                ActivityLogType::ApiKeyCreated,
+                Some(new_key.id.clone()),
+                Some(input.id),
+                Some(new_key.name.clone()),
+            )?;
+
+            Ok(NewApiKey {
+                api_key: ApiKey::from_row(new_key),
+                key,
+            })
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn revoke_api_key(ctx: &ServiceContext, id: &str) -> Result<ApiKey, RevokeApiKeyError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = ApiKeyRowRepository::new(connection);
+            let api_key = repo
+                .find_one_by_id(id)?
+                .ok_or(RevokeApiKeyError::ApiKeyDoesNotExist)?;
+            if api_key.revoked_datetime.is_some() {
+                return Err(RevokeApiKeyError::ApiKeyIsRevoked);
+            }
+
+            let revoked = ApiKeyRow {
+                revoked_datetime: Some(Utc::now().naive_utc()),
+                ..api_key
+            };
+            repo.upsert_one(&revoked)?;
+            activity_log_entry(
+                ctx,
+                ActivityLogType::ApiKeyRevoked,
+                Some(revoked.id.clone()),
+                None,
+                None,
+            )?;
+
+            Ok(ApiKey::from_row(revoked))
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+fn to_json(values: impl Iterator<Item = String>) -> String {
+    serde_json::to_string(&values.collect::<Vec<_>>()).unwrap_or_else(|_| "[]".to_string())
+}
+
+impl From<RepositoryError> for CreateApiKeyError {
+    fn from(error: RepositoryError) -> Self {
+        CreateApiKeyError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for RotateApiKeyError {
+    fn from(error: RepositoryError) -> Self {
+        RotateApiKeyError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for RevokeApiKeyError {
+    fn from(error: RepositoryError) -> Self {
+        RevokeApiKeyError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,103 @@
+mod manage;
+#[cfg(test)]
+mod test;
+mod validate;
+
+use std::str::FromStr;
+
+use rand::RngExt;
+use repository::{ApiKeyRow, ApiKeyRowRepository, RepositoryError};
+use sha2::{Digest, Sha256};
+
+use crate::{auth::Resource, service_provider::ServiceContext};
+pub use manage::*;
+pub(crate) use validate::{authenticate_api_key, check_api_key_scope, record_api_key_use};
+
+/// API keys are sent as bearer tokens, the prefix tells them apart from JWTs
+pub const API_KEY_PREFIX: &str = "omk_";
+/// Characters of the key kept to identify it, including the prefix
+const KEY_PREFIX_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub api_key_row: ApiKeyRow,
+    pub resources: Vec<Resource>,
+    pub store_ids: Vec<String>,
+}
+
+impl ApiKey {
+    pub fn from_row(api_key_row: ApiKeyRow) -> ApiKey {
+        let parse = |json: &str| serde_json::from_str::<Vec<String>>(json).unwrap_or_default();
+        let resources = parse(&api_key_row.resources)
+            .iter()
+            .filter_map(|resource| Resource::from_str(resource).ok())
+            .collect();
+        let store_ids = parse(&api_key_row.store_ids);
+
+        ApiKey {
+            api_key_row,
+            resources,
+            store_ids,
+        }
+    }
+}
+
+/// A newly created or rotated key. The plain key is only available here, only its hash is stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewApiKey {
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+pub trait ApiKeyServiceTrait: Sync + Send {
+    fn get_api_keys(&self, ctx: &ServiceContext) -> Result<Vec<ApiKey>, RepositoryError> {
+        Ok(ApiKeyRowRepository::new(&ctx.connection)
+            .find_all()?
+            .into_iter()
+            .map(ApiKey::from_row)
+            .collect())
+    }
+
+    /// Creates a service account user with an API key
+    fn create_api_key(
+        &self,
+        ctx: &ServiceContext,
+        input: CreateApiKey,
+    ) -> Result<NewApiKey, CreateApiKeyError> {
+        create_api_key(ctx, input)
+    }
+
+    /// Replaces a key with a new one for the same service account and scope
+    fn rotate_api_key(
+        &self,
+        ctx: &ServiceContext,
+        input: RotateApiKey,
+    ) -> Result<NewApiKey, RotateApiKeyError> {
+        rotate_api_key(ctx, input)
+    }
+
+    fn revoke_api_key(&self, ctx: &ServiceContext, id: &str) -> Result<ApiKey, RevokeApiKeyError> {
+        revoke_api_key(ctx, id)
+    }
+}
+
+pub struct ApiKeyService;
+impl ApiKeyServiceTrait for ApiKeyService {}
+
+pub fn is_api_key(token: &str) -> bool {
+    token.starts_with(API_KEY_PREFIX)
+}
+
+fn hash_api_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Returns the key and its stored prefix
+fn generate_api_key() -> (String, String) {
+    let secret: [u8; 32] = rand::rng().random();
+    let key = format!("{API_KEY_PREFIX}{}", hex::encode(secret));
+    let prefix = key[..KEY_PREFIX_LENGTH].to_string();
+    (key, prefix)
+}
@@ -0,0 +1,262 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{Duration, Utc};
+use repository::{
+    activity_log::{ActivityLogFilter, ActivityLogRepository},
+    mock::{mock_store_a, mock_store_b, MockDataInserts},
+    test_db::setup_all,
+    ActivityLogType, ApiKeyRow, ApiKeyRowRepository,
+};
+
+use crate::{
+    auth::{AuthDeniedKind, AuthError, Resource, ResourceAccessRequest},
+    auth_data::AuthData,
+    service_provider::{ServiceContext, ServiceProvider},
+    token_bucket::TokenBucket,
+};
+
+use super::{CreateApiKey, CreateApiKeyError, RevokeApiKeyError, RotateApiKey};
+
+fn auth_data() -> AuthData {
+    AuthData {
+        auth_token_secret: "some secret".to_string(),
+        token_bucket: Arc::new(RwLock::new(TokenBucket::new())),
+        no_ssl: true,
+        debug_no_access_control: false,
+    }
+}
+
+fn request(resource: Resource, store_id: &str) -> ResourceAccessRequest {
+    ResourceAccessRequest {
+        resource,
+        store_id: Some(store_id.to_string()),
+    }
+}
+
+fn validate(
+    service_provider: &ServiceProvider,
+    context: &ServiceContext,
+    key: &str,
+    request: &ResourceAccessRequest,
+) -> Result<String, AuthError> {
+    service_provider
+        .validation_service
+        .validate(
+            context,
+            &auth_data(),
+            &Some(key.to_string()),
+            &None,
+            request,
+        )
+        .map(|user| user.user_id)
+}
+
+#[actix_rt::test]
+async fn test_api_keys() {
+    let (_, connection, connection_manager, _) =
+        setup_all("test_api_keys", MockDataInserts::none().stores()).await;
+
+    let service_provider = ServiceProvider::new(connection_manager);
+    let context = service_provider.basic_context().unwrap();
+    let service = &service_provider.api_key_service;
+
+    let input = CreateApiKey {
+        id: "key_a".to_string(),
+        name: "Reporting integration".to_string(),
+        resources: vec![Resource::QueryStockLine],
+        store_ids: vec![mock_store_a().id],
+        expiry_datetime: None,
+    };
+
+    // Create errors
+    assert_eq!(
+        service.create_api_key(
+            &context,
+            CreateApiKey {
+                name: " ".to_string(),
+                ..input.clone()
+            }
+        ),
+        Err(CreateApiKeyError::NameCannotBeEmpty)
+    );
+    assert_eq!(
+        service.create_api_key(
+            &context,
+            CreateApiKey {
+                resources: vec![],
+                ..input.clone()
+            }
+        ),
+        Err(CreateApiKeyError::NoResources)
+    );
+    assert_eq!(
+        service.create_api_key(
+            &context,
+            CreateApiKey {
+                store_ids: vec!["invalid".to_string()],
+                ..input.clone()
+            }
+        ),
+        Err(CreateApiKeyError::StoreDoesNotExist("invalid".to_string()))
+    );
+    assert_eq!(
+        service.create_api_key(
+            &context,
+            CreateApiKey {
+                expiry_datetime: Some(Utc::now().naive_utc() - Duration::hours(1)),
+                ..input.clone()
+            }
+        ),
+        Err(CreateApiKeyError::ExpiryMustBeInTheFuture)
+    );
+
+    // Create, only the hash is stored
+    let created = service.create_api_key(&context, input.clone()).unwrap();
+    let row = &created.api_key.api_key_row;
+    assert!(created.key.starts_with(super::API_KEY_PREFIX));
+    assert!(created.key.starts_with(&row.key_prefix));
+    assert_ne!(row.key_hash, created.key);
+    assert_eq!(created.api_key.resources, vec![Resource::QueryStockLine]);
+    assert_eq!(created.api_key.store_ids, vec![mock_store_a().id]);
+    assert_eq!(
+        service.create_api_key(&context, input.clone()),
+        Err(CreateApiKeyError::ApiKeyAlreadyExists)
+    );
+
+    // Authenticates as the service account within scope
+    let in_scope = request(Resource::QueryStockLine, &mock_store_a().id);
+    assert_eq!(
+        validate(&service_provider, &context, &created.key, &in_scope).unwrap(),
+        row.user_id
+    );
+    assert!(matches!(
+        validate(
+            &service_provider,
+            &context,
+            &created.key,
+            &request(Resource::MutateStockLine, &mock_store_a().id)
+        ),
+        Err(AuthError::Denied(
+            AuthDeniedKind::InsufficientPermission { .. }
+        ))
+    ));
+    assert!(matches!(
+        validate(
+            &service_provider,
+            &context,
+            &created.key,
+            &request(Resource::QueryStockLine, &mock_store_b().id)
+        ),
+        Err(AuthError::Denied(
+            AuthDeniedKind::InsufficientPermission { .. }
+        ))
+    ));
+    assert!(matches!(
+        validate(
+            &service_provider,
+            &context,
+            &format!("{}invalid", super::API_KEY_PREFIX),
+            &in_scope
+        ),
+        Err(AuthError::Denied(AuthDeniedKind::NotAuthenticated(_)))
+    ));
+
+    // Use is tracked
+    let row = ApiKeyRowRepository::new(&connection)
+        .find_one_by_id("key_a")
+        .unwrap()
+        .unwrap();
+    assert!(row.last_used_datetime.is_some());
+    let uses = ActivityLogRepository::new(&connection)
+        .query_by_filter(ActivityLogFilter::new().r#type(ActivityLogType::ApiKeyUsed.equal_to()))
+        .unwrap();
+    assert_eq!(uses.len(), 1);
+    assert_eq!(
+        uses[0].activity_log_row.record_id,
+        Some("key_a".to_string())
+    );
+    assert_eq!(
+        uses[0].activity_log_row.changed_to,
+        Some(Resource::QueryStockLine.to_string())
+    );
+
+    // Use is recorded at most once per interval
+    let uses_count = || {
+        ActivityLogRepository::new(&connection)
+            .query_by_filter(
+                ActivityLogFilter::new().r#type(ActivityLogType::ApiKeyUsed.equal_to()),
+            )
+            .unwrap()
+            .len()
+    };
+    validate(&service_provider, &context, &created.key, &in_scope).unwrap();
+    assert_eq!(uses_count(), 1);
+    let last_used = Utc::now().naive_utc() - Duration::minutes(10);
+    ApiKeyRowRepository::new(&connection)
+        .update_last_used_datetime("key_a", last_used)
+        .unwrap();
+    validate(&service_provider, &context, &created.key, &in_scope).unwrap();
+    assert_eq!(uses_count(), 2);
+    let row = ApiKeyRowRepository::new(&connection)
+        .find_one_by_id("key_a")
+        .unwrap()
+        .unwrap();
+    assert!(row.last_used_datetime > Some(last_used));
+
+    // Rotate, the old key stops working and the new key keeps the scope
+    let rotated = service
+        .rotate_api_key(
+            &context,
+            RotateApiKey {
+                id: "key_a".to_string(),
+                new_id: "key_b".to_string(),
+                expiry_datetime: None,
+            },
+        )
+        .unwrap();
+    assert_ne!(rotated.key, created.key);
+    assert_eq!(rotated.api_key.api_key_row.user_id, row.user_id);
+    assert_eq!(rotated.api_key.resources, vec![Resource::QueryStockLine]);
+    assert!(matches!(
+        validate(&service_provider, &context, &created.key, &in_scope),
+        Err(AuthError::Denied(AuthDeniedKind::NotAuthenticated(_)))
+    ));
+    assert_eq!(
+        validate(&service_provider, &context, &rotated.key, &in_scope).unwrap(),
+        row.user_id
+    );
+
+    // Revoke
+    service.revoke_api_key(&context, "key_b").unwrap();
+    assert_eq!(
+        service.revoke_api_key(&context, "key_b"),
+        Err(RevokeApiKeyError::ApiKeyIsRevoked)
+    );
+    assert!(matches!(
+        validate(&service_provider, &context, &rotated.key, &in_scope),
+        Err(AuthError::Denied(AuthDeniedKind::NotAuthenticated(_)))
+    ));
+
+    // Expired
+    let expiring = service
+        .create_api_key(
+            &context,
+            CreateApiKey {
+                id: "key_c".to_string(),
+                name: "Expiring".to_string(),
+                expiry_datetime: Some(Utc::now().naive_utc() + Duration::hours(1)),
+                ..input
+            },
+        )
+        .unwrap();
+    ApiKeyRowRepository::new(&connection)
+        .upsert_one(&ApiKeyRow {
+            expiry_datetime: Some(Utc::now().naive_utc() - Duration::hours(1)),
+            ..expiring.api_key.api_key_row
+        })
+        .unwrap();
+    assert!(matches!(
+        validate(&service_provider, &context, &expiring.key, &in_scope),
+        Err(AuthError::Denied(AuthDeniedKind::NotAuthenticated(_)))
+    ));
+}
@@ -0,0 +1,89 @@
+use chrono::{Duration, Utc};
+use repository::{
+    ActivityLogRow, ActivityLogRowRepository, ActivityLogType, ApiKeyRowRepository,
+    RepositoryError, StorageConnection,
+};
+use util::uuid::uuid;
+
+use crate::auth::{AuthDeniedKind, AuthError, ResourceAccessRequest};
+
+use super::{hash_api_key, ApiKey};
+
+// A request can validate the key many times (once per resolver), use is recorded at most once in
+// this interval so every validation doesn't write to the database
+const RECORD_USE_INTERVAL_MINUTES: i64 = 5;
+
+/// Finds the key, it must not be revoked or expired
+pub(crate) fn authenticate_api_key(
+    connection: &StorageConnection,
+    key: &str,
+) -> Result<ApiKey, AuthError> {
+    let not_authenticated =
+        |msg: &str| AuthError::Denied(AuthDeniedKind::NotAuthenticated(msg.to_string()));
+
+    let row = ApiKeyRowRepository::new(connection)
+        .find_one_by_key_hash(&hash_api_key(key))?
+        .ok_or_else(|| not_authenticated("Invalid API key"))?;
+    if row.revoked_datetime.is_some() {
+        return Err(not_authenticated("API key has been revoked"));
+    }
+    if row
+        .expiry_datetime
+        .is_some_and(|expiry| expiry <= Utc::now().naive_utc())
+    {
+        return Err(not_authenticated("API key has expired"));
+    }
+
+    Ok(ApiKey::from_row(row))
+}
+
+/// The key's resources and stores replace user permissions, service accounts don't have any
+pub(crate) fn check_api_key_scope(
+    api_key: &ApiKey,
+    resource_request: &ResourceAccessRequest,
+) -> Result<(), String> {
+    if !api_key.resources.contains(&resource_request.resource) {
+        return Err(format!(
+            "API key {} is not scoped to {}",
+            api_key.api_key_row.key_prefix, resource_request.resource
+        ));
+    }
+    if let Some(store_id) = &resource_request.store_id {
+        if !api_key.store_ids.contains(store_id) {
+            return Err(format!(
+                "API key {} is not scoped to store {store_id}",
+                api_key.api_key_row.key_prefix
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Updates last used datetime and logs the use, unless the key was used within the interval
+pub(crate) fn record_api_key_use(
+    connection: &StorageConnection,
+    api_key: &ApiKey,
+    resource_request: &ResourceAccessRequest,
+) -> Result<(), RepositoryError> {
+    let now = Utc::now().naive_utc();
+    let row = &api_key.api_key_row;
+    if row
+        .last_used_datetime
+        .is_some_and(|last_used| now - last_used < Duration::minutes(RECORD_USE_INTERVAL_MINUTES))
+    {
+        return Ok(());
+    }
+
+    ApiKeyRowRepository::new(connection).update_last_used_datetime(&row.id, now)?;
+    ActivityLogRowRepository::new(connection).insert_one(&ActivityLogRow {
+        id: uuid(),
+        r#type: ActivityLogType::ApiKeyUsed,
+        user_id: Some(row.user_id.clone()),
+        store_id: resource_request.store_id.clone(),
+        record_id: Some(row.id.clone()),
+        datetime: now,
+        changed_to: Some(resource_request.resource.to_string()),
+        changed_from: None,
+    })?;
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use chrono::{Duration, Utc};
+use repository::{
+    AuditLogRow, AuditLogRowRepository, KeyType, KeyValueStoreRepository, RepositoryError,
+};
+
+use crate::service_provider::ServiceContext;
+
+/// Audit log entries are kept for a year unless configured otherwise
+pub const DEFAULT_AUDIT_LOG_RETENTION_DAYS: i32 = 365;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditLogSettings {
+    /// Entries older than this are pruned, None keeps entries forever
+    pub retention_days: Option<i32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateAuditLogSettingsError {
+    /// Retention must be at least a day
+    InvalidRetentionDays,
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for UpdateAuditLogSettingsError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateAuditLogSettingsError::DatabaseError(error)
+    }
+}
+
+/// Field changes of a master data record, newest first
+pub fn get_record_audit_log(
+    ctx: &ServiceContext,
+    table_name: &str,
+    record_id: &str,
+) -> Result<Vec<AuditLogRow>, RepositoryError> {
+    AuditLogRowRepository::new(&ctx.connection).find_many_by_record(table_name, record_id)
+}
+
+pub fn get_audit_log_settings(ctx: &ServiceContext) -> Result<AuditLogSettings, RepositoryError> {
+    let retention_days = KeyValueStoreRepository::new(&ctx.connection)
+        .get_i32(KeyType::SettingsAuditLogRetentionDays)?;
+
+    Ok(AuditLogSettings {
+        // Not set falls back to the default, 0 keeps entries forever
+        retention_days: match retention_days {
+            None => Some(DEFAULT_AUDIT_LOG_RETENTION_DAYS),
+            Some(0) => None,
+            Some(days) => Some(days),
+        },
+    })
+}
+
+pub fn update_audit_log_settings(
+    ctx: &ServiceContext,
+    settings: &AuditLogSettings,
+) -> Result<AuditLogSettings, UpdateAuditLogSettingsError> {
+    let retention_days = match settings.retention_days {
+        Some(days) if days < 1 => return Err(UpdateAuditLogSettingsError::InvalidRetentionDays),
+        Some(days) => days,
+        None => 0,
+    };
+
+    KeyValueStoreRepository::new(&ctx.connection)
+        .set_i32(KeyType::SettingsAuditLogRetentionDays, Some(retention_days))?;
+
+    Ok(get_audit_log_settings(ctx)?)
+}
+
+/// Deletes the audit log entries past retention, returns the number deleted
+pub fn prune_audit_log(ctx: &ServiceContext) -> Result<usize, RepositoryError> {
+    let Some(retention_days) = get_audit_log_settings(ctx)?.retention_days else {
+        return Ok(0);
+    };
+    let before = Utc::now().naive_utc() - Duration::days(retention_days as i64);
+
+    AuditLogRowRepository::new(&ctx.connection).delete_before(before)
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_store_a, mock_user_account_a, MockDataInserts},
+        test_db::setup_all,
+        LocationRow, LocationRowRepository,
+    };
+
+    use crate::service_provider::ServiceProvider;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_audit_log() {
+        let (_, connection, connection_manager, _) =
+            setup_all("test_audit_log", MockDataInserts::none().stores()).await;
+        let service_provider = ServiceProvider::new(connection_manager.clone());
+        let store_id = mock_store_a().id;
+        let location = LocationRow {
+            id: "audited_location".to_string(),
+            name: "Shelf".to_string(),
+            code: "S1".to_string(),
+            store_id: store_id.clone(),
+            ..Default::default()
+        };
+
+        // Not audited without a user
+        LocationRowRepository::new(&connection)
+            .upsert_one(&location)
+            .unwrap();
+        let ctx = service_provider
+            .context(store_id.clone(), mock_user_account_a().id)
+            .unwrap();
+        assert_eq!(
+            get_record_audit_log(&ctx, "location", &location.id).unwrap(),
+            vec![]
+        );
+
+        let ctx = ctx.with_audit_reason(Some("Relabelled".to_string()));
+        LocationRowRepository::new(&ctx.connection)
+            .upsert_one(&LocationRow {
+                code: "S2".to_string(),
+                on_hold: true,
+                ..location.clone()
+            })
+            .unwrap();
+
+        let changes = get_record_audit_log(&ctx, "location", &location.id).unwrap();
+        let fields: Vec<_> = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.field.as_str(),
+                    change.old_value.as_deref(),
+                    change.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("code", Some("S1"), Some("S2")),
+                ("on_hold", Some("false"), Some("true"))
+            ]
+        );
+        assert_eq!(changes[0].change_id, changes[1].change_id);
+        assert_eq!(changes[0].user_id, mock_user_account_a().id);
+        assert_eq!(changes[0].store_id, Some(store_id.clone()));
+        assert_eq!(changes[0].reason, Some("Relabelled".to_string()));
+
+        // Unchanged upsert is not audited
+        LocationRowRepository::new(&ctx.connection)
+            .upsert_one(&LocationRow {
+                code: "S2".to_string(),
+                on_hold: true,
+                ..location.clone()
+            })
+            .unwrap();
+        assert_eq!(
+            get_record_audit_log(&ctx, "location", &location.id)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Settings and pruning
+        assert_eq!(
+            get_audit_log_settings(&ctx).unwrap().retention_days,
+            Some(DEFAULT_AUDIT_LOG_RETENTION_DAYS)
+        );
+        assert_eq!(
+            update_audit_log_settings(
+                &ctx,
+                &AuditLogSettings {
+                    retention_days: Some(0)
+                }
+            ),
+            Err(UpdateAuditLogSettingsError::InvalidRetentionDays)
+        );
+        connection_manager
+            .execute("UPDATE audit_log SET datetime = '2000-01-01 00:00:00'")
+            .unwrap();
+        update_audit_log_settings(
+            &ctx,
+            &AuditLogSettings {
+                retention_days: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(prune_audit_log(&ctx).unwrap(), 0);
+        update_audit_log_settings(
+            &ctx,
+            &AuditLogSettings {
+                retention_days: Some(30),
+            },
+        )
+        .unwrap();
+        assert_eq!(prune_audit_log(&ctx).unwrap(), 2);
+    }
+}
@@ -4,12 +4,14 @@ use repository::{
     EqualFilter, Pagination, PermissionType, RepositoryError, UserPermissionFilter,
     UserPermissionRepository, UserPermissionRow,
 };
+use strum::{Display, EnumString};
 use util::{
     constants::{PATIENT_CONTEXT_ID, PLUGIN_USER_ID},
     uuid::uuid,
 };
 
 use crate::{
+    api_key::{authenticate_api_key, check_api_key_scope, is_api_key, record_api_key_use},
     auth_data::AuthData,
     service_provider::ServiceContext,
     settings::is_develop,
@@ -29,7 +31,7 @@ pub enum PermissionDSL {
 }
 
 /// Resources for permission checks
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display, EnumString)]
 pub enum Resource {
     RouteMe,
     // name
@@ -1087,6 +1089,42 @@ impl AuthService {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// API keys act as their service account, limited to the resources and stores of the key
+    fn validate_api_key(
+        &self,
+        context: &ServiceContext,
+        key: &str,
+        resource_request: &ResourceAccessRequest,
+    ) -> Result<ValidatedUser, AuthError> {
+        let connection = &context.connection;
+        let api_key = authenticate_api_key(connection, key)?;
+
+        let required_permissions = self
+            .resource_permissions
+            .get(&resource_request.resource)
+            .ok_or_else(|| {
+                AuthError::InternalError(format!(
+                    "Unable to identify required permissions for resource {:?}",
+                    &resource_request.resource
+                ))
+            })?;
+        if !matches!(required_permissions, PermissionDSL::NoPermissionRequired) {
+            if let Err(msg) = check_api_key_scope(&api_key, resource_request) {
+                return Err(AuthError::Denied(AuthDeniedKind::InsufficientPermission {
+                    msg,
+                    required_permissions: required_permissions.clone(),
+                }));
+            }
+        }
+
+        record_api_key_use(connection, &api_key, resource_request)?;
+
+        Ok(ValidatedUser {
+            user_id: api_key.api_key_row.user_id,
+            capabilities: Vec::new(),
+        })
+    }
 }
 
 impl AuthServiceTrait for AuthService {
@@ -1098,6 +1136,12 @@ impl AuthServiceTrait for AuthService {
         override_user_id: &Option<String>,
         resource_request: &ResourceAccessRequest,
     ) -> Result<ValidatedUser, AuthError> {
+        if let (None, Some(token)) = (override_user_id, auth_token) {
+            if is_api_key(token) {
+                return self.validate_api_key(context, token, resource_request);
+            }
+        }
+
         let user_id = if let Some(override_user_id) = override_user_id {
             log::info!("Overriding user id with: {override_user_id}");
             override_user_id.clone()
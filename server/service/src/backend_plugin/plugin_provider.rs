@@ -3,8 +3,8 @@ use std::sync::{Arc, RwLock};
 use base64::{prelude::BASE64_STANDARD, Engine};
 
 use repository::{
-    migrations::Version, BackendPluginRow, FrontendPluginRow, PluginType, PluginTypes,
-    PluginVariantType,
+    migrations::Version, BackendPluginRow, FrontendPluginRow, PluginManifest, PluginMutation,
+    PluginType, PluginTypes, PluginVariantType,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
@@ -37,6 +37,7 @@ pub struct PluginInstance {
     pub code: String,
     variant: PluginInstanceVariant,
     pub version: Version,
+    pub manifest: PluginManifest,
 }
 
 pub type PluginResult<T> = Result<T, PluginError>;
@@ -67,6 +68,7 @@ where
             input,
             vec!["plugins", &plugin_type_to_string(r#type)],
             bundle,
+            Some(&plugin.code),
         )
         .map_err(Into::into),
     };
@@ -111,6 +113,33 @@ impl PluginInstance {
             .collect()
     }
 
+    /// Calls plugin method of specified type with untyped input and output,
+    /// used to test plugins outside of the server (see `test-plugin` cli command)
+    pub fn call_json(
+        &self,
+        r#type: PluginType,
+        input: serde_json::Value,
+    ) -> PluginResult<serde_json::Value> {
+        call_plugin(input, r#type, self)
+    }
+
+    /// Mutations declared in manifests of graphql_mutation plugins, with code of the plugin
+    pub fn declared_mutations() -> Vec<(String, PluginMutation)> {
+        let plugins = PLUGINS.read().unwrap();
+
+        plugins
+            .iter()
+            .filter(|p| p.has_type(&PluginType::GraphqlMutation))
+            .flat_map(|p| {
+                p.instance
+                    .manifest
+                    .mutations
+                    .iter()
+                    .map(|mutation| (p.instance.code.clone(), mutation.clone()))
+            })
+            .collect()
+    }
+
     pub fn get_one_with_code(code: &str, r#type: PluginType) -> Option<Arc<PluginInstance>> {
         let plugins = PLUGINS.read().unwrap();
 
@@ -127,6 +156,7 @@ impl PluginInstance {
             types,
             code,
             version,
+            manifest,
             ..
         }: BackendPluginRow,
     ) {
@@ -156,6 +186,7 @@ impl PluginInstance {
                 code: code.clone(),
                 variant: PluginInstanceVariant::BoaJs(plugin_bundle),
                 version,
+                manifest,
             },
         };
 
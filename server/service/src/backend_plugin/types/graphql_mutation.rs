@@ -0,0 +1,32 @@
+use crate::backend_plugin::{plugin_provider::PluginInstance, *};
+use plugin_provider::{call_plugin, PluginResult};
+use repository::PluginType;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+fn plugin_type() -> PluginType {
+    PluginType::GraphqlMutation
+}
+
+#[derive(TS, Clone, Deserialize, Serialize)]
+#[ts(rename = "GraphqlMutationInput")]
+pub struct Input {
+    pub store_id: String,
+    pub user_id: String,
+    #[doc = "Name of the mutation as declared in plugin manifest"]
+    pub name: String,
+    #[doc = "Validated against input_schema of the mutation declared in plugin manifest"]
+    pub input: serde_json::Value,
+}
+
+pub type Output = serde_json::Value;
+
+pub trait Trait: Send + Sync {
+    fn call(&self, input: Input) -> PluginResult<Output>;
+}
+
+impl self::Trait for PluginInstance {
+    fn call(&self, input: Input) -> PluginResult<Output> {
+        call_plugin(input, plugin_type(), self)
+    }
+}
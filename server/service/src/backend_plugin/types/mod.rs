@@ -1,9 +1,11 @@
 pub mod amc;
 pub mod get_consumption;
+pub mod graphql_mutation;
 pub mod graphql_query;
 pub mod processor;
 pub mod schedule;
 pub mod transform_request_requisition_lines;
+pub mod validate_mutation;
 
 pub mod generate_typescript_types {
     use crate::{
@@ -37,8 +39,10 @@ pub mod generate_typescript_types {
         >,
         get_consumption: Function<get_consumption::Input, get_consumption::Output>,
         graphql_query: Function<graphql_query::Input, graphql_query::Output>,
+        graphql_mutation: Function<graphql_mutation::Input, graphql_mutation::Output>,
         processor: Function<processor::Input, processor::Output>,
         schedule: Function<schedule::Input, schedule::Output>,
+        validate_mutation: Function<validate_mutation::Input, validate_mutation::Output>,
         // Extra types to expose, not directly related to plugin interface
         // like for input or output of global methods
         get_store_preferences: StorePreferenceRow,
@@ -0,0 +1,195 @@
+use crate::{
+    backend_plugin::{plugin_provider::PluginInstance, *},
+    service_provider::ServiceContext,
+};
+use plugin_provider::{call_plugin, PluginError, PluginResult};
+use repository::{
+    EqualFilter, InvoiceLineRow, InvoiceLineRowRepository, InvoiceRow, PluginType, RepositoryError,
+    RequisitionLineFilter, RequisitionLineRepository, RequisitionLineRow, RequisitionRow,
+    StocktakeLineFilter, StocktakeLineRepository, StocktakeLineRow, StocktakeRow,
+    StorageConnection,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+fn plugin_type() -> PluginType {
+    PluginType::ValidateMutation
+}
+
+#[derive(TS, Clone, Deserialize, Serialize)]
+#[ts(rename = "ValidateMutationInput")]
+pub struct Input {
+    pub store_id: String,
+    pub user_id: String,
+    #[doc = "Records as they will be committed, the mutation is rolled back if any errors are returned"]
+    pub proposed: ProposedRows,
+}
+
+#[derive(TS, Clone, Deserialize, Serialize)]
+#[serde(tag = "t", content = "v")]
+#[ts(rename = "ValidateMutationProposedRows")]
+pub enum ProposedRows {
+    Invoice {
+        invoice: InvoiceRow,
+        lines: Vec<InvoiceLineRow>,
+    },
+    Requisition {
+        requisition: RequisitionRow,
+        lines: Vec<RequisitionLineRow>,
+    },
+    Stocktake {
+        stocktake: StocktakeRow,
+        lines: Vec<StocktakeLineRow>,
+    },
+}
+
+impl ProposedRows {
+    pub(crate) fn invoice(
+        connection: &StorageConnection,
+        invoice: InvoiceRow,
+    ) -> Result<Self, RepositoryError> {
+        let lines =
+            InvoiceLineRowRepository::new(connection).find_many_by_invoice_id(&invoice.id)?;
+        Ok(ProposedRows::Invoice { invoice, lines })
+    }
+
+    pub(crate) fn requisition(
+        connection: &StorageConnection,
+        requisition: RequisitionRow,
+    ) -> Result<Self, RepositoryError> {
+        let lines = RequisitionLineRepository::new(connection)
+            .query_by_filter(
+                RequisitionLineFilter::new()
+                    .requisition_id(EqualFilter::equal_to(requisition.id.to_string())),
+            )?
+            .into_iter()
+            .map(|line| line.requisition_line_row)
+            .collect();
+        Ok(ProposedRows::Requisition { requisition, lines })
+    }
+
+    pub(crate) fn stocktake(
+        connection: &StorageConnection,
+        stocktake: StocktakeRow,
+    ) -> Result<Self, RepositoryError> {
+        let lines = StocktakeLineRepository::new(connection)
+            .query_by_filter(
+                StocktakeLineFilter::new()
+                    .stocktake_id(EqualFilter::equal_to(stocktake.id.to_string())),
+                None,
+            )?
+            .into_iter()
+            .map(|line| line.line)
+            .collect();
+        Ok(ProposedRows::Stocktake { stocktake, lines })
+    }
+}
+
+#[derive(TS, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[ts(rename = "ValidateMutationMessage")]
+pub struct Message {
+    pub code: String,
+    pub message: String,
+    /// Id of the line (or record) the message relates to
+    #[ts(optional)]
+    pub record_id: Option<String>,
+}
+
+#[derive(TS, Clone, Default, Deserialize, Serialize)]
+#[ts(rename = "ValidateMutationOutput")]
+pub struct Output {
+    /// Non blocking, mutation will proceed
+    #[ts(optional)]
+    pub warnings: Option<Vec<Message>>,
+    /// Blocking, mutation will be rejected
+    #[ts(optional)]
+    pub errors: Option<Vec<Message>>,
+}
+
+pub trait Trait: Send + Sync {
+    fn call(&self, input: Input) -> PluginResult<Output>;
+}
+
+impl self::Trait for PluginInstance {
+    fn call(&self, input: Input) -> PluginResult<Output> {
+        call_plugin(input, plugin_type(), self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PluginValidationMessage {
+    pub plugin_code: String,
+    pub code: String,
+    pub message: String,
+    pub record_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct PluginValidationFailed {
+    pub errors: Vec<PluginValidationMessage>,
+    pub warnings: Vec<PluginValidationMessage>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ValidateMutationError {
+    PluginError(PluginError),
+    Failed(PluginValidationFailed),
+}
+
+fn to_messages(plugin_code: &str, messages: Option<Vec<Message>>) -> Vec<PluginValidationMessage> {
+    messages
+        .unwrap_or_default()
+        .into_iter()
+        .map(
+            |Message {
+                 code,
+                 message,
+                 record_id,
+             }| PluginValidationMessage {
+                plugin_code: plugin_code.to_string(),
+                code,
+                message,
+                record_id,
+            },
+        )
+        .collect()
+}
+
+// Helper
+impl PluginInstance {
+    /// Runs all validate_mutation plugins against proposed rows, should be called inside
+    /// of the mutation transaction after rows are upserted. Warnings are returned alongside
+    /// errors, or added to the context (see `ServiceContext::take_plugin_warnings`) to be
+    /// returned with the mutation result
+    pub(crate) fn validate_mutation(
+        ctx: &ServiceContext,
+        store_id: &str,
+        proposed: ProposedRows,
+    ) -> Result<(), ValidateMutationError> {
+        let plugins = PluginInstance::get_all(plugin_type());
+
+        let mut result = PluginValidationFailed::default();
+        for plugin in plugins {
+            let Output { warnings, errors } = Trait::call(
+                &(*plugin),
+                Input {
+                    store_id: store_id.to_string(),
+                    user_id: ctx.user_id.clone(),
+                    proposed: proposed.clone(),
+                },
+            )
+            .map_err(ValidateMutationError::PluginError)?;
+
+            result.warnings.extend(to_messages(&plugin.code, warnings));
+            result.errors.extend(to_messages(&plugin.code, errors));
+        }
+
+        if !result.errors.is_empty() {
+            return Err(ValidateMutationError::Failed(result));
+        }
+
+        ctx.plugin_warnings.lock().unwrap().extend(result.warnings);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,271 @@
+use super::{encryption::BackupCipher, manifest::BackupManifest, point_in_time, *};
+use crate::settings::Settings;
+use copy_dir::copy_dir;
+use std::{fs, io, path::PathBuf, process::Command, str::FromStr};
+
+/// Backs up app data and the database (online, the server can be running), verifies the backup
+/// and returns its name. An incomplete backup is removed
+pub fn backup(settings: &Settings) -> Result<String, BackupError> {
+    let DirSettings {
+        backup_dir,
+        pg_bin_dir,
+    } = get_dirs_from_settings(settings)?;
+
+    let max_number_of_backups = settings
+        .backup
+        .as_ref()
+        .and_then(|b| b.max_number_of_backups);
+
+    let backup_key = get_backup_key(settings)?;
+
+    let created_datetime = manifest::now();
+    let dirs = create_backup_dir(backup_dir, created_datetime)?;
+
+    if let Err(error) = backup_into(settings, &dirs, pg_bin_dir, backup_key.as_deref()) {
+        let _ = fs::remove_dir_all(&dirs.base_dir);
+        return Err(error);
+    }
+
+    // Verifying what was written to disk
+    BackupManifest::write(&dirs.base_dir, created_datetime)?.verify(&dirs.base_dir)?;
+
+    cleanup_backups(&dirs.backups_dir, max_number_of_backups)?;
+
+    Ok(dirs.backup_name)
+}
+
+fn backup_into(
+    settings: &Settings,
+    Dirs {
+        base_dir,
+        file_dir,
+        database_dir,
+        ..
+    }: &Dirs,
+    pg_bin_dir: Option<String>,
+    backup_key: Option<&str>,
+) -> Result<(), BackupError> {
+    copy_files(settings, file_dir)?;
+
+    // Backup database
+    if cfg!(feature = "postgres") {
+        dump_postgres_database(settings, database_dir, pg_bin_dir.clone())?;
+        check_postgres_dump(database_dir, pg_bin_dir.clone())?;
+        if let Some(wal_archive_dir) = settings
+            .backup
+            .as_ref()
+            .and_then(|b| b.wal_archive_dir.as_ref())
+        {
+            point_in_time::base_backup_postgres(settings, base_dir, pg_bin_dir, wal_archive_dir)?;
+        }
+    } else {
+        snapshot_sqlite_database(settings, database_dir, backup_key)?;
+    }
+
+    if let Some(backup_key) = backup_key {
+        let cipher = BackupCipher::create(base_dir, backup_key)?;
+        cipher.encrypt_dir(file_dir)?;
+        // SQLite database is exported encrypted with the backup key
+        if cfg!(feature = "postgres") {
+            cipher.encrypt_dir(database_dir)?;
+            point_in_time::encrypt_base_backup(&cipher, base_dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+struct Dirs {
+    backup_name: String,
+    base_dir: PathBuf,
+    file_dir: PathBuf,
+    database_dir: PathBuf,
+    backups_dir: PathBuf,
+}
+
+fn create_backup_dir(
+    output_dir: String,
+    created_datetime: chrono::NaiveDateTime,
+) -> Result<Dirs, BackupError> {
+    let backup_name = created_datetime.format("D%Y_%m_%dT%H_%M_%S").to_string();
+
+    let backups_dir = PathBuf::from_str(&output_dir)
+        .map_err(|_| BackupError::InvalidPath(output_dir.to_string()))?;
+
+    let base_dir = backups_dir.join(&backup_name);
+
+    fs::create_dir_all(&base_dir)
+        .map_err(|e| BackupError::CannotCreateBackupFolder(e, base_dir.clone()))?;
+
+    let file_dir = base_dir.join(BACKUP_FILE_DIR);
+    fs::create_dir_all(&file_dir)
+        .map_err(|e| BackupError::CannotCreateBackupFolder(e, file_dir.clone()))?;
+
+    let database_dir = base_dir.join(BACKUP_DATABASE_DIR);
+    fs::create_dir_all(&database_dir)
+        .map_err(|e| BackupError::CannotCreateBackupFolder(e, database_dir.clone()))?;
+
+    Ok(Dirs {
+        backup_name,
+        base_dir,
+        file_dir,
+        database_dir,
+        backups_dir,
+    })
+}
+
+fn copy_files(settings: &Settings, backup_file_dir: &PathBuf) -> Result<(), BackupError> {
+    // TODO should only copy sync_files and plugins
+    let file_dir = get_base_dir(settings)?;
+
+    for entry in fs::read_dir(file_dir)? {
+        let from_dir = entry?.path();
+
+        let (Some(folder_name), true) = (from_dir.file_name(), from_dir.is_dir()) else {
+            continue;
+        };
+
+        let to_dir = backup_file_dir.join(folder_name);
+        copy_dir(&from_dir, &to_dir)
+            .map_err(|e| BackupError::ProblemCopyingFolder(e, from_dir, to_dir))?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn pg_command(
+    pg_bin_dir_opt: &Option<String>,
+    name: &str,
+) -> Result<Command, BackupError> {
+    let pg_bin_dir = pg_bin_dir_opt.clone().unwrap_or_default();
+
+    let command = PathBuf::from_str(&pg_bin_dir)
+        .map_err(|_| BackupError::InvalidPath(pg_bin_dir.clone()))?
+        .join(name);
+
+    Ok(Command::new(command))
+}
+
+/// Output of a postgres command, failing when it's not successful
+pub(super) fn run_pg_command(
+    mut command: Command,
+    pg_bin_dir_opt: &Option<String>,
+) -> Result<(), BackupError> {
+    let result = command
+        .output()
+        .map_err(|e| match (e.kind(), pg_bin_dir_opt.is_some()) {
+            (io::ErrorKind::NotFound, true) => BackupError::PgCommandNotFoundInBinPath,
+            (io::ErrorKind::NotFound, false) => BackupError::PgCommandNotFoundInPath,
+            _ => e.into(),
+        })?;
+
+    if !result.status.success() {
+        return Err(BackupError::CommandLineError(result));
+    }
+
+    Ok(())
+}
+
+fn dump_postgres_database(
+    settings: &Settings,
+    backup_database_dir: &PathBuf,
+    pg_bin_dir_opt: Option<String>,
+) -> Result<(), BackupError> {
+    let mut command = pg_command(&pg_bin_dir_opt, "pg_dump")?;
+    command.args([
+        "--file",
+        backup_database_dir.to_str().unwrap(),
+        "--format",
+        "d",
+        "--dbname",
+        &settings.database.connection_string(),
+    ]);
+
+    run_pg_command(command, &pg_bin_dir_opt)
+}
+
+/// Reads back the table of contents of the dump
+fn check_postgres_dump(
+    backup_database_dir: &PathBuf,
+    pg_bin_dir_opt: Option<String>,
+) -> Result<(), BackupError> {
+    let mut command = pg_command(&pg_bin_dir_opt, "pg_restore")?;
+    command.args([
+        "--list",
+        "--format",
+        "d",
+        backup_database_dir.to_str().unwrap(),
+    ]);
+
+    run_pg_command(command, &pg_bin_dir_opt)
+}
+
+/// Snapshot of the database, encrypted with the backup key (or with the database key when backups
+/// aren't encrypted), and opened to check its integrity
+// feature sqlite
+#[cfg(not(feature = "postgres"))]
+fn snapshot_sqlite_database(
+    settings: &Settings,
+    backup_database_dir: &PathBuf,
+    backup_key: Option<&str>,
+) -> Result<(), BackupError> {
+    use repository::database_backup::{check_sqlite_database, snapshot_sqlite_database};
+
+    let database_path = PathBuf::from(settings.database.database_path());
+    if !database_path.exists() {
+        return Err(BackupError::CannotFindSqliteBackup(
+            settings.database.database_name.clone(),
+        ));
+    }
+    // Unwrap should be safe, database path is a file
+    let backup_file = backup_database_dir.join(database_path.file_name().unwrap());
+
+    let database_key = settings.database.encryption_key()?;
+    let backup_file_key = backup_key.or(database_key.as_deref());
+
+    snapshot_sqlite_database(
+        &database_path,
+        database_key.as_deref(),
+        &backup_file,
+        backup_file_key,
+    )?;
+    check_sqlite_database(&backup_file, backup_file_key)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "postgres")]
+fn snapshot_sqlite_database(_: &Settings, _: &PathBuf, _: Option<&str>) -> Result<(), BackupError> {
+    unreachable!("Only used for sqlite")
+}
+
+fn cleanup_backups(
+    backups_dir: &PathBuf,
+    max_number_of_backups: Option<u32>,
+) -> Result<(), BackupError> {
+    let Some(max_number_of_backups) = &max_number_of_backups else {
+        return Ok(());
+    };
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(backups_dir)?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|f| f.is_dir())
+        .collect();
+
+    paths.sort();
+
+    let max_number_of_backups = *max_number_of_backups as usize;
+    let number_of_backups_to_delete = if paths.len() <= max_number_of_backups {
+        0
+    } else {
+        paths.len() - max_number_of_backups
+    };
+
+    for path in paths.iter().take(number_of_backups_to_delete) {
+        log::info!("Deleting old backup: {path:?}");
+        let _ = fs::remove_dir_all(path);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use super::{files_in, BackupError};
+use aes_gcm::{
+    aead::{
+        stream::{DecryptorBE32, EncryptorBE32},
+        KeyInit,
+    },
+    Aes256Gcm,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Present in the root of encrypted backups
+const ENCRYPTION_FILE: &str = "encryption.json";
+const ENCRYPTED_EXTENSION: &str = "enc";
+#[cfg(not(test))]
+const PBKDF2_ROUNDS: u32 = 600_000;
+// Slow in debug builds
+#[cfg(test)]
+const PBKDF2_ROUNDS: u32 = 1_000;
+/// Rounds accepted from encryption.json, which isn't authenticated
+const MIN_PBKDF2_ROUNDS: u32 = PBKDF2_ROUNDS;
+const MAX_PBKDF2_ROUNDS: u32 = 10 * PBKDF2_ROUNDS;
+/// Files are encrypted in chunks of this size so they are never fully in memory
+const CHUNK_LENGTH: usize = 64 * 1024;
+const TAG_LENGTH: usize = 16;
+/// Nonce prefix of the stream, the remaining 5 bytes of the nonce are the chunk counter and the
+/// last chunk flag
+const NONCE_LENGTH: usize = 7;
+
+#[derive(Serialize, Deserialize)]
+struct BackupEncryption {
+    /// Base64
+    salt: String,
+    rounds: u32,
+}
+
+/// AES-256-GCM with a key derived from the backup key and the backup's salt. Encrypted files are
+/// replaced by `<file name>.enc`, holding the nonce prefix followed by the chunks of the STREAM
+/// construction, each with its own tag, so reordered or truncated chunks fail to decrypt
+pub(super) struct BackupCipher(Aes256Gcm);
+
+/// Reads up to `length` bytes, less only at the end of the file
+fn read_chunk(reader: &mut impl Read, length: usize) -> io::Result<Vec<u8>> {
+    let mut chunk = Vec::with_capacity(length);
+    reader.take(length as u64).read_to_end(&mut chunk)?;
+    Ok(chunk)
+}
+
+impl BackupCipher {
+    /// Cipher with a new salt, recorded in the backup folder
+    pub(super) fn create(backup_dir: &Path, key: &str) -> Result<Self, BackupError> {
+        let salt: [u8; 16] = rand::rng().random();
+        let encryption = BackupEncryption {
+            salt: STANDARD.encode(salt),
+            rounds: PBKDF2_ROUNDS,
+        };
+        fs::write(
+            backup_dir.join(ENCRYPTION_FILE),
+            serde_json::to_string(&encryption).map_err(anyhow::Error::from)?,
+        )?;
+
+        Ok(Self::new(key, &salt, encryption.rounds))
+    }
+
+    /// Cipher of an encrypted backup, see `is_encrypted`
+    pub(super) fn open(backup_dir: &Path, key: &str) -> Result<Self, BackupError> {
+        let encryption: BackupEncryption =
+            serde_json::from_str(&fs::read_to_string(backup_dir.join(ENCRYPTION_FILE))?)
+                .map_err(anyhow::Error::from)?;
+        if !(MIN_PBKDF2_ROUNDS..=MAX_PBKDF2_ROUNDS).contains(&encryption.rounds) {
+            return Err(BackupError::InvalidEncryptionRounds(
+                encryption.rounds,
+                MIN_PBKDF2_ROUNDS,
+                MAX_PBKDF2_ROUNDS,
+            ));
+        }
+        let salt = STANDARD
+            .decode(&encryption.salt)
+            .map_err(anyhow::Error::from)?;
+
+        Ok(Self::new(key, &salt, encryption.rounds))
+    }
+
+    pub(super) fn is_encrypted(backup_dir: &Path) -> bool {
+        backup_dir.join(ENCRYPTION_FILE).exists()
+    }
+
+    fn new(key: &str, salt: &[u8], rounds: u32) -> Self {
+        let mut derived_key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(key.as_bytes(), salt, rounds, &mut derived_key);
+        BackupCipher(Aes256Gcm::new(&derived_key.into()))
+    }
+
+    /// Encrypts every file in the folder and its sub folders
+    pub(super) fn encrypt_dir(&self, dir: &Path) -> Result<(), BackupError> {
+        for file in files_in(dir)? {
+            let mut encrypted_file = file.clone().into_os_string();
+            encrypted_file.push(format!(".{ENCRYPTED_EXTENSION}"));
+
+            if let Err(error) = self.encrypt_file(&file, Path::new(&encrypted_file)) {
+                let _ = fs::remove_file(&encrypted_file);
+                return Err(error);
+            }
+            fs::remove_file(file)?;
+        }
+        Ok(())
+    }
+
+    /// Decrypts the files encrypted by `encrypt_dir`, fails with a wrong key
+    pub(super) fn decrypt_dir(&self, dir: &Path) -> Result<(), BackupError> {
+        for file in files_in(dir)? {
+            if file.extension().and_then(|e| e.to_str()) != Some(ENCRYPTED_EXTENSION) {
+                continue;
+            }
+
+            let decrypted_file = file.with_extension("");
+            if let Err(error) = self.decrypt_file(&file, &decrypted_file) {
+                let _ = fs::remove_file(&decrypted_file);
+                return Err(error);
+            }
+            fs::remove_file(file)?;
+        }
+        Ok(())
+    }
+
+    fn encrypt_file(&self, file: &Path, encrypted_file: &Path) -> Result<(), BackupError> {
+        let failed = |_| BackupError::EncryptionFailed(file.to_path_buf());
+        let nonce: [u8; NONCE_LENGTH] = rand::rng().random();
+        let mut encryptor = EncryptorBE32::from_aead(self.0.clone(), nonce.as_slice().into());
+
+        let mut reader = File::open(file)?;
+        let mut writer = BufWriter::new(File::create(encrypted_file)?);
+        writer.write_all(&nonce)?;
+        // A shorter chunk is the last one, it's empty when the file is a multiple of the chunk
+        // length
+        let last_chunk = loop {
+            let chunk = read_chunk(&mut reader, CHUNK_LENGTH)?;
+            if chunk.len() < CHUNK_LENGTH {
+                break chunk;
+            }
+            writer.write_all(&encryptor.encrypt_next(chunk.as_slice()).map_err(failed)?)?;
+        };
+        let last_chunk = encryptor
+            .encrypt_last(last_chunk.as_slice())
+            .map_err(failed)?;
+        writer.write_all(&last_chunk)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn decrypt_file(&self, file: &Path, decrypted_file: &Path) -> Result<(), BackupError> {
+        let failed = |_| BackupError::DecryptionFailed(file.to_path_buf());
+        let mut reader = File::open(file)?;
+        let nonce = read_chunk(&mut reader, NONCE_LENGTH)?;
+        if nonce.len() < NONCE_LENGTH {
+            return Err(BackupError::DecryptionFailed(file.to_path_buf()));
+        }
+        let mut decryptor = DecryptorBE32::from_aead(self.0.clone(), nonce.as_slice().into());
+
+        let mut writer = BufWriter::new(File::create(decrypted_file)?);
+        let last_chunk = loop {
+            let chunk = read_chunk(&mut reader, CHUNK_LENGTH + TAG_LENGTH)?;
+            if chunk.len() < CHUNK_LENGTH + TAG_LENGTH {
+                break chunk;
+            }
+            writer.write_all(&decryptor.decrypt_next(chunk.as_slice()).map_err(failed)?)?;
+        };
+        let last_chunk = decryptor
+            .decrypt_last(last_chunk.as_slice())
+            .map_err(failed)?;
+        writer.write_all(&last_chunk)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backup_cipher() {
+        let backup_dir = std::env::temp_dir().join("test_backup_cipher");
+        let _ = fs::remove_dir_all(&backup_dir);
+        let files_dir = backup_dir.join("files").join("plugins");
+        fs::create_dir_all(&files_dir).unwrap();
+        fs::write(files_dir.join("plugin.json"), "content").unwrap();
+        // Spans several chunks, the last one empty
+        let large_content: Vec<u8> = (0..2 * CHUNK_LENGTH).map(|i| i as u8).collect();
+        fs::write(files_dir.join("large"), &large_content).unwrap();
+
+        let cipher = BackupCipher::create(&backup_dir, "backup key").unwrap();
+        cipher.encrypt_dir(&backup_dir.join("files")).unwrap();
+        assert!(!files_dir.join("plugin.json").exists());
+        assert!(files_dir.join("plugin.json.enc").exists());
+
+        assert!(BackupCipher::is_encrypted(&backup_dir));
+        let wrong_key = BackupCipher::open(&backup_dir, "wrong").unwrap();
+        assert!(matches!(
+            wrong_key.decrypt_dir(&backup_dir.join("files")),
+            Err(BackupError::DecryptionFailed(_))
+        ));
+
+        assert!(!files_dir.join("plugin.json").exists());
+
+        let cipher = BackupCipher::open(&backup_dir, "backup key").unwrap();
+        cipher.decrypt_dir(&backup_dir.join("files")).unwrap();
+        assert_eq!(
+            fs::read_to_string(files_dir.join("plugin.json")).unwrap(),
+            "content"
+        );
+        assert_eq!(fs::read(files_dir.join("large")).unwrap(), large_content);
+
+        // Dropping the last chunk is detected
+        cipher.encrypt_dir(&backup_dir.join("files")).unwrap();
+        let encrypted = fs::read(files_dir.join("large.enc")).unwrap();
+        fs::write(
+            files_dir.join("large.enc"),
+            &encrypted[..NONCE_LENGTH + 2 * (CHUNK_LENGTH + TAG_LENGTH)],
+        )
+        .unwrap();
+        assert!(matches!(
+            cipher.decrypt_dir(&backup_dir.join("files")),
+            Err(BackupError::DecryptionFailed(_))
+        ));
+        assert!(!files_dir.join("large").exists());
+
+        // Rounds from a tampered encryption.json
+        fs::write(
+            backup_dir.join(ENCRYPTION_FILE),
+            r#"{"salt":"AAAAAAAAAAAAAAAAAAAAAA==","rounds":0}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            BackupCipher::open(&backup_dir, "backup key"),
+            Err(BackupError::InvalidEncryptionRounds(0, _, _))
+        ));
+
+        fs::remove_dir_all(&backup_dir).unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use super::{files_in, BackupError, BACKUP_DATABASE_DIR};
+use chrono::{NaiveDateTime, Utc};
+use repository::migrations::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// Present in the root of backups, older backups don't have one
+const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub(super) struct BackupManifest {
+    pub(super) app_version: String,
+    pub(super) created_datetime: NaiveDateTime,
+    /// sqlite or postgres
+    pub(super) database: String,
+    /// Sha256 (hex) of every file in the backup, by path relative to the backup folder
+    pub(super) files: BTreeMap<String, String>,
+}
+
+impl BackupManifest {
+    /// Checksums the files of a completed backup and records them in the backup folder
+    pub(super) fn write(
+        backup_dir: &Path,
+        created_datetime: NaiveDateTime,
+    ) -> Result<Self, BackupError> {
+        let manifest = BackupManifest {
+            app_version: Version::from_package_json().to_string(),
+            created_datetime,
+            database: BACKUP_DATABASE_DIR.to_string(),
+            files: checksums(backup_dir)?,
+        };
+        fs::write(
+            backup_dir.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest).map_err(anyhow::Error::from)?,
+        )?;
+
+        Ok(manifest)
+    }
+
+    pub(super) fn read(backup_dir: &Path) -> Result<Option<Self>, BackupError> {
+        let manifest_file = backup_dir.join(MANIFEST_FILE);
+        if !manifest_file.exists() {
+            return Ok(None);
+        }
+        let manifest = serde_json::from_str(&fs::read_to_string(manifest_file)?)
+            .map_err(anyhow::Error::from)?;
+
+        Ok(Some(manifest))
+    }
+
+    /// Every file in the manifest exists and matches its checksum
+    pub(super) fn verify(&self, backup_dir: &Path) -> Result<(), BackupError> {
+        for (file, expected) in &self.files {
+            let path = backup_dir.join(file);
+            match checksum(&path) {
+                Ok(checksum) if &checksum == expected => continue,
+                Ok(_) => return Err(BackupError::ChecksumMismatch(path)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    return Err(BackupError::ChecksumMismatch(path))
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Time backups are named after
+pub(super) fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+fn checksums(backup_dir: &Path) -> Result<BTreeMap<String, String>, BackupError> {
+    let mut checksums = BTreeMap::new();
+    for file in files_in(backup_dir)? {
+        // Unwrap is safe, files_in returns paths inside the folder
+        let relative = file.strip_prefix(backup_dir).unwrap();
+        if relative == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+        // Same key on all platforms
+        let key = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        checksums.insert(key, checksum(&file)?);
+    }
+    Ok(checksums)
+}
+
+fn checksum(file: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut fs::File::open(file)?, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backup_manifest() {
+        let backup_dir = std::env::temp_dir().join("test_backup_manifest");
+        let _ = fs::remove_dir_all(&backup_dir);
+        let files_dir = backup_dir.join("files").join("plugins");
+        fs::create_dir_all(&files_dir).unwrap();
+        fs::write(files_dir.join("plugin.json"), "content").unwrap();
+
+        let manifest = BackupManifest::write(&backup_dir, now()).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["files/plugins/plugin.json"]
+        );
+        assert_eq!(BackupManifest::read(&backup_dir).unwrap(), Some(manifest));
+
+        let manifest = BackupManifest::read(&backup_dir).unwrap().unwrap();
+        manifest.verify(&backup_dir).unwrap();
+
+        fs::write(files_dir.join("plugin.json"), "changed").unwrap();
+        assert!(matches!(
+            manifest.verify(&backup_dir),
+            Err(BackupError::ChecksumMismatch(_))
+        ));
+        fs::remove_file(files_dir.join("plugin.json")).unwrap();
+        assert!(matches!(
+            manifest.verify(&backup_dir),
+            Err(BackupError::ChecksumMismatch(_))
+        ));
+
+        fs::remove_dir_all(&backup_dir).unwrap();
+    }
+}
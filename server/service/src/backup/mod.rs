@@ -1,19 +1,27 @@
 mod backup;
-pub(super) use self::backup::*;
+pub use self::backup::*;
+mod encryption;
+mod manifest;
+mod point_in_time;
 mod restore;
-pub(super) use self::restore::*;
+pub use self::restore::*;
+mod status;
+pub use self::status::*;
 
 use std::env::VarError;
 use std::fs;
+use std::path::Path;
 use std::str::FromStr;
 use std::{io, path::PathBuf};
 
+use chrono::NaiveDateTime;
+use repository::database_encryption::{DatabaseKeyError, BACKUP_KEYSTORE_SERVICE};
 use repository::RepositoryError;
-use service::settings::BackupSettings;
-use service::settings::Settings;
 use shellexpand::LookupError;
 use thiserror::Error;
 
+use crate::settings::{BackupSettings, Settings};
+
 const BACKUP_FILE_DIR: &str = "files";
 
 #[cfg(feature = "postgres")]
@@ -22,7 +30,7 @@ const BACKUP_DATABASE_DIR: &'static str = "postgres";
 const BACKUP_DATABASE_DIR: &str = "sqlite";
 
 #[derive(Error, Debug)]
-pub(super) enum BackupError {
+pub enum BackupError {
     #[error("Cannot find pg_dump or pg_restore executable in PATH, add it to PATH or specify Postgres bin directory in the configuration file")]
     PgCommandNotFoundInPath,
     #[error("Cannot find pg_dump or pg_restore executable in Postgres bin directory specified in configurations")]
@@ -41,9 +49,9 @@ pub(super) enum BackupError {
     StdIO(#[from] io::Error),
     #[error("Error while executing command line: {0:#?}")]
     CommandLineError(std::process::Output),
-    #[error("Invalid sqlite backup file: {0}")]
+    #[error("Invalid sqlite file {0}")]
     InvalidSqliteFile(PathBuf),
-    #[error("Failed to confirm restore")]
+    #[error("Restore was not confirmed")]
     RestoreNotConfirmed,
     #[error("Backup configurations needs to be specified in configuration files")]
     BackupConfigurationMissing,
@@ -51,20 +59,33 @@ pub(super) enum BackupError {
     ErrorWhileConvertingPath(LookupError<VarError>, String),
     #[error("Issue opening backup folder {1}")]
     BackupFolderNotExist(#[source] io::Error, PathBuf),
+    #[error("Backup is encrypted, backup encryption needs to be specified in configuration files")]
+    BackupEncryptionMissing,
+    #[error("Problem encrypting {0}")]
+    EncryptionFailed(PathBuf),
+    #[error("Problem decrypting {0}, check the backup key")]
+    DecryptionFailed(PathBuf),
+    #[error("Backup encryption.json has {0} rounds, expected {1} to {2}")]
+    InvalidEncryptionRounds(u32, u32, u32),
+    #[error("Backup file {0} is missing or doesn't match its checksum, the backup is damaged")]
+    ChecksumMismatch(PathBuf),
+    #[error("Scheduled backups can't prompt for the backup key, use key_source: Keystore")]
+    ScheduledBackupKeyPrompt,
+    #[error("Point in time restore needs postgres, with wal_archive_dir and pg_data_dir in backup configurations")]
+    PointInTimeNotConfigured,
+    #[error("Backup {0} has no base backup for point in time restore (taken without wal_archive_dir configured)")]
+    NoBaseBackup(String),
+    #[error("Backup was taken at {0}, after the requested point in time")]
+    PointInTimeBeforeBackup(NaiveDateTime),
+    #[error("Postgres is running ({0} exists), stop it before a point in time restore")]
+    PostgresRunning(PathBuf),
+    #[error(transparent)]
+    DatabaseKey(#[from] DatabaseKeyError),
     #[error(transparent)]
     DatabaseError(#[from] RepositoryError),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
-#[derive(clap::Parser, Debug)]
-pub(super) struct RestoreArguments {
-    /// Name of backup in directory specified by backup configurations
-    #[clap(short, long)]
-    backup_name: String,
-    /// In dev can specify this to skip confirmation
-    #[clap(short, long)]
-    skip_confirmation: bool,
-}
 
 struct DirSettings {
     backup_dir: String,
@@ -81,17 +102,8 @@ fn get_dirs_from_settings(settings: &Settings) -> Result<DirSettings, BackupErro
         return Err(BackupError::BackupConfigurationMissing);
     };
 
-    // Shell expand is mainly used to replace `~` with full path of home directory
-    let backup_dir = shellexpand::full(&backup_dir)
-        .map_err(|e| BackupError::ErrorWhileConvertingPath(e, backup_dir.clone()))?
-        .to_string();
-    let pg_bin_dir = pg_bin_dir
-        .map(|d| {
-            shellexpand::full(&d)
-                .map_err(|e| BackupError::ErrorWhileConvertingPath(e, d.clone()))
-                .map(|s| s.to_string())
-        })
-        .transpose()?;
+    let backup_dir = expand_path(&backup_dir)?;
+    let pg_bin_dir = pg_bin_dir.map(|d| expand_path(&d)).transpose()?;
 
     Ok(DirSettings {
         backup_dir,
@@ -99,6 +111,13 @@ fn get_dirs_from_settings(settings: &Settings) -> Result<DirSettings, BackupErro
     })
 }
 
+// Shell expand is mainly used to replace `~` with full path of home directory
+fn expand_path(path: &str) -> Result<String, BackupError> {
+    Ok(shellexpand::full(path)
+        .map_err(|e| BackupError::ErrorWhileConvertingPath(e, path.to_string()))?
+        .to_string())
+}
+
 fn get_base_dir(settings: &Settings) -> Result<PathBuf, BackupError> {
     let base_dir = PathBuf::from_str(&settings.server.base_dir)
         .map_err(|_| BackupError::InvalidPath(settings.server.base_dir.clone()))?;
@@ -126,3 +145,33 @@ fn get_sqlite_files_paths(settings: &Settings) -> Result<Vec<PathBuf>, BackupErr
 
     Ok(paths)
 }
+
+/// Key backups are encrypted with, None when backup encryption is not configured
+fn get_backup_key(settings: &Settings) -> Result<Option<String>, BackupError> {
+    let Some(encryption) = settings
+        .backup
+        .as_ref()
+        .and_then(|backup| backup.encryption.as_ref())
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(encryption.resolve_key(
+        BACKUP_KEYSTORE_SERVICE,
+        &settings.database.database_name,
+    )?))
+}
+
+/// Files in the folder and its sub folders
+fn files_in(dir: &Path) -> Result<Vec<PathBuf>, BackupError> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
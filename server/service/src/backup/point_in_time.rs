@@ -0,0 +1,197 @@
+//! Point in time restore for postgres. Backups include a base backup of the postgres cluster
+//! (pg_basebackup) when `wal_archive_dir` is configured, and postgres archives its write ahead log
+//! to that folder (`archive_mode = on`, `archive_command`). Restoring replaces the cluster's data
+//! directory with the base backup and configures postgres to replay the archived WAL up to the
+//! requested time when it next starts
+
+use super::{
+    backup::{pg_command, run_pg_command},
+    encryption::BackupCipher,
+    expand_path, BackupError,
+};
+use crate::settings::Settings;
+use chrono::{NaiveDateTime, Utc};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const BASE_BACKUP_DIR: &str = "postgres_base";
+
+pub(super) fn base_backup_postgres(
+    settings: &Settings,
+    backup_dir: &Path,
+    pg_bin_dir_opt: Option<String>,
+    wal_archive_dir: &str,
+) -> Result<(), BackupError> {
+    let wal_archive_dir = expand_path(wal_archive_dir)?;
+    if !Path::new(&wal_archive_dir).is_dir() {
+        log::warn!(
+            "WAL archive folder {wal_archive_dir} doesn't exist, check postgres archive_command"
+        );
+    }
+
+    let base_backup_dir = backup_dir.join(BASE_BACKUP_DIR);
+    let mut command = pg_command(&pg_bin_dir_opt, "pg_basebackup")?;
+    command.args([
+        "--pgdata",
+        base_backup_dir.to_str().unwrap(),
+        "--format",
+        "tar",
+        "--gzip",
+        // WAL needed to make the base backup consistent is included, WAL after it comes from the
+        // archive
+        "--wal-method",
+        "fetch",
+        "--dbname",
+        &settings.database.connection_string(),
+    ]);
+
+    run_pg_command(command, &pg_bin_dir_opt)
+}
+
+pub(super) fn encrypt_base_backup(
+    cipher: &BackupCipher,
+    backup_dir: &Path,
+) -> Result<(), BackupError> {
+    let base_backup_dir = backup_dir.join(BASE_BACKUP_DIR);
+    if base_backup_dir.exists() {
+        cipher.encrypt_dir(&base_backup_dir)?;
+    }
+    Ok(())
+}
+
+/// Replaces the postgres data directory with the base backup of the backup at `backup_dir`, set
+/// to recover up to `target` (UTC). Postgres must be stopped, the current data directory is kept
+/// next to it. Returns the folder the current data directory was moved to
+pub(super) fn restore_point_in_time(
+    settings: &Settings,
+    backup_name: &str,
+    backup_dir: &Path,
+    cipher: Option<&BackupCipher>,
+    backup_created_datetime: NaiveDateTime,
+    target: NaiveDateTime,
+) -> Result<PathBuf, BackupError> {
+    let backup_settings = settings.backup.as_ref();
+    let (Some(wal_archive_dir), Some(pg_data_dir), true) = (
+        backup_settings.and_then(|b| b.wal_archive_dir.as_ref()),
+        backup_settings.and_then(|b| b.pg_data_dir.as_ref()),
+        cfg!(feature = "postgres"),
+    ) else {
+        return Err(BackupError::PointInTimeNotConfigured);
+    };
+    let wal_archive_dir = PathBuf::from(expand_path(wal_archive_dir)?);
+    let pg_data_dir = PathBuf::from(expand_path(pg_data_dir)?);
+
+    if target < backup_created_datetime {
+        return Err(BackupError::PointInTimeBeforeBackup(
+            backup_created_datetime,
+        ));
+    }
+    let base_backup_dir = backup_dir.join(BASE_BACKUP_DIR);
+    if !base_backup_dir.is_dir() {
+        return Err(BackupError::NoBaseBackup(backup_name.to_string()));
+    }
+    let postmaster_pid = pg_data_dir.join("postmaster.pid");
+    if postmaster_pid.exists() {
+        return Err(BackupError::PostgresRunning(postmaster_pid));
+    }
+
+    // Decrypted outside of the backup folder, so the backup stays encrypted
+    let decrypted_dir = match cipher {
+        Some(cipher) => Some(super::restore::decrypted_copy(
+            cipher,
+            &base_backup_dir,
+            &format!("{backup_name}_{BASE_BACKUP_DIR}"),
+        )?),
+        None => None,
+    };
+    let result = replace_data_dir(
+        decrypted_dir.as_deref().unwrap_or(&base_backup_dir),
+        &pg_data_dir,
+        &wal_archive_dir,
+        target,
+    );
+    if let Some(decrypted_dir) = decrypted_dir {
+        let _ = fs::remove_dir_all(decrypted_dir);
+    }
+
+    result
+}
+
+fn replace_data_dir(
+    base_backup_dir: &Path,
+    pg_data_dir: &Path,
+    wal_archive_dir: &Path,
+    target: NaiveDateTime,
+) -> Result<PathBuf, BackupError> {
+    let mut previous_data_dir = pg_data_dir.as_os_str().to_owned();
+    previous_data_dir.push(
+        Utc::now()
+            .naive_utc()
+            .format("_before_restore_D%Y_%m_%dT%H_%M_%S")
+            .to_string(),
+    );
+    let previous_data_dir = PathBuf::from(previous_data_dir);
+    if pg_data_dir.exists() {
+        fs::rename(pg_data_dir, &previous_data_dir)?;
+    }
+    create_data_dir(pg_data_dir)?;
+
+    extract(&base_backup_dir.join("base.tar.gz"), pg_data_dir)?;
+    // Only present when WAL was streamed rather than fetched
+    let wal_tar = base_backup_dir.join("pg_wal.tar.gz");
+    if wal_tar.exists() {
+        extract(&wal_tar, &pg_data_dir.join("pg_wal"))?;
+    }
+
+    let archived_wal = wal_archive_dir
+        .join("%f")
+        .to_string_lossy()
+        .replace('\'', "''");
+    let restore_command = if cfg!(windows) {
+        format!(r#"copy "{archived_wal}" "%p""#)
+    } else {
+        format!(r#"cp "{archived_wal}" "%p""#)
+    };
+    let mut auto_conf = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(pg_data_dir.join("postgresql.auto.conf"))?;
+    writeln!(
+        auto_conf,
+        "\n# Point in time restore by omSupply\nrestore_command = '{restore_command}'\nrecovery_target_time = '{} UTC'\nrecovery_target_action = 'promote'",
+        target.format("%Y-%m-%d %H:%M:%S")
+    )?;
+    fs::write(pg_data_dir.join("recovery.signal"), "")?;
+
+    Ok(previous_data_dir)
+}
+
+fn create_data_dir(pg_data_dir: &Path) -> Result<(), BackupError> {
+    fs::create_dir_all(pg_data_dir)
+        .map_err(|e| BackupError::CannotCreateBackupFolder(e, pg_data_dir.to_path_buf()))?;
+    // Postgres refuses to start with a data directory readable by others
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(pg_data_dir, fs::Permissions::from_mode(0o700))?;
+    }
+    Ok(())
+}
+
+fn extract(tar_file: &Path, to: &Path) -> Result<(), BackupError> {
+    fs::create_dir_all(to)?;
+    let result = Command::new("tar")
+        .arg("-xzf")
+        .arg(tar_file)
+        .arg("-C")
+        .arg(to)
+        .output()?;
+    if !result.status.success() {
+        return Err(BackupError::CommandLineError(result));
+    }
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use super::{
+    backup::{pg_command, run_pg_command},
+    encryption::BackupCipher,
+    manifest::BackupManifest,
+    point_in_time, *,
+};
+use crate::settings::Settings;
+use chrono::NaiveDateTime;
+use copy_dir::copy_dir;
+use repository::database_backup::drop_and_create_database;
+use std::{fs, path::PathBuf, str::FromStr};
+
+pub struct RestoreOptions {
+    /// Name of backup in directory specified by backup configurations
+    pub backup_name: String,
+    /// Postgres only, replay archived WAL up to this time (UTC) after restoring the base backup
+    pub point_in_time: Option<NaiveDateTime>,
+}
+
+pub enum RestoreResult {
+    Restored,
+    /// Postgres data directory was replaced, recovery runs when postgres is started
+    PointInTimePrepared {
+        /// Where the data directory was moved to
+        previous_data_dir: PathBuf,
+    },
+}
+
+/// Replaces app data and the database with the content of a backup. The server must be stopped.
+/// The backup is checked against its manifest before anything is replaced
+pub fn restore(
+    settings: &Settings,
+    RestoreOptions {
+        backup_name,
+        point_in_time,
+    }: RestoreOptions,
+) -> Result<RestoreResult, BackupError> {
+    let DirSettings {
+        backup_dir,
+        pg_bin_dir,
+    } = get_dirs_from_settings(settings)?;
+
+    let Dirs {
+        base_dir,
+        file_dir,
+        database_dir,
+    } = get_backup_dir(backup_dir, backup_name.clone())?;
+
+    // Checking the backup and the key before anything is wiped
+    let manifest = BackupManifest::read(&base_dir)?;
+    if let Some(manifest) = &manifest {
+        manifest.verify(&base_dir)?;
+    }
+    let backup_key = match BackupCipher::is_encrypted(&base_dir) {
+        true => Some(get_backup_key(settings)?.ok_or(BackupError::BackupEncryptionMissing)?),
+        false => None,
+    };
+    let cipher = backup_key
+        .as_deref()
+        .map(|backup_key| BackupCipher::open(&base_dir, backup_key))
+        .transpose()?;
+
+    if let Some(target) = point_in_time {
+        let created_datetime = manifest
+            .as_ref()
+            .map(|manifest| manifest.created_datetime)
+            .ok_or(BackupError::NoBaseBackup(backup_name.clone()))?;
+        let previous_data_dir = point_in_time::restore_point_in_time(
+            settings,
+            &backup_name,
+            &base_dir,
+            cipher.as_ref(),
+            created_datetime,
+            target,
+        )?;
+        restore_files(settings, &file_dir, cipher.as_ref())?;
+        mark_restored(settings, &backup_name)?;
+
+        return Ok(RestoreResult::PointInTimePrepared { previous_data_dir });
+    }
+
+    restore_files(settings, &file_dir, cipher.as_ref())?;
+
+    // Backup database
+    match (cfg!(feature = "postgres"), &cipher, &backup_key) {
+        (true, Some(cipher), _) => {
+            let decrypted_dir = decrypted_copy(cipher, &database_dir, &backup_name)?;
+            let result = restore_postgres_database(settings, &decrypted_dir, pg_bin_dir);
+            let _ = fs::remove_dir_all(&decrypted_dir);
+            result?;
+        }
+        (true, None, _) => restore_postgres_database(settings, &database_dir, pg_bin_dir)?,
+        (false, _, Some(backup_key)) => {
+            import_sqlite_database(settings, &database_dir, backup_key)?
+        }
+        (false, _, None) => copy_sqlite_files(settings, &database_dir)?,
+    }
+
+    mark_restored(settings, &backup_name)?;
+
+    Ok(RestoreResult::Restored)
+}
+
+struct Dirs {
+    base_dir: PathBuf,
+    file_dir: PathBuf,
+    database_dir: PathBuf,
+}
+
+fn get_backup_dir(input_dir: String, backup_name: String) -> Result<Dirs, BackupError> {
+    let base_dir = PathBuf::from_str(&input_dir)
+        .map_err(|_| BackupError::InvalidPath(input_dir.clone()))?
+        .join(backup_name);
+
+    let file_dir = base_dir.join(BACKUP_FILE_DIR);
+
+    let database_dir = base_dir.join(BACKUP_DATABASE_DIR);
+
+    Ok(Dirs {
+        base_dir,
+        file_dir,
+        database_dir,
+    })
+}
+
+/// Decrypted copy of a backup folder, outside of the backup folder so the backup stays encrypted
+pub(super) fn decrypted_copy(
+    cipher: &BackupCipher,
+    dir: &PathBuf,
+    name: &str,
+) -> Result<PathBuf, BackupError> {
+    let decrypted_dir = std::env::temp_dir().join(format!("omsupply_restore_{name}"));
+    let _ = fs::remove_dir_all(&decrypted_dir);
+    copy_dir(dir, &decrypted_dir)
+        .map_err(|e| BackupError::ProblemCopyingFolder(e, dir.clone(), decrypted_dir.clone()))?;
+
+    if let Err(error) = cipher.decrypt_dir(&decrypted_dir) {
+        let _ = fs::remove_dir_all(&decrypted_dir);
+        return Err(error);
+    }
+    Ok(decrypted_dir)
+}
+
+fn restore_files(
+    settings: &Settings,
+    backup_file_dir: &PathBuf,
+    cipher: Option<&BackupCipher>,
+) -> Result<(), BackupError> {
+    copy_files(settings, backup_file_dir)?;
+    if let Some(cipher) = cipher {
+        cipher.decrypt_dir(&get_base_dir(settings)?)?;
+    }
+    Ok(())
+}
+
+fn copy_files(settings: &Settings, backup_file_dir: &PathBuf) -> Result<(), BackupError> {
+    let restore_file_dir = get_base_dir(settings)?;
+    // Wipe existing app_data (files folder) folder
+    let _ = fs::remove_dir_all(&restore_file_dir);
+    fs::create_dir_all(&restore_file_dir)
+        .map_err(|e| BackupError::CannotCreateBackupFolder(e, restore_file_dir.clone()))?;
+
+    for entry in fs::read_dir(backup_file_dir)
+        .map_err(|e| BackupError::BackupFolderNotExist(e, backup_file_dir.clone()))?
+    {
+        let from_dir = entry?.path();
+
+        let (Some(folder_name), true) = (from_dir.file_name(), from_dir.is_dir()) else {
+            continue;
+        };
+
+        let to_dir = restore_file_dir.join(folder_name);
+
+        copy_dir(&from_dir, &to_dir)
+            .map_err(|e| BackupError::ProblemCopyingFolder(e, from_dir, to_dir))?;
+    }
+
+    Ok(())
+}
+
+fn restore_postgres_database(
+    settings: &Settings,
+    backup_database_dir: &PathBuf,
+    pg_bin_dir_opt: Option<String>,
+) -> Result<(), BackupError> {
+    drop_and_create_database(&settings.database)?;
+
+    // Pg restore into database
+    let mut command = pg_command(&pg_bin_dir_opt, "pg_restore")?;
+    command.args([
+        "--format",
+        "d",
+        "--dbname",
+        &settings.database.connection_string(),
+        backup_database_dir.to_str().unwrap(),
+    ]);
+
+    run_pg_command(command, &pg_bin_dir_opt)
+}
+
+fn remove_sqlite_files(settings: &Settings) -> Result<(), BackupError> {
+    let sqlite_files = get_sqlite_files_paths(settings)?;
+
+    for sqlite_filename in sqlite_files {
+        // Unwrap should be safe (would panic only if pathname terminates with '...')
+        let sqlite_filename = sqlite_filename.file_name().unwrap();
+
+        fs::remove_file(sqlite_filename)?;
+    }
+
+    Ok(())
+}
+
+/// Database of an encrypted backup, re-encrypted with the database key (or decrypted when the
+/// database is not encrypted)
+#[cfg(all(feature = "sqlcipher", not(feature = "postgres")))]
+fn import_sqlite_database(
+    settings: &Settings,
+    backup_database_dir: &PathBuf,
+    backup_key: &str,
+) -> Result<(), BackupError> {
+    let backup_file = fs::read_dir(backup_database_dir)
+        .map_err(|e| BackupError::BackupFolderNotExist(e, backup_database_dir.clone()))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.is_file())
+        .ok_or(BackupError::CannotFindSqliteBackup(
+            settings.database.database_name.clone(),
+        ))?;
+    let database_key = settings.database.encryption_key()?;
+
+    remove_sqlite_files(settings)?;
+
+    repository::database_encryption::export_database(
+        &backup_file,
+        Some(backup_key),
+        &PathBuf::from(settings.database.database_path()),
+        database_key.as_deref(),
+    )?;
+
+    Ok(())
+}
+
+// Backup key can't be resolved without sqlcipher
+#[cfg(not(all(feature = "sqlcipher", not(feature = "postgres"))))]
+fn import_sqlite_database(_: &Settings, _: &PathBuf, _: &str) -> Result<(), BackupError> {
+    Err(repository::database_encryption::DatabaseKeyError::NotSupported.into())
+}
+
+fn copy_sqlite_files(
+    settings: &Settings,
+    backup_database_dir: &PathBuf,
+) -> Result<(), BackupError> {
+    // Remove current database files
+    remove_sqlite_files(settings)?;
+
+    // omSupply database name can be specified with .sqlite extension, remove it here
+    let database_name = settings
+        .database
+        .database_name
+        .clone()
+        .replace(".sqlite", "");
+
+    // Move backup files
+    for sqlite_filename in fs::read_dir(backup_database_dir)
+        .map_err(|e| BackupError::BackupFolderNotExist(e, backup_database_dir.clone()))?
+    {
+        let from_file = sqlite_filename?.path();
+        let extension = from_file
+            .extension()
+            .and_then(|e| e.to_str())
+            .ok_or(BackupError::InvalidSqliteFile(from_file.clone()))?;
+        // Preserve database name
+        fs::copy(&from_file, format!("{database_name}.{extension}"))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use super::{backup, get_base_dir, BackupError};
+use crate::{
+    cursor_controller::CursorController,
+    settings::{BackupSettings, Settings},
+    sync::CentralServerConfig,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    database_encryption::DatabaseKeySource, KeyType, KeyValueStoreRepository, RepositoryError,
+    SiteBackupStatusRow, SiteBackupStatusRowRepository, StorageConnection,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Written to the app data folder by restore, picked up by the server on startup
+const RESTORED_MARKER_FILE: &str = "restored_from_backup";
+
+/// Outcome of the last backup, kept in the key value store and reported to the central server
+/// with site status
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BackupStatus {
+    pub last_attempt_datetime: NaiveDateTime,
+    pub last_success_datetime: Option<NaiveDateTime>,
+    pub last_backup_name: Option<String>,
+    /// Last attempt produced a backup that was opened and checksummed
+    pub is_verified: bool,
+    /// Error of the last attempt
+    pub error: Option<String>,
+}
+
+pub fn get_backup_status(
+    connection: &StorageConnection,
+) -> Result<Option<BackupStatus>, RepositoryError> {
+    let status = KeyValueStoreRepository::new(connection).get_string(KeyType::BackupStatus)?;
+    // Unparsable status is treated as no status
+    Ok(status.and_then(|status| serde_json::from_str(&status).ok()))
+}
+
+/// Records the result of a backup (see `backup`) as the latest backup status
+pub fn record_backup_result(
+    connection: &StorageConnection,
+    result: &Result<String, BackupError>,
+) -> Result<BackupStatus, RepositoryError> {
+    let previous = get_backup_status(connection)?;
+    let now = Utc::now().naive_utc();
+
+    let status = match result {
+        Ok(backup_name) => BackupStatus {
+            last_attempt_datetime: now,
+            last_success_datetime: Some(now),
+            last_backup_name: Some(backup_name.clone()),
+            is_verified: true,
+            error: None,
+        },
+        Err(error) => BackupStatus {
+            last_attempt_datetime: now,
+            last_success_datetime: previous
+                .as_ref()
+                .and_then(|previous| previous.last_success_datetime),
+            last_backup_name: previous.and_then(|previous| previous.last_backup_name),
+            is_verified: false,
+            error: Some(error.to_string()),
+        },
+    };
+
+    KeyValueStoreRepository::new(connection).set_string(
+        KeyType::BackupStatus,
+        Some(serde_json::to_string(&status).map_err(|error| {
+            RepositoryError::as_db_error("Failed to serialise backup status", error)
+        })?),
+    )?;
+
+    // Central server records its own status with the statuses reported by sites
+    if CentralServerConfig::is_central_server() {
+        if let Some(site_id) =
+            KeyValueStoreRepository::new(connection).get_i32(KeyType::SettingsSyncSiteId)?
+        {
+            upsert_site_backup_status(connection, site_id, &status)?;
+        }
+    }
+
+    Ok(status)
+}
+
+/// Backup status reported by a site, on central server
+pub fn upsert_site_backup_status(
+    connection: &StorageConnection,
+    site_id: i32,
+    status: &BackupStatus,
+) -> Result<(), RepositoryError> {
+    let BackupStatus {
+        last_attempt_datetime,
+        last_success_datetime,
+        last_backup_name,
+        is_verified,
+        error,
+    } = status.clone();
+
+    SiteBackupStatusRowRepository::new(connection).upsert_one(&SiteBackupStatusRow {
+        site_id,
+        last_attempt_datetime,
+        last_success_datetime,
+        last_backup_name,
+        is_verified,
+        error,
+        received_datetime: Utc::now().naive_utc(),
+    })
+}
+
+/// Runs a backup if backups are scheduled (`schedule_interval_hours`) and the last attempt is
+/// older than the interval. Blocking, returns the new status when a backup was attempted
+pub fn run_scheduled_backup(
+    connection: &StorageConnection,
+    settings: &Settings,
+) -> Result<Option<BackupStatus>, RepositoryError> {
+    let Some(BackupSettings {
+        schedule_interval_hours: Some(interval_hours),
+        encryption,
+        ..
+    }) = &settings.backup
+    else {
+        return Ok(None);
+    };
+
+    let last_attempt = get_backup_status(connection)?.map(|status| status.last_attempt_datetime);
+    let is_due = match last_attempt {
+        Some(last_attempt) => {
+            Utc::now().naive_utc() - last_attempt >= Duration::hours(*interval_hours as i64)
+        }
+        None => true,
+    };
+    if !is_due {
+        return Ok(None);
+    }
+
+    let result = match encryption {
+        // Would block the server waiting for console input
+        Some(encryption) if encryption.key_source == DatabaseKeySource::Prompt => {
+            Err(BackupError::ScheduledBackupKeyPrompt)
+        }
+        _ => backup(settings),
+    };
+
+    record_backup_result(connection, &result).map(Some)
+}
+
+/// Leaves a marker for the server, so it re-syncs the restored database on startup (see
+/// `apply_restore_marker`)
+pub(super) fn mark_restored(settings: &Settings, backup_name: &str) -> Result<(), BackupError> {
+    fs::write(
+        get_base_dir(settings)?.join(RESTORED_MARKER_FILE),
+        backup_name,
+    )?;
+    Ok(())
+}
+
+/// Called on server startup. After a restore, records that were pushed to the central server
+/// after the backup was taken are missing locally, and changes that were pending at backup time
+/// may have been pushed already. The next sync pulls everything for the site again without
+/// pushing, the central server's copy wins. Returns the name of the restored backup
+pub fn apply_restore_marker(
+    connection: &StorageConnection,
+    settings: &Settings,
+) -> Result<Option<String>, BackupError> {
+    let marker = get_base_dir(settings)?.join(RESTORED_MARKER_FILE);
+    if !marker.exists() {
+        return Ok(None);
+    }
+    let backup_name = fs::read_to_string(&marker)?;
+
+    KeyValueStoreRepository::new(connection)
+        .set_bool(KeyType::SyncResyncAfterRestore, Some(true))?;
+    CursorController::new(KeyType::SyncPullCursorV6).update(connection, 0)?;
+    fs::remove_file(marker)?;
+
+    Ok(Some(backup_name))
+}
+
+pub fn is_resync_after_restore(connection: &StorageConnection) -> Result<bool, RepositoryError> {
+    Ok(KeyValueStoreRepository::new(connection)
+        .get_bool(KeyType::SyncResyncAfterRestore)?
+        .unwrap_or(false))
+}
+
+pub fn clear_resync_after_restore(connection: &StorageConnection) -> Result<(), RepositoryError> {
+    KeyValueStoreRepository::new(connection).set_bool(KeyType::SyncResyncAfterRestore, None)
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::MockDataInserts,
+        test_db::{get_test_db_settings, setup_all},
+    };
+
+    use crate::settings::{DiscoveryMode, ServerSettings};
+
+    use super::*;
+
+    fn test_settings(base_dir: &str) -> Settings {
+        Settings {
+            server: ServerSettings {
+                port: 0,
+                danger_allow_http: false,
+                discovery: DiscoveryMode::Disabled,
+                debug_no_access_control: false,
+                cors_origins: vec![],
+                base_dir: base_dir.to_string(),
+                machine_uid: None,
+                override_is_central_server: false,
+            },
+            database: get_test_db_settings("n/a"),
+            sync: None,
+            logging: None,
+            backup: None,
+            mail: None,
+            features: None,
+            graphql: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_backup_status() {
+        let (_, connection, _, _) = setup_all("test_backup_status", MockDataInserts::none()).await;
+
+        assert_eq!(get_backup_status(&connection).unwrap(), None);
+
+        let status =
+            record_backup_result(&connection, &Ok("D2026_01_01T00_00_00".to_string())).unwrap();
+        assert!(status.is_verified);
+        assert_eq!(
+            get_backup_status(&connection).unwrap(),
+            Some(status.clone())
+        );
+
+        // Failure keeps the last successful backup
+        let failed =
+            record_backup_result(&connection, &Err(BackupError::BackupConfigurationMissing))
+                .unwrap();
+        assert!(!failed.is_verified);
+        assert_eq!(failed.last_success_datetime, status.last_success_datetime);
+        assert_eq!(
+            failed.last_backup_name,
+            Some("D2026_01_01T00_00_00".to_string())
+        );
+        assert!(failed.error.is_some());
+
+        // Not scheduled
+        assert_eq!(
+            run_scheduled_backup(&connection, &test_settings("n/a")).unwrap(),
+            None
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_apply_restore_marker() {
+        let (_, connection, _, _) =
+            setup_all("test_apply_restore_marker", MockDataInserts::none()).await;
+        let base_dir = std::env::temp_dir().join("test_apply_restore_marker");
+        let _ = fs::remove_dir_all(&base_dir);
+        let settings = test_settings(&base_dir.to_string_lossy());
+        CursorController::new(KeyType::SyncPullCursorV6)
+            .update(&connection, 100)
+            .unwrap();
+
+        assert_eq!(apply_restore_marker(&connection, &settings).unwrap(), None);
+        assert!(!is_resync_after_restore(&connection).unwrap());
+
+        mark_restored(&settings, "D2026_01_01T00_00_00").unwrap();
+        assert_eq!(
+            apply_restore_marker(&connection, &settings).unwrap(),
+            Some("D2026_01_01T00_00_00".to_string())
+        );
+        assert!(is_resync_after_restore(&connection).unwrap());
+        assert_eq!(
+            CursorController::new(KeyType::SyncPullCursorV6)
+                .get(&connection)
+                .unwrap(),
+            0
+        );
+        // Marker is removed
+        assert_eq!(apply_restore_marker(&connection, &settings).unwrap(), None);
+
+        clear_resync_after_restore(&connection).unwrap();
+        assert!(!is_resync_after_restore(&connection).unwrap());
+
+        fs::remove_dir_all(&base_dir).unwrap();
+    }
+}
@@ -36,6 +36,8 @@ pub(crate) fn call_method<I, O>(
     // reports export { convert_data } thus we look for vec!["convert_data"]
     export_location: Vec<&str>,
     bundle: &Vec<u8>,
+    // Code of the calling plugin, none for report convert_data which can't use plugin tables
+    plugin_code: Option<&str>,
 ) -> Result<O, BoaJsError>
 where
     I: Serialize,
@@ -64,7 +66,7 @@ where
     methods::sql_type::bind_method(context)?;
     methods::get_plugin_data::bind_method(context)?;
     methods::get_store_preferences::bind_method(context)?;
-    methods::use_repository::bind_method(context)?;
+    methods::use_repository::bind_method(context, plugin_code)?;
     methods::use_graphql::bind_method(context)?;
     methods::get_active_stores_on_site::bind_method(context)?;
 
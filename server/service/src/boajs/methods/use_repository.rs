@@ -1,7 +1,8 @@
 use boa_engine::*;
 use repository::{
     DaysOutOfStockFilter, DaysOutOfStockRepository, DaysOutOfStockRow, PluginDataRow,
-    PluginDataRowRepository, SyncMessageRow, SyncMessageRowRepository,
+    PluginDataRowRepository, PluginTableRow, PluginTableRowRepository, StorageConnection,
+    SyncMessageRow, SyncMessageRowRepository,
 };
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -15,6 +16,9 @@ pub(crate) enum UseRepositoryInput {
     UpsertPluginData(PluginDataRow),
     UpsertSyncMessage(SyncMessageRow),
     GetDaysOutOfStock(DaysOutOfStockFilter),
+    // Tables declared in plugin manifest migrations, can be queried with `sql` method
+    UpsertPluginTableRow(PluginTableRow),
+    DeletePluginTableRow { table_name: String, id: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -24,54 +28,94 @@ pub(crate) enum UseRepositoryOutput {
     UpsertSyncMessage(i64),
     UpsertPluginData(i64),
     GetDaysOutOfStock(Vec<DaysOutOfStockRow>),
+    UpsertPluginTableRow(i64),
+    DeletePluginTableRow(Option<i64>),
 }
 
-pub(crate) fn bind_method(context: &mut Context) -> Result<(), JsError> {
+/// Plugin table methods are limited to tables declared by the calling plugin's migrations
+fn validate_plugin_table(
+    connection: &StorageConnection,
+    plugin_code: &Option<String>,
+    table_name: &str,
+) -> JsResult<()> {
+    let plugin_code = plugin_code
+        .as_deref()
+        .ok_or_else(|| string_to_js_error("Plugin tables can only be used by plugins"))?;
+
+    PluginTableRowRepository::new(connection)
+        .validate_plugin_table(plugin_code, table_name)
+        .map_err(std_error_to_js_error)
+}
+
+pub(crate) fn bind_method(context: &mut Context, plugin_code: Option<&str>) -> Result<(), JsError> {
     context.register_global_callable(
         JsString::from("use_repository"),
         0,
-        NativeFunction::from_copy_closure(move |_, args, ctx| {
-            let input: UseRepositoryInput = get_serde_argument(ctx, args, 0)?;
+        NativeFunction::from_copy_closure_with_captures(
+            move |_, args, plugin_code: &Option<String>, ctx| {
+                let input: UseRepositoryInput = get_serde_argument(ctx, args, 0)?;
 
-            // When using BoaJsContext, it's best to use 'scope'
-            let output: UseRepositoryOutput = {
-                let service_provider = BoaJsContext::service_provider();
-                let connection = service_provider
-                    .connection()
-                    .map_err(std_error_to_js_error)?;
+                // When using BoaJsContext, it's best to use 'scope'
+                let output: UseRepositoryOutput = {
+                    let service_provider = BoaJsContext::service_provider();
+                    let connection = service_provider
+                        .connection()
+                        .map_err(std_error_to_js_error)?;
 
-                use UseRepositoryInput as In;
-                use UseRepositoryOutput as Out;
+                    use UseRepositoryInput as In;
+                    use UseRepositoryOutput as Out;
 
-                match input {
-                    In::GetSyncMessageById(id) => Out::GetSyncMessageById(
-                        SyncMessageRowRepository::new(&connection)
-                            .find_one_by_id(&id)
-                            .map_err(std_error_to_js_error)?,
-                    ),
-                    In::UpsertSyncMessage(message_row) => Out::UpsertSyncMessage(
-                        SyncMessageRowRepository::new(&connection)
-                            .upsert_one(&message_row)
-                            .map_err(std_error_to_js_error)?,
-                    ),
-                    In::UpsertPluginData(plugin_data_row) => Out::UpsertPluginData(
-                        PluginDataRowRepository::new(&connection)
-                            .upsert_one(&plugin_data_row)
-                            .map_err(std_error_to_js_error)?,
-                    ),
-                    In::GetDaysOutOfStock(filter) => Out::GetDaysOutOfStock(
-                        DaysOutOfStockRepository::new(&connection)
-                            .query(filter)
-                            .map_err(std_error_to_js_error)?,
-                    ),
-                }
-            };
+                    match input {
+                        In::GetSyncMessageById(id) => Out::GetSyncMessageById(
+                            SyncMessageRowRepository::new(&connection)
+                                .find_one_by_id(&id)
+                                .map_err(std_error_to_js_error)?,
+                        ),
+                        In::UpsertSyncMessage(message_row) => Out::UpsertSyncMessage(
+                            SyncMessageRowRepository::new(&connection)
+                                .upsert_one(&message_row)
+                                .map_err(std_error_to_js_error)?,
+                        ),
+                        In::UpsertPluginData(plugin_data_row) => Out::UpsertPluginData(
+                            PluginDataRowRepository::new(&connection)
+                                .upsert_one(&plugin_data_row)
+                                .map_err(std_error_to_js_error)?,
+                        ),
+                        In::GetDaysOutOfStock(filter) => Out::GetDaysOutOfStock(
+                            DaysOutOfStockRepository::new(&connection)
+                                .query(filter)
+                                .map_err(std_error_to_js_error)?,
+                        ),
+                        In::UpsertPluginTableRow(plugin_table_row) => {
+                            validate_plugin_table(
+                                &connection,
+                                plugin_code,
+                                &plugin_table_row.table_name,
+                            )?;
+                            Out::UpsertPluginTableRow(
+                                PluginTableRowRepository::new(&connection)
+                                    .upsert_one(&plugin_table_row)
+                                    .map_err(std_error_to_js_error)?,
+                            )
+                        }
+                        In::DeletePluginTableRow { table_name, id } => {
+                            validate_plugin_table(&connection, plugin_code, &table_name)?;
+                            Out::DeletePluginTableRow(
+                                PluginTableRowRepository::new(&connection)
+                                    .delete(&table_name, &id)
+                                    .map_err(std_error_to_js_error)?,
+                            )
+                        }
+                    }
+                };
 
-            let value: serde_json::Value =
-                serde_json::to_value(&output).map_err(std_error_to_js_error)?;
-            // We return the moved variable as a `JsValue`.
-            JsValue::from_json(&value, ctx)
-        }),
+                let value: serde_json::Value =
+                    serde_json::to_value(&output).map_err(std_error_to_js_error)?;
+                // We return the moved variable as a `JsValue`.
+                JsValue::from_json(&value, ctx)
+            },
+            plugin_code.map(str::to_string),
+        ),
     )?;
     Ok(())
 }
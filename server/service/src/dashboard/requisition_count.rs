@@ -1,6 +1,9 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
 use repository::{
-    EqualFilter, RepositoryError, RequisitionFilter, RequisitionRepository, RequisitionStatus,
-    RequisitionType,
+    DatetimeFilter, EqualFilter, ProgramRow, RepositoryError, RequisitionFilter,
+    RequisitionRepository, RequisitionStatus, RequisitionType,
 };
 
 use crate::service_provider::ServiceContext;
@@ -29,6 +32,25 @@ pub trait RequisitionCountServiceTrait: Send + Sync {
     ) -> Result<i64, RepositoryError> {
         RequisitionCountService {}.new_emergency_response_requisition_count(ctx, store_id)
     }
+
+    fn emergency_response_requisition_count_by_program(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        created_after: NaiveDateTime,
+    ) -> Result<Vec<ProgramEmergencyRequisitionCount>, RepositoryError> {
+        RequisitionCountService {}.emergency_response_requisition_count_by_program(
+            ctx,
+            store_id,
+            created_after,
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ProgramEmergencyRequisitionCount {
+    pub program: ProgramRow,
+    pub count: i64,
 }
 
 pub struct RequisitionCountService {}
@@ -76,4 +98,92 @@ impl RequisitionCountServiceTrait for RequisitionCountService {
                 .is_emergency(true),
         ))
     }
+
+    /// How often customers raised emergency orders for each program, regardless of status
+    fn emergency_response_requisition_count_by_program(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        created_after: NaiveDateTime,
+    ) -> Result<Vec<ProgramEmergencyRequisitionCount>, RepositoryError> {
+        let requisitions = RequisitionRepository::new(&ctx.connection).query_by_filter(
+            RequisitionFilter::new()
+                .store_id(EqualFilter::equal_to(store_id.to_string()))
+                .r#type(RequisitionType::Response.equal_to())
+                .is_emergency(true)
+                .created_datetime(DatetimeFilter::after_or_equal_to(created_after)),
+        )?;
+
+        let mut counts: BTreeMap<String, ProgramEmergencyRequisitionCount> = BTreeMap::new();
+        for program in requisitions.into_iter().filter_map(|r| r.program) {
+            counts
+                .entry(program.id.clone())
+                .or_insert(ProgramEmergencyRequisitionCount { program, count: 0 })
+                .count += 1;
+        }
+
+        Ok(counts.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use repository::{
+        mock::{mock_program_a, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        RequisitionRow, RequisitionType,
+    };
+
+    use crate::service_provider::ServiceProvider;
+
+    #[actix_rt::test]
+    async fn emergency_response_requisition_count_by_program() {
+        let now = Utc::now().naive_utc();
+        let emergency = |id: &str, created_datetime| RequisitionRow {
+            id: id.to_string(),
+            store_id: mock_store_a().id,
+            name_id: "name_store_b".to_string(),
+            r#type: RequisitionType::Response,
+            program_id: Some(mock_program_a().id),
+            is_emergency: true,
+            created_datetime,
+            ..Default::default()
+        };
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "emergency_response_requisition_count_by_program",
+            MockDataInserts::all(),
+            MockData {
+                requisitions: vec![
+                    emergency("emergency_1", now),
+                    emergency("emergency_2", now),
+                    // Before the reporting window
+                    emergency("emergency_3", now - Duration::days(60)),
+                    RequisitionRow {
+                        is_emergency: false,
+                        ..emergency("routine_1", now)
+                    },
+                ],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.basic_context().unwrap();
+        let service = service_provider.requisition_count_service;
+
+        let result = service
+            .emergency_response_requisition_count_by_program(
+                &context,
+                &mock_store_a().id,
+                now - Duration::days(30),
+            )
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].program, mock_program_a());
+        assert_eq!(result[0].count, 2);
+    }
 }
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+use repository::{
+    ConsumptionFilter, ConsumptionRepository, ConsumptionRow, DateFilter, DaysOutOfStockFilter,
+    DaysOutOfStockRepository, EqualFilter, RepositoryError, StorageConnection,
+};
+use util::{date_with_months_offset, first_day_of_the_month, last_day_of_the_month};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlyDemand {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub consumption: f64,
+    pub days_out_of_stock: f64,
+    /// Consumption scaled up to the whole month for the days the item was in stock, none if the
+    /// item was out of stock for the whole month
+    pub adjusted_consumption: Option<f64>,
+}
+
+/// First days of the `number_of_months` complete months before the reference date's month
+pub fn history_months(reference_date: &NaiveDate, number_of_months: u32) -> Vec<NaiveDate> {
+    let current_month = first_day_of_the_month(reference_date);
+    (1..=number_of_months as i32)
+        .rev()
+        .map(|offset| date_with_months_offset(&current_month, -offset))
+        .collect()
+}
+
+pub fn get_demand_history(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_ids: &[String],
+    months: &[NaiveDate],
+) -> Result<HashMap<String, Vec<MonthlyDemand>>, RepositoryError> {
+    let (Some(first_month), Some(last_month)) = (months.first(), months.last()) else {
+        return Ok(HashMap::new());
+    };
+
+    let consumption_rows = ConsumptionRepository::new(connection).query(Some(
+        ConsumptionFilter::new()
+            .store_id(EqualFilter::equal_to(store_id.to_string()))
+            .item_id(EqualFilter::equal_any(item_ids.to_vec()))
+            .date(DateFilter::date_range(
+                first_month,
+                &last_day_of_the_month(last_month),
+            )),
+    ))?;
+
+    let mut days_out_of_stock = HashMap::new();
+    for month in months {
+        let rows = DaysOutOfStockRepository::new(connection).query(DaysOutOfStockFilter {
+            store_id: Some(EqualFilter::equal_to(store_id.to_string())),
+            item_id: Some(EqualFilter::equal_any(item_ids.to_vec())),
+            from: *month,
+            to: last_day_of_the_month(month),
+        })?;
+        for row in rows {
+            days_out_of_stock.insert((row.item_id, *month), row.total_dos);
+        }
+    }
+
+    Ok(build_demand_history(
+        item_ids,
+        months,
+        &consumption_rows,
+        &days_out_of_stock,
+    ))
+}
+
+fn build_demand_history(
+    item_ids: &[String],
+    months: &[NaiveDate],
+    consumption_rows: &[ConsumptionRow],
+    days_out_of_stock: &HashMap<(String, NaiveDate), f64>,
+) -> HashMap<String, Vec<MonthlyDemand>> {
+    let mut consumption: HashMap<(&str, NaiveDate), f64> = HashMap::new();
+    for row in consumption_rows {
+        *consumption
+            .entry((row.item_id.as_str(), first_day_of_the_month(&row.date)))
+            .or_default() += row.quantity;
+    }
+
+    item_ids
+        .iter()
+        .map(|item_id| {
+            let history = months
+                .iter()
+                .map(|month| {
+                    let consumption = consumption
+                        .get(&(item_id.as_str(), *month))
+                        .copied()
+                        .unwrap_or_default();
+                    let days_out_of_stock = days_out_of_stock
+                        .get(&(item_id.clone(), *month))
+                        .copied()
+                        .unwrap_or_default();
+
+                    MonthlyDemand {
+                        month: *month,
+                        consumption,
+                        days_out_of_stock,
+                        adjusted_consumption: adjust_for_days_out_of_stock(
+                            consumption,
+                            last_day_of_the_month(month).day() as f64,
+                            days_out_of_stock,
+                        ),
+                    }
+                })
+                .collect();
+            (item_id.clone(), history)
+        })
+        .collect()
+}
+
+fn adjust_for_days_out_of_stock(
+    consumption: f64,
+    days_in_month: f64,
+    days_out_of_stock: f64,
+) -> Option<f64> {
+    let days_in_stock = days_in_month - days_out_of_stock;
+    if days_in_stock < 1.0 {
+        return None;
+    }
+    Some(consumption * days_in_month / days_in_stock)
+}
+
+/// Demand series for fitting, months without usable data take the average of the other months
+pub fn demand_series(history: &[MonthlyDemand]) -> Vec<f64> {
+    let known: Vec<f64> = history
+        .iter()
+        .filter_map(|month| month.adjusted_consumption)
+        .collect();
+    let average = if known.is_empty() {
+        0.0
+    } else {
+        known.iter().sum::<f64>() / known.len() as f64
+    };
+
+    history
+        .iter()
+        .map(|month| month.adjusted_consumption.unwrap_or(average))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_history_months() {
+        assert_eq!(
+            history_months(&date(2024, 2, 15), 3),
+            vec![date(2023, 11, 1), date(2023, 12, 1), date(2024, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_build_demand_history() {
+        let months = history_months(&date(2024, 4, 10), 3);
+        let consumption_row = |day: NaiveDate, quantity: f64| ConsumptionRow {
+            item_id: "item".to_string(),
+            date: day,
+            quantity,
+            ..Default::default()
+        };
+        let consumption_rows = vec![
+            consumption_row(date(2024, 1, 5), 10.0),
+            consumption_row(date(2024, 1, 20), 20.0),
+            consumption_row(date(2024, 3, 1), 15.0),
+        ];
+        let days_out_of_stock = HashMap::from([
+            // Out of stock all of February
+            (("item".to_string(), date(2024, 2, 1)), 29.0),
+            // In stock for half of March
+            (("item".to_string(), date(2024, 3, 1)), 15.5),
+        ]);
+
+        let history = build_demand_history(
+            &["item".to_string()],
+            &months,
+            &consumption_rows,
+            &days_out_of_stock,
+        );
+        let history = &history["item"];
+
+        assert_eq!(
+            history,
+            &vec![
+                MonthlyDemand {
+                    month: date(2024, 1, 1),
+                    consumption: 30.0,
+                    days_out_of_stock: 0.0,
+                    adjusted_consumption: Some(30.0),
+                },
+                MonthlyDemand {
+                    month: date(2024, 2, 1),
+                    consumption: 0.0,
+                    days_out_of_stock: 29.0,
+                    adjusted_consumption: None,
+                },
+                MonthlyDemand {
+                    month: date(2024, 3, 1),
+                    consumption: 15.0,
+                    days_out_of_stock: 15.5,
+                    adjusted_consumption: Some(30.0),
+                },
+            ]
+        );
+
+        assert_eq!(demand_series(history), vec![30.0, 30.0, 30.0]);
+    }
+}
@@ -0,0 +1,158 @@
+mod history;
+mod models;
+
+use chrono::{NaiveDate, Utc};
+use repository::{RepositoryError, StorageConnection};
+use util::{date_with_months_offset, first_day_of_the_month};
+
+use crate::service_provider::ServiceContext;
+pub use history::MonthlyDemand;
+use history::{demand_series, get_demand_history, history_months};
+pub use models::ForecastModel;
+use models::{fit, fit_best};
+
+/// z score of the 95% confidence interval
+const CONFIDENCE_Z: f64 = 1.96;
+const MAX_HISTORY_MONTHS: u32 = 120;
+const MAX_HORIZON_MONTHS: u32 = 36;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DemandForecastInput {
+    pub item_ids: Vec<String>,
+    /// When not set all models are fitted and the one with the lowest error is used
+    pub model: Option<ForecastModel>,
+    pub history_months: u32,
+    pub horizon_months: u32,
+}
+
+impl Default for DemandForecastInput {
+    fn default() -> Self {
+        DemandForecastInput {
+            item_ids: Vec::new(),
+            model: None,
+            history_months: 24,
+            horizon_months: 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastPoint {
+    /// First day of the month
+    pub month: NaiveDate,
+    pub quantity: f64,
+    /// Bounds of the 95% confidence interval
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDemandForecast {
+    pub item_id: String,
+    pub model: ForecastModel,
+    pub history: Vec<MonthlyDemand>,
+    pub forecast: Vec<ForecastPoint>,
+}
+
+impl ItemDemandForecast {
+    /// Average forecast demand per month over the first `months` of the forecast
+    pub fn average_monthly_demand(&self, months: usize) -> f64 {
+        let points = &self.forecast[..months.min(self.forecast.len())];
+        if points.is_empty() {
+            return 0.0;
+        }
+        points.iter().map(|point| point.quantity).sum::<f64>() / points.len() as f64
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DemandForecastError {
+    InvalidHistoryMonths,
+    InvalidHorizonMonths,
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for DemandForecastError {
+    fn from(error: RepositoryError) -> Self {
+        DemandForecastError::DatabaseError(error)
+    }
+}
+
+pub trait DemandForecastServiceTrait: Sync + Send {
+    /// Monthly demand forecast for items in the context store, from consumption history corrected
+    /// for days out of stock
+    fn get_demand_forecasts(
+        &self,
+        ctx: &ServiceContext,
+        input: DemandForecastInput,
+    ) -> Result<Vec<ItemDemandForecast>, DemandForecastError> {
+        if input.history_months == 0 || input.history_months > MAX_HISTORY_MONTHS {
+            return Err(DemandForecastError::InvalidHistoryMonths);
+        }
+        if input.horizon_months == 0 || input.horizon_months > MAX_HORIZON_MONTHS {
+            return Err(DemandForecastError::InvalidHorizonMonths);
+        }
+
+        Ok(forecast_demand(
+            &ctx.connection,
+            &ctx.store_id,
+            &input,
+            &Utc::now().date_naive(),
+        )?)
+    }
+}
+
+pub struct DemandForecastService;
+impl DemandForecastServiceTrait for DemandForecastService {}
+
+/// Forecast starts at the reference date's month, history is the complete months before it
+pub(crate) fn forecast_demand(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &DemandForecastInput,
+    reference_date: &NaiveDate,
+) -> Result<Vec<ItemDemandForecast>, RepositoryError> {
+    let months = history_months(reference_date, input.history_months);
+    let mut histories = get_demand_history(connection, store_id, &input.item_ids, &months)?;
+    let forecast_start = first_day_of_the_month(reference_date);
+
+    let forecasts = input
+        .item_ids
+        .iter()
+        .map(|item_id| {
+            let history = histories.remove(item_id).unwrap_or_default();
+            let series = demand_series(&history);
+            let horizon = input.horizon_months as usize;
+            let fit = match input.model {
+                Some(model) => fit(model, &series, horizon),
+                None => fit_best(&series, horizon),
+            };
+            let standard_error = fit.standard_error();
+
+            let forecast = fit
+                .forecast
+                .iter()
+                .enumerate()
+                .map(|(index, quantity)| {
+                    let step = index + 1;
+                    let margin = CONFIDENCE_Z * standard_error * (step as f64).sqrt();
+                    ForecastPoint {
+                        month: date_with_months_offset(&forecast_start, index as i32),
+                        quantity: *quantity,
+                        lower_bound: (quantity - margin).max(0.0),
+                        upper_bound: quantity + margin,
+                    }
+                })
+                .collect();
+
+            ItemDemandForecast {
+                item_id: item_id.clone(),
+                model: fit.model,
+                history,
+                forecast,
+            }
+        })
+        .collect();
+
+    Ok(forecasts)
+}
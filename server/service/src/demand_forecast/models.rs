@@ -0,0 +1,287 @@
+/// Smoothing parameters are fitted by minimising the one step ahead squared error over this grid
+const PARAMETER_GRID: [f64; 9] = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+const MOVING_AVERAGE_WINDOW: usize = 3;
+pub const SEASON_LENGTH: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForecastModel {
+    MovingAverage,
+    ExponentialSmoothing,
+    /// Additive Holt-Winters, falls back to Holt's linear trend when there is less than two
+    /// seasons of history
+    HoltWinters,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFit {
+    pub model: ForecastModel,
+    /// Point forecast for each month of the horizon
+    pub forecast: Vec<f64>,
+    /// One step ahead errors over the history, used for confidence intervals and model selection
+    pub errors: Vec<f64>,
+}
+
+impl ModelFit {
+    pub fn mean_absolute_error(&self) -> Option<f64> {
+        if self.errors.is_empty() {
+            return None;
+        }
+        Some(self.errors.iter().map(|e| e.abs()).sum::<f64>() / self.errors.len() as f64)
+    }
+
+    /// Root mean squared one step ahead error
+    pub fn standard_error(&self) -> f64 {
+        if self.errors.is_empty() {
+            return 0.0;
+        }
+        (self.errors.iter().map(|e| e * e).sum::<f64>() / self.errors.len() as f64).sqrt()
+    }
+}
+
+pub fn fit(model: ForecastModel, series: &[f64], horizon: usize) -> ModelFit {
+    match model {
+        ForecastModel::MovingAverage => moving_average(series, horizon),
+        ForecastModel::ExponentialSmoothing => exponential_smoothing(series, horizon),
+        ForecastModel::HoltWinters => holt_winters(series, horizon),
+    }
+}
+
+/// Fits all models and returns the one with the lowest mean absolute error
+pub fn fit_best(series: &[f64], horizon: usize) -> ModelFit {
+    [
+        ForecastModel::HoltWinters,
+        ForecastModel::ExponentialSmoothing,
+        ForecastModel::MovingAverage,
+    ]
+    .into_iter()
+    .map(|model| fit(model, series, horizon))
+    .min_by(|a, b| {
+        let a = a.mean_absolute_error().unwrap_or(f64::MAX);
+        let b = b.mean_absolute_error().unwrap_or(f64::MAX);
+        a.total_cmp(&b)
+    })
+    .unwrap()
+}
+
+fn moving_average(series: &[f64], horizon: usize) -> ModelFit {
+    let mean = |values: &[f64]| {
+        if values.is_empty() {
+            0.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    };
+
+    let errors = (MOVING_AVERAGE_WINDOW..series.len())
+        .map(|index| series[index] - mean(&series[index - MOVING_AVERAGE_WINDOW..index]))
+        .collect();
+    let window_start = series.len().saturating_sub(MOVING_AVERAGE_WINDOW);
+    let level = mean(&series[window_start..]);
+
+    ModelFit {
+        model: ForecastModel::MovingAverage,
+        forecast: vec![level; horizon],
+        errors,
+    }
+}
+
+fn exponential_smoothing(series: &[f64], horizon: usize) -> ModelFit {
+    let smooth = |alpha: f64| {
+        let mut level = series.first().copied().unwrap_or_default();
+        let mut errors = Vec::new();
+        for value in series.iter().skip(1) {
+            errors.push(value - level);
+            level = alpha * value + (1.0 - alpha) * level;
+        }
+        (level, errors)
+    };
+
+    let (level, errors) = PARAMETER_GRID
+        .iter()
+        .map(|alpha| smooth(*alpha))
+        .min_by(|(_, a), (_, b)| sum_of_squares(a).total_cmp(&sum_of_squares(b)))
+        .unwrap();
+
+    ModelFit {
+        model: ForecastModel::ExponentialSmoothing,
+        forecast: vec![level; horizon],
+        errors,
+    }
+}
+
+fn holt_winters(series: &[f64], horizon: usize) -> ModelFit {
+    if series.len() < 2 * SEASON_LENGTH {
+        return ModelFit {
+            model: ForecastModel::HoltWinters,
+            ..holt_linear(series, horizon)
+        };
+    }
+
+    let smooth = |alpha: f64, beta: f64, gamma: f64| {
+        // Initial level and trend from the first two seasons, seasonal indices from the first
+        let first_season_mean = series[..SEASON_LENGTH].iter().sum::<f64>() / SEASON_LENGTH as f64;
+        let second_season_mean =
+            series[SEASON_LENGTH..2 * SEASON_LENGTH].iter().sum::<f64>() / SEASON_LENGTH as f64;
+        let mut level = first_season_mean;
+        let mut trend = (second_season_mean - first_season_mean) / SEASON_LENGTH as f64;
+        let mut seasonal: Vec<f64> = series[..SEASON_LENGTH]
+            .iter()
+            .map(|value| value - first_season_mean)
+            .collect();
+
+        let mut errors = Vec::new();
+        for (index, value) in series.iter().enumerate().skip(SEASON_LENGTH) {
+            let season_index = index % SEASON_LENGTH;
+            errors.push(value - (level + trend + seasonal[season_index]));
+
+            let previous_level = level;
+            level = alpha * (value - seasonal[season_index]) + (1.0 - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+            seasonal[season_index] =
+                gamma * (value - level) + (1.0 - gamma) * seasonal[season_index];
+        }
+
+        let forecast = (1..=horizon)
+            .map(|step| {
+                let season_index = (series.len() + step - 1) % SEASON_LENGTH;
+                level + step as f64 * trend + seasonal[season_index]
+            })
+            .collect::<Vec<f64>>();
+        (forecast, errors)
+    };
+
+    let (forecast, errors) = grid(3)
+        .map(|parameters| smooth(parameters[0], parameters[1], parameters[2]))
+        .min_by(|(_, a), (_, b)| sum_of_squares(a).total_cmp(&sum_of_squares(b)))
+        .unwrap();
+
+    ModelFit {
+        model: ForecastModel::HoltWinters,
+        forecast: non_negative(forecast),
+        errors,
+    }
+}
+
+/// Holt's linear trend method
+fn holt_linear(series: &[f64], horizon: usize) -> ModelFit {
+    if series.len() < 2 {
+        return exponential_smoothing(series, horizon);
+    }
+
+    let smooth = |alpha: f64, beta: f64| {
+        let mut level = series[0];
+        let mut trend = series[1] - series[0];
+        let mut errors = Vec::new();
+        for value in series.iter().skip(1) {
+            errors.push(value - (level + trend));
+
+            let previous_level = level;
+            level = alpha * value + (1.0 - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+        }
+
+        let forecast = (1..=horizon)
+            .map(|step| level + step as f64 * trend)
+            .collect::<Vec<f64>>();
+        (forecast, errors)
+    };
+
+    let (forecast, errors) = grid(2)
+        .map(|parameters| smooth(parameters[0], parameters[1]))
+        .min_by(|(_, a), (_, b)| sum_of_squares(a).total_cmp(&sum_of_squares(b)))
+        .unwrap();
+
+    ModelFit {
+        model: ForecastModel::HoltWinters,
+        forecast: non_negative(forecast),
+        errors,
+    }
+}
+
+/// All combinations of `dimensions` parameters from the parameter grid
+fn grid(dimensions: u32) -> impl Iterator<Item = Vec<f64>> {
+    let size = PARAMETER_GRID.len();
+    (0..size.pow(dimensions)).map(move |mut combination| {
+        (0..dimensions)
+            .map(|_| {
+                let value = PARAMETER_GRID[combination % size];
+                combination /= size;
+                value
+            })
+            .collect()
+    })
+}
+
+fn sum_of_squares(errors: &[f64]) -> f64 {
+    errors.iter().map(|e| e * e).sum()
+}
+
+fn non_negative(forecast: Vec<f64>) -> Vec<f64> {
+    forecast.into_iter().map(|value| value.max(0.0)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flat_series() {
+        let series = vec![10.0; 24];
+
+        for model in [
+            ForecastModel::MovingAverage,
+            ForecastModel::ExponentialSmoothing,
+            ForecastModel::HoltWinters,
+        ] {
+            let fit = fit(model, &series, 3);
+            assert_eq!(fit.model, model);
+            for value in fit.forecast {
+                assert!((value - 10.0).abs() < 1e-9, "{model:?} {value}");
+            }
+            assert!(fit.standard_error() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_trend() {
+        // Short history uses Holt's linear trend
+        let series: Vec<f64> = (1..=10).map(|month| month as f64 * 10.0).collect();
+        let fit = fit(ForecastModel::HoltWinters, &series, 2);
+        assert!((fit.forecast[0] - 110.0).abs() < 1e-6);
+        assert!((fit.forecast[1] - 120.0).abs() < 1e-6);
+
+        // Flat models lag behind the trend
+        assert_eq!(fit_best(&series, 1).model, ForecastModel::HoltWinters);
+    }
+
+    #[test]
+    fn test_seasonality() {
+        // Three years of a rainy season peak
+        let season = [
+            10.0, 10.0, 10.0, 20.0, 50.0, 80.0, 80.0, 50.0, 20.0, 10.0, 10.0, 10.0,
+        ];
+        let series: Vec<f64> = season.iter().cycle().take(36).copied().collect();
+
+        let fit = fit_best(&series, 12);
+        assert_eq!(fit.model, ForecastModel::HoltWinters);
+        for (forecast, expected) in fit.forecast.iter().zip(season.iter()) {
+            assert!((forecast - expected).abs() < 1.0, "{forecast} {expected}");
+        }
+    }
+
+    #[test]
+    fn test_short_history() {
+        assert_eq!(
+            fit(ForecastModel::HoltWinters, &[], 2).forecast,
+            vec![0.0, 0.0]
+        );
+        assert_eq!(
+            fit(ForecastModel::MovingAverage, &[4.0], 1).forecast,
+            vec![4.0]
+        );
+        assert_eq!(
+            fit(ForecastModel::MovingAverage, &[4.0], 1).mean_absolute_error(),
+            None
+        );
+    }
+}
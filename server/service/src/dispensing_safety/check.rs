@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, Utc};
+use repository::{
+    AbbreviationRowRepository, DatetimeFilter, DrugInteractionRow, DrugInteractionRowRepository,
+    DrugInteractionSeverity, EqualFilter, InvoiceFilter, InvoiceLineFilter, InvoiceLineRepository,
+    InvoiceLineType, InvoiceRepository, InvoiceRow, InvoiceStatus, InvoiceType,
+    ItemClinicalInfoRow, ItemClinicalInfoRowRepository, ItemRow, RepositoryError,
+    StorageConnection,
+};
+
+use crate::{
+    document::get_latest_doc,
+    programs::patient::{main_patient_doc_name, patient_schema::Allergies},
+    refill::daily_dose,
+};
+
+use super::{DispensingAlert, DispensingAlertSeverity, DispensingAlertType};
+
+/// Prescriptions picked within this many days are treated as current medication when checking
+/// interactions and duplicate therapy
+pub const CURRENT_MEDICATION_DAYS: i64 = 30;
+/// ATC level 4 (chemical subgroup) code length, items sharing it are the same therapy
+const THERAPEUTIC_CLASS_LENGTH: usize = 5;
+
+/// Safety alerts for dispensing an item on a prescription, against the patient's recorded
+/// allergies, other items on the prescription and their current medication
+pub(crate) fn check_prescription_item(
+    connection: &StorageConnection,
+    invoice: &InvoiceRow,
+    item: &ItemRow,
+    directions: Option<&str>,
+) -> Result<Vec<DispensingAlert>, RepositoryError> {
+    let other_items = get_current_medication(connection, invoice, &item.id)?;
+
+    let item_ids: Vec<String> = other_items
+        .keys()
+        .cloned()
+        .chain(std::iter::once(item.id.clone()))
+        .collect();
+    let mut clinical_info: HashMap<String, ItemClinicalInfoRow> =
+        ItemClinicalInfoRowRepository::new(connection)
+            .find_many_by_item_ids(&item_ids)?
+            .into_iter()
+            .map(|info| (info.item_id.clone(), info))
+            .collect();
+    let item_info = clinical_info.remove(&item.id).unwrap_or_default();
+    let atc_code = item_info.atc_code.as_deref();
+
+    let mut alerts = allergy_alerts(connection, &invoice.name_id, item, atc_code)?;
+
+    if let Some(atc_code) = atc_code {
+        let interactions = DrugInteractionRowRepository::new(connection).find_all()?;
+        for (other_item_id, other_item_name) in &other_items {
+            let Some(other_atc_code) = clinical_info
+                .get(other_item_id)
+                .and_then(|info| info.atc_code.as_deref())
+            else {
+                continue;
+            };
+
+            if let Some(interaction) = find_interaction(&interactions, atc_code, other_atc_code) {
+                alerts.push(DispensingAlert {
+                    alert_type: DispensingAlertType::Interaction,
+                    severity: match interaction.severity {
+                        DrugInteractionSeverity::Minor | DrugInteractionSeverity::Moderate => {
+                            DispensingAlertSeverity::Warning
+                        }
+                        DrugInteractionSeverity::Major
+                        | DrugInteractionSeverity::Contraindicated => {
+                            DispensingAlertSeverity::Error
+                        }
+                    },
+                    item_id: item.id.clone(),
+                    related_item_id: Some(other_item_id.clone()),
+                    message: format!(
+                        "{} interacts with {}: {}",
+                        item.name, other_item_name, interaction.description
+                    ),
+                });
+            }
+
+            if let Some(class) = therapeutic_class(atc_code) {
+                if therapeutic_class(other_atc_code) == Some(class) {
+                    alerts.push(DispensingAlert {
+                        alert_type: DispensingAlertType::DuplicateTherapy,
+                        severity: DispensingAlertSeverity::Warning,
+                        item_id: item.id.clone(),
+                        related_item_id: Some(other_item_id.clone()),
+                        message: format!(
+                            "{} is in the same therapeutic class ({class}) as {}",
+                            item.name, other_item_name
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    let max_daily_dose = item_info.max_daily_dose;
+    if let (Some(max_daily_dose), Some(directions)) = (max_daily_dose, directions) {
+        let abbreviations = AbbreviationRowRepository::new(connection).find_all()?;
+        if let Some(dose) = daily_dose(directions, &abbreviations) {
+            if dose > max_daily_dose {
+                alerts.push(DispensingAlert {
+                    alert_type: DispensingAlertType::MaximumDailyDose,
+                    severity: DispensingAlertSeverity::Error,
+                    item_id: item.id.clone(),
+                    related_item_id: None,
+                    message: format!(
+                        "Directions give {dose} units of {} a day, more than the maximum daily dose of {max_daily_dose}",
+                        item.name
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// Names of other items on the prescription and on the patient's recently picked prescriptions,
+/// by item id
+fn get_current_medication(
+    connection: &StorageConnection,
+    invoice: &InvoiceRow,
+    item_id: &str,
+) -> Result<BTreeMap<String, String>, RepositoryError> {
+    let recent_invoice_ids: Vec<String> = InvoiceRepository::new(connection)
+        .query_by_filter(
+            InvoiceFilter::new()
+                .r#type(InvoiceType::Prescription.equal_to())
+                .name_id(EqualFilter::equal_to(invoice.name_id.clone()))
+                .status(InvoiceStatus::equal_any(vec![
+                    InvoiceStatus::Picked,
+                    InvoiceStatus::Verified,
+                ]))
+                .picked_datetime(DatetimeFilter::after_or_equal_to(
+                    Utc::now().naive_utc() - Duration::days(CURRENT_MEDICATION_DAYS),
+                )),
+        )?
+        .into_iter()
+        .map(|invoice| invoice.invoice_row.id)
+        .chain(std::iter::once(invoice.id.clone()))
+        .collect();
+
+    let lines = InvoiceLineRepository::new(connection).query_by_filter(
+        InvoiceLineFilter::new()
+            .invoice_id(EqualFilter::equal_any(recent_invoice_ids))
+            .r#type(InvoiceLineType::StockOut.equal_to()),
+    )?;
+
+    Ok(lines
+        .into_iter()
+        .filter(|line| line.item_row.id != item_id)
+        .map(|line| (line.item_row.id, line.item_row.name))
+        .collect())
+}
+
+fn allergy_alerts(
+    connection: &StorageConnection,
+    patient_id: &str,
+    item: &ItemRow,
+    atc_code: Option<&str>,
+) -> Result<Vec<DispensingAlert>, RepositoryError> {
+    let Some(document) = get_latest_doc(connection, &main_patient_doc_name(patient_id))? else {
+        return Ok(Vec::new());
+    };
+    // Patients without recorded allergies, or a document that doesn't match the schema
+    let Some(allergies) = document
+        .data
+        .get("allergies")
+        .and_then(|allergies| serde_json::from_value::<Allergies>(allergies.clone()).ok())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let item_name = item.name.to_lowercase();
+    let alerts = allergies
+        .drug_allergies
+        .into_iter()
+        .filter_map(|allergy| {
+            let drug = allergy.drug?.trim().to_string();
+            let matches_name = !drug.is_empty() && item_name.contains(&drug.to_lowercase());
+            let matches_atc_code = atc_code.is_some_and(|code| atc_code_in_group(code, &drug));
+            if !matches_name && !matches_atc_code {
+                return None;
+            }
+
+            let message = match allergy.description {
+                Some(description) => format!("Patient is allergic to {drug}: {description}"),
+                None => format!("Patient is allergic to {drug}"),
+            };
+            Some(DispensingAlert {
+                alert_type: DispensingAlertType::Allergy,
+                severity: DispensingAlertSeverity::Error,
+                item_id: item.id.clone(),
+                related_item_id: None,
+                message,
+            })
+        })
+        .collect();
+
+    Ok(alerts)
+}
+
+fn find_interaction<'a>(
+    interactions: &'a [DrugInteractionRow],
+    atc_code: &str,
+    other_atc_code: &str,
+) -> Option<&'a DrugInteractionRow> {
+    interactions
+        .iter()
+        .filter(|interaction| {
+            (atc_code_in_group(atc_code, &interaction.atc_code_a)
+                && atc_code_in_group(other_atc_code, &interaction.atc_code_b))
+                || (atc_code_in_group(atc_code, &interaction.atc_code_b)
+                    && atc_code_in_group(other_atc_code, &interaction.atc_code_a))
+        })
+        .max_by_key(|interaction| interaction.severity)
+}
+
+/// ATC codes are hierarchical, `N02BE01` is in the groups `N`, `N02`, `N02B` and `N02BE`
+pub(crate) fn atc_code_in_group(atc_code: &str, group: &str) -> bool {
+    let group = group.trim();
+    group.len() >= 3
+        && !group.contains(char::is_whitespace)
+        && atc_code
+            .trim()
+            .to_uppercase()
+            .starts_with(&group.to_uppercase())
+}
+
+fn therapeutic_class(atc_code: &str) -> Option<String> {
+    let class: String = atc_code
+        .trim()
+        .to_uppercase()
+        .chars()
+        .take(THERAPEUTIC_CLASS_LENGTH)
+        .collect();
+    (class.len() == THERAPEUTIC_CLASS_LENGTH).then_some(class)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_atc_code_in_group() {
+        assert!(atc_code_in_group("N02BE01", "N02BE01"));
+        assert!(atc_code_in_group("N02BE01", "n02b"));
+        assert!(!atc_code_in_group("N02BE01", "N03"));
+        // Too broad, or not a code (e.g. an allergy recorded as a drug name)
+        assert!(!atc_code_in_group("N02BE01", "N"));
+        assert!(!atc_code_in_group("N02BE01", "N02 tablets"));
+
+        assert_eq!(therapeutic_class("m01ae01"), Some("M01AE".to_string()));
+        assert_eq!(therapeutic_class("M01"), None);
+    }
+}
@@ -0,0 +1,147 @@
+mod check;
+mod reference;
+#[cfg(test)]
+mod test;
+
+use repository::{
+    DrugInteractionRow, DrugInteractionRowRepository, InvoiceRowRepository, InvoiceType,
+    ItemClinicalInfoRow, ItemClinicalInfoRowRepository, ItemRowRepository, RepositoryError,
+};
+
+use crate::service_provider::ServiceContext;
+pub(crate) use check::check_prescription_item;
+pub use check::CURRENT_MEDICATION_DAYS;
+pub use reference::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispensingAlertType {
+    /// Item matches an allergy in the patient document
+    Allergy,
+    /// Item interacts with another item on the prescription or the patient's current medication
+    Interaction,
+    /// Another item in the same therapeutic class is on the prescription or current medication
+    DuplicateTherapy,
+    /// Directions exceed the item's maximum daily dose
+    MaximumDailyDose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispensingAlertSeverity {
+    /// Informational, doesn't stop dispensing
+    Warning,
+    /// Prescription lines for the item can only be added with an override reason
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispensingAlert {
+    pub alert_type: DispensingAlertType,
+    pub severity: DispensingAlertSeverity,
+    pub item_id: String,
+    /// Item the alert is against, for interactions and duplicate therapy
+    pub related_item_id: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CheckPrescriptionItem {
+    pub invoice_id: String,
+    pub item_id: String,
+    /// Dosing directions, used for the maximum daily dose check
+    pub directions: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CheckPrescriptionItemError {
+    InvoiceDoesNotExist,
+    NotThisStoreInvoice,
+    NotAPrescription,
+    ItemDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for CheckPrescriptionItemError {
+    fn from(error: RepositoryError) -> Self {
+        CheckPrescriptionItemError::DatabaseError(error)
+    }
+}
+
+pub trait DispensingSafetyServiceTrait: Sync + Send {
+    /// Alerts for adding an item to a prescription, so they can be shown before the line is
+    /// saved. Adding the line fails with the `Error` alerts unless an override reason is given
+    fn check_prescription_item(
+        &self,
+        ctx: &ServiceContext,
+        input: CheckPrescriptionItem,
+    ) -> Result<Vec<DispensingAlert>, CheckPrescriptionItemError> {
+        let invoice = InvoiceRowRepository::new(&ctx.connection)
+            .find_one_by_id(&input.invoice_id)?
+            .ok_or(CheckPrescriptionItemError::InvoiceDoesNotExist)?;
+        if invoice.store_id != ctx.store_id {
+            return Err(CheckPrescriptionItemError::NotThisStoreInvoice);
+        }
+        if invoice.r#type != InvoiceType::Prescription {
+            return Err(CheckPrescriptionItemError::NotAPrescription);
+        }
+        let item = ItemRowRepository::new(&ctx.connection)
+            .find_one_by_id(&input.item_id)?
+            .ok_or(CheckPrescriptionItemError::ItemDoesNotExist)?;
+
+        Ok(check_prescription_item(
+            &ctx.connection,
+            &invoice,
+            &item,
+            input.directions.as_deref(),
+        )?)
+    }
+
+    fn get_item_clinical_info(
+        &self,
+        ctx: &ServiceContext,
+        item_ids: &[String],
+    ) -> Result<Vec<ItemClinicalInfoRow>, RepositoryError> {
+        ItemClinicalInfoRowRepository::new(&ctx.connection).find_many_by_item_ids(item_ids)
+    }
+
+    fn upsert_item_clinical_info(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertItemClinicalInfo,
+    ) -> Result<ItemClinicalInfoRow, UpsertItemClinicalInfoError> {
+        upsert_item_clinical_info(ctx, input)
+    }
+
+    fn delete_item_clinical_info(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteClinicalReferenceError> {
+        delete_item_clinical_info(ctx, id)
+    }
+
+    fn get_drug_interactions(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Vec<DrugInteractionRow>, RepositoryError> {
+        DrugInteractionRowRepository::new(&ctx.connection).find_all()
+    }
+
+    fn upsert_drug_interaction(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertDrugInteraction,
+    ) -> Result<DrugInteractionRow, UpsertDrugInteractionError> {
+        upsert_drug_interaction(ctx, input)
+    }
+
+    fn delete_drug_interaction(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteClinicalReferenceError> {
+        delete_drug_interaction(ctx, id)
+    }
+}
+
+pub struct DispensingSafetyService;
+impl DispensingSafetyServiceTrait for DispensingSafetyService {}
@@ -0,0 +1,206 @@
+use repository::{
+    DrugInteractionRow, DrugInteractionRowRepository, DrugInteractionSeverity, ItemClinicalInfoRow,
+    ItemClinicalInfoRowRepository, ItemRowRepository, RepositoryError,
+};
+
+use crate::{service_provider::ServiceContext, sync::CentralServerConfig};
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertItemClinicalInfoError {
+    /// Clinical reference data is maintained on the central server and synced to remote sites
+    NotCentralServer,
+    ItemDoesNotExist,
+    ItemAlreadyHasClinicalInfo,
+    InvalidAtcCode,
+    MaxDailyDoseMustBePositive,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertDrugInteractionError {
+    NotCentralServer,
+    /// Interaction codes need at least the ATC therapeutic subgroup, e.g. `M01`
+    InvalidAtcCode,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteClinicalReferenceError {
+    NotCentralServer,
+    RecordDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct UpsertItemClinicalInfo {
+    pub id: String,
+    pub item_id: String,
+    pub atc_code: Option<String>,
+    /// Units of the item
+    pub max_daily_dose: Option<f64>,
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct UpsertDrugInteraction {
+    pub id: String,
+    pub atc_code_a: String,
+    pub atc_code_b: String,
+    pub severity: DrugInteractionSeverity,
+    pub description: String,
+}
+
+pub fn upsert_item_clinical_info(
+    ctx: &ServiceContext,
+    input: UpsertItemClinicalInfo,
+) -> Result<ItemClinicalInfoRow, UpsertItemClinicalInfoError> {
+    use UpsertItemClinicalInfoError as Error;
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            if !CentralServerConfig::is_central_server() {
+                return Err(Error::NotCentralServer);
+            }
+            if ItemRowRepository::new(connection)
+                .find_active_by_id(&input.item_id)?
+                .is_none()
+            {
+                return Err(Error::ItemDoesNotExist);
+            }
+            let atc_code = input.atc_code.map(|code| code.trim().to_uppercase());
+            if atc_code
+                .as_deref()
+                .is_some_and(|code| !is_atc_code(code, 1))
+            {
+                return Err(Error::InvalidAtcCode);
+            }
+            if input.max_daily_dose.is_some_and(|dose| dose <= 0.0) {
+                return Err(Error::MaxDailyDoseMustBePositive);
+            }
+
+            let repo = ItemClinicalInfoRowRepository::new(connection);
+            let already_has_info = repo
+                .find_many_by_item_ids(&[input.item_id.clone()])?
+                .into_iter()
+                .any(|info| info.id != input.id);
+            if already_has_info {
+                return Err(Error::ItemAlreadyHasClinicalInfo);
+            }
+
+            repo.upsert_one(&ItemClinicalInfoRow {
+                id: input.id.clone(),
+                item_id: input.item_id,
+                atc_code,
+                max_daily_dose: input.max_daily_dose,
+            })?;
+
+            repo.find_one_by_id(&input.id)?
+                .ok_or(Error::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn upsert_drug_interaction(
+    ctx: &ServiceContext,
+    input: UpsertDrugInteraction,
+) -> Result<DrugInteractionRow, UpsertDrugInteractionError> {
+    use UpsertDrugInteractionError as Error;
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            if !CentralServerConfig::is_central_server() {
+                return Err(Error::NotCentralServer);
+            }
+            let UpsertDrugInteraction {
+                id,
+                atc_code_a,
+                atc_code_b,
+                severity,
+                description,
+            } = input;
+            let atc_code_a = atc_code_a.trim().to_uppercase();
+            let atc_code_b = atc_code_b.trim().to_uppercase();
+            if !is_atc_code(&atc_code_a, 3) || !is_atc_code(&atc_code_b, 3) {
+                return Err(Error::InvalidAtcCode);
+            }
+
+            let repo = DrugInteractionRowRepository::new(connection);
+            repo.upsert_one(&DrugInteractionRow {
+                id: id.clone(),
+                atc_code_a,
+                atc_code_b,
+                severity,
+                description,
+            })?;
+
+            repo.find_one_by_id(&id)?
+                .ok_or(Error::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn delete_item_clinical_info(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteClinicalReferenceError> {
+    if !CentralServerConfig::is_central_server() {
+        return Err(DeleteClinicalReferenceError::NotCentralServer);
+    }
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = ItemClinicalInfoRowRepository::new(connection);
+            if repo.find_one_by_id(id)?.is_none() {
+                return Err(DeleteClinicalReferenceError::RecordDoesNotExist);
+            }
+            repo.delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn delete_drug_interaction(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteClinicalReferenceError> {
+    if !CentralServerConfig::is_central_server() {
+        return Err(DeleteClinicalReferenceError::NotCentralServer);
+    }
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = DrugInteractionRowRepository::new(connection);
+            if repo.find_one_by_id(id)?.is_none() {
+                return Err(DeleteClinicalReferenceError::RecordDoesNotExist);
+            }
+            repo.delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+/// Up to 7 characters starting with the anatomical group letter, e.g. `N`, `N02` or `N02BE01`
+fn is_atc_code(code: &str, min_length: usize) -> bool {
+    (min_length..=7).contains(&code.len())
+        && code.starts_with(|c: char| c.is_ascii_alphabetic())
+        && code.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+impl From<RepositoryError> for UpsertItemClinicalInfoError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertItemClinicalInfoError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for UpsertDrugInteractionError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertDrugInteractionError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteClinicalReferenceError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteClinicalReferenceError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::{Duration, Utc};
+use repository::{
+    activity_log::{ActivityLogFilter, ActivityLogRepository},
+    mock::{
+        context_program_a, mock_patient_b, mock_store_a, mock_user_account_a, MockData,
+        MockDataInserts,
+    },
+    test_db::setup_all_with_data,
+    ActivityLogType, Document, DocumentRepository, DocumentStatus, DrugInteractionRow,
+    DrugInteractionRowRepository, DrugInteractionSeverity, InvoiceLineRow, InvoiceLineType,
+    InvoiceRow, InvoiceStatus, InvoiceType, ItemClinicalInfoRow, ItemClinicalInfoRowRepository,
+    ItemRow, ItemType, StockLineRow,
+};
+use serde_json::json;
+
+use crate::{
+    invoice_line::stock_out_line::{InsertStockOutLine, InsertStockOutLineError, StockOutType},
+    programs::patient::main_patient_doc_name,
+    service_provider::ServiceProvider,
+};
+
+use super::{CheckPrescriptionItem, DispensingAlertSeverity, DispensingAlertType};
+
+fn item(id: &str, name: &str) -> ItemRow {
+    ItemRow {
+        id: id.to_string(),
+        name: name.to_string(),
+        code: id.to_string(),
+        r#type: ItemType::Stock,
+        is_active: true,
+        ..Default::default()
+    }
+}
+
+fn prescription(id: &str, status: InvoiceStatus, days_ago: i64) -> InvoiceRow {
+    let datetime = Utc::now().naive_utc() - Duration::days(days_ago);
+    InvoiceRow {
+        id: id.to_string(),
+        name_id: mock_patient_b().id,
+        store_id: mock_store_a().id,
+        r#type: InvoiceType::Prescription,
+        status: status.clone(),
+        created_datetime: datetime,
+        picked_datetime: (status == InvoiceStatus::Picked).then_some(datetime),
+        ..Default::default()
+    }
+}
+
+fn line(id: &str, invoice_id: &str, item_id: &str) -> InvoiceLineRow {
+    InvoiceLineRow {
+        id: id.to_string(),
+        invoice_id: invoice_id.to_string(),
+        item_link_id: item_id.to_string(),
+        item_name: item_id.to_string(),
+        r#type: InvoiceLineType::StockOut,
+        pack_size: 1.0,
+        number_of_packs: 10.0,
+        ..Default::default()
+    }
+}
+
+fn stock_line(id: &str, item_id: &str) -> StockLineRow {
+    StockLineRow {
+        id: id.to_string(),
+        item_link_id: item_id.to_string(),
+        store_id: mock_store_a().id,
+        pack_size: 1.0,
+        available_number_of_packs: 10.0,
+        total_number_of_packs: 10.0,
+        ..Default::default()
+    }
+}
+
+#[actix_rt::test]
+async fn test_dispensing_safety_checks() {
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "test_dispensing_safety_checks",
+        MockDataInserts::all(),
+        MockData {
+            items: vec![
+                item("paracetamol", "Paracetamol 500mg tablet"),
+                item("propacetamol", "Propacetamol 1g injection"),
+                item("warfarin", "Warfarin 5mg tablet"),
+            ],
+            stock_lines: vec![
+                stock_line("paracetamol_a", "paracetamol"),
+                stock_line("paracetamol_b", "paracetamol"),
+            ],
+            invoices: vec![
+                prescription("rx_past", InvoiceStatus::Picked, 5),
+                prescription("rx_old", InvoiceStatus::Picked, 60),
+                prescription("rx_new", InvoiceStatus::New, 0),
+            ],
+            invoice_lines: vec![
+                line("rx_past_warfarin", "rx_past", "warfarin"),
+                // Outside current medication, no duplicate therapy with itself
+                line("rx_old_paracetamol", "rx_old", "paracetamol"),
+                line("rx_new_propacetamol", "rx_new", "propacetamol"),
+            ],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let info_repo = ItemClinicalInfoRowRepository::new(&connection);
+    for (item_id, atc_code, max_daily_dose) in [
+        ("paracetamol", "N02BE01", Some(4.0)),
+        ("propacetamol", "N02BE05", None),
+        ("warfarin", "B01AA03", None),
+    ] {
+        info_repo
+            .upsert_one(&ItemClinicalInfoRow {
+                id: item_id.to_string(),
+                item_id: item_id.to_string(),
+                atc_code: Some(atc_code.to_string()),
+                max_daily_dose,
+            })
+            .unwrap();
+    }
+    DrugInteractionRowRepository::new(&connection)
+        .upsert_one(&DrugInteractionRow {
+            id: "anticoagulant_analgesic".to_string(),
+            atc_code_a: "B01AA".to_string(),
+            atc_code_b: "N02B".to_string(),
+            severity: DrugInteractionSeverity::Major,
+            description: "Increased bleeding risk".to_string(),
+        })
+        .unwrap();
+    DocumentRepository::new(&connection)
+        .insert(&Document {
+            id: "patient_document".to_string(),
+            name: main_patient_doc_name(&mock_patient_b().id),
+            parent_ids: vec![],
+            user_id: mock_user_account_a().id,
+            datetime: Utc::now(),
+            r#type: "Patient".to_string(),
+            data: json!({
+                "allergies": { "drugAllergies": [{ "drug": "Paracetamol", "description": "Rash" }] }
+            }),
+            form_schema_id: None,
+            status: DocumentStatus::Active,
+            owner_name_id: Some(mock_patient_b().id),
+            context_id: context_program_a().id,
+        })
+        .unwrap();
+
+    let service_provider = ServiceProvider::new(connection_manager);
+    let context = service_provider
+        .context(mock_store_a().id, mock_user_account_a().id)
+        .unwrap();
+
+    let alerts = service_provider
+        .dispensing_safety_service
+        .check_prescription_item(
+            &context,
+            CheckPrescriptionItem {
+                invoice_id: "rx_new".to_string(),
+                item_id: "paracetamol".to_string(),
+                directions: Some("2 tabs qid".to_string()),
+            },
+        )
+        .unwrap();
+    let alert_types: Vec<_> = alerts
+        .iter()
+        .map(|alert| {
+            (
+                alert.alert_type,
+                alert.severity,
+                alert.related_item_id.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        alert_types,
+        vec![
+            (
+                DispensingAlertType::Allergy,
+                DispensingAlertSeverity::Error,
+                None
+            ),
+            (
+                DispensingAlertType::DuplicateTherapy,
+                DispensingAlertSeverity::Warning,
+                Some("propacetamol")
+            ),
+            (
+                DispensingAlertType::Interaction,
+                DispensingAlertSeverity::Error,
+                Some("warfarin")
+            ),
+            (
+                DispensingAlertType::MaximumDailyDose,
+                DispensingAlertSeverity::Error,
+                None
+            ),
+        ]
+    );
+
+    // Errors block adding the item to the prescription without an override reason
+    let insert = |id: &str, stock_line_id: &str, reason: Option<&str>| InsertStockOutLine {
+        id: id.to_string(),
+        r#type: StockOutType::Prescription,
+        invoice_id: "rx_new".to_string(),
+        stock_line_id: stock_line_id.to_string(),
+        number_of_packs: 2.0,
+        note: Some("2 tabs qid".to_string()),
+        dispensing_override_reason: reason.map(str::to_string),
+        ..Default::default()
+    };
+    let invoice_line_service = &service_provider.invoice_line_service;
+    let result = invoice_line_service
+        .insert_stock_out_line(&context, insert("line_a", "paracetamol_a", Some(" ")));
+    let errors = match result {
+        Err(InsertStockOutLineError::DispensingAlertsRequireOverride(errors)) => errors,
+        other => panic!("expected dispensing alerts, got {other:?}"),
+    };
+    assert_eq!(errors.len(), 3);
+
+    invoice_line_service
+        .insert_stock_out_line(
+            &context,
+            insert("line_a", "paracetamol_a", Some("Prescriber confirmed")),
+        )
+        .unwrap();
+    let logs = ActivityLogRepository::new(&connection)
+        .query(
+            Default::default(),
+            Some(
+                ActivityLogFilter::new()
+                    .r#type(ActivityLogType::DispensingAlertOverridden.equal_to()),
+            ),
+            None,
+        )
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(
+        logs[0].activity_log_row.record_id,
+        Some("rx_new".to_string())
+    );
+    assert_eq!(
+        logs[0].activity_log_row.changed_to,
+        Some("Prescriber confirmed".to_string())
+    );
+
+    // Only checked when the item is first added
+    invoice_line_service
+        .insert_stock_out_line(&context, insert("line_b", "paracetamol_b", None))
+        .unwrap();
+}
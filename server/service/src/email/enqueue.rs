@@ -36,7 +36,7 @@ pub fn enqueue_email(
         retry_at: None,
     };
 
-    repo.upsert_one(&email_queue_row)
+    repo.insert_one(&email_queue_row)
         .map_err(EmailServiceError::DatabaseError)?;
 
     Ok(email_queue_row)
@@ -0,0 +1,107 @@
+use reqwest::{header::CONTENT_TYPE, Client, Url};
+use serde_json::Value;
+
+use super::{EpcisExportError, EpcisSettings};
+
+#[derive(Debug, PartialEq)]
+pub struct EpcisCaptureResult {
+    pub status: u16,
+    /// Location of the capture job, as returned by the capture endpoint
+    pub capture_id: Option<String>,
+}
+
+/// Posts an EPCIS document to the configured capture endpoint (the `/capture` resource
+/// of an EPCIS 2.0 repository)
+pub async fn capture_epcis_document(
+    settings: &EpcisSettings,
+    document: &Value,
+) -> Result<EpcisCaptureResult, EpcisExportError> {
+    let url = Url::parse(&settings.capture_url)
+        .map_err(|error| EpcisExportError::CaptureFailed(format!("{error}")))?;
+
+    let mut request = Client::new()
+        .post(url)
+        .header(CONTENT_TYPE, "application/ld+json")
+        .header("GS1-EPCIS-Version", "2.0.0")
+        .header("GS1-CBV-Version", "2.0.0")
+        .json(document);
+    if let Some(api_key) = &settings.api_key {
+        request = request.bearer_auth(api_key);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|error| EpcisExportError::CaptureFailed(format!("{error}")))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(EpcisExportError::CaptureFailed(format!("{status}: {body}")));
+    }
+
+    Ok(EpcisCaptureResult {
+        status: status.as_u16(),
+        capture_id: response
+            .headers()
+            .get("location")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use httpmock::{Method::POST, MockServer};
+    use serde_json::json;
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_capture_epcis_document() {
+        // Local stub of an EPCIS capture endpoint
+        let mock_server = MockServer::start();
+        let document = json!({ "type": "EPCISDocument", "schemaVersion": "2.0" });
+
+        let mock = mock_server.mock(|when, then| {
+            when.method(POST)
+                .path("/epcis/capture")
+                .header("authorization", "Bearer key")
+                .header("GS1-EPCIS-Version", "2.0.0")
+                .json_body(document.clone());
+            then.status(202).header("location", "/epcis/capture/1");
+        });
+
+        let settings = EpcisSettings {
+            capture_url: mock_server.url("/epcis/capture"),
+            api_key: Some("key".to_string()),
+        };
+        let result = capture_epcis_document(&settings, &document).await;
+
+        mock.assert();
+        assert_eq!(
+            result,
+            Ok(EpcisCaptureResult {
+                status: 202,
+                capture_id: Some("/epcis/capture/1".to_string())
+            })
+        );
+
+        // Rejected
+        let mock_server = MockServer::start();
+        mock_server.mock(|when, then| {
+            when.method(POST).path("/epcis/capture");
+            then.status(400).body("invalid document");
+        });
+        let settings = EpcisSettings {
+            capture_url: mock_server.url("/epcis/capture"),
+            api_key: None,
+        };
+        assert_eq!(
+            capture_epcis_document(&settings, &document).await,
+            Err(EpcisExportError::CaptureFailed(
+                "400 Bad Request: invalid document".to_string()
+            ))
+        );
+    }
+}
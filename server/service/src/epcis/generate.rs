@@ -0,0 +1,627 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use repository::{
+    BarcodeFilter, BarcodeRepository, BarcodeRow, DatetimeFilter, EqualFilter, Invoice,
+    InvoiceFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineType,
+    InvoiceRepository, InvoiceType, NameRowRepository, RepositoryError,
+    SerialNumberLineRowRepository, SerialNumberRowRepository, StorageConnection,
+};
+use serde_json::{json, Map, Value};
+
+pub const EPCIS_CONTEXT: &str = "https://ref.gs1.org/standards/epcis/epcis-context.jsonld";
+const GS1_DIGITAL_LINK: &str = "https://id.gs1.org";
+/// Key of the name property holding the GLN of a name
+pub const GLN_NAME_PROPERTY_KEY: &str = "gln";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EpcisPeriod {
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
+}
+
+/// Generates an EPCIS 2.0 JSON-LD document with the events of a store in the period:
+/// - ObjectEvent (receiving) for received inbound shipments
+/// - ObjectEvent (dispensing) for picked prescriptions
+/// - AggregationEvent (packing) for picked outbound shipments
+/// - TransactionEvent (shipping) for shipped outbound shipments
+pub fn generate_epcis_document(
+    connection: &StorageConnection,
+    store_id: &str,
+    period: &EpcisPeriod,
+) -> Result<Value, RepositoryError> {
+    let mut generator = EventGenerator::new(connection);
+    let mut events = Vec::new();
+
+    let date_range = || DatetimeFilter::date_range(period.from, period.to);
+    let invoices = |r#type: InvoiceType, filter: InvoiceFilter| {
+        InvoiceRepository::new(connection).query_by_filter(
+            filter
+                .store_id(EqualFilter::equal_to(store_id))
+                .r#type(r#type.equal_to()),
+        )
+    };
+
+    for invoice in invoices(
+        InvoiceType::InboundShipment,
+        InvoiceFilter::new().received_datetime(date_range()),
+    )? {
+        events.push(generator.receiving_event(&invoice)?);
+    }
+
+    for invoice in invoices(
+        InvoiceType::Prescription,
+        InvoiceFilter::new().picked_datetime(date_range()),
+    )? {
+        events.push(generator.dispensing_event(&invoice)?);
+    }
+
+    for invoice in invoices(
+        InvoiceType::OutboundShipment,
+        InvoiceFilter::new().picked_datetime(date_range()),
+    )? {
+        events.push(generator.packing_event(&invoice)?);
+    }
+
+    for invoice in invoices(
+        InvoiceType::OutboundShipment,
+        InvoiceFilter::new().shipped_datetime(date_range()),
+    )? {
+        events.push(generator.shipping_event(&invoice)?);
+    }
+
+    events.sort_by_key(|event| event["eventTime"].as_str().map(str::to_string));
+
+    Ok(json!({
+        "@context": [EPCIS_CONTEXT],
+        "type": "EPCISDocument",
+        "schemaVersion": "2.0",
+        "creationDate": format_event_time(&Utc::now().naive_utc()),
+        "epcisBody": {
+            "eventList": events
+        }
+    }))
+}
+
+/// Quantities of a set of invoice lines, either as instance identifiers (serial numbers)
+/// or class identifiers (GTIN and lot) with a quantity in units
+#[derive(Default)]
+struct Quantities {
+    epcs: Vec<String>,
+    quantities: Vec<Value>,
+}
+
+struct EventGenerator<'a> {
+    connection: &'a StorageConnection,
+    glns: HashMap<String, Option<String>>,
+    barcodes: HashMap<String, Vec<BarcodeRow>>,
+}
+
+impl<'a> EventGenerator<'a> {
+    fn new(connection: &'a StorageConnection) -> Self {
+        EventGenerator {
+            connection,
+            glns: HashMap::new(),
+            barcodes: HashMap::new(),
+        }
+    }
+
+    fn receiving_event(&mut self, invoice: &Invoice) -> Result<Value, RepositoryError> {
+        let row = &invoice.invoice_row;
+        let store_location = self.location_id(&invoice.store_row.name_id)?;
+        let supplier_location = self.location_id(&invoice.name_row.id)?;
+        let Quantities { epcs, quantities } = self.quantities(&row.id, InvoiceLineType::StockIn)?;
+
+        let mut event = base_event(
+            "ObjectEvent",
+            &row.id,
+            "receiving",
+            row.received_datetime.unwrap_or(row.created_datetime),
+        );
+        event.extend([
+            ("action".to_string(), json!("OBSERVE")),
+            ("epcList".to_string(), json!(epcs)),
+            ("quantityList".to_string(), json!(quantities)),
+            ("disposition".to_string(), json!("in_progress")),
+            ("readPoint".to_string(), json!({ "id": store_location })),
+            ("bizLocation".to_string(), json!({ "id": store_location })),
+            (
+                "sourceList".to_string(),
+                json!([{ "type": "owning_party", "source": supplier_location }]),
+            ),
+            (
+                "destinationList".to_string(),
+                json!([{ "type": "owning_party", "destination": store_location }]),
+            ),
+            (
+                "bizTransactionList".to_string(),
+                biz_transactions(&row.id, row.purchase_order_id.as_deref()),
+            ),
+        ]);
+
+        Ok(Value::Object(event))
+    }
+
+    fn dispensing_event(&mut self, invoice: &Invoice) -> Result<Value, RepositoryError> {
+        let row = &invoice.invoice_row;
+        let store_location = self.location_id(&invoice.store_row.name_id)?;
+        let Quantities { epcs, quantities } =
+            self.quantities(&row.id, InvoiceLineType::StockOut)?;
+
+        let mut event = base_event(
+            "ObjectEvent",
+            &row.id,
+            "dispensing",
+            row.picked_datetime.unwrap_or(row.created_datetime),
+        );
+        // Patient is intentionally not included
+        event.extend([
+            ("action".to_string(), json!("OBSERVE")),
+            ("epcList".to_string(), json!(epcs)),
+            ("quantityList".to_string(), json!(quantities)),
+            ("disposition".to_string(), json!("dispensed")),
+            ("readPoint".to_string(), json!({ "id": store_location })),
+            ("bizLocation".to_string(), json!({ "id": store_location })),
+        ]);
+
+        Ok(Value::Object(event))
+    }
+
+    fn packing_event(&mut self, invoice: &Invoice) -> Result<Value, RepositoryError> {
+        let row = &invoice.invoice_row;
+        let store_location = self.location_id(&invoice.store_row.name_id)?;
+        let Quantities { epcs, quantities } =
+            self.quantities(&row.id, InvoiceLineType::StockOut)?;
+
+        let mut event = base_event(
+            "AggregationEvent",
+            &row.id,
+            "packing",
+            row.picked_datetime.unwrap_or(row.created_datetime),
+        );
+        event.extend([
+            ("action".to_string(), json!("ADD")),
+            ("parentID".to_string(), json!(shipment_id(&row.id))),
+            ("childEPCs".to_string(), json!(epcs)),
+            ("childQuantityList".to_string(), json!(quantities)),
+            ("disposition".to_string(), json!("in_progress")),
+            ("readPoint".to_string(), json!({ "id": store_location })),
+            ("bizLocation".to_string(), json!({ "id": store_location })),
+        ]);
+
+        Ok(Value::Object(event))
+    }
+
+    fn shipping_event(&mut self, invoice: &Invoice) -> Result<Value, RepositoryError> {
+        let row = &invoice.invoice_row;
+        let store_location = self.location_id(&invoice.store_row.name_id)?;
+        let customer_location = self.location_id(&invoice.name_row.id)?;
+        let Quantities { epcs, quantities } =
+            self.quantities(&row.id, InvoiceLineType::StockOut)?;
+
+        let mut event = base_event(
+            "TransactionEvent",
+            &row.id,
+            "shipping",
+            row.shipped_datetime.unwrap_or(row.created_datetime),
+        );
+        event.extend([
+            ("action".to_string(), json!("ADD")),
+            ("parentID".to_string(), json!(shipment_id(&row.id))),
+            ("epcList".to_string(), json!(epcs)),
+            ("quantityList".to_string(), json!(quantities)),
+            ("disposition".to_string(), json!("in_transit")),
+            ("readPoint".to_string(), json!({ "id": store_location })),
+            (
+                "bizTransactionList".to_string(),
+                biz_transactions(&row.id, None),
+            ),
+            (
+                "sourceList".to_string(),
+                json!([{ "type": "owning_party", "source": store_location }]),
+            ),
+            (
+                "destinationList".to_string(),
+                json!([{ "type": "owning_party", "destination": customer_location }]),
+            ),
+        ]);
+
+        Ok(Value::Object(event))
+    }
+
+    fn quantities(
+        &mut self,
+        invoice_id: &str,
+        r#type: InvoiceLineType,
+    ) -> Result<Quantities, RepositoryError> {
+        let lines = InvoiceLineRepository::new(self.connection).query_by_filter(
+            InvoiceLineFilter::new()
+                .invoice_id(EqualFilter::equal_to(invoice_id))
+                .r#type(r#type.equal_to()),
+        )?;
+
+        let mut result = Quantities::default();
+        for line in lines {
+            let serial_numbers = self.serial_numbers(&line.invoice_line_row.id)?;
+            let gtin = self.gtin(&line)?;
+            let item_id = &line.item_row.id;
+
+            if !serial_numbers.is_empty() {
+                result.epcs.extend(
+                    serial_numbers
+                        .iter()
+                        .map(|serial| instance_id(item_id, gtin.as_deref(), serial)),
+                );
+                continue;
+            }
+
+            let row = &line.invoice_line_row;
+            if row.number_of_packs == 0.0 {
+                continue;
+            }
+            result.quantities.push(json!({
+                "epcClass": class_id(item_id, gtin.as_deref(), row.batch.as_deref()),
+                "quantity": row.number_of_packs * row.pack_size,
+            }));
+        }
+
+        Ok(result)
+    }
+
+    fn serial_numbers(&self, invoice_line_id: &str) -> Result<Vec<String>, RepositoryError> {
+        let serial_number_ids = SerialNumberLineRowRepository::new(self.connection)
+            .find_many_by_invoice_line_id(invoice_line_id)?
+            .into_iter()
+            .map(|line| line.serial_number_id)
+            .collect::<Vec<String>>();
+
+        if serial_number_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut serial_numbers = SerialNumberRowRepository::new(self.connection)
+            .find_many_by_id(&serial_number_ids)?
+            .into_iter()
+            .map(|row| row.serial_number)
+            .collect::<Vec<String>>();
+        serial_numbers.sort();
+
+        Ok(serial_numbers)
+    }
+
+    /// GTIN of the line's item, preferring a barcode matching the line's pack size
+    fn gtin(&mut self, line: &InvoiceLine) -> Result<Option<String>, RepositoryError> {
+        let item_id = &line.item_row.id;
+        if !self.barcodes.contains_key(item_id) {
+            let barcodes = BarcodeRepository::new(self.connection)
+                .query_by_filter(BarcodeFilter::new().item_id(EqualFilter::equal_to(item_id)))?
+                .into_iter()
+                .map(|barcode| barcode.barcode_row)
+                .filter(|row| is_gtin(&row.gtin))
+                .collect();
+            self.barcodes.insert(item_id.clone(), barcodes);
+        }
+
+        let barcodes = &self.barcodes[item_id];
+        let pack_size = line.invoice_line_row.pack_size;
+        let barcode = barcodes
+            .iter()
+            .find(|row| row.pack_size == Some(pack_size))
+            .or_else(|| barcodes.first());
+
+        Ok(barcode.map(|row| format!("{:0>14}", row.gtin)))
+    }
+
+    /// GS1 Digital Link of the name's GLN, falling back to an oms URN if the name has no GLN
+    fn location_id(&mut self, name_id: &str) -> Result<String, RepositoryError> {
+        if !self.glns.contains_key(name_id) {
+            let gln = NameRowRepository::new(self.connection)
+                .find_one_oms_fields_by_id(name_id)?
+                .and_then(|row| row.properties)
+                .and_then(|properties| serde_json::from_str::<Map<String, Value>>(&properties).ok())
+                .and_then(|properties| {
+                    properties
+                        .get(GLN_NAME_PROPERTY_KEY)
+                        .and_then(Value::as_str)
+                        .map(|gln| gln.trim().to_string())
+                })
+                .filter(|gln| gln.len() == 13 && gln.chars().all(|c| c.is_ascii_digit()));
+            self.glns.insert(name_id.to_string(), gln);
+        }
+
+        Ok(match &self.glns[name_id] {
+            Some(gln) => format!("{GS1_DIGITAL_LINK}/414/{gln}"),
+            None => format!("urn:omsupply:name:{name_id}"),
+        })
+    }
+}
+
+fn base_event(
+    r#type: &str,
+    invoice_id: &str,
+    biz_step: &str,
+    time: NaiveDateTime,
+) -> Map<String, Value> {
+    let mut event = Map::new();
+    event.insert("type".to_string(), json!(r#type));
+    // Deterministic so that re-exporting the same event can be de-duplicated by the receiver
+    event.insert(
+        "eventID".to_string(),
+        json!(format!("urn:omsupply:event:{invoice_id}:{biz_step}")),
+    );
+    event.insert("eventTime".to_string(), json!(format_event_time(&time)));
+    event.insert("eventTimeZoneOffset".to_string(), json!("+00:00"));
+    event.insert("bizStep".to_string(), json!(biz_step));
+    event
+}
+
+fn biz_transactions(invoice_id: &str, purchase_order_id: Option<&str>) -> Value {
+    let mut transactions = vec![json!({
+        "type": "desadv",
+        "bizTransaction": shipment_id(invoice_id),
+    })];
+    if let Some(purchase_order_id) = purchase_order_id {
+        transactions.push(json!({
+            "type": "po",
+            "bizTransaction": format!("urn:omsupply:purchase_order:{purchase_order_id}"),
+        }));
+    }
+    Value::Array(transactions)
+}
+
+fn shipment_id(invoice_id: &str) -> String {
+    format!("urn:omsupply:invoice:{invoice_id}")
+}
+
+fn class_id(item_id: &str, gtin: Option<&str>, batch: Option<&str>) -> String {
+    match (gtin, batch) {
+        (Some(gtin), Some(batch)) => format!("{GS1_DIGITAL_LINK}/01/{gtin}/10/{batch}"),
+        (Some(gtin), None) => format!("{GS1_DIGITAL_LINK}/01/{gtin}"),
+        (None, Some(batch)) => format!("urn:omsupply:item:{item_id}:lot:{batch}"),
+        (None, None) => format!("urn:omsupply:item:{item_id}"),
+    }
+}
+
+fn instance_id(item_id: &str, gtin: Option<&str>, serial_number: &str) -> String {
+    match gtin {
+        Some(gtin) => format!("{GS1_DIGITAL_LINK}/01/{gtin}/21/{serial_number}"),
+        None => format!("urn:omsupply:item:{item_id}:serial:{serial_number}"),
+    }
+}
+
+fn is_gtin(gtin: &str) -> bool {
+    matches!(gtin.len(), 8 | 12 | 13 | 14) && gtin.chars().all(|c| c.is_ascii_digit())
+}
+
+fn format_event_time(datetime: &NaiveDateTime) -> String {
+    datetime.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            mock_item_a, mock_name_store_b, mock_patient, mock_store_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        BarcodeRow, InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType,
+        NameRowRepository, SerialNumberLineRow, SerialNumberLineRowRepository, SerialNumberRow,
+        SerialNumberRowRepository,
+    };
+    use serde_json::json;
+
+    use super::*;
+
+    fn datetime(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap()
+    }
+
+    fn invoice(id: &str, r#type: InvoiceType, name_id: &str) -> InvoiceRow {
+        InvoiceRow {
+            id: id.to_string(),
+            name_id: name_id.to_string(),
+            store_id: mock_store_a().id,
+            r#type,
+            created_datetime: datetime(1),
+            ..Default::default()
+        }
+    }
+
+    fn line(id: &str, invoice_id: &str, r#type: InvoiceLineType) -> InvoiceLineRow {
+        InvoiceLineRow {
+            id: id.to_string(),
+            invoice_id: invoice_id.to_string(),
+            item_link_id: mock_item_a().id,
+            item_name: mock_item_a().name,
+            item_code: mock_item_a().code,
+            r#type,
+            batch: Some("B1".to_string()),
+            pack_size: 10.0,
+            number_of_packs: 2.0,
+            ..Default::default()
+        }
+    }
+
+    fn mock_data() -> MockData {
+        MockData {
+            barcodes: vec![BarcodeRow {
+                id: "epcis_barcode".to_string(),
+                gtin: "9312345678907".to_string(),
+                item_id: mock_item_a().id,
+                pack_size: Some(10.0),
+                ..Default::default()
+            }],
+            invoices: vec![
+                InvoiceRow {
+                    status: InvoiceStatus::Received,
+                    received_datetime: Some(datetime(2)),
+                    ..invoice(
+                        "epcis_inbound",
+                        InvoiceType::InboundShipment,
+                        &mock_name_store_b().id,
+                    )
+                },
+                InvoiceRow {
+                    status: InvoiceStatus::Shipped,
+                    picked_datetime: Some(datetime(3)),
+                    shipped_datetime: Some(datetime(4)),
+                    ..invoice(
+                        "epcis_outbound",
+                        InvoiceType::OutboundShipment,
+                        &mock_name_store_b().id,
+                    )
+                },
+                InvoiceRow {
+                    status: InvoiceStatus::Picked,
+                    picked_datetime: Some(datetime(5)),
+                    ..invoice(
+                        "epcis_prescription",
+                        InvoiceType::Prescription,
+                        &mock_patient().id,
+                    )
+                },
+                // Outside of period
+                InvoiceRow {
+                    status: InvoiceStatus::Picked,
+                    picked_datetime: Some(datetime(20)),
+                    ..invoice(
+                        "epcis_prescription_later",
+                        InvoiceType::Prescription,
+                        &mock_patient().id,
+                    )
+                },
+            ],
+            invoice_lines: vec![
+                line(
+                    "epcis_inbound_line",
+                    "epcis_inbound",
+                    InvoiceLineType::StockIn,
+                ),
+                line(
+                    "epcis_outbound_line",
+                    "epcis_outbound",
+                    InvoiceLineType::StockOut,
+                ),
+                InvoiceLineRow {
+                    batch: None,
+                    pack_size: 1.0,
+                    ..line(
+                        "epcis_prescription_line",
+                        "epcis_prescription",
+                        InvoiceLineType::StockOut,
+                    )
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_generate_epcis_document() {
+        let (_, connection, _, _) = setup_all_with_data(
+            "test_generate_epcis_document",
+            MockDataInserts::all(),
+            mock_data(),
+        )
+        .await;
+
+        NameRowRepository::new(&connection)
+            .update_properties(
+                &mock_name_store_b().id,
+                &Some(r#"{"gln":"9312345000005"}"#.to_string()),
+            )
+            .unwrap();
+        SerialNumberRowRepository::new(&connection)
+            .upsert_one(&SerialNumberRow {
+                id: "epcis_serial".to_string(),
+                item_id: mock_item_a().id,
+                store_id: mock_store_a().id,
+                serial_number: "S1".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+        SerialNumberLineRowRepository::new(&connection)
+            .upsert_one(&SerialNumberLineRow {
+                id: "epcis_serial_line".to_string(),
+                serial_number_id: "epcis_serial".to_string(),
+                store_id: mock_store_a().id,
+                invoice_line_id: Some("epcis_outbound_line".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let document = generate_epcis_document(
+            &connection,
+            &mock_store_a().id,
+            &EpcisPeriod {
+                from: datetime(1),
+                to: datetime(10),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(document["@context"], json!([EPCIS_CONTEXT]));
+        assert_eq!(document["schemaVersion"], json!("2.0"));
+
+        let events = document["epcisBody"]["eventList"].as_array().unwrap();
+        let steps: Vec<&str> = events
+            .iter()
+            .map(|event| event["bizStep"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            steps,
+            vec!["receiving", "packing", "shipping", "dispensing"]
+        );
+
+        let store_location = format!("urn:omsupply:name:{}", mock_store_a().name_id);
+        let supplier_location = "https://id.gs1.org/414/9312345000005";
+
+        // Receipt
+        let receipt = &events[0];
+        assert_eq!(receipt["type"], json!("ObjectEvent"));
+        assert_eq!(receipt["eventTime"], json!("2024-01-02T10:00:00.000Z"));
+        assert_eq!(
+            receipt["quantityList"],
+            json!([{ "epcClass": "https://id.gs1.org/01/09312345678907/10/B1", "quantity": 20.0 }])
+        );
+        assert_eq!(
+            receipt["sourceList"],
+            json!([{ "type": "owning_party", "source": supplier_location }])
+        );
+        assert_eq!(receipt["bizLocation"], json!({ "id": store_location }));
+
+        // Shipment, serialised units are listed individually
+        let packing = &events[1];
+        assert_eq!(packing["type"], json!("AggregationEvent"));
+        assert_eq!(
+            packing["parentID"],
+            json!("urn:omsupply:invoice:epcis_outbound")
+        );
+        assert_eq!(
+            packing["childEPCs"],
+            json!(["https://id.gs1.org/01/09312345678907/21/S1"])
+        );
+        let shipping = &events[2];
+        assert_eq!(shipping["type"], json!("TransactionEvent"));
+        assert_eq!(
+            shipping["destinationList"],
+            json!([{ "type": "owning_party", "destination": supplier_location }])
+        );
+        assert_eq!(
+            shipping["bizTransactionList"],
+            json!([{ "type": "desadv", "bizTransaction": "urn:omsupply:invoice:epcis_outbound" }])
+        );
+
+        // Dispensing, no barcode for pack size 1 so falls back to first barcode
+        let dispensing = &events[3];
+        assert_eq!(dispensing["disposition"], json!("dispensed"));
+        assert_eq!(
+            dispensing["quantityList"],
+            json!([{ "epcClass": "https://id.gs1.org/01/09312345678907", "quantity": 2.0 }])
+        );
+    }
+}
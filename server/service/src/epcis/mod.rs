@@ -0,0 +1,110 @@
+mod capture;
+mod generate;
+
+use chrono::{DateTime, Utc};
+use repository::{KeyType, KeyValueStoreRepository, RepositoryError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::SystemTime;
+
+use crate::{
+    service_provider::ServiceContext,
+    static_files::{StaticFileCategory, StaticFileService},
+};
+pub use capture::{capture_epcis_document, EpcisCaptureResult};
+pub use generate::{generate_epcis_document, EpcisPeriod, GLN_NAME_PROPERTY_KEY};
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct EpcisSettings {
+    /// Url of the capture endpoint of an EPCIS 2.0 repository
+    pub capture_url: String,
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EpcisExportError {
+    InvalidPeriod,
+    CaptureEndpointNotConfigured,
+    CaptureFailed(String),
+    FileError(String),
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for EpcisExportError {
+    fn from(error: RepositoryError) -> Self {
+        EpcisExportError::DatabaseError(error)
+    }
+}
+
+pub trait EpcisServiceTrait: Sync + Send {
+    /// Loads the EPCIS capture endpoint settings from the DB
+    fn epcis_settings(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Option<EpcisSettings>, RepositoryError> {
+        let key_value_store = KeyValueStoreRepository::new(&ctx.connection);
+
+        let settings = match key_value_store.get_string(KeyType::SettingsEpcis)? {
+            Some(value) => serde_json::from_str::<EpcisSettings>(&value).ok(),
+            None => None,
+        };
+
+        Ok(settings)
+    }
+
+    fn update_epcis_settings(
+        &self,
+        ctx: &ServiceContext,
+        settings: &EpcisSettings,
+    ) -> anyhow::Result<()> {
+        let key_value_store = KeyValueStoreRepository::new(&ctx.connection);
+        let serialised = serde_json::to_string(settings)?;
+
+        key_value_store.set_string(KeyType::SettingsEpcis, Some(serialised))?;
+
+        Ok(())
+    }
+
+    /// EPCIS document with the events of the context store in the period
+    fn generate_epcis_document(
+        &self,
+        ctx: &ServiceContext,
+        period: &EpcisPeriod,
+    ) -> Result<Value, EpcisExportError> {
+        if period.from > period.to {
+            return Err(EpcisExportError::InvalidPeriod);
+        }
+
+        Ok(generate_epcis_document(
+            &ctx.connection,
+            &ctx.store_id,
+            period,
+        )?)
+    }
+
+    /// Generates the EPCIS document and stores it as a JSON-LD file, returns the file id
+    fn export_epcis_to_file(
+        &self,
+        ctx: &ServiceContext,
+        base_dir: &str,
+        period: &EpcisPeriod,
+    ) -> Result<String, EpcisExportError> {
+        let document = self.generate_epcis_document(ctx, period)?;
+
+        let file_service = StaticFileService::new(base_dir)
+            .map_err(|err| EpcisExportError::FileError(format!("{err}")))?;
+        let now: DateTime<Utc> = SystemTime::now().into();
+        let file = file_service
+            .store_file(
+                &format!("{}_epcis.jsonld", now.format("%Y%m%d_%H%M%S")),
+                StaticFileCategory::Temporary,
+                document.to_string().as_bytes(),
+            )
+            .map_err(|err| EpcisExportError::FileError(format!("{err}")))?;
+
+        Ok(file.id)
+    }
+}
+
+pub struct EpcisService;
+impl EpcisServiceTrait for EpcisService {}
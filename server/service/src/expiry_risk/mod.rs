@@ -0,0 +1,431 @@
+mod projection;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Duration, NaiveDate};
+use repository::{
+    EqualFilter, RepositoryError, StockLine, StockLineFilter, StockLineRepository, StockLineRow,
+    StorageConnection, StoreFilter, StoreRepository,
+};
+use util::date_now;
+
+use crate::{
+    backend_plugin::plugin_provider::PluginError,
+    common::days_in_a_month,
+    item_stats::get_item_stats,
+    network_stock::{get_network_stock_levels, StoreItemStock},
+    service_provider::ServiceContext,
+    sync::CentralServerConfig,
+    PluginOrRepositoryError,
+};
+pub use projection::{months_to_expiry, project_consumption, LineProjection};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpiryRiskAction {
+    /// The line would be used before it expires if it was issued ahead of other stock
+    PrioritiseInAllocation,
+    /// Another store uses enough of the item to use the stock before it expires
+    Redistribute {
+        store_id: String,
+        quantity: f64,
+    },
+    ReturnToSupplier {
+        supplier_id: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StockLineExpiryRisk {
+    pub stock_line: StockLineRow,
+    /// Item the stock line's item link resolves to
+    pub item_id: String,
+    pub average_monthly_consumption: f64,
+    /// Available units on the line
+    pub quantity: f64,
+    /// Units projected to be used before the expiry date at the current AMC
+    pub projected_consumption: f64,
+    pub quantity_at_risk: f64,
+    /// Quantity at risk at cost price
+    pub value_at_risk: f64,
+    pub suggested_action: Option<ExpiryRiskAction>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpiryRiskInput {
+    pub item_ids: Option<Vec<String>>,
+    /// Only include stock expiring within this many months
+    pub expiring_within_months: Option<f64>,
+    /// Only include stock lines with quantity at risk
+    pub at_risk_only: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExpiryRiskError {
+    NotCentralServer,
+    PluginError(PluginError),
+    DatabaseError(RepositoryError),
+}
+
+pub trait ExpiryRiskServiceTrait: Sync + Send {
+    /// Projected expiry losses of the stock in the context store, largest value at risk first
+    fn get_expiry_risk(
+        &self,
+        ctx: &ServiceContext,
+        input: ExpiryRiskInput,
+    ) -> Result<Vec<StockLineExpiryRisk>, ExpiryRiskError> {
+        Ok(get_expiry_risk(
+            &ctx.connection,
+            &[ctx.store_id.clone()],
+            &input,
+            &date_now(),
+        )?)
+    }
+
+    /// Projected expiry losses across stores, or all active stores when `store_ids` is not set.
+    /// Only available on the central server
+    fn get_network_expiry_risk(
+        &self,
+        ctx: &ServiceContext,
+        store_ids: Option<Vec<String>>,
+        input: ExpiryRiskInput,
+    ) -> Result<Vec<StockLineExpiryRisk>, ExpiryRiskError> {
+        if !CentralServerConfig::is_central_server() {
+            return Err(ExpiryRiskError::NotCentralServer);
+        }
+
+        let store_ids = match store_ids {
+            Some(store_ids) => store_ids,
+            None => active_store_ids(&ctx.connection)?,
+        };
+        Ok(get_expiry_risk(
+            &ctx.connection,
+            &store_ids,
+            &input,
+            &date_now(),
+        )?)
+    }
+}
+
+pub struct ExpiryRiskService;
+impl ExpiryRiskServiceTrait for ExpiryRiskService {}
+
+fn active_store_ids(connection: &StorageConnection) -> Result<Vec<String>, RepositoryError> {
+    Ok(StoreRepository::new(connection)
+        .query_by_filter(StoreFilter::new())?
+        .into_iter()
+        .filter(|store| !store.store_row.is_disabled)
+        .map(|store| store.store_row.id)
+        .collect())
+}
+
+fn get_expiry_risk(
+    connection: &StorageConnection,
+    store_ids: &[String],
+    input: &ExpiryRiskInput,
+    reference_date: &NaiveDate,
+) -> Result<Vec<StockLineExpiryRisk>, PluginOrRepositoryError> {
+    let days_in_month = days_in_a_month(connection);
+
+    // Projection needs every line of an item, so the expiry window is applied afterwards
+    let mut filter = StockLineFilter::new()
+        .store_id(EqualFilter::equal_any(store_ids.to_vec()))
+        .is_available(true);
+    if let Some(item_ids) = &input.item_ids {
+        filter = filter.item_id(EqualFilter::equal_any(item_ids.clone()));
+    }
+    let stock_lines: Vec<StockLine> =
+        StockLineRepository::new(connection).query_by_filter(filter, None)?;
+
+    // Grouped by the resolved item, lines of merged items link to the item they were merged into
+    let mut lines_by_store_item: BTreeMap<(&str, &str), Vec<&StockLineRow>> = BTreeMap::new();
+    for StockLine {
+        stock_line_row,
+        item_row,
+        ..
+    } in &stock_lines
+    {
+        lines_by_store_item
+            .entry((stock_line_row.store_id.as_str(), item_row.id.as_str()))
+            .or_default()
+            .push(stock_line_row);
+    }
+
+    let mut amc_by_store_item: HashMap<(String, String), f64> = HashMap::new();
+    for store_id in store_ids {
+        let item_ids: Vec<String> = lines_by_store_item
+            .keys()
+            .filter(|(line_store_id, _)| *line_store_id == store_id.as_str())
+            .map(|(_, item_id)| item_id.to_string())
+            .collect();
+        if item_ids.is_empty() {
+            continue;
+        }
+        for stats in get_item_stats(connection, store_id, None, item_ids, None)? {
+            amc_by_store_item.insert(
+                (store_id.clone(), stats.item_id),
+                stats.average_monthly_consumption,
+            );
+        }
+    }
+
+    let expiry_cut_off = input
+        .expiring_within_months
+        .map(|months| *reference_date + Duration::days((months * days_in_month).round() as i64));
+    let mut risks = Vec::new();
+    for ((store_id, item_id), stock_lines) in lines_by_store_item {
+        let average_monthly_consumption = amc_by_store_item
+            .get(&(store_id.to_string(), item_id.to_string()))
+            .copied()
+            .unwrap_or_default();
+        // Expired stock is already lost and isn't part of the projection
+        let stock_lines = stock_lines.into_iter().filter(|line| {
+            line.expiry_date
+                .is_some_and(|expiry| expiry > *reference_date)
+        });
+
+        for projection in project_consumption(
+            stock_lines,
+            average_monthly_consumption,
+            reference_date,
+            days_in_month,
+        ) {
+            let in_window = match (expiry_cut_off, projection.stock_line.expiry_date) {
+                (Some(cut_off), Some(expiry)) => expiry <= cut_off,
+                _ => true,
+            };
+            let quantity_at_risk = projection.quantity_at_risk();
+            if !in_window || (input.at_risk_only && quantity_at_risk <= 0.0) {
+                continue;
+            }
+
+            let stock_line = projection.stock_line;
+            let cost_price_per_unit = if stock_line.pack_size > 0.0 {
+                stock_line.cost_price_per_pack / stock_line.pack_size
+            } else {
+                0.0
+            };
+            risks.push(StockLineExpiryRisk {
+                stock_line: stock_line.clone(),
+                item_id: item_id.to_string(),
+                average_monthly_consumption,
+                quantity: projection.quantity,
+                projected_consumption: projection.projected_consumption,
+                quantity_at_risk,
+                value_at_risk: quantity_at_risk * cost_price_per_unit,
+                suggested_action: None,
+            });
+        }
+    }
+
+    suggest_actions(connection, &mut risks, reference_date, days_in_month)?;
+
+    risks.sort_by(|a, b| b.value_at_risk.total_cmp(&a.value_at_risk));
+    Ok(risks)
+}
+
+/// Suggests what to do with stock at risk, in order of preference: issue it ahead of other stock,
+/// send it to a store that uses it faster (central server only, where other stores' stock levels
+/// are known) or return it to the supplier
+fn suggest_actions(
+    connection: &StorageConnection,
+    risks: &mut [StockLineExpiryRisk],
+    reference_date: &NaiveDate,
+    days_in_month: f64,
+) -> Result<(), PluginOrRepositoryError> {
+    let network_stock_levels = if CentralServerConfig::is_central_server() {
+        let item_ids: Vec<String> = risks
+            .iter()
+            .filter(|risk| risk.quantity_at_risk > 0.0)
+            .map(|risk| risk.item_id.clone())
+            .collect::<HashSet<String>>()
+            .into_iter()
+            .collect();
+        if item_ids.is_empty() {
+            Vec::new()
+        } else {
+            get_network_stock_levels(connection, &item_ids, None)?
+        }
+    } else {
+        Vec::new()
+    };
+
+    // Earliest expiry first, so the shortest dated stock gets first pick of receiving stores
+    let mut order: Vec<usize> = (0..risks.len()).collect();
+    order.sort_by_key(|index| risks[*index].stock_line.expiry_date);
+
+    let mut redistributed: HashMap<(String, String), f64> = HashMap::new();
+    for index in order {
+        let risk = &mut risks[index];
+        if risk.quantity_at_risk <= 0.0 {
+            continue;
+        }
+        let months_to_expiry =
+            months_to_expiry(&risk.stock_line, reference_date, days_in_month).unwrap_or_default();
+
+        risk.suggested_action = if !risk.stock_line.on_hold
+            && risk.average_monthly_consumption * months_to_expiry >= risk.quantity
+        {
+            Some(ExpiryRiskAction::PrioritiseInAllocation)
+        } else if let Some((store_id, quantity)) = best_receiver(
+            &network_stock_levels,
+            &redistributed,
+            &risk.item_id,
+            &risk.stock_line,
+            risk.quantity_at_risk,
+            months_to_expiry,
+        ) {
+            *redistributed
+                .entry((store_id.clone(), risk.item_id.clone()))
+                .or_default() += quantity;
+            Some(ExpiryRiskAction::Redistribute { store_id, quantity })
+        } else {
+            risk.stock_line
+                .supplier_id
+                .clone()
+                .map(|supplier_id| ExpiryRiskAction::ReturnToSupplier { supplier_id })
+        };
+    }
+
+    Ok(())
+}
+
+/// Store that can use the most of the stock before it expires, on top of its own stock and stock
+/// already suggested to be sent to it
+fn best_receiver(
+    network_stock_levels: &[StoreItemStock],
+    redistributed: &HashMap<(String, String), f64>,
+    item_id: &str,
+    stock_line: &StockLineRow,
+    quantity_at_risk: f64,
+    months_to_expiry: f64,
+) -> Option<(String, f64)> {
+    network_stock_levels
+        .iter()
+        .filter(|stock| stock.item_id == item_id && stock.store_id != stock_line.store_id)
+        .map(|stock| {
+            let already_redistributed = redistributed
+                .get(&(stock.store_id.clone(), stock.item_id.clone()))
+                .copied()
+                .unwrap_or_default();
+            let usable = stock.average_monthly_consumption * months_to_expiry
+                - stock.available_stock_on_hand
+                - already_redistributed;
+            (stock.store_id.clone(), usable.min(quantity_at_risk))
+        })
+        .filter(|(_, quantity)| *quantity > 0.0)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+impl From<RepositoryError> for ExpiryRiskError {
+    fn from(error: RepositoryError) -> Self {
+        ExpiryRiskError::DatabaseError(error)
+    }
+}
+
+impl From<PluginOrRepositoryError> for ExpiryRiskError {
+    fn from(error: PluginOrRepositoryError) -> Self {
+        match error {
+            PluginOrRepositoryError::RepositoryError(error) => error.into(),
+            PluginOrRepositoryError::PluginError(error) => ExpiryRiskError::PluginError(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_item_a, mock_item_b, mock_name_a, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        ItemLinkRow, ItemLinkRowRepository, StockLineRow, StockLineRowRepository,
+    };
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_get_expiry_risk() {
+        let reference_date = date_now();
+        let stock_line = |id: &str, packs: f64, expiry_days: i64| StockLineRow {
+            id: id.to_string(),
+            item_link_id: mock_item_a().id,
+            store_id: mock_store_a().id,
+            supplier_id: Some(mock_name_a().id),
+            pack_size: 1.0,
+            cost_price_per_pack: 2.0,
+            available_number_of_packs: packs,
+            total_number_of_packs: packs,
+            expiry_date: Some(reference_date + Duration::days(expiry_days)),
+            ..Default::default()
+        };
+
+        let (_, connection, _, _) = setup_all_with_data(
+            "test_get_expiry_risk",
+            MockDataInserts::none().stores().names().units().items(),
+            MockData {
+                stock_lines: vec![
+                    stock_line("expiring", 5.0, 30),
+                    stock_line("expired", 5.0, -1),
+                ],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        // No consumption so all stock that hasn't expired yet is at risk
+        let risks = get_expiry_risk(
+            &connection,
+            &[mock_store_a().id],
+            &ExpiryRiskInput::default(),
+            &reference_date,
+        )
+        .unwrap();
+
+        assert_eq!(risks.len(), 1);
+        let risk = &risks[0];
+        assert_eq!(risk.stock_line.id, "expiring");
+        assert_eq!(risk.quantity_at_risk, 5.0);
+        assert_eq!(risk.value_at_risk, 10.0);
+        assert_eq!(
+            risk.suggested_action,
+            Some(ExpiryRiskAction::ReturnToSupplier {
+                supplier_id: mock_name_a().id
+            })
+        );
+
+        // Outside of the expiry window
+        let risks = get_expiry_risk(
+            &connection,
+            &[mock_store_a().id],
+            &ExpiryRiskInput {
+                expiring_within_months: Some(0.5),
+                ..Default::default()
+            },
+            &reference_date,
+        )
+        .unwrap();
+        assert!(risks.is_empty());
+
+        // Stock of a merged item is grouped under the item it was merged into
+        ItemLinkRowRepository::new(&connection)
+            .upsert_one(&ItemLinkRow {
+                id: mock_item_b().id,
+                item_id: mock_item_a().id,
+            })
+            .unwrap();
+        StockLineRowRepository::new(&connection)
+            .upsert_one(&StockLineRow {
+                item_link_id: mock_item_b().id,
+                ..stock_line("merged", 5.0, 60)
+            })
+            .unwrap();
+
+        let risks = get_expiry_risk(
+            &connection,
+            &[mock_store_a().id],
+            &ExpiryRiskInput::default(),
+            &reference_date,
+        )
+        .unwrap();
+        assert_eq!(risks.len(), 2);
+        assert!(risks.iter().all(|risk| risk.item_id == mock_item_a().id));
+    }
+}
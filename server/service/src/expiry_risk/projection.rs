@@ -0,0 +1,122 @@
+use chrono::NaiveDate;
+use repository::StockLineRow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineProjection<'a> {
+    pub stock_line: &'a StockLineRow,
+    /// None when the line has no expiry date
+    pub months_to_expiry: Option<f64>,
+    /// Available units on the line
+    pub quantity: f64,
+    /// Units used before the line expires
+    pub projected_consumption: f64,
+}
+
+impl LineProjection<'_> {
+    pub fn quantity_at_risk(&self) -> f64 {
+        self.quantity - self.projected_consumption
+    }
+}
+
+pub fn months_to_expiry(
+    stock_line: &StockLineRow,
+    reference_date: &NaiveDate,
+    days_in_month: f64,
+) -> Option<f64> {
+    stock_line
+        .expiry_date
+        .map(|expiry| (expiry - *reference_date).num_days() as f64 / days_in_month)
+}
+
+/// Projects how much of each stock line of one item is used before it expires, when the store
+/// keeps consuming at `average_monthly_consumption` and issues first expiry first. Lines on hold
+/// are not issued. Projections are returned first expiry first, lines without expiry last
+pub fn project_consumption<'a>(
+    stock_lines: impl IntoIterator<Item = &'a StockLineRow>,
+    average_monthly_consumption: f64,
+    reference_date: &NaiveDate,
+    days_in_month: f64,
+) -> Vec<LineProjection<'a>> {
+    let mut stock_lines: Vec<&StockLineRow> = stock_lines.into_iter().collect();
+    stock_lines
+        .sort_by_key(|stock_line| (stock_line.expiry_date.is_none(), stock_line.expiry_date));
+
+    let mut consumed = 0.0;
+    stock_lines
+        .into_iter()
+        .map(|stock_line| {
+            let quantity = stock_line.available_number_of_packs * stock_line.pack_size;
+            let months_to_expiry = months_to_expiry(stock_line, reference_date, days_in_month);
+            let projected_consumption = match (stock_line.on_hold, months_to_expiry) {
+                (true, _) => 0.0,
+                (false, Some(months)) => {
+                    (average_monthly_consumption * months - consumed).clamp(0.0, quantity)
+                }
+                (false, None) => quantity,
+            };
+            consumed += projected_consumption;
+
+            LineProjection {
+                stock_line,
+                months_to_expiry,
+                quantity,
+                projected_consumption,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stock_line(id: &str, packs: f64, expiry: Option<NaiveDate>) -> StockLineRow {
+        StockLineRow {
+            id: id.to_string(),
+            pack_size: 10.0,
+            available_number_of_packs: packs,
+            expiry_date: expiry,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_project_consumption() {
+        let reference_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let in_months = |months: i64| Some(reference_date + chrono::Duration::days(months * 30));
+        let stock_lines = vec![
+            stock_line("no_expiry", 1.0, None),
+            stock_line("six_months", 10.0, in_months(6)),
+            stock_line("two_months", 5.0, in_months(2)),
+            StockLineRow {
+                on_hold: true,
+                ..stock_line("on_hold", 1.0, in_months(1))
+            },
+        ];
+
+        // 20 units a month
+        let projections = project_consumption(&stock_lines, 20.0, &reference_date, 30.0);
+        let summary: Vec<(&str, f64, f64)> = projections
+            .iter()
+            .map(|projection| {
+                (
+                    projection.stock_line.id.as_str(),
+                    projection.projected_consumption,
+                    projection.quantity_at_risk(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("on_hold", 0.0, 10.0),
+                // 40 used in two months
+                ("two_months", 40.0, 10.0),
+                // 120 used in six months, 40 of those from the earlier line
+                ("six_months", 80.0, 20.0),
+                ("no_expiry", 10.0, 0.0),
+            ]
+        );
+    }
+}
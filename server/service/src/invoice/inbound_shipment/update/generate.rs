@@ -19,6 +19,7 @@ use crate::invoice::{
     },
     stock_effect::{stock_effects, StockEffect},
 };
+use crate::recall::is_stock_recalled;
 use crate::service_provider::ServiceContext;
 
 use super::{
@@ -565,6 +566,14 @@ pub fn generate_lines_and_stock_lines(
             ..
         }: InvoiceLineRow = invoice_line;
 
+        // Recalled stock is put on hold as soon as it's received
+        let on_hold = is_stock_recalled(
+            connection,
+            &item_link_id,
+            batch.as_deref(),
+            item_variant_id.as_deref(),
+        )?;
+
         let stock_line = StockLineRow {
             id: stock_line_id,
             item_link_id,
@@ -588,7 +597,7 @@ pub fn generate_lines_and_stock_lines(
             volume_per_pack,
             total_volume: volume_per_pack * number_of_packs,
             manufacture_date,
-            on_hold: false,
+            on_hold,
             barcode_id: None,
         };
         result.push(LineAndStockLine {
@@ -1,5 +1,10 @@
 use crate::activity_log::{activity_log_entry_with_store, log_type_from_invoice_status};
+use crate::backend_plugin::plugin_provider::{PluginError, PluginInstance};
+use crate::backend_plugin::types::validate_mutation::{
+    PluginValidationFailed, ProposedRows, ValidateMutationError,
+};
 use crate::invoice_line::ShipmentTaxUpdate;
+use crate::serial_number::link_serial_numbers_to_stock_line;
 use crate::{invoice::query::get_invoice, service_provider::ServiceContext, WithDBError};
 use chrono::{DateTime, Utc};
 use repository::vvm_status::vvm_status_log_row::VVMStatusLogRowRepository;
@@ -110,6 +115,7 @@ pub fn update_inbound_shipment(
                 for LineAndStockLine { line, stock_line } in updates.into_iter() {
                     if let Some(ref stock_line) = stock_line {
                         stock_line_repository.upsert_one(stock_line)?;
+                        link_serial_numbers_to_stock_line(connection, &line.id, &stock_line.id)?;
                     }
                     invoice_line_repository.upsert_one(&line)?;
                 }
@@ -170,6 +176,12 @@ pub fn update_inbound_shipment(
             }
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    store_id.unwrap_or(&ctx.store_id),
+                    ProposedRows::invoice(connection, update_invoice.clone())?,
+                )?;
+
                 activity_log_entry_with_store(
                     ctx,
                     log_type_from_invoice_status(&update_invoice.status, false),
@@ -226,8 +238,10 @@ pub enum UpdateInboundShipmentError {
     OtherPartyDoesNotExist,
     OtherPartyNotVisible,
     OtherPartyNotASupplier,
+    PluginValidationFailed(PluginValidationFailed),
     // Internal
     PreferenceError(String),
+    PluginError(PluginError),
     DatabaseError(RepositoryError),
     UpdatedInvoiceDoesNotExist,
 }
@@ -244,6 +258,19 @@ impl From<crate::preference::PreferenceError> for UpdateInboundShipmentError {
     }
 }
 
+impl From<ValidateMutationError> for UpdateInboundShipmentError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateInboundShipmentError::PluginError(error)
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateInboundShipmentError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 impl<ERR> From<WithDBError<ERR>> for UpdateInboundShipmentError
 where
     ERR: Into<UpdateInboundShipmentError>,
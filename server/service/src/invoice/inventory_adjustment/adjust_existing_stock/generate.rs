@@ -181,6 +181,7 @@ pub fn generate(
             item_variant_id: None,
             donor_id: None,
             manufacturer_id: None,
+            dispensing_override_reason: None,
         }),
     };
 
@@ -1,7 +1,9 @@
+use chrono::NaiveDateTime;
 use repository::Invoice;
 use repository::InvoiceFilter;
 use repository::InvoiceLine;
 use repository::InvoiceSort;
+use repository::Keyset;
 use repository::PaginationOption;
 use repository::RepositoryError;
 use repository::StockLine;
@@ -67,6 +69,17 @@ pub trait InvoiceServiceTrait: Sync + Send {
         get_invoices(ctx, store_id_option, pagination, filter, sort)
     }
 
+    fn get_invoices_after(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+        filter: Option<InvoiceFilter>,
+    ) -> Result<ListResult<Invoice>, ListError> {
+        get_invoices_after(ctx, store_id, after, limit, filter)
+    }
+
     fn get_invoice_by_number(
         &self,
         ctx: &ServiceContext,
@@ -11,6 +11,10 @@ use generate::generate;
 use validate::validate;
 
 use crate::activity_log::{activity_log_entry, log_type_from_invoice_status};
+use crate::backend_plugin::plugin_provider::{PluginError, PluginInstance};
+use crate::backend_plugin::types::validate_mutation::{
+    PluginValidationFailed, ProposedRows, ValidateMutationError,
+};
 use crate::invoice::outbound_shipment::update::generate::GenerateResult;
 use crate::invoice::query::get_invoice;
 use crate::invoice_line::ShipmentTaxUpdate;
@@ -61,6 +65,8 @@ pub enum UpdateOutboundShipmentError {
     UpdatedInvoiceDoesNotExist,
     DatabaseError(RepositoryError),
     PreferenceError(crate::preference::PreferenceError),
+    PluginError(PluginError),
+    PluginValidationFailed(PluginValidationFailed),
     /// Holds the id of the invalid invoice line
     InvoiceLineHasNoStockLine(String),
 }
@@ -112,6 +118,12 @@ pub fn update_outbound_shipment(
             }
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    &ctx.store_id,
+                    ProposedRows::invoice(connection, update_invoice.clone())?,
+                )?;
+
                 activity_log_entry(
                     ctx,
                     log_type_from_invoice_status(&update_invoice.status, false),
@@ -146,6 +158,19 @@ impl From<crate::preference::PreferenceError> for UpdateOutboundShipmentError {
     }
 }
 
+impl From<ValidateMutationError> for UpdateOutboundShipmentError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateOutboundShipmentError::PluginError(error)
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateOutboundShipmentError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 impl From<TransactionError<UpdateOutboundShipmentError>> for UpdateOutboundShipmentError {
     fn from(error: TransactionError<UpdateOutboundShipmentError>) -> Self {
         match error {
@@ -1,6 +1,11 @@
 use crate::{
     activity_log::{activity_log_entry, log_type_from_invoice_status},
+    backend_plugin::{
+        plugin_provider::{PluginError, PluginInstance},
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
     invoice::{query::get_invoice, stock_effect::StockEffect},
+    serial_number::release_invoice_line_serial_numbers,
     service_provider::ServiceContext,
     NullableUpdate,
 };
@@ -51,9 +56,11 @@ pub enum UpdatePrescriptionError {
     NotThisStoreInvoice,
     ClinicianDoesNotExist,
     PatientDoesNotExist,
+    PluginValidationFailed(PluginValidationFailed),
     // Internal
     UpdatedInvoiceDoesNotExist,
     DatabaseError(RepositoryError),
+    PluginError(PluginError),
     /// Holds the id of the invalid invoice line
     InvoiceLineHasNoStockLine(String),
     /// Can't backdate an invoice with allocated lines
@@ -86,6 +93,12 @@ pub fn update_prescription(
             }
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    &ctx.store_id,
+                    ProposedRows::invoice(connection, update_invoice.clone())?,
+                )?;
+
                 activity_log_entry(
                     ctx,
                     log_type_from_invoice_status(&update_invoice.status, true),
@@ -135,6 +148,9 @@ pub fn create_reverse_prescription(
 
     // Reverse the stock direction of each line and update DB
     for mut line in lines {
+        // Units issued to the patient are back in stock
+        release_invoice_line_serial_numbers(connection, &line.invoice_line_row, 0.0)?;
+
         line.invoice_line_row.id = uuid();
         line.invoice_line_row.invoice_id = new_invoice.id.clone();
         line.invoice_line_row.r#type = match line.invoice_line_row.r#type {
@@ -189,6 +205,19 @@ impl From<RepositoryError> for UpdatePrescriptionError {
     }
 }
 
+impl From<ValidateMutationError> for UpdatePrescriptionError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdatePrescriptionError::PluginError(error)
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdatePrescriptionError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use chrono::NaiveDate;
@@ -1,7 +1,9 @@
 use crate::{
     get_pagination_or_default, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
 };
+use chrono::NaiveDateTime;
 use repository::EqualFilter;
+use repository::Keyset;
 use repository::PaginationOption;
 use repository::{Invoice, InvoiceFilter, InvoiceRepository, InvoiceSort, RepositoryError};
 
@@ -27,6 +29,27 @@ pub fn get_invoices(
     })
 }
 
+/// Page of the store's invoices after `after` by created datetime, for keyset (cursor) paging.
+/// Reads `limit` rows, cancellation reversals are left out as in `get_invoices`
+pub fn get_invoices_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<NaiveDateTime>>,
+    limit: u32,
+    filter: Option<InvoiceFilter>,
+) -> Result<ListResult<Invoice>, ListError> {
+    let repository = InvoiceRepository::new(&ctx.connection);
+
+    let mut filter = filter.unwrap_or_default();
+    filter.store_id = Some(EqualFilter::equal_to(store_id.to_string()));
+    filter.is_cancellation = Some(false);
+
+    Ok(ListResult {
+        rows: repository.query_after(Some(filter.clone()), after, limit)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
+
 pub fn get_invoice(
     ctx: &ServiceContext,
     store_id_option: Option<&str>,
@@ -199,6 +199,7 @@ fn generate_new_line(
         item_variant_id: None,
         donor_id: None,
         manufacturer_id: None,
+        dispensing_override_reason: None,
     }
 }
 
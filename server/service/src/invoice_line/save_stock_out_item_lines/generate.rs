@@ -41,7 +41,8 @@ pub fn generate(
         lines,
         placeholder_quantity,
         prescribed_quantity: _,
-        note: _,
+        note,
+        dispensing_override_reason,
     }: SaveStockOutItemLines,
 ) -> Result<GenerateResult, SaveStockOutItemLinesError> {
     let stock_out_type = match invoice.r#type {
@@ -101,7 +102,8 @@ pub fn generate(
                 prescribed_quantity: None,
                 total_before_tax: None,
                 tax_percentage: None,
-                note: None,
+                // Directions are needed for the dispensing safety checks on insert
+                note: note.clone(),
                 location_id: None,
                 cost_price_per_pack: None,
                 sell_price_per_pack: None,
@@ -109,6 +111,7 @@ pub fn generate(
                 item_variant_id: None,
                 donor_id: None,
                 manufacturer_id: None,
+                dispensing_override_reason: dispensing_override_reason.clone(),
             },
         )
         .collect();
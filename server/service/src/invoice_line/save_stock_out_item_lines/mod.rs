@@ -30,6 +30,8 @@ pub struct SaveStockOutItemLines {
     pub placeholder_quantity: Option<f64>,
     pub prescribed_quantity: Option<f64>,
     pub note: Option<String>,
+    /// Reason for dispensing despite dispensing safety errors, see `InsertStockOutLine`
+    pub dispensing_override_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
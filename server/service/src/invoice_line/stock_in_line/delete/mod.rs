@@ -1,5 +1,7 @@
 use crate::{
-    invoice::common::generate_invoice_user_id_update, service_provider::ServiceContext, WithDBError,
+    invoice::common::generate_invoice_user_id_update,
+    serial_number::release_invoice_line_serial_numbers, service_provider::ServiceContext,
+    WithDBError,
 };
 use repository::{
     vvm_status::vvm_status_log_row::VVMStatusLogRowRepository, InvoiceLineRowRepository,
@@ -42,6 +44,7 @@ pub fn delete_stock_in_line(
                 }
             }
 
+            release_invoice_line_serial_numbers(connection, &line, 0.0)?;
             InvoiceLineRowRepository::new(connection).delete(&line.id)?;
 
             let delete_batch_id_option = line.stock_line_id.clone();
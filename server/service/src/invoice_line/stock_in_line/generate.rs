@@ -3,6 +3,8 @@ use repository::{
 };
 use util::uuid::uuid;
 
+use crate::recall::is_stock_recalled;
+
 pub fn convert_invoice_line_to_single_pack(invoice_line: InvoiceLineRow) -> InvoiceLineRow {
     InvoiceLineRow {
         number_of_packs: invoice_line.number_of_packs * invoice_line.pack_size,
@@ -93,6 +95,15 @@ pub fn generate_batch(
         None => (barcode_id, Some(supplier_link_id)),
     };
 
+    // Recalled stock is put on hold as soon as it's received
+    let on_hold = on_hold
+        || is_stock_recalled(
+            connection,
+            &item_link_id,
+            batch.as_deref(),
+            item_variant_id.as_deref(),
+        )?;
+
     let stock_line_row = StockLineRow {
         id: stock_line_id,
         item_link_id,
@@ -1,6 +1,12 @@
 use crate::{
-    invoice::inbound_shipment::InboundShipmentType, invoice_line::query::get_invoice_line,
-    service_provider::ServiceContext, NullableUpdate, WithDBError,
+    backend_plugin::{
+        plugin_provider::PluginInstance,
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
+    invoice::inbound_shipment::InboundShipmentType,
+    invoice_line::query::get_invoice_line,
+    service_provider::ServiceContext,
+    NullableUpdate, WithDBError,
 };
 use chrono::NaiveDate;
 use repository::{
@@ -72,7 +78,13 @@ pub fn insert_stock_in_line(
                 stock_line,
                 barcode,
                 vvm_status_log,
-            } = generate(connection, &ctx.user_id, input.clone(), item, invoice)?;
+            } = generate(
+                connection,
+                &ctx.user_id,
+                input.clone(),
+                item,
+                invoice.clone(),
+            )?;
 
             if let Some(barcode_row) = barcode {
                 BarcodeRowRepository::new(connection).upsert_one(&barcode_row)?;
@@ -83,13 +95,19 @@ pub fn insert_stock_in_line(
             }
             InvoiceLineRowRepository::new(connection).upsert_one(&invoice_line)?;
 
-            if let Some(invoice_row) = invoice_user_update {
-                InvoiceRowRepository::new(connection).upsert_one(&invoice_row)?;
+            if let Some(invoice_row) = &invoice_user_update {
+                InvoiceRowRepository::new(connection).upsert_one(invoice_row)?;
             }
             if let Some(vvm_status_log_row) = vvm_status_log {
                 VVMStatusLogRowRepository::new(connection).upsert_one(&vvm_status_log_row)?;
             }
 
+            PluginInstance::validate_mutation(
+                ctx,
+                &ctx.store_id,
+                ProposedRows::invoice(connection, invoice_user_update.unwrap_or(invoice))?,
+            )?;
+
             get_invoice_line(ctx, &invoice_line.id)
                 .map_err(OutError::DatabaseError)?
                 .ok_or(OutError::NewlyCreatedLineDoesNotExist)
@@ -124,6 +142,9 @@ pub enum InsertStockInLineError {
     PurchaseOrderLineIdRequired,
     PurchaseOrderLineDoesNotExist,
     WrongInboundShipmentType,
+    /// Plugin failed to run, holds the formatted plugin error
+    PluginError(String),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl From<RepositoryError> for InsertStockInLineError {
@@ -132,6 +153,19 @@ impl From<RepositoryError> for InsertStockInLineError {
     }
 }
 
+impl From<ValidateMutationError> for InsertStockInLineError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                InsertStockInLineError::PluginError(error.to_string())
+            }
+            ValidateMutationError::Failed(failed) => {
+                InsertStockInLineError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 impl<ERR> From<WithDBError<ERR>> for InsertStockInLineError
 where
     ERR: Into<InsertStockInLineError>,
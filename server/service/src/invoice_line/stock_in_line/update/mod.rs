@@ -1,5 +1,10 @@
 use crate::{
+    backend_plugin::{
+        plugin_provider::PluginInstance,
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
     invoice_line::{query::get_invoice_line, ShipmentTaxUpdate},
+    serial_number::release_invoice_line_serial_numbers,
     service_provider::ServiceContext,
     NullableUpdate, WithDBError,
 };
@@ -67,7 +72,7 @@ pub fn update_stock_in_line(
                 upsert_batch_option,
                 batch_to_delete_id,
                 vvm_status_log_option,
-            } = generate(connection, &ctx.user_id, input, line, item, invoice)?;
+            } = generate(connection, &ctx.user_id, input, line, item, invoice.clone())?;
 
             let stock_line_repository = StockLineRowRepository::new(connection);
             if let Some(upsert_batch) = upsert_batch_option {
@@ -75,19 +80,30 @@ pub fn update_stock_in_line(
             }
 
             InvoiceLineRowRepository::new(connection).upsert_one(&updated_line)?;
+            release_invoice_line_serial_numbers(
+                connection,
+                &updated_line,
+                updated_line.number_of_packs * updated_line.pack_size,
+            )?;
 
             if let Some(id) = batch_to_delete_id {
                 stock_line_repository.delete(&id)?;
             }
 
-            if let Some(invoice_row) = invoice_row_option {
-                InvoiceRowRepository::new(connection).upsert_one(&invoice_row)?;
+            if let Some(invoice_row) = &invoice_row_option {
+                InvoiceRowRepository::new(connection).upsert_one(invoice_row)?;
             }
 
             if let Some(vvm_status_log_row) = vvm_status_log_option {
                 VVMStatusLogRowRepository::new(connection).upsert_one(&vvm_status_log_row)?;
             }
 
+            PluginInstance::validate_mutation(
+                ctx,
+                &ctx.store_id,
+                ProposedRows::invoice(connection, invoice_row_option.unwrap_or(invoice))?,
+            )?;
+
             get_invoice_line(ctx, &updated_line.id)
                 .map_err(OutError::DatabaseError)?
                 .ok_or(OutError::UpdatedLineDoesNotExist)
@@ -123,6 +139,22 @@ pub enum UpdateStockInLineError {
     CampaignDoesNotExist,
     WrongInboundShipmentType,
     CannotEditCostPrice,
+    /// Plugin failed to run, holds the formatted plugin error
+    PluginError(String),
+    PluginValidationFailed(PluginValidationFailed),
+}
+
+impl From<ValidateMutationError> for UpdateStockInLineError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateStockInLineError::PluginError(error.to_string())
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateStockInLineError::PluginValidationFailed(failed)
+            }
+        }
+    }
 }
 
 impl From<RepositoryError> for UpdateStockInLineError {
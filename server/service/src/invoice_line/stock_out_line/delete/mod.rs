@@ -1,6 +1,7 @@
 use super::StockOutType;
 use crate::{
-    invoice_line::stock_in_line::get_existing_vvm_status_log_id, service_provider::ServiceContext,
+    invoice_line::stock_in_line::get_existing_vvm_status_log_id,
+    serial_number::release_invoice_line_serial_numbers, service_provider::ServiceContext,
 };
 use repository::{
     vvm_status::vvm_status_log_row::VVMStatusLogRowRepository, InvoiceLineRowRepository,
@@ -36,6 +37,7 @@ pub fn delete_stock_out_line(
                 }
             }
 
+            release_invoice_line_serial_numbers(connection, &line, 0.0)?;
             InvoiceLineRowRepository::new(connection).delete(&line.id)?;
 
             if let Some(stock_line_id) = stock_line_id_option {
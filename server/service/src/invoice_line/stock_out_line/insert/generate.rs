@@ -110,6 +110,7 @@ fn generate_batch_update(
         stock_line_id: _,
         total_before_tax: _,
         tax_percentage: _,
+        dispensing_override_reason: _,
     }: InsertStockOutLine,
     batch: StockLineRow,
     adjust_total_number_of_packs: bool,
@@ -177,6 +178,7 @@ fn generate_line(
         cost_price_per_pack: _,
         sell_price_per_pack: _,
         vvm_status_id: _,
+        dispensing_override_reason: _,
     }: InsertStockOutLine,
     ItemRow {
         id: item_id,
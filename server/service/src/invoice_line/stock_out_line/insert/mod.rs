@@ -1,5 +1,11 @@
 use super::StockOutType;
 use crate::{
+    activity_log::activity_log_entry,
+    backend_plugin::{
+        plugin_provider::PluginInstance,
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
+    dispensing_safety::DispensingAlert,
     invoice::update_picked_date::{update_picked_date, UpdatePickedDateError},
     invoice_line::{query::get_invoice_line, stock_out_line::insert::generate::GenerateResult},
     service_provider::ServiceContext,
@@ -7,14 +13,14 @@ use crate::{
 };
 use chrono::NaiveDate;
 use repository::{
-    vvm_status::vvm_status_log_row::VVMStatusLogRowRepository, InvoiceLine,
+    vvm_status::vvm_status_log_row::VVMStatusLogRowRepository, ActivityLogType, InvoiceLine,
     InvoiceLineRowRepository, RepositoryError, StockLineRowRepository,
 };
 
 mod generate;
 use generate::generate;
 mod validate;
-use validate::validate;
+use validate::{validate, ValidateResult};
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct InsertStockOutLine {
@@ -42,6 +48,10 @@ pub struct InsertStockOutLine {
     pub item_variant_id: Option<NullableUpdate<String>>,
     pub donor_id: Option<NullableUpdate<String>>,
     pub manufacturer_id: Option<NullableUpdate<String>>,
+
+    /// Reason for dispensing despite dispensing safety errors, recorded in the activity log.
+    /// Only used for the first line of an item on a prescription
+    pub dispensing_override_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -60,8 +70,15 @@ pub enum InsertStockOutLineError {
     AutoPickFailed(String),
     NewlyCreatedLineDoesNotExist,
     BatchIsOnHold,
-    ReductionBelowZero { stock_line_id: String },
+    ReductionBelowZero {
+        stock_line_id: String,
+    },
     VVMStatusDoesNotExist,
+    /// Dispensing safety errors for the item, an override reason is needed to dispense it
+    DispensingAlertsRequireOverride(Vec<DispensingAlert>),
+    /// Plugin failed to run, holds the formatted plugin error
+    PluginError(String),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 impl From<RepositoryError> for InsertStockOutLineError {
@@ -70,6 +87,19 @@ impl From<RepositoryError> for InsertStockOutLineError {
     }
 }
 
+impl From<ValidateMutationError> for InsertStockOutLineError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                InsertStockOutLineError::PluginError(error.to_string())
+            }
+            ValidateMutationError::Failed(failed) => {
+                InsertStockOutLineError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 impl<ERR> From<WithDBError<ERR>> for InsertStockOutLineError
 where
     ERR: Into<InsertStockOutLineError>,
@@ -91,8 +121,14 @@ pub fn insert_stock_out_line(
     let new_line = ctx
         .connection
         .transaction_sync(|connection| {
-            let (item, invoice, batch, adjusted_input) =
-                validate(connection, input, &ctx.store_id)?;
+            let ValidateResult {
+                item,
+                invoice,
+                batch,
+                adjusted_input,
+                overridden_alerts,
+            } = validate(connection, input, &ctx.store_id)?;
+            let override_reason = adjusted_input.dispensing_override_reason.clone();
             let GenerateResult {
                 new_line,
                 update_batch,
@@ -105,6 +141,27 @@ pub fn insert_stock_out_line(
                 VVMStatusLogRowRepository::new(connection).upsert_one(&vvm_status_log)?;
             }
 
+            if !overridden_alerts.is_empty() {
+                let alerts = overridden_alerts
+                    .iter()
+                    .map(|alert| alert.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                activity_log_entry(
+                    ctx,
+                    ActivityLogType::DispensingAlertOverridden,
+                    Some(invoice.id.clone()),
+                    Some(alerts),
+                    override_reason,
+                )?;
+            }
+
+            PluginInstance::validate_mutation(
+                ctx,
+                &ctx.store_id,
+                ProposedRows::invoice(connection, invoice.clone())?,
+            )?;
+
             update_picked_date(ctx, &invoice).map_err(|e| match e {
                 UpdatePickedDateError::AutoPickFailed(msg) => OutError::AutoPickFailed(msg),
                 UpdatePickedDateError::RepositoryError(repo_error) => {
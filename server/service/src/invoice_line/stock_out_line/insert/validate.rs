@@ -1,6 +1,7 @@
-use super::{InsertStockOutLine, InsertStockOutLineError};
+use super::{InsertStockOutLine, InsertStockOutLineError, StockOutType};
 use crate::{
     check_vvm_status_exists,
+    dispensing_safety::{check_prescription_item, DispensingAlert, DispensingAlertSeverity},
     invoice::{check_invoice_exists, check_invoice_is_editable, check_invoice_type, check_store},
     invoice_line::{
         check_batch_exists, check_batch_on_hold, check_existing_stock_line, check_location_on_hold,
@@ -12,14 +13,24 @@ use crate::{
     stock_line::historical_stock::get_historical_stock_line_available_quantity,
 };
 use repository::{
-    InvoiceRow, InvoiceStatus, ItemRow, LocationRowRepository, StockLine, StorageConnection,
+    EqualFilter, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineType, InvoiceRow,
+    InvoiceStatus, ItemRow, LocationRowRepository, RepositoryError, StockLine, StorageConnection,
 };
 
+pub struct ValidateResult {
+    pub item: ItemRow,
+    pub invoice: InvoiceRow,
+    pub batch: StockLine,
+    pub adjusted_input: InsertStockOutLine,
+    /// Dispensing safety errors dispensed through with an override reason
+    pub overridden_alerts: Vec<DispensingAlert>,
+}
+
 pub fn validate(
     connection: &StorageConnection,
     input: InsertStockOutLine,
     store_id: &str,
-) -> Result<(ItemRow, InvoiceRow, StockLine, InsertStockOutLine), InsertStockOutLineError> {
+) -> Result<ValidateResult, InsertStockOutLineError> {
     use InsertStockOutLineError::*;
 
     if (check_line_exists(connection, &input.id)?).is_some() {
@@ -59,7 +70,7 @@ pub fn validate(
     if !check_invoice_is_editable(&invoice) {
         return Err(CannotEditFinalised);
     }
-    if !check_batch_on_hold(&batch, &input.r#type) {
+    if !check_batch_on_hold(connection, &batch, &input.r#type)? {
         return Err(BatchIsOnHold);
     }
 
@@ -110,5 +121,61 @@ pub fn validate(
         });
     }
 
-    Ok((item, invoice, batch, adjusted_input))
+    let overridden_alerts = if is_first_prescription_line(connection, &adjusted_input, &item)? {
+        check_dispensing_safety(connection, &adjusted_input, &invoice, &item)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(ValidateResult {
+        item,
+        invoice,
+        batch,
+        adjusted_input,
+        overridden_alerts,
+    })
+}
+
+/// Dispensing safety is checked when an item is first added to a prescription, further batches
+/// of the same item aren't checked again
+fn is_first_prescription_line(
+    connection: &StorageConnection,
+    input: &InsertStockOutLine,
+    item: &ItemRow,
+) -> Result<bool, RepositoryError> {
+    if input.r#type != StockOutType::Prescription {
+        return Ok(false);
+    }
+    let existing_lines = InvoiceLineRepository::new(connection).count(Some(
+        InvoiceLineFilter::new()
+            .invoice_id(EqualFilter::equal_to(input.invoice_id.clone()))
+            .item_id(EqualFilter::equal_to(item.id.clone()))
+            .r#type(InvoiceLineType::StockOut.equal_to()),
+    ))?;
+    Ok(existing_lines == 0)
+}
+
+fn check_dispensing_safety(
+    connection: &StorageConnection,
+    input: &InsertStockOutLine,
+    invoice: &InvoiceRow,
+    item: &ItemRow,
+) -> Result<Vec<DispensingAlert>, InsertStockOutLineError> {
+    let errors: Vec<DispensingAlert> =
+        check_prescription_item(connection, invoice, item, input.note.as_deref())?
+            .into_iter()
+            .filter(|alert| alert.severity == DispensingAlertSeverity::Error)
+            .collect();
+
+    let has_override_reason = input
+        .dispensing_override_reason
+        .as_deref()
+        .is_some_and(|reason| !reason.trim().is_empty());
+    if !errors.is_empty() && !has_override_reason {
+        return Err(InsertStockOutLineError::DispensingAlertsRequireOverride(
+            errors,
+        ));
+    }
+
+    Ok(errors)
 }
@@ -1,7 +1,12 @@
 use super::StockOutType;
 use crate::{
+    backend_plugin::{
+        plugin_provider::PluginInstance,
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
     invoice::update_picked_date::{update_picked_date, UpdatePickedDateError},
     invoice_line::{query::get_invoice_line, ShipmentTaxUpdate},
+    serial_number::release_invoice_line_serial_numbers,
     service_provider::ServiceContext,
 };
 use repository::{
@@ -56,6 +61,9 @@ pub enum UpdateStockOutLineError {
         line_id: String,
     },
     VVMStatusDoesNotExist,
+    /// Plugin failed to run, holds the formatted plugin error
+    PluginError(String),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 type OutError = UpdateStockOutLineError;
@@ -77,6 +85,13 @@ pub fn update_stock_out_line(
             } = generate(ctx, adjusted_input, line, item, batch_pair, invoice.clone())?;
             InvoiceLineRowRepository::new(connection).upsert_one(&update_line)?;
 
+            // Units picked from a previous stock line or beyond the line quantity are released
+            let serial_number_units = match batch_pair.previous_batch_option {
+                Some(_) => 0.0,
+                None => update_line.number_of_packs * update_line.pack_size,
+            };
+            release_invoice_line_serial_numbers(connection, &update_line, serial_number_units)?;
+
             let stock_line_repo = StockLineRowRepository::new(connection);
             stock_line_repo.upsert_one(&batch_pair.main_batch.stock_line_row)?;
             if let Some(previous_batch) = batch_pair.previous_batch_option {
@@ -87,6 +102,12 @@ pub fn update_stock_out_line(
                 VVMStatusLogRowRepository::new(connection).upsert_one(&vvm_status_log)?;
             }
 
+            PluginInstance::validate_mutation(
+                ctx,
+                &ctx.store_id,
+                ProposedRows::invoice(connection, invoice.clone())?,
+            )?;
+
             update_picked_date(ctx, &invoice).map_err(|e| match e {
                 UpdatePickedDateError::AutoPickFailed(msg) => OutError::AutoPickFailed(msg),
                 UpdatePickedDateError::RepositoryError(repo_error) => {
@@ -135,6 +156,19 @@ impl BatchPair {
     }
 }
 
+impl From<ValidateMutationError> for UpdateStockOutLineError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateStockOutLineError::PluginError(error.to_string())
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateStockOutLineError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 impl From<RepositoryError> for UpdateStockOutLineError {
     fn from(error: RepositoryError) -> Self {
         UpdateStockOutLineError::DatabaseError(error)
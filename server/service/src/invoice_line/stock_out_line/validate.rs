@@ -3,7 +3,7 @@ use repository::{
     StockLine, StockLineFilter, StockLineRepository, StorageConnection,
 };
 
-use crate::invoice_line::stock_out_line::StockOutType;
+use crate::{invoice_line::stock_out_line::StockOutType, recall::is_stock_recalled};
 
 const LAST_PACK_THRESHOLD: f64 = 0.001;
 
@@ -63,16 +63,31 @@ pub fn check_item_matches_batch(batch: &StockLine, item: &ItemRow) -> bool {
     true
 }
 
-pub fn check_batch_on_hold(batch: &StockLine, stock_out_type: &StockOutType) -> bool {
+pub fn check_batch_on_hold(
+    connection: &StorageConnection,
+    batch: &StockLine,
+    stock_out_type: &StockOutType,
+) -> Result<bool, RepositoryError> {
     // Even when stock is on hold, we can do inventory adjustments to ensure accurate stock levels
     if stock_out_type == &StockOutType::InventoryReduction {
-        return true;
+        return Ok(true);
     }
 
-    if batch.stock_line_row.on_hold {
-        return false;
+    if !batch.stock_line_row.on_hold {
+        return Ok(true);
     }
-    true
+
+    // Stock held because of a recall can still be returned to the supplier
+    if stock_out_type == &StockOutType::SupplierReturn {
+        return is_stock_recalled(
+            connection,
+            &batch.item_row.id,
+            batch.stock_line_row.batch.as_deref(),
+            batch.stock_line_row.item_variant_id.as_deref(),
+        );
+    }
+
+    Ok(false)
 }
 
 pub enum LocationIsOnHoldError {
@@ -138,10 +153,17 @@ mod test {
         assert_eq!(available_stock, adjusted_requested_stock);
     }
 
-    #[test]
-    fn test_check_batch_on_hold() {
+    #[actix_rt::test]
+    async fn test_check_batch_on_hold() {
         use super::{check_batch_on_hold, StockOutType};
-        use repository::{StockLine, StockLineRow};
+        use repository::{
+            mock::{mock_item_a, MockDataInserts},
+            test_db::setup_all,
+            RecallRow, RecallRowRepository, StockLine, StockLineRow,
+        };
+
+        let (_, connection, _, _) =
+            setup_all("test_check_batch_on_hold", MockDataInserts::none().items()).await;
 
         // Test batch not on hold - should always return true
         let batch_not_on_hold = StockLine {
@@ -153,28 +175,61 @@ mod test {
             ..Default::default()
         };
 
-        let result = check_batch_on_hold(&batch_not_on_hold, &StockOutType::OutboundShipment);
-        assert!(result);
+        let result = check_batch_on_hold(
+            &connection,
+            &batch_not_on_hold,
+            &StockOutType::OutboundShipment,
+        );
+        assert_eq!(result, Ok(true));
 
-        let result = check_batch_on_hold(&batch_not_on_hold, &StockOutType::InventoryReduction);
-        assert!(result);
+        let result = check_batch_on_hold(
+            &connection,
+            &batch_not_on_hold,
+            &StockOutType::InventoryReduction,
+        );
+        assert_eq!(result, Ok(true));
 
         // Test batch on hold - should return false for outbound shipment
         let batch_on_hold = StockLine {
             stock_line_row: StockLineRow {
                 id: "batch_2".to_string(),
+                batch: Some("recalled".to_string()),
                 on_hold: true,
                 ..Default::default()
             },
+            item_row: mock_item_a(),
             ..Default::default()
         };
 
-        let result = check_batch_on_hold(&batch_on_hold, &StockOutType::OutboundShipment);
-        assert!(!result);
+        let result =
+            check_batch_on_hold(&connection, &batch_on_hold, &StockOutType::OutboundShipment);
+        assert_eq!(result, Ok(false));
 
         // Test batch on hold - should return true for inventory reduction (i.e. allow adjustments)
-        let result = check_batch_on_hold(&batch_on_hold, &StockOutType::InventoryReduction);
-        assert!(result);
+        let result = check_batch_on_hold(
+            &connection,
+            &batch_on_hold,
+            &StockOutType::InventoryReduction,
+        );
+        assert_eq!(result, Ok(true));
+
+        // Test batch on hold - should return false for supplier return when batch is not recalled
+        let result =
+            check_batch_on_hold(&connection, &batch_on_hold, &StockOutType::SupplierReturn);
+        assert_eq!(result, Ok(false));
+
+        // Test batch on hold - should return true for supplier return of recalled batch
+        RecallRowRepository::new(&connection)
+            .upsert_one(&RecallRow {
+                id: "recall".to_string(),
+                item_id: mock_item_a().id,
+                batch: Some("recalled".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        let result =
+            check_batch_on_hold(&connection, &batch_on_hold, &StockOutType::SupplierReturn);
+        assert_eq!(result, Ok(true));
     }
 
     #[test]
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use chrono::Duration;
 use repository::{
     ConsumptionFilter, ConsumptionRepository, DateFilter, EqualFilter, Item, ItemFilter,
-    ItemRepository, ItemSort, Pagination, PaginationOption, RepositoryError, StorageConnection,
-    StorageConnectionManager,
+    ItemRepository, ItemSort, Keyset, Pagination, PaginationOption, RepositoryError,
+    StorageConnection, StorageConnectionManager,
 };
 use util::{date_now, date_with_offset, format_error};
 
@@ -17,6 +17,7 @@ use crate::{
         NumberOfMonthsToCheckForConsumptionWhenCalculatingOutOfStockProducts, Preference,
         PreferenceError,
     },
+    service_provider::ServiceContext,
     ListError, ListResult, PluginOrRepositoryError,
 };
 
@@ -35,34 +36,7 @@ pub fn get_items(
     let repository = ItemRepository::new(&connection);
 
     let filter = filter
-        .map(|mut filter| -> Result<ItemFilter, ListError> {
-            // If there is a filter for either min or max months of stock...
-            if filter.min_months_of_stock.is_some() || filter.max_months_of_stock.is_some() {
-                // ...then produce a list of item ids that have <= the max and >= the min months of stock...
-                let item_ids_filtered_by_mos = get_item_ids_by_mos(
-                    &connection,
-                    filter.clone(),
-                    store_id,
-                    filter.min_months_of_stock,
-                    filter.max_months_of_stock,
-                )?;
-                // ...and filter for only those ids.
-                filter = filter.id(EqualFilter::equal_any(item_ids_filtered_by_mos));
-            }
-
-            if filter.with_recent_consumption.is_some() {
-                let item_ids = get_items_with_consumption(&connection, filter.clone(), store_id)?;
-                filter = filter.id(EqualFilter::equal_any(item_ids))
-            }
-
-            if filter.products_at_risk_of_being_out_of_stock.is_some() {
-                let item_ids =
-                    get_products_at_risk_item_ids(&connection, filter.clone(), store_id)?;
-                filter = filter.id(EqualFilter::equal_any(item_ids))
-            }
-
-            Ok(filter)
-        })
+        .map(|filter| resolve_item_filter(&connection, filter, store_id))
         .transpose()?;
 
     let rows = repository.query(pagination, filter.clone(), sort, Some(store_id.to_string()))?;
@@ -73,6 +47,58 @@ pub fn get_items(
     })
 }
 
+/// Page of the store's items after `after` by name, for keyset (cursor) paging. Reads `limit` rows
+pub fn get_items_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<String>>,
+    limit: u32,
+    filter: Option<ItemFilter>,
+) -> Result<ListResult<Item>, ListError> {
+    let repository = ItemRepository::new(&ctx.connection);
+    let filter = filter
+        .map(|filter| resolve_item_filter(&ctx.connection, filter, store_id))
+        .transpose()?;
+
+    Ok(ListResult {
+        rows: repository.query_after(store_id.to_string(), filter.clone(), after, limit)?,
+        count: i64_to_u32(repository.count(store_id.to_string(), filter)?),
+    })
+}
+
+/// Replaces the stock and consumption based parts of the filter with the matching item ids
+fn resolve_item_filter(
+    connection: &StorageConnection,
+    mut filter: ItemFilter,
+    store_id: &str,
+) -> Result<ItemFilter, ListError> {
+    // If there is a filter for either min or max months of stock...
+    if filter.min_months_of_stock.is_some() || filter.max_months_of_stock.is_some() {
+        // ...then produce a list of item ids that have <= the max and >= the min months of stock...
+        let item_ids_filtered_by_mos = get_item_ids_by_mos(
+            connection,
+            filter.clone(),
+            store_id,
+            filter.min_months_of_stock,
+            filter.max_months_of_stock,
+        )?;
+        // ...and filter for only those ids.
+        filter = filter.id(EqualFilter::equal_any(item_ids_filtered_by_mos));
+    }
+
+    if filter.with_recent_consumption.is_some() {
+        let item_ids = get_items_with_consumption(connection, filter.clone(), store_id)?;
+        filter = filter.id(EqualFilter::equal_any(item_ids))
+    }
+
+    if filter.products_at_risk_of_being_out_of_stock.is_some() {
+        let item_ids = get_products_at_risk_item_ids(connection, filter.clone(), store_id)?;
+        filter = filter.id(EqualFilter::equal_any(item_ids))
+    }
+
+    Ok(filter)
+}
+
 pub fn get_item_ids_by_mos(
     connection: &StorageConnection,
     filter: ItemFilter,
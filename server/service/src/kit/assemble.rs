@@ -0,0 +1,396 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use repository::{
+    ActivityLogType, EqualFilter, InvoiceLineRowRepository, InvoiceRowRepository,
+    ItemRowRepository, KitAssemblyRow, KitAssemblyRowRepository, KitAssemblyType, KitComponentRow,
+    KitComponentRowRepository, LocationMovementRowRepository, RepositoryError, StockLine,
+    StockLineFilter, StockLineRepository, StockLineRow, StockLineRowRepository, StorageConnection,
+};
+use util::{date_now, uuid::uuid};
+
+use crate::{
+    activity_log::activity_log_entry,
+    check_location_exists,
+    repack::generate::{generate_location_movement, generate_repack_invoice},
+    service_provider::ServiceContext,
+};
+
+use super::{stock_in_line, stock_out_line};
+
+#[derive(Default, Clone, Debug)]
+pub struct AssembleKit {
+    pub id: String,
+    pub kit_item_id: String,
+    /// Units of the kit item to make
+    pub number_of_kits: f64,
+    /// Component stock lines to use, when empty all available component stock is used first
+    /// expiry first. Expired stock is never used
+    pub component_stock_line_ids: Vec<String>,
+    pub batch: Option<String>,
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AssembleKitError {
+    KitAssemblyAlreadyExists,
+    KitItemDoesNotExist,
+    KitHasNoComponents,
+    NumberOfKitsMustBePositive,
+    CannotHaveFractionalKits,
+    LocationDoesNotExist,
+    StockLineDoesNotExist(String),
+    StockLineIsNotAComponent(String),
+    NotEnoughComponentStock {
+        item_id: String,
+        required: f64,
+        available: f64,
+    },
+    DatabaseError(RepositoryError),
+}
+
+/// Component stock issued to the kit
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ComponentAllocation {
+    pub stock_line: StockLine,
+    pub number_of_packs: f64,
+}
+
+pub fn assemble_kit(
+    ctx: &ServiceContext,
+    input: AssembleKit,
+) -> Result<KitAssemblyRow, AssembleKitError> {
+    let kit_assembly = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (components, stock_lines) = validate(connection, &ctx.store_id, &input)?;
+            let allocations =
+                allocate_components(&components, stock_lines, input.number_of_kits, &date_now())?;
+
+            let kit_item = ItemRowRepository::new(connection)
+                .find_active_by_id(&input.kit_item_id)?
+                .ok_or(AssembleKitError::KitItemDoesNotExist)?;
+            let invoice = generate_repack_invoice(ctx)?;
+
+            let stock_line_repo = StockLineRowRepository::new(connection);
+            let mut invoice_lines = Vec::new();
+            let mut component_cost = 0.0;
+            let mut component_sell_price = 0.0;
+            for ComponentAllocation {
+                stock_line,
+                number_of_packs,
+            } in &allocations
+            {
+                let row = &stock_line.stock_line_row;
+                let total_number_of_packs = row.total_number_of_packs - number_of_packs;
+                stock_line_repo.upsert_one(&StockLineRow {
+                    available_number_of_packs: row.available_number_of_packs - number_of_packs,
+                    total_number_of_packs,
+                    total_volume: row.volume_per_pack * total_number_of_packs,
+                    ..row.clone()
+                })?;
+
+                component_cost += row.cost_price_per_pack * number_of_packs;
+                component_sell_price += row.sell_price_per_pack * number_of_packs;
+                invoice_lines.push(stock_out_line(
+                    &invoice.id,
+                    &stock_line.item_row,
+                    row,
+                    *number_of_packs,
+                ));
+            }
+
+            // Kit can only be used until its first component expires
+            let expiry_date = allocations
+                .iter()
+                .filter_map(|allocation| allocation.stock_line.stock_line_row.expiry_date)
+                .min();
+            let kit_stock_line = StockLineRow {
+                id: uuid(),
+                item_link_id: kit_item.id.clone(),
+                store_id: ctx.store_id.clone(),
+                location_id: input.location_id.clone(),
+                batch: input.batch.clone(),
+                expiry_date,
+                pack_size: 1.0,
+                cost_price_per_pack: component_cost / input.number_of_kits,
+                sell_price_per_pack: component_sell_price / input.number_of_kits,
+                available_number_of_packs: input.number_of_kits,
+                total_number_of_packs: input.number_of_kits,
+                ..Default::default()
+            };
+            stock_line_repo.upsert_one(&kit_stock_line)?;
+            invoice_lines.push(stock_in_line(&invoice.id, &kit_item, &kit_stock_line));
+
+            InvoiceRowRepository::new(connection).upsert_one(&invoice)?;
+            let invoice_line_repo = InvoiceLineRowRepository::new(connection);
+            for line in invoice_lines {
+                invoice_line_repo.upsert_one(&line)?;
+            }
+
+            if kit_stock_line.location_id.is_some() {
+                LocationMovementRowRepository::new(connection)
+                    .upsert_one(&generate_location_movement(&ctx.store_id, &kit_stock_line))?;
+            }
+
+            let kit_assembly = KitAssemblyRow {
+                id: input.id.clone(),
+                store_id: ctx.store_id.clone(),
+                invoice_id: invoice.id.clone(),
+                kit_item_id: kit_item.id,
+                kit_stock_line_id: kit_stock_line.id.clone(),
+                number_of_kits: input.number_of_kits,
+                assembly_type: KitAssemblyType::Assembly,
+                user_id: Some(ctx.user_id.clone()),
+                created_datetime: Utc::now().naive_utc(),
+            };
+            KitAssemblyRowRepository::new(connection).upsert_one(&kit_assembly)?;
+
+            activity_log_entry(
+                ctx,
+                ActivityLogType::KitAssembled,
+                Some(kit_stock_line.id),
+                None,
+                Some(input.number_of_kits.to_string()),
+            )?;
+
+            Ok(kit_assembly)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(kit_assembly)
+}
+
+impl From<RepositoryError> for AssembleKitError {
+    fn from(error: RepositoryError) -> Self {
+        AssembleKitError::DatabaseError(error)
+    }
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &AssembleKit,
+) -> Result<(Vec<KitComponentRow>, Vec<StockLine>), AssembleKitError> {
+    use AssembleKitError as Error;
+
+    if KitAssemblyRowRepository::new(connection)
+        .find_one_by_id(&input.id)?
+        .is_some()
+    {
+        return Err(Error::KitAssemblyAlreadyExists);
+    }
+
+    if input.number_of_kits <= 0.0 {
+        return Err(Error::NumberOfKitsMustBePositive);
+    }
+    if input.number_of_kits.fract() != 0.0 {
+        return Err(Error::CannotHaveFractionalKits);
+    }
+
+    if ItemRowRepository::new(connection)
+        .find_active_by_id(&input.kit_item_id)?
+        .is_none()
+    {
+        return Err(Error::KitItemDoesNotExist);
+    }
+
+    let components =
+        KitComponentRowRepository::new(connection).find_many_by_kit_item_id(&input.kit_item_id)?;
+    if components.is_empty() {
+        return Err(Error::KitHasNoComponents);
+    }
+
+    if let Some(location_id) = &input.location_id {
+        if !check_location_exists(connection, store_id, location_id)? {
+            return Err(Error::LocationDoesNotExist);
+        }
+    }
+
+    let component_item_ids: Vec<String> = components
+        .iter()
+        .map(|component| component.component_item_id.clone())
+        .collect();
+    let mut filter = StockLineFilter::new()
+        .store_id(EqualFilter::equal_to(store_id.to_string()))
+        .item_id(EqualFilter::equal_any(component_item_ids));
+    if !input.component_stock_line_ids.is_empty() {
+        filter = filter.id(EqualFilter::equal_any(
+            input.component_stock_line_ids.clone(),
+        ));
+    }
+    let stock_lines =
+        StockLineRepository::new(connection).query_by_filter(filter, Some(store_id.to_string()))?;
+
+    // Selected lines that are missing were either not found in store or are another item
+    for stock_line_id in &input.component_stock_line_ids {
+        if stock_lines
+            .iter()
+            .any(|stock_line| &stock_line.stock_line_row.id == stock_line_id)
+        {
+            continue;
+        }
+        let exists = StockLineRowRepository::new(connection)
+            .find_one_by_id(stock_line_id)?
+            .is_some_and(|stock_line| stock_line.store_id == store_id);
+        return Err(match exists {
+            true => Error::StockLineIsNotAComponent(stock_line_id.clone()),
+            false => Error::StockLineDoesNotExist(stock_line_id.clone()),
+        });
+    }
+
+    Ok((components, stock_lines))
+}
+
+/// Issues component stock for `number_of_kits` first expiry first, stock on hold or expired by
+/// `today` isn't used
+pub(super) fn allocate_components(
+    components: &[KitComponentRow],
+    stock_lines: Vec<StockLine>,
+    number_of_kits: f64,
+    today: &NaiveDate,
+) -> Result<Vec<ComponentAllocation>, AssembleKitError> {
+    let mut stock_lines_by_item: HashMap<String, Vec<StockLine>> = HashMap::new();
+    for stock_line in stock_lines {
+        let row = &stock_line.stock_line_row;
+        let is_expired = row.expiry_date.is_some_and(|expiry| expiry <= *today);
+        if row.on_hold || is_expired || row.available_number_of_packs <= 0.0 {
+            continue;
+        }
+        stock_lines_by_item
+            .entry(stock_line.item_row.id.clone())
+            .or_default()
+            .push(stock_line);
+    }
+
+    let mut allocations = Vec::new();
+    for component in components {
+        let mut stock_lines = stock_lines_by_item
+            .remove(&component.component_item_id)
+            .unwrap_or_default();
+        stock_lines.sort_by_key(|stock_line| {
+            let expiry_date = stock_line.stock_line_row.expiry_date;
+            (expiry_date.is_none(), expiry_date)
+        });
+
+        let required = component.quantity * number_of_kits;
+        let available: f64 = stock_lines
+            .iter()
+            .map(|stock_line| stock_line.available_quantity())
+            .sum();
+        if available < required {
+            return Err(AssembleKitError::NotEnoughComponentStock {
+                item_id: component.component_item_id.clone(),
+                required,
+                available,
+            });
+        }
+
+        let mut remaining = required;
+        for stock_line in stock_lines {
+            if remaining <= 0.0 {
+                break;
+            }
+            let quantity = stock_line.available_quantity().min(remaining);
+            remaining -= quantity;
+            allocations.push(ComponentAllocation {
+                number_of_packs: quantity / stock_line.stock_line_row.pack_size,
+                stock_line,
+            });
+        }
+    }
+
+    Ok(allocations)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{ItemRow, KitComponentRow, StockLine, StockLineRow};
+
+    use super::{allocate_components, AssembleKitError};
+
+    fn stock_line(
+        id: &str,
+        item_id: &str,
+        pack_size: f64,
+        packs: f64,
+        expiry: Option<NaiveDate>,
+    ) -> StockLine {
+        StockLine {
+            stock_line_row: StockLineRow {
+                id: id.to_string(),
+                item_link_id: item_id.to_string(),
+                pack_size,
+                available_number_of_packs: packs,
+                total_number_of_packs: packs,
+                expiry_date: expiry,
+                ..Default::default()
+            },
+            item_row: ItemRow {
+                id: item_id.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn on_hold(mut stock_line: StockLine) -> StockLine {
+        stock_line.stock_line_row.on_hold = true;
+        stock_line
+    }
+
+    #[test]
+    fn test_allocate_components() {
+        let date = |month| NaiveDate::from_ymd_opt(2025, month, 1);
+        let today = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let components = vec![
+            KitComponentRow {
+                id: "a".to_string(),
+                kit_item_id: "kit".to_string(),
+                component_item_id: "item_a".to_string(),
+                quantity: 3.0,
+            },
+            KitComponentRow {
+                id: "b".to_string(),
+                kit_item_id: "kit".to_string(),
+                component_item_id: "item_b".to_string(),
+                quantity: 1.0,
+            },
+        ];
+        let stock_lines = vec![
+            stock_line("a_no_expiry", "item_a", 1.0, 100.0, None),
+            stock_line("a_later", "item_a", 2.0, 10.0, date(6)),
+            stock_line("a_first", "item_a", 1.0, 4.0, date(3)),
+            on_hold(stock_line("a_on_hold", "item_a", 1.0, 100.0, date(2))),
+            stock_line("a_expired", "item_a", 1.0, 100.0, Some(today)),
+            stock_line("b", "item_b", 1.0, 5.0, date(2)),
+        ];
+
+        // 12 of item a, first 4 then 4 packs of 2
+        let allocations =
+            allocate_components(&components, stock_lines.clone(), 4.0, &today).unwrap();
+        let summary: Vec<(&str, f64)> = allocations
+            .iter()
+            .map(|allocation| {
+                (
+                    allocation.stock_line.stock_line_row.id.as_str(),
+                    allocation.number_of_packs,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("a_first", 4.0), ("a_later", 4.0), ("b", 4.0)]
+        );
+
+        assert_eq!(
+            allocate_components(&components, stock_lines, 6.0, &today),
+            Err(AssembleKitError::NotEnoughComponentStock {
+                item_id: "item_b".to_string(),
+                required: 6.0,
+                available: 5.0,
+            })
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use repository::{
+    ItemRowRepository, KitComponentRow, KitComponentRowRepository, RepositoryError,
+    StorageConnection,
+};
+
+use crate::{service_provider::ServiceContext, sync::CentralServerConfig};
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertKitComponentError {
+    /// Bills of materials are maintained on the central server and synced to remote sites
+    NotCentralServer,
+    KitItemDoesNotExist,
+    ComponentItemDoesNotExist,
+    KitCannotBeItsOwnComponent,
+    ComponentAlreadyInKit,
+    QuantityMustBePositive,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteKitComponentError {
+    NotCentralServer,
+    KitComponentDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct UpsertKitComponent {
+    pub id: String,
+    pub kit_item_id: String,
+    pub component_item_id: String,
+    /// Units of the component in one unit of the kit
+    pub quantity: f64,
+}
+
+pub fn upsert_kit_component(
+    ctx: &ServiceContext,
+    input: UpsertKitComponent,
+) -> Result<KitComponentRow, UpsertKitComponentError> {
+    let row = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+
+            let UpsertKitComponent {
+                id,
+                kit_item_id,
+                component_item_id,
+                quantity,
+            } = input;
+            let repo = KitComponentRowRepository::new(connection);
+            repo.upsert_one(&KitComponentRow {
+                id: id.clone(),
+                kit_item_id,
+                component_item_id,
+                quantity,
+            })?;
+
+            repo.find_one_by_id(&id)?
+                .ok_or(UpsertKitComponentError::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(row)
+}
+
+pub fn delete_kit_component(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<String, DeleteKitComponentError> {
+    if !CentralServerConfig::is_central_server() {
+        return Err(DeleteKitComponentError::NotCentralServer);
+    }
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = KitComponentRowRepository::new(connection);
+            if repo.find_one_by_id(id)?.is_none() {
+                return Err(DeleteKitComponentError::KitComponentDoesNotExist);
+            }
+            repo.delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+impl From<RepositoryError> for UpsertKitComponentError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertKitComponentError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteKitComponentError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteKitComponentError::DatabaseError(error)
+    }
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &UpsertKitComponent,
+) -> Result<(), UpsertKitComponentError> {
+    use UpsertKitComponentError as Error;
+
+    if !CentralServerConfig::is_central_server() {
+        return Err(Error::NotCentralServer);
+    }
+
+    if input.quantity <= 0.0 {
+        return Err(Error::QuantityMustBePositive);
+    }
+
+    if input.kit_item_id == input.component_item_id {
+        return Err(Error::KitCannotBeItsOwnComponent);
+    }
+
+    let item_repo = ItemRowRepository::new(connection);
+    if item_repo.find_active_by_id(&input.kit_item_id)?.is_none() {
+        return Err(Error::KitItemDoesNotExist);
+    }
+    if item_repo
+        .find_active_by_id(&input.component_item_id)?
+        .is_none()
+    {
+        return Err(Error::ComponentItemDoesNotExist);
+    }
+
+    let already_in_kit = KitComponentRowRepository::new(connection)
+        .find_many_by_kit_item_id(&input.kit_item_id)?
+        .into_iter()
+        .any(|component| {
+            component.component_item_id == input.component_item_id && component.id != input.id
+        });
+    if already_in_kit {
+        return Err(Error::ComponentAlreadyInKit);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use repository::{
+    ActivityLogType, EqualFilter, InvoiceLineFilter, InvoiceLineRepository,
+    InvoiceLineRowRepository, InvoiceLineType, InvoiceRowRepository, ItemRow, ItemRowRepository,
+    KitAssemblyRow, KitAssemblyRowRepository, KitAssemblyType, KitComponentRow,
+    KitComponentRowRepository, LocationMovementRowRepository, RepositoryError, StockLine,
+    StockLineRow, StockLineRowRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+use crate::{
+    activity_log::activity_log_entry,
+    check_location_exists,
+    common::{check_stock_line_exists, CommonStockLineError},
+    repack::generate::{generate_location_movement, generate_repack_invoice},
+    service_provider::ServiceContext,
+};
+
+use super::{stock_in_line, stock_out_line};
+
+#[derive(Default, Clone, Debug)]
+pub struct DisassembleKit {
+    pub id: String,
+    pub kit_stock_line_id: String,
+    /// Units of the kit item to break down
+    pub number_of_kits: f64,
+    /// Location of the component stock, defaults to the kit's location
+    pub location_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DisassembleKitError {
+    KitAssemblyAlreadyExists,
+    StockLineDoesNotExist,
+    NotThisStoreStockLine,
+    StockLineIsNotAKit,
+    NumberOfKitsMustBePositive,
+    CannotHaveFractionalKits,
+    NotEnoughKitStock { available: f64 },
+    LocationDoesNotExist,
+    ComponentItemDoesNotExist(String),
+    DatabaseError(RepositoryError),
+}
+
+/// Component stock that goes back on the shelf for each kit taken apart
+#[derive(Debug, Clone, PartialEq)]
+struct KitComponentStock {
+    item: ItemRow,
+    units_per_kit: f64,
+    batch: Option<String>,
+    expiry_date: Option<NaiveDate>,
+    pack_size: f64,
+    cost_price_per_pack: f64,
+    sell_price_per_pack: f64,
+    item_variant_id: Option<String>,
+}
+
+pub fn disassemble_kit(
+    ctx: &ServiceContext,
+    input: DisassembleKit,
+) -> Result<KitAssemblyRow, DisassembleKitError> {
+    let kit_assembly = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (kit_stock_line, components) = validate(connection, &ctx.store_id, &input)?;
+            let kit_row = &kit_stock_line.stock_line_row;
+            let kit_item_id = kit_stock_line.item_row.id.clone();
+            let component_stock = kit_component_stock(connection, kit_row, &components)?;
+            let invoice = generate_repack_invoice(ctx)?;
+
+            let stock_line_repo = StockLineRowRepository::new(connection);
+            let number_of_packs = input.number_of_kits / kit_row.pack_size;
+            let total_number_of_packs = kit_row.total_number_of_packs - number_of_packs;
+            stock_line_repo.upsert_one(&StockLineRow {
+                available_number_of_packs: kit_row.available_number_of_packs - number_of_packs,
+                total_number_of_packs,
+                total_volume: kit_row.volume_per_pack * total_number_of_packs,
+                ..kit_row.clone()
+            })?;
+            let mut invoice_lines = vec![stock_out_line(
+                &invoice.id,
+                &kit_stock_line.item_row,
+                kit_row,
+                number_of_packs,
+            )];
+
+            let location_id = input
+                .location_id
+                .clone()
+                .or_else(|| kit_row.location_id.clone());
+            for component in &component_stock {
+                let number_of_packs =
+                    component.units_per_kit * input.number_of_kits / component.pack_size;
+                let component_stock_line = StockLineRow {
+                    id: uuid(),
+                    item_link_id: component.item.id.clone(),
+                    store_id: ctx.store_id.clone(),
+                    location_id: location_id.clone(),
+                    batch: component.batch.clone(),
+                    expiry_date: component.expiry_date,
+                    pack_size: component.pack_size,
+                    cost_price_per_pack: component.cost_price_per_pack,
+                    sell_price_per_pack: component.sell_price_per_pack,
+                    available_number_of_packs: number_of_packs,
+                    total_number_of_packs: number_of_packs,
+                    item_variant_id: component.item_variant_id.clone(),
+                    ..Default::default()
+                };
+                stock_line_repo.upsert_one(&component_stock_line)?;
+
+                if component_stock_line.location_id.is_some() {
+                    LocationMovementRowRepository::new(connection).upsert_one(
+                        &generate_location_movement(&ctx.store_id, &component_stock_line),
+                    )?;
+                }
+                invoice_lines.push(stock_in_line(
+                    &invoice.id,
+                    &component.item,
+                    &component_stock_line,
+                ));
+            }
+
+            InvoiceRowRepository::new(connection).upsert_one(&invoice)?;
+            let invoice_line_repo = InvoiceLineRowRepository::new(connection);
+            for line in invoice_lines {
+                invoice_line_repo.upsert_one(&line)?;
+            }
+
+            let kit_assembly = KitAssemblyRow {
+                id: input.id.clone(),
+                store_id: ctx.store_id.clone(),
+                invoice_id: invoice.id.clone(),
+                kit_item_id,
+                kit_stock_line_id: kit_row.id.clone(),
+                number_of_kits: input.number_of_kits,
+                assembly_type: KitAssemblyType::Disassembly,
+                user_id: Some(ctx.user_id.clone()),
+                created_datetime: Utc::now().naive_utc(),
+            };
+            KitAssemblyRowRepository::new(connection).upsert_one(&kit_assembly)?;
+
+            activity_log_entry(
+                ctx,
+                ActivityLogType::KitDisassembled,
+                Some(kit_row.id.clone()),
+                None,
+                Some(input.number_of_kits.to_string()),
+            )?;
+
+            Ok(kit_assembly)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(kit_assembly)
+}
+
+impl From<RepositoryError> for DisassembleKitError {
+    fn from(error: RepositoryError) -> Self {
+        DisassembleKitError::DatabaseError(error)
+    }
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &DisassembleKit,
+) -> Result<(StockLine, Vec<KitComponentRow>), DisassembleKitError> {
+    use DisassembleKitError as Error;
+
+    if KitAssemblyRowRepository::new(connection)
+        .find_one_by_id(&input.id)?
+        .is_some()
+    {
+        return Err(Error::KitAssemblyAlreadyExists);
+    }
+
+    let kit_stock_line = check_stock_line_exists(connection, store_id, &input.kit_stock_line_id)
+        .map_err(|error| match error {
+            CommonStockLineError::DatabaseError(RepositoryError::NotFound) => {
+                Error::StockLineDoesNotExist
+            }
+            CommonStockLineError::StockLineDoesNotBelongToStore => Error::NotThisStoreStockLine,
+            CommonStockLineError::DatabaseError(error) => Error::DatabaseError(error),
+        })?;
+
+    let components = KitComponentRowRepository::new(connection)
+        .find_many_by_kit_item_id(&kit_stock_line.item_row.id)?;
+    if components.is_empty() {
+        return Err(Error::StockLineIsNotAKit);
+    }
+
+    if input.number_of_kits <= 0.0 {
+        return Err(Error::NumberOfKitsMustBePositive);
+    }
+    if input.number_of_kits.fract() != 0.0 {
+        return Err(Error::CannotHaveFractionalKits);
+    }
+
+    let available = kit_stock_line.available_quantity();
+    if available < input.number_of_kits {
+        return Err(Error::NotEnoughKitStock { available });
+    }
+
+    if let Some(location_id) = &input.location_id {
+        if !check_location_exists(connection, store_id, location_id)? {
+            return Err(Error::LocationDoesNotExist);
+        }
+    }
+
+    Ok((kit_stock_line, components))
+}
+
+/// Component stock issued when the kit stock line was assembled, with its batch, expiry and cost.
+/// Kits that weren't made by kit assembly (e.g. received from a supplier) are broken down into
+/// their bill of materials with the kit's batch and expiry, which is no later than the components'
+fn kit_component_stock(
+    connection: &StorageConnection,
+    kit_row: &StockLineRow,
+    components: &[KitComponentRow],
+) -> Result<Vec<KitComponentStock>, DisassembleKitError> {
+    let assembly = KitAssemblyRowRepository::new(connection)
+        .find_many_by_kit_stock_line_id(&kit_row.id)?
+        .into_iter()
+        .find(|assembly| assembly.assembly_type == KitAssemblyType::Assembly);
+
+    if let Some(assembly) = assembly {
+        let lines = InvoiceLineRepository::new(connection).query_by_filter(
+            InvoiceLineFilter::new()
+                .invoice_id(EqualFilter::equal_to(assembly.invoice_id.clone()))
+                .r#type(InvoiceLineType::StockOut.equal_to()),
+        )?;
+        return Ok(lines
+            .into_iter()
+            .filter(|line| line.invoice_line_row.number_of_packs > 0.0)
+            .map(|line| {
+                let row = line.invoice_line_row;
+                KitComponentStock {
+                    item: line.item_row,
+                    units_per_kit: row.number_of_packs * row.pack_size / assembly.number_of_kits,
+                    batch: row.batch,
+                    expiry_date: row.expiry_date,
+                    pack_size: row.pack_size,
+                    cost_price_per_pack: row.cost_price_per_pack,
+                    sell_price_per_pack: row.sell_price_per_pack,
+                    item_variant_id: row.item_variant_id,
+                }
+            })
+            .collect());
+    }
+
+    let component_item_ids = components
+        .iter()
+        .map(|component| component.component_item_id.clone())
+        .collect();
+    let mut items: HashMap<String, ItemRow> = ItemRowRepository::new(connection)
+        .find_many_by_id(&component_item_ids)?
+        .into_iter()
+        .map(|item| (item.id.clone(), item))
+        .collect();
+
+    components
+        .iter()
+        .map(|component| {
+            let item = items.remove(&component.component_item_id).ok_or_else(|| {
+                DisassembleKitError::ComponentItemDoesNotExist(component.component_item_id.clone())
+            })?;
+            Ok(KitComponentStock {
+                item,
+                units_per_kit: component.quantity,
+                batch: kit_row.batch.clone(),
+                expiry_date: kit_row.expiry_date,
+                pack_size: 1.0,
+                cost_price_per_pack: 0.0,
+                sell_price_per_pack: 0.0,
+                item_variant_id: None,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,114 @@
+mod assemble;
+mod component;
+mod disassemble;
+#[cfg(test)]
+mod test;
+
+use crate::service_provider::ServiceContext;
+pub use assemble::{assemble_kit, AssembleKit, AssembleKitError};
+pub use component::{
+    delete_kit_component, upsert_kit_component, DeleteKitComponentError, UpsertKitComponent,
+    UpsertKitComponentError,
+};
+pub use disassemble::{disassemble_kit, DisassembleKit, DisassembleKitError};
+use repository::{
+    InvoiceLineRow, InvoiceLineType, ItemRow, KitAssemblyRow, KitAssemblyRowRepository,
+    KitComponentRow, KitComponentRowRepository, RepositoryError, StockLineRow,
+};
+use util::uuid::uuid;
+
+pub trait KitServiceTrait: Send + Sync {
+    /// Bill of materials of a kit item
+    fn get_kit_components(
+        &self,
+        ctx: &ServiceContext,
+        kit_item_id: &str,
+    ) -> Result<Vec<KitComponentRow>, RepositoryError> {
+        KitComponentRowRepository::new(&ctx.connection).find_many_by_kit_item_id(kit_item_id)
+    }
+
+    fn upsert_kit_component(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertKitComponent,
+    ) -> Result<KitComponentRow, UpsertKitComponentError> {
+        upsert_kit_component(ctx, input)
+    }
+
+    fn delete_kit_component(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, DeleteKitComponentError> {
+        delete_kit_component(ctx, id)
+    }
+
+    fn get_kit_assemblies(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Vec<KitAssemblyRow>, RepositoryError> {
+        KitAssemblyRowRepository::new(&ctx.connection).find_many_by_store_id(&ctx.store_id)
+    }
+
+    fn assemble_kit(
+        &self,
+        ctx: &ServiceContext,
+        input: AssembleKit,
+    ) -> Result<KitAssemblyRow, AssembleKitError> {
+        assemble_kit(ctx, input)
+    }
+
+    fn disassemble_kit(
+        &self,
+        ctx: &ServiceContext,
+        input: DisassembleKit,
+    ) -> Result<KitAssemblyRow, DisassembleKitError> {
+        disassemble_kit(ctx, input)
+    }
+}
+
+pub struct KitService;
+impl KitServiceTrait for KitService {}
+
+/// Repack invoice line issuing `number_of_packs` of the stock line
+fn stock_out_line(
+    invoice_id: &str,
+    item: &ItemRow,
+    stock_line: &StockLineRow,
+    number_of_packs: f64,
+) -> InvoiceLineRow {
+    let total = stock_line.cost_price_per_pack * number_of_packs;
+    InvoiceLineRow {
+        id: uuid(),
+        invoice_id: invoice_id.to_string(),
+        item_link_id: item.id.clone(),
+        item_name: item.name.clone(),
+        item_code: item.code.clone(),
+        stock_line_id: Some(stock_line.id.clone()),
+        location_id: stock_line.location_id.clone(),
+        batch: stock_line.batch.clone(),
+        expiry_date: stock_line.expiry_date,
+        pack_size: stock_line.pack_size,
+        r#type: InvoiceLineType::StockOut,
+        number_of_packs,
+        cost_price_per_pack: stock_line.cost_price_per_pack,
+        sell_price_per_pack: stock_line.sell_price_per_pack,
+        total_before_tax: total,
+        total_after_tax: total,
+        volume_per_pack: stock_line.volume_per_pack,
+        ..Default::default()
+    }
+}
+
+/// Repack invoice line receiving a newly created stock line
+fn stock_in_line(invoice_id: &str, item: &ItemRow, stock_line: &StockLineRow) -> InvoiceLineRow {
+    InvoiceLineRow {
+        r#type: InvoiceLineType::StockIn,
+        ..stock_out_line(
+            invoice_id,
+            item,
+            stock_line,
+            stock_line.total_number_of_packs,
+        )
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::NaiveDate;
+use repository::{
+    mock::{mock_store_a, mock_user_account_a, MockData, MockDataInserts},
+    test_db::setup_all_with_data,
+    EqualFilter, InvoiceType, ItemLedgerFilter, ItemLedgerRepository, ItemRow, ItemType,
+    KitAssemblyType, KitComponentRow, KitComponentRowRepository, Pagination, StockLineRow,
+    StockLineRowRepository,
+};
+
+use crate::service_provider::ServiceProvider;
+
+use super::{AssembleKit, AssembleKitError, DisassembleKit, DisassembleKitError};
+
+fn item(id: &str) -> ItemRow {
+    ItemRow {
+        id: id.to_string(),
+        name: id.to_string(),
+        code: id.to_string(),
+        r#type: ItemType::Stock,
+        is_active: true,
+        ..Default::default()
+    }
+}
+
+fn stock_line(id: &str, item_id: &str, packs: f64, cost: f64, expiry: NaiveDate) -> StockLineRow {
+    StockLineRow {
+        id: id.to_string(),
+        item_link_id: item_id.to_string(),
+        store_id: mock_store_a().id,
+        pack_size: 1.0,
+        available_number_of_packs: packs,
+        total_number_of_packs: packs,
+        cost_price_per_pack: cost,
+        expiry_date: Some(expiry),
+        ..Default::default()
+    }
+}
+
+#[actix_rt::test]
+async fn test_assemble_and_disassemble_kit() {
+    let date = |month| NaiveDate::from_ymd_opt(2030, month, 1).unwrap();
+    let (_, connection, connection_manager, _) = setup_all_with_data(
+        "test_assemble_and_disassemble_kit",
+        MockDataInserts::all(),
+        MockData {
+            items: vec![item("kit"), item("bandage"), item("gloves")],
+            stock_lines: vec![
+                stock_line("bandage_line", "bandage", 10.0, 2.0, date(6)),
+                stock_line("gloves_line", "gloves", 10.0, 1.0, date(3)),
+                stock_line(
+                    "expired_gloves_line",
+                    "gloves",
+                    10.0,
+                    1.0,
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                ),
+            ],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // 2 bandages and a pair of gloves per kit
+    let component_repo = KitComponentRowRepository::new(&connection);
+    for (id, component_item_id, quantity) in [
+        ("kit_bandage", "bandage", 2.0),
+        ("kit_gloves", "gloves", 1.0),
+    ] {
+        component_repo
+            .upsert_one(&KitComponentRow {
+                id: id.to_string(),
+                kit_item_id: "kit".to_string(),
+                component_item_id: component_item_id.to_string(),
+                quantity,
+            })
+            .unwrap();
+    }
+
+    let service_provider = ServiceProvider::new(connection_manager);
+    let context = service_provider
+        .context(mock_store_a().id, mock_user_account_a().id)
+        .unwrap();
+    let service = service_provider.kit_service;
+
+    assert_eq!(
+        service.assemble_kit(
+            &context,
+            AssembleKit {
+                id: "assembly".to_string(),
+                kit_item_id: "bandage".to_string(),
+                number_of_kits: 1.0,
+                ..Default::default()
+            }
+        ),
+        Err(AssembleKitError::KitHasNoComponents)
+    );
+    assert_eq!(
+        service.assemble_kit(
+            &context,
+            AssembleKit {
+                id: "assembly".to_string(),
+                kit_item_id: "kit".to_string(),
+                number_of_kits: 6.0,
+                ..Default::default()
+            }
+        ),
+        Err(AssembleKitError::NotEnoughComponentStock {
+            item_id: "bandage".to_string(),
+            required: 12.0,
+            available: 10.0,
+        })
+    );
+
+    let assembly = service
+        .assemble_kit(
+            &context,
+            AssembleKit {
+                id: "assembly".to_string(),
+                kit_item_id: "kit".to_string(),
+                number_of_kits: 4.0,
+                batch: Some("KIT1".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(assembly.assembly_type, KitAssemblyType::Assembly);
+
+    let stock_line_repo = StockLineRowRepository::new(&connection);
+    let bandage_line = stock_line_repo
+        .find_one_by_id("bandage_line")
+        .unwrap()
+        .unwrap();
+    assert_eq!(bandage_line.available_number_of_packs, 2.0);
+    assert_eq!(bandage_line.total_number_of_packs, 2.0);
+    // Expired stock is left alone
+    let expired_gloves_line = stock_line_repo
+        .find_one_by_id("expired_gloves_line")
+        .unwrap()
+        .unwrap();
+    assert_eq!(expired_gloves_line.available_number_of_packs, 10.0);
+
+    // Expires with the gloves, costs 2 * 2.0 + 1.0
+    let kit_line = stock_line_repo
+        .find_one_by_id(&assembly.kit_stock_line_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(kit_line.item_link_id, "kit");
+    assert_eq!(kit_line.available_number_of_packs, 4.0);
+    assert_eq!(kit_line.expiry_date, Some(date(3)));
+    assert_eq!(kit_line.cost_price_per_pack, 5.0);
+    assert_eq!(kit_line.batch, Some("KIT1".to_string()));
+
+    let ledger = |item_id: &str| {
+        ItemLedgerRepository::new(&connection)
+            .query(
+                Pagination::all(),
+                Some(
+                    ItemLedgerFilter::new()
+                        .item_id(EqualFilter::equal_to(item_id.to_string()))
+                        .store_id(EqualFilter::equal_to(mock_store_a().id)),
+                ),
+            )
+            .unwrap()
+    };
+    let kit_ledger = ledger("kit");
+    assert_eq!(kit_ledger.len(), 1);
+    assert_eq!(kit_ledger[0].invoice_type, InvoiceType::Repack);
+    assert_eq!(kit_ledger[0].movement_in_units, 4.0);
+    assert_eq!(ledger("bandage")[0].movement_in_units, -8.0);
+
+    assert_eq!(
+        service.disassemble_kit(
+            &context,
+            DisassembleKit {
+                id: "disassembly".to_string(),
+                kit_stock_line_id: assembly.kit_stock_line_id.clone(),
+                number_of_kits: 5.0,
+                ..Default::default()
+            }
+        ),
+        Err(DisassembleKitError::NotEnoughKitStock { available: 4.0 })
+    );
+
+    let disassembly = service
+        .disassemble_kit(
+            &context,
+            DisassembleKit {
+                id: "disassembly".to_string(),
+                kit_stock_line_id: assembly.kit_stock_line_id.clone(),
+                number_of_kits: 1.0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(disassembly.assembly_type, KitAssemblyType::Disassembly);
+
+    let kit_line = stock_line_repo
+        .find_one_by_id(&assembly.kit_stock_line_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(kit_line.available_number_of_packs, 3.0);
+    assert_eq!(ledger("kit").len(), 2);
+
+    // Components come back with their own batch expiry and cost
+    let bandage_ledger = ledger("bandage");
+    let returned = bandage_ledger
+        .iter()
+        .find(|row| row.invoice_id == disassembly.invoice_id)
+        .unwrap();
+    assert_eq!(returned.movement_in_units, 2.0);
+    assert_eq!(returned.expiry_date, Some(date(6)));
+    assert_eq!(returned.cost_price_per_pack, 2.0);
+}
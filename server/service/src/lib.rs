@@ -21,6 +21,9 @@ use thiserror::Error;
 
 pub mod abbreviation;
 pub mod activity_log;
+pub mod activity_log_chain;
+pub mod api_idempotency;
+pub mod api_key;
 pub mod apis;
 pub mod app_data;
 pub mod boajs;
@@ -28,8 +31,10 @@ pub mod campaign;
 pub mod ledger_fix;
 
 pub mod asset;
+pub mod audit_log;
 pub mod auth;
 pub mod auth_data;
+pub mod backup;
 pub mod backend_plugin;
 pub mod barcode;
 pub mod catalogue;
@@ -41,11 +46,15 @@ pub mod contact_form;
 pub mod currency;
 pub mod cursor_controller;
 pub mod dashboard;
+pub mod demand_forecast;
 pub mod demographic;
 pub mod diagnosis;
+pub mod dispensing_safety;
 pub mod display_settings_service;
 pub mod document;
 pub mod email;
+pub mod epcis;
+pub mod expiry_risk;
 pub mod insurance;
 pub mod insurance_provider;
 pub mod invoice;
@@ -54,6 +63,7 @@ pub mod item;
 pub mod item_stats;
 pub mod item_warning_join;
 pub mod json_translate;
+pub mod kit;
 pub mod label_printer_settings_service;
 pub mod ledger;
 pub mod localisations;
@@ -64,6 +74,7 @@ pub mod login;
 pub mod master_list;
 pub mod name;
 pub mod name_property;
+pub mod network_stock;
 pub mod number;
 pub mod permission;
 pub mod plugin;
@@ -77,17 +88,22 @@ pub mod program;
 pub mod programs;
 pub mod purchase_order;
 pub mod purchase_order_line;
+pub mod rate_limit;
 pub mod reason_option;
+pub mod recall;
+pub mod refill;
 pub mod repack;
 pub mod report;
 pub mod requisition;
 pub mod requisition_line;
 pub mod rnr_form;
 pub mod sensor;
+pub mod serial_number;
 pub mod service_provider;
 pub mod settings;
 pub mod settings_service;
 pub mod shipping_method;
+pub mod site_health;
 pub mod standard_reports;
 pub mod static_files;
 pub mod stock_line;
@@ -106,6 +122,7 @@ pub mod vaccine_course;
 pub mod validate;
 pub mod vvm;
 pub mod warning;
+pub mod webhook;
 
 #[cfg(test)]
 mod login_mock_data;
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use repository::{
+    location::{LocationFilter, LocationRepository},
+    EqualFilter, Invoice, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineType,
+    InvoiceStatus, InvoiceType, LocationRow, RepositoryError, StockLineFilter, StockLineRepository,
+    StorageConnection,
+};
+
+use crate::{
+    invoice::{
+        query::get_invoice,
+        stock_effect::{stock_effects, StockEffect},
+    },
+    service_provider::ServiceContext,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum LocationCapacityError {
+    InvoiceDoesNotExist,
+    NotAnInboundShipment,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocationUtilisation {
+    pub location: LocationRow,
+    pub volume_used: f64,
+}
+
+impl LocationUtilisation {
+    /// Free volume, 0 if location capacity (volume) is not set
+    pub fn free_volume(&self) -> f64 {
+        (self.location.volume - self.volume_used).max(0.0)
+    }
+
+    /// Fraction of location capacity in use, None if location capacity is not set
+    pub fn utilisation(&self) -> Option<f64> {
+        (self.location.volume > 0.0).then(|| self.volume_used / self.location.volume)
+    }
+}
+
+/// Location that would be overfilled once stock of an inbound shipment is received
+#[derive(Debug, PartialEq)]
+pub struct LocationCapacityWarning {
+    pub utilisation: LocationUtilisation,
+    pub incoming_volume: f64,
+    pub invoice_line_ids: Vec<String>,
+}
+
+/// Volume used and capacity of every location in store
+pub fn get_location_utilisation(
+    ctx: &ServiceContext,
+) -> Result<Vec<LocationUtilisation>, RepositoryError> {
+    location_utilisation(&ctx.connection, &ctx.store_id)
+}
+
+pub(crate) fn location_utilisation(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<Vec<LocationUtilisation>, RepositoryError> {
+    let locations = LocationRepository::new(connection).query_by_filter(
+        LocationFilter::new().store_id(EqualFilter::equal_to(store_id.to_string())),
+    )?;
+
+    let stock_lines = StockLineRepository::new(connection).query_by_filter(
+        StockLineFilter::new()
+            .store_id(EqualFilter::equal_to(store_id.to_string()))
+            .has_packs_in_store(true),
+        Some(store_id.to_string()),
+    )?;
+
+    let mut volume_by_location: HashMap<String, f64> = HashMap::new();
+    for line in stock_lines {
+        if let Some(location_id) = line.stock_line_row.location_id {
+            *volume_by_location.entry(location_id).or_default() += line.stock_line_row.total_volume;
+        }
+    }
+
+    Ok(locations
+        .into_iter()
+        .map(|location| LocationUtilisation {
+            volume_used: volume_by_location
+                .get(&location.location_row.id)
+                .copied()
+                .unwrap_or_default(),
+            location: location.location_row,
+        })
+        .collect())
+}
+
+/// Locations of inbound shipment lines that don't have enough free volume for the stock
+/// being received. Stock that is already received is part of location volume used
+pub fn get_inbound_shipment_capacity_warnings(
+    ctx: &ServiceContext,
+    invoice_id: &str,
+) -> Result<Vec<LocationCapacityWarning>, LocationCapacityError> {
+    let invoice = get_inbound_shipment(ctx, invoice_id)?;
+
+    if stock_effects(
+        &InvoiceType::InboundShipment,
+        &invoice.invoice_row.status,
+        &InvoiceStatus::Received,
+    ) != StockEffect::CreateStock
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut incoming_by_location: HashMap<String, (f64, Vec<String>)> = HashMap::new();
+    for line in get_stock_in_lines(&ctx.connection, invoice_id)? {
+        let Some(location_id) = line.invoice_line_row.location_id.clone() else {
+            continue;
+        };
+        let (incoming_volume, line_ids) = incoming_by_location.entry(location_id).or_default();
+        *incoming_volume += line_volume(&line);
+        line_ids.push(line.invoice_line_row.id);
+    }
+
+    let warnings = location_utilisation(&ctx.connection, &ctx.store_id)?
+        .into_iter()
+        .filter_map(|utilisation| {
+            let (incoming_volume, invoice_line_ids) =
+                incoming_by_location.remove(&utilisation.location.id)?;
+
+            let is_overfilled = utilisation.location.volume > 0.0
+                && utilisation.volume_used + incoming_volume > utilisation.location.volume;
+
+            is_overfilled.then_some(LocationCapacityWarning {
+                utilisation,
+                incoming_volume,
+                invoice_line_ids,
+            })
+        })
+        .collect();
+
+    Ok(warnings)
+}
+
+pub(crate) fn get_inbound_shipment(
+    ctx: &ServiceContext,
+    invoice_id: &str,
+) -> Result<Invoice, LocationCapacityError> {
+    let invoice = get_invoice(ctx, Some(&ctx.store_id), invoice_id, None)?
+        .ok_or(LocationCapacityError::InvoiceDoesNotExist)?;
+
+    if invoice.invoice_row.r#type != InvoiceType::InboundShipment {
+        return Err(LocationCapacityError::NotAnInboundShipment);
+    }
+
+    Ok(invoice)
+}
+
+pub(crate) fn get_stock_in_lines(
+    connection: &StorageConnection,
+    invoice_id: &str,
+) -> Result<Vec<InvoiceLine>, RepositoryError> {
+    InvoiceLineRepository::new(connection).query_by_filter(
+        InvoiceLineFilter::new()
+            .invoice_id(EqualFilter::equal_to(invoice_id.to_string()))
+            .r#type(InvoiceLineType::StockIn.equal_to()),
+    )
+}
+
+pub(crate) fn line_volume(line: &InvoiceLine) -> f64 {
+    line.invoice_line_row.number_of_packs * line.invoice_line_row.volume_per_pack
+}
+
+impl From<RepositoryError> for LocationCapacityError {
+    fn from(error: RepositoryError) -> Self {
+        LocationCapacityError::DatabaseError(error)
+    }
+}
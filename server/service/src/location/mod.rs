@@ -1,6 +1,11 @@
 use self::{
+    capacity::{
+        get_inbound_shipment_capacity_warnings, get_location_utilisation, LocationCapacityError,
+        LocationCapacityWarning, LocationUtilisation,
+    },
     delete::{delete_location, DeleteLocation, DeleteLocationError},
     insert::{insert_location, InsertLocation, InsertLocationError},
+    putaway::{get_putaway_suggestions, PutawaySuggestion},
     query::{get_location, get_locations},
     update::{update_location, UpdateLocation, UpdateLocationError},
 };
@@ -8,10 +13,12 @@ use self::{
 use super::{ListError, ListResult};
 use crate::{service_provider::ServiceContext, SingleRecordError};
 use repository::location::{Location, LocationFilter, LocationSort};
-use repository::PaginationOption;
+use repository::{PaginationOption, RepositoryError};
 
+pub mod capacity;
 pub mod delete;
 pub mod insert;
+pub mod putaway;
 pub mod query;
 pub mod update;
 mod validate;
@@ -58,6 +65,29 @@ pub trait LocationServiceTrait: Sync + Send {
     ) -> Result<Location, UpdateLocationError> {
         update_location(ctx, input)
     }
+
+    fn get_location_utilisation(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<Vec<LocationUtilisation>, RepositoryError> {
+        get_location_utilisation(ctx)
+    }
+
+    fn get_inbound_shipment_capacity_warnings(
+        &self,
+        ctx: &ServiceContext,
+        invoice_id: &str,
+    ) -> Result<Vec<LocationCapacityWarning>, LocationCapacityError> {
+        get_inbound_shipment_capacity_warnings(ctx, invoice_id)
+    }
+
+    fn get_putaway_suggestions(
+        &self,
+        ctx: &ServiceContext,
+        invoice_id: &str,
+    ) -> Result<Vec<PutawaySuggestion>, LocationCapacityError> {
+        get_putaway_suggestions(ctx, invoice_id)
+    }
 }
 
 pub struct LocationService {}
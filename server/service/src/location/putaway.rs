@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use repository::{
+    item_variant::item_variant_row::ItemVariantRowRepository, LocationRow, LocationTypeRow,
+    LocationTypeRowRepository, RepositoryError, StorageConnection,
+};
+
+use crate::service_provider::ServiceContext;
+
+use super::capacity::{
+    get_inbound_shipment, get_stock_in_lines, line_volume, location_utilisation,
+    LocationCapacityError,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct PutawaySuggestion {
+    pub invoice_line_id: String,
+    /// From item variant, or item location restriction when line has no variant
+    pub required_location_type_id: Option<String>,
+    pub volume: f64,
+    /// None when no location of the required type has enough free volume
+    pub location: Option<LocationRow>,
+}
+
+/// Suggests a location for each inbound shipment line without a location. Candidates are
+/// locations that are not on hold, match the required location type (or a type with a temperature
+/// range inside the required range) and have enough free volume. The location with the least
+/// free volume that fits the line is picked, volume of lines already placed on the shipment and of
+/// earlier suggestions is taken into account
+pub fn get_putaway_suggestions(
+    ctx: &ServiceContext,
+    invoice_id: &str,
+) -> Result<Vec<PutawaySuggestion>, LocationCapacityError> {
+    let connection = &ctx.connection;
+    get_inbound_shipment(ctx, invoice_id)?;
+
+    let mut candidates: Vec<(LocationRow, f64)> = location_utilisation(connection, &ctx.store_id)?
+        .into_iter()
+        .filter(|utilisation| !utilisation.location.on_hold)
+        .map(|utilisation| {
+            let free_volume = utilisation.free_volume();
+            (utilisation.location, free_volume)
+        })
+        .collect();
+
+    let (placed_lines, lines): (Vec<_>, Vec<_>) = get_stock_in_lines(connection, invoice_id)?
+        .into_iter()
+        .partition(|line| line.invoice_line_row.location_id.is_some());
+
+    // Lines that already have a location will take up some of its free volume
+    for line in &placed_lines {
+        if let Some((_, free_volume)) = candidates
+            .iter_mut()
+            .find(|(location, _)| line.invoice_line_row.location_id.as_ref() == Some(&location.id))
+        {
+            *free_volume = (*free_volume - line_volume(line)).max(0.0);
+        }
+    }
+
+    let mut location_types = LocationTypes::new(connection);
+    let mut suggestions = Vec::new();
+
+    for line in lines {
+        let required_location_type_id = match &line.invoice_line_row.item_variant_id {
+            Some(item_variant_id) => ItemVariantRowRepository::new(connection)
+                .find_one_by_id(item_variant_id)?
+                .and_then(|variant| variant.location_type_id),
+            None => None,
+        }
+        .or(line.item_row.restricted_location_type_id.clone());
+
+        let volume = line_volume(&line);
+
+        let mut best_fit: Option<usize> = None;
+        for (index, (location, free_volume)) in candidates.iter().enumerate() {
+            let fits = volume <= 0.0 || (location.volume > 0.0 && *free_volume >= volume);
+            if !fits {
+                continue;
+            }
+            if !location_types.is_compatible(
+                required_location_type_id.as_deref(),
+                location.location_type_id.as_deref(),
+            )? {
+                continue;
+            }
+            if best_fit.is_none_or(|best| *free_volume < candidates[best].1) {
+                best_fit = Some(index);
+            }
+        }
+
+        let location = best_fit.map(|index| {
+            let (location, free_volume) = &mut candidates[index];
+            *free_volume = (*free_volume - volume).max(0.0);
+            location.clone()
+        });
+
+        suggestions.push(PutawaySuggestion {
+            invoice_line_id: line.invoice_line_row.id,
+            required_location_type_id,
+            volume,
+            location,
+        });
+    }
+
+    Ok(suggestions)
+}
+
+struct LocationTypes<'a> {
+    connection: &'a StorageConnection,
+    cache: HashMap<String, Option<LocationTypeRow>>,
+}
+
+impl<'a> LocationTypes<'a> {
+    fn new(connection: &'a StorageConnection) -> Self {
+        LocationTypes {
+            connection,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, id: &str) -> Result<Option<LocationTypeRow>, RepositoryError> {
+        if let Some(location_type) = self.cache.get(id) {
+            return Ok(location_type.clone());
+        }
+        let location_type = LocationTypeRowRepository::new(self.connection).find_one_by_id(id)?;
+        self.cache.insert(id.to_string(), location_type.clone());
+        Ok(location_type)
+    }
+
+    /// Location type matches, or its temperature range is within the required range
+    fn is_compatible(
+        &mut self,
+        required_id: Option<&str>,
+        location_type_id: Option<&str>,
+    ) -> Result<bool, RepositoryError> {
+        let Some(required_id) = required_id else {
+            return Ok(true);
+        };
+        let Some(location_type_id) = location_type_id else {
+            return Ok(false);
+        };
+        if required_id == location_type_id {
+            return Ok(true);
+        }
+
+        let (Some(required), Some(location_type)) =
+            (self.get(required_id)?, self.get(location_type_id)?)
+        else {
+            return Ok(false);
+        };
+
+        Ok(location_type.min_temperature >= required.min_temperature
+            && location_type.max_temperature <= required.max_temperature)
+    }
+}
@@ -0,0 +1,297 @@
+#[cfg(test)]
+mod capacity {
+    use repository::{
+        item_variant::item_variant_row::ItemVariantRow,
+        mock::{
+            mock_item_a, mock_item_b, mock_location_type_a, mock_location_type_b,
+            mock_name_store_b, mock_outbound_shipment_a, mock_store_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceRowRepository, InvoiceStatus,
+        InvoiceType, ItemRow, LocationRow, LocationTypeRow, StockLineRow,
+    };
+
+    use crate::{location::capacity::LocationCapacityError, service_provider::ServiceProvider};
+
+    fn location_type_narrow() -> LocationTypeRow {
+        LocationTypeRow {
+            id: "location_type_narrow".to_string(),
+            name: "narrow_cold_location_type".to_string(),
+            min_temperature: 2.0,
+            max_temperature: 3.0,
+        }
+    }
+
+    fn location(id: &str, location_type_id: &str, volume: f64) -> LocationRow {
+        LocationRow {
+            id: id.to_string(),
+            code: id.to_string(),
+            name: id.to_string(),
+            store_id: mock_store_a().id,
+            location_type_id: Some(location_type_id.to_string()),
+            volume,
+            ..Default::default()
+        }
+    }
+
+    fn location_small() -> LocationRow {
+        location("location_small", &mock_location_type_a().id, 100.0)
+    }
+
+    fn location_large() -> LocationRow {
+        location("location_large", &mock_location_type_a().id, 1000.0)
+    }
+
+    fn location_narrow() -> LocationRow {
+        location("location_narrow", &location_type_narrow().id, 150.0)
+    }
+
+    fn location_held() -> LocationRow {
+        LocationRow {
+            on_hold: true,
+            ..location("location_held", &mock_location_type_a().id, 10000.0)
+        }
+    }
+
+    fn stock_line_in_small_location() -> StockLineRow {
+        StockLineRow {
+            id: "stock_line_in_small_location".to_string(),
+            store_id: mock_store_a().id,
+            item_link_id: mock_item_a().id,
+            location_id: Some(location_small().id),
+            pack_size: 1.0,
+            available_number_of_packs: 6.0,
+            total_number_of_packs: 6.0,
+            volume_per_pack: 10.0,
+            total_volume: 60.0,
+            ..Default::default()
+        }
+    }
+
+    fn cold_item() -> ItemRow {
+        ItemRow {
+            restricted_location_type_id: Some(mock_location_type_a().id),
+            ..mock_item_b()
+        }
+    }
+
+    fn freezer_variant() -> ItemVariantRow {
+        ItemVariantRow {
+            id: "freezer_variant".to_string(),
+            name: "freezer_variant".to_string(),
+            item_link_id: mock_item_a().id,
+            location_type_id: Some(mock_location_type_b().id),
+            ..Default::default()
+        }
+    }
+
+    fn inbound_shipment() -> InvoiceRow {
+        InvoiceRow {
+            id: "capacity_inbound_shipment".to_string(),
+            name_id: mock_name_store_b().id,
+            store_id: mock_store_a().id,
+            invoice_number: 1001,
+            r#type: InvoiceType::InboundShipment,
+            status: InvoiceStatus::New,
+            ..Default::default()
+        }
+    }
+
+    fn line(id: &str, item: ItemRow, number_of_packs: f64) -> InvoiceLineRow {
+        InvoiceLineRow {
+            id: id.to_string(),
+            invoice_id: inbound_shipment().id,
+            item_link_id: item.id,
+            item_name: item.name,
+            item_code: item.code,
+            r#type: InvoiceLineType::StockIn,
+            pack_size: 1.0,
+            number_of_packs,
+            volume_per_pack: 10.0,
+            ..Default::default()
+        }
+    }
+
+    fn mock_data() -> MockData {
+        MockData {
+            location_types: vec![location_type_narrow()],
+            items: vec![cold_item()],
+            item_variants: vec![freezer_variant()],
+            locations: vec![
+                location_small(),
+                location_large(),
+                location_narrow(),
+                location_held(),
+            ],
+            stock_lines: vec![stock_line_in_small_location()],
+            invoices: vec![inbound_shipment()],
+            invoice_lines: vec![
+                // 50 into location with 40 free
+                InvoiceLineRow {
+                    location_id: Some(location_small().id),
+                    ..line("line_overfills_small", mock_item_a(), 5.0)
+                },
+                InvoiceLineRow {
+                    location_id: Some(location_large().id),
+                    ..line("line_fits_large", mock_item_a(), 1.0)
+                },
+                // 120, only fits large and narrow, narrow is the better fit
+                line("line_cold_120", cold_item(), 12.0),
+                // 40, small is full from line above and narrow has 30 left, so large
+                line("line_cold_40", cold_item(), 4.0),
+                // No freezer locations with volume
+                InvoiceLineRow {
+                    item_variant_id: Some(freezer_variant().id),
+                    ..line("line_freezer", mock_item_a(), 1.0)
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn location_utilisation() {
+        let (_, _, connection_manager, _) =
+            setup_all_with_data("location_utilisation", MockDataInserts::all(), mock_data()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.location_service;
+
+        let utilisation = service.get_location_utilisation(&context).unwrap();
+
+        let small = utilisation
+            .iter()
+            .find(|u| u.location.id == location_small().id)
+            .unwrap();
+        assert_eq!(small.volume_used, 60.0);
+        assert_eq!(small.free_volume(), 40.0);
+        assert_eq!(small.utilisation(), Some(0.6));
+
+        let large = utilisation
+            .iter()
+            .find(|u| u.location.id == location_large().id)
+            .unwrap();
+        assert_eq!(large.volume_used, 0.0);
+        assert_eq!(large.utilisation(), Some(0.0));
+
+        // Location without volume set
+        let no_volume = utilisation
+            .iter()
+            .find(|u| u.location.volume == 0.0)
+            .unwrap();
+        assert_eq!(no_volume.utilisation(), None);
+    }
+
+    #[actix_rt::test]
+    async fn inbound_shipment_capacity_warnings() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "inbound_shipment_capacity_warnings",
+            MockDataInserts::all(),
+            mock_data(),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.location_service;
+
+        // Errors
+        assert_eq!(
+            service.get_inbound_shipment_capacity_warnings(&context, "invalid"),
+            Err(LocationCapacityError::InvoiceDoesNotExist)
+        );
+        assert_eq!(
+            service
+                .get_inbound_shipment_capacity_warnings(&context, &mock_outbound_shipment_a().id),
+            Err(LocationCapacityError::NotAnInboundShipment)
+        );
+
+        // Success
+        let warnings = service
+            .get_inbound_shipment_capacity_warnings(&context, &inbound_shipment().id)
+            .unwrap();
+
+        assert_eq!(warnings.len(), 1);
+        let warning = &warnings[0];
+        assert_eq!(warning.utilisation.location.id, location_small().id);
+        assert_eq!(warning.utilisation.volume_used, 60.0);
+        assert_eq!(warning.incoming_volume, 50.0);
+        assert_eq!(
+            warning.invoice_line_ids,
+            vec!["line_overfills_small".to_string()]
+        );
+
+        // No warnings once stock is received (it's part of volume used)
+        InvoiceRowRepository::new(&connection)
+            .upsert_one(&InvoiceRow {
+                status: InvoiceStatus::Received,
+                ..inbound_shipment()
+            })
+            .unwrap();
+
+        let warnings = service
+            .get_inbound_shipment_capacity_warnings(&context, &inbound_shipment().id)
+            .unwrap();
+        assert_eq!(warnings, Vec::new());
+    }
+
+    #[actix_rt::test]
+    async fn putaway_suggestions() {
+        let (_, _, connection_manager, _) =
+            setup_all_with_data("putaway_suggestions", MockDataInserts::all(), mock_data()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.location_service;
+
+        assert_eq!(
+            service.get_putaway_suggestions(&context, "invalid"),
+            Err(LocationCapacityError::InvoiceDoesNotExist)
+        );
+
+        let suggestions = service
+            .get_putaway_suggestions(&context, &inbound_shipment().id)
+            .unwrap();
+
+        // Lines with a location don't get a suggestion
+        assert_eq!(suggestions.len(), 3);
+
+        let suggested_location = |line_id: &str| {
+            suggestions
+                .iter()
+                .find(|s| s.invoice_line_id == line_id)
+                .unwrap()
+                .location
+                .as_ref()
+                .map(|location| location.id.clone())
+        };
+
+        // Narrow cold range is within required cold range
+        assert_eq!(
+            suggested_location("line_cold_120"),
+            Some(location_narrow().id)
+        );
+        assert_eq!(
+            suggested_location("line_cold_40"),
+            Some(location_large().id)
+        );
+        assert_eq!(suggested_location("line_freezer"), None);
+
+        let freezer = suggestions
+            .iter()
+            .find(|s| s.invoice_line_id == "line_freezer")
+            .unwrap();
+        assert_eq!(
+            freezer.required_location_type_id,
+            Some(mock_location_type_b().id)
+        );
+        assert_eq!(freezer.volume, 10.0);
+    }
+}
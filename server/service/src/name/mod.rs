@@ -1,9 +1,9 @@
-use repository::{Name, NameFilter, NameSort, PaginationOption};
+use repository::{Keyset, Name, NameFilter, NameSort, PaginationOption};
 use update::{update_name_properties, UpdateNameProperties, UpdateNamePropertiesError};
 
 use crate::{service_provider::ServiceContext, ListError, ListResult};
 
-use self::query::get_names;
+use self::query::{get_names, get_names_after};
 
 mod query;
 pub mod update;
@@ -21,6 +21,17 @@ pub trait NameServiceTrait: Sync + Send {
         get_names(ctx, store_id, pagination, filter, sort)
     }
 
+    fn get_names_after(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        after: Option<Keyset<String>>,
+        limit: u32,
+        filter: Option<NameFilter>,
+    ) -> Result<ListResult<Name>, ListError> {
+        get_names_after(ctx, store_id, after, limit, filter)
+    }
+
     fn update_name_properties(
         &self,
         ctx: &ServiceContext,
@@ -1,6 +1,6 @@
 use repository::NameRepository;
 use repository::PaginationOption;
-use repository::{Name, NameFilter, NameSort};
+use repository::{Keyset, Name, NameFilter, NameSort};
 
 use crate::{
     get_pagination_or_default, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
@@ -21,3 +21,20 @@ pub fn get_names(
         count: i64_to_u32(repository.count(store_id, filter)?),
     })
 }
+
+/// Page of the names visible to the store after `after` by name, for keyset (cursor) paging.
+/// Reads `limit` rows
+pub fn get_names_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<String>>,
+    limit: u32,
+    filter: Option<NameFilter>,
+) -> Result<ListResult<Name>, ListError> {
+    let repository = NameRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query_after(store_id, filter.clone(), after, limit)?,
+        count: i64_to_u32(repository.count(store_id, filter)?),
+    })
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use repository::{Invoice, RepositoryError, StoreRowRepository};
+use util::uuid::uuid;
+
+use crate::{
+    invoice::outbound_shipment::insert::{
+        insert_outbound_shipment, InsertOutboundShipment, InsertOutboundShipmentError,
+    },
+    invoice_line::stock_out_line::{
+        insert_stock_out_line, InsertStockOutLine, InsertStockOutLineError, StockOutType,
+    },
+    service_provider::ServiceContext,
+    sync::{ActiveStoresOnSite, CentralServerConfig, GetActiveStoresOnSiteError},
+};
+
+use super::RedistributionSuggestion;
+
+#[derive(PartialEq, Debug)]
+pub enum CreateRedistributionShipmentsError {
+    NotCentralServer,
+    NothingToTransfer,
+    /// Shipments can only be created in the store the stock is sent from
+    NotFromThisStore,
+    /// Sending store is not active on this site, its stock is managed by another site
+    SendingStoreNotActiveOnSite,
+    ReceivingStoreDoesNotExist(String),
+    OutboundShipmentError {
+        to_store_id: String,
+        error: InsertOutboundShipmentError,
+    },
+    StockOutLineError {
+        stock_line_id: String,
+        error: InsertStockOutLineError,
+    },
+    DatabaseError(RepositoryError),
+}
+
+type OutError = CreateRedistributionShipmentsError;
+
+/// Creates a draft outbound shipment from the current store to each receiving store in the
+/// suggestions, with the suggested lines
+pub fn create_redistribution_shipments(
+    ctx: &ServiceContext,
+    suggestions: Vec<RedistributionSuggestion>,
+) -> Result<Vec<Invoice>, OutError> {
+    if !CentralServerConfig::is_central_server() {
+        return Err(OutError::NotCentralServer);
+    }
+    if suggestions
+        .iter()
+        .any(|suggestion| suggestion.from_store_id != ctx.store_id)
+    {
+        return Err(OutError::NotFromThisStore);
+    }
+
+    let mut suggestions_by_store: BTreeMap<String, Vec<RedistributionSuggestion>> = BTreeMap::new();
+    for suggestion in suggestions
+        .into_iter()
+        .filter(|suggestion| !suggestion.lines.is_empty())
+    {
+        suggestions_by_store
+            .entry(suggestion.to_store_id.clone())
+            .or_default()
+            .push(suggestion);
+    }
+    if suggestions_by_store.is_empty() {
+        return Err(OutError::NothingToTransfer);
+    }
+
+    let shipments = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let active_stores =
+                ActiveStoresOnSite::get(connection).map_err(|error| match error {
+                    GetActiveStoresOnSiteError::DatabaseError(error) => {
+                        OutError::DatabaseError(error)
+                    }
+                    GetActiveStoresOnSiteError::SiteIdNotSet => {
+                        OutError::SendingStoreNotActiveOnSite
+                    }
+                })?;
+            if !active_stores.store_ids().contains(&ctx.store_id) {
+                return Err(OutError::SendingStoreNotActiveOnSite);
+            }
+
+            let mut shipments = Vec::new();
+            for (to_store_id, suggestions) in suggestions_by_store {
+                let to_store = StoreRowRepository::new(connection)
+                    .find_one_by_id(&to_store_id)?
+                    .ok_or_else(|| OutError::ReceivingStoreDoesNotExist(to_store_id.clone()))?;
+
+                let shipment = insert_outbound_shipment(
+                    ctx,
+                    InsertOutboundShipment {
+                        id: uuid(),
+                        other_party_id: to_store.name_id,
+                        their_reference: Some("Redistribution".to_string()),
+                        ..Default::default()
+                    },
+                )
+                .map_err(|error| OutError::OutboundShipmentError {
+                    to_store_id: to_store_id.clone(),
+                    error,
+                })?;
+
+                for line in suggestions
+                    .into_iter()
+                    .flat_map(|suggestion| suggestion.lines)
+                {
+                    insert_stock_out_line(
+                        ctx,
+                        InsertStockOutLine {
+                            id: uuid(),
+                            r#type: StockOutType::OutboundShipment,
+                            invoice_id: shipment.invoice_row.id.clone(),
+                            stock_line_id: line.stock_line_id.clone(),
+                            number_of_packs: line.number_of_packs,
+                            ..Default::default()
+                        },
+                    )
+                    .map_err(|error| OutError::StockOutLineError {
+                        stock_line_id: line.stock_line_id,
+                        error,
+                    })?;
+                }
+
+                shipments.push(shipment);
+            }
+
+            Ok(shipments)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(shipments)
+}
+
+impl From<RepositoryError> for CreateRedistributionShipmentsError {
+    fn from(error: RepositoryError) -> Self {
+        CreateRedistributionShipmentsError::DatabaseError(error)
+    }
+}
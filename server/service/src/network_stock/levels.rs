@@ -0,0 +1,135 @@
+use repository::{EqualFilter, RepositoryError, StorageConnection, StoreFilter, StoreRepository};
+
+use crate::{
+    item_stats::get_item_stats,
+    preference::{
+        NumberOfMonthsThresholdToShowLowStockAlertsForProducts,
+        NumberOfMonthsThresholdToShowOverStockAlertsForProducts, Preference,
+    },
+    PluginOrRepositoryError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StockStatus {
+    Understocked,
+    Ok,
+    Overstocked,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoreItemStock {
+    pub store_id: String,
+    pub item_id: String,
+    pub available_stock_on_hand: f64,
+    pub average_monthly_consumption: f64,
+    /// None when the item has no consumption in the store
+    pub months_of_stock: Option<f64>,
+    /// Store's low and over stock alert thresholds in months, 0 when not configured
+    pub low_stock_threshold: f64,
+    pub over_stock_threshold: f64,
+    pub status: StockStatus,
+}
+
+impl StoreItemStock {
+    pub fn new(
+        store_id: String,
+        item_id: String,
+        available_stock_on_hand: f64,
+        average_monthly_consumption: f64,
+        low_stock_threshold: f64,
+        over_stock_threshold: f64,
+    ) -> Self {
+        let months_of_stock = if average_monthly_consumption > 0.0 {
+            Some(available_stock_on_hand / average_monthly_consumption)
+        } else {
+            None
+        };
+
+        let status = match months_of_stock {
+            Some(months) if months < low_stock_threshold => StockStatus::Understocked,
+            Some(months) if over_stock_threshold > 0.0 && months > over_stock_threshold => {
+                StockStatus::Overstocked
+            }
+            // Stock that isn't being used anywhere in the store is all excess
+            None if over_stock_threshold > 0.0 && available_stock_on_hand > 0.0 => {
+                StockStatus::Overstocked
+            }
+            _ => StockStatus::Ok,
+        };
+
+        StoreItemStock {
+            store_id,
+            item_id,
+            available_stock_on_hand,
+            average_monthly_consumption,
+            months_of_stock,
+            low_stock_threshold,
+            over_stock_threshold,
+            status,
+        }
+    }
+
+    /// Months of stock an understocked store is brought up to, between the two thresholds
+    pub fn target_months_of_stock(&self) -> f64 {
+        if self.over_stock_threshold > self.low_stock_threshold {
+            (self.low_stock_threshold + self.over_stock_threshold) / 2.0
+        } else {
+            self.low_stock_threshold
+        }
+    }
+
+    /// Stock above the over stock threshold
+    pub fn excess_stock(&self) -> f64 {
+        if self.status != StockStatus::Overstocked {
+            return 0.0;
+        }
+        (self.available_stock_on_hand
+            - self.over_stock_threshold * self.average_monthly_consumption)
+            .max(0.0)
+    }
+}
+
+/// Stock levels of the items in every active store, or only in `store_ids` when provided
+pub fn get_network_stock_levels(
+    connection: &StorageConnection,
+    item_ids: &[String],
+    store_ids: Option<Vec<String>>,
+) -> Result<Vec<StoreItemStock>, PluginOrRepositoryError> {
+    let mut filter = StoreFilter::new();
+    if let Some(store_ids) = store_ids {
+        filter = filter.id(EqualFilter::equal_any(store_ids));
+    }
+    let stores = StoreRepository::new(connection).query_by_filter(filter)?;
+
+    let mut stock_levels = Vec::new();
+    for store in stores
+        .into_iter()
+        .filter(|store| !store.store_row.is_disabled)
+    {
+        let store_id = store.store_row.id;
+        let low_stock_threshold = NumberOfMonthsThresholdToShowLowStockAlertsForProducts
+            .load(connection, Some(store_id.clone()))
+            .map_err(|e| {
+                RepositoryError::as_db_error("Could not load low stock threshold preference", e)
+            })?;
+        let over_stock_threshold = NumberOfMonthsThresholdToShowOverStockAlertsForProducts
+            .load(connection, Some(store_id.clone()))
+            .map_err(|e| {
+                RepositoryError::as_db_error("Could not load over stock threshold preference", e)
+            })?;
+
+        let item_stats = get_item_stats(connection, &store_id, None, item_ids.to_vec(), None)?;
+        stock_levels.extend(item_stats.into_iter().map(|stats| {
+            StoreItemStock::new(
+                store_id.clone(),
+                stats.item_id,
+                stats.available_stock_on_hand,
+                stats.average_monthly_consumption,
+                low_stock_threshold,
+                over_stock_threshold,
+            )
+        }));
+    }
+
+    Ok(stock_levels)
+}
@@ -0,0 +1,137 @@
+mod create;
+mod levels;
+mod suggest;
+
+use std::collections::{BTreeMap, HashMap};
+
+use repository::{
+    EqualFilter, Invoice, RepositoryError, StockLineFilter, StockLineRepository, StockLineRow,
+};
+use util::date_now;
+
+use crate::{
+    backend_plugin::plugin_provider::PluginError, common::days_in_a_month,
+    service_provider::ServiceContext, sync::CentralServerConfig, PluginOrRepositoryError,
+};
+pub use create::{create_redistribution_shipments, CreateRedistributionShipmentsError};
+pub use levels::{get_network_stock_levels, StockStatus, StoreItemStock};
+pub use suggest::{RedistributionSuggestion, SuggestedTransferLine};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkStockInput {
+    pub item_ids: Vec<String>,
+    /// Defaults to all active stores
+    pub store_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NetworkStockError {
+    NotCentralServer,
+    PluginError(PluginError),
+    DatabaseError(RepositoryError),
+}
+
+pub trait NetworkStockServiceTrait: Sync + Send {
+    /// Months of stock of the items in each store, flagged against the store's low and over stock
+    /// alert thresholds. Only available on the central server, which holds all stores' stock
+    fn get_network_stock_levels(
+        &self,
+        ctx: &ServiceContext,
+        input: NetworkStockInput,
+    ) -> Result<Vec<StoreItemStock>, NetworkStockError> {
+        if !CentralServerConfig::is_central_server() {
+            return Err(NetworkStockError::NotCentralServer);
+        }
+
+        Ok(get_network_stock_levels(
+            &ctx.connection,
+            &input.item_ids,
+            input.store_ids,
+        )?)
+    }
+
+    /// Transfers from overstocked to understocked stores, per item
+    fn get_redistribution_suggestions(
+        &self,
+        ctx: &ServiceContext,
+        input: NetworkStockInput,
+    ) -> Result<Vec<RedistributionSuggestion>, NetworkStockError> {
+        let stock_levels = self.get_network_stock_levels(ctx, input)?;
+
+        let mut levels_by_item: BTreeMap<&str, Vec<StoreItemStock>> = BTreeMap::new();
+        for stock in &stock_levels {
+            levels_by_item
+                .entry(stock.item_id.as_str())
+                .or_default()
+                .push(stock.clone());
+        }
+
+        let reference_date = date_now();
+        let days_in_month = days_in_a_month(&ctx.connection);
+        let mut suggestions = Vec::new();
+        for (item_id, item_levels) in levels_by_item {
+            let has_receivers = item_levels
+                .iter()
+                .any(|stock| stock.status == StockStatus::Understocked);
+            let donor_store_ids: Vec<String> = item_levels
+                .iter()
+                .filter(|stock| stock.status == StockStatus::Overstocked)
+                .map(|stock| stock.store_id.clone())
+                .collect();
+            if !has_receivers || donor_store_ids.is_empty() {
+                continue;
+            }
+
+            let stock_lines = StockLineRepository::new(&ctx.connection).query_by_filter(
+                StockLineFilter::new()
+                    .item_id(EqualFilter::equal_to(item_id.to_string()))
+                    .store_id(EqualFilter::equal_any(donor_store_ids))
+                    .is_available(true),
+                None,
+            )?;
+            let mut stock_lines_by_store: HashMap<String, Vec<StockLineRow>> = HashMap::new();
+            for stock_line in stock_lines.into_iter().map(|line| line.stock_line_row) {
+                stock_lines_by_store
+                    .entry(stock_line.store_id.clone())
+                    .or_default()
+                    .push(stock_line);
+            }
+
+            suggestions.extend(suggest::suggest_transfers(
+                &item_levels,
+                &stock_lines_by_store,
+                &reference_date,
+                days_in_month,
+            ));
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Creates draft outbound shipments for suggestions sent from the context store
+    fn create_redistribution_shipments(
+        &self,
+        ctx: &ServiceContext,
+        suggestions: Vec<RedistributionSuggestion>,
+    ) -> Result<Vec<Invoice>, CreateRedistributionShipmentsError> {
+        create_redistribution_shipments(ctx, suggestions)
+    }
+}
+
+pub struct NetworkStockService;
+impl NetworkStockServiceTrait for NetworkStockService {}
+
+impl From<RepositoryError> for NetworkStockError {
+    fn from(error: RepositoryError) -> Self {
+        NetworkStockError::DatabaseError(error)
+    }
+}
+
+impl From<PluginOrRepositoryError> for NetworkStockError {
+    fn from(error: PluginOrRepositoryError) -> Self {
+        match error {
+            PluginOrRepositoryError::RepositoryError(error) => error.into(),
+            PluginOrRepositoryError::PluginError(error) => NetworkStockError::PluginError(error),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use repository::StockLineRow;
+
+use super::{StockStatus, StoreItemStock};
+use crate::expiry_risk::{months_to_expiry, project_consumption};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SuggestedTransferLine {
+    pub stock_line_id: String,
+    pub batch: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub pack_size: f64,
+    pub number_of_packs: f64,
+    /// Units of this line that would expire in the sending store before being used there
+    pub quantity_at_risk: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedistributionSuggestion {
+    pub item_id: String,
+    pub from_store_id: String,
+    pub to_store_id: String,
+    pub lines: Vec<SuggestedTransferLine>,
+}
+
+impl RedistributionSuggestion {
+    pub fn quantity(&self) -> f64 {
+        self.lines
+            .iter()
+            .map(|line| line.number_of_packs * line.pack_size)
+            .sum()
+    }
+
+    pub fn quantity_at_risk(&self) -> f64 {
+        self.lines.iter().map(|line| line.quantity_at_risk).sum()
+    }
+}
+
+/// Quantity of a donor's batch that can be moved, split into the part that would expire at the
+/// donor and the part the donor would use in time
+struct Candidate<'a> {
+    stock_line: &'a StockLineRow,
+    months_to_expiry: Option<f64>,
+    at_risk: f64,
+    safe: f64,
+}
+
+struct Receiver<'a> {
+    stock: &'a StoreItemStock,
+    need: f64,
+    /// Units received so far, used in the receiver's consumption before later batches
+    received: f64,
+}
+
+/// Proposes transfers of one item from overstocked to understocked stores.
+///
+/// Donors give down to their over stock threshold, receivers are filled up to the middle of their
+/// low and over stock thresholds. Stock that would expire at the donor before it is used goes
+/// first, to the receivers that can use it before it expires. Any other excess is taken from the
+/// longest dated batches, leaving the donor with the stock it will use first.
+pub(super) fn suggest_transfers(
+    stock_levels: &[StoreItemStock],
+    stock_lines_by_store: &HashMap<String, Vec<StockLineRow>>,
+    reference_date: &NaiveDate,
+    days_in_month: f64,
+) -> Vec<RedistributionSuggestion> {
+    let mut receivers: Vec<Receiver> = stock_levels
+        .iter()
+        .filter(|stock| stock.status == StockStatus::Understocked)
+        .map(|stock| Receiver {
+            stock,
+            need: stock.target_months_of_stock() * stock.average_monthly_consumption
+                - stock.available_stock_on_hand,
+            received: 0.0,
+        })
+        .filter(|receiver| receiver.need > 0.0)
+        .collect();
+    // Fastest consuming receivers are the most likely to use short dated stock in time
+    receivers.sort_by(|a, b| {
+        b.stock
+            .average_monthly_consumption
+            .total_cmp(&a.stock.average_monthly_consumption)
+    });
+
+    let mut transfers: HashMap<(String, String), Vec<SuggestedTransferLine>> = HashMap::new();
+
+    for donor in stock_levels
+        .iter()
+        .filter(|stock| stock.status == StockStatus::Overstocked)
+    {
+        let mut excess = donor.excess_stock();
+        let stock_lines = stock_lines_by_store
+            .get(&donor.store_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        for candidate in candidates(donor, stock_lines, reference_date, days_in_month) {
+            let Candidate {
+                stock_line,
+                months_to_expiry,
+                mut at_risk,
+                mut safe,
+            } = candidate;
+
+            for receiver in receivers.iter_mut() {
+                if excess <= 0.0 || at_risk + safe <= 0.0 {
+                    break;
+                }
+                let pack_size = stock_line.pack_size;
+                // Receiver uses its own stock and earlier transfers before this batch
+                let usable_before_expiry = match months_to_expiry {
+                    Some(months) => {
+                        receiver.stock.average_monthly_consumption * months
+                            - receiver.stock.available_stock_on_hand
+                            - receiver.received
+                    }
+                    None => f64::MAX,
+                };
+                let quantity = (at_risk + safe)
+                    .min(excess)
+                    .min(receiver.need)
+                    .min(usable_before_expiry);
+                let number_of_packs = (quantity / pack_size).floor();
+                if number_of_packs <= 0.0 {
+                    continue;
+                }
+
+                let quantity = number_of_packs * pack_size;
+                let quantity_at_risk = quantity.min(at_risk);
+                at_risk -= quantity_at_risk;
+                safe -= quantity - quantity_at_risk;
+                excess -= quantity;
+                receiver.need -= quantity;
+                receiver.received += quantity;
+
+                transfers
+                    .entry((donor.store_id.clone(), receiver.stock.store_id.clone()))
+                    .or_default()
+                    .push(SuggestedTransferLine {
+                        stock_line_id: stock_line.id.clone(),
+                        batch: stock_line.batch.clone(),
+                        expiry_date: stock_line.expiry_date,
+                        pack_size,
+                        number_of_packs,
+                        quantity_at_risk,
+                    });
+            }
+        }
+    }
+
+    let item_id = stock_levels
+        .first()
+        .map(|stock| stock.item_id.clone())
+        .unwrap_or_default();
+    let mut suggestions: Vec<RedistributionSuggestion> = transfers
+        .into_iter()
+        .map(
+            |((from_store_id, to_store_id), lines)| RedistributionSuggestion {
+                item_id: item_id.clone(),
+                from_store_id,
+                to_store_id,
+                lines,
+            },
+        )
+        .collect();
+    suggestions.sort_by(|a, b| {
+        (&a.from_store_id, &a.to_store_id).cmp(&(&b.from_store_id, &b.to_store_id))
+    });
+    suggestions
+}
+
+/// Donor batches in the order they should leave the donor, at risk stock first
+fn candidates<'a>(
+    donor: &StoreItemStock,
+    stock_lines: &'a [StockLineRow],
+    reference_date: &NaiveDate,
+    days_in_month: f64,
+) -> Vec<Candidate<'a>> {
+    let stock_lines = stock_lines.iter().filter(|stock_line| {
+        !stock_line.on_hold
+            && stock_line.available_number_of_packs > 0.0
+            && months_to_expiry(stock_line, reference_date, days_in_month)
+                .is_none_or(|months| months > 0.0)
+    });
+
+    let mut candidates: Vec<Candidate> = project_consumption(
+        stock_lines,
+        donor.average_monthly_consumption,
+        reference_date,
+        days_in_month,
+    )
+    .into_iter()
+    .map(|projection| Candidate {
+        stock_line: projection.stock_line,
+        months_to_expiry: projection.months_to_expiry,
+        at_risk: projection.quantity_at_risk(),
+        safe: projection.projected_consumption,
+    })
+    .collect();
+
+    // At risk stock first expiry first, then the rest longest dated first
+    candidates.sort_by(|a, b| match (a.at_risk > 0.0, b.at_risk > 0.0) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        (true, true) => std::cmp::Ordering::Equal,
+        (false, false) => {
+            let a = a.months_to_expiry.unwrap_or(f64::MAX);
+            let b = b.months_to_expiry.unwrap_or(f64::MAX);
+            b.total_cmp(&a)
+        }
+    });
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stock(store_id: &str, soh: f64, amc: f64) -> StoreItemStock {
+        StoreItemStock::new(store_id.to_string(), "item".to_string(), soh, amc, 3.0, 6.0)
+    }
+
+    fn stock_line(id: &str, store_id: &str, packs: f64, expiry: Option<NaiveDate>) -> StockLineRow {
+        StockLineRow {
+            id: id.to_string(),
+            item_link_id: "item".to_string(),
+            store_id: store_id.to_string(),
+            pack_size: 1.0,
+            available_number_of_packs: packs,
+            total_number_of_packs: packs,
+            expiry_date: expiry,
+            ..Default::default()
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_stock_status() {
+        assert_eq!(stock("a", 20.0, 10.0).status, StockStatus::Understocked);
+        assert_eq!(stock("a", 40.0, 10.0).status, StockStatus::Ok);
+        assert_eq!(stock("a", 100.0, 10.0).status, StockStatus::Overstocked);
+        // Stock without demand is all excess
+        assert_eq!(stock("a", 5.0, 0.0).status, StockStatus::Overstocked);
+        assert_eq!(stock("a", 5.0, 0.0).excess_stock(), 5.0);
+        assert_eq!(stock("a", 0.0, 0.0).status, StockStatus::Ok);
+    }
+
+    #[test]
+    fn test_suggest_transfers() {
+        let reference_date = date(2024, 1, 1);
+        // Donor uses 10 a month and keeps 60
+        let donor = stock("donor", 160.0, 10.0);
+        // Needs 45 - 10 = 35, uses 10 a month
+        let fast_receiver = stock("fast", 10.0, 10.0);
+        // Needs 22.5 - 5 = 17.5, uses 5 a month
+        let slow_receiver = stock("slow", 5.0, 5.0);
+        let stock_levels = vec![donor, slow_receiver, fast_receiver];
+
+        let stock_lines_by_store = HashMap::from([(
+            "donor".to_string(),
+            vec![
+                // Donor only uses 20 of these in the two months before they expire
+                stock_line("short", "donor", 60.0, Some(date(2024, 3, 1))),
+                stock_line("long", "donor", 100.0, Some(date(2025, 1, 1))),
+                stock_line("expired", "donor", 50.0, Some(date(2023, 12, 1))),
+            ],
+        )]);
+
+        let suggestions =
+            suggest_transfers(&stock_levels, &stock_lines_by_store, &reference_date, 30.0);
+
+        assert_eq!(suggestions.len(), 2);
+        let fast = &suggestions[0];
+        assert_eq!(fast.to_store_id, "fast");
+        // Fast receiver can use 10 * 2 - 10 = 10 of the short dated stock before it expires,
+        // the rest of its need comes from the long dated batch
+        assert_eq!(
+            fast.lines
+                .iter()
+                .map(|line| (line.stock_line_id.as_str(), line.number_of_packs))
+                .collect::<Vec<_>>(),
+            vec![("short", 10.0), ("long", 25.0)]
+        );
+        assert_eq!(fast.quantity_at_risk(), 10.0);
+
+        // Slow receiver can use 5 * 2 - 5 = 5 of the short dated stock in time, whole packs only
+        let slow = &suggestions[1];
+        assert_eq!(slow.to_store_id, "slow");
+        assert_eq!(
+            slow.lines
+                .iter()
+                .map(|line| (line.stock_line_id.as_str(), line.number_of_packs))
+                .collect::<Vec<_>>(),
+            vec![("short", 5.0), ("long", 12.0)]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use base64::{prelude::BASE64_STANDARD, Engine};
 use log::info;
 use repository::{
     migrations::Version, BackendPluginRowRepository, FrontendPluginFile, FrontendPluginRow,
-    FrontendPluginRowRepository, PluginType, RepositoryError,
+    FrontendPluginRowRepository, PluginMutation, PluginType, RepositoryError,
 };
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
@@ -17,7 +17,7 @@ use thiserror::Error;
 use crate::{
     backend_plugin::{
         plugin_provider::{PluginBundle, PluginError, PluginInstance},
-        types::graphql_query,
+        types::{graphql_mutation, graphql_query},
     },
     processors::ProcessorType,
     service_provider::ServiceContext,
@@ -57,6 +57,20 @@ pub enum PluginGraphqlQueryError {
     NotFound,
 }
 
+#[derive(Error, Debug)]
+pub enum PluginGraphqlMutationError {
+    #[error(transparent)]
+    PluginError(#[from] PluginError),
+    #[error("Graphql mutation plugin with specified code not found")]
+    NotFound,
+    #[error("Mutation {0} is not declared in plugin manifest")]
+    MutationNotDeclared(String),
+    #[error("Invalid mutation input schema in plugin manifest: {0}")]
+    InvalidInputSchema(String),
+    #[error("Mutation input does not match input schema: {0}")]
+    InvalidInput(String),
+}
+
 #[derive(Clone, Debug)]
 pub struct FrontendPluginMetadata {
     pub code: String,
@@ -283,6 +297,48 @@ pub trait PluginServiceTrait: Sync + Send {
             graphql_query::Input { store_id, input },
         )?)
     }
+
+    /// (plugin code, mutation) of mutations declared by graphql_mutation plugins, these are
+    /// exposed as named graphql mutations
+    fn declared_plugin_graphql_mutations(&self) -> Vec<(String, PluginMutation)> {
+        PluginInstance::declared_mutations()
+    }
+
+    fn plugin_graphql_mutation(
+        &self,
+        store_id: String,
+        user_id: String,
+        plugin_code: &str,
+        name: &str,
+        input: serde_json::Value,
+    ) -> Result<serde_json::Value, PluginGraphqlMutationError> {
+        use PluginGraphqlMutationError as Error;
+        let plugin = PluginInstance::get_one_with_code(plugin_code, PluginType::GraphqlMutation)
+            .ok_or(Error::NotFound)?;
+
+        let mutation = plugin
+            .manifest
+            .mutations
+            .iter()
+            .find(|mutation| mutation.name == name)
+            .ok_or_else(|| Error::MutationNotDeclared(name.to_string()))?;
+
+        let validator = jsonschema::validator_for(&mutation.input_schema)
+            .map_err(|e| Error::InvalidInputSchema(e.to_string()))?;
+        validator
+            .validate(&input)
+            .map_err(|e| Error::InvalidInput(e.to_string()))?;
+
+        Ok(graphql_mutation::Trait::call(
+            &(*plugin),
+            graphql_mutation::Input {
+                store_id,
+                user_id,
+                name: name.to_string(),
+                input,
+            },
+        )?)
+    }
 }
 
 pub struct PluginService;
@@ -377,4 +433,75 @@ mod test {
             ]
         )
     }
+
+    #[actix_rt::test]
+    async fn plugin_graphql_mutation() {
+        use super::PluginGraphqlMutationError;
+        use crate::{
+            backend_plugin::plugin_provider::PluginInstance, service_provider::ServiceProvider,
+        };
+        use base64::{prelude::BASE64_STANDARD, Engine};
+        use repository::{
+            migrations::Version, test_db::setup_all, PluginManifest, PluginMutation, PluginType,
+            PluginTypes,
+        };
+        use serde_json::json;
+        use util::assert_matches;
+
+        let (_, _, connection_manager, _) =
+            setup_all("plugin_graphql_mutation", MockDataInserts::none()).await;
+
+        let bundle = r#"
+            export const plugins = {
+                graphql_mutation: ({ name, input }) => ({ name, count: input.count + 1 }),
+            };
+        "#;
+        PluginInstance::bind(BackendPluginRow {
+            id: "graphql_mutation_test".to_string(),
+            code: "graphql_mutation_test".to_string(),
+            version: Version::from_package_json().to_string(),
+            bundle_base64: BASE64_STANDARD.encode(bundle),
+            types: PluginTypes(vec![PluginType::GraphqlMutation]),
+            manifest: PluginManifest {
+                mutations: vec![PluginMutation {
+                    name: "increment".to_string(),
+                    input_schema: json!({
+                        "type": "object",
+                        "properties": { "count": { "type": "integer" } },
+                        "required": ["count"]
+                    }),
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let service = ServiceProvider::new(connection_manager).plugin_service;
+        assert!(service.declared_plugin_graphql_mutations().iter().any(
+            |(plugin_code, mutation)| plugin_code == "graphql_mutation_test"
+                && mutation.name == "increment"
+        ));
+        let call = |name: &str, input: serde_json::Value| {
+            service.plugin_graphql_mutation(
+                "store_a".to_string(),
+                "user".to_string(),
+                "graphql_mutation_test",
+                name,
+                input,
+            )
+        };
+
+        assert_matches!(
+            call("decrement", json!({ "count": 1 })),
+            Err(PluginGraphqlMutationError::MutationNotDeclared(_))
+        );
+        assert_matches!(
+            call("increment", json!({ "count": "one" })),
+            Err(PluginGraphqlMutationError::InvalidInput(_))
+        );
+        assert_eq!(
+            call("increment", json!({ "count": 1 })).unwrap(),
+            json!({ "name": "increment", "count": 2 })
+        );
+    }
 }
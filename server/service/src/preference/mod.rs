@@ -65,6 +65,7 @@ pub trait PreferenceServiceTrait: Sync + Send {
             warn_when_missing_recent_stocktake,
             store_custom_colour,
             invoice_status_options,
+            use_demand_forecast_for_suggested_quantity,
         } = self.get_preference_provider();
 
         let input = AppendIfTypeInputs {
@@ -143,6 +144,11 @@ pub trait PreferenceServiceTrait: Sync + Send {
         append_if_type(store_custom_colour, &mut prefs, &input)?;
         append_if_type(warn_when_missing_recent_stocktake, &mut prefs, &input)?;
         append_if_type(invoice_status_options, &mut prefs, &input)?;
+        append_if_type(
+            use_demand_forecast_for_suggested_quantity,
+            &mut prefs,
+            &input,
+        )?;
 
         Ok(prefs)
     }
@@ -72,6 +72,8 @@ pub mod global_table_configs;
 pub use global_table_configs::*;
 pub mod backdating;
 pub use backdating::*;
+pub mod use_demand_forecast_for_suggested_quantity;
+pub use use_demand_forecast_for_suggested_quantity::*;
 
 pub struct PreferenceProvider {
     // Global preferences
@@ -119,6 +121,7 @@ pub struct PreferenceProvider {
     pub store_custom_colour: StoreCustomColour,
     pub invoice_status_options: InvoiceStatusOptions,
     pub show_indicative_price_in_requisitions: ShowIndicativePriceInRequisitions,
+    pub use_demand_forecast_for_suggested_quantity: UseDemandForecastForSuggestedQuantity,
 }
 
 pub fn get_preference_provider() -> PreferenceProvider {
@@ -168,5 +171,6 @@ pub fn get_preference_provider() -> PreferenceProvider {
         warn_when_missing_recent_stocktake: WarnWhenMissingRecentStocktake,
         invoice_status_options: InvoiceStatusOptions,
         show_indicative_price_in_requisitions: ShowIndicativePriceInRequisitions,
+        use_demand_forecast_for_suggested_quantity: UseDemandForecastForSuggestedQuantity,
     }
 }
@@ -0,0 +1,20 @@
+use crate::preference::{PrefKey, Preference, PreferenceType, PreferenceValueType};
+
+/// Request requisition suggested quantity uses the seasonal demand forecast instead of AMC
+pub struct UseDemandForecastForSuggestedQuantity;
+
+impl Preference for UseDemandForecastForSuggestedQuantity {
+    type Value = bool;
+
+    fn key(&self) -> PrefKey {
+        PrefKey::UseDemandForecastForSuggestedQuantity
+    }
+
+    fn preference_type(&self) -> PreferenceType {
+        PreferenceType::Store
+    }
+
+    fn value_type(&self) -> PreferenceValueType {
+        PreferenceValueType::Boolean
+    }
+}
@@ -54,6 +54,7 @@ pub enum PrefKey {
     WarnWhenMissingRecentStocktake,
     InvoiceStatusOptions,
     ShowIndicativePriceInRequisitions,
+    UseDemandForecastForSuggestedQuantity,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -60,6 +60,7 @@ pub struct UpsertPreferences {
     pub store_custom_colour: Option<Vec<StorePrefUpdate<String>>>,
     pub invoice_status_options: Option<Vec<StorePrefUpdate<Vec<InvoiceStatus>>>>,
     pub show_indicative_price_in_requisitions: Option<Vec<StorePrefUpdate<bool>>>,
+    pub use_demand_forecast_for_suggested_quantity: Option<Vec<StorePrefUpdate<bool>>>,
 }
 
 pub fn upsert_preferences(
@@ -112,6 +113,7 @@ pub fn upsert_preferences(
         store_custom_colour: store_custom_colour_input,
         invoice_status_options: invoice_status_options_input,
         show_indicative_price_in_requisitions: show_indicative_price_in_requisitions_input,
+        use_demand_forecast_for_suggested_quantity: use_demand_forecast_for_suggested_quantity_input,
     }: UpsertPreferences,
 ) -> Result<(), UpsertPreferenceError> {
     let PreferenceProvider {
@@ -156,6 +158,7 @@ pub fn upsert_preferences(
         invoice_status_options,
         external_inbound_shipment_lines_must_be_authorised,
         show_indicative_price_in_requisitions,
+        use_demand_forecast_for_suggested_quantity,
     }: PreferenceProvider = get_preference_provider();
 
     ctx.connection
@@ -345,6 +348,10 @@ pub fn upsert_preferences(
                 upsert_store_input(connection, show_indicative_price_in_requisitions, input)?;
             }
 
+            if let Some(input) = use_demand_forecast_for_suggested_quantity_input {
+                upsert_store_input(connection, use_demand_forecast_for_suggested_quantity, input)?;
+            }
+
             Ok(())
         })
         .map_err(|error: TransactionError<UpsertPreferenceError>| error.to_inner_error())?;
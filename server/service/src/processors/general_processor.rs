@@ -21,7 +21,7 @@ use crate::{
 use super::{
     add_central_patient_visibility::AddPatientVisibilityForCentral,
     assign_requisition_number::AssignRequisitionNumber, contact_form::QueueContactEmailProcessor,
-    load_plugin::LoadPlugin, plugin_processor::PluginProcessor,
+    load_plugin::LoadPlugin, plugin_processor::PluginProcessor, recall::RecallStockOnHoldProcessor,
     requisition_auto_finalise::RequisitionAutoFinaliseProcessor,
 };
 
@@ -53,6 +53,7 @@ pub enum ProcessorType {
     AddPatientVisibilityForCentral,
     Plugins,
     RequisitionAutoFinalise,
+    RecallStockOnHold,
 }
 
 impl ProcessorType {
@@ -69,6 +70,7 @@ impl ProcessorType {
             ProcessorType::RequisitionAutoFinalise => {
                 vec![Box::new(RequisitionAutoFinaliseProcessor)]
             }
+            ProcessorType::RecallStockOnHold => vec![Box::new(RecallStockOnHoldProcessor)],
         }
     }
 
@@ -22,6 +22,7 @@ mod contact_form;
 mod general_processor;
 mod load_plugin;
 mod plugin_processor;
+mod recall;
 mod requisition_auto_finalise;
 pub use general_processor::ProcessorType;
 #[cfg(test)]
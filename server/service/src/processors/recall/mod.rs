@@ -0,0 +1,5 @@
+mod recall_stock_on_hold;
+pub(crate) use self::recall_stock_on_hold::*;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use repository::{
+    ActivityLogType, ChangelogFilter, ChangelogRow, ChangelogTableName, EqualFilter, KeyType,
+    RecallRowRepository, StockLineFilter, StockLineRepository, StockLineRow,
+    StockLineRowRepository, StorageConnection,
+};
+
+use crate::{
+    activity_log::system_activity_log_entry,
+    cursor_controller::CursorType,
+    processors::general_processor::{Processor, ProcessorError},
+    recall::is_stock_recalled,
+    service_provider::{ServiceContext, ServiceProvider},
+    sync::ActiveStoresOnSite,
+};
+
+/// Puts stock lines matching an active recall on hold, in all stores active on this site.
+/// Runs when a recall is created or synced. Recalled stock is already put on hold when received
+/// (see `is_stock_recalled`), stock line changes are still checked for stock created otherwise
+pub(crate) struct RecallStockOnHoldProcessor;
+
+#[async_trait]
+impl Processor for RecallStockOnHoldProcessor {
+    fn get_description(&self) -> String {
+        "Put stock lines matching active recalls on hold".to_string()
+    }
+
+    async fn try_process_record(
+        &self,
+        ctx: &ServiceContext,
+        _service_provider: &ServiceProvider,
+        changelog: &ChangelogRow,
+    ) -> Result<Option<String>, ProcessorError> {
+        let connection = &ctx.connection;
+        let active_stores = ActiveStoresOnSite::get(connection)
+            .map_err(ProcessorError::GetActiveStoresOnSiteError)?;
+
+        let stock_line_filter = match changelog.table_name {
+            ChangelogTableName::Recall => {
+                let recall = RecallRowRepository::new(connection)
+                    .find_one_by_id(&changelog.record_id)?
+                    .ok_or(ProcessorError::RecordNotFound(
+                        "Recall".to_string(),
+                        changelog.record_id.clone(),
+                    ))?;
+
+                if recall.closed_datetime.is_some() {
+                    return Ok(None);
+                }
+
+                StockLineFilter::new().item_id(EqualFilter::equal_to(recall.item_id))
+            }
+            ChangelogTableName::StockLine => {
+                StockLineFilter::new().id(EqualFilter::equal_to(changelog.record_id.clone()))
+            }
+            _ => return Ok(None),
+        };
+
+        let stock_lines = StockLineRepository::new(connection).query_by_filter(
+            stock_line_filter
+                .store_id(EqualFilter::equal_any(active_stores.store_ids()))
+                .has_packs_in_store(true),
+            None,
+        )?;
+
+        let mut held_count = 0;
+        for stock_line in stock_lines {
+            if stock_line.stock_line_row.on_hold {
+                continue;
+            }
+
+            let is_recalled = is_stock_recalled(
+                connection,
+                &stock_line.item_row.id,
+                stock_line.stock_line_row.batch.as_deref(),
+                stock_line.stock_line_row.item_variant_id.as_deref(),
+            )?;
+
+            if is_recalled {
+                hold_stock_line(connection, stock_line.stock_line_row)?;
+                held_count += 1;
+            }
+        }
+
+        if held_count == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(format!(
+            "{held_count} recalled stock line(s) put on hold for {:?} ({})",
+            changelog.table_name, changelog.record_id
+        )))
+    }
+
+    fn changelogs_filter(&self, ctx: &ServiceContext) -> Result<ChangelogFilter, ProcessorError> {
+        let active_stores = ActiveStoresOnSite::get(&ctx.connection)
+            .map_err(ProcessorError::GetActiveStoresOnSiteError)?;
+
+        // Recall changelogs don't have a store_id, stock line changelogs are limited to active stores
+        let filter = ChangelogFilter::new()
+            .table_name(EqualFilter {
+                equal_any: Some(vec![
+                    ChangelogTableName::Recall,
+                    ChangelogTableName::StockLine,
+                ]),
+                ..Default::default()
+            })
+            .store_id(EqualFilter::equal_any_or_null(active_stores.store_ids()));
+
+        Ok(filter)
+    }
+
+    fn cursor_type(&self) -> CursorType {
+        CursorType::Standard(KeyType::RecallProcessorCursor)
+    }
+}
+
+fn hold_stock_line(
+    connection: &StorageConnection,
+    stock_line: StockLineRow,
+) -> Result<(), ProcessorError> {
+    let stock_line = StockLineRow {
+        on_hold: true,
+        ..stock_line
+    };
+    StockLineRowRepository::new(connection).upsert_one(&stock_line)?;
+
+    system_activity_log_entry(
+        connection,
+        ActivityLogType::StockLineRecalled,
+        &stock_line.store_id,
+        &stock_line.id,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use chrono::Utc;
+use repository::{
+    mock::{MockData, MockDataInserts},
+    ActivityLogFilter, ActivityLogRepository, ActivityLogType, EqualFilter, InvoiceLineRow,
+    ItemRow, KeyType, KeyValueStoreRow, NameRow, RecallRow, RecallRowRepository, StockLineRow,
+    StockLineRowRepository, StorageConnection, StoreRow,
+};
+use util::uuid::uuid;
+
+use crate::{
+    invoice_line::stock_in_line::{generate_batch, StockLineInput},
+    processors::ProcessorType,
+    service_provider::ServiceContext,
+    test_helpers::{setup_all_with_data_and_service_provider, ServiceTestContext},
+};
+
+#[tokio::test]
+async fn test_recall_stock_on_hold() {
+    let site_id = 25;
+
+    let store_name = NameRow {
+        id: uuid(),
+        ..Default::default()
+    };
+
+    let store = StoreRow {
+        id: uuid(),
+        name_id: store_name.id.clone(),
+        site_id,
+        ..Default::default()
+    };
+
+    let other_site_store_name = NameRow {
+        id: uuid(),
+        ..Default::default()
+    };
+
+    let other_site_store = StoreRow {
+        id: uuid(),
+        name_id: other_site_store_name.id.clone(),
+        site_id: site_id + 1,
+        ..Default::default()
+    };
+
+    let site_id_settings = KeyValueStoreRow {
+        id: KeyType::SettingsSyncSiteId,
+        value_int: Some(site_id),
+        ..Default::default()
+    };
+
+    let item = ItemRow {
+        id: uuid(),
+        name: "recalled item".to_string(),
+        r#type: repository::ItemType::Stock,
+        is_active: true,
+        ..Default::default()
+    };
+
+    let recalled_stock_line = StockLineRow {
+        id: uuid(),
+        item_link_id: item.id.clone(),
+        store_id: store.id.clone(),
+        batch: Some("B1".to_string()),
+        total_number_of_packs: 10.0,
+        available_number_of_packs: 10.0,
+        pack_size: 1.0,
+        ..Default::default()
+    };
+
+    let other_batch_stock_line = StockLineRow {
+        id: uuid(),
+        batch: Some("B2".to_string()),
+        ..recalled_stock_line.clone()
+    };
+
+    // Stock of stores on other sites is managed by those sites
+    let other_site_stock_line = StockLineRow {
+        id: uuid(),
+        store_id: other_site_store.id.clone(),
+        ..recalled_stock_line.clone()
+    };
+
+    let ServiceTestContext {
+        service_context: ctx,
+        connection,
+        ..
+    } = setup_all_with_data_and_service_provider(
+        "recall_stock_on_hold_processor_test",
+        MockDataInserts::none().stores().names(),
+        MockData {
+            names: vec![store_name.clone(), other_site_store_name],
+            stores: vec![store.clone(), other_site_store],
+            key_value_store_rows: vec![site_id_settings],
+            items: vec![item.clone()],
+            stock_lines: vec![
+                recalled_stock_line.clone(),
+                other_batch_stock_line.clone(),
+                other_site_stock_line.clone(),
+            ],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    RecallRowRepository::new(&connection)
+        .upsert_one(&RecallRow {
+            id: uuid(),
+            item_id: item.id.clone(),
+            batch: Some("B1".to_string()),
+            reason: "Contamination".to_string(),
+            created_datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        })
+        .unwrap();
+
+    run_processor(&ctx).await;
+    assert!(stock_line_get(&connection, &recalled_stock_line.id).on_hold);
+    assert!(!stock_line_get(&connection, &other_batch_stock_line.id).on_hold);
+    assert!(!stock_line_get(&connection, &other_site_stock_line.id).on_hold);
+
+    let logs = ActivityLogRepository::new(&connection)
+        .query_by_filter(ActivityLogFilter::new().r#type(EqualFilter {
+            equal_to: Some(ActivityLogType::StockLineRecalled),
+            ..Default::default()
+        }))
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(
+        logs[0].activity_log_row.record_id,
+        Some(recalled_stock_line.id.clone())
+    );
+
+    // Recalled batch is put on hold as soon as it's received
+    let received_stock_line = generate_batch(
+        &connection,
+        InvoiceLineRow {
+            item_link_id: item.id.clone(),
+            batch: Some("B1".to_string()),
+            number_of_packs: 5.0,
+            pack_size: 1.0,
+            ..Default::default()
+        },
+        StockLineInput {
+            stock_line_id: None,
+            store_id: store.id.clone(),
+            on_hold: false,
+            barcode_id: None,
+            supplier_id: store_name.id.clone(),
+            overwrite_stock_levels: false,
+        },
+    )
+    .unwrap();
+    assert!(received_stock_line.on_hold);
+
+    // Recalled batch created other than by receiving (e.g. synced) is put on hold by the processor
+    let received_stock_line = StockLineRow {
+        id: uuid(),
+        ..recalled_stock_line
+    };
+    StockLineRowRepository::new(&connection)
+        .upsert_one(&received_stock_line)
+        .unwrap();
+
+    run_processor(&ctx).await;
+    assert!(stock_line_get(&connection, &received_stock_line.id).on_hold);
+}
+
+async fn run_processor(ctx: &ServiceContext) {
+    ctx.processors_trigger
+        .general_processor
+        .try_send(ProcessorType::RecallStockOnHold)
+        .unwrap();
+    ctx.processors_trigger.await_events_processed().await;
+}
+
+fn stock_line_get(connection: &StorageConnection, id: &str) -> StockLineRow {
+    StockLineRowRepository::new(connection)
+        .find_one_by_id(id)
+        .unwrap()
+        .unwrap()
+}
@@ -5,7 +5,10 @@ use crate::{
     preference::{Preference, PreventTransfersMonthsBeforeInitialisation},
     pricing::item_price::{get_pricing_for_items, ItemPriceLookup},
     processors::transfer::requisition::RequisitionTransferOutput,
-    requisition::common::{get_indicative_price_pref, get_lines_for_requisition},
+    requisition::{
+        approval::{get_next_approval_step, notify_approver},
+        common::{get_indicative_price_pref, get_lines_for_requisition},
+    },
     store_preference::get_store_preferences,
 };
 use chrono::{Months, Utc};
@@ -116,7 +119,7 @@ impl RequisitionTransferProcessor for CreateResponseRequisitionProcessor {
             None
         };
 
-        let new_response_requisition_row = RequisitionRow {
+        let mut new_response_requisition_row = RequisitionRow {
             approval_status,
             ..generate_response_requisition(connection, request_requisition, record_for_processing)?
         };
@@ -127,6 +130,16 @@ impl RequisitionTransferProcessor for CreateResponseRequisitionProcessor {
             &request_requisition.requisition_row,
         )?;
 
+        // Approval chains configured in omSupply take over from remote authorisation
+        let first_approval_step = get_next_approval_step(
+            connection,
+            &new_response_requisition_row,
+            &new_requisition_lines,
+        )?;
+        if first_approval_step.is_some() {
+            new_response_requisition_row.approval_status = Some(ApprovalStatusType::Pending);
+        }
+
         RequisitionRowRepository::new(connection).upsert_one(&new_response_requisition_row)?;
 
         system_activity_log_entry(
@@ -142,6 +155,10 @@ impl RequisitionTransferProcessor for CreateResponseRequisitionProcessor {
             requisition_line_row_repository.upsert_one(line)?;
         }
 
+        if let Some(step) = first_approval_step {
+            notify_approver(connection, &step, &new_response_requisition_row)?;
+        }
+
         let customer_name_id = StoreRepository::new(connection)
             .query_by_filter(StoreFilter::new().id(EqualFilter::equal_to(
                 request_requisition.store_row.id.to_string(),
@@ -284,6 +301,7 @@ fn generate_response_requisition(
         period_id: request_requisition_row.period_id.clone(),
         order_type: request_requisition_row.order_type.clone(),
         is_emergency: request_requisition_row.is_emergency,
+        emergency_reason_option_id: request_requisition_row.emergency_reason_option_id.clone(),
         destination_customer_id: request_requisition_row.destination_customer_id.clone(),
         created_from_requisition_id: request_requisition_row.created_from_requisition_id.clone(),
         // Default
@@ -1,4 +1,4 @@
-use self::query::{get_purchase_order, get_purchase_orders};
+use self::query::{get_purchase_order, get_purchase_orders, get_purchase_orders_after};
 use crate::{
     purchase_order::{
         delete::{delete_purchase_order, DeletePurchaseOrderError},
@@ -9,9 +9,10 @@ use crate::{
     ListError, ListResult,
 };
 
+use chrono::NaiveDateTime;
 use repository::{
-    PaginationOption, PurchaseOrder, PurchaseOrderFilter, PurchaseOrderLine, PurchaseOrderRow,
-    PurchaseOrderSort, RepositoryError,
+    Keyset, PaginationOption, PurchaseOrder, PurchaseOrderFilter, PurchaseOrderLine,
+    PurchaseOrderRow, PurchaseOrderSort, RepositoryError,
 };
 
 pub mod add_to_purchase_order_from_master_list;
@@ -44,6 +45,17 @@ pub trait PurchaseOrderServiceTrait: Sync + Send {
         get_purchase_orders(ctx, store_id, pagination, filter, sort)
     }
 
+    fn get_purchase_orders_after(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+        filter: Option<PurchaseOrderFilter>,
+    ) -> Result<ListResult<PurchaseOrder>, ListError> {
+        get_purchase_orders_after(ctx, store_id, after, limit, filter)
+    }
+
     fn insert_purchase_order(
         &self,
         ctx: &ServiceContext,
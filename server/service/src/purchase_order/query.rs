@@ -1,9 +1,10 @@
 use crate::{
     get_pagination_or_default, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
 };
+use chrono::NaiveDateTime;
 use repository::{
-    EqualFilter, PaginationOption, PurchaseOrder, PurchaseOrderFilter, PurchaseOrderRepository,
-    PurchaseOrderSort, RepositoryError,
+    EqualFilter, Keyset, PaginationOption, PurchaseOrder, PurchaseOrderFilter,
+    PurchaseOrderRepository, PurchaseOrderSort, RepositoryError,
 };
 
 pub const MAX_LIMIT: u32 = 1000;
@@ -28,6 +29,26 @@ pub fn get_purchase_orders(
     })
 }
 
+/// Page of the store's purchase orders after `after` by created datetime, for keyset (cursor)
+/// paging. Reads `limit` rows
+pub fn get_purchase_orders_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<NaiveDateTime>>,
+    limit: u32,
+    filter: Option<PurchaseOrderFilter>,
+) -> Result<ListResult<PurchaseOrder>, ListError> {
+    let repository = PurchaseOrderRepository::new(&ctx.connection);
+
+    let mut filter: PurchaseOrderFilter = filter.unwrap_or_default();
+    filter.store_id = Some(EqualFilter::equal_to(store_id.to_string()));
+
+    Ok(ListResult {
+        rows: repository.query_after(Some(filter.clone()), after, limit)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
+
 pub fn get_purchase_order(
     ctx: &ServiceContext,
     store_id: Option<&str>,
@@ -0,0 +1,186 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::settings::RateLimitSettings;
+
+// Expired windows are pruned once this many are tracked
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Operations expensive enough to pin the database, limited per user and per client IP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitedOperation {
+    Report,
+    ItemLedger,
+    Export,
+    PluginMutation,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RateLimitExceeded {
+    pub operation: RateLimitedOperation,
+    /// Exceeded by the user, otherwise by the client IP
+    pub by_user: bool,
+    pub retry_after_seconds: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RateLimitKey {
+    User(String, RateLimitedOperation),
+    Ip(String, RateLimitedOperation),
+}
+
+struct Window {
+    start: Instant,
+    count: u32,
+}
+
+/// Fixed window rate limiter, state is kept in memory and reset on restart
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    windows: Mutex<HashMap<RateLimitKey, Window>>,
+}
+
+impl RateLimiter {
+    pub fn new(settings: &RateLimitSettings) -> Self {
+        RateLimiter {
+            max_requests: settings.max_requests,
+            window: Duration::from_secs(settings.window_seconds),
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts the operation against the user and the client IP, an operation that exceeds either
+    /// limit is not counted
+    pub fn check(
+        &self,
+        operation: RateLimitedOperation,
+        user_id: &str,
+        ip_address: Option<&str>,
+    ) -> Result<(), RateLimitExceeded> {
+        self.check_at(operation, user_id, ip_address, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        operation: RateLimitedOperation,
+        user_id: &str,
+        ip_address: Option<&str>,
+        now: Instant,
+    ) -> Result<(), RateLimitExceeded> {
+        let mut keys = vec![RateLimitKey::User(user_id.to_string(), operation)];
+        if let Some(ip_address) = ip_address {
+            keys.push(RateLimitKey::Ip(ip_address.to_string(), operation));
+        }
+
+        let mut windows = self.windows.lock().unwrap();
+        if windows.len() > PRUNE_THRESHOLD {
+            windows.retain(|_, window| now.duration_since(window.start) < self.window);
+        }
+
+        for key in &keys {
+            let Some(window) = windows.get(key) else {
+                continue;
+            };
+            let elapsed = now.duration_since(window.start);
+            if elapsed < self.window && window.count >= self.max_requests {
+                return Err(RateLimitExceeded {
+                    operation,
+                    by_user: matches!(key, RateLimitKey::User(..)),
+                    retry_after_seconds: (self.window - elapsed).as_secs().max(1),
+                });
+            }
+        }
+
+        for key in keys {
+            let window = windows.entry(key).or_insert(Window {
+                start: now,
+                count: 0,
+            });
+            if now.duration_since(window.start) >= self.window {
+                window.start = now;
+                window.count = 0;
+            }
+            window.count += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use crate::settings::RateLimitSettings;
+
+    use super::{RateLimitExceeded, RateLimitedOperation, RateLimiter};
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(&RateLimitSettings {
+            max_requests: 2,
+            window_seconds: 60,
+        });
+        let now = Instant::now();
+        let report = RateLimitedOperation::Report;
+
+        assert!(limiter
+            .check_at(report, "user_a", Some("ip_a"), now)
+            .is_ok());
+        assert!(limiter
+            .check_at(report, "user_a", Some("ip_a"), now)
+            .is_ok());
+        assert_eq!(
+            limiter.check_at(
+                report,
+                "user_a",
+                Some("ip_b"),
+                now + Duration::from_secs(20)
+            ),
+            Err(RateLimitExceeded {
+                operation: report,
+                by_user: true,
+                retry_after_seconds: 40,
+            })
+        );
+        // Other operations have their own limit
+        assert!(limiter
+            .check_at(
+                RateLimitedOperation::ItemLedger,
+                "user_a",
+                Some("ip_a"),
+                now
+            )
+            .is_ok());
+        // Other users on the same IP are limited by the IP
+        assert_eq!(
+            limiter.check_at(report, "user_b", Some("ip_a"), now),
+            Err(RateLimitExceeded {
+                operation: report,
+                by_user: false,
+                retry_after_seconds: 60,
+            })
+        );
+        // Rejected operations are not counted
+        assert!(limiter
+            .check_at(report, "user_b", Some("ip_b"), now)
+            .is_ok());
+        assert!(limiter.check_at(report, "user_b", None, now).is_ok());
+
+        // New window
+        let later = now + Duration::from_secs(60);
+        assert!(limiter
+            .check_at(report, "user_a", Some("ip_a"), later)
+            .is_ok());
+        assert!(limiter
+            .check_at(report, "user_b", Some("ip_a"), later)
+            .is_ok());
+        assert!(limiter
+            .check_at(report, "user_b", Some("ip_a"), later)
+            .is_err());
+    }
+}
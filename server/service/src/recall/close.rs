@@ -0,0 +1,50 @@
+use chrono::Utc;
+use repository::{RecallRow, RecallRowRepository, RepositoryError};
+
+use crate::{service_provider::ServiceContext, sync::CentralServerConfig};
+
+#[derive(PartialEq, Debug)]
+pub enum CloseRecallError {
+    NotCentralServer,
+    RecallDoesNotExist,
+    RecallAlreadyClosed,
+    DatabaseError(RepositoryError),
+}
+
+/// Closing a recall stops new stock being put on hold, stock already on hold stays on hold
+/// until it's returned, disposed of, or released by the store
+pub fn close_recall(ctx: &ServiceContext, recall_id: &str) -> Result<RecallRow, CloseRecallError> {
+    let row = ctx
+        .connection
+        .transaction_sync(|connection| {
+            if !CentralServerConfig::is_central_server() {
+                return Err(CloseRecallError::NotCentralServer);
+            }
+
+            let repo = RecallRowRepository::new(connection);
+            let recall = repo
+                .find_one_by_id(recall_id)?
+                .ok_or(CloseRecallError::RecallDoesNotExist)?;
+
+            if recall.closed_datetime.is_some() {
+                return Err(CloseRecallError::RecallAlreadyClosed);
+            }
+
+            let closed = RecallRow {
+                closed_datetime: Some(Utc::now().naive_utc()),
+                ..recall
+            };
+            repo.upsert_one(&closed)?;
+
+            Ok(closed)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(row)
+}
+
+impl From<RepositoryError> for CloseRecallError {
+    fn from(error: RepositoryError) -> Self {
+        CloseRecallError::DatabaseError(error)
+    }
+}
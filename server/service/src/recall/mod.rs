@@ -0,0 +1,83 @@
+mod close;
+mod supplier_return;
+mod trace;
+mod upsert;
+
+use crate::service_provider::ServiceContext;
+pub use close::{close_recall, CloseRecallError};
+use repository::{
+    Invoice, ItemLinkRowRepository, RecallRow, RecallRowRepository, RepositoryError,
+    StorageConnection,
+};
+pub use supplier_return::{create_recall_supplier_returns, CreateRecallSupplierReturnsError};
+pub use trace::{get_recall_trace, RecallTrace, RecallTraceError};
+pub use upsert::{upsert_recall, UpsertRecall, UpsertRecallError};
+
+pub trait RecallServiceTrait: Send + Sync {
+    fn get_recalls(
+        &self,
+        ctx: &ServiceContext,
+        active_only: bool,
+    ) -> Result<Vec<RecallRow>, RepositoryError> {
+        let repo = RecallRowRepository::new(&ctx.connection);
+        match active_only {
+            true => repo.find_active(),
+            false => repo.find_all(),
+        }
+    }
+
+    fn upsert_recall(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertRecall,
+    ) -> Result<RecallRow, UpsertRecallError> {
+        upsert_recall(ctx, input)
+    }
+
+    fn close_recall(
+        &self,
+        ctx: &ServiceContext,
+        recall_id: &str,
+    ) -> Result<RecallRow, CloseRecallError> {
+        close_recall(ctx, recall_id)
+    }
+
+    fn get_recall_trace(
+        &self,
+        ctx: &ServiceContext,
+        recall_id: &str,
+    ) -> Result<RecallTrace, RecallTraceError> {
+        get_recall_trace(ctx, recall_id)
+    }
+
+    fn create_recall_supplier_returns(
+        &self,
+        ctx: &ServiceContext,
+        recall_id: &str,
+    ) -> Result<Vec<Invoice>, CreateRecallSupplierReturnsError> {
+        create_recall_supplier_returns(ctx, recall_id)
+    }
+}
+
+/// Does stock of the item with given batch and item variant fall under an active recall
+pub fn is_stock_recalled(
+    connection: &StorageConnection,
+    item_link_id: &str,
+    batch: Option<&str>,
+    item_variant_id: Option<&str>,
+) -> Result<bool, RepositoryError> {
+    let item_id = ItemLinkRowRepository::new(connection)
+        .find_one_by_id(item_link_id)?
+        .map(|item_link| item_link.item_id)
+        .unwrap_or_else(|| item_link_id.to_string());
+
+    let is_recalled = RecallRowRepository::new(connection)
+        .find_active_by_item_id(&item_id)?
+        .iter()
+        .any(|recall| recall.matches(batch, item_variant_id));
+
+    Ok(is_recalled)
+}
+
+pub struct RecallService;
+impl RecallServiceTrait for RecallService {}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+
+use repository::{
+    EqualFilter, Invoice, RecallRow, RecallRowRepository, RepositoryError, StockLineFilter,
+    StockLineRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+use crate::{
+    invoice::supplier_return::{
+        insert::{insert_supplier_return, InsertSupplierReturn, InsertSupplierReturnError},
+        SupplierReturnLineInput,
+    },
+    service_provider::ServiceContext,
+};
+
+#[derive(PartialEq, Debug)]
+pub enum CreateRecallSupplierReturnsError {
+    RecallDoesNotExist,
+    RecallIsClosed,
+    /// No available recalled stock with a known supplier in store
+    NoStockToReturn,
+    SupplierReturnError {
+        supplier_id: String,
+        error: InsertSupplierReturnError,
+    },
+    DatabaseError(RepositoryError),
+}
+
+type OutError = CreateRecallSupplierReturnsError;
+
+/// Creates a supplier return for each supplier of recalled stock available in the current store,
+/// returning all available packs of the recalled stock lines
+pub fn create_recall_supplier_returns(
+    ctx: &ServiceContext,
+    recall_id: &str,
+) -> Result<Vec<Invoice>, OutError> {
+    let supplier_returns = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let recall = RecallRowRepository::new(connection)
+                .find_one_by_id(recall_id)?
+                .ok_or(OutError::RecallDoesNotExist)?;
+
+            if recall.closed_datetime.is_some() {
+                return Err(OutError::RecallIsClosed);
+            }
+
+            let lines_by_supplier = generate_lines_by_supplier(connection, &ctx.store_id, &recall)?;
+            if lines_by_supplier.is_empty() {
+                return Err(OutError::NoStockToReturn);
+            }
+
+            let mut supplier_returns = Vec::new();
+            for (supplier_id, supplier_return_lines) in lines_by_supplier {
+                let supplier_return = insert_supplier_return(
+                    ctx,
+                    InsertSupplierReturn {
+                        id: uuid(),
+                        other_party_id: supplier_id.clone(),
+                        inbound_shipment_id: None,
+                        their_reference: Some(format!("Recall {}", recall.id)),
+                        supplier_return_lines,
+                    },
+                )
+                .map_err(|error| OutError::SupplierReturnError { supplier_id, error })?;
+
+                supplier_returns.push(supplier_return);
+            }
+
+            Ok(supplier_returns)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(supplier_returns)
+}
+
+fn generate_lines_by_supplier(
+    connection: &StorageConnection,
+    store_id: &str,
+    recall: &RecallRow,
+) -> Result<BTreeMap<String, Vec<SupplierReturnLineInput>>, RepositoryError> {
+    let stock_lines = StockLineRepository::new(connection).query_by_filter(
+        StockLineFilter::new()
+            .item_id(EqualFilter::equal_to(recall.item_id.clone()))
+            .store_id(EqualFilter::equal_to(store_id.to_string()))
+            .is_available(true),
+        Some(store_id.to_string()),
+    )?;
+
+    let mut lines_by_supplier: BTreeMap<String, Vec<SupplierReturnLineInput>> = BTreeMap::new();
+    for stock_line in stock_lines.into_iter().map(|line| line.stock_line_row) {
+        if !recall.matches(
+            stock_line.batch.as_deref(),
+            stock_line.item_variant_id.as_deref(),
+        ) {
+            continue;
+        }
+        // Stock without a known supplier has to be returned manually
+        let Some(supplier_id) = stock_line.supplier_id else {
+            continue;
+        };
+
+        lines_by_supplier
+            .entry(supplier_id)
+            .or_default()
+            .push(SupplierReturnLineInput {
+                id: uuid(),
+                stock_line_id: stock_line.id,
+                number_of_packs: stock_line.available_number_of_packs,
+                reason_id: None,
+                note: Some(format!("Recall: {}", recall.reason)),
+            });
+    }
+
+    Ok(lines_by_supplier)
+}
+
+impl From<RepositoryError> for CreateRecallSupplierReturnsError {
+    fn from(error: RepositoryError) -> Self {
+        CreateRecallSupplierReturnsError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use repository::{
+        mock::{
+            mock_item_a, mock_name_a, mock_store_a, mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceType, RecallRow, RecallRowRepository, StockLineRow,
+    };
+
+    use crate::{recall::CreateRecallSupplierReturnsError, service_provider::ServiceProvider};
+
+    #[actix_rt::test]
+    async fn create_recall_supplier_returns() {
+        let recall = RecallRow {
+            id: "recall".to_string(),
+            item_id: mock_item_a().id,
+            batch: Some("recalled".to_string()),
+            reason: "Contamination".to_string(),
+            created_datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        };
+
+        let recalled_stock_line = StockLineRow {
+            id: "recalled_stock_line".to_string(),
+            item_link_id: mock_item_a().id,
+            store_id: mock_store_a().id,
+            batch: Some("recalled".to_string()),
+            supplier_id: Some(mock_name_a().id),
+            pack_size: 1.0,
+            total_number_of_packs: 5.0,
+            available_number_of_packs: 5.0,
+            on_hold: true,
+            ..Default::default()
+        };
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "create_recall_supplier_returns",
+            MockDataInserts::all(),
+            MockData {
+                stock_lines: vec![recalled_stock_line.clone()],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let repo = RecallRowRepository::new(&connection);
+        repo.upsert_one(&recall).unwrap();
+        repo.upsert_one(&RecallRow {
+            id: "no_stock_recall".to_string(),
+            batch: Some("not_in_store".to_string()),
+            ..recall.clone()
+        })
+        .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.recall_service;
+
+        assert_eq!(
+            service.create_recall_supplier_returns(&context, "does_not_exist"),
+            Err(CreateRecallSupplierReturnsError::RecallDoesNotExist)
+        );
+        assert_eq!(
+            service.create_recall_supplier_returns(&context, "no_stock_recall"),
+            Err(CreateRecallSupplierReturnsError::NoStockToReturn)
+        );
+
+        // Stock on hold because of the recall can still be returned
+        let supplier_returns = service
+            .create_recall_supplier_returns(&context, &recall.id)
+            .unwrap();
+        assert_eq!(supplier_returns.len(), 1);
+        assert_eq!(
+            supplier_returns[0].invoice_row.r#type,
+            InvoiceType::SupplierReturn
+        );
+        assert_eq!(supplier_returns[0].invoice_row.name_id, mock_name_a().id);
+    }
+}
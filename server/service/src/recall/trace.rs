@@ -0,0 +1,324 @@
+use std::collections::HashSet;
+
+use repository::{
+    EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineType,
+    InvoiceStatus, InvoiceType, PermissionType, RecallRow, RecallRowRepository, RepositoryError,
+    StockLine, StockLineFilter, StockLineRepository, StorageConnection, UserPermissionFilter,
+    UserPermissionRepository,
+};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(PartialEq, Debug)]
+pub enum RecallTraceError {
+    RecallDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+/// Where recalled stock is now and where it went, across the stores on this site that the user
+/// can query stock of. On central server this includes stock and shipments of remote stores
+#[derive(Debug, PartialEq)]
+pub struct RecallTrace {
+    pub recall: RecallRow,
+    /// Matching stock lines that still have stock in store
+    pub stock_lines: Vec<StockLine>,
+    /// Matching lines of outbound shipments and prescriptions that left the store
+    pub issued_lines: Vec<InvoiceLine>,
+    /// Matching lines of supplier returns that left the store
+    pub returned_lines: Vec<InvoiceLine>,
+}
+
+pub fn get_recall_trace(
+    ctx: &ServiceContext,
+    recall_id: &str,
+) -> Result<RecallTrace, RecallTraceError> {
+    let connection = &ctx.connection;
+    let recall = RecallRowRepository::new(connection)
+        .find_one_by_id(recall_id)?
+        .ok_or(RecallTraceError::RecallDoesNotExist)?;
+    let store_ids = permitted_store_ids(connection, &ctx.user_id)?;
+
+    let stock_lines = StockLineRepository::new(connection)
+        .query_by_filter(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_any(store_ids.clone()))
+                .item_id(EqualFilter::equal_to(recall.item_id.clone()))
+                .has_packs_in_store(true),
+            None,
+        )?
+        .into_iter()
+        .filter(|line| {
+            recall.matches(
+                line.stock_line_row.batch.as_deref(),
+                line.stock_line_row.item_variant_id.as_deref(),
+            )
+        })
+        .collect();
+
+    let (returned_lines, issued_lines) = InvoiceLineRepository::new(connection)
+        .query_by_filter(
+            InvoiceLineFilter::new()
+                .store_id(EqualFilter::equal_any(store_ids))
+                .item_id(EqualFilter::equal_to(recall.item_id.clone()))
+                .r#type(InvoiceLineType::StockOut.equal_to())
+                .invoice_type(InvoiceType::equal_any(vec![
+                    InvoiceType::OutboundShipment,
+                    InvoiceType::Prescription,
+                    InvoiceType::SupplierReturn,
+                ]))
+                .invoice_status(InvoiceStatus::equal_any(vec![
+                    InvoiceStatus::Picked,
+                    InvoiceStatus::Shipped,
+                    InvoiceStatus::Received,
+                    InvoiceStatus::Delivered,
+                    InvoiceStatus::Verified,
+                ])),
+        )?
+        .into_iter()
+        .filter(|line| {
+            recall.matches(
+                line.invoice_line_row.batch.as_deref(),
+                line.invoice_line_row.item_variant_id.as_deref(),
+            )
+        })
+        .partition(|line| line.invoice_row.r#type == InvoiceType::SupplierReturn);
+
+    Ok(RecallTrace {
+        recall,
+        stock_lines,
+        issued_lines,
+        returned_lines,
+    })
+}
+
+/// Stores where the user has store access and can query stock lines
+fn permitted_store_ids(
+    connection: &StorageConnection,
+    user_id: &str,
+) -> Result<Vec<String>, RepositoryError> {
+    let permissions = UserPermissionRepository::new(connection).query_by_filter(
+        UserPermissionFilter::new().user_id(EqualFilter::equal_to(user_id.to_string())),
+    )?;
+
+    let store_ids_with = |permission: PermissionType| -> HashSet<String> {
+        permissions
+            .iter()
+            .filter(|row| row.permission == permission)
+            .filter_map(|row| row.store_id.clone())
+            .collect()
+    };
+
+    Ok(store_ids_with(PermissionType::StoreAccess)
+        .intersection(&store_ids_with(PermissionType::StockLineQuery))
+        .cloned()
+        .collect())
+}
+
+impl RecallTrace {
+    pub fn units_in_stock(&self) -> f64 {
+        self.stock_lines
+            .iter()
+            .map(|line| line.stock_line_row.total_number_of_packs * line.stock_line_row.pack_size)
+            .sum()
+    }
+
+    pub fn units_on_hold(&self) -> f64 {
+        self.stock_lines
+            .iter()
+            .filter(|line| line.stock_line_row.on_hold)
+            .map(|line| line.stock_line_row.total_number_of_packs * line.stock_line_row.pack_size)
+            .sum()
+    }
+
+    /// Units shipped to other stores and customers
+    pub fn units_shipped(&self) -> f64 {
+        units_by_invoice_type(&self.issued_lines, InvoiceType::OutboundShipment)
+    }
+
+    /// Units dispensed to patients
+    pub fn units_dispensed(&self) -> f64 {
+        units_by_invoice_type(&self.issued_lines, InvoiceType::Prescription)
+    }
+
+    pub fn units_returned(&self) -> f64 {
+        units_by_invoice_type(&self.returned_lines, InvoiceType::SupplierReturn)
+    }
+}
+
+fn units_by_invoice_type(lines: &[InvoiceLine], r#type: InvoiceType) -> f64 {
+    lines
+        .iter()
+        .filter(|line| line.invoice_row.r#type == r#type)
+        .map(|line| line.invoice_line_row.number_of_packs * line.invoice_line_row.pack_size)
+        .sum()
+}
+
+impl From<RepositoryError> for RecallTraceError {
+    fn from(error: RepositoryError) -> Self {
+        RecallTraceError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use repository::{
+        mock::{
+            mock_item_a, mock_name_store_b, mock_patient, mock_store_a, mock_store_b,
+            mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType, PermissionType,
+        RecallRow, RecallRowRepository, StockLineRow, UserPermissionRow,
+    };
+
+    use crate::{recall::RecallTraceError, service_provider::ServiceProvider};
+
+    #[actix_rt::test]
+    async fn recall_trace() {
+        let recall = RecallRow {
+            id: "recall".to_string(),
+            item_id: mock_item_a().id,
+            batch: Some("recalled".to_string()),
+            reason: "Contamination".to_string(),
+            created_datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        };
+
+        let stock_line = |id: &str, batch: &str, on_hold| StockLineRow {
+            id: id.to_string(),
+            item_link_id: mock_item_a().id,
+            store_id: mock_store_a().id,
+            batch: Some(batch.to_string()),
+            pack_size: 10.0,
+            total_number_of_packs: 2.0,
+            available_number_of_packs: 2.0,
+            on_hold,
+            ..Default::default()
+        };
+
+        let invoice = |id: &str, r#type, name_id: String, status| InvoiceRow {
+            id: id.to_string(),
+            store_id: mock_store_a().id,
+            name_id,
+            r#type,
+            status,
+            ..Default::default()
+        };
+
+        let line = |id: &str, invoice_id: &str, batch: &str, number_of_packs| InvoiceLineRow {
+            id: id.to_string(),
+            invoice_id: invoice_id.to_string(),
+            item_link_id: mock_item_a().id,
+            batch: Some(batch.to_string()),
+            r#type: InvoiceLineType::StockOut,
+            pack_size: 10.0,
+            number_of_packs,
+            ..Default::default()
+        };
+
+        let permission = |store_id: &str, permission: PermissionType| UserPermissionRow {
+            id: format!("recall_trace_{store_id}_{permission:?}"),
+            user_id: mock_user_account_a().id,
+            store_id: Some(store_id.to_string()),
+            permission,
+            context_id: None,
+        };
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "recall_trace",
+            MockDataInserts::all(),
+            MockData {
+                stock_lines: vec![
+                    stock_line("recalled_on_hold", "recalled", true),
+                    stock_line("recalled_not_on_hold", "recalled", false),
+                    stock_line("not_recalled", "other", false),
+                    // User can't query stock of store b
+                    StockLineRow {
+                        store_id: mock_store_b().id,
+                        ..stock_line("recalled_other_store", "recalled", false)
+                    },
+                ],
+                invoices: vec![
+                    invoice(
+                        "shipped",
+                        InvoiceType::OutboundShipment,
+                        mock_name_store_b().id,
+                        InvoiceStatus::Shipped,
+                    ),
+                    // Stock hasn't left the store yet
+                    invoice(
+                        "allocated",
+                        InvoiceType::OutboundShipment,
+                        mock_name_store_b().id,
+                        InvoiceStatus::Allocated,
+                    ),
+                    invoice(
+                        "dispensed",
+                        InvoiceType::Prescription,
+                        mock_patient().id,
+                        InvoiceStatus::Verified,
+                    ),
+                ],
+                invoice_lines: vec![
+                    line("shipped_recalled", "shipped", "recalled", 3.0),
+                    line("shipped_other", "shipped", "other", 5.0),
+                    line("allocated_recalled", "allocated", "recalled", 7.0),
+                    line("dispensed_recalled", "dispensed", "recalled", 1.0),
+                ],
+                user_permissions: vec![
+                    permission(&mock_store_a().id, PermissionType::StoreAccess),
+                    permission(&mock_store_a().id, PermissionType::StockLineQuery),
+                    // Store access alone doesn't allow querying stock
+                    permission(&mock_store_b().id, PermissionType::StoreAccess),
+                ],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        RecallRowRepository::new(&connection)
+            .upsert_one(&recall)
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.recall_service;
+
+        assert_eq!(
+            service.get_recall_trace(&context, "does_not_exist"),
+            Err(RecallTraceError::RecallDoesNotExist)
+        );
+
+        let trace = service.get_recall_trace(&context, &recall.id).unwrap();
+
+        let mut stock_line_ids: Vec<String> = trace
+            .stock_lines
+            .iter()
+            .map(|line| line.stock_line_row.id.clone())
+            .collect();
+        stock_line_ids.sort();
+        assert_eq!(
+            stock_line_ids,
+            vec!["recalled_not_on_hold", "recalled_on_hold"]
+        );
+        assert_eq!(trace.units_in_stock(), 40.0);
+        assert_eq!(trace.units_on_hold(), 20.0);
+
+        let mut issued_line_ids: Vec<String> = trace
+            .issued_lines
+            .iter()
+            .map(|line| line.invoice_line_row.id.clone())
+            .collect();
+        issued_line_ids.sort();
+        assert_eq!(
+            issued_line_ids,
+            vec!["dispensed_recalled", "shipped_recalled"]
+        );
+        assert_eq!(trace.units_shipped(), 30.0);
+        assert_eq!(trace.units_dispensed(), 10.0);
+        assert_eq!(trace.units_returned(), 0.0);
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::Utc;
+use repository::{
+    item_variant::item_variant_row::ItemVariantRowRepository, ItemRowRepository, RecallRow,
+    RecallRowRepository, RecallSeverity, RepositoryError, StorageConnection,
+};
+
+use crate::{
+    processors::ProcessorType, service_provider::ServiceContext, sync::CentralServerConfig,
+};
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertRecallError {
+    /// Recalls are raised on the central server and synced to remote sites
+    NotCentralServer,
+    ItemDoesNotExist,
+    ItemVariantDoesNotExist,
+    ItemVariantDoesNotBelongToItem,
+    ReasonNotProvided,
+    RecallIsClosed,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct UpsertRecall {
+    pub id: String,
+    pub item_id: String,
+    pub batch: Option<String>,
+    pub item_variant_id: Option<String>,
+    pub reason: String,
+    pub severity: RecallSeverity,
+}
+
+pub fn upsert_recall(
+    ctx: &ServiceContext,
+    input: UpsertRecall,
+) -> Result<RecallRow, UpsertRecallError> {
+    let row = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let existing = validate(connection, &input)?;
+            let new_row = generate(existing, input.clone());
+            let repo = RecallRowRepository::new(connection);
+
+            repo.upsert_one(&new_row)?;
+
+            repo.find_one_by_id(&new_row.id)?
+                .ok_or(UpsertRecallError::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    // Put matching stock on hold in stores active on central, remote sites will do the same when recall is synced
+    ctx.processors_trigger
+        .trigger_processor(ProcessorType::RecallStockOnHold);
+
+    Ok(row)
+}
+
+impl From<RepositoryError> for UpsertRecallError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertRecallError::DatabaseError(error)
+    }
+}
+
+fn generate(
+    existing: Option<RecallRow>,
+    UpsertRecall {
+        id,
+        item_id,
+        batch,
+        item_variant_id,
+        reason,
+        severity,
+    }: UpsertRecall,
+) -> RecallRow {
+    RecallRow {
+        id,
+        item_id,
+        // Treat empty batch as recall of all batches
+        batch: batch.filter(|batch| !batch.trim().is_empty()),
+        item_variant_id,
+        reason,
+        severity,
+        created_datetime: existing
+            .map(|existing| existing.created_datetime)
+            .unwrap_or_else(|| Utc::now().naive_utc()),
+        closed_datetime: None,
+    }
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &UpsertRecall,
+) -> Result<Option<RecallRow>, UpsertRecallError> {
+    use UpsertRecallError as Error;
+
+    if !CentralServerConfig::is_central_server() {
+        return Err(Error::NotCentralServer);
+    }
+
+    let existing = RecallRowRepository::new(connection).find_one_by_id(&input.id)?;
+    if existing
+        .as_ref()
+        .is_some_and(|existing| existing.closed_datetime.is_some())
+    {
+        return Err(Error::RecallIsClosed);
+    }
+
+    if input.reason.trim().is_empty() {
+        return Err(Error::ReasonNotProvided);
+    }
+
+    if ItemRowRepository::new(connection)
+        .find_one_by_id(&input.item_id)?
+        .is_none()
+    {
+        return Err(Error::ItemDoesNotExist);
+    }
+
+    if let Some(item_variant_id) = &input.item_variant_id {
+        let item_variant = ItemVariantRowRepository::new(connection)
+            .find_one_by_id(item_variant_id)?
+            .ok_or(Error::ItemVariantDoesNotExist)?;
+
+        if item_variant.item_link_id != input.item_id {
+            return Err(Error::ItemVariantDoesNotBelongToItem);
+        }
+    }
+
+    Ok(existing)
+}
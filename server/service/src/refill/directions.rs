@@ -0,0 +1,166 @@
+use repository::AbbreviationRow;
+
+/// Units taken per day according to dosing directions, e.g. `2 tabs bd` is 4. Abbreviations are
+/// expanded first. None when no frequency is found (e.g. `apply as needed`)
+pub fn daily_dose(directions: &str, abbreviations: &[AbbreviationRow]) -> Option<f64> {
+    let tokens = tokenize(&expand_abbreviations(directions, abbreviations));
+    let mut used = vec![false; tokens.len()];
+
+    let mut frequency = None;
+    for (index, token) in tokens.iter().enumerate() {
+        frequency = match token.as_str() {
+            "times" | "x" if index > 0 => parse_number(&tokens[index - 1]).map(|times| {
+                used[index - 1] = true;
+                times / period_in_days(&tokens[index + 1..])
+            }),
+            "every" => parse_every(&tokens[index + 1..], &mut used[index + 1..]),
+            token => fixed_frequency(token).or_else(|| times_suffix(token, &tokens[index + 1..])),
+        };
+        if frequency.is_some() {
+            break;
+        }
+    }
+
+    // Dose is the first number not describing the frequency, a single unit if not given
+    let dose = tokens
+        .iter()
+        .zip(used)
+        .find_map(|(token, used)| (!used).then(|| parse_number(token)).flatten())
+        .unwrap_or(1.0);
+
+    frequency.map(|frequency| dose * frequency)
+}
+
+fn expand_abbreviations(directions: &str, abbreviations: &[AbbreviationRow]) -> String {
+    directions
+        .split_whitespace()
+        .map(|word| {
+            abbreviations
+                .iter()
+                .find(|abbreviation| abbreviation.text.eq_ignore_ascii_case(word))
+                .map(|abbreviation| abbreviation.expansion.as_str())
+                .unwrap_or(word)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn tokenize(directions: &str) -> Vec<String> {
+    directions
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '(' | ')'))
+        .map(|token| token.trim_end_matches('.').to_string())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+fn parse_number(token: &str) -> Option<f64> {
+    if let Some((numerator, denominator)) = token.split_once('/') {
+        let denominator: f64 = denominator.parse().ok()?;
+        return (denominator != 0.0).then_some(numerator.parse::<f64>().ok()? / denominator);
+    }
+
+    token.parse().ok().or_else(|| number_word(token))
+}
+
+fn number_word(token: &str) -> Option<f64> {
+    let number = match token {
+        "half" => 0.5,
+        "one" => 1.0,
+        "two" => 2.0,
+        "three" => 3.0,
+        "four" => 4.0,
+        "five" => 5.0,
+        "six" => 6.0,
+        "eight" => 8.0,
+        "twelve" => 12.0,
+        _ => return None,
+    };
+    Some(number)
+}
+
+/// Days in the period following "times", e.g. `3 times a week`
+fn period_in_days(following: &[String]) -> f64 {
+    let is_weekly = following
+        .iter()
+        .take(3)
+        .any(|token| matches!(token.as_str(), "week" | "weekly" | "wk"));
+    match is_weekly {
+        true => 7.0,
+        false => 1.0,
+    }
+}
+
+/// Doses per day for `every 8 hours`, `every day`, `every other day`
+fn parse_every(following: &[String], used: &mut [bool]) -> Option<f64> {
+    let first = following.first()?;
+    match first.as_str() {
+        "day" | "morning" | "night" | "evening" => return Some(1.0),
+        "other" | "second" => return Some(0.5),
+        "week" => return Some(1.0 / 7.0),
+        _ => {}
+    }
+
+    let hours = parse_number(first)?;
+    let unit = following.get(1)?;
+    used[0] = true;
+    match unit.as_str() {
+        "hours" | "hour" | "hrs" | "hr" | "h" if hours > 0.0 => Some(24.0 / hours),
+        "days" | "day" if hours > 0.0 => Some(1.0 / hours),
+        _ => None,
+    }
+}
+
+fn fixed_frequency(token: &str) -> Option<f64> {
+    let frequency = match token {
+        "od" | "qd" | "daily" | "once" | "nocte" | "mane" | "qhs" | "bedtime" => 1.0,
+        "bd" | "bid" | "twice" => 2.0,
+        "tds" | "tid" | "thrice" => 3.0,
+        "qid" | "qds" => 4.0,
+        "weekly" => 1.0 / 7.0,
+        _ => return hourly(token),
+    };
+    Some(frequency)
+}
+
+/// `q8h`, `8hourly`, `8hrly`
+fn hourly(token: &str) -> Option<f64> {
+    let hours = token.strip_prefix('q').unwrap_or(token);
+    let hours = ["hourly", "hrly", "h"]
+        .iter()
+        .find_map(|suffix| hours.strip_suffix(suffix))?;
+    let hours: f64 = hours.parse().ok()?;
+    (hours > 0.0).then_some(24.0 / hours)
+}
+
+/// `2x daily`
+fn times_suffix(token: &str, following: &[String]) -> Option<f64> {
+    let times: f64 = token.strip_suffix('x')?.parse().ok()?;
+    Some(times / period_in_days(following))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_daily_dose() {
+        let abbreviations = vec![AbbreviationRow {
+            id: "1t".to_string(),
+            text: "1T".to_string(),
+            expansion: "one tablet".to_string(),
+        }];
+        let dose = |directions: &str| daily_dose(directions, &abbreviations);
+
+        assert_eq!(dose("1 tab bd"), Some(2.0));
+        assert_eq!(dose("2 tablets three times a day"), Some(6.0));
+        assert_eq!(dose("1T tds"), Some(3.0));
+        assert_eq!(dose("Take 1 capsule every 8 hours"), Some(3.0));
+        assert_eq!(dose("1/2 tab q12h"), Some(1.0));
+        assert_eq!(dose("One tablet at night (nocte)."), Some(1.0));
+        assert_eq!(dose("2 puffs 2x daily"), Some(4.0));
+        assert_eq!(dose("1 tablet 3 times a week"), Some(3.0 / 7.0));
+        assert_eq!(dose("once daily"), Some(1.0));
+        assert_eq!(dose("apply as needed"), None);
+    }
+}
@@ -0,0 +1,261 @@
+mod directions;
+mod schedule;
+#[cfg(test)]
+mod test;
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use repository::{
+    AbbreviationRowRepository, EqualFilter, InvoiceFilter, InvoiceLineFilter,
+    InvoiceLineRepository, InvoiceLineType, InvoiceRepository, InvoiceStatus, InvoiceType,
+    ItemDirectionRowRepository, RepositoryError, StorageConnection,
+};
+use util::date_now;
+
+use crate::service_provider::ServiceContext;
+pub use directions::daily_dose;
+pub use schedule::{
+    days_supplied, refill_history, Dispensing, Refill, RefillHistory, RefillStatus,
+    RefillThresholds,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefillScheduleInput {
+    /// Only include prescriptions for this program
+    pub program_id: Option<String>,
+    /// Days supplied when the directions don't give a daily dose
+    pub default_days_supplied: i64,
+    pub grace_period_days: i64,
+    pub lost_to_follow_up_days: i64,
+    /// Days before today used for the medication possession ratio
+    pub adherence_period_days: i64,
+    /// Refills due within this many days are `Due`
+    pub due_within_days: i64,
+}
+
+impl Default for RefillScheduleInput {
+    fn default() -> Self {
+        RefillScheduleInput {
+            program_id: None,
+            default_days_supplied: 30,
+            grace_period_days: 7,
+            lost_to_follow_up_days: 28,
+            adherence_period_days: 180,
+            due_within_days: 7,
+        }
+    }
+}
+
+/// Refill schedule of a patient for an item dispensed under a program
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatientRefillSchedule {
+    pub patient_id: String,
+    pub program_id: Option<String>,
+    pub item_id: String,
+    pub item_name: String,
+    /// Directions of the latest dispensing
+    pub directions: Option<String>,
+    pub history: RefillHistory,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RefillScheduleError {
+    DefaultDaysSuppliedMustBePositive,
+    ThresholdsCannotBeNegative,
+    DatabaseError(RepositoryError),
+}
+
+impl From<RepositoryError> for RefillScheduleError {
+    fn from(error: RepositoryError) -> Self {
+        RefillScheduleError::DatabaseError(error)
+    }
+}
+
+pub trait RefillServiceTrait: Sync + Send {
+    /// Refill schedules of a patient's prescriptions in the context store
+    fn get_patient_refill_schedules(
+        &self,
+        ctx: &ServiceContext,
+        patient_id: &str,
+        input: RefillScheduleInput,
+    ) -> Result<Vec<PatientRefillSchedule>, RefillScheduleError> {
+        validate(&input)?;
+        Ok(get_refill_schedules(
+            &ctx.connection,
+            &ctx.store_id,
+            Some(patient_id),
+            &input,
+            date_now(),
+        )?)
+    }
+
+    /// Refill schedules in the context store that are due, overdue or lost to follow up, earliest
+    /// due first
+    fn get_refills_due(
+        &self,
+        ctx: &ServiceContext,
+        input: RefillScheduleInput,
+    ) -> Result<Vec<PatientRefillSchedule>, RefillScheduleError> {
+        validate(&input)?;
+        let mut schedules =
+            get_refill_schedules(&ctx.connection, &ctx.store_id, None, &input, date_now())?;
+        schedules.retain(|schedule| schedule.history.status != RefillStatus::OnSchedule);
+        schedules.sort_by(|a, b| a.history.next_due_date.cmp(&b.history.next_due_date));
+        Ok(schedules)
+    }
+}
+
+pub struct RefillService;
+impl RefillServiceTrait for RefillService {}
+
+fn validate(input: &RefillScheduleInput) -> Result<(), RefillScheduleError> {
+    if input.default_days_supplied <= 0 {
+        return Err(RefillScheduleError::DefaultDaysSuppliedMustBePositive);
+    }
+    let thresholds = [
+        input.grace_period_days,
+        input.lost_to_follow_up_days,
+        input.adherence_period_days,
+        input.due_within_days,
+    ];
+    if thresholds.iter().any(|days| *days < 0) {
+        return Err(RefillScheduleError::ThresholdsCannotBeNegative);
+    }
+    Ok(())
+}
+
+struct ItemDispensings {
+    item_name: String,
+    directions: Option<String>,
+    /// By invoice id, lines of the same item on a prescription are one dispensing
+    dispensings: BTreeMap<String, Dispensing>,
+}
+
+/// Schedules from picked and verified prescriptions, for all patients if patient_id is not set
+pub(crate) fn get_refill_schedules(
+    connection: &StorageConnection,
+    store_id: &str,
+    patient_id: Option<&str>,
+    input: &RefillScheduleInput,
+    today: NaiveDate,
+) -> Result<Vec<PatientRefillSchedule>, RepositoryError> {
+    let mut filter = InvoiceLineFilter::new()
+        .store_id(EqualFilter::equal_to(store_id.to_string()))
+        .r#type(InvoiceLineType::StockOut.equal_to())
+        .invoice_type(InvoiceType::Prescription.equal_to())
+        .invoice_status(InvoiceStatus::equal_any(vec![
+            InvoiceStatus::Picked,
+            InvoiceStatus::Verified,
+        ]));
+    if let Some(program_id) = &input.program_id {
+        filter = filter.program_id(EqualFilter::equal_to(program_id.to_string()));
+    }
+    if let Some(patient_id) = patient_id {
+        let invoice_ids = InvoiceRepository::new(connection)
+            .query_by_filter(
+                InvoiceFilter::new()
+                    .store_id(EqualFilter::equal_to(store_id.to_string()))
+                    .r#type(InvoiceType::Prescription.equal_to())
+                    .name_id(EqualFilter::equal_to(patient_id.to_string())),
+            )?
+            .into_iter()
+            .map(|invoice| invoice.invoice_row.id)
+            .collect();
+        filter = filter.invoice_id(EqualFilter::equal_any(invoice_ids));
+    }
+    let lines = InvoiceLineRepository::new(connection).query_by_filter(filter)?;
+
+    let item_link_ids: Vec<String> = lines
+        .iter()
+        .map(|line| line.invoice_line_row.item_link_id.clone())
+        .collect();
+    // Highest priority directions first, used when the prescription line has none
+    let mut item_directions: HashMap<String, String> = HashMap::new();
+    for direction in
+        ItemDirectionRowRepository::new(connection).find_many_by_item_link_ids(&item_link_ids)?
+    {
+        item_directions
+            .entry(direction.item_link_id)
+            .or_insert(direction.directions);
+    }
+    let abbreviations = AbbreviationRowRepository::new(connection).find_all()?;
+
+    let mut by_patient_item: BTreeMap<(String, Option<String>, String), ItemDispensings> =
+        BTreeMap::new();
+    for line in lines {
+        let invoice = line.invoice_row;
+        let line_row = line.invoice_line_row;
+        let date = invoice
+            .picked_datetime
+            .or(invoice.verified_datetime)
+            .unwrap_or(invoice.created_datetime)
+            .date();
+        let directions = line_row
+            .note
+            .filter(|note| !note.trim().is_empty())
+            .or_else(|| item_directions.get(&line_row.item_link_id).cloned());
+
+        let item = by_patient_item
+            .entry((invoice.name_id, invoice.program_id, line.item_row.id))
+            .or_insert_with(|| ItemDispensings {
+                item_name: line_row.item_name,
+                directions: None,
+                dispensings: BTreeMap::new(),
+            });
+        let dispensing = item
+            .dispensings
+            .entry(invoice.id.clone())
+            .or_insert_with(|| Dispensing {
+                invoice_id: invoice.id,
+                date,
+                quantity: 0.0,
+                daily_dose: None,
+                days_supplied: 0,
+            });
+        dispensing.quantity += line_row.number_of_packs * line_row.pack_size;
+        if let Some(directions) = directions {
+            dispensing.daily_dose = dispensing
+                .daily_dose
+                .or_else(|| daily_dose(&directions, &abbreviations));
+            if item.dispensings.values().all(|other| other.date <= date) {
+                item.directions = Some(directions);
+            }
+        }
+    }
+
+    let thresholds = RefillThresholds {
+        due_within_days: input.due_within_days,
+        grace_period_days: input.grace_period_days,
+        lost_to_follow_up_days: input.lost_to_follow_up_days,
+        adherence_period_days: input.adherence_period_days,
+    };
+    let schedules = by_patient_item
+        .into_iter()
+        .filter_map(|((patient_id, program_id, item_id), item)| {
+            let dispensings = item
+                .dispensings
+                .into_values()
+                .map(|dispensing| Dispensing {
+                    days_supplied: days_supplied(
+                        dispensing.quantity,
+                        dispensing.daily_dose,
+                        input.default_days_supplied,
+                    ),
+                    ..dispensing
+                })
+                .collect();
+
+            Some(PatientRefillSchedule {
+                patient_id,
+                program_id,
+                item_id,
+                item_name: item.item_name,
+                directions: item.directions,
+                history: refill_history(dispensings, &thresholds, today)?,
+            })
+        })
+        .collect();
+
+    Ok(schedules)
+}
@@ -0,0 +1,240 @@
+use chrono::{Duration, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefillStatus {
+    OnSchedule,
+    /// Next refill is due within the due window
+    Due,
+    /// Overdue, within the grace period
+    Late,
+    /// Overdue past the grace period
+    Missed,
+    /// Overdue past the lost to follow up threshold
+    LostToFollowUp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefillThresholds {
+    pub due_within_days: i64,
+    pub grace_period_days: i64,
+    pub lost_to_follow_up_days: i64,
+    pub adherence_period_days: i64,
+}
+
+/// Quantity of an item dispensed to a patient on one prescription
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dispensing {
+    pub invoice_id: String,
+    pub date: NaiveDate,
+    /// Units dispensed
+    pub quantity: f64,
+    /// Units per day from the directions, None when they couldn't be interpreted
+    pub daily_dose: Option<f64>,
+    pub days_supplied: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Refill {
+    pub dispensing: Dispensing,
+    /// Supply starts when the previous supply runs out if refilled early
+    pub supply_start_date: NaiveDate,
+    pub due_date: NaiveDate,
+    /// Days after the previous refill was due, 0 for the first dispensing and early refills
+    pub days_late: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefillHistory {
+    pub refills: Vec<Refill>,
+    pub next_due_date: NaiveDate,
+    /// Days past the next due date, 0 if not yet due
+    pub days_overdue: i64,
+    /// Refills collected after the grace period
+    pub late_refills: u32,
+    /// Whole supply periods without medication between refills
+    pub missed_refills: u32,
+    /// Days supplied over days between the first and last dispensing within the adherence period,
+    /// capped at 1. None with fewer than 2 dispensings in the period
+    pub medication_possession_ratio: Option<f64>,
+    pub status: RefillStatus,
+}
+
+/// Days the quantity lasts at the daily dose, the default when the dose is not known
+pub fn days_supplied(quantity: f64, daily_dose: Option<f64>, default_days_supplied: i64) -> i64 {
+    match daily_dose {
+        Some(daily_dose) if daily_dose > 0.0 => ((quantity / daily_dose).round() as i64).max(1),
+        _ => default_days_supplied,
+    }
+}
+
+/// Refill history of a patient for an item, None without dispensings
+pub fn refill_history(
+    mut dispensings: Vec<Dispensing>,
+    thresholds: &RefillThresholds,
+    today: NaiveDate,
+) -> Option<RefillHistory> {
+    dispensings.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let mut refills: Vec<Refill> = Vec::new();
+    let mut late_refills = 0;
+    let mut missed_refills = 0;
+    for dispensing in dispensings {
+        let previous = refills.last();
+        let days_late = previous
+            .map(|previous| (dispensing.date - previous.due_date).num_days().max(0))
+            .unwrap_or_default();
+        if days_late > thresholds.grace_period_days {
+            late_refills += 1;
+        }
+        if let Some(previous) = previous {
+            missed_refills += (days_late / previous.dispensing.days_supplied.max(1)) as u32;
+        }
+
+        let supply_start_date = previous
+            .map(|previous| previous.due_date.max(dispensing.date))
+            .unwrap_or(dispensing.date);
+        refills.push(Refill {
+            supply_start_date,
+            due_date: supply_start_date + Duration::days(dispensing.days_supplied),
+            days_late,
+            dispensing,
+        });
+    }
+
+    let next_due_date = refills.last()?.due_date;
+    let days_overdue = (today - next_due_date).num_days();
+    let status = if days_overdue > thresholds.lost_to_follow_up_days {
+        RefillStatus::LostToFollowUp
+    } else if days_overdue > thresholds.grace_period_days {
+        RefillStatus::Missed
+    } else if days_overdue > 0 {
+        RefillStatus::Late
+    } else if -days_overdue <= thresholds.due_within_days {
+        RefillStatus::Due
+    } else {
+        RefillStatus::OnSchedule
+    };
+
+    let adherence_start = today - Duration::days(thresholds.adherence_period_days);
+    let medication_possession_ratio = medication_possession_ratio(
+        refills
+            .iter()
+            .map(|refill| &refill.dispensing)
+            .filter(|dispensing| dispensing.date >= adherence_start),
+    );
+
+    Some(RefillHistory {
+        refills,
+        next_due_date,
+        days_overdue: days_overdue.max(0),
+        late_refills,
+        missed_refills,
+        medication_possession_ratio,
+        status,
+    })
+}
+
+/// The last dispensing's supply is excluded as it runs past the period
+fn medication_possession_ratio<'a>(
+    dispensings: impl Iterator<Item = &'a Dispensing>,
+) -> Option<f64> {
+    let dispensings: Vec<_> = dispensings.collect();
+    let (first, last) = (dispensings.first()?, dispensings.last()?);
+    let period_days = (last.date - first.date).num_days();
+    if period_days <= 0 {
+        return None;
+    }
+
+    let days_supplied: i64 = dispensings[..dispensings.len() - 1]
+        .iter()
+        .map(|dispensing| dispensing.days_supplied)
+        .sum();
+    Some((days_supplied as f64 / period_days as f64).min(1.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const THRESHOLDS: RefillThresholds = RefillThresholds {
+        due_within_days: 7,
+        grace_period_days: 7,
+        lost_to_follow_up_days: 28,
+        adherence_period_days: 180,
+    };
+
+    fn dispensing(id: &str, date: NaiveDate, days_supplied: i64) -> Dispensing {
+        Dispensing {
+            invoice_id: id.to_string(),
+            date,
+            quantity: days_supplied as f64,
+            daily_dose: Some(1.0),
+            days_supplied,
+        }
+    }
+
+    #[test]
+    fn test_days_supplied() {
+        assert_eq!(days_supplied(60.0, Some(2.0), 30), 30);
+        assert_eq!(days_supplied(10.0, Some(3.0), 30), 3);
+        assert_eq!(days_supplied(10.0, None, 30), 30);
+        assert_eq!(days_supplied(1.0, Some(4.0), 30), 1);
+    }
+
+    #[test]
+    fn test_refill_history() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+
+        assert_eq!(refill_history(Vec::new(), &THRESHOLDS, date(1, 1)), None);
+
+        let dispensings = vec![
+            // Collected 15 days early, supply carries over to the next due date
+            dispensing("3", date(2, 25), 30),
+            dispensing("1", date(1, 1), 30),
+            // 10 days late
+            dispensing("2", date(2, 10), 30),
+            // More than a whole supply period without medication
+            dispensing("4", date(6, 1), 30),
+        ];
+        let history = refill_history(dispensings, &THRESHOLDS, date(6, 25)).unwrap();
+
+        let refills: Vec<_> = history
+            .refills
+            .iter()
+            .map(|refill| {
+                (
+                    refill.dispensing.invoice_id.as_str(),
+                    refill.due_date,
+                    refill.days_late,
+                )
+            })
+            .collect();
+        assert_eq!(
+            refills,
+            vec![
+                ("1", date(1, 31), 0),
+                ("2", date(3, 11), 10),
+                ("3", date(4, 10), 0),
+                ("4", date(7, 1), 52),
+            ]
+        );
+        assert_eq!(history.late_refills, 2);
+        assert_eq!(history.missed_refills, 1);
+        assert_eq!(history.next_due_date, date(7, 1));
+        assert_eq!(history.status, RefillStatus::Due);
+        assert_eq!(history.days_overdue, 0);
+        // 90 days supplied over 152 days
+        assert_eq!(history.medication_possession_ratio, Some(90.0 / 152.0));
+
+        let status = |today| {
+            refill_history(vec![dispensing("1", date(1, 1), 30)], &THRESHOLDS, today)
+                .unwrap()
+                .status
+        };
+        assert_eq!(status(date(1, 2)), RefillStatus::OnSchedule);
+        assert_eq!(status(date(1, 24)), RefillStatus::Due);
+        assert_eq!(status(date(2, 5)), RefillStatus::Late);
+        assert_eq!(status(date(2, 10)), RefillStatus::Missed);
+        assert_eq!(status(date(3, 1)), RefillStatus::LostToFollowUp);
+    }
+}
@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use repository::{
+    mock::{
+        mock_item_a, mock_item_b, mock_patient_b, mock_program_a, mock_store_a, MockData,
+        MockDataInserts,
+    },
+    test_db::setup_all_with_data,
+    InvoiceLineRow, InvoiceLineType, InvoiceRow, InvoiceStatus, InvoiceType, ItemDirectionRow,
+    ItemDirectionRowRepository,
+};
+
+use super::{get_refill_schedules, RefillScheduleInput, RefillStatus};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+}
+
+fn prescription(id: &str, picked: NaiveDate) -> InvoiceRow {
+    InvoiceRow {
+        id: id.to_string(),
+        name_id: mock_patient_b().id,
+        store_id: mock_store_a().id,
+        r#type: InvoiceType::Prescription,
+        status: InvoiceStatus::Picked,
+        program_id: Some(mock_program_a().id),
+        created_datetime: picked.and_hms_opt(9, 0, 0).unwrap(),
+        picked_datetime: Some(picked.and_hms_opt(10, 0, 0).unwrap()),
+        ..Default::default()
+    }
+}
+
+fn line(
+    id: &str,
+    invoice_id: &str,
+    item_id: &str,
+    packs: f64,
+    note: Option<&str>,
+) -> InvoiceLineRow {
+    InvoiceLineRow {
+        id: id.to_string(),
+        invoice_id: invoice_id.to_string(),
+        item_link_id: item_id.to_string(),
+        item_name: item_id.to_string(),
+        r#type: InvoiceLineType::StockOut,
+        pack_size: 10.0,
+        number_of_packs: packs,
+        note: note.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[actix_rt::test]
+async fn test_refill_schedules() {
+    let (_, connection, _, _) = setup_all_with_data(
+        "test_refill_schedules",
+        MockDataInserts::all(),
+        MockData {
+            invoices: vec![
+                prescription("rx1", date(1, 1)),
+                prescription("rx2", date(2, 15)),
+            ],
+            invoice_lines: vec![
+                // 60 units at 2 a day, split over two lines
+                line("rx1_a1", "rx1", &mock_item_a().id, 3.0, Some("1 tab bd")),
+                line("rx1_a2", "rx1", &mock_item_a().id, 3.0, None),
+                // 30 units at the item's default directions of 1 a day
+                line("rx1_b", "rx1", &mock_item_b().id, 3.0, None),
+                line("rx2_a", "rx2", &mock_item_a().id, 6.0, Some("1 tab bd")),
+            ],
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let direction_repo = ItemDirectionRowRepository::new(&connection);
+    for (id, directions, priority) in [("low", "1 tab tds", 2), ("high", "1 tab daily", 1)] {
+        direction_repo
+            .upsert_one(&ItemDirectionRow {
+                id: id.to_string(),
+                item_link_id: mock_item_b().id,
+                directions: directions.to_string(),
+                priority,
+            })
+            .unwrap();
+    }
+
+    let schedules = get_refill_schedules(
+        &connection,
+        &mock_store_a().id,
+        Some(&mock_patient_b().id),
+        &RefillScheduleInput::default(),
+        date(3, 10),
+    )
+    .unwrap();
+    assert_eq!(schedules.len(), 2);
+
+    let item_a = &schedules[0];
+    assert_eq!(item_a.item_id, mock_item_a().id);
+    assert_eq!(item_a.program_id, Some(mock_program_a().id));
+    let refills = &item_a.history.refills;
+    assert_eq!(refills.len(), 2);
+    assert_eq!(refills[0].dispensing.quantity, 60.0);
+    assert_eq!(refills[0].dispensing.days_supplied, 30);
+    // Due on 31 January, 15 days late
+    assert_eq!(refills[1].days_late, 15);
+    assert_eq!(item_a.history.late_refills, 1);
+    assert_eq!(item_a.history.next_due_date, date(3, 16));
+    assert_eq!(item_a.history.status, RefillStatus::Due);
+
+    let item_b = &schedules[1];
+    assert_eq!(item_b.directions, Some("1 tab daily".to_string()));
+    assert_eq!(item_b.history.next_due_date, date(1, 31));
+    assert_eq!(item_b.history.days_overdue, 39);
+    assert_eq!(item_b.history.status, RefillStatus::LostToFollowUp);
+
+    let other_program = get_refill_schedules(
+        &connection,
+        &mock_store_a().id,
+        Some(&mock_patient_b().id),
+        &RefillScheduleInput {
+            program_id: Some("other".to_string()),
+            ..Default::default()
+        },
+        date(3, 10),
+    )
+    .unwrap();
+    assert!(other_program.is_empty());
+}
@@ -71,31 +71,7 @@ fn generate_invoice_and_lines(
     new_stock_line: &StockLineRow,
 ) -> Result<(InvoiceRow, Vec<InvoiceLineRow>), RepositoryError> {
     let connection = &ctx.connection;
-
-    let repack_name = NameRowRepository::new(connection)
-        .find_one_by_code(REPACK_NAME_CODE)?
-        .ok_or(RepositoryError::NotFound)?;
-
-    let currency = CurrencyRepository::new(connection)
-        .query_by_filter(CurrencyFilter::new().is_home_currency(true))?
-        .pop()
-        .ok_or(RepositoryError::NotFound)?;
-
-    let invoice = InvoiceRow {
-        id: uuid(),
-        name_id: repack_name.id,
-        store_id: ctx.store_id.clone(),
-        user_id: Some(ctx.user_id.clone()),
-        invoice_number: next_number(connection, &NumberRowType::Repack, &ctx.store_id)?,
-        r#type: InvoiceType::Repack,
-        status: InvoiceStatus::Verified,
-        on_hold: false,
-        created_datetime: Utc::now().naive_utc(),
-        verified_datetime: Some(Utc::now().naive_utc()),
-        currency_id: Some(currency.currency_row.id),
-        currency_rate: 1.0,
-        ..Default::default()
-    };
+    let invoice = generate_repack_invoice(ctx)?;
 
     let mut invoice_lines = Vec::new();
 
@@ -146,6 +122,36 @@ fn generate_invoice_and_lines(
     Ok((invoice, invoice_lines))
 }
 
+/// Verified repack invoice, stock movements are recorded as lines on it
+pub fn generate_repack_invoice(ctx: &ServiceContext) -> Result<InvoiceRow, RepositoryError> {
+    let connection = &ctx.connection;
+
+    let repack_name = NameRowRepository::new(connection)
+        .find_one_by_code(REPACK_NAME_CODE)?
+        .ok_or(RepositoryError::NotFound)?;
+
+    let currency = CurrencyRepository::new(connection)
+        .query_by_filter(CurrencyFilter::new().is_home_currency(true))?
+        .pop()
+        .ok_or(RepositoryError::NotFound)?;
+
+    Ok(InvoiceRow {
+        id: uuid(),
+        name_id: repack_name.id,
+        store_id: ctx.store_id.clone(),
+        user_id: Some(ctx.user_id.clone()),
+        invoice_number: next_number(connection, &NumberRowType::Repack, &ctx.store_id)?,
+        r#type: InvoiceType::Repack,
+        status: InvoiceStatus::Verified,
+        on_hold: false,
+        created_datetime: Utc::now().naive_utc(),
+        verified_datetime: Some(Utc::now().naive_utc()),
+        currency_id: Some(currency.currency_row.id),
+        currency_rate: 1.0,
+        ..Default::default()
+    })
+}
+
 fn generate_new_stock_lines(stock_line: &StockLineRow, input: &InsertRepack) -> StockLineJob {
     let total_number_of_packs = stock_line.total_number_of_packs - input.number_of_packs;
 
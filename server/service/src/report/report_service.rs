@@ -586,6 +586,7 @@ fn transform_data_boajs(data: ReportData, convert_data: String) -> Result<Report
         data,
         vec!["convert_data"],
         &BASE64_STANDARD.decode(convert_data).unwrap(),
+        None,
     )
 }
 
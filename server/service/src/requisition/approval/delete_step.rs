@@ -0,0 +1,36 @@
+use repository::{RepositoryError, RequisitionApprovalStepRowRepository};
+
+use crate::{service_provider::ServiceContext, sync::CentralServerConfig};
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteRequisitionApprovalStepError {
+    NotCentralServer,
+    DatabaseError(RepositoryError),
+}
+
+pub struct DeleteRequisitionApprovalStep {
+    pub id: String,
+}
+
+pub fn delete_requisition_approval_step(
+    ctx: &ServiceContext,
+    input: DeleteRequisitionApprovalStep,
+) -> Result<String, DeleteRequisitionApprovalStepError> {
+    if !CentralServerConfig::is_central_server() {
+        return Err(DeleteRequisitionApprovalStepError::NotCentralServer);
+    }
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            // Soft delete, approvals already recorded against the step are kept
+            RequisitionApprovalStepRowRepository::new(connection).mark_deleted(&input.id)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(input.id)
+}
+
+impl From<RepositoryError> for DeleteRequisitionApprovalStepError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteRequisitionApprovalStepError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,124 @@
+use repository::{
+    RepositoryError, RequisitionApprovalDecision, RequisitionApprovalRowRepository,
+    RequisitionApprovalStepRow, RequisitionApprovalStepRowRepository, RequisitionLineRow,
+    RequisitionRow, StorageConnection, UserAccountRowRepository,
+};
+
+use crate::email::enqueue::{enqueue_email, EnqueueEmailData};
+
+mod delete_step;
+pub use delete_step::*;
+
+mod upsert_step;
+pub use upsert_step::*;
+
+mod query;
+pub use query::*;
+
+/// Approval chain steps that apply to response requisition, a step applies when it has no
+/// thresholds or when total requested quantity or total requested value reaches a threshold
+pub fn get_applicable_approval_steps(
+    connection: &StorageConnection,
+    requisition: &RequisitionRow,
+    lines: &[RequisitionLineRow],
+) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+    let steps = RequisitionApprovalStepRowRepository::new(connection)
+        .find_active_by_store_and_program(
+            &requisition.store_id,
+            requisition.program_id.as_deref(),
+        )?;
+
+    let total_quantity: f64 = lines.iter().map(|line| line.requested_quantity).sum();
+    let total_value: f64 = lines
+        .iter()
+        .map(|line| line.requested_quantity * line.price_per_unit.unwrap_or(0.0))
+        .sum();
+
+    let result = steps
+        .into_iter()
+        .filter(
+            |step| match (step.threshold_quantity, step.threshold_value) {
+                (None, None) => true,
+                (threshold_quantity, threshold_value) => {
+                    threshold_quantity.is_some_and(|threshold| total_quantity >= threshold)
+                        || threshold_value.is_some_and(|threshold| total_value >= threshold)
+                }
+            },
+        )
+        .collect();
+
+    Ok(result)
+}
+
+/// First applicable step that has not been approved yet for the requisition
+pub fn get_next_approval_step(
+    connection: &StorageConnection,
+    requisition: &RequisitionRow,
+    lines: &[RequisitionLineRow],
+) -> Result<Option<RequisitionApprovalStepRow>, RepositoryError> {
+    // Steps of different scopes (e.g. all stores and this store) can share a step number
+    let approved_step_ids: Vec<String> = RequisitionApprovalRowRepository::new(connection)
+        .find_many_by_requisition_id(&requisition.id)?
+        .into_iter()
+        .filter(|approval| approval.decision == RequisitionApprovalDecision::Approved)
+        .map(|approval| approval.step_id)
+        .collect();
+
+    let next_step = get_applicable_approval_steps(connection, requisition, lines)?
+        .into_iter()
+        .find(|step| !approved_step_ids.contains(&step.id));
+
+    Ok(next_step)
+}
+
+/// Queues email to approver of the step, approvers without email address are skipped
+pub fn notify_approver(
+    connection: &StorageConnection,
+    step: &RequisitionApprovalStepRow,
+    requisition: &RequisitionRow,
+) -> Result<(), RepositoryError> {
+    let Some(approver) =
+        UserAccountRowRepository::new(connection).find_one_by_id(&step.approver_user_id)?
+    else {
+        log::warn!(
+            "Approver {} for requisition approval step {} not found",
+            step.approver_user_id,
+            step.id
+        );
+        return Ok(());
+    };
+    let Some(to_address) = approver.email.filter(|email| !email.is_empty()) else {
+        return Ok(());
+    };
+
+    let subject = format!(
+        "Requisition {} is waiting for your approval",
+        requisition.requisition_number
+    );
+    let text_body = format!(
+        "Requisition {} requires approval for step {}.\nPlease log in to Open mSupply to approve or deny it.",
+        requisition.requisition_number, step.step_number
+    );
+    let html_body = format!(
+        "<p>Requisition {} requires approval for step {}.</p><p>Please log in to Open mSupply to approve or deny it.</p>",
+        requisition.requisition_number, step.step_number
+    );
+
+    if let Err(e) = enqueue_email(
+        connection,
+        EnqueueEmailData {
+            to_address,
+            subject,
+            html_body,
+            text_body,
+        },
+    ) {
+        log::error!(
+            "Error queueing approval email for requisition {}: {:?}",
+            requisition.id,
+            e
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use repository::{
+    requisition_row::{RequisitionStatus, RequisitionType},
+    ApprovalStatusType, EqualFilter, RepositoryError, Requisition, RequisitionApprovalStepRow,
+    RequisitionApprovalStepRowRepository, RequisitionFilter, RequisitionRepository,
+};
+
+use super::get_next_approval_step;
+use crate::{requisition::common::get_lines_for_requisition, service_provider::ServiceContext};
+
+/// Approval chain steps configured for the supplying store, including steps for all stores
+pub fn get_requisition_approval_steps(
+    ctx: &ServiceContext,
+    store_id: &str,
+) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+    RequisitionApprovalStepRowRepository::new(&ctx.connection).find_active_by_store(store_id)
+}
+
+/// Response requisitions in the store where current user is the approver of the next step
+pub fn get_requisitions_pending_my_approval(
+    ctx: &ServiceContext,
+    store_id: &str,
+) -> Result<Vec<Requisition>, RepositoryError> {
+    let connection = &ctx.connection;
+    let requisitions = RequisitionRepository::new(connection).query_by_filter(
+        RequisitionFilter::new()
+            .store_id(EqualFilter::equal_to(store_id.to_string()))
+            .r#type(RequisitionType::Response.equal_to())
+            .status(RequisitionStatus::New.equal_to()),
+    )?;
+
+    let mut result = Vec::new();
+    for requisition in requisitions {
+        let row = &requisition.requisition_row;
+        if row.approval_status != Some(ApprovalStatusType::Pending) {
+            continue;
+        }
+
+        let lines: Vec<_> = get_lines_for_requisition(connection, &row.id)?
+            .into_iter()
+            .map(|line| line.requisition_line_row)
+            .collect();
+        let next_step = get_next_approval_step(connection, row, &lines)?;
+        if next_step.is_some_and(|step| step.approver_user_id == ctx.user_id) {
+            result.push(requisition);
+        }
+    }
+
+    Ok(result)
+}
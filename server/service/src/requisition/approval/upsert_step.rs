@@ -0,0 +1,214 @@
+use repository::{
+    ProgramRowRepository, RepositoryError, RequisitionApprovalStepRow,
+    RequisitionApprovalStepRowRepository, StorageConnection, StoreRowRepository,
+    UserAccountRowRepository,
+};
+
+use crate::{service_provider::ServiceContext, sync::CentralServerConfig};
+
+#[derive(PartialEq, Debug)]
+pub enum UpsertRequisitionApprovalStepError {
+    /// Approval chains can only be configured on the central server
+    NotCentralServer,
+    StoreDoesNotExist,
+    ProgramDoesNotExist,
+    ApproverDoesNotExist,
+    /// Step numbers start at 1
+    StepNumberMustBePositive,
+    /// Another active step of the same store and program scope has this step number
+    StepNumberAlreadyExists,
+    ThresholdCannotBeNegative,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct UpsertRequisitionApprovalStep {
+    pub id: String,
+    pub store_id: Option<String>,
+    pub program_id: Option<String>,
+    pub step_number: i32,
+    pub approver_user_id: String,
+    pub threshold_quantity: Option<f64>,
+    pub threshold_value: Option<f64>,
+}
+
+pub fn upsert_requisition_approval_step(
+    ctx: &ServiceContext,
+    input: UpsertRequisitionApprovalStep,
+) -> Result<RequisitionApprovalStepRow, UpsertRequisitionApprovalStepError> {
+    let row = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+            let new_row = generate(input.clone());
+            let repo = RequisitionApprovalStepRowRepository::new(connection);
+
+            repo.upsert_one(&new_row)?;
+
+            repo.find_one_by_id(&new_row.id)?
+                .ok_or(UpsertRequisitionApprovalStepError::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(row)
+}
+
+impl From<RepositoryError> for UpsertRequisitionApprovalStepError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertRequisitionApprovalStepError::DatabaseError(error)
+    }
+}
+
+fn generate(
+    UpsertRequisitionApprovalStep {
+        id,
+        store_id,
+        program_id,
+        step_number,
+        approver_user_id,
+        threshold_quantity,
+        threshold_value,
+    }: UpsertRequisitionApprovalStep,
+) -> RequisitionApprovalStepRow {
+    RequisitionApprovalStepRow {
+        id,
+        store_id,
+        program_id,
+        step_number,
+        approver_user_id,
+        threshold_quantity,
+        threshold_value,
+        deleted_datetime: None,
+    }
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &UpsertRequisitionApprovalStep,
+) -> Result<(), UpsertRequisitionApprovalStepError> {
+    use UpsertRequisitionApprovalStepError as Error;
+
+    if !CentralServerConfig::is_central_server() {
+        return Err(Error::NotCentralServer);
+    }
+
+    if input.step_number < 1 {
+        return Err(Error::StepNumberMustBePositive);
+    }
+
+    let step_number_exists = RequisitionApprovalStepRowRepository::new(connection)
+        .find_active_by_scope(input.store_id.as_deref(), input.program_id.as_deref())?
+        .into_iter()
+        .any(|step| step.step_number == input.step_number && step.id != input.id);
+    if step_number_exists {
+        return Err(Error::StepNumberAlreadyExists);
+    }
+
+    if input
+        .threshold_quantity
+        .is_some_and(|threshold| threshold < 0.0)
+        || input
+            .threshold_value
+            .is_some_and(|threshold| threshold < 0.0)
+    {
+        return Err(Error::ThresholdCannotBeNegative);
+    }
+
+    if let Some(store_id) = &input.store_id {
+        if StoreRowRepository::new(connection)
+            .find_one_by_id(store_id)?
+            .is_none()
+        {
+            return Err(Error::StoreDoesNotExist);
+        }
+    }
+
+    if let Some(program_id) = &input.program_id {
+        if !ProgramRowRepository::new(connection).check_exists_by_id(program_id)? {
+            return Err(Error::ProgramDoesNotExist);
+        }
+    }
+
+    if UserAccountRowRepository::new(connection)
+        .find_one_by_id(&input.approver_user_id)?
+        .is_none()
+    {
+        return Err(Error::ApproverDoesNotExist);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_store_a, mock_user_account_a, MockDataInserts},
+        test_db::setup_all,
+    };
+
+    use crate::{
+        requisition::approval::{
+            UpsertRequisitionApprovalStep, UpsertRequisitionApprovalStepError as ServiceError,
+        },
+        service_provider::ServiceProvider,
+        sync::test_util_set_is_central_server,
+    };
+
+    #[actix_rt::test]
+    async fn upsert_requisition_approval_step() {
+        let (_, _, connection_manager, _) =
+            setup_all("upsert_requisition_approval_step", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.basic_context().unwrap();
+        let service = service_provider.requisition_service;
+        test_util_set_is_central_server(true);
+
+        let step = UpsertRequisitionApprovalStep {
+            id: "step_1".to_string(),
+            store_id: Some(mock_store_a().id),
+            step_number: 1,
+            approver_user_id: mock_user_account_a().id,
+            ..Default::default()
+        };
+        service
+            .upsert_requisition_approval_step(&context, step.clone())
+            .unwrap();
+        // Updating the same step keeps its step number
+        service
+            .upsert_requisition_approval_step(
+                &context,
+                UpsertRequisitionApprovalStep {
+                    threshold_quantity: Some(10.0),
+                    ..step.clone()
+                },
+            )
+            .unwrap();
+
+        // StepNumberAlreadyExists
+        assert_eq!(
+            service.upsert_requisition_approval_step(
+                &context,
+                UpsertRequisitionApprovalStep {
+                    id: "step_2".to_string(),
+                    ..step.clone()
+                },
+            ),
+            Err(ServiceError::StepNumberAlreadyExists)
+        );
+
+        // Same step number in another scope
+        service
+            .upsert_requisition_approval_step(
+                &context,
+                UpsertRequisitionApprovalStep {
+                    id: "step_2".to_string(),
+                    store_id: None,
+                    ..step
+                },
+            )
+            .unwrap();
+
+        test_util_set_is_central_server(false);
+    }
+}
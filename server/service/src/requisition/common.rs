@@ -1,17 +1,20 @@
 use std::collections::{HashMap, HashSet};
 
+use chrono::{Duration, NaiveTime};
 use repository::{
     requisition_row::RequisitionRow, RepositoryError, RequisitionLine, RequisitionLineFilter,
     RequisitionLineRepository, RequisitionRowRepository, StorageConnection,
 };
 use repository::{
-    ApprovalStatusType, EqualFilter, IndicatorColumnRow, IndicatorColumnRowRepository,
-    IndicatorLineRow, IndicatorLineRowRepository, IndicatorValueType, MasterList, MasterListFilter,
-    MasterListRepository, ProgramFilter, ProgramIndicatorFilter, ProgramIndicatorRepository,
-    ProgramRepository, ProgramRequisitionOrderTypeRowRepository, ProgramRequisitionSettingsFilter,
-    ProgramRequisitionSettingsRepository, Requisition, RequisitionFilter, RequisitionRepository,
-    RequisitionType,
+    ApprovalStatusType, DatetimeFilter, EqualFilter, IndicatorColumnRow,
+    IndicatorColumnRowRepository, IndicatorLineRow, IndicatorLineRowRepository, IndicatorValueType,
+    MasterList, MasterListFilter, MasterListRepository, PeriodRowRepository, ProgramFilter,
+    ProgramIndicatorFilter, ProgramIndicatorRepository, ProgramRepository,
+    ProgramRequisitionOrderTypeRow, ProgramRequisitionOrderTypeRowRepository,
+    ProgramRequisitionSettingsFilter, ProgramRequisitionSettingsRepository, Requisition,
+    RequisitionFilter, RequisitionRepository, RequisitionType,
 };
+use util::date_now;
 
 use crate::preference::{Preference, ShowIndicativePriceInRequisitions};
 
@@ -142,6 +145,49 @@ pub fn check_exceeded_max_orders_for_period(
     }
 }
 
+/// Length of the emergency order window when the schedule has no periods started yet
+const DEFAULT_EMERGENCY_ORDER_WINDOW_DAYS: i64 = 30;
+
+/// Emergency orders raised without a period are not tied to the program schedule,
+/// instead they are limited by `max_order_per_period` of the emergency order type
+/// counted by created date over a rolling window ending today. The window is as long as
+/// the latest schedule period started (covering today, or the last one if the schedule
+/// has run out), so orders raised at the end of a period still count in the next one
+pub fn check_exceeded_max_emergency_orders(
+    connection: &StorageConnection,
+    program_id: &str,
+    period_schedule_id: &str,
+    order_type: &ProgramRequisitionOrderTypeRow,
+    store_id: &str,
+) -> Result<bool, RepositoryError> {
+    let today = date_now();
+    let window_days = PeriodRowRepository::new(connection)
+        .find_many_by_program_schedule_ids(vec![period_schedule_id])?
+        .into_iter()
+        .filter(|period| period.start_date <= today)
+        .max_by_key(|period| period.start_date)
+        .map(|period| (period.end_date - period.start_date).num_days() + 1)
+        .unwrap_or(DEFAULT_EMERGENCY_ORDER_WINDOW_DAYS);
+    let window_start = today - Duration::days(window_days.max(1) - 1);
+
+    let filter = RequisitionFilter::new()
+        .program_id(EqualFilter::equal_to(program_id.to_string()))
+        .order_type(EqualFilter::equal_to(order_type.name.to_owned()))
+        .store_id(EqualFilter::equal_to(store_id.to_string()))
+        .r#type(RequisitionType::Request.equal_to())
+        .is_emergency(true)
+        .created_datetime(DatetimeFilter::date_range(
+            window_start.and_time(NaiveTime::MIN),
+            today
+                .and_hms_opt(23, 59, 59)
+                .unwrap_or(today.and_time(NaiveTime::MIN)),
+        ));
+
+    let current_orders = RequisitionRepository::new(connection).count(Some(filter))?;
+
+    Ok(current_orders >= i64::from(order_type.max_order_per_period))
+}
+
 /// Expand `program_indicator_ids` to include indicators from all programs
 /// sharing the same `elmis_code`. Deployments can split a single
 /// logical program across multiple programs per facility level (customer vs
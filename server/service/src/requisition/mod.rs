@@ -1,5 +1,5 @@
 use self::{
-    query::{get_requisition, get_requisition_by_number, get_requisitions},
+    query::{get_requisition, get_requisition_by_number, get_requisitions, get_requisitions_after},
     request_requisition::{
         add_from_master_list, batch_request_requisition, delete_request_requisition,
         insert_program_request_requisition, insert_request_requisition, update_request_requisition,
@@ -32,6 +32,13 @@ use crate::{
     },
     service_provider::ServiceContext,
 };
+use approval::{
+    delete_requisition_approval_step, get_requisition_approval_steps,
+    get_requisitions_pending_my_approval, upsert_requisition_approval_step,
+    DeleteRequisitionApprovalStep, DeleteRequisitionApprovalStepError,
+    UpsertRequisitionApprovalStep, UpsertRequisitionApprovalStepError,
+};
+use chrono::NaiveDateTime;
 use program_settings::{
     customer_program_settings::{
         get_program_requisition_settings_by_customer, prepare::CustomerProgramRequisitionSetting,
@@ -40,15 +47,17 @@ use program_settings::{
     supplier_program_settings::SupplierProgramSettings,
 };
 use repository::{
-    requisition_row::RequisitionType, Invoice, PaginationOption, RepositoryError, Requisition,
-    RequisitionFilter, RequisitionLine, RequisitionSort,
+    requisition_row::RequisitionType, Invoice, Keyset, PaginationOption, RepositoryError,
+    Requisition, RequisitionApprovalStepRow, RequisitionFilter, RequisitionLine, RequisitionSort,
 };
 use request_requisition::{get_indicator_information, CustomerIndicatorInformation};
 use response_requisition::{
-    batch_response_requisition, delete_response_requisition, BatchResponseRequisition,
+    approve_response_requisition, batch_response_requisition, delete_response_requisition,
+    ApproveResponseRequisition, ApproveResponseRequisitionError, BatchResponseRequisition,
     BatchResponseRequisitionResult, DeleteResponseRequisition, DeleteResponseRequisitionError,
 };
 
+pub mod approval;
 pub mod common;
 pub mod indicator_value;
 pub mod program_indicator;
@@ -70,6 +79,17 @@ pub trait RequisitionServiceTrait: Sync + Send {
         get_requisitions(ctx, store_id_option, pagination, filter, sort)
     }
 
+    fn get_requisitions_after(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        after: Option<Keyset<NaiveDateTime>>,
+        limit: u32,
+        filter: Option<RequisitionFilter>,
+    ) -> Result<ListResult<Requisition>, ListError> {
+        get_requisitions_after(ctx, store_id, after, limit, filter)
+    }
+
     fn get_requisition(
         &self,
         ctx: &ServiceContext,
@@ -258,6 +278,46 @@ pub trait RequisitionServiceTrait: Sync + Send {
     ) -> Result<Vec<CustomerIndicatorInformation>, RepositoryError> {
         get_indicator_information(ctx, line_ids, store_id, period_id)
     }
+
+    fn approve_response_requisition(
+        &self,
+        ctx: &ServiceContext,
+        input: ApproveResponseRequisition,
+    ) -> Result<Requisition, ApproveResponseRequisitionError> {
+        approve_response_requisition(ctx, input)
+    }
+
+    fn upsert_requisition_approval_step(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertRequisitionApprovalStep,
+    ) -> Result<RequisitionApprovalStepRow, UpsertRequisitionApprovalStepError> {
+        upsert_requisition_approval_step(ctx, input)
+    }
+
+    fn delete_requisition_approval_step(
+        &self,
+        ctx: &ServiceContext,
+        input: DeleteRequisitionApprovalStep,
+    ) -> Result<String, DeleteRequisitionApprovalStepError> {
+        delete_requisition_approval_step(ctx, input)
+    }
+
+    fn get_requisition_approval_steps(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<RequisitionApprovalStepRow>, RepositoryError> {
+        get_requisition_approval_steps(ctx, store_id)
+    }
+
+    fn get_requisitions_pending_my_approval(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<Requisition>, RepositoryError> {
+        get_requisitions_pending_my_approval(ctx, store_id)
+    }
 }
 pub struct RequisitionService {}
 impl RequisitionServiceTrait for RequisitionService {}
@@ -1,8 +1,9 @@
+use chrono::NaiveDateTime;
 use repository::{
     requisition_row::RequisitionType, RepositoryError, Requisition, RequisitionFilter,
     RequisitionRepository, RequisitionSort,
 };
-use repository::{EqualFilter, PaginationOption};
+use repository::{EqualFilter, Keyset, PaginationOption};
 
 use crate::{
     get_pagination_or_default, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
@@ -27,6 +28,26 @@ pub fn get_requisitions(
     })
 }
 
+/// Page of the store's requisitions after `after` by created datetime, for keyset (cursor)
+/// paging. Reads `limit` rows
+pub fn get_requisitions_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<NaiveDateTime>>,
+    limit: u32,
+    filter: Option<RequisitionFilter>,
+) -> Result<ListResult<Requisition>, ListError> {
+    let repository = RequisitionRepository::new(&ctx.connection);
+
+    let mut filter = filter.unwrap_or_default();
+    filter.store_id = Some(EqualFilter::equal_to(store_id.to_string()));
+
+    Ok(ListResult {
+        rows: repository.query_after(Some(filter.clone()), after, limit)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
+
 pub fn get_requisition(
     ctx: &ServiceContext,
     store_id_option: Option<&str>,
@@ -1,6 +1,9 @@
+use crate::demand_forecast::{forecast_demand, DemandForecastInput};
 use crate::item_stats::get_item_stats;
 use crate::location::query::get_available_volume_by_location_type;
-use crate::preference::preferences::DisplayPopulationBasedForecasting;
+use crate::preference::preferences::{
+    DisplayPopulationBasedForecasting, UseDemandForecastForSuggestedQuantity,
+};
 use crate::preference::types::Preference;
 use crate::pricing::item_price::{get_pricing_for_items, ItemPriceLookup};
 use crate::requisition::common::get_indicative_price_pref;
@@ -8,7 +11,7 @@ use crate::requisition::request_requisition::generate_population_forecast::calcu
 use crate::service_provider::ServiceContext;
 use crate::PluginOrRepositoryError;
 use chrono::{NaiveDate, Utc};
-use repository::{RequisitionLineRow, RequisitionRow};
+use repository::{RepositoryError, RequisitionLineRow, RequisitionRow};
 use util::uuid::uuid;
 
 pub struct GenerateSuggestedQuantity {
@@ -82,6 +85,37 @@ pub fn generate_requisition_lines(
         std::collections::HashMap::new()
     };
 
+    // Forecast demand over the months the requisition is stocking up for
+    let forecast_months = (requisition_row.max_months_of_stock.ceil() as u32).clamp(1, 36);
+    let use_demand_forecast = UseDemandForecastForSuggestedQuantity
+        .load(&ctx.connection, Some(store_id.to_string()))
+        .map_err(|e| {
+            RepositoryError::as_db_error(
+                "Could not load use demand forecast for suggested quantity preference",
+                e,
+            )
+        })?;
+    let demand_forecast = if use_demand_forecast {
+        forecast_demand(
+            &ctx.connection,
+            store_id,
+            &DemandForecastInput {
+                item_ids: item_ids.clone(),
+                horizon_months: forecast_months,
+                ..Default::default()
+            },
+            &Utc::now().date_naive(),
+        )?
+        .into_iter()
+        .map(|forecast| {
+            let demand = forecast.average_monthly_demand(forecast_months as usize);
+            (forecast.item_id, demand)
+        })
+        .collect()
+    } else {
+        std::collections::HashMap::new()
+    };
+
     let lines = item_stats_rows
         .into_iter()
         .map(
@@ -99,7 +133,10 @@ pub fn generate_requisition_lines(
                     }
                     (true, Some(_)) => 0.0,
                     _ => generate_suggested_quantity(GenerateSuggestedQuantity {
-                        average_monthly_consumption,
+                        average_monthly_consumption: demand_forecast
+                            .get(&item_stats.item_id)
+                            .copied()
+                            .unwrap_or(average_monthly_consumption),
                         available_stock_on_hand,
                         min_months_of_stock: requisition_row.min_months_of_stock,
                         max_months_of_stock: requisition_row.max_months_of_stock,
@@ -7,9 +7,9 @@ use crate::{
     number::next_number,
     requisition::{
         common::{
-            check_exceeded_max_orders_for_period, check_requisition_row_exists,
-            default_indicator_value, indicator_value_type, related_indicator_schema,
-            CheckExceededOrdersForPeriod,
+            check_exceeded_max_emergency_orders, check_exceeded_max_orders_for_period,
+            check_requisition_row_exists, default_indicator_value, indicator_value_type,
+            related_indicator_schema, CheckExceededOrdersForPeriod,
         },
         program_indicator::query::{program_indicators, ProgramIndicator},
         program_settings::get_supplier_program_requisition_settings,
@@ -24,11 +24,12 @@ use repository::{
     indicator_value::{IndicatorValueFilter, IndicatorValueRepository},
     requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
     ActivityLogType, EqualFilter, IndicatorValueRow, IndicatorValueRowRepository,
-    IndicatorValueType, MasterListLineFilter, MasterListLineRepository, NameFilter,
-    NameRepository, NumberRowType, Pagination, PeriodRowRepository, PluginDataRowRepository,
-    ProgramIndicatorFilter, ProgramRequisitionOrderTypeRow, ProgramRow, RepositoryError,
-    Requisition, RequisitionLineRow, RequisitionLineRowRepository, RequisitionRowRepository,
-    StorageConnection, StoreFilter, StoreRepository,
+    IndicatorValueType, MasterListLineFilter, MasterListLineRepository, NameFilter, NameRepository,
+    NumberRowType, Pagination, PeriodRowRepository, PluginDataRowRepository,
+    ProgramIndicatorFilter, ProgramRequisitionOrderTypeRow, ProgramRow, ReasonOptionFilter,
+    ReasonOptionRepository, ReasonOptionType, RepositoryError, Requisition, RequisitionLineRow,
+    RequisitionLineRowRepository, RequisitionRowRepository, StorageConnection, StoreFilter,
+    StoreRepository,
 };
 use util::uuid::uuid;
 
@@ -42,6 +43,11 @@ pub enum InsertProgramRequestRequisitionError {
     // Program validation
     ProgramOrderTypeDoesNotExist,
     MaxOrdersReachedForPeriod,
+    /// Only emergency orders can be raised outside of the program period schedule
+    PeriodNotProvided,
+    // Emergency reason validation
+    EmergencyReasonNotProvided,
+    EmergencyReasonDoesNotExist,
     // Internal
     NewlyCreatedRequisitionDoesNotExist,
     PluginError(PluginError),
@@ -57,7 +63,9 @@ pub struct InsertProgramRequestRequisition {
     pub comment: Option<String>,
     pub expected_delivery_date: Option<NaiveDate>,
     pub program_order_type_id: String,
-    pub period_id: String,
+    /// Optional for emergency order types
+    pub period_id: Option<String>,
+    pub emergency_reason_option_id: Option<String>,
 }
 
 type OutError = InsertProgramRequestRequisitionError;
@@ -139,10 +147,6 @@ fn validate(
         })
         .ok_or(OutError::ProgramOrderTypeDoesNotExist)?;
 
-    if order_type.available_periods.is_empty() {
-        return Err(OutError::MaxOrdersReachedForPeriod);
-    }
-
     if !program_setting
         .suppliers
         .iter()
@@ -151,21 +155,47 @@ fn validate(
         return Err(OutError::SupplierNotValid);
     }
 
-    if check_exceeded_max_orders_for_period(
-        connection,
-        CheckExceededOrdersForPeriod {
-            program_id: &program_setting.program_requisition_settings.program_row.id,
-            period_id: &input.period_id,
-            program_order_type_id: &input.program_order_type_id,
-            max_orders_per_period: i64::from(order_type.order_type.max_order_per_period),
-            requisition_type: RequisitionType::Request,
-            store_id: &ctx.store_id,
-            other_party_id: None,
-        },
-    )? {
+    let program_id = &program_setting.program_requisition_settings.program_row.id;
+
+    let exceeded_max_orders = match &input.period_id {
+        Some(period_id) => {
+            order_type.available_periods.is_empty()
+                || check_exceeded_max_orders_for_period(
+                    connection,
+                    CheckExceededOrdersForPeriod {
+                        program_id,
+                        period_id,
+                        program_order_type_id: &input.program_order_type_id,
+                        max_orders_per_period: i64::from(
+                            order_type.order_type.max_order_per_period,
+                        ),
+                        requisition_type: RequisitionType::Request,
+                        store_id: &ctx.store_id,
+                        other_party_id: None,
+                    },
+                )?
+        }
+        None if order_type.order_type.is_emergency => check_exceeded_max_emergency_orders(
+            connection,
+            program_id,
+            &program_setting
+                .program_requisition_settings
+                .program_settings_row
+                .period_schedule_id,
+            &order_type.order_type,
+            &ctx.store_id,
+        )?,
+        None => return Err(OutError::PeriodNotProvided),
+    };
+
+    if exceeded_max_orders {
         return Err(OutError::MaxOrdersReachedForPeriod);
     }
 
+    if order_type.order_type.is_emergency {
+        check_emergency_reason(connection, &input.emergency_reason_option_id)?;
+    }
+
     Ok((
         program_setting
             .program_requisition_settings
@@ -181,6 +211,36 @@ pub(super) struct GenerateResult {
     pub(crate) indicator_values: Vec<IndicatorValueRow>,
 }
 
+/// Reason is required for emergency orders when emergency requisition reasons are configured
+fn check_emergency_reason(
+    connection: &StorageConnection,
+    emergency_reason_option_id: &Option<String>,
+) -> Result<(), OutError> {
+    let reason_options = ReasonOptionRepository::new(connection).query_by_filter(
+        ReasonOptionFilter::new()
+            .r#type(ReasonOptionType::EmergencyRequisition.equal_to())
+            .is_active(true),
+    )?;
+
+    match emergency_reason_option_id {
+        Some(reason_id) => {
+            if !reason_options
+                .iter()
+                .any(|reason| &reason.reason_option_row.id == reason_id)
+            {
+                return Err(OutError::EmergencyReasonDoesNotExist);
+            }
+        }
+        None => {
+            if !reason_options.is_empty() {
+                return Err(OutError::EmergencyReasonNotProvided);
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn generate(
     ctx: &ServiceContext,
     program: ProgramRow,
@@ -194,6 +254,7 @@ fn generate(
         expected_delivery_date,
         program_order_type_id: _,
         period_id,
+        emergency_reason_option_id,
     }: InsertProgramRequestRequisition,
 ) -> Result<GenerateResult, PluginOrRepositoryError> {
     let connection = &ctx.connection;
@@ -218,9 +279,10 @@ fn generate(
         max_months_of_stock: order_type.max_mos,
         min_months_of_stock: order_type.threshold_mos,
         program_id: Some(program.id.clone()),
-        period_id: Some(period_id.clone()),
+        period_id: period_id.clone(),
         order_type: Some(order_type.name),
         is_emergency: order_type.is_emergency,
+        emergency_reason_option_id: emergency_reason_option_id.filter(|_| order_type.is_emergency),
         // Default
         sent_datetime: None,
         approval_status: None,
@@ -242,17 +304,24 @@ fn generate(
         .map(|line| line.item_id)
         .collect();
 
-    let period = PeriodRowRepository::new(connection)
-        .find_one_by_id(&period_id)
-        .unwrap()
-        .unwrap_or_default();
+    // Emergency orders without a period use current stock and consumption
+    let period_end = match &period_id {
+        Some(period_id) => Some(
+            PeriodRowRepository::new(connection)
+                .find_one_by_id(period_id)
+                .unwrap()
+                .unwrap_or_default()
+                .end_date,
+        ),
+        None => None,
+    };
 
     let requisition_lines = generate_requisition_lines(
         ctx,
         &ctx.store_id,
         &requisition,
         program_item_ids,
-        Some(period.end_date),
+        period_end,
     )?;
 
     let program_indicators = program_indicators(
@@ -272,16 +341,15 @@ fn generate(
         .name_row
         .id;
 
-    let indicator_values = if !order_type.is_emergency {
-        generate_program_indicator_values(
+    let indicator_values = match (order_type.is_emergency, &period_id) {
+        (false, Some(period_id)) => generate_program_indicator_values(
             connection,
             &ctx.store_id,
-            &period_id,
+            period_id,
             program_indicators,
             &customer_name_id,
-        )?
-    } else {
-        vec![]
+        )?,
+        _ => vec![],
     };
 
     Ok(GenerateResult {
@@ -415,16 +483,20 @@ mod test_insert {
         },
         service_provider::ServiceProvider,
     };
+    use chrono::Duration;
     use repository::{
         mock::{
-            mock_name_store_b, mock_period, mock_program_a, mock_program_order_types_a,
+            mock_name_store_b, mock_period, mock_period_schedule_1, mock_program_a,
+            mock_program_order_types_a, mock_program_requisition_setting_a,
             mock_request_draft_requisition, mock_user_account_a, program_master_list_store,
             MockData, MockDataInserts,
         },
         test_db::{setup_all, setup_all_with_data},
-        EqualFilter, NameRow, RequisitionLineFilter, RequisitionLineRepository,
-        RequisitionRowRepository,
+        EqualFilter, NameRow, PeriodRow, ProgramRequisitionOrderTypeRow, ReasonOptionRow,
+        ReasonOptionType, RequisitionLineFilter, RequisitionLineRepository, RequisitionRow,
+        RequisitionRowRepository, RequisitionType,
     };
+    use util::{date_now_with_offset, datetime_now};
     #[actix_rt::test]
     async fn insert_program_request_requisition_errors() {
         fn not_visible() -> NameRow {
@@ -470,7 +542,7 @@ mod test_insert {
                     id: "new_program_request_requisition".to_string(),
                     other_party_id: mock_name_store_b().id.clone(),
                     program_order_type_id: "does_not_exist".to_string(),
-                    period_id: mock_period().id,
+                    period_id: Some(mock_period().id),
                     ..Default::default()
                 }
             ),
@@ -485,7 +557,7 @@ mod test_insert {
                     id: "new_program_request_requisition".to_string(),
                     other_party_id: "invalid".to_string(),
                     program_order_type_id: mock_program_order_types_a().id,
-                    period_id: mock_period().id,
+                    period_id: Some(mock_period().id),
                     ..Default::default()
                 }
             ),
@@ -514,7 +586,7 @@ mod test_insert {
                     id: "new_program_request_requisition".to_string(),
                     other_party_id: mock_name_store_b().id.clone(),
                     program_order_type_id: mock_program_order_types_a().id,
-                    period_id: mock_period().id,
+                    period_id: Some(mock_period().id),
                     ..Default::default()
                 },
             )
@@ -545,11 +617,219 @@ mod test_insert {
                     id: "error_program_requisition".to_string(),
                     other_party_id: mock_name_store_b().id.clone(),
                     program_order_type_id: mock_program_order_types_a().id,
-                    period_id: mock_period().id,
+                    period_id: Some(mock_period().id),
                     ..Default::default()
                 },
             ),
             Err(ServiceError::MaxOrdersReachedForPeriod)
         );
     }
+
+    #[actix_rt::test]
+    async fn insert_program_request_requisition_emergency() {
+        fn emergency_order_type() -> ProgramRequisitionOrderTypeRow {
+            ProgramRequisitionOrderTypeRow {
+                id: "emergency_order_type".to_string(),
+                program_requisition_settings_id: mock_program_requisition_setting_a().id,
+                name: "Emergency".to_string(),
+                threshold_mos: 2.0,
+                max_mos: 4.0,
+                max_order_per_period: 1,
+                is_emergency: true,
+                max_items_in_emergency_order: 10,
+            }
+        }
+        fn current_period() -> PeriodRow {
+            PeriodRow {
+                id: "current_period".to_string(),
+                name: "Current".to_string(),
+                period_schedule_id: mock_period_schedule_1().id,
+                start_date: date_now_with_offset(Duration::days(-1)),
+                end_date: date_now_with_offset(Duration::days(30)),
+            }
+        }
+        fn emergency_reason() -> ReasonOptionRow {
+            ReasonOptionRow {
+                id: "emergency_reason".to_string(),
+                r#type: ReasonOptionType::EmergencyRequisition,
+                is_active: true,
+                reason: "Stock out".to_string(),
+            }
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_program_request_requisition_emergency",
+            MockDataInserts::all(),
+            MockData {
+                program_order_types: vec![emergency_order_type()],
+                periods: vec![current_period()],
+                reason_options: vec![emergency_reason()],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(program_master_list_store().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.requisition_service;
+
+        // PeriodNotProvided
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                InsertProgramRequestRequisition {
+                    id: "routine_without_period".to_string(),
+                    other_party_id: mock_name_store_b().id.clone(),
+                    program_order_type_id: mock_program_order_types_a().id,
+                    period_id: None,
+                    ..Default::default()
+                }
+            ),
+            Err(ServiceError::PeriodNotProvided)
+        );
+
+        // EmergencyReasonNotProvided
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                InsertProgramRequestRequisition {
+                    id: "emergency_requisition".to_string(),
+                    other_party_id: mock_name_store_b().id.clone(),
+                    program_order_type_id: emergency_order_type().id,
+                    period_id: None,
+                    ..Default::default()
+                }
+            ),
+            Err(ServiceError::EmergencyReasonNotProvided)
+        );
+
+        // EmergencyReasonDoesNotExist
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                InsertProgramRequestRequisition {
+                    id: "emergency_requisition".to_string(),
+                    other_party_id: mock_name_store_b().id.clone(),
+                    program_order_type_id: emergency_order_type().id,
+                    period_id: None,
+                    emergency_reason_option_id: Some("invalid".to_string()),
+                    ..Default::default()
+                }
+            ),
+            Err(ServiceError::EmergencyReasonDoesNotExist)
+        );
+
+        // Success, outside of period schedule
+        let result = service
+            .insert_program_request_requisition(
+                &context,
+                InsertProgramRequestRequisition {
+                    id: "emergency_requisition".to_string(),
+                    other_party_id: mock_name_store_b().id.clone(),
+                    program_order_type_id: emergency_order_type().id,
+                    period_id: None,
+                    emergency_reason_option_id: Some(emergency_reason().id),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let new_row = RequisitionRowRepository::new(&connection)
+            .find_one_by_id(&result.requisition_row.id)
+            .unwrap()
+            .unwrap();
+        assert!(new_row.is_emergency);
+        assert_eq!(new_row.period_id, None);
+        assert_eq!(
+            new_row.emergency_reason_option_id,
+            Some(emergency_reason().id)
+        );
+
+        // Emergency limit applies to orders created within the length of the current period
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                InsertProgramRequestRequisition {
+                    id: "second_emergency_requisition".to_string(),
+                    other_party_id: mock_name_store_b().id.clone(),
+                    program_order_type_id: emergency_order_type().id,
+                    period_id: None,
+                    emergency_reason_option_id: Some(emergency_reason().id),
+                    ..Default::default()
+                }
+            ),
+            Err(ServiceError::MaxOrdersReachedForPeriod)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_program_request_requisition_emergency_without_current_period() {
+        fn emergency_order_type() -> ProgramRequisitionOrderTypeRow {
+            ProgramRequisitionOrderTypeRow {
+                id: "emergency_order_type".to_string(),
+                program_requisition_settings_id: mock_program_requisition_setting_a().id,
+                name: "Emergency".to_string(),
+                threshold_mos: 2.0,
+                max_mos: 4.0,
+                max_order_per_period: 1,
+                is_emergency: true,
+                max_items_in_emergency_order: 10,
+            }
+        }
+        // Older than the window (length of the last period of the schedule, January 2023)
+        fn old_emergency_requisition() -> RequisitionRow {
+            RequisitionRow {
+                id: "old_emergency_requisition".to_string(),
+                name_id: mock_name_store_b().id,
+                store_id: program_master_list_store().id,
+                r#type: RequisitionType::Request,
+                created_datetime: datetime_now() - Duration::days(40),
+                program_id: Some(mock_program_a().id),
+                order_type: Some(emergency_order_type().name),
+                is_emergency: true,
+                ..Default::default()
+            }
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "insert_program_request_requisition_emergency_without_current_period",
+            MockDataInserts::all(),
+            MockData {
+                program_order_types: vec![emergency_order_type()],
+                requisitions: vec![old_emergency_requisition()],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(program_master_list_store().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.requisition_service;
+
+        let input = |id: &str| InsertProgramRequestRequisition {
+            id: id.to_string(),
+            other_party_id: mock_name_store_b().id.clone(),
+            program_order_type_id: emergency_order_type().id,
+            period_id: None,
+            ..Default::default()
+        };
+
+        // Schedule has no period covering today, old order is outside of the window
+        service
+            .insert_program_request_requisition(&context, input("emergency_requisition"))
+            .unwrap();
+
+        // Limit still applies
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                input("second_emergency_requisition")
+            ),
+            Err(ServiceError::MaxOrdersReachedForPeriod)
+        );
+    }
 }
@@ -166,6 +166,7 @@ fn generate(
         max_months_of_stock: response_requisition.max_months_of_stock,
         min_months_of_stock: response_requisition.min_months_of_stock,
         created_from_requisition_id: Some(response_requisition_id.clone()),
+        emergency_reason_option_id: None,
         program_id: response_requisition.program_id.clone(),
         period_id: response_requisition.period_id.clone(),
         order_type: response_requisition.order_type.clone(),
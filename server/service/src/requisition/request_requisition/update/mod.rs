@@ -2,7 +2,10 @@ use crate::{
     activity_log::activity_log_entry,
     backend_plugin::{
         plugin_provider::{PluginError, PluginInstance},
-        types::transform_request_requisition_lines::Context,
+        types::{
+            transform_request_requisition_lines::Context,
+            validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+        },
     },
     requisition::query::get_requisition,
     service_provider::ServiceContext,
@@ -66,6 +69,7 @@ pub enum UpdateRequestRequisitionError {
     // Internal
     UpdatedRequisitionDoesNotExist,
     PluginError(PluginError),
+    PluginValidationFailed(PluginValidationFailed),
     DatabaseError(RepositoryError),
     // Cannot be an error, names are filtered so that name linked to current store is not shown
     // OtherPartyIsThisStore
@@ -114,6 +118,12 @@ pub fn update_request_requisition(
             }
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    &ctx.store_id,
+                    ProposedRows::requisition(connection, updated_requisition_row.clone())?,
+                )?;
+
                 activity_log_entry(
                     ctx,
                     ActivityLogType::RequisitionStatusSent,
@@ -140,3 +150,16 @@ impl From<RepositoryError> for UpdateRequestRequisitionError {
         UpdateRequestRequisitionError::DatabaseError(error)
     }
 }
+
+impl From<ValidateMutationError> for UpdateRequestRequisitionError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateRequestRequisitionError::PluginError(error)
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateRequestRequisitionError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
@@ -0,0 +1,526 @@
+use crate::{
+    activity_log::activity_log_entry,
+    requisition::{
+        approval::{get_applicable_approval_steps, get_next_approval_step, notify_approver},
+        common::{check_requisition_row_exists, get_lines_for_requisition},
+        query::get_requisition,
+    },
+    service_provider::ServiceContext,
+};
+use chrono::Utc;
+use repository::{
+    requisition_row::{RequisitionRow, RequisitionStatus, RequisitionType},
+    ActivityLogType, ApprovalStatusType, RepositoryError, Requisition, RequisitionApprovalDecision,
+    RequisitionApprovalRow, RequisitionApprovalRowRepository, RequisitionApprovalStepRow,
+    RequisitionLineRow, RequisitionLineRowRepository, RequisitionRowRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ApproveResponseRequisitionLineAction {
+    /// Approve requested quantity
+    Approve,
+    /// Set approved quantity to zero
+    Deny,
+    /// Approve a different quantity
+    Adjust(f64),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ApproveResponseRequisitionLine {
+    pub id: String,
+    pub action: ApproveResponseRequisitionLineAction,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ApproveResponseRequisition {
+    pub id: String,
+    pub decision: RequisitionApprovalDecision,
+    pub comment: Option<String>,
+    /// Lines not included keep their approved quantity, on the first step it defaults
+    /// to requested quantity
+    pub lines: Vec<ApproveResponseRequisitionLine>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ApproveResponseRequisitionError {
+    RequisitionDoesNotExist,
+    NotThisStoreRequisition,
+    NotAResponseRequisition,
+    /// Requisition is no longer new (e.g. already finalised)
+    CannotEditRequisition,
+    /// Requisition approval status is not pending
+    NotPendingApproval,
+    /// There are no approval chain steps left to approve for this requisition
+    NoApprovalStepPending,
+    /// Current user is not the approver of the next approval step
+    NotCurrentApprover,
+    LineDoesNotExist(String),
+    ApprovedQuantityCannotBeNegative(String),
+    UpdatedRequisitionDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = ApproveResponseRequisitionError;
+
+pub fn approve_response_requisition(
+    ctx: &ServiceContext,
+    input: ApproveResponseRequisition,
+) -> Result<Requisition, OutError> {
+    let requisition = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (requisition_row, lines, step) = validate(connection, ctx, &input)?;
+            let GenerateResult {
+                approval,
+                updated_lines,
+                adjusted_lines,
+                updated_requisition,
+                next_step,
+            } = generate(
+                connection,
+                &ctx.user_id,
+                requisition_row,
+                lines,
+                step,
+                input,
+            )?;
+
+            RequisitionApprovalRowRepository::new(connection).upsert_one(&approval)?;
+
+            let line_repository = RequisitionLineRowRepository::new(connection);
+            for line in updated_lines {
+                line_repository.upsert_one(&line)?;
+            }
+            for (item_name, from, to) in adjusted_lines {
+                activity_log_entry(
+                    ctx,
+                    ActivityLogType::RequisitionLineApprovedQuantityAdjusted,
+                    Some(updated_requisition.id.to_string()),
+                    Some(format!("{item_name}: {from}")),
+                    Some(format!("{item_name}: {to}")),
+                )?;
+            }
+
+            RequisitionRowRepository::new(connection).upsert_one(&updated_requisition)?;
+
+            let log_type = match (&approval.decision, &next_step) {
+                (RequisitionApprovalDecision::Denied, _) => ActivityLogType::RequisitionDenied,
+                (RequisitionApprovalDecision::Approved, Some(_)) => {
+                    ActivityLogType::RequisitionApprovalStepApproved
+                }
+                (RequisitionApprovalDecision::Approved, None) => {
+                    ActivityLogType::RequisitionApproved
+                }
+            };
+            activity_log_entry(
+                ctx,
+                log_type,
+                Some(updated_requisition.id.to_string()),
+                None,
+                Some(approval.step_number.to_string()),
+            )?;
+
+            if let Some(next_step) = next_step {
+                notify_approver(connection, &next_step, &updated_requisition)?;
+            }
+
+            get_requisition(ctx, None, &updated_requisition.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedRequisitionDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    ctx.processors_trigger
+        .trigger_requisition_transfer_processors();
+    Ok(requisition)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    ctx: &ServiceContext,
+    input: &ApproveResponseRequisition,
+) -> Result<
+    (
+        RequisitionRow,
+        Vec<RequisitionLineRow>,
+        RequisitionApprovalStepRow,
+    ),
+    OutError,
+> {
+    let requisition_row = check_requisition_row_exists(connection, &input.id)?
+        .ok_or(OutError::RequisitionDoesNotExist)?;
+
+    if requisition_row.store_id != ctx.store_id {
+        return Err(OutError::NotThisStoreRequisition);
+    }
+
+    if requisition_row.r#type != RequisitionType::Response {
+        return Err(OutError::NotAResponseRequisition);
+    }
+
+    if requisition_row.status != RequisitionStatus::New {
+        return Err(OutError::CannotEditRequisition);
+    }
+
+    if requisition_row.approval_status != Some(ApprovalStatusType::Pending) {
+        return Err(OutError::NotPendingApproval);
+    }
+
+    let lines: Vec<RequisitionLineRow> = get_lines_for_requisition(connection, &input.id)?
+        .into_iter()
+        .map(|line| line.requisition_line_row)
+        .collect();
+
+    let step = get_next_approval_step(connection, &requisition_row, &lines)?
+        .ok_or(OutError::NoApprovalStepPending)?;
+
+    if step.approver_user_id != ctx.user_id {
+        return Err(OutError::NotCurrentApprover);
+    }
+
+    for input_line in &input.lines {
+        if !lines.iter().any(|line| line.id == input_line.id) {
+            return Err(OutError::LineDoesNotExist(input_line.id.clone()));
+        }
+        if let ApproveResponseRequisitionLineAction::Adjust(quantity) = input_line.action {
+            if quantity < 0.0 {
+                return Err(OutError::ApprovedQuantityCannotBeNegative(
+                    input_line.id.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok((requisition_row, lines, step))
+}
+
+struct GenerateResult {
+    approval: RequisitionApprovalRow,
+    updated_lines: Vec<RequisitionLineRow>,
+    /// Item name and approved quantity (from, to) of lines that were denied or adjusted
+    adjusted_lines: Vec<(String, f64, f64)>,
+    updated_requisition: RequisitionRow,
+    next_step: Option<RequisitionApprovalStepRow>,
+}
+
+fn generate(
+    connection: &StorageConnection,
+    user_id: &str,
+    existing: RequisitionRow,
+    lines: Vec<RequisitionLineRow>,
+    step: RequisitionApprovalStepRow,
+    ApproveResponseRequisition {
+        id: _,
+        decision,
+        comment,
+        lines: input_lines,
+    }: ApproveResponseRequisition,
+) -> Result<GenerateResult, RepositoryError> {
+    let applicable_steps = get_applicable_approval_steps(connection, &existing, &lines)?;
+    let is_first_step = applicable_steps
+        .first()
+        .is_some_and(|first| first.id == step.id);
+
+    let mut updated_lines = Vec::new();
+    let mut adjusted_lines = Vec::new();
+    for line in lines {
+        let input_line = input_lines.iter().find(|input| input.id == line.id);
+
+        let approved_quantity = match input_line.map(|input| &input.action) {
+            Some(ApproveResponseRequisitionLineAction::Approve) => line.requested_quantity,
+            Some(ApproveResponseRequisitionLineAction::Deny) => 0.0,
+            Some(ApproveResponseRequisitionLineAction::Adjust(quantity)) => *quantity,
+            None if is_first_step => line.requested_quantity,
+            None => line.approved_quantity,
+        };
+        let approval_comment = input_line
+            .and_then(|input| input.comment.clone())
+            .or(line.approval_comment.clone());
+
+        if approved_quantity != line.requested_quantity && input_line.is_some() {
+            adjusted_lines.push((
+                line.item_name.clone(),
+                line.approved_quantity,
+                approved_quantity,
+            ));
+        }
+
+        if approved_quantity != line.approved_quantity || approval_comment != line.approval_comment
+        {
+            updated_lines.push(RequisitionLineRow {
+                approved_quantity,
+                approval_comment,
+                ..line
+            });
+        }
+    }
+
+    let next_step = match decision {
+        RequisitionApprovalDecision::Denied => None,
+        RequisitionApprovalDecision::Approved => applicable_steps
+            .into_iter()
+            .skip_while(|applicable| applicable.id != step.id)
+            .nth(1),
+    };
+
+    let approval_status = match (&decision, &next_step) {
+        (RequisitionApprovalDecision::Denied, _) => ApprovalStatusType::Denied,
+        (RequisitionApprovalDecision::Approved, Some(_)) => ApprovalStatusType::Pending,
+        (RequisitionApprovalDecision::Approved, None) => ApprovalStatusType::Approved,
+    };
+
+    let approval = RequisitionApprovalRow {
+        id: uuid(),
+        requisition_id: existing.id.clone(),
+        store_id: existing.store_id.clone(),
+        step_id: step.id,
+        step_number: step.step_number,
+        user_id: user_id.to_string(),
+        decision,
+        comment,
+        datetime: Utc::now().naive_utc(),
+    };
+
+    Ok(GenerateResult {
+        approval,
+        updated_lines,
+        adjusted_lines,
+        updated_requisition: RequisitionRow {
+            approval_status: Some(approval_status),
+            ..existing
+        },
+        next_step,
+    })
+}
+
+impl From<RepositoryError> for ApproveResponseRequisitionError {
+    fn from(error: RepositoryError) -> Self {
+        ApproveResponseRequisitionError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        requisition::response_requisition::{
+            ApproveResponseRequisition, ApproveResponseRequisitionError as ServiceError,
+            ApproveResponseRequisitionLine, ApproveResponseRequisitionLineAction,
+        },
+        service_provider::ServiceProvider,
+    };
+    use repository::{
+        email_queue_row::EmailQueueRowRepository,
+        mock::{
+            mock_finalised_response_requisition, mock_new_response_requisition_for_update_test,
+            mock_new_response_requisition_for_update_test_line, mock_store_a, mock_user_account_a,
+            mock_user_account_b, MockDataInserts,
+        },
+        requisition_row::RequisitionRow,
+        test_db::setup_all,
+        ActivityLogRowRepository, ActivityLogType, ApprovalStatusType, RequisitionApprovalDecision,
+        RequisitionApprovalStepRow, RequisitionApprovalStepRowRepository,
+        RequisitionLineRowRepository, RequisitionRowRepository,
+    };
+
+    #[actix_rt::test]
+    async fn approve_response_requisition() {
+        let requisition = RequisitionRow {
+            approval_status: Some(ApprovalStatusType::Pending),
+            ..mock_new_response_requisition_for_update_test()
+        };
+        let step_1 = RequisitionApprovalStepRow {
+            id: "step_1".to_string(),
+            store_id: Some(mock_store_a().id),
+            step_number: 1,
+            approver_user_id: mock_user_account_a().id,
+            ..Default::default()
+        };
+        // Step of the all stores scope with the same step number, approvals are matched by step id
+        let step_2 = RequisitionApprovalStepRow {
+            id: "step_2".to_string(),
+            step_number: 1,
+            approver_user_id: mock_user_account_b().id,
+            threshold_quantity: Some(5.0),
+            ..Default::default()
+        };
+        // Not applicable, requested quantity is below threshold
+        let step_3 = RequisitionApprovalStepRow {
+            id: "step_3".to_string(),
+            step_number: 3,
+            approver_user_id: mock_user_account_a().id,
+            threshold_quantity: Some(1000.0),
+            ..Default::default()
+        };
+
+        let (_, connection, connection_manager, _) =
+            setup_all("approve_response_requisition", MockDataInserts::all()).await;
+        let step_repository = RequisitionApprovalStepRowRepository::new(&connection);
+        for step in [step_1, step_2, step_3] {
+            step_repository.upsert_one(&step).unwrap();
+        }
+        RequisitionRowRepository::new(&connection)
+            .upsert_one(&requisition)
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let service = service_provider.requisition_service;
+        let line_id = mock_new_response_requisition_for_update_test_line().id;
+
+        let context = service_provider.basic_context().unwrap();
+        let steps = service
+            .get_requisition_approval_steps(&context, &mock_store_a().id)
+            .unwrap();
+        assert_eq!(steps.len(), 3);
+
+        // NotCurrentApprover
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_b().id)
+            .unwrap();
+        assert_eq!(
+            service.approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: requisition.id.clone(),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::NotCurrentApprover)
+        );
+
+        // LineDoesNotExist
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        // CannotEditRequisition
+        let finalised = RequisitionRow {
+            approval_status: Some(ApprovalStatusType::Pending),
+            ..mock_finalised_response_requisition()
+        };
+        RequisitionRowRepository::new(&connection)
+            .upsert_one(&finalised)
+            .unwrap();
+        assert_eq!(
+            service.approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: finalised.id.clone(),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::CannotEditRequisition)
+        );
+
+        assert_eq!(
+            service.approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: requisition.id.clone(),
+                    lines: vec![ApproveResponseRequisitionLine {
+                        id: "invalid".to_string(),
+                        action: ApproveResponseRequisitionLineAction::Approve,
+                        comment: None,
+                    }],
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::LineDoesNotExist("invalid".to_string()))
+        );
+
+        // Step 1 approved with adjusted line, waiting for step 2
+        let result = service
+            .approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: requisition.id.clone(),
+                    decision: RequisitionApprovalDecision::Approved,
+                    comment: None,
+                    lines: vec![ApproveResponseRequisitionLine {
+                        id: line_id.clone(),
+                        action: ApproveResponseRequisitionLineAction::Adjust(7.0),
+                        comment: Some("Reduced".to_string()),
+                    }],
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result.requisition_row.approval_status,
+            Some(ApprovalStatusType::Pending)
+        );
+        let line = RequisitionLineRowRepository::new(&connection)
+            .find_one_by_id(&line_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(line.approved_quantity, 7.0);
+        assert_eq!(line.approval_comment, Some("Reduced".to_string()));
+
+        // Next approver is notified
+        let emails = EmailQueueRowRepository::new(&connection).un_sent().unwrap();
+        assert!(emails
+            .iter()
+            .any(|email| Some(&email.to_address) == mock_user_account_b().email.as_ref()));
+
+        // Step 2 approved, no more applicable steps
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_b().id)
+            .unwrap();
+        let pending = service
+            .get_requisitions_pending_my_approval(&context, &mock_store_a().id)
+            .unwrap();
+        assert_eq!(
+            pending
+                .into_iter()
+                .map(|requisition| requisition.requisition_row.id)
+                .collect::<Vec<_>>(),
+            vec![requisition.id.clone()]
+        );
+        let result = service
+            .approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: requisition.id.clone(),
+                    decision: RequisitionApprovalDecision::Approved,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result.requisition_row.approval_status,
+            Some(ApprovalStatusType::Approved)
+        );
+        // Quantity adjusted in previous step is kept
+        let line = RequisitionLineRowRepository::new(&connection)
+            .find_one_by_id(&line_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(line.approved_quantity, 7.0);
+
+        let logs = ActivityLogRowRepository::new(&connection)
+            .find_many_by_record_id(&requisition.id)
+            .unwrap();
+        let adjusted_log = logs
+            .iter()
+            .find(|log| log.r#type == ActivityLogType::RequisitionLineApprovedQuantityAdjusted)
+            .unwrap();
+        // Adjusted line is identified by item name
+        let item_name = mock_new_response_requisition_for_update_test_line().item_name;
+        assert_eq!(adjusted_log.changed_to, Some(format!("{item_name}: 7")));
+        let log_types: Vec<ActivityLogType> = logs.into_iter().map(|log| log.r#type).collect();
+        assert!(log_types.contains(&ActivityLogType::RequisitionLineApprovedQuantityAdjusted));
+        assert!(log_types.contains(&ActivityLogType::RequisitionApprovalStepApproved));
+        assert!(log_types.contains(&ActivityLogType::RequisitionApproved));
+
+        // NotPendingApproval
+        assert_eq!(
+            service.approve_response_requisition(
+                &context,
+                ApproveResponseRequisition {
+                    id: requisition.id.clone(),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::NotPendingApproval)
+        );
+    }
+}
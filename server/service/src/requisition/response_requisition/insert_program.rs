@@ -210,6 +210,7 @@ fn generate(
         finalised_datetime: None,
         linked_requisition_id: None,
         created_from_requisition_id: None,
+        emergency_reason_option_id: None,
         destination_customer_id: None,
     };
 
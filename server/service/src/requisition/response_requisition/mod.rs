@@ -21,3 +21,6 @@ pub use self::batch::*;
 
 mod add_from_master_list;
 pub use self::add_from_master_list::*;
+
+mod approve;
+pub use self::approve::*;
@@ -1,5 +1,9 @@
 use crate::{
     activity_log::activity_log_entry,
+    backend_plugin::{
+        plugin_provider::{PluginError, PluginInstance},
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
     requisition::{
         common::{
             check_approval_status, check_emergency_order_within_max_items_limit,
@@ -53,6 +57,10 @@ pub enum UpdateResponseRequisitionError {
     DatabaseError(RepositoryError),
     #[error("Reason not provided for one or more requisition lines")]
     ReasonsNotProvided(Vec<RequisitionLine>),
+    #[error("Plugin error")]
+    PluginError(PluginError),
+    #[error("Rejected by plugin validation")]
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 type OutError = UpdateResponseRequisitionError;
@@ -71,6 +79,12 @@ pub fn update_response_requisition(
             RequisitionRowRepository::new(connection).upsert_one(&updated_requisition)?;
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    &ctx.store_id,
+                    ProposedRows::requisition(connection, updated_requisition.clone())?,
+                )?;
+
                 activity_log_entry(
                     ctx,
                     ActivityLogType::RequisitionStatusFinalised,
@@ -210,6 +224,19 @@ impl From<RepositoryError> for UpdateResponseRequisitionError {
     }
 }
 
+impl From<ValidateMutationError> for UpdateResponseRequisitionError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => {
+                UpdateResponseRequisitionError::PluginError(error)
+            }
+            ValidateMutationError::Failed(failed) => {
+                UpdateResponseRequisitionError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_update {
     use crate::{
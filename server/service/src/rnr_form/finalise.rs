@@ -157,6 +157,7 @@ fn generate(
         order_type: None, // Should we capture this in the RnR form?
         is_emergency: false,
         created_from_requisition_id: None,
+        emergency_reason_option_id: None,
         destination_customer_id: None,
     };
 
@@ -0,0 +1,79 @@
+use repository::{
+    InvoiceLineRowRepository, InvoiceRow, InvoiceRowRepository, RepositoryError,
+    SerialNumberLineRow, SerialNumberLineRowRepository, SerialNumberRow, SerialNumberRowRepository,
+    StocktakeLineRowRepository, StocktakeRow, StocktakeRowRepository,
+};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(Debug, PartialEq)]
+pub struct SerialNumberHistoryEntry {
+    /// Unit as held by the store of the entry
+    pub serial_number: SerialNumberRow,
+    pub line: SerialNumberLineRow,
+    /// Receipt or issue of the unit
+    pub invoice: Option<InvoiceRow>,
+    /// Count of the unit
+    pub stocktake: Option<StocktakeRow>,
+}
+
+/// Custody history of a serialised unit, across all stores it has been held in (that are visible
+/// to this site), oldest first
+pub fn get_serial_number_history(
+    ctx: &ServiceContext,
+    item_id: &str,
+    serial_number: &str,
+) -> Result<Vec<SerialNumberHistoryEntry>, RepositoryError> {
+    let connection = &ctx.connection;
+
+    let serial_numbers = SerialNumberRowRepository::new(connection)
+        .find_many_by_serial_number(item_id, serial_number)?;
+    let serial_number_ids: Vec<String> = serial_numbers.iter().map(|row| row.id.clone()).collect();
+
+    let lines = SerialNumberLineRowRepository::new(connection)
+        .find_many_by_serial_number_ids(&serial_number_ids)?;
+
+    let mut history = Vec::new();
+    for line in lines {
+        let Some(serial_number) = serial_numbers
+            .iter()
+            .find(|row| row.id == line.serial_number_id)
+            .cloned()
+        else {
+            continue;
+        };
+
+        let invoice = match &line.invoice_line_id {
+            Some(invoice_line_id) => {
+                match InvoiceLineRowRepository::new(connection).find_one_by_id(invoice_line_id)? {
+                    Some(invoice_line) => InvoiceRowRepository::new(connection)
+                        .find_one_by_id(&invoice_line.invoice_id)?,
+                    None => None,
+                }
+            }
+            None => None,
+        };
+
+        let stocktake = match &line.stocktake_line_id {
+            Some(stocktake_line_id) => {
+                match StocktakeLineRowRepository::new(connection)
+                    .find_one_by_id(stocktake_line_id)?
+                {
+                    Some(stocktake_line) => StocktakeRowRepository::new(connection)
+                        .find_one_by_id(&stocktake_line.stocktake_id)?,
+                    None => None,
+                }
+            }
+            None => None,
+        };
+
+        history.push(SerialNumberHistoryEntry {
+            serial_number,
+            line,
+            invoice,
+            stocktake,
+        });
+    }
+
+    Ok(history)
+}
@@ -0,0 +1,783 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use repository::{
+    EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineRow,
+    InvoiceLineType, RepositoryError, SerialNumberLineRow, SerialNumberLineRowRepository,
+    SerialNumberRow, SerialNumberRowRepository, SerialNumberStatus, StorageConnection,
+};
+use util::uuid::uuid;
+
+use crate::{
+    invoice::{check_invoice_is_editable, check_store},
+    service_provider::ServiceContext,
+};
+
+use super::{check_gtin_matches_item, parse_serial_number};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SetInvoiceLineSerialNumbers {
+    pub invoice_line_id: String,
+    /// Serial numbers or scanned GS1 codes, replaces serial numbers already on the line
+    pub serial_numbers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SetInvoiceLineSerialNumbersError {
+    InvoiceLineDoesNotExist,
+    NotThisStoreInvoice,
+    CannotEditInvoice,
+    /// Only stock in and stock out lines have serialised units
+    NotAStockLine,
+    InvalidSerialNumber(String),
+    /// Scanned GS1 code is for a different item
+    GtinDoesNotMatchItem(String),
+    DuplicateSerialNumber(String),
+    /// More serial numbers than units on the line
+    TooManySerialNumbers,
+    /// Stock in: unit is already in stock in this store
+    SerialNumberAlreadyInStock(String),
+    /// Stock out: unit is not available in the stock line being picked
+    SerialNumberNotAvailable(String),
+    DatabaseError(RepositoryError),
+}
+
+type OutError = SetInvoiceLineSerialNumbersError;
+
+/// Receive (stock in lines) or pick (stock out lines) specific units of a serialised item
+pub fn set_invoice_line_serial_numbers(
+    ctx: &ServiceContext,
+    input: SetInvoiceLineSerialNumbers,
+) -> Result<Vec<SerialNumberRow>, OutError> {
+    let serial_numbers = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (line, serial_numbers) = validate(connection, &ctx.store_id, &input)?;
+
+            let existing_lines = SerialNumberLineRowRepository::new(connection)
+                .find_many_by_invoice_line_id(&line.invoice_line_row.id)?;
+
+            match line.invoice_line_row.r#type {
+                InvoiceLineType::StockIn => receive(
+                    connection,
+                    &ctx.store_id,
+                    &line,
+                    serial_numbers,
+                    existing_lines,
+                ),
+                _ => pick(
+                    connection,
+                    &ctx.store_id,
+                    &line,
+                    serial_numbers,
+                    existing_lines,
+                ),
+            }
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(serial_numbers)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &SetInvoiceLineSerialNumbers,
+) -> Result<(InvoiceLine, Vec<String>), OutError> {
+    let line = InvoiceLineRepository::new(connection)
+        .query_by_filter(
+            InvoiceLineFilter::new().id(EqualFilter::equal_to(input.invoice_line_id.to_string())),
+        )?
+        .pop()
+        .ok_or(OutError::InvoiceLineDoesNotExist)?;
+
+    if !check_store(&line.invoice_row, store_id) {
+        return Err(OutError::NotThisStoreInvoice);
+    }
+    if !check_invoice_is_editable(&line.invoice_row) {
+        return Err(OutError::CannotEditInvoice);
+    }
+    if !matches!(
+        line.invoice_line_row.r#type,
+        InvoiceLineType::StockIn | InvoiceLineType::StockOut
+    ) {
+        return Err(OutError::NotAStockLine);
+    }
+
+    let mut serial_numbers = Vec::new();
+    let mut seen = HashSet::new();
+    for input in &input.serial_numbers {
+        let scanned = parse_serial_number(input)
+            .ok_or_else(|| OutError::InvalidSerialNumber(input.clone()))?;
+        if let Some(gtin) = scanned.gtin {
+            if !check_gtin_matches_item(connection, &line.item_row.id, &gtin)? {
+                return Err(OutError::GtinDoesNotMatchItem(input.clone()));
+            }
+        }
+        let serial_number = scanned.serial_number;
+
+        if !seen.insert(serial_number.clone()) {
+            return Err(OutError::DuplicateSerialNumber(serial_number));
+        }
+        serial_numbers.push(serial_number);
+    }
+
+    let units = line.invoice_line_row.number_of_packs * line.invoice_line_row.pack_size;
+    if serial_numbers.len() as f64 > units {
+        return Err(OutError::TooManySerialNumbers);
+    }
+
+    Ok((line, serial_numbers))
+}
+
+/// Units are created in store (or back in store for returned units), units removed from the line
+/// are deleted unless they have history elsewhere
+fn receive(
+    connection: &StorageConnection,
+    store_id: &str,
+    line: &InvoiceLine,
+    serial_numbers: Vec<String>,
+    existing_lines: Vec<SerialNumberLineRow>,
+) -> Result<Vec<SerialNumberRow>, OutError> {
+    let serial_repo = SerialNumberRowRepository::new(connection);
+    let line_repo = SerialNumberLineRowRepository::new(connection);
+    let now = Utc::now().naive_utc();
+    let item_id = &line.item_row.id;
+
+    let mut existing = existing_serial_numbers(connection, existing_lines)?;
+    let mut result = Vec::new();
+
+    for serial_number in serial_numbers {
+        if let Some(index) = existing
+            .iter()
+            .position(|(_, row)| row.serial_number == serial_number)
+        {
+            let (_, row) = existing.remove(index);
+            result.push(row);
+            continue;
+        }
+
+        let row = match serial_repo.find_one_in_store(store_id, item_id, &serial_number)? {
+            // Unit issued from this store is coming back (e.g. customer return)
+            Some(row) if row.status == SerialNumberStatus::Issued => row,
+            Some(_) => return Err(OutError::SerialNumberAlreadyInStock(serial_number)),
+            None => SerialNumberRow {
+                id: uuid(),
+                item_id: item_id.clone(),
+                store_id: store_id.to_string(),
+                serial_number,
+                created_datetime: now,
+                ..Default::default()
+            },
+        };
+
+        let row = SerialNumberRow {
+            stock_line_id: line.invoice_line_row.stock_line_id.clone(),
+            status: SerialNumberStatus::Available,
+            ..row
+        };
+        serial_repo.upsert_one(&row)?;
+        line_repo.upsert_one(&SerialNumberLineRow {
+            id: uuid(),
+            serial_number_id: row.id.clone(),
+            store_id: store_id.to_string(),
+            invoice_line_id: Some(line.invoice_line_row.id.clone()),
+            stocktake_line_id: None,
+            created_datetime: now,
+        })?;
+        result.push(row);
+    }
+
+    unreceive(connection, existing)?;
+
+    Ok(result)
+}
+
+/// Units removed from a stock in line are deleted unless they have history elsewhere
+fn unreceive(
+    connection: &StorageConnection,
+    removed: Vec<(SerialNumberLineRow, SerialNumberRow)>,
+) -> Result<(), RepositoryError> {
+    let serial_repo = SerialNumberRowRepository::new(connection);
+    let line_repo = SerialNumberLineRowRepository::new(connection);
+
+    for (serial_number_line, row) in removed {
+        line_repo.delete(&serial_number_line.id)?;
+
+        let has_history = !line_repo
+            .find_many_by_serial_number_ids(&[row.id.clone()])?
+            .is_empty();
+        if has_history {
+            serial_repo.upsert_one(&SerialNumberRow {
+                stock_line_id: None,
+                status: SerialNumberStatus::Issued,
+                ..row
+            })?;
+        } else {
+            serial_repo.delete(&row.id)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Units must be available in the stock line being picked, units removed from the line are made
+/// available again
+fn pick(
+    connection: &StorageConnection,
+    store_id: &str,
+    line: &InvoiceLine,
+    serial_numbers: Vec<String>,
+    existing_lines: Vec<SerialNumberLineRow>,
+) -> Result<Vec<SerialNumberRow>, OutError> {
+    let serial_repo = SerialNumberRowRepository::new(connection);
+    let line_repo = SerialNumberLineRowRepository::new(connection);
+    let now = Utc::now().naive_utc();
+
+    let mut existing = existing_serial_numbers(connection, existing_lines)?;
+    let mut result = Vec::new();
+
+    for serial_number in serial_numbers {
+        if let Some(index) = existing
+            .iter()
+            .position(|(_, row)| row.serial_number == serial_number)
+        {
+            let (_, row) = existing.remove(index);
+            result.push(row);
+            continue;
+        }
+
+        let row = serial_repo
+            .find_one_in_store(store_id, &line.item_row.id, &serial_number)?
+            .filter(|row| {
+                row.status != SerialNumberStatus::Issued
+                    && row.stock_line_id.is_some()
+                    && row.stock_line_id == line.invoice_line_row.stock_line_id
+            })
+            .ok_or_else(|| OutError::SerialNumberNotAvailable(serial_number))?;
+
+        let row = SerialNumberRow {
+            status: SerialNumberStatus::Issued,
+            ..row
+        };
+        serial_repo.upsert_one(&row)?;
+        line_repo.upsert_one(&SerialNumberLineRow {
+            id: uuid(),
+            serial_number_id: row.id.clone(),
+            store_id: store_id.to_string(),
+            invoice_line_id: Some(line.invoice_line_row.id.clone()),
+            stocktake_line_id: None,
+            created_datetime: now,
+        })?;
+        result.push(row);
+    }
+
+    unpick(connection, existing)?;
+
+    Ok(result)
+}
+
+/// Units removed from a stock out line are made available again
+fn unpick(
+    connection: &StorageConnection,
+    removed: Vec<(SerialNumberLineRow, SerialNumberRow)>,
+) -> Result<(), RepositoryError> {
+    let serial_repo = SerialNumberRowRepository::new(connection);
+    let line_repo = SerialNumberLineRowRepository::new(connection);
+
+    for (serial_number_line, row) in removed {
+        line_repo.delete(&serial_number_line.id)?;
+        serial_repo.upsert_one(&SerialNumberRow {
+            status: SerialNumberStatus::Available,
+            ..row
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Releases serial numbers of the line beyond `units` (oldest are kept), when line quantity is
+/// reduced or when the line is deleted or cancelled (`units` of zero)
+pub(crate) fn release_invoice_line_serial_numbers(
+    connection: &StorageConnection,
+    invoice_line: &InvoiceLineRow,
+    units: f64,
+) -> Result<(), RepositoryError> {
+    let lines = SerialNumberLineRowRepository::new(connection)
+        .find_many_by_invoice_line_id(&invoice_line.id)?;
+    if lines.len() as f64 <= units {
+        return Ok(());
+    }
+
+    let mut existing = existing_serial_numbers(connection, lines)?;
+    existing.sort_by(|(a, _), (b, _)| a.created_datetime.cmp(&b.created_datetime));
+    let keep = (units.max(0.0) as usize).min(existing.len());
+    let released = existing.split_off(keep);
+
+    match invoice_line.r#type {
+        InvoiceLineType::StockIn => unreceive(connection, released),
+        _ => unpick(connection, released),
+    }
+}
+
+fn existing_serial_numbers(
+    connection: &StorageConnection,
+    lines: Vec<SerialNumberLineRow>,
+) -> Result<Vec<(SerialNumberLineRow, SerialNumberRow)>, RepositoryError> {
+    let serial_repo = SerialNumberRowRepository::new(connection);
+
+    let mut result = Vec::new();
+    for line in lines {
+        if let Some(row) = serial_repo.find_one_by_id(&line.serial_number_id)? {
+            result.push((line, row));
+        }
+    }
+    Ok(result)
+}
+
+/// Stock line of received units is only known once stock is created (e.g. when inbound shipment
+/// is received)
+pub(crate) fn link_serial_numbers_to_stock_line(
+    connection: &StorageConnection,
+    invoice_line_id: &str,
+    stock_line_id: &str,
+) -> Result<(), RepositoryError> {
+    let lines = SerialNumberLineRowRepository::new(connection)
+        .find_many_by_invoice_line_id(invoice_line_id)?;
+    let serial_repo = SerialNumberRowRepository::new(connection);
+
+    for (_, row) in existing_serial_numbers(connection, lines)? {
+        if row.stock_line_id.as_deref() == Some(stock_line_id) {
+            continue;
+        }
+        serial_repo.upsert_one(&SerialNumberRow {
+            stock_line_id: Some(stock_line_id.to_string()),
+            ..row
+        })?;
+    }
+
+    Ok(())
+}
+
+impl From<RepositoryError> for SetInvoiceLineSerialNumbersError {
+    fn from(error: RepositoryError) -> Self {
+        SetInvoiceLineSerialNumbersError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_item_a, mock_name_store_b, mock_patient, mock_store_a, mock_user_account_a,
+            MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineType, InvoiceRow,
+        InvoiceRowRepository, InvoiceStatus, InvoiceType, SerialNumberLineRowRepository,
+        SerialNumberRowRepository, SerialNumberStatus,
+    };
+
+    use crate::{
+        invoice::{
+            inbound_shipment::{
+                InboundShipmentType, UpdateInboundShipment, UpdateInboundShipmentStatus,
+            },
+            prescription::{UpdatePrescription, UpdatePrescriptionStatus},
+        },
+        invoice_line::stock_out_line::{DeleteStockOutLine, StockOutType, UpdateStockOutLine},
+        serial_number::{SetInvoiceLineSerialNumbers, SetInvoiceLineSerialNumbersError},
+        service_provider::ServiceProvider,
+    };
+
+    fn inbound_shipment() -> InvoiceRow {
+        InvoiceRow {
+            id: "serial_inbound_shipment".to_string(),
+            name_id: mock_name_store_b().id,
+            store_id: mock_store_a().id,
+            r#type: InvoiceType::InboundShipment,
+            status: InvoiceStatus::New,
+            ..Default::default()
+        }
+    }
+
+    fn inbound_line() -> InvoiceLineRow {
+        InvoiceLineRow {
+            id: "serial_inbound_line".to_string(),
+            invoice_id: inbound_shipment().id,
+            item_link_id: mock_item_a().id,
+            item_name: mock_item_a().name,
+            item_code: mock_item_a().code,
+            r#type: InvoiceLineType::StockIn,
+            pack_size: 1.0,
+            number_of_packs: 3.0,
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn set_invoice_line_serial_numbers() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "set_invoice_line_serial_numbers",
+            MockDataInserts::all(),
+            MockData {
+                invoices: vec![inbound_shipment()],
+                invoice_lines: vec![inbound_line()],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        let service = service_provider.serial_number_service;
+
+        let input = |invoice_line_id: &str, serial_numbers: &[&str]| SetInvoiceLineSerialNumbers {
+            invoice_line_id: invoice_line_id.to_string(),
+            serial_numbers: serial_numbers.iter().map(|s| s.to_string()).collect(),
+        };
+
+        // Errors
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(&context, input("invalid", &[])),
+            Err(SetInvoiceLineSerialNumbersError::InvoiceLineDoesNotExist)
+        );
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(
+                &context,
+                input(&inbound_line().id, &["SN1", "SN1"])
+            ),
+            Err(SetInvoiceLineSerialNumbersError::DuplicateSerialNumber(
+                "SN1".to_string()
+            ))
+        );
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(
+                &context,
+                input(&inbound_line().id, &["SN1", "SN2", "SN3", "SN4"])
+            ),
+            Err(SetInvoiceLineSerialNumbersError::TooManySerialNumbers)
+        );
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(
+                &context,
+                input(&inbound_line().id, &["(01)00012345600012"])
+            ),
+            Err(SetInvoiceLineSerialNumbersError::InvalidSerialNumber(
+                "(01)00012345600012".to_string()
+            ))
+        );
+
+        // Scanned code of another item (item b barcode)
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(
+                &context,
+                input(&inbound_line().id, &["]d20100009876543210\x1d21SN1"])
+            ),
+            Err(SetInvoiceLineSerialNumbersError::GtinDoesNotMatchItem(
+                "]d20100009876543210\x1d21SN1".to_string()
+            ))
+        );
+
+        // Receive, scanned GS1 (human readable and raw) and one entered serial number
+        let received = service
+            .set_invoice_line_serial_numbers(
+                &context,
+                input(
+                    &inbound_line().id,
+                    &[
+                        "(01)00000123456789(21)SN1",
+                        "]d20100000123456789\x1d21SN2",
+                        "SN3",
+                    ],
+                ),
+            )
+            .unwrap();
+        assert_eq!(received.len(), 3);
+        assert!(received.iter().all(|row| row.stock_line_id.is_none()));
+
+        // Remove one, unit without other history is deleted
+        service
+            .set_invoice_line_serial_numbers(&context, input(&inbound_line().id, &["SN1", "SN2"]))
+            .unwrap();
+        let serial_repo = SerialNumberRowRepository::new(&connection);
+        assert_eq!(
+            serial_repo
+                .find_one_in_store(&mock_store_a().id, &mock_item_a().id, "SN3")
+                .unwrap(),
+            None
+        );
+
+        // Stock line is linked once shipment is received
+        service_provider
+            .invoice_service
+            .update_inbound_shipment(
+                &context,
+                UpdateInboundShipment {
+                    id: inbound_shipment().id,
+                    status: Some(UpdateInboundShipmentStatus::Received),
+                    ..Default::default()
+                },
+                InboundShipmentType::InboundShipment,
+            )
+            .unwrap();
+
+        let stock_line_id = InvoiceLineRowRepository::new(&connection)
+            .find_one_by_id(&inbound_line().id)
+            .unwrap()
+            .unwrap()
+            .stock_line_id
+            .unwrap();
+        let in_stock = service
+            .get_stock_line_serial_numbers(&context, &stock_line_id)
+            .unwrap();
+        assert_eq!(
+            in_stock
+                .iter()
+                .map(|row| row.serial_number.as_str())
+                .collect::<Vec<_>>(),
+            vec!["SN1", "SN2"]
+        );
+
+        // Pick
+        let outbound_shipment = InvoiceRow {
+            id: "serial_outbound_shipment".to_string(),
+            r#type: InvoiceType::OutboundShipment,
+            status: InvoiceStatus::New,
+            ..inbound_shipment()
+        };
+        let outbound_line = InvoiceLineRow {
+            id: "serial_outbound_line".to_string(),
+            invoice_id: outbound_shipment.id.clone(),
+            r#type: InvoiceLineType::StockOut,
+            stock_line_id: Some(stock_line_id.clone()),
+            number_of_packs: 1.0,
+            ..inbound_line()
+        };
+        InvoiceRowRepository::new(&connection)
+            .upsert_one(&outbound_shipment)
+            .unwrap();
+        InvoiceLineRowRepository::new(&connection)
+            .upsert_one(&outbound_line)
+            .unwrap();
+
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(&context, input(&outbound_line.id, &["SN9"])),
+            Err(SetInvoiceLineSerialNumbersError::SerialNumberNotAvailable(
+                "SN9".to_string()
+            ))
+        );
+
+        let picked = service
+            .set_invoice_line_serial_numbers(&context, input(&outbound_line.id, &["SN1"]))
+            .unwrap();
+        assert_eq!(picked[0].status, SerialNumberStatus::Issued);
+
+        // Issued unit can't be picked again
+        let other_outbound_line = InvoiceLineRow {
+            id: "serial_outbound_line_2".to_string(),
+            ..outbound_line.clone()
+        };
+        InvoiceLineRowRepository::new(&connection)
+            .upsert_one(&other_outbound_line)
+            .unwrap();
+        assert_eq!(
+            service.set_invoice_line_serial_numbers(
+                &context,
+                input(&other_outbound_line.id, &["SN1"])
+            ),
+            Err(SetInvoiceLineSerialNumbersError::SerialNumberNotAvailable(
+                "SN1".to_string()
+            ))
+        );
+
+        // Custody history, receipt then issue
+        let history = service
+            .get_serial_number_history(&context, &mock_item_a().id, "SN1")
+            .unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| entry.invoice.as_ref().map(|invoice| invoice.id.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(inbound_shipment().id),
+                Some(outbound_shipment.id.clone())
+            ]
+        );
+
+        // Unpick
+        service
+            .set_invoice_line_serial_numbers(&context, input(&outbound_line.id, &[]))
+            .unwrap();
+        assert_eq!(
+            serial_repo
+                .find_one_in_store(&mock_store_a().id, &mock_item_a().id, "SN1")
+                .unwrap()
+                .unwrap()
+                .status,
+            SerialNumberStatus::Available
+        );
+    }
+
+    #[actix_rt::test]
+    async fn release_invoice_line_serial_numbers() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "release_invoice_line_serial_numbers",
+            MockDataInserts::all(),
+            MockData {
+                invoices: vec![inbound_shipment()],
+                invoice_lines: vec![inbound_line()],
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, mock_user_account_a().id)
+            .unwrap();
+        let service = &service_provider.serial_number_service;
+        let line_service = &service_provider.invoice_line_service;
+
+        let input = |invoice_line_id: &str, serial_numbers: &[&str]| SetInvoiceLineSerialNumbers {
+            invoice_line_id: invoice_line_id.to_string(),
+            serial_numbers: serial_numbers.iter().map(|s| s.to_string()).collect(),
+        };
+        let status = |serial_number: &str| {
+            SerialNumberRowRepository::new(&connection)
+                .find_one_in_store(&mock_store_a().id, &mock_item_a().id, serial_number)
+                .unwrap()
+                .unwrap()
+                .status
+        };
+        let serial_number_line_count = |invoice_line_id: &str| {
+            SerialNumberLineRowRepository::new(&connection)
+                .find_many_by_invoice_line_id(invoice_line_id)
+                .unwrap()
+                .len()
+        };
+
+        service
+            .set_invoice_line_serial_numbers(
+                &context,
+                input(&inbound_line().id, &["SN1", "SN2", "SN3"]),
+            )
+            .unwrap();
+        service_provider
+            .invoice_service
+            .update_inbound_shipment(
+                &context,
+                UpdateInboundShipment {
+                    id: inbound_shipment().id,
+                    status: Some(UpdateInboundShipmentStatus::Received),
+                    ..Default::default()
+                },
+                InboundShipmentType::InboundShipment,
+            )
+            .unwrap();
+        let stock_line_id = InvoiceLineRowRepository::new(&connection)
+            .find_one_by_id(&inbound_line().id)
+            .unwrap()
+            .unwrap()
+            .stock_line_id;
+
+        let outbound_shipment = InvoiceRow {
+            id: "release_outbound_shipment".to_string(),
+            r#type: InvoiceType::OutboundShipment,
+            ..inbound_shipment()
+        };
+        let outbound_line = InvoiceLineRow {
+            id: "release_outbound_line".to_string(),
+            invoice_id: outbound_shipment.id.clone(),
+            r#type: InvoiceLineType::StockOut,
+            stock_line_id: stock_line_id.clone(),
+            number_of_packs: 2.0,
+            ..inbound_line()
+        };
+        InvoiceRowRepository::new(&connection)
+            .upsert_one(&outbound_shipment)
+            .unwrap();
+        InvoiceLineRowRepository::new(&connection)
+            .upsert_one(&outbound_line)
+            .unwrap();
+        service
+            .set_invoice_line_serial_numbers(&context, input(&outbound_line.id, &["SN1", "SN2"]))
+            .unwrap();
+
+        // Quantity reduced, unit picked last is released
+        line_service
+            .update_stock_out_line(
+                &context,
+                UpdateStockOutLine {
+                    id: outbound_line.id.clone(),
+                    r#type: Some(StockOutType::OutboundShipment),
+                    number_of_packs: Some(1.0),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(status("SN1"), SerialNumberStatus::Issued);
+        assert_eq!(status("SN2"), SerialNumberStatus::Available);
+        assert_eq!(serial_number_line_count(&outbound_line.id), 1);
+
+        // Line deleted, all units are released
+        line_service
+            .delete_stock_out_line(
+                &context,
+                DeleteStockOutLine {
+                    id: outbound_line.id.clone(),
+                    r#type: Some(StockOutType::OutboundShipment),
+                },
+            )
+            .unwrap();
+        assert_eq!(status("SN1"), SerialNumberStatus::Available);
+        assert_eq!(serial_number_line_count(&outbound_line.id), 0);
+
+        // Prescription cancelled, units issued to the patient are back in stock
+        let prescription = InvoiceRow {
+            id: "release_prescription".to_string(),
+            name_id: mock_patient().id,
+            r#type: InvoiceType::Prescription,
+            ..inbound_shipment()
+        };
+        let prescription_line = InvoiceLineRow {
+            id: "release_prescription_line".to_string(),
+            invoice_id: prescription.id.clone(),
+            number_of_packs: 1.0,
+            ..outbound_line
+        };
+        InvoiceRowRepository::new(&connection)
+            .upsert_one(&prescription)
+            .unwrap();
+        InvoiceLineRowRepository::new(&connection)
+            .upsert_one(&prescription_line)
+            .unwrap();
+        service
+            .set_invoice_line_serial_numbers(&context, input(&prescription_line.id, &["SN3"]))
+            .unwrap();
+        assert_eq!(status("SN3"), SerialNumberStatus::Issued);
+
+        InvoiceRowRepository::new(&connection)
+            .upsert_one(&InvoiceRow {
+                status: InvoiceStatus::Verified,
+                ..prescription.clone()
+            })
+            .unwrap();
+        service_provider
+            .invoice_service
+            .update_prescription(
+                &context,
+                UpdatePrescription {
+                    id: prescription.id.clone(),
+                    status: Some(UpdatePrescriptionStatus::Cancelled),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(status("SN3"), SerialNumberStatus::Available);
+        assert_eq!(serial_number_line_count(&prescription_line.id), 0);
+    }
+}
@@ -0,0 +1,165 @@
+mod history;
+mod invoice_line;
+mod stocktake;
+
+use crate::service_provider::ServiceContext;
+pub use history::{get_serial_number_history, SerialNumberHistoryEntry};
+pub(crate) use invoice_line::{
+    link_serial_numbers_to_stock_line, release_invoice_line_serial_numbers,
+};
+pub use invoice_line::{
+    set_invoice_line_serial_numbers, SetInvoiceLineSerialNumbers, SetInvoiceLineSerialNumbersError,
+};
+use repository::{
+    BarcodeRowRepository, RepositoryError, SerialNumberRow, SerialNumberRowRepository,
+    StorageConnection,
+};
+pub use stocktake::{
+    verify_stocktake_serial_numbers, StocktakeSerialNumbers, VerifyStocktakeSerialNumbers,
+    VerifyStocktakeSerialNumbersError,
+};
+use util::GS1;
+
+pub trait SerialNumberServiceTrait: Send + Sync {
+    fn get_stock_line_serial_numbers(
+        &self,
+        ctx: &ServiceContext,
+        stock_line_id: &str,
+    ) -> Result<Vec<SerialNumberRow>, RepositoryError> {
+        SerialNumberRowRepository::new(&ctx.connection).find_many_by_stock_line_id(stock_line_id)
+    }
+
+    fn set_invoice_line_serial_numbers(
+        &self,
+        ctx: &ServiceContext,
+        input: SetInvoiceLineSerialNumbers,
+    ) -> Result<Vec<SerialNumberRow>, SetInvoiceLineSerialNumbersError> {
+        set_invoice_line_serial_numbers(ctx, input)
+    }
+
+    fn verify_stocktake_serial_numbers(
+        &self,
+        ctx: &ServiceContext,
+        input: VerifyStocktakeSerialNumbers,
+    ) -> Result<StocktakeSerialNumbers, VerifyStocktakeSerialNumbersError> {
+        verify_stocktake_serial_numbers(ctx, input)
+    }
+
+    fn get_serial_number_history(
+        &self,
+        ctx: &ServiceContext,
+        item_id: &str,
+        serial_number: &str,
+    ) -> Result<Vec<SerialNumberHistoryEntry>, RepositoryError> {
+        get_serial_number_history(ctx, item_id, serial_number)
+    }
+}
+
+pub struct SerialNumberService;
+impl SerialNumberServiceTrait for SerialNumberService {}
+
+#[derive(Debug, PartialEq)]
+pub struct ScannedSerialNumber {
+    pub serial_number: String,
+    /// GTIN (AI 01) when a GS1 code was scanned
+    pub gtin: Option<String>,
+}
+
+/// Serial number (AI 21) of a scanned GS1 DataMatrix, either in human readable form, e.g.
+/// `(01)00012345600012(21)S12345678`, or as the raw element string sent by scanners, e.g.
+/// `]d20100012345600012\x1d21S12345678`, otherwise the serial number as entered
+pub fn parse_serial_number(input: &str) -> Option<ScannedSerialNumber> {
+    let input = input.trim_start_matches('\u{FEFF}').trim();
+
+    let gs1 = if input.starts_with('(') {
+        GS1::from_human_readable_string(input.to_string())
+    } else if input.starts_with(']') || input.contains('\x1d') {
+        GS1::from_element_string(input.to_string())
+    } else {
+        return (!input.is_empty()).then(|| ScannedSerialNumber {
+            serial_number: input.to_string(),
+            gtin: None,
+        });
+    };
+
+    let gs1 = gs1.ok()?;
+    Some(ScannedSerialNumber {
+        serial_number: gs1.serial_number()?,
+        gtin: gs1.gtin(),
+    })
+}
+
+/// Scanned GTIN must be one of the item's barcodes, GTIN of items without barcodes can't be checked
+pub(crate) fn check_gtin_matches_item(
+    connection: &StorageConnection,
+    item_id: &str,
+    gtin: &str,
+) -> Result<bool, RepositoryError> {
+    // GTIN-14 of AI 01 is zero padded, barcodes may be stored as GTIN-8, 12 or 13
+    let normalise = |gtin: &str| gtin.trim().trim_start_matches('0').to_string();
+
+    let barcodes = BarcodeRowRepository::new(connection).find_many_by_item_id(item_id)?;
+    Ok(barcodes.is_empty()
+        || barcodes
+            .iter()
+            .any(|barcode| normalise(&barcode.gtin) == normalise(gtin)))
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_item_a, mock_item_c, MockDataInserts},
+        test_db::setup_all,
+    };
+
+    use super::{check_gtin_matches_item, parse_serial_number, ScannedSerialNumber};
+
+    fn scanned(serial_number: &str, gtin: Option<&str>) -> Option<ScannedSerialNumber> {
+        Some(ScannedSerialNumber {
+            serial_number: serial_number.to_string(),
+            gtin: gtin.map(|gtin| gtin.to_string()),
+        })
+    }
+
+    #[test]
+    fn test_parse_serial_number() {
+        assert_eq!(
+            parse_serial_number("(01)00012345600012(11)241007(21)S12345678"),
+            scanned("S12345678", Some("00012345600012"))
+        );
+        assert_eq!(parse_serial_number(" SN-001 "), scanned("SN-001", None));
+        // GS1 without a serial number
+        assert_eq!(parse_serial_number("(01)00012345600012"), None);
+        assert_eq!(parse_serial_number("  "), None);
+    }
+
+    #[test]
+    fn test_parse_raw_scanner_input() {
+        // Symbology identifier, batch (AI 10) terminated by group separator
+        assert_eq!(
+            parse_serial_number("]d2010001234560001210B1\x1d21S12345678"),
+            scanned("S12345678", Some("00012345600012"))
+        );
+        // Leading FNC1 transmitted as group separator, serial number before expiry
+        assert_eq!(
+            parse_serial_number("\x1d010001234560001221S1\x1d17260131\r\n"),
+            scanned("S1", Some("00012345600012"))
+        );
+        // Truncated scan
+        assert_eq!(parse_serial_number("]d20100012345"), None);
+        // Raw GS1 without a serial number
+        assert_eq!(parse_serial_number("]d20100012345600012"), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_check_gtin_matches_item() {
+        let (_, connection, _, _) =
+            setup_all("test_check_gtin_matches_item", MockDataInserts::all()).await;
+
+        // Item a has barcode 0123456789
+        assert!(check_gtin_matches_item(&connection, &mock_item_a().id, "00000123456789").unwrap());
+        assert!(!check_gtin_matches_item(&connection, &mock_item_a().id, "09876543210").unwrap());
+        // Item without barcodes
+        assert!(check_gtin_matches_item(&connection, &mock_item_c().id, "09876543210").unwrap());
+    }
+}
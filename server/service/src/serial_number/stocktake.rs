@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use repository::{
+    EqualFilter, RepositoryError, SerialNumberLineRow, SerialNumberLineRowRepository,
+    SerialNumberRow, SerialNumberRowRepository, SerialNumberStatus, StocktakeLineFilter,
+    StocktakeLineRepository,
+};
+use util::uuid::uuid;
+
+use crate::{
+    service_provider::ServiceContext,
+    stocktake::{check_stocktake_exist, check_stocktake_not_finalised},
+};
+
+use super::{check_gtin_matches_item, parse_serial_number};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct VerifyStocktakeSerialNumbers {
+    pub stocktake_line_id: String,
+    /// Serial numbers or scanned GS1 codes of units counted
+    pub serial_numbers: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum VerifyStocktakeSerialNumbersError {
+    StocktakeLineDoesNotExist,
+    NotThisStoreStocktake,
+    StocktakeIsFinalised,
+    /// Serial numbers are only held against existing stock
+    StocktakeLineHasNoStockLine,
+    InvalidSerialNumber(String),
+    /// Scanned GS1 code is for a different item
+    GtinDoesNotMatchItem(String),
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct StocktakeSerialNumbers {
+    pub found: Vec<SerialNumberRow>,
+    /// Units of the stock line that were not counted, now marked as missing
+    pub missing: Vec<SerialNumberRow>,
+    /// Counted serial numbers that are not part of the stock line
+    pub unknown: Vec<String>,
+}
+
+type OutError = VerifyStocktakeSerialNumbersError;
+
+/// Compare counted units against units held in stock line of a stocktake line. Counted units
+/// are verified (and made available again if they were missing), others are marked as missing
+pub fn verify_stocktake_serial_numbers(
+    ctx: &ServiceContext,
+    input: VerifyStocktakeSerialNumbers,
+) -> Result<StocktakeSerialNumbers, OutError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let stocktake_line = StocktakeLineRepository::new(connection)
+                .query_by_filter(
+                    StocktakeLineFilter::new()
+                        .id(EqualFilter::equal_to(input.stocktake_line_id.to_string())),
+                    Some(ctx.store_id.clone()),
+                )?
+                .pop()
+                .ok_or(OutError::StocktakeLineDoesNotExist)?;
+
+            let stocktake = check_stocktake_exist(connection, &stocktake_line.line.stocktake_id)?
+                .ok_or(OutError::StocktakeLineDoesNotExist)?;
+            if stocktake.store_id != ctx.store_id {
+                return Err(OutError::NotThisStoreStocktake);
+            }
+            if !check_stocktake_not_finalised(&stocktake.status) {
+                return Err(OutError::StocktakeIsFinalised);
+            }
+            let stock_line_id = stocktake_line
+                .line
+                .stock_line_id
+                .ok_or(OutError::StocktakeLineHasNoStockLine)?;
+
+            let mut counted = HashSet::new();
+            for input in &input.serial_numbers {
+                let scanned = parse_serial_number(input)
+                    .ok_or_else(|| OutError::InvalidSerialNumber(input.clone()))?;
+                if let Some(gtin) = scanned.gtin {
+                    if !check_gtin_matches_item(connection, &stocktake_line.item.id, &gtin)? {
+                        return Err(OutError::GtinDoesNotMatchItem(input.clone()));
+                    }
+                }
+                counted.insert(scanned.serial_number);
+            }
+
+            let serial_repo = SerialNumberRowRepository::new(connection);
+            let line_repo = SerialNumberLineRowRepository::new(connection);
+            let now = Utc::now().naive_utc();
+            let mut result = StocktakeSerialNumbers::default();
+
+            for row in serial_repo.find_many_by_stock_line_id(&stock_line_id)? {
+                if row.status == SerialNumberStatus::Issued {
+                    continue;
+                }
+
+                if counted.remove(&row.serial_number) {
+                    let row = SerialNumberRow {
+                        status: SerialNumberStatus::Available,
+                        last_verified_datetime: Some(now),
+                        ..row
+                    };
+                    serial_repo.upsert_one(&row)?;
+                    line_repo.upsert_one(&SerialNumberLineRow {
+                        id: uuid(),
+                        serial_number_id: row.id.clone(),
+                        store_id: ctx.store_id.clone(),
+                        invoice_line_id: None,
+                        stocktake_line_id: Some(stocktake_line.line.id.clone()),
+                        created_datetime: now,
+                    })?;
+                    result.found.push(row);
+                } else {
+                    let row = SerialNumberRow {
+                        status: SerialNumberStatus::Missing,
+                        ..row
+                    };
+                    serial_repo.upsert_one(&row)?;
+                    result.missing.push(row);
+                }
+            }
+
+            result.unknown = counted.into_iter().collect();
+            result.unknown.sort();
+
+            Ok(result)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(result)
+}
+
+impl From<RepositoryError> for VerifyStocktakeSerialNumbersError {
+    fn from(error: RepositoryError) -> Self {
+        VerifyStocktakeSerialNumbersError::DatabaseError(error)
+    }
+}
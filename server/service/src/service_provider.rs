@@ -1,7 +1,9 @@
 use crate::{
+    api_key::{ApiKeyService, ApiKeyServiceTrait},
     app_data::{AppDataService, AppDataServiceTrait},
     asset::AssetServiceTrait,
     auth::{AuthService, AuthServiceTrait},
+    backend_plugin::types::validate_mutation::PluginValidationMessage,
     barcode::{BarcodeService, BarcodeServiceTrait},
     campaign::{CampaignService, CampaignServiceTrait},
     catalogue::{AssetCatalogueServiceTrait, CatalogueService},
@@ -16,7 +18,9 @@ use crate::{
         requisition_count::{RequisitionCountService, RequisitionCountServiceTrait},
         stock_expiry_count::{StockExpiryCountServiceTrait, StockExpiryServiceCount},
     },
+    demand_forecast::{DemandForecastService, DemandForecastServiceTrait},
     demographic::DemographicServiceTrait,
+    dispensing_safety::{DispensingSafetyService, DispensingSafetyServiceTrait},
     display_settings_service::{DisplaySettingsService, DisplaySettingsServiceTrait},
     document::{
         document_registry::{DocumentRegistryService, DocumentRegistryServiceTrait},
@@ -24,12 +28,15 @@ use crate::{
         form_schema_service::{FormSchemaService, FormSchemaServiceTrait},
     },
     email::{EmailService, EmailServiceTrait},
+    epcis::{EpcisService, EpcisServiceTrait},
+    expiry_risk::{ExpiryRiskService, ExpiryRiskServiceTrait},
     insurance::{InsuranceService, InsuranceServiceTrait},
     insurance_provider::{InsuranceProviderService, InsuranceProviderServiceTrait},
     invoice::{InvoiceService, InvoiceServiceTrait},
     invoice_line::{InvoiceLineService, InvoiceLineServiceTrait},
     item::ItemServiceTrait,
     item_stats::{ItemStatsService, ItemStatsServiceTrait},
+    kit::{KitService, KitServiceTrait},
     label_printer_settings_service::LabelPrinterSettingsServiceTrait,
     ledger_fix::ledger_fix_driver::LedgerFixTrigger,
     localisations::LocalisationsService,
@@ -37,6 +44,7 @@ use crate::{
     log_service::{LogService, LogServiceTrait},
     master_list::{MasterListService, MasterListServiceTrait},
     name::{NameService, NameServiceTrait},
+    network_stock::{NetworkStockService, NetworkStockServiceTrait},
     plugin::{FrontendPluginCache, PluginService, PluginServiceTrait},
     plugin_data::{PluginDataService, PluginDataServiceTrait},
     preference::{PreferenceService, PreferenceServiceTrait},
@@ -53,6 +61,8 @@ use crate::{
     },
     purchase_order::{PurchaseOrderService, PurchaseOrderServiceTrait},
     purchase_order_line::{PurchaseOrderLineService, PurchaseOrderLineServiceTrait},
+    recall::{RecallService, RecallServiceTrait},
+    refill::{RefillService, RefillServiceTrait},
     repack::{RepackService, RepackServiceTrait},
     report::report_service::{ReportService, ReportServiceTrait},
     requisition::{
@@ -63,6 +73,7 @@ use crate::{
     requisition_line::{RequisitionLineService, RequisitionLineServiceTrait},
     rnr_form::{RnRFormService, RnRFormServiceTrait},
     sensor::{SensorService, SensorServiceTrait},
+    serial_number::{SerialNumberService, SerialNumberServiceTrait},
     settings::MailSettings,
     settings_service::{SettingsService, SettingsServiceTrait},
     shipping_method::{ShippingMethodService, ShippingMethodServiceTrait},
@@ -80,14 +91,16 @@ use crate::{
     vaccination::{VaccinationService, VaccinationServiceTrait},
     vaccine_course::VaccineCourseServiceTrait,
     vvm::{VVMService, VVMServiceTrait},
+    webhook::{WebhookService, WebhookServiceTrait},
     ListError, ListResult,
 };
 use repository::{
-    PaginationOption, RepositoryError, StorageConnection, StorageConnectionManager, Store,
-    StoreFilter, StoreSort,
+    AuditContext, PaginationOption, RepositoryError, StorageConnection, StorageConnectionManager,
+    Store, StoreFilter, StoreSort,
 };
 
 use crate::subscription::SubscriptionTriggerHandle;
+use std::sync::Mutex;
 use util::constants::SYSTEM_USER_ID;
 
 pub struct ServiceProvider {
@@ -168,6 +181,14 @@ pub struct ServiceProvider {
     pub asset_service: Box<dyn AssetServiceTrait>,
     // Label Printer
     pub label_printer_settings_service: Box<dyn LabelPrinterSettingsServiceTrait>,
+    // Demand forecast
+    pub demand_forecast_service: Box<dyn DemandForecastServiceTrait>,
+    // Expiry risk
+    pub expiry_risk_service: Box<dyn ExpiryRiskServiceTrait>,
+    // Network stock (central only)
+    pub network_stock_service: Box<dyn NetworkStockServiceTrait>,
+    // EPCIS
+    pub epcis_service: Box<dyn EpcisServiceTrait>,
     // Demographic
     pub demographic_service: Box<dyn DemographicServiceTrait>,
     // Vaccine Course
@@ -195,6 +216,20 @@ pub struct ServiceProvider {
     pub vvm_service: Box<dyn VVMServiceTrait>,
     // Campaign
     pub campaign_service: Box<dyn CampaignServiceTrait>,
+    // Recall
+    pub recall_service: Box<dyn RecallServiceTrait>,
+    // Serial numbers
+    pub serial_number_service: Box<dyn SerialNumberServiceTrait>,
+    // Kits
+    pub kit_service: Box<dyn KitServiceTrait>,
+    // Medication refills
+    pub refill_service: Box<dyn RefillServiceTrait>,
+    // Dispensing safety checks
+    pub dispensing_safety_service: Box<dyn DispensingSafetyServiceTrait>,
+    // Scoped API keys for service accounts
+    pub api_key_service: Box<dyn ApiKeyServiceTrait>,
+    // Outbound webhooks driven by the changelog
+    pub webhook_service: Box<dyn WebhookServiceTrait>,
     // Purchase Orders
     pub purchase_order_service: Box<dyn PurchaseOrderServiceTrait>,
     pub purchase_order_line_service: Box<dyn PurchaseOrderLineServiceTrait>,
@@ -211,6 +246,9 @@ pub struct ServiceContext {
     pub connection: StorageConnection,
     pub(crate) processors_trigger: ProcessorsTrigger,
     pub(crate) frontend_plugins_cache: FrontendPluginCache,
+    /// Non blocking validate_mutation plugin messages of successful mutations, see
+    /// `take_plugin_warnings`
+    pub(crate) plugin_warnings: Mutex<Vec<PluginValidationMessage>>,
     pub user_id: String,
     pub store_id: String,
 }
@@ -294,6 +332,10 @@ impl ServiceProvider {
             label_printer_settings_service: Box::new(
                 crate::label_printer_settings_service::LabelPrinterSettingsService {},
             ),
+            epcis_service: Box::new(EpcisService),
+            demand_forecast_service: Box::new(DemandForecastService),
+            network_stock_service: Box::new(NetworkStockService),
+            expiry_risk_service: Box::new(ExpiryRiskService),
             name_service: Box::new(NameService {}),
             demographic_service: Box::new(crate::demographic::DemographicService {}),
             vaccine_course_service: Box::new(crate::vaccine_course::VaccineCourseService {}),
@@ -313,6 +355,13 @@ impl ServiceProvider {
             preference_service: Box::new(PreferenceService {}),
             vvm_service: Box::new(VVMService {}),
             campaign_service: Box::new(CampaignService),
+            recall_service: Box::new(RecallService),
+            serial_number_service: Box::new(SerialNumberService),
+            kit_service: Box::new(KitService),
+            refill_service: Box::new(RefillService),
+            dispensing_safety_service: Box::new(DispensingSafetyService),
+            api_key_service: Box::new(ApiKeyService),
+            webhook_service: Box::new(WebhookService),
             purchase_order_service: Box::new(PurchaseOrderService),
             purchase_order_line_service: Box::new(PurchaseOrderLineService),
             contact_service: Box::new(ContactService {}),
@@ -330,6 +379,7 @@ impl ServiceProvider {
             user_id: "".to_string(),
             store_id: "".to_string(),
             frontend_plugins_cache: self.frontend_plugins_cache.clone(),
+            plugin_warnings: Default::default(),
         })
     }
 
@@ -343,6 +393,7 @@ impl ServiceProvider {
             user_id: SYSTEM_USER_ID.to_string(),
             store_id: store_id.unwrap_or("".to_string()),
             frontend_plugins_cache: self.frontend_plugins_cache.clone(),
+            plugin_warnings: Default::default(),
         })
     }
 
@@ -351,12 +402,23 @@ impl ServiceProvider {
         store_id: String,
         user_id: String,
     ) -> Result<ServiceContext, RepositoryError> {
+        let connection = self.connection()?;
+        // Master data edits made for a user are audited, see `AuditCapture`
+        if !user_id.is_empty() {
+            connection.set_audit_context(Some(AuditContext {
+                user_id: user_id.clone(),
+                store_id: (!store_id.is_empty()).then(|| store_id.clone()),
+                reason: None,
+            }));
+        }
+
         Ok(ServiceContext {
-            connection: self.connection()?,
+            connection,
             processors_trigger: self.processors_trigger.clone(),
             user_id,
             store_id,
             frontend_plugins_cache: self.frontend_plugins_cache.clone(),
+            plugin_warnings: Default::default(),
         })
     }
 
@@ -367,6 +429,18 @@ impl ServiceProvider {
 }
 
 impl ServiceContext {
+    /// Reason recorded in the audit log with the master data edits made through this context
+    pub fn with_audit_reason(self, reason: Option<String>) -> Self {
+        self.connection.set_audit_reason(reason);
+        self
+    }
+
+    /// Warnings returned by validate_mutation plugins for the mutations made through this
+    /// context, to be returned with the mutation result
+    pub fn take_plugin_warnings(&self) -> Vec<PluginValidationMessage> {
+        std::mem::take(&mut *self.plugin_warnings.lock().unwrap())
+    }
+
     #[cfg(test)]
     pub(crate) fn new_without_triggers(connection: StorageConnection) -> ServiceContext {
         ServiceContext {
@@ -375,6 +449,7 @@ impl ServiceContext {
             user_id: "".to_string(),
             store_id: "".to_string(),
             frontend_plugins_cache: FrontendPluginCache::new(),
+            plugin_warnings: Default::default(),
         }
     }
 }
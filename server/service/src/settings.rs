@@ -3,7 +3,9 @@ use std::{
     fmt::{Display, Formatter, Result},
 };
 
-use repository::database_settings::DatabaseSettings;
+use repository::{
+    database_encryption::DatabaseEncryptionSettings, database_settings::DatabaseSettings,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::sync::settings::SyncSettings;
@@ -17,6 +19,14 @@ pub struct Settings {
     pub backup: Option<BackupSettings>,
     pub mail: Option<MailSettings>,
     pub features: Option<HashMap<String, bool>>,
+    pub graphql: Option<GraphqlSettings>,
+}
+
+impl Settings {
+    /// Configured GraphQL limits, or the defaults when not configured
+    pub fn graphql(&self) -> GraphqlSettings {
+        self.graphql.clone().unwrap_or_default()
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -58,6 +68,65 @@ impl ServerSettings {
     }
 }
 
+/// Limits protecting the database from expensive GraphQL requests
+#[derive(Deserialize, Serialize, Clone)]
+pub struct GraphqlSettings {
+    /// Maximum nesting depth of a query
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+    /// Maximum complexity of a query, each selected field adds 1
+    #[serde(default = "default_max_complexity")]
+    pub max_complexity: usize,
+    #[serde(default)]
+    pub rate_limit: RateLimitSettings,
+}
+
+impl Default for GraphqlSettings {
+    fn default() -> Self {
+        GraphqlSettings {
+            max_depth: default_max_depth(),
+            max_complexity: default_max_complexity(),
+            rate_limit: RateLimitSettings::default(),
+        }
+    }
+}
+
+fn default_max_depth() -> usize {
+    20
+}
+
+fn default_max_complexity() -> usize {
+    5000
+}
+
+/// Rate limit of expensive operations (reports, item ledger, exports, plugin mutations), applied
+/// per user and per client IP
+#[derive(Deserialize, Serialize, Clone)]
+pub struct RateLimitSettings {
+    /// Number of expensive operations allowed in each window
+    #[serde(default = "default_max_requests")]
+    pub max_requests: u32,
+    #[serde(default = "default_window_seconds")]
+    pub window_seconds: u64,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        RateLimitSettings {
+            max_requests: default_max_requests(),
+            window_seconds: default_window_seconds(),
+        }
+    }
+}
+
+fn default_max_requests() -> u32 {
+    30
+}
+
+fn default_window_seconds() -> u64 {
+    60
+}
+
 /// See backup cli for more details
 #[derive(Deserialize, Serialize, Clone)]
 pub struct BackupSettings {
@@ -67,6 +136,16 @@ pub struct BackupSettings {
     pub pg_bin_dir: Option<String>,
     // Number of backups to keep
     pub max_number_of_backups: Option<u32>,
+    // Encrypts backups with a key separate from the database key, requires the sqlcipher feature
+    #[serde(default)]
+    pub encryption: Option<DatabaseEncryptionSettings>,
+    // Server runs a backup when the last one is older than this, only CLI backups when not set
+    pub schedule_interval_hours: Option<u32>,
+    // Postgres only, folder postgres archives WAL to (archive_command). Backups include a base
+    // backup for point in time restore when set
+    pub wal_archive_dir: Option<String>,
+    // Postgres only, data directory of the postgres cluster, required for point in time restore
+    pub pg_data_dir: Option<String>,
 }
 
 pub fn is_develop() -> bool {
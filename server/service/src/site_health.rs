@@ -0,0 +1,314 @@
+//! Site health, reported by sites to the central server with site status on each sync (see
+//! `SynchroniserV6::report_site_status`), and the fleet wide view of the reports on central server
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    database_stats::{database_file_path, database_size_bytes},
+    migrations::Version,
+    EqualFilter, RepositoryError, SiteBackupStatusRow, SiteBackupStatusRowRepository,
+    SiteHealthReportRow, SiteHealthReportRowRepository, StorageConnection, StoreFilter,
+    StoreRepository, SystemLogRowRepository, SystemLogType,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+/// Processor errors are counted over this window
+const PROCESSOR_ERROR_WINDOW_HOURS: i64 = 24;
+const LOW_FREE_DISK_BYTES: i64 = 1024 * 1024 * 1024;
+const CLOCK_OFFSET_TOLERANCE_SECONDS: i64 = 5 * 60;
+const PENDING_CHANGELOG_TOLERANCE: i64 = 1000;
+const BACKUP_OVERDUE_DAYS: i64 = 7;
+const NOT_REPORTING_DAYS: i64 = 2;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteHealthReport {
+    pub app_version: String,
+    /// Time on the site, central server works out clock offset from it
+    pub site_datetime: NaiveDateTime,
+    pub database_size_bytes: Option<i64>,
+    pub free_disk_bytes: Option<i64>,
+    pub processor_error_count: i64,
+    pub last_processor_error: Option<String>,
+    pub last_processor_error_datetime: Option<NaiveDateTime>,
+    /// Changes not yet pushed to both the legacy and the open mSupply central server
+    pub pending_changelog_count: i64,
+}
+
+/// Health of this site. Database size and free disk are left out when they can't be read, rather
+/// than failing the report
+pub fn generate_site_health_report(
+    connection: &StorageConnection,
+    pending_changelog_count: u64,
+) -> Result<SiteHealthReport, RepositoryError> {
+    let now = Utc::now().naive_utc();
+    let system_log_repo = SystemLogRowRepository::new(connection);
+    let processor_error_count = system_log_repo.count_by_type_since(
+        SystemLogType::ProcessorError,
+        now - Duration::hours(PROCESSOR_ERROR_WINDOW_HOURS),
+    )?;
+    let last_processor_error =
+        system_log_repo.find_latest_by_type(SystemLogType::ProcessorError)?;
+
+    let database_size_bytes = database_size_bytes(connection)
+        .map_err(|error| log::warn!("Failed to get database size {error:?}"))
+        .ok();
+
+    Ok(SiteHealthReport {
+        app_version: Version::from_package_json().to_string(),
+        site_datetime: now,
+        database_size_bytes,
+        free_disk_bytes: free_disk_bytes(connection),
+        processor_error_count,
+        last_processor_error_datetime: last_processor_error.as_ref().map(|row| row.datetime),
+        last_processor_error: last_processor_error.and_then(|row| row.message),
+        pending_changelog_count: pending_changelog_count as i64,
+    })
+}
+
+/// Free space on the disk of the sqlite database, or of the working folder (app data) for postgres
+fn free_disk_bytes(connection: &StorageConnection) -> Option<i64> {
+    let folder = match database_file_path(connection) {
+        Ok(Some(file)) => file.parent().map(PathBuf::from),
+        _ => None,
+    }
+    .or_else(|| std::env::current_dir().ok())?;
+
+    fs4::available_space(&folder)
+        .map_err(|error| log::warn!("Failed to get free disk space of {folder:?} {error:?}"))
+        .ok()
+        .map(|bytes| bytes as i64)
+}
+
+/// Health report sent by a site, on central server
+pub fn record_site_health_report(
+    connection: &StorageConnection,
+    site_id: i32,
+    report: SiteHealthReport,
+) -> Result<(), RepositoryError> {
+    let SiteHealthReport {
+        app_version,
+        site_datetime,
+        database_size_bytes,
+        free_disk_bytes,
+        processor_error_count,
+        last_processor_error,
+        last_processor_error_datetime,
+        pending_changelog_count,
+    } = report;
+    let received_datetime = Utc::now().naive_utc();
+
+    SiteHealthReportRowRepository::new(connection).upsert_one(&SiteHealthReportRow {
+        site_id,
+        app_version,
+        site_datetime,
+        received_datetime,
+        clock_offset_seconds: (site_datetime - received_datetime).num_seconds(),
+        database_size_bytes,
+        free_disk_bytes,
+        processor_error_count,
+        last_processor_error,
+        last_processor_error_datetime,
+        pending_changelog_count,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SiteHealthIssue {
+    /// No health report for `NOT_REPORTING_DAYS`
+    NotReporting,
+    LowFreeDisk,
+    /// Last backup attempt failed
+    BackupFailed,
+    /// No successful backup for `BACKUP_OVERDUE_DAYS`
+    BackupOverdue,
+    ProcessorErrors,
+    PendingChangelogs,
+    ClockOffset,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteHealth {
+    pub site_id: i32,
+    /// Codes of the stores on the site
+    pub store_codes: Vec<String>,
+    pub report: Option<SiteHealthReportRow>,
+    pub backup_status: Option<SiteBackupStatusRow>,
+    pub issues: Vec<SiteHealthIssue>,
+}
+
+/// Latest health of every site that reported health or backup status, on central server
+pub fn get_site_health(connection: &StorageConnection) -> Result<Vec<SiteHealth>, RepositoryError> {
+    let now = Utc::now().naive_utc();
+    let mut reports: HashMap<i32, SiteHealthReportRow> =
+        SiteHealthReportRowRepository::new(connection)
+            .find_all()?
+            .into_iter()
+            .map(|row| (row.site_id, row))
+            .collect();
+    let mut backup_statuses: HashMap<i32, SiteBackupStatusRow> =
+        SiteBackupStatusRowRepository::new(connection)
+            .find_all()?
+            .into_iter()
+            .map(|row| (row.site_id, row))
+            .collect();
+
+    let mut site_ids: Vec<i32> = reports
+        .keys()
+        .chain(backup_statuses.keys())
+        .cloned()
+        .collect();
+    site_ids.sort();
+    site_ids.dedup();
+
+    let mut store_codes: HashMap<i32, Vec<String>> = HashMap::new();
+    for store in StoreRepository::new(connection)
+        .query_by_filter(StoreFilter::new().site_id(EqualFilter::equal_any(site_ids.clone())))?
+    {
+        store_codes
+            .entry(store.store_row.site_id)
+            .or_default()
+            .push(store.store_row.code);
+    }
+
+    Ok(site_ids
+        .into_iter()
+        .map(|site_id| {
+            let report = reports.remove(&site_id);
+            let backup_status = backup_statuses.remove(&site_id);
+            let issues = site_health_issues(now, report.as_ref(), backup_status.as_ref());
+            SiteHealth {
+                site_id,
+                store_codes: store_codes.remove(&site_id).unwrap_or_default(),
+                report,
+                backup_status,
+                issues,
+            }
+        })
+        .collect())
+}
+
+fn site_health_issues(
+    now: NaiveDateTime,
+    report: Option<&SiteHealthReportRow>,
+    backup_status: Option<&SiteBackupStatusRow>,
+) -> Vec<SiteHealthIssue> {
+    let mut issues = Vec::new();
+
+    match report {
+        Some(report) => {
+            if now - report.received_datetime > Duration::days(NOT_REPORTING_DAYS) {
+                issues.push(SiteHealthIssue::NotReporting);
+            }
+            if report
+                .free_disk_bytes
+                .is_some_and(|free_disk_bytes| free_disk_bytes < LOW_FREE_DISK_BYTES)
+            {
+                issues.push(SiteHealthIssue::LowFreeDisk);
+            }
+            if report.processor_error_count > 0 {
+                issues.push(SiteHealthIssue::ProcessorErrors);
+            }
+            if report.pending_changelog_count > PENDING_CHANGELOG_TOLERANCE {
+                issues.push(SiteHealthIssue::PendingChangelogs);
+            }
+            if report.clock_offset_seconds.abs() > CLOCK_OFFSET_TOLERANCE_SECONDS {
+                issues.push(SiteHealthIssue::ClockOffset);
+            }
+        }
+        None => issues.push(SiteHealthIssue::NotReporting),
+    }
+
+    // Sites that don't back up themselves (e.g. backed up via sync) have no backup status
+    if let Some(backup_status) = backup_status {
+        if backup_status.error.is_some() {
+            issues.push(SiteHealthIssue::BackupFailed);
+        }
+        let is_overdue = match backup_status.last_success_datetime {
+            Some(last_success) => now - last_success > Duration::days(BACKUP_OVERDUE_DAYS),
+            None => true,
+        };
+        if is_overdue {
+            issues.push(SiteHealthIssue::BackupOverdue);
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::backup::{upsert_site_backup_status, BackupStatus};
+    use repository::{mock::MockDataInserts, test_db::setup_all, SystemLogRow};
+
+    #[actix_rt::test]
+    async fn test_site_health() {
+        let (_, connection, _, _) = setup_all("test_site_health", MockDataInserts::none()).await;
+
+        SystemLogRowRepository::new(&connection)
+            .insert_one(&SystemLogRow {
+                id: "processor_error".to_string(),
+                r#type: SystemLogType::ProcessorError,
+                datetime: Utc::now().naive_utc(),
+                message: Some("Processor failed".to_string()),
+                is_error: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let report = generate_site_health_report(&connection, 5).unwrap();
+        assert_eq!(report.processor_error_count, 1);
+        assert_eq!(
+            report.last_processor_error,
+            Some("Processor failed".to_string())
+        );
+        assert_eq!(report.pending_changelog_count, 5);
+        assert!(report.database_size_bytes.is_some());
+
+        // Site clock is an hour behind
+        record_site_health_report(
+            &connection,
+            2,
+            SiteHealthReport {
+                site_datetime: report.site_datetime - Duration::hours(1),
+                ..report.clone()
+            },
+        )
+        .unwrap();
+        // Backup status without a health report
+        upsert_site_backup_status(
+            &connection,
+            3,
+            &BackupStatus {
+                last_attempt_datetime: report.site_datetime,
+                last_success_datetime: None,
+                last_backup_name: None,
+                is_verified: false,
+                error: Some("Backup failed".to_string()),
+            },
+        )
+        .unwrap();
+
+        let site_health = get_site_health(&connection).unwrap();
+        assert_eq!(site_health.len(), 2);
+
+        let site_2 = &site_health[0];
+        assert_eq!(site_2.site_id, 2);
+        assert!(site_2.report.as_ref().unwrap().clock_offset_seconds <= -3600);
+        assert!(site_2.issues.contains(&SiteHealthIssue::ClockOffset));
+        assert!(site_2.issues.contains(&SiteHealthIssue::ProcessorErrors));
+        assert!(!site_2.issues.contains(&SiteHealthIssue::NotReporting));
+
+        let site_3 = &site_health[1];
+        assert_eq!(site_3.site_id, 3);
+        assert_eq!(
+            site_3.issues,
+            vec![
+                SiteHealthIssue::NotReporting,
+                SiteHealthIssue::BackupFailed,
+                SiteHealthIssue::BackupOverdue
+            ]
+        );
+    }
+}
@@ -1,11 +1,13 @@
-use self::query::{get_stock_line, get_stock_lines, get_items_by_stock_line_filter};
+use self::query::{
+    get_items_by_stock_line_filter, get_stock_line, get_stock_lines, get_stock_lines_after,
+};
 
 use super::{ListError, ListResult};
 use crate::{service_provider::ServiceContext, SingleRecordError};
 use chrono::NaiveDateTime;
 use historical_stock::get_historical_stock_lines;
 use repository::{
-    Item, ItemSort, PaginationOption, StockLine, StockLineFilter, StockLineSort,
+    Item, ItemSort, Keyset, PaginationOption, StockLine, StockLineFilter, StockLineSort,
 };
 
 pub mod historical_stock;
@@ -25,6 +27,17 @@ pub trait StockLineServiceTrait: Sync + Send {
         get_stock_lines(ctx, pagination, filter, sort, store_id)
     }
 
+    fn get_stock_lines_after(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        after: Option<Keyset<String>>,
+        limit: u32,
+        filter: Option<StockLineFilter>,
+    ) -> Result<ListResult<StockLine>, ListError> {
+        get_stock_lines_after(ctx, store_id, after, limit, filter)
+    }
+
     fn get_stock_line(
         &self,
         ctx: &ServiceContext,
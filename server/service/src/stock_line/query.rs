@@ -3,8 +3,8 @@ use crate::{
     Pagination, SingleRecordError,
 };
 use repository::{
-    EqualFilter, Item, ItemSort, PaginationOption, StockLine, StockLineFilter, StockLineRepository,
-    StockLineSort,
+    EqualFilter, Item, ItemSort, Keyset, PaginationOption, StockLine, StockLineFilter,
+    StockLineRepository, StockLineSort,
 };
 
 pub const MAX_LIMIT: u32 = 5000;
@@ -41,6 +41,24 @@ pub fn get_stock_lines(
     })
 }
 
+/// Page of the store's stock lines after `after` by item name, for keyset (cursor) paging. Reads
+/// `limit` rows
+pub fn get_stock_lines_after(
+    ctx: &ServiceContext,
+    store_id: &str,
+    after: Option<Keyset<String>>,
+    limit: u32,
+    filter: Option<StockLineFilter>,
+) -> Result<ListResult<StockLine>, ListError> {
+    let repository = StockLineRepository::new(&ctx.connection);
+    let store_id = Some(store_id.to_string());
+
+    Ok(ListResult {
+        rows: repository.query_after(filter.clone(), store_id.clone(), after, limit)?,
+        count: i64_to_u32(repository.count(filter, store_id)?),
+    })
+}
+
 /// Returns items that have at least one stock_line matching `filter` in
 /// `store_id`. Companion to `get_stock_lines` — same filter shape, but the
 /// result is one row per item (sorted by item attributes), so it's safe to
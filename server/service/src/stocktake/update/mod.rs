@@ -14,6 +14,10 @@ use repository::{
 
 use crate::{
     activity_log::activity_log_entry,
+    backend_plugin::{
+        plugin_provider::{PluginError, PluginInstance},
+        types::validate_mutation::{PluginValidationFailed, ProposedRows, ValidateMutationError},
+    },
     invoice_line::{
         stock_in_line::{insert_stock_in_line, InsertStockInLineError},
         stock_out_line::{insert_stock_out_line, InsertStockOutLineError},
@@ -61,6 +65,8 @@ pub enum UpdateStocktakeError {
     /// Holds list of affected stock lines
     SnapshotCountCurrentCountMismatch(Vec<StocktakeLine>),
     StockLinesReducedBelowZero(Vec<StockLine>),
+    PluginError(PluginError),
+    PluginValidationFailed(PluginValidationFailed),
 }
 
 pub fn update_stocktake(
@@ -156,6 +162,12 @@ pub fn update_stocktake(
             }
 
             if status_changed {
+                PluginInstance::validate_mutation(
+                    ctx,
+                    &ctx.store_id,
+                    ProposedRows::stocktake(connection, result.stocktake.clone())?,
+                )?;
+
                 activity_log_entry(
                     ctx,
                     ActivityLogType::StocktakeStatusFinalised,
@@ -175,6 +187,17 @@ pub fn update_stocktake(
     Ok(result)
 }
 
+impl From<ValidateMutationError> for UpdateStocktakeError {
+    fn from(error: ValidateMutationError) -> Self {
+        match error {
+            ValidateMutationError::PluginError(error) => UpdateStocktakeError::PluginError(error),
+            ValidateMutationError::Failed(failed) => {
+                UpdateStocktakeError::PluginValidationFailed(failed)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use chrono::NaiveDate;
@@ -931,4 +954,110 @@ mod test {
             Some(mock_immunisation_program_a().id)
         );
     }
+
+    #[actix_rt::test]
+    async fn update_stocktake_validate_mutation_plugin() {
+        use crate::backend_plugin::{
+            plugin_provider::PluginInstance,
+            types::validate_mutation::{PluginValidationFailed, PluginValidationMessage},
+        };
+        use base64::{prelude::BASE64_STANDARD, Engine};
+        use repository::{
+            migrations::Version, BackendPluginRow, PluginType, PluginTypes, StocktakeRowRepository,
+        };
+
+        let (_, connection, connection_manager, _) = setup_all(
+            "update_stocktake_validate_mutation_plugin",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        // Plugin only acts on stocktakes with specific comments, other tests are unaffected
+        let bundle = r#"
+            export const plugins = {
+                validate_mutation: ({ proposed }) => {
+                    if (proposed.t !== 'Stocktake') return {};
+                    const { stocktake, lines } = proposed.v;
+                    const warning = { code: 'WARN', message: 'Check counts' };
+                    if (stocktake.comment === 'plugin warn') return { warnings: [warning] };
+                    if (stocktake.comment !== 'plugin reject') return {};
+                    return {
+                        warnings: [warning],
+                        errors: [{ code: 'REJECT', message: 'Rejected', record_id: lines[0].id }],
+                    };
+                },
+            };
+        "#;
+        PluginInstance::bind(BackendPluginRow {
+            id: "validate_mutation_test".to_string(),
+            code: "validate_mutation_test".to_string(),
+            version: Version::from_package_json().to_string(),
+            bundle_base64: BASE64_STANDARD.encode(bundle),
+            types: PluginTypes(vec![PluginType::ValidateMutation]),
+            ..Default::default()
+        });
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider
+            .context(mock_store_a().id, "".to_string())
+            .unwrap();
+        let service = service_provider.stocktake_service;
+
+        // Blocking error
+        let result = service.update_stocktake(
+            &context,
+            UpdateStocktake {
+                id: mock_stocktake_stock_surplus().id,
+                comment: Some("plugin reject".to_string()),
+                status: Some(UpdateStocktakeStatus::Finalised),
+                ..Default::default()
+            },
+        );
+        let message = |code: &str, text: &str, record_id: Option<String>| PluginValidationMessage {
+            plugin_code: "validate_mutation_test".to_string(),
+            code: code.to_string(),
+            message: text.to_string(),
+            record_id,
+        };
+        assert_eq!(
+            result,
+            Err(UpdateStocktakeError::PluginValidationFailed(
+                PluginValidationFailed {
+                    errors: vec![message(
+                        "REJECT",
+                        "Rejected",
+                        Some(mock_stocktake_line_stock_surplus().id)
+                    )],
+                    warnings: vec![message("WARN", "Check counts", None)],
+                }
+            ))
+        );
+        // Transaction was rolled back
+        let stocktake = StocktakeRowRepository::new(&connection)
+            .find_one_by_id(&mock_stocktake_stock_surplus().id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stocktake.status, StocktakeStatus::New);
+        // Warnings of rejected mutation are returned with the error only
+        assert_eq!(context.take_plugin_warnings(), vec![]);
+
+        // Warnings only
+        let result = service
+            .update_stocktake(
+                &context,
+                UpdateStocktake {
+                    id: mock_stocktake_stock_surplus().id,
+                    comment: Some("plugin warn".to_string()),
+                    status: Some(UpdateStocktakeStatus::Finalised),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.status, StocktakeStatus::Finalised);
+        assert_eq!(
+            context.take_plugin_warnings(),
+            vec![message("WARN", "Check counts", None)]
+        );
+        assert_eq!(context.take_plugin_warnings(), vec![]);
+    }
 }
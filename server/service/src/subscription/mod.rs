@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use repository::SyncLogRow;
+use repository::{ChangelogRepository, SyncLogRow};
 use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
@@ -9,6 +9,9 @@ use tokio::time::Instant;
 use crate::service_provider::ServiceProvider;
 use crate::sync::sync_status::status::{FullSyncStatus, InitialisationStatus};
 
+mod store_event;
+pub use store_event::*;
+
 const CHANNEL_BUFFER_SIZE: usize = 64;
 const PUSH_QUEUE_DEBOUNCE: Duration = Duration::from_secs(30);
 
@@ -20,6 +23,8 @@ pub enum SubscriptionTrigger {
     SyncStatus(SyncLogRow),
     /// Changelogs were inserted (mutations created/modified data)
     PushQueueChanged,
+    /// Changelogs were inserted for records resolved into store events
+    StoreRecordsChanged,
 }
 
 // ── Resolved events (outbound from worker to subscribers) ──
@@ -32,6 +37,7 @@ pub enum ResolvedSubscription {
         push_queue_count: u64,
     },
     InitialisationStatus(InitialisationStatus),
+    StoreEvent(StoreEvent),
 }
 
 #[derive(Clone)]
@@ -109,6 +115,17 @@ async fn subscription_worker_loop(
     let mut last_push_query = Instant::now() - PUSH_QUEUE_DEBOUNCE;
     let mut push_queue_queued = false;
     let trigger_handle = service_provider.subscription_trigger.clone();
+    // Only changes committed after startup are resolved into store events
+    let mut store_event_cursor = service_provider
+        .basic_context()
+        .ok()
+        .and_then(|ctx| {
+            ChangelogRepository::new(&ctx.connection)
+                .latest_cursor()
+                .ok()
+        })
+        .unwrap_or(0)
+        + 1;
 
     loop {
         let Some(trigger) = rx.recv().await else {
@@ -191,6 +208,25 @@ async fn subscription_worker_loop(
                     });
                 }
             }
+
+            SubscriptionTrigger::StoreRecordsChanged => {
+                let ctx = match service_provider.basic_context() {
+                    Ok(ctx) => ctx,
+                    Err(e) => {
+                        log::error!("Failed to get DB connection for store events: {e:?}");
+                        continue;
+                    }
+                };
+                match resolve_store_events(&ctx.connection, store_event_cursor) {
+                    Ok((events, cursor)) => {
+                        store_event_cursor = cursor;
+                        for event in events {
+                            let _ = tx.send(ResolvedSubscription::StoreEvent(event));
+                        }
+                    }
+                    Err(e) => log::error!("Failed to resolve store events: {e:?}"),
+                }
+            }
         }
     }
 }
@@ -0,0 +1,344 @@
+use repository::{
+    ActivityLogRowRepository, ActivityLogType, ChangelogFilter, ChangelogRepository, ChangelogRow,
+    ChangelogTableName, EqualFilter, Invoice, InvoiceFilter, InvoiceRepository, InvoiceType,
+    RepositoryError, Requisition, RequisitionFilter, RequisitionRepository, RequisitionType,
+    RowActionType, StockLine, StockLineFilter, StockLineRepository, StorageConnection,
+    TemperatureBreach, TemperatureBreachFilter, TemperatureBreachRepository,
+};
+use strum::IntoEnumIterator;
+
+const CHANGELOG_BATCH_SIZE: u32 = 500;
+
+/// Store scoped domain event, resolved from changelogs committed since the last resolution
+#[derive(Clone, Debug)]
+pub enum StoreEvent {
+    InvoiceCreated(Invoice),
+    InvoiceStatusChanged(Invoice),
+    /// Inbound shipment generated for this store from another store's outbound shipment
+    InboundShipmentFromTransfer(Invoice),
+    /// Response requisition generated for this store from another store's request requisition
+    RequisitionReceived(Requisition),
+    StockLineChanged(StockLine),
+    TemperatureBreachCreated(TemperatureBreach),
+}
+
+impl StoreEvent {
+    pub fn store_id(&self) -> &str {
+        match self {
+            StoreEvent::InvoiceCreated(invoice)
+            | StoreEvent::InvoiceStatusChanged(invoice)
+            | StoreEvent::InboundShipmentFromTransfer(invoice) => &invoice.invoice_row.store_id,
+            StoreEvent::RequisitionReceived(requisition) => &requisition.requisition_row.store_id,
+            StoreEvent::StockLineChanged(stock_line) => &stock_line.stock_line_row.store_id,
+            StoreEvent::TemperatureBreachCreated(breach) => &breach.temperature_breach_row.store_id,
+        }
+    }
+}
+
+/// Resolves store events from changelogs starting at `cursor`, returns the events and the cursor
+/// to resolve from next time
+pub fn resolve_store_events(
+    connection: &StorageConnection,
+    cursor: u64,
+) -> Result<(Vec<StoreEvent>, u64), RepositoryError> {
+    let filter = ChangelogFilter::new()
+        .table_name(EqualFilter {
+            equal_any: Some(
+                ChangelogTableName::iter()
+                    .filter(ChangelogTableName::is_store_event)
+                    .collect(),
+            ),
+            ..Default::default()
+        })
+        .action(RowActionType::Upsert.equal_to());
+
+    let changelog_repo = ChangelogRepository::new(connection);
+    let start_cursor = cursor;
+    let mut cursor = cursor;
+    let mut events = Vec::new();
+
+    loop {
+        let logs = changelog_repo.changelogs(cursor, CHANGELOG_BATCH_SIZE, Some(filter.clone()))?;
+        let Some(last) = logs.last() else {
+            break;
+        };
+        cursor = last.cursor as u64 + 1;
+
+        for log in logs {
+            if log.store_id.is_none() {
+                continue;
+            }
+            if let Some(event) = store_event(connection, &log, start_cursor)? {
+                events.push(event);
+            }
+        }
+    }
+
+    Ok((events, cursor))
+}
+
+fn store_event(
+    connection: &StorageConnection,
+    log: &ChangelogRow,
+    start_cursor: u64,
+) -> Result<Option<StoreEvent>, RepositoryError> {
+    let event = match log.table_name {
+        ChangelogTableName::ActivityLog => {
+            let Some(activity_log) =
+                ActivityLogRowRepository::new(connection).find_one_by_id(&log.record_id)?
+            else {
+                return Ok(None);
+            };
+            let Some(record_id) = activity_log.record_id else {
+                return Ok(None);
+            };
+
+            match activity_log.r#type {
+                ActivityLogType::InvoiceCreated | ActivityLogType::PrescriptionCreated => {
+                    invoice(connection, &record_id)?.map(|invoice| {
+                        let is_transfer = invoice.invoice_row.r#type
+                            == InvoiceType::InboundShipment
+                            && invoice.invoice_row.linked_invoice_id.is_some();
+                        if is_transfer {
+                            StoreEvent::InboundShipmentFromTransfer(invoice)
+                        } else {
+                            StoreEvent::InvoiceCreated(invoice)
+                        }
+                    })
+                }
+                ActivityLogType::InvoiceStatusAllocated
+                | ActivityLogType::InvoiceStatusPicked
+                | ActivityLogType::InvoiceStatusShipped
+                | ActivityLogType::InvoiceStatusDelivered
+                | ActivityLogType::InvoiceStatusReceived
+                | ActivityLogType::InvoiceStatusVerified
+                | ActivityLogType::InvoiceStatusCancelled
+                | ActivityLogType::PrescriptionStatusPicked
+                | ActivityLogType::PrescriptionStatusVerified
+                | ActivityLogType::PrescriptionStatusCancelled => {
+                    invoice(connection, &record_id)?.map(StoreEvent::InvoiceStatusChanged)
+                }
+                ActivityLogType::RequisitionCreated => RequisitionRepository::new(connection)
+                    .query_one(
+                        RequisitionFilter::new().id(EqualFilter::equal_to(record_id.to_string())),
+                    )?
+                    .filter(|requisition| {
+                        requisition.requisition_row.r#type == RequisitionType::Response
+                            && requisition.requisition_row.linked_requisition_id.is_some()
+                    })
+                    .map(StoreEvent::RequisitionReceived),
+                _ => None,
+            }
+        }
+        ChangelogTableName::StockLine => StockLineRepository::new(connection)
+            .query_by_filter(
+                StockLineFilter::new().id(EqualFilter::equal_to(log.record_id.to_string())),
+                log.store_id.clone(),
+            )?
+            .pop()
+            .map(StoreEvent::StockLineChanged),
+        ChangelogTableName::TemperatureBreach => {
+            // Breaches are updated when acknowledged or when they end, only the first change is new
+            let first_cursor = first_changelog_cursor(connection, &log.record_id)?;
+            if first_cursor < start_cursor {
+                return Ok(None);
+            }
+            TemperatureBreachRepository::new(connection)
+                .query_by_filter(
+                    TemperatureBreachFilter::new()
+                        .id(EqualFilter::equal_to(log.record_id.to_string())),
+                )?
+                .pop()
+                .map(StoreEvent::TemperatureBreachCreated)
+        }
+        _ => None,
+    };
+
+    Ok(event)
+}
+
+fn invoice(connection: &StorageConnection, id: &str) -> Result<Option<Invoice>, RepositoryError> {
+    InvoiceRepository::new(connection)
+        .query_one(InvoiceFilter::new().id(EqualFilter::equal_to(id.to_string())))
+}
+
+fn first_changelog_cursor(
+    connection: &StorageConnection,
+    record_id: &str,
+) -> Result<u64, RepositoryError> {
+    let first_cursor = ChangelogRepository::new(connection).first_cursor(record_id)?;
+    Ok(first_cursor.unwrap_or_default() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use repository::{
+        mock::{mock_item_a, mock_name_store_b, mock_store_a, MockDataInserts},
+        test_db::setup_all,
+        ActivityLogRow, ActivityLogRowRepository, ActivityLogType, ChangelogRepository, InvoiceRow,
+        InvoiceRowRepository, InvoiceStatus, InvoiceType, SensorRow, SensorRowRepository,
+        StockLineRow, StockLineRowRepository, TemperatureBreachRow, TemperatureBreachRowRepository,
+    };
+
+    use super::{resolve_store_events, StoreEvent};
+
+    fn activity_log(id: &str, r#type: ActivityLogType, record_id: &str) -> ActivityLogRow {
+        ActivityLogRow {
+            id: id.to_string(),
+            r#type,
+            store_id: Some(mock_store_a().id),
+            record_id: Some(record_id.to_string()),
+            datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_resolve_store_events() {
+        let sensor = SensorRow {
+            id: "sensor".to_string(),
+            serial: "serial".to_string(),
+            store_id: mock_store_a().id,
+            ..Default::default()
+        };
+        let (_, connection, _, _) = setup_all(
+            "test_resolve_store_events",
+            MockDataInserts::none().names().stores().units().items(),
+        )
+        .await;
+        SensorRowRepository::new(&connection)
+            .upsert_one(&sensor)
+            .unwrap();
+
+        let invoice = |id: &str, r#type: InvoiceType, linked_invoice_id: Option<&str>| InvoiceRow {
+            id: id.to_string(),
+            name_id: mock_name_store_b().id,
+            store_id: mock_store_a().id,
+            r#type,
+            status: InvoiceStatus::New,
+            linked_invoice_id: linked_invoice_id.map(str::to_string),
+            created_datetime: Utc::now().naive_utc(),
+            ..Default::default()
+        };
+        let invoice_repo = InvoiceRowRepository::new(&connection);
+        let log_repo = ActivityLogRowRepository::new(&connection);
+        let cursor = ChangelogRepository::new(&connection)
+            .latest_cursor()
+            .unwrap()
+            + 1;
+
+        // Invoice created, then its status changed
+        invoice_repo
+            .upsert_one(&invoice("outbound", InvoiceType::OutboundShipment, None))
+            .unwrap();
+        log_repo
+            .insert_one(&activity_log(
+                "created",
+                ActivityLogType::InvoiceCreated,
+                "outbound",
+            ))
+            .unwrap();
+        log_repo
+            .insert_one(&activity_log(
+                "picked",
+                ActivityLogType::InvoiceStatusPicked,
+                "outbound",
+            ))
+            .unwrap();
+        // Inbound shipment from transfer
+        invoice_repo
+            .upsert_one(&invoice(
+                "inbound",
+                InvoiceType::InboundShipment,
+                Some("outbound"),
+            ))
+            .unwrap();
+        log_repo
+            .insert_one(&activity_log(
+                "transfer",
+                ActivityLogType::InvoiceCreated,
+                "inbound",
+            ))
+            .unwrap();
+        // Activity not resolved into an event
+        log_repo
+            .insert_one(&activity_log(
+                "number",
+                ActivityLogType::InvoiceNumberAllocated,
+                "inbound",
+            ))
+            .unwrap();
+        // Stock line change
+        StockLineRowRepository::new(&connection)
+            .upsert_one(&StockLineRow {
+                id: "stock_line".to_string(),
+                item_link_id: mock_item_a().id,
+                store_id: mock_store_a().id,
+                pack_size: 1.0,
+                ..Default::default()
+            })
+            .unwrap();
+        // New temperature breach
+        let breach = TemperatureBreachRow {
+            id: "breach".to_string(),
+            sensor_id: sensor.id.clone(),
+            store_id: mock_store_a().id,
+            start_datetime: Utc::now().naive_utc(),
+            unacknowledged: true,
+            ..Default::default()
+        };
+        let breach_repo = TemperatureBreachRowRepository::new(&connection);
+        breach_repo.upsert_one(&breach).unwrap();
+
+        let (events, next_cursor) = resolve_store_events(&connection, cursor).unwrap();
+        let events: Vec<(&str, &str)> = events
+            .iter()
+            .map(|event| match event {
+                StoreEvent::InvoiceCreated(invoice) => ("created", invoice.invoice_row.id.as_str()),
+                StoreEvent::InvoiceStatusChanged(invoice) => {
+                    ("status", invoice.invoice_row.id.as_str())
+                }
+                StoreEvent::InboundShipmentFromTransfer(invoice) => {
+                    ("transfer", invoice.invoice_row.id.as_str())
+                }
+                StoreEvent::RequisitionReceived(requisition) => {
+                    ("requisition", requisition.requisition_row.id.as_str())
+                }
+                StoreEvent::StockLineChanged(stock_line) => {
+                    ("stock_line", stock_line.stock_line_row.id.as_str())
+                }
+                StoreEvent::TemperatureBreachCreated(breach) => {
+                    ("breach", breach.temperature_breach_row.id.as_str())
+                }
+            })
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                ("created", "outbound"),
+                ("status", "outbound"),
+                ("transfer", "inbound"),
+                ("stock_line", "stock_line"),
+                ("breach", "breach"),
+            ]
+        );
+        assert_eq!(
+            next_cursor,
+            ChangelogRepository::new(&connection)
+                .latest_cursor()
+                .unwrap()
+                + 1
+        );
+
+        // Acknowledging a breach is not a new breach
+        breach_repo
+            .upsert_one(&TemperatureBreachRow {
+                unacknowledged: false,
+                ..breach
+            })
+            .unwrap();
+        let (events, _) = resolve_store_events(&connection, next_cursor).unwrap();
+        assert!(events.is_empty());
+    }
+}
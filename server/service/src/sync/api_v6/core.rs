@@ -154,6 +154,15 @@ impl SyncApiV6 {
     }
 
     pub async fn get_site_status(&self) -> Result<SiteStatusV6, SyncApiErrorV6> {
+        self.post_site_status(None, None).await
+    }
+
+    /// Site status, also reporting the status of the site's last backup and site health
+    pub async fn post_site_status(
+        &self,
+        backup_status: Option<BackupStatus>,
+        health_report: Option<SiteHealthReport>,
+    ) -> Result<SiteStatusV6, SyncApiErrorV6> {
         let Self {
             sync_v5_settings,
             url,
@@ -166,6 +175,8 @@ impl SyncApiV6 {
         let request = SiteStatusRequestV6 {
             sync_v5_settings: sync_v5_settings.clone(),
             sync_v6_version: *sync_v6_version,
+            backup_status,
+            health_report,
         };
 
         let result = with_retries(RetrySeconds::default(), |client| {
@@ -185,4 +196,42 @@ impl SyncApiV6 {
             source: error,
         })
     }
+
+    /// Anchors of the store's activity log chain that reached the central server
+    pub async fn get_activity_log_chain_anchors(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<ActivityLogChainAnchorRow>, SyncApiErrorV6> {
+        let Self {
+            sync_v5_settings,
+            url,
+            sync_v6_version,
+        } = self;
+
+        let route = "activity_log_chain_anchors";
+        let url = url.join(route).unwrap();
+
+        let request = ActivityLogChainAnchorsRequestV6 {
+            sync_v5_settings: sync_v5_settings.clone(),
+            sync_v6_version: *sync_v6_version,
+            store_id: store_id.to_string(),
+        };
+
+        let result = with_retries(RetrySeconds::default(), |client| {
+            client.post(url.clone()).json(&request)
+        })
+        .await;
+
+        let error = match response_or_err(result).await {
+            Ok(ActivityLogChainAnchorsResponseV6::Data(data)) => return Ok(data),
+            Ok(ActivityLogChainAnchorsResponseV6::Error(error)) => error.into(),
+            Err(error) => error,
+        };
+
+        Err(SyncApiErrorV6 {
+            url,
+            route: route.to_string(),
+            source: error,
+        })
+    }
 }
@@ -1,13 +1,13 @@
 mod core;
 pub mod download_file;
 pub mod upload_file;
-use repository::RepositoryError;
+use repository::{ActivityLogChainAnchorRow, RepositoryError};
 use reqwest::{Response, Url};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use util::format_error;
 
-use crate::i64_to_u64;
+use crate::{backup::BackupStatus, i64_to_u64, site_health::SiteHealthReport};
 
 pub use self::core::*;
 
@@ -107,6 +107,13 @@ pub enum SiteStatusResponseV6 {
     Error(SyncParsedErrorV6),
 }
 
+#[derive(Deserialize, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivityLogChainAnchorsResponseV6 {
+    Data(Vec<ActivityLogChainAnchorRow>),
+    Error(SyncParsedErrorV6),
+}
+
 #[derive(Error, Debug)]
 #[error("Sync api error, url: '{url}', route: '{route}'")]
 pub struct SyncApiErrorV6 {
@@ -192,6 +199,12 @@ pub struct SiteStatusRequestV6 {
     pub(crate) sync_v5_settings: SyncApiSettings,
     #[serde(default)]
     pub(crate) sync_v6_version: u32,
+    /// Status of the site's last backup, recorded on central server
+    #[serde(default)]
+    pub(crate) backup_status: Option<BackupStatus>,
+    /// Recorded on central server
+    #[serde(default)]
+    pub(crate) health_report: Option<SiteHealthReport>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -199,6 +212,17 @@ pub struct SiteStatusV6 {
     pub(crate) is_integrating: bool,
 }
 
+/// Anchors of a store's activity log chain held by the central server
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityLogChainAnchorsRequestV6 {
+    pub(crate) sync_v5_settings: SyncApiSettings,
+    #[serde(default)]
+    pub(crate) sync_v6_version: u32,
+    /// Must be a store of the requesting site
+    pub(crate) store_id: String,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncDownloadFileRequestV6 {
@@ -1,7 +1,9 @@
 use std::time::{Duration, SystemTime};
 
 use crate::{
+    backup::get_backup_status,
     cursor_controller::CursorController,
+    site_health::generate_site_health_report,
     sync::{
         api_v6::{SyncBatchV6, SyncRecordV6},
         sync_status::logger::SyncStepProgress,
@@ -58,6 +60,16 @@ pub(crate) enum WaitForSyncOperationErrorV6 {
     TimeoutReached,
 }
 
+#[derive(Error, Debug)]
+pub(crate) enum ReportSiteStatusErrorV6 {
+    #[error(transparent)]
+    SyncApiError(#[from] SyncApiErrorV6),
+    #[error("Database error")]
+    DatabaseError(#[from] RepositoryError),
+    #[error("Problem getting changelog filter")]
+    SyncChangelogError(#[from] SyncChangelogError),
+}
+
 pub(crate) struct SynchroniserV6 {
     sync_api_v6: SyncApiV6,
 }
@@ -241,6 +253,29 @@ impl SynchroniserV6 {
         Ok(())
     }
 
+    /// Reports site health, and the status of the last backup (if any backup was attempted), to
+    /// the central server
+    pub(crate) async fn report_site_status(
+        &self,
+        connection: &StorageConnection,
+    ) -> Result<(), ReportSiteStatusErrorV6> {
+        // Legacy and open mSupply central servers are pushed to with their own cursors, a change
+        // is pending until both have it (the legacy push runs after this report)
+        let legacy_push_cursor =
+            CursorController::new(KeyType::RemoteSyncPushCursor).get(connection)?;
+        let push_cursor = CursorController::new(KeyType::SyncPushCursorV6).get(connection)?;
+        let pending_changelog_count = ChangelogRepository::new(connection).count(
+            legacy_push_cursor.min(push_cursor),
+            get_sync_push_changelogs_filter(connection)?,
+        )?;
+        let health_report = generate_site_health_report(connection, pending_changelog_count)?;
+
+        self.sync_api_v6
+            .post_site_status(get_backup_status(connection)?, Some(health_report))
+            .await?;
+        Ok(())
+    }
+
     pub(crate) async fn wait_for_sync_operation(
         &self,
         poll_period_seconds: u64,
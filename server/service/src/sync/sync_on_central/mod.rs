@@ -5,15 +5,18 @@ use std::{
 
 use actix_multipart::form::tempfile::TempFile;
 use repository::{
-    ChangelogRepository, SyncBufferRowRepository, SyncFileReferenceRow,
+    ActivityLogChainAnchorRow, ActivityLogChainAnchorRowRepository, ChangelogRepository,
+    StoreRowRepository, SyncBufferRowRepository, SyncFileReferenceRow,
     SyncFileReferenceRowRepository,
 };
 use util::format_error;
 
 use crate::{
+    backup::upsert_site_backup_status,
     processors::ProcessorType,
     service_provider::ServiceProvider,
     settings::Settings,
+    site_health::record_site_health_report,
     static_files::{StaticFile, StaticFileCategory, StaticFileService},
     sync::{
         api::{validate_site_auth, CommonSyncRecord},
@@ -27,9 +30,9 @@ use crate::{
 
 use super::{
     api_v6::{
-        SiteStatusRequestV6, SyncBatchV6, SyncDownloadFileRequestV6, SyncParsedErrorV6,
-        SyncPatientPullRequestV6, SyncPullRequestV6, SyncPushRequestV6, SyncPushSuccessV6,
-        SyncRecordV6, SyncUploadFileRequestV6,
+        ActivityLogChainAnchorsRequestV6, SiteStatusRequestV6, SyncBatchV6,
+        SyncDownloadFileRequestV6, SyncParsedErrorV6, SyncPatientPullRequestV6, SyncPullRequestV6,
+        SyncPushRequestV6, SyncPushSuccessV6, SyncRecordV6, SyncUploadFileRequestV6,
     },
     translations::translate_changelogs_to_sync_records,
 };
@@ -280,6 +283,8 @@ pub async fn get_site_status(
     SiteStatusRequestV6 {
         sync_v5_settings,
         sync_v6_version,
+        backup_status,
+        health_report,
     }: SiteStatusRequestV6,
 ) -> Result<SiteStatusV6, SyncParsedErrorV6> {
     use SyncParsedErrorV6 as Error;
@@ -301,11 +306,59 @@ pub async fn get_site_status(
         .await
         .map_err(|e| Error::OtherServerError(format_error(&e)))?;
 
+    if let Some(backup_status) = backup_status {
+        upsert_site_backup_status(&ctx.connection, response.site_id, &backup_status)?;
+    }
+    if let Some(health_report) = health_report {
+        record_site_health_report(&ctx.connection, response.site_id, health_report)?;
+    }
+
     let is_integrating = is_integrating(response.site_id);
 
     Ok(SiteStatusV6 { is_integrating })
 }
 
+/// Anchors central holds for a store of the requesting site, the site compares its activity log
+/// chain with them as they can't be rewritten along with the site's database
+pub async fn get_activity_log_chain_anchors(
+    service_provider: &ServiceProvider,
+    ActivityLogChainAnchorsRequestV6 {
+        sync_v5_settings,
+        sync_v6_version,
+        store_id,
+    }: ActivityLogChainAnchorsRequestV6,
+) -> Result<Vec<ActivityLogChainAnchorRow>, SyncParsedErrorV6> {
+    use SyncParsedErrorV6 as Error;
+
+    if !CentralServerConfig::is_central_server() {
+        return Err(Error::NotACentralServer);
+    }
+
+    if !is_sync_version_compatible(sync_v6_version) {
+        return Err(Error::SyncVersionMismatch(
+            MIN_VERSION,
+            MAX_VERSION,
+            sync_v6_version,
+        ));
+    }
+
+    let ctx = service_provider.basic_context()?;
+    let response = validate_site_auth(&ctx, &sync_v5_settings)
+        .await
+        .map_err(|e| Error::OtherServerError(format_error(&e)))?;
+
+    let store = StoreRowRepository::new(&ctx.connection).find_one_by_id(&store_id)?;
+    if store.map(|store| store.site_id) != Some(response.site_id) {
+        return Err(Error::OtherServerError(format!(
+            "Store {store_id} is not a store of site {}",
+            response.site_id
+        )));
+    }
+
+    Ok(ActivityLogChainAnchorRowRepository::new(&ctx.connection)
+        .find_many_by_store_id(&store_id)?)
+}
+
 fn spawn_integration(service_provider: Arc<ServiceProvider>, site_id: i32) {
     tokio::spawn(async move {
         let ctx = match service_provider.basic_context() {
@@ -1,4 +1,6 @@
 use crate::{
+    activity_log_chain::anchor_activity_log_chains,
+    backup::{clear_resync_after_restore, is_resync_after_restore},
     processors::ProcessorType,
     service_provider::{ServiceContext, ServiceProvider},
     sync::{sync_buffer::SyncBufferSource, sync_status::logger::SyncStep, CentralServerConfig},
@@ -187,9 +189,14 @@ impl Synchroniser {
         // Initialisation request was sent and successfully processed
         let is_sync_queue_initialised = sync_status_service.is_sync_queue_initialised(ctx)?;
 
+        // Database was restored from a backup, records of this site are pulled again and nothing
+        // is pushed until they are integrated (see `apply_restore_marker`)
+        let is_resync_after_restore = is_resync_after_restore(&ctx.connection)?;
+        let should_push = is_initialised && !is_resync_after_restore;
+
         // REQUEST INITIALISATION
         logger.start_step(SyncStep::PrepareInitial)?;
-        if !is_sync_queue_initialised {
+        if !is_sync_queue_initialised || is_resync_after_restore {
             self.remote.request_initialisation(&site_info).await?;
         }
         logger.done_step(SyncStep::PrepareInitial)?;
@@ -211,7 +218,8 @@ impl Synchroniser {
 
         // PUSH V6
         logger.start_step(SyncStep::PushCentralV6)?;
-        if let (true, Some(v6_sync)) = (is_initialised, &v6_sync) {
+        if let (true, Some(v6_sync)) = (should_push, &v6_sync) {
+            anchor_activity_log_chains(&ctx.connection)?;
             v6_sync
                 .push(&ctx.connection, batch_size.remote_push, logger)
                 .await?;
@@ -225,10 +233,16 @@ impl Synchroniser {
         }
         logger.done_step(SyncStep::PushCentralV6)?;
 
+        if let Some(v6_sync) = &v6_sync {
+            if let Err(error) = v6_sync.report_site_status(&ctx.connection).await {
+                warn!("Failed to report site status {}", format_error(&error));
+            }
+        }
+
         // PUSH
         // Only push if initialised (site data was initialised on central and successfully pulled)
         logger.start_step(SyncStep::Push)?;
-        if is_initialised {
+        if should_push {
             self.remote
                 .push(&ctx.connection, batch_size.remote_push, logger)
                 .await?;
@@ -271,7 +285,7 @@ impl Synchroniser {
                         .pull(
                             &ctx.connection,
                             batch_size.central_pull,
-                            is_initialised,
+                            is_initialised && !is_resync_after_restore,
                             logger,
                         )
                         .await?;
@@ -298,6 +312,11 @@ impl Synchroniser {
 
         logger.done_step(SyncStep::Integrate)?;
 
+        // Changes that were pending when the backup was taken are pushed with the next sync
+        if is_resync_after_restore {
+            clear_resync_after_restore(&ctx.connection)?;
+        }
+
         if !is_initialised {
             self.remote.advance_push_cursor(&ctx.connection)?;
             if let Some(v6_sync) = &v6_sync {
@@ -329,6 +348,9 @@ impl Synchroniser {
         ctx.processors_trigger
             .trigger_processor(ProcessorType::RequisitionAutoFinalise);
 
+        ctx.processors_trigger
+            .trigger_processor(ProcessorType::RecallStockOnHold);
+
         Ok(())
     }
 }
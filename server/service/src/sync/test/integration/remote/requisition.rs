@@ -46,6 +46,7 @@ impl SyncRecordTester for RequisitionRecordTester {
             order_type: None,
             is_emergency: false,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         };
         let requisition_row_1 = base_requisition_row.clone();
@@ -1,4 +1,4 @@
-use repository::{BackendPluginRow, PluginType, PluginTypes, PluginVariantType};
+use repository::{BackendPluginRow, PluginManifest, PluginType, PluginTypes, PluginVariantType};
 use serde_json::json;
 
 // Data in this file is used in "test_backend_plugin_translation" and "test_sync_pull_and_push"
@@ -26,6 +26,7 @@ fn backend_plugin() -> BackendPluginRow {
         types: PluginTypes(vec![PluginType::AverageMonthlyConsumption]),
         variant_type: PluginVariantType::BoaJs,
         version: "1.0.0".to_string(),
+        manifest: PluginManifest::default(),
     }
 }
 
@@ -91,6 +91,7 @@ fn requisition_request_pull_record() -> TestSyncIncomingRecord {
             order_type: None,
             is_emergency: false,
             created_from_requisition_id: Some("created_from_id".to_string()),
+            emergency_reason_option_id: None,
             destination_customer_id: Some("name1".to_string()),
         },
     )
@@ -138,7 +139,8 @@ fn requisition_request_push_record() -> TestSyncOutgoingRecord {
             is_emergency: false,
             oms_fields: Some(OmsFields {
                 created_from_requisition_id: Some("created_from_id".to_string()),
-                destination_customer_id: Some("name1".to_string())
+                destination_customer_id: Some("name1".to_string()),
+                emergency_reason_option_id: None,
             }),
         }),
     }
@@ -213,6 +215,7 @@ fn requisition_response_pull_record() -> TestSyncIncomingRecord {
             order_type: Some("Normal".to_string()),
             is_emergency: true,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         },
     )
@@ -344,6 +347,7 @@ fn requisition_om_fields_pull_record() -> TestSyncIncomingRecord {
             order_type: Some("Normal".to_string()),
             is_emergency: false,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         },
     )
@@ -475,6 +479,7 @@ fn program_requisition_request_pull_record() -> TestSyncIncomingRecord {
             order_type: Some("Normal".to_string()),
             is_emergency: false,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         },
     )
@@ -599,6 +604,7 @@ fn requisition_imprest_pull_record() -> TestSyncIncomingRecord {
             order_type: None,
             is_emergency: false,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         },
     )
@@ -720,6 +726,7 @@ fn requisition_stock_history_pull_record() -> TestSyncIncomingRecord {
             order_type: None,
             is_emergency: false,
             created_from_requisition_id: None,
+            emergency_reason_option_id: None,
             destination_customer_id: None,
         },
     )
@@ -0,0 +1,159 @@
+use repository::{
+    email_queue_row::{EmailQueueRow, EmailQueueRowRepository},
+    ChangelogRow, ChangelogTableName, StorageConnection, SyncBufferRow,
+};
+
+use super::{
+    PullTranslateResult, PushTranslateResult, SyncTranslation, ToSyncRecordTranslationType,
+};
+
+// Needs to be added to all_translators()
+#[deny(dead_code)]
+pub(crate) fn boxed() -> Box<dyn SyncTranslation> {
+    Box::new(EmailQueueTranslation)
+}
+
+/// Queued emails are sent by the central server (the only site with mail settings), emails queued
+/// on remote sites, e.g. requisition approval notifications, are pushed to central to be sent
+pub(crate) struct EmailQueueTranslation;
+
+impl SyncTranslation for EmailQueueTranslation {
+    fn table_name(&self) -> &'static str {
+        "email_queue"
+    }
+
+    fn pull_dependencies(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn try_translate_from_upsert_sync_record(
+        &self,
+        _: &StorageConnection,
+        sync_record: &SyncBufferRow,
+    ) -> Result<PullTranslateResult, anyhow::Error> {
+        Ok(PullTranslateResult::upsert(serde_json::from_str::<
+            EmailQueueRow,
+        >(&sync_record.data)?))
+    }
+
+    fn change_log_type(&self) -> Option<ChangelogTableName> {
+        Some(ChangelogTableName::EmailQueue)
+    }
+
+    fn should_translate_to_sync_record(
+        &self,
+        row: &ChangelogRow,
+        r#type: &ToSyncRecordTranslationType,
+    ) -> bool {
+        match r#type {
+            ToSyncRecordTranslationType::PushToOmSupplyCentral => {
+                self.change_log_type().as_ref() == Some(&row.table_name)
+            }
+            _ => false,
+        }
+    }
+
+    fn try_translate_to_upsert_sync_record(
+        &self,
+        connection: &StorageConnection,
+        changelog: &ChangelogRow,
+    ) -> Result<PushTranslateResult, anyhow::Error> {
+        let row = EmailQueueRowRepository::new(connection)
+            .find_one_by_id(&changelog.record_id)?
+            .ok_or(anyhow::Error::msg(format!(
+                "Email queue row ({}) not found",
+                changelog.record_id
+            )))?;
+
+        Ok(PushTranslateResult::upsert(
+            changelog,
+            self.table_name(),
+            serde_json::to_value(row)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requisition::approval::notify_approver;
+    use repository::{
+        email_queue_row::EmailQueueStatus,
+        mock::{
+            mock_new_response_requisition_for_update_test, mock_user_account_b, MockDataInserts,
+        },
+        test_db::setup_all,
+        ChangelogFilter, ChangelogRepository, RequisitionApprovalStepRow,
+    };
+
+    #[actix_rt::test]
+    async fn test_approval_email_on_remote_site_is_pushed_to_central() {
+        let (_, connection, _, _) = setup_all(
+            "test_approval_email_on_remote_site_is_pushed_to_central",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        // Test server is a remote site, email is queued but only central sends emails
+        let step = RequisitionApprovalStepRow {
+            id: "step_1".to_string(),
+            step_number: 1,
+            approver_user_id: mock_user_account_b().id,
+            ..Default::default()
+        };
+        notify_approver(
+            &connection,
+            &step,
+            &mock_new_response_requisition_for_update_test(),
+        )
+        .unwrap();
+
+        let changelog = ChangelogRepository::new(&connection)
+            .changelogs(
+                0,
+                100,
+                Some(ChangelogFilter::new().table_name(ChangelogTableName::EmailQueue.equal_to())),
+            )
+            .unwrap()
+            .pop()
+            .unwrap();
+
+        let translator = EmailQueueTranslation;
+        assert!(translator.should_translate_to_sync_record(
+            &changelog,
+            &ToSyncRecordTranslationType::PushToOmSupplyCentral
+        ));
+        assert!(!translator.should_translate_to_sync_record(
+            &changelog,
+            &ToSyncRecordTranslationType::PullFromOmSupplyCentral
+        ));
+
+        let PushTranslateResult::PushRecord(pushed) = translator
+            .try_translate_to_upsert_sync_record(&connection, &changelog)
+            .unwrap()
+        else {
+            panic!("Email should be pushed to central")
+        };
+
+        // Central integrates the email as queued, ready to be sent
+        let queued = EmailQueueRowRepository::new(&connection)
+            .find_one_by_id(&changelog.record_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(queued.status, EmailQueueStatus::Queued);
+        assert_eq!(Some(queued.to_address.clone()), mock_user_account_b().email);
+
+        let translated = translator
+            .try_translate_from_upsert_sync_record(
+                &connection,
+                &SyncBufferRow {
+                    record_id: changelog.record_id.clone(),
+                    table_name: translator.table_name().to_string(),
+                    data: pushed[0].record.record_data.to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(translated, PullTranslateResult::upsert(queued));
+    }
+}
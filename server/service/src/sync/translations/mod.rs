@@ -1,5 +1,6 @@
 pub(crate) mod abbreviation;
 pub(crate) mod activity_log;
+pub(crate) mod activity_log_chain_anchor;
 pub(crate) mod ancillary_item;
 pub(crate) mod asset;
 pub(crate) mod asset_catalogue_item;
@@ -23,6 +24,8 @@ pub(crate) mod demographic;
 pub(crate) mod diagnosis;
 pub(crate) mod document;
 pub(crate) mod document_registry;
+pub(crate) mod drug_interaction;
+pub(crate) mod email_queue;
 pub(crate) mod encounter_legacy;
 pub(crate) mod form_schema;
 pub(crate) mod frontend_plugin;
@@ -34,10 +37,13 @@ pub(crate) mod insurance_provider;
 pub(crate) mod invoice;
 pub(crate) mod invoice_line;
 pub(crate) mod item;
+pub(crate) mod item_clinical_info;
 pub(crate) mod item_direction;
 pub(crate) mod item_store_join;
 pub(crate) mod item_variant;
 pub(crate) mod item_warning_join;
+pub(crate) mod kit_assembly;
+pub(crate) mod kit_component;
 pub(crate) mod location;
 pub(crate) mod location_movement;
 pub(crate) mod location_type;
@@ -56,6 +62,7 @@ pub(crate) mod packaging_variant;
 pub(crate) mod period;
 pub(crate) mod period_schedule;
 pub(crate) mod plugin_data;
+pub(crate) mod plugin_table;
 pub(crate) mod preference;
 pub(crate) mod program_indicator;
 pub(crate) mod program_requisition_settings;
@@ -63,12 +70,17 @@ pub(crate) mod property;
 pub(crate) mod purchase_order;
 pub(crate) mod purchase_order_line;
 pub(crate) mod reason;
+pub(crate) mod recall;
 pub(crate) mod report;
 pub(crate) mod requisition;
+pub(crate) mod requisition_approval;
+pub(crate) mod requisition_approval_step;
 pub(crate) mod requisition_line;
 pub(crate) mod rnr_form;
 pub(crate) mod rnr_form_line;
 pub(crate) mod sensor;
+pub(crate) mod serial_number;
+pub(crate) mod serial_number_line;
 pub(crate) mod shipping_method;
 pub(crate) mod special;
 pub(crate) mod stock_line;
@@ -214,6 +226,7 @@ pub(crate) fn all_translators() -> SyncTranslators {
         backend_plugin::boxed(),
         frontend_plugin::boxed(),
         plugin_data::boxed(),
+        plugin_table::boxed(),
         // Insurance
         insurance_provider::boxed(),
         name_insurance_join::boxed(),
@@ -228,6 +241,24 @@ pub(crate) fn all_translators() -> SyncTranslators {
         // Goods Received (legacy OG → InboundShipment)
         goods_received::boxed(),
         goods_received_line::boxed(),
+        // Requisition approval
+        requisition_approval_step::boxed(),
+        requisition_approval::boxed(),
+        // Recall
+        recall::boxed(),
+        // Serial numbers
+        serial_number::boxed(),
+        serial_number_line::boxed(),
+        // Kits
+        kit_component::boxed(),
+        kit_assembly::boxed(),
+        // Dispensing safety
+        item_clinical_info::boxed(),
+        drug_interaction::boxed(),
+        // Activity log chain
+        activity_log_chain_anchor::boxed(),
+        // Emails queued on remote sites
+        email_queue::boxed(),
     ]
 }
 
@@ -0,0 +1,112 @@
+use repository::{
+    parse_plugin_table_record_id, ChangelogRow, ChangelogTableName, PluginTableRow,
+    PluginTableRowDelete, PluginTableRowRepository, StorageConnection, SyncBufferRow,
+};
+
+use crate::sync::translations::{backend_plugin::BackendPluginTranslator, store::StoreTranslation};
+
+use super::{
+    PullTranslateResult, PushTranslateResult, SyncTranslation, ToSyncRecordTranslationType,
+};
+
+// Needs to be added to all_translators()
+#[deny(dead_code)]
+pub(crate) fn boxed() -> Box<dyn SyncTranslation> {
+    Box::new(PluginTableTranslator)
+}
+
+/// Rows of all tables declared by plugin manifest migrations are synced under one table name,
+/// actual table name is part of the record (and of record_id, see plugin_table_record_id)
+pub(crate) struct PluginTableTranslator;
+
+impl SyncTranslation for PluginTableTranslator {
+    fn table_name(&self) -> &str {
+        "plugin_table"
+    }
+
+    // Plugin tables are created when backend plugin is integrated
+    fn pull_dependencies(&self) -> Vec<&str> {
+        vec![
+            BackendPluginTranslator.table_name(),
+            StoreTranslation.table_name(),
+        ]
+    }
+
+    fn try_translate_from_upsert_sync_record(
+        &self,
+        _: &StorageConnection,
+        sync_record: &SyncBufferRow,
+    ) -> Result<PullTranslateResult, anyhow::Error> {
+        Ok(PullTranslateResult::upsert(serde_json::from_str::<
+            PluginTableRow,
+        >(&sync_record.data)?))
+    }
+
+    fn try_translate_from_delete_sync_record(
+        &self,
+        _: &StorageConnection,
+        sync_record: &SyncBufferRow,
+    ) -> Result<PullTranslateResult, anyhow::Error> {
+        let (table_name, id) =
+            parse_plugin_table_record_id(&sync_record.record_id).ok_or(anyhow::Error::msg(
+                format!("Invalid plugin_table record_id ({})", sync_record.record_id),
+            ))?;
+
+        Ok(PullTranslateResult::delete(PluginTableRowDelete {
+            table_name: table_name.to_string(),
+            id: id.to_string(),
+        }))
+    }
+
+    fn change_log_type(&self) -> Option<ChangelogTableName> {
+        Some(ChangelogTableName::PluginTable)
+    }
+
+    fn should_translate_to_sync_record(
+        &self,
+        row: &ChangelogRow,
+        r#type: &ToSyncRecordTranslationType,
+    ) -> bool {
+        match r#type {
+            ToSyncRecordTranslationType::PullFromOmSupplyCentral => {
+                self.change_log_type().as_ref() == Some(&row.table_name)
+            }
+            ToSyncRecordTranslationType::PushToOmSupplyCentral => {
+                self.change_log_type().as_ref() == Some(&row.table_name)
+            }
+            _ => false,
+        }
+    }
+
+    fn try_translate_to_upsert_sync_record(
+        &self,
+        connection: &StorageConnection,
+        changelog: &ChangelogRow,
+    ) -> Result<PushTranslateResult, anyhow::Error> {
+        let (table_name, id) =
+            parse_plugin_table_record_id(&changelog.record_id).ok_or(anyhow::Error::msg(
+                format!("Invalid plugin_table record_id ({})", changelog.record_id),
+            ))?;
+
+        let row = PluginTableRowRepository::new(connection)
+            .find_one_by_id(table_name, id)?
+            .ok_or(anyhow::Error::msg(format!(
+                "plugin_table row ({}) not found",
+                changelog.record_id
+            )))?;
+
+        Ok(PushTranslateResult::upsert(
+            changelog,
+            self.table_name(),
+            serde_json::to_value(row)?,
+        ))
+    }
+
+    fn try_translate_to_delete_sync_record(
+        &self,
+        _: &StorageConnection,
+        changelog: &ChangelogRow,
+    ) -> Result<PushTranslateResult, anyhow::Error> {
+        Ok(PushTranslateResult::delete(changelog, self.table_name()))
+    }
+}
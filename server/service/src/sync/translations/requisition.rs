@@ -28,6 +28,8 @@ pub struct OmsFields {
     #[serde(rename = "original_customer_id")]
     #[serde(alias = "destination_customer_id")]
     pub destination_customer_id: Option<String>,
+    #[serde(default)]
+    pub emergency_reason_option_id: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
@@ -326,6 +328,10 @@ impl SyncTranslation for RequisitionTranslation {
                 .oms_fields
                 .clone()
                 .and_then(|f| f.created_from_requisition_id),
+            emergency_reason_option_id: data
+                .oms_fields
+                .clone()
+                .and_then(|f| f.emergency_reason_option_id),
             destination_customer_id: data.oms_fields.and_then(|f| f.destination_customer_id),
         };
 
@@ -374,6 +380,7 @@ impl SyncTranslation for RequisitionTranslation {
                     order_type,
                     is_emergency,
                     created_from_requisition_id,
+                    emergency_reason_option_id,
                     destination_customer_id,
                 },
             name_row,
@@ -391,15 +398,18 @@ impl SyncTranslation for RequisitionTranslation {
             )?
             .is_empty();
 
-        let oms_fields =
-            if created_from_requisition_id.is_some() || destination_customer_id.is_some() {
-                Some(OmsFields {
-                    created_from_requisition_id,
-                    destination_customer_id,
-                })
-            } else {
-                None
-            };
+        let oms_fields = if created_from_requisition_id.is_some()
+            || destination_customer_id.is_some()
+            || emergency_reason_option_id.is_some()
+        {
+            Some(OmsFields {
+                created_from_requisition_id,
+                destination_customer_id,
+                emergency_reason_option_id,
+            })
+        } else {
+            None
+        };
 
         let legacy_row = LegacyRequisitionRow {
             ID: id.clone(),
@@ -63,6 +63,7 @@ pub(crate) async fn setup_all_with_data_and_service_provider(
             interval: 1,
         }),
         features: None,
+        graphql: None,
     };
     let (file_sync_trigger, _) = FileSyncDriver::init(&settings);
     let (sync_trigger, _) = SynchroniserDriver::init(file_sync_trigger);
@@ -59,6 +59,7 @@ pub fn generate_create_prescription(
         item_variant_id: None,
         donor_id: None,
         manufacturer_id: None,
+        dispensing_override_reason: None,
     };
 
     let finalise_prescription = UpdatePrescription {
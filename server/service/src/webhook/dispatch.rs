@@ -0,0 +1,282 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use repository::{
+    ActivityLogRowRepository, ActivityLogType, ChangelogFilter, ChangelogRepository, ChangelogRow,
+    ChangelogTableName, EqualFilter, RepositoryError, RowActionType, StorageConnection,
+    WebhookDeliveryRow, WebhookDeliveryRowRepository, WebhookDeliveryStatus, WebhookRow,
+    WebhookRowRepository,
+};
+use reqwest::{header::CONTENT_TYPE, Client};
+use serde::Serialize;
+use util::uuid::uuid;
+
+use crate::{cursor_controller::CursorController, service_provider::ServiceContext};
+
+use super::{
+    sign_payload, webhook_cursor_type, Webhook, DELIVERY_ID_HEADER, SIGNATURE_HEADER,
+    TIMESTAMP_HEADER,
+};
+
+const CHANGELOG_BATCH_SIZE: u32 = 100;
+/// Deliveries sent per dispatch, the rest are picked up by the next one
+pub static DELIVERY_BATCH_SIZE: i64 = 500;
+pub static MAX_ATTEMPTS: i32 = 6;
+pub static RETRY_DELAY_SECS: i64 = 60; // Doubles each attempt
+pub static TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// Domain event recorded in the activity log, e.g. an invoice status changed to shipped
+    Activity {
+        activity_type: ActivityLogType,
+        activity_log_id: String,
+        record_id: Option<String>,
+        store_id: Option<String>,
+        datetime: NaiveDateTime,
+    },
+    RowChanged {
+        table_name: ChangelogTableName,
+        record_id: String,
+        row_action: RowActionType,
+        store_id: Option<String>,
+    },
+}
+
+/// Body posted to the webhook url
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload {
+    /// Delivery id, the same for retries of the delivery
+    pub id: String,
+    pub webhook_id: String,
+    /// Changelog cursor of the change, events of a webhook are created in cursor order
+    pub cursor: i64,
+    pub event: WebhookEvent,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct WebhookDispatchResult {
+    pub queued: usize,
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// Queues deliveries for new changelogs of each active webhook, then sends the deliveries that
+/// are due
+pub async fn dispatch_webhooks(
+    ctx: &ServiceContext,
+) -> Result<WebhookDispatchResult, RepositoryError> {
+    let mut result = WebhookDispatchResult::default();
+
+    for webhook in WebhookRowRepository::new(&ctx.connection).find_active()? {
+        result.queued += queue_deliveries(&ctx.connection, &Webhook::from_row(webhook))?;
+    }
+
+    let client = Client::builder()
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .build()
+        .map_err(|error| RepositoryError::as_db_error("Webhook client", error.to_string()))?;
+    let repo = WebhookDeliveryRowRepository::new(&ctx.connection);
+    let mut webhooks: HashMap<String, Option<WebhookRow>> = HashMap::new();
+
+    for delivery in repo.find_due(Utc::now().naive_utc(), DELIVERY_BATCH_SIZE)? {
+        let webhook = match webhooks.get(&delivery.webhook_id) {
+            Some(webhook) => webhook.clone(),
+            None => {
+                let webhook = WebhookRowRepository::new(&ctx.connection)
+                    .find_one_by_id(&delivery.webhook_id)?
+                    .filter(|webhook| webhook.is_active);
+                webhooks.insert(delivery.webhook_id.clone(), webhook.clone());
+                webhook
+            }
+        };
+        // Deliveries of deactivated webhooks wait until the webhook is active again
+        let Some(webhook) = webhook else {
+            continue;
+        };
+
+        let sent = send(&client, &webhook, &delivery).await;
+        let delivery = match sent {
+            Ok(_) => {
+                result.delivered += 1;
+                delivered(delivery)
+            }
+            Err(error) => {
+                log::error!(
+                    "Failed to deliver webhook {} delivery {} - {error}",
+                    webhook.name,
+                    delivery.id
+                );
+                result.failed += 1;
+                failed(delivery, error)
+            }
+        };
+        repo.upsert_one(&delivery)?;
+    }
+
+    Ok(result)
+}
+
+fn queue_deliveries(
+    connection: &StorageConnection,
+    webhook: &Webhook,
+) -> Result<usize, RepositoryError> {
+    let mut table_names = webhook.table_names.clone();
+    if !webhook.event_types.is_empty() && !table_names.contains(&ChangelogTableName::ActivityLog) {
+        table_names.push(ChangelogTableName::ActivityLog);
+    }
+    let filter = ChangelogFilter::new().table_name(EqualFilter {
+        equal_any: Some(table_names),
+        ..Default::default()
+    });
+
+    let changelog_repo = ChangelogRepository::new(connection);
+    let cursor_controller =
+        CursorController::from_cursor_type(webhook_cursor_type(&webhook.webhook_row.id));
+    let mut queued = 0;
+
+    loop {
+        let cursor = cursor_controller.get(connection)?;
+        let logs = changelog_repo.changelogs(cursor, CHANGELOG_BATCH_SIZE, Some(filter.clone()))?;
+        if logs.is_empty() {
+            break;
+        }
+
+        for log in logs {
+            connection
+                .transaction_sync(|connection| {
+                    for event in webhook_events(connection, webhook, &log)? {
+                        WebhookDeliveryRowRepository::new(connection)
+                            .upsert_one(&new_delivery(webhook, &log, event))?;
+                        queued += 1;
+                    }
+                    cursor_controller.update(connection, (log.cursor + 1) as u64)
+                })
+                .map_err(|error| error.to_inner_error())?;
+        }
+    }
+
+    Ok(queued)
+}
+
+fn webhook_events(
+    connection: &StorageConnection,
+    webhook: &Webhook,
+    log: &ChangelogRow,
+) -> Result<Vec<WebhookEvent>, RepositoryError> {
+    let mut events = Vec::new();
+
+    if log.table_name == ChangelogTableName::ActivityLog && !webhook.event_types.is_empty() {
+        let activity_log =
+            ActivityLogRowRepository::new(connection).find_one_by_id(&log.record_id)?;
+        if let Some(activity_log) = activity_log {
+            if webhook.event_types.contains(&activity_log.r#type) {
+                events.push(WebhookEvent::Activity {
+                    activity_type: activity_log.r#type,
+                    activity_log_id: activity_log.id,
+                    record_id: activity_log.record_id,
+                    store_id: activity_log.store_id,
+                    datetime: activity_log.datetime,
+                });
+            }
+        }
+    }
+
+    if webhook.table_names.contains(&log.table_name) {
+        events.push(WebhookEvent::RowChanged {
+            table_name: log.table_name.clone(),
+            record_id: log.record_id.clone(),
+            row_action: log.row_action.clone(),
+            store_id: log.store_id.clone(),
+        });
+    }
+
+    Ok(events)
+}
+
+fn new_delivery(webhook: &Webhook, log: &ChangelogRow, event: WebhookEvent) -> WebhookDeliveryRow {
+    let event_name = match &event {
+        WebhookEvent::Activity { activity_type, .. } => format!("{activity_type:?}"),
+        WebhookEvent::RowChanged {
+            table_name,
+            row_action,
+            ..
+        } => format!("{table_name:?} {row_action:?}"),
+    };
+    let payload = WebhookPayload {
+        id: uuid(),
+        webhook_id: webhook.webhook_row.id.clone(),
+        cursor: log.cursor,
+        event,
+    };
+
+    WebhookDeliveryRow {
+        id: payload.id.clone(),
+        webhook_id: payload.webhook_id.clone(),
+        changelog_cursor: log.cursor,
+        event: event_name,
+        payload: serde_json::to_string(&payload).unwrap_or_default(),
+        status: WebhookDeliveryStatus::Pending,
+        attempts: 0,
+        next_attempt_datetime: None,
+        last_error: None,
+        created_datetime: Utc::now().naive_utc(),
+        delivered_datetime: None,
+    }
+}
+
+async fn send(
+    client: &Client,
+    webhook: &WebhookRow,
+    delivery: &WebhookDeliveryRow,
+) -> Result<(), reqwest::Error> {
+    let timestamp = Utc::now().timestamp();
+
+    client
+        .post(&webhook.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(DELIVERY_ID_HEADER, &delivery.id)
+        .header(TIMESTAMP_HEADER, timestamp.to_string())
+        .header(
+            SIGNATURE_HEADER,
+            sign_payload(&webhook.secret, timestamp, &delivery.payload),
+        )
+        .body(delivery.payload.clone())
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+fn delivered(delivery: WebhookDeliveryRow) -> WebhookDeliveryRow {
+    WebhookDeliveryRow {
+        status: WebhookDeliveryStatus::Delivered,
+        attempts: delivery.attempts + 1,
+        next_attempt_datetime: None,
+        last_error: None,
+        delivered_datetime: Some(Utc::now().naive_utc()),
+        ..delivery
+    }
+}
+
+fn failed(delivery: WebhookDeliveryRow, error: reqwest::Error) -> WebhookDeliveryRow {
+    let attempts = delivery.attempts + 1;
+    let (status, next_attempt_datetime) = if attempts >= MAX_ATTEMPTS {
+        (WebhookDeliveryStatus::DeadLetter, None)
+    } else {
+        let delay = RETRY_DELAY_SECS * i64::pow(2, (attempts - 1) as u32);
+        (
+            WebhookDeliveryStatus::Pending,
+            Some(Utc::now().naive_utc() + chrono::Duration::seconds(delay)),
+        )
+    };
+
+    WebhookDeliveryRow {
+        status,
+        attempts,
+        next_attempt_datetime,
+        last_error: Some(error.to_string()),
+        ..delivery
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::Utc;
+use repository::{
+    ActivityLogType, ChangelogRepository, ChangelogTableName, RepositoryError, WebhookDeliveryRow,
+    WebhookDeliveryRowRepository, WebhookDeliveryStatus, WebhookRow, WebhookRowRepository,
+};
+use reqwest::Url;
+
+use crate::{cursor_controller::CursorController, service_provider::ServiceContext};
+
+use super::{webhook_cursor_type, Webhook};
+
+#[derive(Debug, Clone, Default)]
+pub struct UpsertWebhook {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    /// Required for new webhooks, keeps the existing secret when not provided
+    pub secret: Option<String>,
+    pub event_types: Vec<ActivityLogType>,
+    pub table_names: Vec<ChangelogTableName>,
+    pub is_active: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpsertWebhookError {
+    NameCannotBeEmpty,
+    InvalidUrl,
+    SecretCannotBeEmpty,
+    NoEventsOrTables,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeleteWebhookError {
+    WebhookDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReplayWebhookError {
+    WebhookDoesNotExist,
+    CursorAfterLatestChange,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RetryWebhookDeliveryError {
+    DeliveryDoesNotExist,
+    DeliveryIsNotADeadLetter,
+    DatabaseError(RepositoryError),
+}
+
+pub fn upsert_webhook(
+    ctx: &ServiceContext,
+    input: UpsertWebhook,
+) -> Result<Webhook, UpsertWebhookError> {
+    use UpsertWebhookError as Error;
+
+    ctx.connection
+        .transaction_sync(|connection| {
+            let name = input.name.trim().to_string();
+            if name.is_empty() {
+                return Err(Error::NameCannotBeEmpty);
+            }
+            let is_http =
+                Url::parse(&input.url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !is_http {
+                return Err(Error::InvalidUrl);
+            }
+            if input.event_types.is_empty() && input.table_names.is_empty() {
+                return Err(Error::NoEventsOrTables);
+            }
+
+            let repo = WebhookRowRepository::new(connection);
+            let existing = repo.find_one_by_id(&input.id)?;
+            let is_new = existing.is_none();
+            let secret = match (input.secret, &existing) {
+                (Some(secret), _) => secret,
+                (None, Some(existing)) => existing.secret.clone(),
+                (None, None) => String::new(),
+            };
+            if secret.is_empty() {
+                return Err(Error::SecretCannotBeEmpty);
+            }
+
+            let row = WebhookRow {
+                id: input.id,
+                name,
+                url: input.url,
+                secret,
+                event_types: serde_json::to_string(&input.event_types)
+                    .unwrap_or_else(|_| "[]".to_string()),
+                table_names: serde_json::to_string(&input.table_names)
+                    .unwrap_or_else(|_| "[]".to_string()),
+                is_active: input.is_active,
+                created_datetime: existing
+                    .map(|existing| existing.created_datetime)
+                    .unwrap_or_else(|| Utc::now().naive_utc()),
+            };
+
+            if is_new {
+                let latest_cursor = ChangelogRepository::new(connection).latest_cursor()?;
+                CursorController::from_cursor_type(webhook_cursor_type(&row.id))
+                    .update(connection, latest_cursor + 1)?;
+            }
+            repo.upsert_one(&row)?;
+
+            Ok(Webhook::from_row(row))
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn delete_webhook(ctx: &ServiceContext, id: &str) -> Result<String, DeleteWebhookError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = WebhookRowRepository::new(connection);
+            if repo.find_one_by_id(id)?.is_none() {
+                return Err(DeleteWebhookError::WebhookDoesNotExist);
+            }
+
+            WebhookDeliveryRowRepository::new(connection).delete_by_webhook_id(id)?;
+            repo.delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn replay_webhook(
+    ctx: &ServiceContext,
+    id: &str,
+    from_cursor: u64,
+) -> Result<Webhook, ReplayWebhookError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let webhook = WebhookRowRepository::new(connection)
+                .find_one_by_id(id)?
+                .ok_or(ReplayWebhookError::WebhookDoesNotExist)?;
+            if from_cursor > ChangelogRepository::new(connection).latest_cursor()? {
+                return Err(ReplayWebhookError::CursorAfterLatestChange);
+            }
+
+            CursorController::from_cursor_type(webhook_cursor_type(id))
+                .update(connection, from_cursor)?;
+            Ok(Webhook::from_row(webhook))
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+pub fn retry_delivery(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<WebhookDeliveryRow, RetryWebhookDeliveryError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let repo = WebhookDeliveryRowRepository::new(connection);
+            let delivery = repo
+                .find_one_by_id(id)?
+                .ok_or(RetryWebhookDeliveryError::DeliveryDoesNotExist)?;
+            if delivery.status != WebhookDeliveryStatus::DeadLetter {
+                return Err(RetryWebhookDeliveryError::DeliveryIsNotADeadLetter);
+            }
+
+            let delivery = WebhookDeliveryRow {
+                status: WebhookDeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_datetime: None,
+                ..delivery
+            };
+            repo.upsert_one(&delivery)?;
+            Ok(delivery)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+impl From<RepositoryError> for UpsertWebhookError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertWebhookError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for DeleteWebhookError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteWebhookError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for ReplayWebhookError {
+    fn from(error: RepositoryError) -> Self {
+        ReplayWebhookError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for RetryWebhookDeliveryError {
+    fn from(error: RepositoryError) -> Self {
+        RetryWebhookDeliveryError::DatabaseError(error)
+    }
+}
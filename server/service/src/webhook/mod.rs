@@ -0,0 +1,98 @@
+mod dispatch;
+mod manage;
+mod signature;
+#[cfg(test)]
+mod test;
+
+use repository::{
+    ActivityLogType, ChangelogTableName, RepositoryError, WebhookDeliveryRow,
+    WebhookDeliveryRowRepository, WebhookDeliveryStatus, WebhookRow, WebhookRowRepository,
+};
+
+use crate::{cursor_controller::CursorType, service_provider::ServiceContext};
+pub use dispatch::*;
+pub use manage::*;
+pub use signature::{sign_payload, verify_signature};
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const DELIVERY_ID_HEADER: &str = "X-Webhook-Delivery";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Webhook {
+    pub webhook_row: WebhookRow,
+    pub event_types: Vec<ActivityLogType>,
+    pub table_names: Vec<ChangelogTableName>,
+}
+
+impl Webhook {
+    pub fn from_row(webhook_row: WebhookRow) -> Webhook {
+        let event_types = serde_json::from_str(&webhook_row.event_types).unwrap_or_default();
+        let table_names = serde_json::from_str(&webhook_row.table_names).unwrap_or_default();
+
+        Webhook {
+            webhook_row,
+            event_types,
+            table_names,
+        }
+    }
+}
+
+/// Each webhook walks the changelog with its own cursor
+pub(crate) fn webhook_cursor_type(webhook_id: &str) -> CursorType {
+    CursorType::Dynamic(format!("webhook_{webhook_id}"))
+}
+
+pub trait WebhookServiceTrait: Sync + Send {
+    fn get_webhooks(&self, ctx: &ServiceContext) -> Result<Vec<Webhook>, RepositoryError> {
+        Ok(WebhookRowRepository::new(&ctx.connection)
+            .find_all()?
+            .into_iter()
+            .map(Webhook::from_row)
+            .collect())
+    }
+
+    /// New webhooks start from the current end of the changelog
+    fn upsert_webhook(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertWebhook,
+    ) -> Result<Webhook, UpsertWebhookError> {
+        upsert_webhook(ctx, input)
+    }
+
+    fn delete_webhook(&self, ctx: &ServiceContext, id: &str) -> Result<String, DeleteWebhookError> {
+        delete_webhook(ctx, id)
+    }
+
+    fn get_deliveries(
+        &self,
+        ctx: &ServiceContext,
+        status: WebhookDeliveryStatus,
+        webhook_id: Option<&str>,
+    ) -> Result<Vec<WebhookDeliveryRow>, RepositoryError> {
+        WebhookDeliveryRowRepository::new(&ctx.connection).find_by_status(status, webhook_id)
+    }
+
+    /// Moves a dead letter back to pending, it's sent with the next dispatch
+    fn retry_delivery(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<WebhookDeliveryRow, RetryWebhookDeliveryError> {
+        retry_delivery(ctx, id)
+    }
+
+    /// Re-delivers changes from the cursor onwards, including ones already delivered
+    fn replay_webhook(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+        from_cursor: u64,
+    ) -> Result<Webhook, ReplayWebhookError> {
+        replay_webhook(ctx, id, from_cursor)
+    }
+}
+
+pub struct WebhookService;
+impl WebhookServiceTrait for WebhookService {}
@@ -0,0 +1,83 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+fn signed_content(timestamp: i64, payload: &str) -> String {
+    format!("{timestamp}.{payload}")
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(message);
+    mac
+}
+
+/// Value of the signature header, receivers recompute it over `{timestamp}.{body}` with the
+/// webhook secret
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &str) -> String {
+    let signature = hmac_sha256(
+        secret.as_bytes(),
+        signed_content(timestamp, payload).as_bytes(),
+    )
+    .finalize()
+    .into_bytes();
+    format!("{SIGNATURE_PREFIX}{}", hex::encode(signature))
+}
+
+/// Checks a signature header value in constant time
+pub fn verify_signature(secret: &str, timestamp: i64, payload: &str, signature: &str) -> bool {
+    let Some(signature) = signature
+        .strip_prefix(SIGNATURE_PREFIX)
+        .and_then(|signature| hex::decode(signature).ok())
+    else {
+        return false;
+    };
+
+    hmac_sha256(
+        secret.as_bytes(),
+        signed_content(timestamp, payload).as_bytes(),
+    )
+    .verify_slice(&signature)
+    .is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{hmac_sha256, sign_payload, verify_signature};
+
+    // Test cases 2 and 6 from RFC 4231
+    #[test]
+    fn test_hmac_sha256() {
+        let hmac_hex = |key: &[u8], message: &[u8]| {
+            hex::encode(hmac_sha256(key, message).finalize().into_bytes())
+        };
+        assert_eq!(
+            hmac_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hmac_hex(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let signature = sign_payload("secret", 1700000000, "{}");
+        assert!(verify_signature("secret", 1700000000, "{}", &signature));
+        assert!(!verify_signature("other", 1700000000, "{}", &signature));
+        assert!(!verify_signature("secret", 1700000001, "{}", &signature));
+        assert!(!verify_signature(
+            "secret",
+            1700000000,
+            "{}",
+            signature.trim_start_matches("sha256=")
+        ));
+    }
+}
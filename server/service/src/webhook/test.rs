@@ -0,0 +1,211 @@
+use chrono::Utc;
+use httpmock::{Method::POST, MockServer};
+use repository::{
+    mock::{mock_store_a, MockDataInserts},
+    test_db::setup_all,
+    ActivityLogRow, ActivityLogRowRepository, ActivityLogType, ChangelogRepository,
+    ChangelogTableName, WebhookDeliveryRowRepository, WebhookDeliveryStatus,
+};
+
+use crate::service_provider::ServiceProvider;
+
+use super::{
+    dispatch_webhooks, ReplayWebhookError, RetryWebhookDeliveryError, UpsertWebhook,
+    UpsertWebhookError, WebhookDispatchResult, MAX_ATTEMPTS, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+
+fn activity_log(id: &str, r#type: ActivityLogType) -> ActivityLogRow {
+    ActivityLogRow {
+        id: id.to_string(),
+        r#type,
+        store_id: Some(mock_store_a().id),
+        record_id: Some(format!("{id}_invoice")),
+        datetime: Utc::now().naive_utc(),
+        ..Default::default()
+    }
+}
+
+#[actix_rt::test]
+async fn test_webhooks() {
+    let (_, connection, connection_manager, _) =
+        setup_all("test_webhooks", MockDataInserts::none().stores()).await;
+
+    let service_provider = ServiceProvider::new(connection_manager);
+    let context = service_provider.basic_context().unwrap();
+    let service = &service_provider.webhook_service;
+
+    let mock_server = MockServer::start();
+    let shipped_mock = mock_server.mock(|when, then| {
+        when.method(POST)
+            .path("/shipped")
+            .header_exists(SIGNATURE_HEADER)
+            .header_exists(TIMESTAMP_HEADER)
+            .body_includes("InvoiceStatusShipped");
+        then.status(200);
+    });
+    let failing_mock = mock_server.mock(|when, then| {
+        when.method(POST).path("/failing");
+        then.status(500);
+    });
+
+    let input = UpsertWebhook {
+        id: "shipped".to_string(),
+        name: "Shipments".to_string(),
+        url: mock_server.url("/shipped"),
+        secret: Some("secret".to_string()),
+        event_types: vec![ActivityLogType::InvoiceStatusShipped],
+        table_names: vec![],
+        is_active: true,
+    };
+
+    // Upsert errors
+    assert_eq!(
+        service.upsert_webhook(
+            &context,
+            UpsertWebhook {
+                url: "ftp://invalid".to_string(),
+                ..input.clone()
+            }
+        ),
+        Err(UpsertWebhookError::InvalidUrl)
+    );
+    assert_eq!(
+        service.upsert_webhook(
+            &context,
+            UpsertWebhook {
+                event_types: vec![],
+                ..input.clone()
+            }
+        ),
+        Err(UpsertWebhookError::NoEventsOrTables)
+    );
+    assert_eq!(
+        service.upsert_webhook(
+            &context,
+            UpsertWebhook {
+                secret: None,
+                ..input.clone()
+            }
+        ),
+        Err(UpsertWebhookError::SecretCannotBeEmpty)
+    );
+
+    // Changes before the webhook is created are not delivered
+    let log_repo = ActivityLogRowRepository::new(&connection);
+    log_repo
+        .insert_one(&activity_log(
+            "before",
+            ActivityLogType::InvoiceStatusShipped,
+        ))
+        .unwrap();
+
+    service.upsert_webhook(&context, input.clone()).unwrap();
+    service
+        .upsert_webhook(
+            &context,
+            UpsertWebhook {
+                id: "failing".to_string(),
+                name: "Failing".to_string(),
+                url: mock_server.url("/failing"),
+                table_names: vec![ChangelogTableName::ActivityLog],
+                ..input.clone()
+            },
+        )
+        .unwrap();
+
+    let shipped_cursor = log_repo
+        .insert_one(&activity_log(
+            "shipped",
+            ActivityLogType::InvoiceStatusShipped,
+        ))
+        .unwrap();
+    log_repo
+        .insert_one(&activity_log(
+            "picked",
+            ActivityLogType::InvoiceStatusPicked,
+        ))
+        .unwrap();
+
+    // Only matching events are queued, failing webhook gets the shipped event and both row changes
+    let result = dispatch_webhooks(&context).await.unwrap();
+    assert_eq!(
+        result,
+        WebhookDispatchResult {
+            queued: 4,
+            delivered: 1,
+            failed: 3,
+        }
+    );
+    shipped_mock.assert_hits(1);
+    failing_mock.assert_hits(3);
+
+    // Failed deliveries are retried later, not on the next dispatch
+    let pending = service
+        .get_deliveries(&context, WebhookDeliveryStatus::Pending, Some("failing"))
+        .unwrap();
+    assert_eq!(pending.len(), 3);
+    assert!(pending
+        .iter()
+        .all(|delivery| delivery.attempts == 1 && delivery.next_attempt_datetime.is_some()));
+    assert_eq!(
+        dispatch_webhooks(&context).await.unwrap(),
+        WebhookDispatchResult::default()
+    );
+
+    // Dead letter after the last attempt
+    let delivery_repo = WebhookDeliveryRowRepository::new(&connection);
+    for delivery in pending {
+        delivery_repo
+            .upsert_one(&repository::WebhookDeliveryRow {
+                attempts: MAX_ATTEMPTS - 1,
+                next_attempt_datetime: None,
+                ..delivery
+            })
+            .unwrap();
+    }
+    dispatch_webhooks(&context).await.unwrap();
+    let dead_letters = service
+        .get_deliveries(&context, WebhookDeliveryStatus::DeadLetter, None)
+        .unwrap();
+    assert_eq!(dead_letters.len(), 3);
+    assert!(dead_letters[0].last_error.is_some());
+
+    // Manual retry of a dead letter
+    let retried = service
+        .retry_delivery(&context, &dead_letters[0].id)
+        .unwrap();
+    assert_eq!(retried.status, WebhookDeliveryStatus::Pending);
+    assert_eq!(retried.attempts, 0);
+    assert_eq!(
+        service.retry_delivery(&context, &dead_letters[0].id),
+        Err(RetryWebhookDeliveryError::DeliveryIsNotADeadLetter)
+    );
+
+    // Replay from cursor delivers the shipped event again
+    let latest_cursor = ChangelogRepository::new(&connection)
+        .latest_cursor()
+        .unwrap();
+    assert_eq!(
+        service.replay_webhook(&context, "shipped", latest_cursor + 10),
+        Err(ReplayWebhookError::CursorAfterLatestChange)
+    );
+    service
+        .replay_webhook(&context, "shipped", shipped_cursor as u64)
+        .unwrap();
+    service
+        .upsert_webhook(
+            &context,
+            UpsertWebhook {
+                id: "failing".to_string(),
+                name: "Failing".to_string(),
+                url: mock_server.url("/failing"),
+                is_active: false,
+                ..input
+            },
+        )
+        .unwrap();
+    let result = dispatch_webhooks(&context).await.unwrap();
+    assert_eq!(result.queued, 1);
+    assert_eq!(result.delivered, 1);
+    shipped_mock.assert_hits(2);
+}
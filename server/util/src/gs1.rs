@@ -60,6 +60,14 @@ impl GS1 {
         Ok(Self { gs1 })
     }
 
+    /// Element string as sent by a scanner, AIs are not bracketed and variable length data is
+    /// terminated by FNC1 (transmitted as GS, `\x1d`), e.g. `]d20100012345600012\x1d21S12345678`
+    pub fn from_element_string(gs1_input: String) -> Result<Self, GS1ParseError> {
+        let gs1 = parse_gs1_element_string(gs1_input)?;
+
+        Ok(Self { gs1 })
+    }
+
     pub fn get(&self, key: &str) -> Option<&String> {
         self.gs1.get(key)
     }
@@ -142,6 +150,85 @@ fn parse_gs1_string(gs1_input: String) -> Result<HashMap<String, String>, GS1Par
     Ok(gs1)
 }
 
+const GROUP_SEPARATOR: char = '\x1d';
+
+fn parse_gs1_element_string(gs1_input: String) -> Result<HashMap<String, String>, GS1ParseError> {
+    // Scanners may prefix a symbology identifier, e.g. `]d2` for GS1 DataMatrix or `]C1` for
+    // GS1-128, and may transmit the leading FNC1
+    let input = match gs1_input.strip_prefix(']') {
+        Some(rest) => rest.get(2..).ok_or(GS1ParseError::InvalidFormat)?,
+        None => gs1_input.as_str(),
+    };
+    let mut input = input.trim_start_matches(GROUP_SEPARATOR);
+
+    let mut gs1 = HashMap::new();
+
+    while !input.is_empty() {
+        let ai_length = input
+            .get(0..2)
+            .and_then(element_ai_length)
+            .ok_or(GS1ParseError::InvalidFormat)?;
+        let ai = input
+            .get(0..ai_length)
+            .ok_or(GS1ParseError::InvalidFormat)?;
+        if !ai.chars().all(|c| c.is_ascii_digit()) {
+            return Err(GS1ParseError::InvalidFormat);
+        }
+        let rest = &input[ai_length..];
+
+        let (data, remaining) = match predefined_data_length(ai) {
+            Some(length) => {
+                let data = rest.get(0..length).ok_or(GS1ParseError::InvalidFormat)?;
+                (data, &rest[length..])
+            }
+            // Variable length data runs until the next separator (or the end of the input)
+            None => match rest.find(GROUP_SEPARATOR) {
+                Some(index) => (&rest[..index], &rest[index..]),
+                None => (rest, ""),
+            },
+        };
+        if data.is_empty() {
+            return Err(GS1ParseError::InvalidFormat);
+        }
+
+        gs1.insert(ai.to_string(), data.to_string());
+        // Separator is allowed (but not needed) after predefined length data
+        input = remaining.trim_start_matches(GROUP_SEPARATOR);
+    }
+
+    if gs1.is_empty() {
+        return Err(GS1ParseError::InvalidFormat);
+    }
+
+    Ok(gs1)
+}
+
+/// Length of the AI from its first two digits https://ref.gs1.org/ai/
+fn element_ai_length(prefix: &str) -> Option<usize> {
+    let length = match prefix.parse::<u8>().ok()? {
+        0..=22 | 30 | 37 | 90..=99 => 2,
+        23..=25 | 40..=43 | 71 => 3,
+        31..=36 | 39 | 70 | 72 | 80..=82 => 4,
+        _ => return None,
+    };
+    Some(length)
+}
+
+/// AIs with predefined data length are not terminated by FNC1
+fn predefined_data_length(ai: &str) -> Option<usize> {
+    let length = match ai.get(0..2)?.parse::<u8>().ok()? {
+        0 => 18,
+        1..=3 => 14,
+        4 => 16,
+        11..=19 => 6,
+        20 => 2,
+        31..=36 => 6,
+        41 => 13,
+        _ => return None,
+    };
+    Some(length)
+}
+
 #[cfg(test)]
 mod test {
     use crate::{gs1::parse_gs1_string, GS1};
@@ -232,4 +319,30 @@ mod test {
         let urls = gs1.get("92").unwrap();
         assert_eq!(urls, "{\"pqs\":\"https://apps.who.int/immunization_standards/vaccine_quality/pqs_catalogue/LinkPDF.aspx?UniqueID=3bf9439f-3316-49b4-845e-d50360f8280f&TipoDoc=DataSheet&ID=0\"}");
     }
+
+    #[test]
+    fn test_parse_gs1_element_string() {
+        // Scanned GS1 DataMatrix with symbology identifier, GTIN and expiry have predefined length,
+        // batch is terminated by a separator
+        let gs1 = GS1::from_element_string(
+            "]d201000123456000121726013110B123\x1d21S12345678".to_string(),
+        )
+        .unwrap();
+        assert_eq!(gs1.gtin(), Some("00012345600012".to_string()));
+        assert_eq!(gs1.get("17").unwrap(), "260131");
+        assert_eq!(gs1.get("10").unwrap(), "B123");
+        assert_eq!(gs1.serial_number(), Some("S12345678".to_string()));
+
+        // Leading FNC1 and a 3 digit AI
+        let gs1 = GS1::from_element_string("\x1d0100012345600012241E003/002\x1d21S1".to_string())
+            .unwrap();
+        assert_eq!(gs1.part_number(), Some("E003/002".to_string()));
+        assert_eq!(gs1.serial_number(), Some("S1".to_string()));
+
+        // Truncated GTIN
+        assert!(GS1::from_element_string("]d201000123".to_string()).is_err());
+        // Not an AI
+        assert!(GS1::from_element_string("\x1dAB123".to_string()).is_err());
+        assert!(GS1::from_element_string("]d2".to_string()).is_err());
+    }
 }